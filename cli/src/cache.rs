@@ -0,0 +1,224 @@
+//! Binary cache layout and pruning
+//!
+//! The binaries in use live at the top of the cache directory. When an
+//! update replaces them, the previous release is moved to
+//! `versions/<version>/` so it can be restored by hand, and the oldest of
+//! those are pruned to stay within `cache.max_versions`.
+
+use semver::Version;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::version::parse_version;
+
+/// Subdirectory holding binaries from earlier releases
+const VERSIONS_DIR: &str = "versions";
+
+/// Archive extensions left behind by interrupted downloads
+const ARCHIVE_EXTENSIONS: &[&str] = &[".tar.gz", ".zip"];
+
+/// A release kept under `versions/`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetiredVersion {
+    pub name: String,
+    pub path: PathBuf,
+    version: Option<Version>,
+}
+
+pub struct BinaryCache {
+    dir: PathBuf,
+}
+
+impl BinaryCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    fn versions_dir(&self) -> PathBuf {
+        self.dir.join(VERSIONS_DIR)
+    }
+
+    /// Move the current binaries of `version` to `versions/<version>/`
+    ///
+    /// Files that don't exist are skipped, so this is safe on a fresh cache.
+    pub fn retire(&self, version: &str, binaries: &[PathBuf]) -> io::Result<()> {
+        let target = self.versions_dir().join(version);
+        for binary in binaries.iter().filter(|path| path.is_file()) {
+            let Some(name) = binary.file_name() else {
+                continue;
+            };
+            fs::create_dir_all(&target)?;
+            fs::rename(binary, target.join(name))?;
+        }
+        Ok(())
+    }
+
+    /// Releases kept under `versions/`, newest first
+    ///
+    /// Directories whose name isn't a version sort last.
+    pub fn retired_versions(&self) -> io::Result<Vec<RetiredVersion>> {
+        let entries = match fs::read_dir(self.versions_dir()) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+
+        let mut versions: Vec<RetiredVersion> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+            .filter_map(|entry| {
+                let name = entry.file_name().into_string().ok()?;
+                Some(RetiredVersion {
+                    version: parse_version(&name).ok(),
+                    path: entry.path(),
+                    name,
+                })
+            })
+            .collect();
+        versions.sort_by(|a, b| b.version.cmp(&a.version).then(b.name.cmp(&a.name)));
+        Ok(versions)
+    }
+
+    /// Delete retired releases so at most `max_versions` remain, counting
+    /// the one in use
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes reclaimed.
+    pub fn prune(&self, max_versions: usize) -> io::Result<u64> {
+        let keep = max_versions.saturating_sub(1);
+        let mut reclaimed = 0;
+        for retired in self.retired_versions()?.into_iter().skip(keep) {
+            reclaimed += remove_dir(&retired.path)?;
+        }
+        Ok(reclaimed)
+    }
+
+    /// Delete every retired release and any archive left by an interrupted
+    /// download, keeping only the binaries in use
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes reclaimed.
+    pub fn clean(&self) -> io::Result<u64> {
+        let mut reclaimed = self.prune(1)?;
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(reclaimed),
+            Err(e) => return Err(e),
+        };
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let is_archive = entry.file_name().to_str().is_some_and(|name| {
+                ARCHIVE_EXTENSIONS
+                    .iter()
+                    .any(|extension| name.ends_with(extension))
+            });
+            if is_archive && entry.file_type().is_ok_and(|t| t.is_file()) {
+                reclaimed += entry.metadata().map(|m| m.len()).unwrap_or(0);
+                fs::remove_file(entry.path())?;
+            }
+        }
+        Ok(reclaimed)
+    }
+}
+
+/// Remove a directory tree, returning the size of the files it held
+fn remove_dir(path: &Path) -> io::Result<u64> {
+    let size = dir_size(path);
+    fs::remove_dir_all(path)?;
+    Ok(size)
+}
+
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| match entry.file_type() {
+            Ok(t) if t.is_dir() => dir_size(&entry.path()),
+            Ok(_) => entry.metadata().map(|m| m.len()).unwrap_or(0),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, len: usize) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, vec![0u8; len]).unwrap();
+    }
+
+    #[test]
+    fn test_retire_moves_binaries() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = BinaryCache::new(dir.path().to_path_buf());
+        let sb = dir.path().join("sb");
+        write(&sb, 10);
+
+        cache
+            .retire("0.3.0", &[sb.clone(), dir.path().join("missing")])
+            .unwrap();
+
+        assert!(!sb.exists());
+        assert!(dir.path().join("versions/0.3.0/sb").is_file());
+    }
+
+    #[test]
+    fn test_retired_versions_newest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = BinaryCache::new(dir.path().to_path_buf());
+        for version in ["0.9.0", "0.10.0", "nightly", "0.2.1"] {
+            write(&dir.path().join("versions").join(version).join("sb"), 1);
+        }
+
+        let names: Vec<_> = cache
+            .retired_versions()
+            .unwrap()
+            .into_iter()
+            .map(|v| v.name)
+            .collect();
+        assert_eq!(names, vec!["0.10.0", "0.9.0", "0.2.1", "nightly"]);
+    }
+
+    #[test]
+    fn test_prune_keeps_most_recent() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = BinaryCache::new(dir.path().to_path_buf());
+        for version in ["0.1.0", "0.2.0", "0.3.0"] {
+            write(&dir.path().join("versions").join(version).join("sb"), 100);
+        }
+
+        // Three versions in total: the one in use plus two retired
+        assert_eq!(cache.prune(3).unwrap(), 100);
+        assert!(!dir.path().join("versions/0.1.0").exists());
+        assert!(dir.path().join("versions/0.3.0").exists());
+        assert_eq!(cache.prune(3).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_clean_removes_retired_and_archives() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = BinaryCache::new(dir.path().to_path_buf());
+        write(&dir.path().join("sb"), 50);
+        write(&dir.path().join("installed.json"), 5);
+        write(&dir.path().join("saorsa-linux-x86_64.tar.gz"), 200);
+        write(&dir.path().join("versions/0.1.0/sb"), 40);
+
+        assert_eq!(cache.clean().unwrap(), 240);
+        assert!(dir.path().join("sb").exists());
+        assert!(dir.path().join("installed.json").exists());
+        assert!(!dir.path().join("versions/0.1.0").exists());
+    }
+
+    #[test]
+    fn test_clean_missing_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = BinaryCache::new(dir.path().join("absent"));
+        assert_eq!(cache.clean().unwrap(), 0);
+    }
+}
@@ -8,6 +8,8 @@ use std::path::PathBuf;
 const DEFAULT_GITHUB_OWNER: &str = "saorsa-labs";
 const DEFAULT_GITHUB_REPO: &str = "saorsa-cli";
 const LEGACY_GITHUB_OWNER: &str = "dirvine";
+/// Environment variable overriding the binary cache directory
+const CACHE_DIR_ENV: &str = "SAORSA_CACHE_DIR";
 
 /// Main configuration structure for the Saorsa CLI
 ///
@@ -24,6 +26,9 @@ pub struct Config {
     /// Version state for auto-update tracking
     #[serde(default)]
     pub version_state: VersionState,
+    /// Signature verification for downloaded binaries
+    #[serde(default)]
+    pub signing: SigningConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -37,6 +42,7 @@ pub struct GitHubConfig {
 pub struct CacheConfig {
     pub directory: Option<PathBuf>,
     pub auto_clean: bool,
+    /// Releases kept in the binary cache, counting the one in use
     pub max_versions: usize,
 }
 
@@ -47,6 +53,17 @@ pub struct BehaviorConfig {
     pub prefer_local_build: bool,
 }
 
+/// Signature verification for downloaded releases.
+///
+/// When a public key is set, every release archive must come with a
+/// minisign signature (`<archive>.minisig`) made by that key.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SigningConfig {
+    /// Minisign public key in base64, the `RW...` line of `minisign.pub`
+    #[serde(default)]
+    pub public_key: Option<String>,
+}
+
 /// Version state tracking for the auto-update system.
 ///
 /// Stores information about installed binary versions and update checks.
@@ -82,6 +99,7 @@ impl Default for Config {
                 prefer_local_build: false,
             },
             version_state: VersionState::default(),
+            signing: SigningConfig::default(),
         }
     }
 }
@@ -127,8 +145,13 @@ impl Config {
         Ok(config_dir.join("saorsa-cli").join("config.toml"))
     }
 
+    /// Directory for downloaded binaries
+    ///
+    /// `SAORSA_CACHE_DIR` takes precedence over `cache.directory`.
     pub fn cache_dir(&self) -> Result<PathBuf> {
-        if let Some(ref dir) = self.cache.directory {
+        if let Some(dir) = std::env::var_os(CACHE_DIR_ENV).filter(|dir| !dir.is_empty()) {
+            Ok(PathBuf::from(dir))
+        } else if let Some(ref dir) = self.cache.directory {
             Ok(dir.clone())
         } else {
             let cache_dir = dirs::cache_dir()
@@ -1,15 +1,21 @@
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use indicatif::{ProgressBar, ProgressStyle};
+use minisign_verify::{PublicKey, Signature};
 use reqwest::blocking::Client;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs::{self, File};
 use std::io::{self, Read, Write};
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
+use crate::cache::BinaryCache;
 use crate::platform::Platform;
+use crate::version::parse_version;
+use saorsa_cli_core::format_bytes;
+use semver::Version;
 
 #[derive(Debug, Error)]
 pub enum DownloadError {
@@ -25,6 +31,10 @@ pub enum DownloadError {
     ChecksumMismatch { expected: String, actual: String },
     #[error("Checksum not found for asset: {0}")]
     ChecksumNotFound(String),
+    #[error("Signature verification failed: {0}")]
+    SignatureInvalid(String),
+    #[error("Signature not found for asset: {0}")]
+    SignatureNotFound(String),
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -44,22 +54,80 @@ pub struct GitHubAsset {
     pub size: u64,
 }
 
+/// File in the cache directory recording which release each binary came from
+const INSTALLED_MANIFEST: &str = "installed.json";
+
+/// Directory in the cache a bundle is extracted into before it replaces
+/// the installed binaries
+const STAGING_DIR: &str = ".staging";
+
+/// A cached binary as recorded in `installed.json`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct InstalledBinary {
+    /// Release version the binary was extracted from, without the `v`
+    pub version: String,
+    /// SHA256 of the extracted binary, to notice when it is replaced
+    pub sha256: String,
+    pub downloaded_at: DateTime<Utc>,
+}
+
+/// Contents of `installed.json`, keyed by binary name
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct InstalledManifest {
+    binaries: BTreeMap<String, InstalledBinary>,
+}
+
+impl InstalledManifest {
+    /// Reads the manifest at `path`
+    ///
+    /// A missing or unreadable manifest is treated as empty, so the worst
+    /// case is one unnecessary download.
+    pub fn load(path: &Path) -> Self {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Self::default(),
+            Err(e) => {
+                tracing::warn!("Could not read {}: {}", path.display(), e);
+                return Self::default();
+            }
+        };
+        serde_json::from_str(&content).unwrap_or_else(|e| {
+            tracing::warn!("Ignoring malformed {}: {}", path.display(), e);
+            Self::default()
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    pub fn get(&self, binary_name: &str) -> Option<&InstalledBinary> {
+        self.binaries.get(binary_name)
+    }
+
+    pub fn record(&mut self, binary_name: &str, binary: InstalledBinary) {
+        self.binaries.insert(binary_name.to_string(), binary);
+    }
+}
+
 pub struct Downloader {
     client: Client,
     repo_owner: String,
     repo_name: String,
     cache_dir: PathBuf,
+    cache: BinaryCache,
+    /// Releases to keep in the cache, counting the one in use
+    max_versions: usize,
+    /// Key release archives must be signed with; `None` skips verification
+    public_key: Option<PublicKey>,
 }
 
-const BUNDLED_BINARIES: &[&str] = &["saorsa", "saorsa-cli", "sb", "sdisk"];
+pub const BUNDLED_BINARIES: &[&str] = &["saorsa", "saorsa-cli", "sb", "sdisk"];
 
 impl Downloader {
-    pub fn new(repo_owner: String, repo_name: String) -> Result<Self> {
-        let cache_dir = dirs::cache_dir()
-            .context("Failed to find cache directory")?
-            .join("saorsa-cli")
-            .join("binaries");
-
+    pub fn new(repo_owner: String, repo_name: String, cache_dir: PathBuf) -> Result<Self> {
         fs::create_dir_all(&cache_dir).context("Failed to create cache directory")?;
 
         let client = Client::builder()
@@ -71,10 +139,31 @@ impl Downloader {
             client,
             repo_owner,
             repo_name,
+            cache: BinaryCache::new(cache_dir.clone()),
             cache_dir,
+            max_versions: 3,
+            public_key: None,
         })
     }
 
+    /// Keep at most `max_versions` releases in the cache, counting the one in use
+    pub fn with_max_versions(mut self, max_versions: usize) -> Self {
+        self.max_versions = max_versions;
+        self
+    }
+
+    /// Require release archives to carry a minisign signature made by `public_key`
+    ///
+    /// `public_key` is the base64 key from `minisign.pub`; `None` turns
+    /// verification off.
+    pub fn with_public_key(mut self, public_key: Option<&str>) -> Result<Self> {
+        self.public_key = public_key
+            .map(|key| PublicKey::from_base64(key.trim()))
+            .transpose()
+            .map_err(|e| anyhow::anyhow!("Invalid minisign public key in config: {}", e))?;
+        Ok(self)
+    }
+
     pub fn get_latest_release(&self) -> Result<GitHubRelease, DownloadError> {
         let url = format!(
             "https://api.github.com/repos/{}/{}/releases/latest",
@@ -98,11 +187,60 @@ impl Downloader {
         }
     }
 
+    /// Latest released version of `binary_name` for `platform`, without downloading
+    ///
+    /// Every binary ships in one bundle per release, so this is the release
+    /// tag, provided the release has an archive for the platform.
+    pub fn latest_version(&self, binary_name: &str, platform: &Platform) -> Result<Version> {
+        let release = self
+            .get_latest_release()
+            .context("Failed to get latest release")?;
+        if !release
+            .assets
+            .iter()
+            .any(|a| a.name == platform.archive_name())
+        {
+            return Err(DownloadError::NoMatchingAsset.into());
+        }
+        parse_version(&release.tag_name).with_context(|| {
+            format!(
+                "Release tag {} is not a version of {binary_name}",
+                release.tag_name
+            )
+        })
+    }
+
+    fn manifest_path(&self) -> PathBuf {
+        self.cache_dir.join(INSTALLED_MANIFEST)
+    }
+
+    /// Versions of the cached binaries, as recorded when they were extracted
+    pub fn installed(&self) -> InstalledManifest {
+        InstalledManifest::load(&self.manifest_path())
+    }
+
+    /// Whether the cached `binary_name` is the one extracted from `version`
+    ///
+    /// The binary is hashed and compared with the manifest, so a cached file
+    /// replaced by hand counts as stale.
+    pub fn is_current(&self, binary_name: &str, platform: &Platform, version: &str) -> bool {
+        let Some(recorded) = self.installed().get(binary_name).cloned() else {
+            return false;
+        };
+        recorded.version == version
+            && sha256_file(&self.binary_path(binary_name, platform))
+                .is_ok_and(|actual| actual == recorded.sha256)
+    }
+
     pub fn binary_path(&self, binary_name: &str, platform: &Platform) -> PathBuf {
         self.cache_dir
             .join(format!("{}{}", binary_name, platform.binary_extension()))
     }
 
+    /// Download the latest release of `binary_name` unless it is cached
+    ///
+    /// `force` downloads it again even when the cached binary already
+    /// matches the latest release.
     pub fn download_binary(
         &self,
         binary_name: &str,
@@ -115,10 +253,31 @@ impl Downloader {
             tracing::info!("Binary already exists at {:?}", binary_path);
             return Ok(binary_path);
         }
+        self.install_latest(binary_name, platform, false)
+    }
 
+    /// Download the latest release of `binary_name` unless the cached binary
+    /// already is that release
+    pub fn update_binary(&self, binary_name: &str, platform: &Platform) -> Result<PathBuf> {
+        self.install_latest(binary_name, platform, true)
+    }
+
+    fn install_latest(
+        &self,
+        binary_name: &str,
+        platform: &Platform,
+        skip_current: bool,
+    ) -> Result<PathBuf> {
+        let binary_path = self.binary_path(binary_name, platform);
         let release = self
             .get_latest_release()
             .context("Failed to get latest release")?;
+        let version = release_version(&release.tag_name);
+
+        if skip_current && self.is_current(binary_name, platform, &version) {
+            tracing::info!("{} is already at {}", binary_name, version);
+            return Ok(binary_path);
+        }
 
         let archive_name = platform.archive_name();
         let asset = release
@@ -156,8 +315,26 @@ impl Downloader {
             }
         }
 
-        self.extract_bundle(&archive_path, platform)
-            .context("Failed to extract Saorsa bundle")?;
+        if let Err(e) = self.verify_signature(&release, asset, &archive_path) {
+            fs::remove_file(&archive_path).ok();
+            return Err(e).context("Signature verification failed");
+        }
+
+        let extracted = match self.install_bundle(&archive_path, platform, &version) {
+            Ok(extracted) => extracted,
+            Err(e) => {
+                fs::remove_file(&archive_path).ok();
+                return Err(e);
+            }
+        };
+        if let Err(e) = self.record_installed(&extracted, platform, &version) {
+            tracing::warn!("Could not record installed versions: {:#}", e);
+        }
+        match self.cache.prune(self.max_versions) {
+            Ok(0) => {}
+            Ok(reclaimed) => tracing::info!("Pruned {} of old releases", format_bytes(reclaimed)),
+            Err(e) => tracing::warn!("Could not prune old releases: {}", e),
+        }
 
         // Clean up archive
         fs::remove_file(&archive_path).ok();
@@ -211,7 +388,100 @@ impl Downloader {
         Ok(archive_path)
     }
 
-    fn extract_bundle(&self, archive_path: &Path, platform: &Platform) -> Result<()> {
+    /// Delete old releases and leftover archives from the cache
+    ///
+    /// Returns the number of bytes reclaimed.
+    pub fn clean_cache(&self) -> io::Result<u64> {
+        self.cache.clean()
+    }
+
+    /// Move cached binaries from a release other than `version` to
+    /// `versions/` before the new bundle overwrites them
+    fn retire_previous(&self, platform: &Platform, version: &str) {
+        let installed = self.installed();
+        let mut previous: BTreeMap<&str, Vec<PathBuf>> = BTreeMap::new();
+        for binary in BUNDLED_BINARIES {
+            if let Some(record) = installed.get(binary) {
+                if record.version != version {
+                    previous
+                        .entry(record.version.as_str())
+                        .or_default()
+                        .push(self.binary_path(binary, platform));
+                }
+            }
+        }
+        for (old_version, binaries) in previous {
+            if let Err(e) = self.cache.retire(old_version, &binaries) {
+                tracing::warn!("Could not keep binaries from {}: {}", old_version, e);
+            }
+        }
+    }
+
+    /// Records `binaries` as extracted from `version` in `installed.json`
+    fn record_installed(
+        &self,
+        binaries: &[&'static str],
+        platform: &Platform,
+        version: &str,
+    ) -> Result<()> {
+        let path = self.manifest_path();
+        let mut manifest = InstalledManifest::load(&path);
+        let downloaded_at = Utc::now();
+        for binary in binaries {
+            let sha256 = sha256_file(&self.binary_path(binary, platform))?;
+            manifest.record(
+                binary,
+                InstalledBinary {
+                    version: version.to_string(),
+                    sha256,
+                    downloaded_at,
+                },
+            );
+        }
+        manifest.save(&path)
+    }
+
+    /// Extracts the bundle from `version` and puts its binaries in place of
+    /// the installed ones, returning their names
+    ///
+    /// The archive is unpacked into a staging directory first, so a corrupt
+    /// archive leaves the installed binaries untouched.
+    fn install_bundle(
+        &self,
+        archive_path: &Path,
+        platform: &Platform,
+        version: &str,
+    ) -> Result<Vec<&'static str>> {
+        let staging = self.cache_dir.join(STAGING_DIR);
+        if staging.exists() {
+            fs::remove_dir_all(&staging).context("Failed to clear staging directory")?;
+        }
+        fs::create_dir_all(&staging).context("Failed to create staging directory")?;
+
+        let result = self
+            .extract_bundle(archive_path, platform, &staging)
+            .context("Failed to extract Saorsa bundle")
+            .and_then(|extracted| {
+                self.retire_previous(platform, version);
+                for binary in &extracted {
+                    let file_name = format!("{}{}", binary, platform.binary_extension());
+                    fs::rename(staging.join(&file_name), self.binary_path(binary, platform))
+                        .with_context(|| format!("Failed to install {}", binary))?;
+                }
+                Ok(extracted)
+            });
+        fs::remove_dir_all(&staging).ok();
+        result
+    }
+
+    /// Extracts the bundled binaries into `dest`, returning their names
+    fn extract_bundle(
+        &self,
+        archive_path: &Path,
+        platform: &Platform,
+        dest: &Path,
+    ) -> Result<Vec<&'static str>> {
+        let mut extracted = Vec::new();
         match platform.archive_extension() {
             ".tar.gz" => {
                 use flate2::read::GzDecoder;
@@ -226,15 +496,17 @@ impl Downloader {
                     let path = entry.path()?;
 
                     if let Some(target) = self.bundle_target_from_path(&path, platform) {
-                        let target_path = self.binary_path(target, platform);
+                        let target_path =
+                            dest.join(format!("{}{}", target, platform.binary_extension()));
                         let mut output =
                             File::create(&target_path).context("Failed to create binary file")?;
                         io::copy(&mut entry, &mut output).context("Failed to extract binary")?;
                         Self::ensure_executable(&target_path)?;
+                        extracted.push(target);
                     }
                 }
 
-                Ok(())
+                Ok(extracted)
             }
             ".zip" => {
                 use zip::ZipArchive;
@@ -245,15 +517,17 @@ impl Downloader {
                 for i in 0..archive.len() {
                     let mut file = archive.by_index(i)?;
                     if let Some(target) = self.bundle_target_from_str(file.name(), platform) {
-                        let target_path = self.binary_path(target, platform);
+                        let target_path =
+                            dest.join(format!("{}{}", target, platform.binary_extension()));
                         let mut output =
                             File::create(&target_path).context("Failed to create binary file")?;
                         io::copy(&mut file, &mut output).context("Failed to extract binary")?;
                         Self::ensure_executable(&target_path)?;
+                        extracted.push(target);
                     }
                 }
 
-                Ok(())
+                Ok(extracted)
             }
             _ => anyhow::bail!("Unsupported archive format"),
         }
@@ -315,21 +589,42 @@ impl Downloader {
         Ok(parse_checksums(&content))
     }
 
-    /// Verify a file's SHA256 checksum.
-    fn verify_checksum(path: &Path, expected: &str) -> Result<(), DownloadError> {
-        let mut file = File::open(path)?;
-        let mut hasher = Sha256::new();
-        let mut buffer = [0u8; 8192];
+    /// Verify a downloaded archive against its `.minisig` release asset.
+    ///
+    /// Does nothing when no public key is configured.
+    fn verify_signature(
+        &self,
+        release: &GitHubRelease,
+        asset: &GitHubAsset,
+        archive_path: &Path,
+    ) -> Result<(), DownloadError> {
+        let Some(public_key) = &self.public_key else {
+            return Ok(());
+        };
 
-        loop {
-            let bytes_read = file.read(&mut buffer)?;
-            if bytes_read == 0 {
-                break;
-            }
-            hasher.update(&buffer[..bytes_read]);
-        }
+        let signature_name = format!("{}.minisig", asset.name);
+        let signature_asset = release
+            .assets
+            .iter()
+            .find(|a| a.name == signature_name)
+            .ok_or(DownloadError::SignatureNotFound(signature_name))?;
 
-        let actual = hex::encode(hasher.finalize());
+        let signature = self
+            .client
+            .get(&signature_asset.browser_download_url)
+            .send()?
+            .error_for_status()?
+            .text()?;
+        let data = fs::read(archive_path)?;
+
+        verify_minisign(public_key, &data, &signature)?;
+        tracing::info!("Signature verified for {}", asset.name);
+        Ok(())
+    }
+
+    /// Verify a file's SHA256 checksum.
+    fn verify_checksum(path: &Path, expected: &str) -> Result<(), DownloadError> {
+        let actual = sha256_file(path)?;
 
         if actual != expected {
             return Err(DownloadError::ChecksumMismatch {
@@ -341,6 +636,47 @@ impl Downloader {
     }
 }
 
+/// Hex-encoded SHA256 of a file's contents.
+fn sha256_file(path: &Path) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 8192];
+
+    loop {
+        let bytes_read = file.read(&mut buffer)?;
+        if bytes_read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..bytes_read]);
+    }
+
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Check a minisign signature (the contents of a `.minisig` file) over `data`.
+///
+/// Only prehashed signatures are accepted, as made by minisign 0.8 and later.
+fn verify_minisign(
+    public_key: &PublicKey,
+    data: &[u8],
+    signature: &str,
+) -> Result<(), DownloadError> {
+    let signature =
+        Signature::decode(signature).map_err(|e| DownloadError::SignatureInvalid(e.to_string()))?;
+    public_key
+        .verify(data, &signature, false)
+        .map_err(|e| DownloadError::SignatureInvalid(e.to_string()))
+}
+
+/// Version recorded for a release tag: the semver without its `v`, or the
+/// bare tag if it isn't one.
+fn release_version(tag: &str) -> String {
+    parse_version(tag).map_or_else(
+        |_| tag.trim_start_matches('v').to_string(),
+        |v| v.to_string(),
+    )
+}
+
 /// Parse sha256sum format: "hash  filename" (two spaces between hash and filename).
 fn parse_checksums(content: &str) -> HashMap<String, String> {
     content
@@ -469,4 +805,108 @@ mod tests {
 
         assert!(Downloader::verify_checksum(file.path(), expected).is_ok());
     }
+
+    // Key pair and signature over "test content", made with minisign's format
+    const TEST_PUBLIC_KEY: &str = "RWQBAgMEBQYHCAOhB7/zzhC+HXDdGOdLwJln5NYwm6UNXx3chmQSVTG4";
+    const TEST_SIGNATURE: &str = "untrusted comment: signature from minisign secret key
+RUQBAgMEBQYHCIqG9ViVI1Ji2dKgxqW1EZRz/FUBfawDk4tcV/5vnK3x+4Fk4udGM5O88PG1Qh/AIzlLiOC0jT1kYdc3zl22fAI=
+trusted comment: timestamp:1700000000\tfile:test.tar.gz
+xQLlnXG+p3131I2R/p5oCfYIrGj4p1eCHgk1zODgegYV5D955W9Dm53pbJf8SKQAG0UdsrUuCX2qM4T1jLVyDw==
+";
+
+    #[test]
+    fn test_verify_minisign_success() {
+        let key = PublicKey::from_base64(TEST_PUBLIC_KEY).unwrap();
+        assert!(verify_minisign(&key, b"test content", TEST_SIGNATURE).is_ok());
+    }
+
+    #[test]
+    fn test_verify_minisign_tampered_data() {
+        let key = PublicKey::from_base64(TEST_PUBLIC_KEY).unwrap();
+        let result = verify_minisign(&key, b"tampered content", TEST_SIGNATURE);
+        assert!(matches!(result, Err(DownloadError::SignatureInvalid(_))));
+    }
+
+    #[test]
+    fn test_verify_minisign_malformed_signature() {
+        let key = PublicKey::from_base64(TEST_PUBLIC_KEY).unwrap();
+        let result = verify_minisign(&key, b"test content", "not a signature");
+        assert!(matches!(result, Err(DownloadError::SignatureInvalid(_))));
+    }
+
+    #[test]
+    fn test_installed_manifest_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(INSTALLED_MANIFEST);
+        let binary = InstalledBinary {
+            version: "0.4.0".to_string(),
+            sha256: "abc123".to_string(),
+            downloaded_at: Utc::now(),
+        };
+
+        let mut manifest = InstalledManifest::default();
+        manifest.record("sb", binary.clone());
+        manifest.save(&path).unwrap();
+
+        let loaded = InstalledManifest::load(&path);
+        assert_eq!(loaded.get("sb"), Some(&binary));
+        assert_eq!(loaded.get("sdisk"), None);
+
+        // Keyed by binary name at the top level
+        let raw: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(raw["sb"]["version"], "0.4.0");
+    }
+
+    #[test]
+    fn test_installed_manifest_missing_or_malformed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(INSTALLED_MANIFEST);
+        assert_eq!(InstalledManifest::load(&path), InstalledManifest::default());
+
+        fs::write(&path, "not json").unwrap();
+        assert_eq!(InstalledManifest::load(&path), InstalledManifest::default());
+    }
+
+    #[test]
+    fn test_bad_archive_keeps_installed_binary() {
+        let dir = tempfile::tempdir().unwrap();
+        let downloader = Downloader::new(
+            "saorsa-labs".to_string(),
+            "saorsa-cli".to_string(),
+            dir.path().to_path_buf(),
+        )
+        .unwrap();
+        let platform = Platform::detect().unwrap();
+        let binary = downloader.binary_path("sb", &platform);
+        fs::write(&binary, "old sb").unwrap();
+        let mut manifest = InstalledManifest::default();
+        manifest.record(
+            "sb",
+            InstalledBinary {
+                version: "0.3.0".to_string(),
+                sha256: sha256_file(&binary).unwrap(),
+                downloaded_at: Utc::now(),
+            },
+        );
+        manifest.save(&downloader.manifest_path()).unwrap();
+
+        let archive = dir.path().join(platform.archive_name());
+        fs::write(&archive, "not an archive").unwrap();
+        assert!(downloader
+            .install_bundle(&archive, &platform, "0.4.0")
+            .is_err());
+
+        assert_eq!(fs::read_to_string(&binary).unwrap(), "old sb");
+        assert!(downloader.is_current("sb", &platform, "0.3.0"));
+        assert!(!dir.path().join("versions").exists());
+        assert!(!dir.path().join(STAGING_DIR).exists());
+    }
+
+    #[test]
+    fn test_release_version() {
+        assert_eq!(release_version("v0.4.0"), "0.4.0");
+        assert_eq!(release_version("0.4.0"), "0.4.0");
+        assert_eq!(release_version("nightly"), "nightly");
+    }
 }
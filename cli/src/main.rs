@@ -1,3 +1,4 @@
+mod cache;
 mod config;
 mod downloader;
 mod error;
@@ -9,20 +10,25 @@ mod updater;
 mod version;
 
 use crate::config::Config;
-use crate::downloader::{DownloadError, Downloader};
+use crate::downloader::{DownloadError, Downloader, InstalledManifest, BUNDLED_BINARIES};
 use crate::menu::{Menu, MenuChoice};
 use crate::platform::Platform;
 use crate::runner::BinaryRunner;
 use crate::updater::{UpdateCheckResult, UpdateChecker};
+use crate::version::parse_version;
 use anyhow::{Context, Result};
 use chrono::Local;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
 use parking_lot::RwLock;
 use saorsa_cli_core::{
-    PluginContext, PluginDescriptor, PluginHistory, PluginManager, PluginRunStats,
+    format_bytes, PluginContext, PluginDescriptor, PluginHistory, PluginManager, PluginMetadata,
+    PluginRunStats,
 };
-use std::path::PathBuf;
+use semver::Version;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::thread;
 use std::time::Instant;
@@ -37,19 +43,24 @@ use tracing_subscriber::EnvFilter;
 )]
 struct Args {
     /// Disable automatic update checks
-    #[arg(long)]
+    #[arg(long, global = true)]
     no_update_check: bool,
 
     /// Use system-installed binaries instead of downloading
-    #[arg(long)]
+    #[arg(long, global = true)]
     use_system: bool,
 
     /// Force re-download of binaries
-    #[arg(long)]
+    #[arg(long, global = true)]
     force_download: bool,
 
+    /// Compare installed binaries with the latest release without
+    /// downloading; exits with an error if any are outdated
+    #[arg(long)]
+    check_only: bool,
+
     /// Enable verbose logging
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     verbose: bool,
 
     /// Run a specific tool directly (sb or sdisk)
@@ -63,6 +74,52 @@ struct Args {
     /// Execute a plugin
     #[arg(long)]
     plugin: Option<String>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// Non-interactive commands for scripts and CI; none of them show the menu
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run saorsa, sb or sdisk, downloading it first if needed
+    Run {
+        tool: String,
+        /// Arguments for the tool; put them after `--` if they clash with
+        /// saorsa-cli flags
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Download the latest release of every binary
+    Update,
+    /// Work with plugins
+    Plugin {
+        #[command(subcommand)]
+        command: PluginCommand,
+    },
+    /// List binaries and plugins
+    List {
+        /// Print JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum PluginCommand {
+    /// Execute a plugin
+    Run {
+        name: String,
+        /// Arguments for the plugin
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// List plugins with their run statistics
+    List {
+        /// Print JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 fn main() -> Result<()> {
@@ -102,10 +159,15 @@ fn main() -> Result<()> {
     tracing::debug!("Detected platform: {:?}", platform);
 
     // Initialize components with Arc wrappers for thread-safe sharing
-    let downloader = Arc::new(Downloader::new(
-        config.github.owner.clone(),
-        config.github.repo.clone(),
-    )?);
+    let downloader = Arc::new(
+        Downloader::new(
+            config.github.owner.clone(),
+            config.github.repo.clone(),
+            config.cache_dir()?,
+        )?
+        .with_max_versions(config.cache.max_versions)
+        .with_public_key(config.signing.public_key.as_deref())?,
+    );
 
     // Wrap config in Arc<RwLock<>> for thread-safe access from background task
     let config = Arc::new(RwLock::new(config));
@@ -124,10 +186,54 @@ fn main() -> Result<()> {
 
     let runner = BinaryRunner::new();
 
+    if args.check_only {
+        let config_read = config.read();
+        return check_for_updates(&config_read, &platform, &downloader, &runner);
+    }
+
     // Initialize plugin system
     let mut plugin_manager = PluginManager::default();
     plugin_manager.load().context("Failed to load plugins")?;
 
+    if let Some(command) = args.command {
+        let config_read = config.read();
+        return match command {
+            Command::Run {
+                tool,
+                args: tool_args,
+            } => run_tool_directly(
+                &tool,
+                tool_args,
+                &config_read,
+                &platform,
+                &downloader,
+                &runner,
+                args.force_download,
+            ),
+            Command::Update => update_binaries(&platform, &downloader),
+            Command::Plugin {
+                command:
+                    PluginCommand::Run {
+                        name,
+                        args: plugin_args,
+                    },
+            } => plugin_manager
+                .execute_plugin(&name, &plugin_args, PluginContext::default())
+                .context("Failed to execute plugin"),
+            Command::Plugin {
+                command: PluginCommand::List { json },
+            } => list_plugins(&plugin_manager, json),
+            Command::List { json } => list_installed(
+                &config_read,
+                &platform,
+                &downloader,
+                &runner,
+                &plugin_manager,
+                json,
+            ),
+        };
+    }
+
     // Handle plugin execution
     if let Some(plugin_name) = args.plugin.as_ref() {
         return plugin_manager
@@ -166,6 +272,15 @@ fn main() -> Result<()> {
             check_binaries(&config_read, &platform, &downloader, &runner)?;
         drop(config_read); // Release lock before menu interaction
         menu.set_binary_paths(saorsa_path.clone(), sb_path.clone(), sdisk_path.clone());
+        menu.set_binary_versions(cached_versions(
+            &platform,
+            &downloader,
+            [
+                ("saorsa", &saorsa_path),
+                ("sb", &sb_path),
+                ("sdisk", &sdisk_path),
+            ],
+        ));
 
         // Show menu and get choice
         let choice = menu.run()?;
@@ -281,6 +396,16 @@ fn main() -> Result<()> {
                 let mut input = String::new();
                 std::io::stdin().read_line(&mut input)?;
             }
+            MenuChoice::CleanCache => {
+                match downloader.clean_cache() {
+                    Ok(0) => println!("Cache is already clean."),
+                    Ok(reclaimed) => println!("✓ Reclaimed {}", format_bytes(reclaimed)),
+                    Err(e) => println!("❌ Failed to clean cache: {}", e),
+                }
+                println!("Press Enter to continue...");
+                let mut input = String::new();
+                std::io::stdin().read_line(&mut input)?;
+            }
             MenuChoice::UpdateCLI => {
                 use dialoguer::{theme::ColorfulTheme, Confirm};
 
@@ -389,16 +514,41 @@ fn check_binaries(
     Ok((saorsa_path, sb_path, sdisk_path))
 }
 
+/// Versions recorded in the download manifest for binaries running from the cache
+///
+/// Binaries found on the system `PATH` aren't in the manifest and are left out.
+fn cached_versions<const N: usize>(
+    platform: &Platform,
+    downloader: &Downloader,
+    binaries: [(&str, &Option<PathBuf>); N],
+) -> HashMap<String, String> {
+    let installed = downloader.installed();
+    binaries
+        .into_iter()
+        .filter(|(binary, path)| path.as_ref() == Some(&downloader.binary_path(binary, platform)))
+        .filter_map(|(binary, _)| {
+            installed
+                .get(binary)
+                .map(|record| (binary.to_string(), record.version.clone()))
+        })
+        .collect()
+}
+
 fn update_binaries(platform: &Platform, downloader: &Downloader) -> Result<()> {
     fn fetch(
         downloader: &Downloader,
         platform: &Platform,
         binary: &str,
         label: &str,
-        force: bool,
+        update: bool,
     ) -> Result<()> {
         println!("Checking for latest {label}...");
-        match downloader.download_binary(binary, platform, force) {
+        let fetched = if update {
+            downloader.update_binary(binary, platform)
+        } else {
+            downloader.download_binary(binary, platform, false)
+        };
+        match fetched {
             Ok(_) => println!("✓ {label} is up to date"),
             Err(e) => {
                 if let Some(download_err) = e.downcast_ref::<DownloadError>() {
@@ -422,7 +572,7 @@ fn update_binaries(platform: &Platform, downloader: &Downloader) -> Result<()> {
         Ok(())
     }
 
-    // Downloading the Saorsa bundle once (force) refreshes all binaries.
+    // Updating the Saorsa bundle once refreshes all binaries.
     fetch(downloader, platform, "saorsa", "Saorsa TUI", true)?;
     fetch(downloader, platform, "saorsa-cli", "Saorsa CLI", false)?;
     fetch(downloader, platform, "sb", "Saorsa Browser (sb)", false)?;
@@ -431,6 +581,216 @@ fn update_binaries(platform: &Platform, downloader: &Downloader) -> Result<()> {
     Ok(())
 }
 
+/// Whether an installed binary matches the latest release
+#[derive(Debug, PartialEq, Eq)]
+enum BinaryStatus {
+    UpToDate,
+    Outdated,
+    NotInstalled,
+    Unknown,
+}
+
+impl BinaryStatus {
+    fn compare(installed: Option<&PathBuf>, version: Option<&Version>, latest: &Version) -> Self {
+        match (installed, version) {
+            (None, _) => BinaryStatus::NotInstalled,
+            (Some(_), None) => BinaryStatus::Unknown,
+            (Some(_), Some(version)) if version < latest => BinaryStatus::Outdated,
+            (Some(_), Some(_)) => BinaryStatus::UpToDate,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            BinaryStatus::UpToDate => "✓ up to date",
+            BinaryStatus::Outdated => "⚠ outdated",
+            BinaryStatus::NotInstalled => "- not installed",
+            BinaryStatus::Unknown => "? unknown version",
+        }
+    }
+}
+
+/// Where a binary would be launched from: the system `PATH` first if
+/// enabled, then the cache
+fn find_binary(
+    binary: &str,
+    config: &Config,
+    platform: &Platform,
+    downloader: &Downloader,
+    runner: &BinaryRunner,
+) -> Option<PathBuf> {
+    config
+        .behavior
+        .use_system_binaries
+        .then(|| runner.which(binary))
+        .flatten()
+        .or_else(|| {
+            let cache_path = downloader.binary_path(binary, platform);
+            runner
+                .check_binary_exists(&cache_path)
+                .then_some(cache_path)
+        })
+}
+
+/// Version of the binary at `path`, falling back to the download manifest
+/// for cached binaries that don't report one
+fn binary_version(
+    binary: &str,
+    path: Option<&Path>,
+    installed: &InstalledManifest,
+    platform: &Platform,
+    downloader: &Downloader,
+    runner: &BinaryRunner,
+) -> Option<Version> {
+    let path = path?;
+    runner.installed_version(path).or_else(|| {
+        (path == downloader.binary_path(binary, platform))
+            .then(|| installed.get(binary))
+            .flatten()
+            .and_then(|record| parse_version(&record.version).ok())
+    })
+}
+
+/// One line of `saorsa-cli list`
+#[derive(Serialize)]
+struct BinaryListing {
+    name: String,
+    installed: bool,
+    path: Option<PathBuf>,
+    version: Option<String>,
+}
+
+#[derive(Serialize)]
+struct PluginListing {
+    name: String,
+    version: String,
+    description: String,
+}
+
+#[derive(Serialize)]
+struct Listing {
+    binaries: Vec<BinaryListing>,
+    plugins: Vec<PluginListing>,
+}
+
+fn list_installed(
+    config: &Config,
+    platform: &Platform,
+    downloader: &Downloader,
+    runner: &BinaryRunner,
+    plugin_manager: &PluginManager,
+    json: bool,
+) -> Result<()> {
+    let installed = downloader.installed();
+    let binaries: Vec<BinaryListing> = BUNDLED_BINARIES
+        .iter()
+        .map(|binary| {
+            let path = find_binary(binary, config, platform, downloader, runner);
+            let version = binary_version(
+                binary,
+                path.as_deref(),
+                &installed,
+                platform,
+                downloader,
+                runner,
+            );
+            BinaryListing {
+                name: binary.to_string(),
+                installed: path.is_some(),
+                path,
+                version: version.map(|v| v.to_string()),
+            }
+        })
+        .collect();
+    let plugins: Vec<PluginListing> = plugin_manager
+        .descriptors()
+        .into_iter()
+        .map(|descriptor| PluginListing {
+            name: descriptor.metadata.name,
+            version: descriptor.metadata.version,
+            description: descriptor.metadata.description,
+        })
+        .collect();
+
+    if json {
+        let listing = Listing { binaries, plugins };
+        println!("{}", serde_json::to_string_pretty(&listing)?);
+        return Ok(());
+    }
+
+    println!("{:<12} {:<12} Path", "Binary", "Version");
+    for binary in &binaries {
+        let path = binary.path.as_ref().map_or_else(
+            || "(not installed)".to_string(),
+            |p| p.display().to_string(),
+        );
+        println!(
+            "{:<12} {:<12} {}",
+            binary.name,
+            binary.version.as_deref().unwrap_or("-"),
+            path
+        );
+    }
+    if !plugins.is_empty() {
+        println!();
+        println!("{:<12} {:<12} Description", "Plugin", "Version");
+        for plugin in &plugins {
+            println!(
+                "{:<12} {:<12} {}",
+                plugin.name, plugin.version, plugin.description
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Print installed and latest versions of every bundled binary, without downloading
+fn check_for_updates(
+    config: &Config,
+    platform: &Platform,
+    downloader: &Downloader,
+    runner: &BinaryRunner,
+) -> Result<()> {
+    let latest = downloader
+        .latest_version("saorsa", platform)
+        .context("Failed to look up the latest release")?;
+
+    println!(
+        "{:<12} {:<12} {:<12} Status",
+        "Binary", "Installed", "Latest"
+    );
+    let installed = downloader.installed();
+    let mut outdated = 0;
+    for binary in BUNDLED_BINARIES {
+        let path = find_binary(binary, config, platform, downloader, runner);
+        let version = binary_version(
+            binary,
+            path.as_deref(),
+            &installed,
+            platform,
+            downloader,
+            runner,
+        );
+        let status = BinaryStatus::compare(path.as_ref(), version.as_ref(), &latest);
+        if status == BinaryStatus::Outdated {
+            outdated += 1;
+        }
+        let installed = version.map_or_else(|| "-".to_string(), |v| v.to_string());
+        println!(
+            "{:<12} {:<12} {:<12} {}",
+            binary,
+            installed,
+            latest.to_string(),
+            status.label()
+        );
+    }
+
+    if outdated > 0 {
+        anyhow::bail!("{outdated} binaries are older than {latest}");
+    }
+    Ok(())
+}
+
 fn run_tool_directly(
     tool: &str,
     args: Vec<String>,
@@ -441,10 +801,11 @@ fn run_tool_directly(
     force_download: bool,
 ) -> Result<()> {
     let binary_name = match tool {
+        "saorsa" => "saorsa",
         "sb" | "saorsa-browser" => "sb",
         "sdisk" | "saorsa-disk" => "sdisk",
         _ => {
-            anyhow::bail!("Unknown tool: {}. Available tools: sb, sdisk", tool);
+            anyhow::bail!("Unknown tool: {}. Available tools: saorsa, sb, sdisk", tool);
         }
     };
 
@@ -716,26 +1077,74 @@ fn show_plugin_directories(plugin_manager: &PluginManager) -> Result<()> {
     Ok(())
 }
 
-fn show_plugins_menu(plugin_manager: &mut PluginManager) -> Result<()> {
-    use chrono::{DateTime, Utc};
-
-    fn stats_summary(stats: Option<&PluginRunStats>) -> String {
-        match stats {
-            Some(stats) => {
-                let when = stats
-                    .last_run
-                    .map(|ts: DateTime<Utc>| {
-                        ts.with_timezone(&Local)
-                            .format("%Y-%m-%d %H:%M")
-                            .to_string()
-                    })
-                    .unwrap_or_else(|| "never".to_string());
-                format!("✓{} ✗{} (last: {})", stats.successes, stats.failures, when)
-            }
-            None => "no runs yet".to_string(),
+fn stats_summary(stats: Option<&PluginRunStats>) -> String {
+    match stats {
+        Some(stats) => {
+            let when = stats
+                .last_run
+                .map(|ts| {
+                    ts.with_timezone(&Local)
+                        .format("%Y-%m-%d %H:%M")
+                        .to_string()
+                })
+                .unwrap_or_else(|| "never".to_string());
+            format!("✓{} ✗{} (last: {})", stats.successes, stats.failures, when)
+        }
+        None => "no runs yet".to_string(),
+    }
+}
+
+/// One entry of `saorsa-cli plugin list --json`
+///
+/// Only the manifest's public fields; its paths and environment, which may
+/// hold secrets, are left out.
+#[derive(Serialize)]
+struct PluginStatus<'a> {
+    name: &'a str,
+    version: &'a str,
+    description: &'a str,
+    author: &'a str,
+    stats: Option<&'a PluginRunStats>,
+}
+
+impl<'a> PluginStatus<'a> {
+    fn new(metadata: &'a PluginMetadata, history: &'a PluginHistory) -> Self {
+        Self {
+            name: &metadata.name,
+            version: &metadata.version,
+            description: &metadata.description,
+            author: &metadata.author,
+            stats: history.stats_for(&metadata.name),
         }
     }
+}
+
+fn list_plugins(plugin_manager: &PluginManager, json: bool) -> Result<()> {
+    let history = PluginHistory::load();
+    let descriptors = plugin_manager.descriptors();
+
+    if json {
+        let statuses: Vec<PluginStatus> = descriptors
+            .iter()
+            .map(|descriptor| PluginStatus::new(&descriptor.metadata, &history))
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&statuses)?);
+        return Ok(());
+    }
+    for descriptor in &descriptors {
+        let metadata = &descriptor.metadata;
+        println!(
+            "{:<16} {:<10} {} ({})",
+            metadata.name,
+            metadata.version,
+            metadata.description,
+            stats_summary(history.stats_for(&metadata.name))
+        );
+    }
+    Ok(())
+}
 
+fn show_plugins_menu(plugin_manager: &mut PluginManager) -> Result<()> {
     let warn_paths = plugin_manager.search_paths().to_owned();
     let mut history = PluginHistory::load();
 
@@ -809,7 +1218,13 @@ fn show_plugins_menu(plugin_manager: &mut PluginManager) -> Result<()> {
                 );
                 println!("⚠️  Press Ctrl+C to abort if this plugin looks suspicious.\n");
 
+                let default_args = &plugin.metadata.default_args;
                 let args = match plugin_name.as_str() {
+                    // The manifest already says how this plugin runs
+                    _ if !default_args.is_empty() => {
+                        println!("📎 Manifest arguments: {}", default_args.join(" "));
+                        vec![]
+                    }
                     "fd" => prompt_fd_args()?,
                     "rg" => prompt_rg_args()?,
                     _ => {
@@ -12,6 +12,7 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
     Frame, Terminal,
 };
+use std::collections::HashMap;
 use std::io;
 use std::path::PathBuf;
 
@@ -21,6 +22,7 @@ pub enum MenuChoice {
     RunSB,
     RunSDisk,
     UpdateBinaries,
+    CleanCache,
     UpdateCLI,
     Settings,
     Plugins,
@@ -33,6 +35,8 @@ pub struct Menu {
     saorsa_path: Option<PathBuf>,
     sb_path: Option<PathBuf>,
     sdisk_path: Option<PathBuf>,
+    /// Recorded versions of the cached binaries, keyed by binary name
+    versions: HashMap<String, String>,
     update_available: Option<String>,
 }
 
@@ -47,6 +51,7 @@ impl Menu {
             saorsa_path: None,
             sb_path: None,
             sdisk_path: None,
+            versions: HashMap::new(),
             update_available: None,
         };
         menu.rebuild_items();
@@ -72,6 +77,7 @@ impl Menu {
                 MenuChoice::RunSDisk,
             ),
             ("🔄 Update Binaries".to_string(), MenuChoice::UpdateBinaries),
+            ("🧹 Clean Cache".to_string(), MenuChoice::CleanCache),
         ];
 
         // Add update option if available
@@ -100,6 +106,19 @@ impl Menu {
         self.sdisk_path = sdisk_path;
     }
 
+    /// Set the versions shown next to installed binaries.
+    pub fn set_binary_versions(&mut self, versions: HashMap<String, String>) {
+        self.versions = versions;
+    }
+
+    /// Suffix naming the recorded version of `binary`, if known.
+    fn version_suffix(&self, binary: &str) -> String {
+        self.versions
+            .get(binary)
+            .map(|version| format!(" (v{})", version))
+            .unwrap_or_default()
+    }
+
     pub fn run(&mut self) -> Result<MenuChoice> {
         enable_raw_mode()?;
         let mut stdout = io::stdout();
@@ -212,6 +231,7 @@ impl Menu {
                             style = style.fg(Color::Yellow);
                         } else {
                             style = style.fg(Color::Green);
+                            suffix = self.version_suffix("saorsa");
                         }
                     }
                     MenuChoice::RunSB => {
@@ -220,6 +240,7 @@ impl Menu {
                             suffix = " (not installed)".to_string();
                         } else {
                             style = style.fg(Color::Green);
+                            suffix = self.version_suffix("sb");
                         }
                     }
                     MenuChoice::RunSDisk => {
@@ -228,6 +249,7 @@ impl Menu {
                             suffix = " (not installed)".to_string();
                         } else {
                             style = style.fg(Color::Green);
+                            suffix = self.version_suffix("sdisk");
                         }
                     }
                     MenuChoice::UpdateCLI => {
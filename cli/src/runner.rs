@@ -61,6 +61,15 @@ impl BinaryRunner {
         Ok(())
     }
 
+    /// Version reported by `<binary> --version`, if it runs and prints one
+    pub fn installed_version(&self, binary_path: &Path) -> Option<semver::Version> {
+        let output = Command::new(binary_path).arg("--version").output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        crate::version::parse_version_output(&String::from_utf8_lossy(&output.stdout))
+    }
+
     pub fn check_binary_exists(&self, binary_path: &Path) -> bool {
         binary_path.exists()
     }
@@ -44,7 +44,7 @@ pub fn perform_self_update(
     // Download new binary to cache
     println!("Downloading update...");
     let new_binary = downloader
-        .download_binary("saorsa-cli", platform, true)
+        .update_binary("saorsa-cli", platform)
         .map_err(SelfUpdateError::Download)?;
 
     // Create backup path
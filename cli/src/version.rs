@@ -80,6 +80,16 @@ pub fn compare_versions(a: &str, b: &str) -> Option<Ordering> {
     Some(va.cmp(&vb))
 }
 
+/// Find the version in the output of `<binary> --version`.
+///
+/// Takes the first whitespace-separated word that parses as a semantic
+/// version, so both `sb 0.3.12` and `saorsa-cli v0.3.12 (abc123)` work.
+pub fn parse_version_output(output: &str) -> Option<Version> {
+    output
+        .split_whitespace()
+        .find_map(|word| parse_version(word.trim_matches(|c: char| c == '(' || c == ')')).ok())
+}
+
 /// Check if version `a` is newer than version `b`.
 ///
 /// Returns `None` if either version string is invalid.
@@ -111,6 +121,19 @@ mod tests {
         assert!(parse_version("invalid").is_err());
     }
 
+    #[test]
+    fn test_parse_version_output() {
+        assert_eq!(
+            parse_version_output("sb 0.3.12\n"),
+            Some(Version::new(0, 3, 12))
+        );
+        assert_eq!(
+            parse_version_output("saorsa-cli v1.2.0 (abc123)"),
+            Some(Version::new(1, 2, 0))
+        );
+        assert_eq!(parse_version_output("sdisk: unknown build"), None);
+    }
+
     #[test]
     fn test_compare_versions() {
         assert_eq!(
@@ -3,15 +3,27 @@
 //! The `AppCoordinator` trait defines the interface for the main application
 //! loop that coordinates tabs, handles input, and manages the overall state.
 
+use crate::error::CoreResult;
 use crate::event::Message;
+use crate::record::{Replay, ReplayPace};
 use crate::tab::{Tab, TabId};
 use crate::theme::Theme;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
 
 /// Trait for the main application coordinator
 ///
 /// This trait defines the interface for managing the application lifecycle,
 /// including tab management, theme handling, and message dispatching.
 ///
+/// # Lifecycle
+///
+/// Main loops call [`on_start`](AppCoordinator::on_start) once the terminal
+/// is set up, then render, dispatch and tick until
+/// [`should_quit`](AppCoordinator::should_quit), and finally call
+/// [`on_exit`](AppCoordinator::on_exit) before restoring the terminal.
+///
 /// # Thread Safety
 ///
 /// Implementations should be careful about thread safety when dealing with
@@ -44,6 +56,18 @@ use crate::theme::Theme;
 /// }
 /// ```
 pub trait AppCoordinator {
+    /// Called once the terminal is set up, before the first frame is drawn
+    ///
+    /// The default implementation does nothing.
+    fn on_start(&mut self) {}
+
+    /// Called once the main loop ends, before the terminal is restored
+    ///
+    /// Use this to stop playback and background work and to save state.
+    /// It runs on a normal exit and when the loop fails. The default
+    /// implementation does nothing.
+    fn on_exit(&mut self) {}
+
     /// Returns a slice of all tabs
     ///
     /// The order of tabs in the slice corresponds to their display order
@@ -70,13 +94,59 @@ pub trait AppCoordinator {
 
     /// Called on each tick of the main loop
     ///
-    /// This method is called periodically (typically every 100-250ms)
+    /// This method is called whenever the event poll times out (see
+    /// [`desired_tick_interval`](AppCoordinator::desired_tick_interval))
     /// and can be used for animations, polling, or other periodic updates.
     fn tick(&mut self);
 
+    /// Returns how long the main loop may wait for input before ticking
+    ///
+    /// Main loops use this as their event poll timeout, so an animating
+    /// tab can ask for frequent ticks while an idle application sleeps.
+    ///
+    /// # Returns
+    ///
+    /// `None` by default, meaning nothing is animating and the loop may
+    /// fall back to a long idle timeout.
+    fn desired_tick_interval(&self) -> Option<Duration> {
+        None
+    }
+
     /// Returns true if the application should quit
     ///
     /// The main loop checks this after processing each batch of events.
     /// When this returns `true`, the application will begin shutdown.
     fn should_quit(&self) -> bool;
+
+    /// Dispatches the messages of a recorded session
+    ///
+    /// Reads a recording written by a [`Recorder`](crate::Recorder) and
+    /// dispatches its messages in order, sleeping between them to keep
+    /// their recorded timings unless `pace` is
+    /// [`ReplayPace::Immediate`]. Stops early once the application should
+    /// quit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the recording cannot be read or parsed; nothing
+    /// is dispatched in that case.
+    ///
+    /// # Returns
+    ///
+    /// The number of messages dispatched.
+    fn replay_from(&mut self, path: &Path, pace: ReplayPace) -> CoreResult<usize> {
+        let mut replay = Replay::open(path, pace)?;
+        let mut dispatched = 0;
+        while let Some(wait) = replay.until_next() {
+            if self.should_quit() {
+                break;
+            }
+            thread::sleep(wait);
+            if let Some(msg) = replay.next_due() {
+                self.dispatch(msg);
+                dispatched += 1;
+            }
+        }
+        Ok(dispatched)
+    }
 }
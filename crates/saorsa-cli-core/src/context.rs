@@ -0,0 +1,100 @@
+//! Shared application context
+//!
+//! The [`AppContext`] holds the state every tab may need: the theme to draw
+//! with, the terminal's color depth, the message bus and the application's
+//! [`AppConfig`]. The coordinator owns one context and passes it to
+//! [`Tab::focus`](crate::Tab::focus) and [`Tab::view`](crate::Tab::view), so
+//! tabs read the current theme and settings and publish messages without
+//! keeping their own copies.
+//!
+//! # Example
+//!
+//! ```
+//! use saorsa_cli_core::{AppContext, Message, MessageBus, Theme};
+//!
+//! let ctx = AppContext::new(Theme::dark(), MessageBus::new(16));
+//! let mut rx = ctx.bus.subscribe();
+//! ctx.publish(Message::ToggleHelp);
+//! assert_eq!(rx.try_recv().ok(), Some(Message::ToggleHelp));
+//! ```
+
+use crate::event::{Message, MessageBus};
+use crate::theme::{ColorDepth, Theme};
+
+/// Settings chosen when the application starts that tabs should honor
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AppConfig {
+    /// Move deleted files to the system trash instead of removing them
+    pub use_trash: bool,
+}
+
+/// State shared by the coordinator with its tabs
+#[derive(Debug, Clone, Default)]
+pub struct AppContext {
+    /// Theme to draw with, already adapted to `color_depth`
+    pub theme: Theme,
+    /// Color capability of the terminal
+    pub color_depth: ColorDepth,
+    /// Bus carrying messages between the coordinator, tabs and plugins
+    pub bus: MessageBus,
+    /// Application settings
+    pub config: AppConfig,
+}
+
+impl AppContext {
+    /// Creates a truecolor context drawing with `theme` and the default
+    /// [`AppConfig`]
+    ///
+    /// # Arguments
+    ///
+    /// * `theme` - Theme tabs draw with
+    /// * `bus` - Bus tabs publish on
+    #[must_use]
+    pub fn new(theme: Theme, bus: MessageBus) -> Self {
+        AppContext {
+            theme,
+            color_depth: ColorDepth::TrueColor,
+            bus,
+            config: AppConfig::default(),
+        }
+    }
+
+    /// Sets the color depth and adapts the theme to it
+    ///
+    /// # Arguments
+    ///
+    /// * `theme` - Theme as configured, before adapting
+    /// * `depth` - Color capability of the terminal
+    pub fn set_theme(&mut self, theme: &Theme, depth: ColorDepth) {
+        self.color_depth = depth;
+        self.theme = theme.for_color_depth(depth);
+    }
+
+    /// Publishes a message to every subscriber of the bus
+    ///
+    /// Having no subscribers is not an error; the message is dropped.
+    pub fn publish(&self, message: Message) {
+        let _ = self.bus.send(message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context_adapts_theme_to_color_depth() {
+        let mut ctx = AppContext::new(Theme::nord(), MessageBus::new(4));
+        assert_eq!(ctx.theme, Theme::nord());
+
+        ctx.set_theme(&Theme::nord(), ColorDepth::Ansi256);
+        assert_eq!(ctx.color_depth, ColorDepth::Ansi256);
+        assert_eq!(ctx.theme, Theme::nord().quantize_to_256());
+    }
+
+    #[test]
+    fn test_context_publish_without_subscribers() {
+        let ctx = AppContext::default();
+        ctx.publish(Message::Quit);
+    }
+}
@@ -79,6 +79,25 @@ pub enum CoreError {
     #[error("plugin manifest has invalid sha256 at {path:?}: {source}")]
     PluginHashInvalid { path: PathBuf, source: FromHexError },
 
+    /// Plugin search directories could not be watched for changes.
+    #[error("failed to watch plugin directories: {0}")]
+    PluginWatch(#[from] notify::Error),
+
+    /// A tab could not draw part of its content.
+    ///
+    /// The message says what failed, e.g. a preview that could not be parsed.
+    #[error("{0}")]
+    Render(String),
+
+    /// A line of a recorded session could not be parsed.
+    #[error("invalid recording at {path:?} line {line}: {source}")]
+    RecordingInvalid {
+        path: PathBuf,
+        line: usize,
+        #[source]
+        source: serde_json::Error,
+    },
+
     /// Plugin checksum did not match expected value.
     #[error("plugin checksum mismatch at {path:?}: expected {expected}, got {actual}")]
     PluginHashMismatch {
@@ -4,14 +4,16 @@
 //! between tabs, panes, and the application coordinator.
 
 use crossterm::event::{KeyEvent, MouseEvent};
+use serde::{Deserialize, Serialize};
 use tokio::sync::broadcast;
 
 use crate::error::CoreError;
-use crate::pane::{PaneId, Split};
+use crate::pane::{FocusDirection, PaneId, Split};
 use crate::tab::TabId;
+use crate::theme::Theme;
 
 /// Messages that can be sent through the TUI framework
-#[derive(Debug, Clone, PartialEq, Default)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub enum Message {
     // === Navigation ===
     /// Switch to a specific tab by ID
@@ -23,6 +25,22 @@ pub enum Message {
     /// Switch to the previous tab
     PrevTab,
 
+    // === Tab Lifecycle ===
+    /// A tab was added (published by the tab manager)
+    TabOpened(TabId),
+    /// A tab became the active tab (published by the tab manager)
+    TabFocused(TabId),
+    /// A tab was removed (published by the tab manager)
+    TabClosed(TabId),
+
+    // === Plugins ===
+    /// Plugin directories changed and plugins were reloaded (published by
+    /// the plugin manager while it watches)
+    PluginsReloaded {
+        /// Number of plugins available after the reload
+        loaded: usize,
+    },
+
     // === Pane Management ===
     /// Split the current pane
     SplitPane {
@@ -33,6 +51,14 @@ pub enum Message {
     ClosePane(PaneId),
     /// Focus a specific pane
     FocusPane(PaneId),
+    /// Focus the Nth leaf pane (1-based) of the active tab's layout
+    SwitchPane(PaneId),
+    /// Focus the pane next to the focused one on screen, in the active
+    /// tab's layout
+    FocusAdjacentPane(FocusDirection),
+    /// Zoom the focused pane of the active tab to fill it, or restore the
+    /// split layout
+    ToggleZoom,
     /// Resize a pane by delta
     ResizePane {
         /// Pane to resize
@@ -46,14 +72,26 @@ pub enum Message {
     Quit,
     /// Toggle help display
     ToggleHelp,
+    /// Show a transient notification over the content
+    Notify(Notification),
     /// Open the command palette
     OpenCommandPalette,
+    /// Switch every tab to a new theme
+    SetTheme(Theme),
 
     // === Input ===
     /// Keyboard input event
     Key(KeyEvent),
     /// Mouse input event
     Mouse(MouseEvent),
+    /// Mouse wheel scroll over the content area (translated from
+    /// [`Message::Mouse`] by the coordinator)
+    Scroll {
+        /// Rows to scroll; negative scrolls up
+        lines: i16,
+        /// Pane under the pointer, for tabs that expose a pane layout
+        area_hint: Option<PaneId>,
+    },
     /// Terminal resize event
     Resize(u16, u16),
 
@@ -156,6 +194,63 @@ impl Message {
     }
 }
 
+/// Severity of a [`Notification`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NotifyLevel {
+    /// Something finished or changed
+    Info,
+    /// Something needs attention but nothing was lost
+    Warning,
+    /// An operation failed
+    Error,
+}
+
+/// Short-lived message shown to the user, such as a failed save
+///
+/// # Example
+///
+/// ```
+/// use saorsa_cli_core::event::{Message, Notification, NotifyLevel};
+///
+/// let msg = Message::Notify(Notification::error("Save failed: disk full"));
+/// assert!(matches!(
+///     msg,
+///     Message::Notify(Notification { level: NotifyLevel::Error, .. })
+/// ));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Notification {
+    /// How prominently the notification is shown
+    pub level: NotifyLevel,
+    /// Text shown to the user
+    pub message: String,
+}
+
+impl Notification {
+    /// Creates a notification with the given level
+    pub fn new(level: NotifyLevel, message: impl Into<String>) -> Self {
+        Notification {
+            level,
+            message: message.into(),
+        }
+    }
+
+    /// Creates an informational notification
+    pub fn info(message: impl Into<String>) -> Self {
+        Self::new(NotifyLevel::Info, message)
+    }
+
+    /// Creates a warning notification
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self::new(NotifyLevel::Warning, message)
+    }
+
+    /// Creates an error notification
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(NotifyLevel::Error, message)
+    }
+}
+
 /// Input events from the terminal
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum InputEvent {
@@ -493,6 +588,8 @@ mod tests {
         };
         let close = Message::ClosePane(1);
         let focus = Message::FocusPane(2);
+        let switch = Message::SwitchPane(1);
+        let adjacent = Message::FocusAdjacentPane(FocusDirection::Left);
         let resize = Message::ResizePane { pane: 3, delta: 10 };
 
         assert!(matches!(
@@ -503,6 +600,11 @@ mod tests {
         ));
         assert!(matches!(close, Message::ClosePane(1)));
         assert!(matches!(focus, Message::FocusPane(2)));
+        assert!(matches!(switch, Message::SwitchPane(1)));
+        assert!(matches!(
+            adjacent,
+            Message::FocusAdjacentPane(FocusDirection::Left)
+        ));
         assert!(matches!(resize, Message::ResizePane { pane: 3, delta: 10 }));
     }
 
@@ -523,6 +625,21 @@ mod tests {
         assert!(matches!(msg, Message::Resize(120, 40)));
     }
 
+    #[test]
+    fn test_message_scroll() {
+        let msg = Message::Scroll {
+            lines: -3,
+            area_hint: Some(1),
+        };
+        assert!(matches!(
+            msg,
+            Message::Scroll {
+                lines: -3,
+                area_hint: Some(1)
+            }
+        ));
+    }
+
     #[test]
     fn test_input_event_equality() {
         use crossterm::event::{KeyCode, KeyModifiers};
@@ -0,0 +1,131 @@
+//! Ignore-file handling for directory walks
+//!
+//! Shared by the sb file tree and the disk analyzer's scans.
+//!
+//! [`IgnoreRules`] combines the `.gitignore` files met while descending a
+//! tree with a list of extra patterns supplied by the user. Patterns use
+//! gitignore syntax; deeper `.gitignore` files take precedence, and a
+//! negated pattern (`!keep.log`) re-includes a path.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Match;
+
+/// Ignore rules in effect for one directory
+#[derive(Clone, Default)]
+pub struct IgnoreRules {
+    /// Whether `.gitignore` files are read while descending
+    respect_gitignore: bool,
+    /// Extra patterns, applied everywhere below the scan root
+    extra: Option<Arc<Gitignore>>,
+    /// `.gitignore` matchers from the root down to the current directory
+    gitignores: Vec<Arc<Gitignore>>,
+}
+
+impl IgnoreRules {
+    /// Rules for a scan rooted at `root`
+    ///
+    /// Invalid extra patterns are skipped.
+    pub fn new(root: &Path, respect_gitignore: bool, patterns: &[String]) -> Self {
+        let extra = if patterns.is_empty() {
+            None
+        } else {
+            let mut builder = GitignoreBuilder::new(root);
+            for pattern in patterns {
+                let _ = builder.add_line(None, pattern);
+            }
+            builder.build().ok().map(Arc::new)
+        };
+        IgnoreRules {
+            respect_gitignore,
+            extra,
+            gitignores: Vec::new(),
+        }
+    }
+
+    /// Rules for the entries of `dir`, including its own `.gitignore`
+    pub fn descend(&self, dir: &Path) -> Self {
+        let mut rules = self.clone();
+        if self.respect_gitignore {
+            let file = dir.join(".gitignore");
+            if file.is_file() {
+                let (gitignore, _) = Gitignore::new(&file);
+                if !gitignore.is_empty() {
+                    rules.gitignores.push(Arc::new(gitignore));
+                }
+            }
+        }
+        rules
+    }
+
+    /// Whether `path` should be skipped
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        if let Some(extra) = &self.extra {
+            match extra.matched(path, is_dir) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => {}
+            }
+        }
+        for gitignore in self.gitignores.iter().rev() {
+            match gitignore.matched(path, is_dir) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => {}
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_gitignore_rules() {
+        let dir = tempdir().expect("create temp dir");
+        let root = dir.path();
+        fs::write(root.join(".gitignore"), "target/\n*.log\n!keep.log\n").expect("write");
+
+        let rules = IgnoreRules::new(root, true, &[]).descend(root);
+        assert!(rules.is_ignored(&root.join("target"), true));
+        assert!(rules.is_ignored(&root.join("debug.log"), false));
+        assert!(!rules.is_ignored(&root.join("keep.log"), false));
+        assert!(!rules.is_ignored(&root.join("src"), true));
+
+        let disabled = IgnoreRules::new(root, false, &[]).descend(root);
+        assert!(!disabled.is_ignored(&root.join("target"), true));
+    }
+
+    #[test]
+    fn test_nested_gitignore_takes_precedence() {
+        let dir = tempdir().expect("create temp dir");
+        let root = dir.path();
+        let sub = root.join("sub");
+        fs::create_dir(&sub).expect("create sub");
+        fs::write(root.join(".gitignore"), "*.tmp\n").expect("write root");
+        fs::write(sub.join(".gitignore"), "!wanted.tmp\n").expect("write sub");
+
+        let rules = IgnoreRules::new(root, true, &[])
+            .descend(root)
+            .descend(&sub);
+        assert!(rules.is_ignored(&sub.join("other.tmp"), false));
+        assert!(!rules.is_ignored(&sub.join("wanted.tmp"), false));
+    }
+
+    #[test]
+    fn test_extra_patterns_apply_without_gitignore() {
+        let dir = tempdir().expect("create temp dir");
+        let root = dir.path();
+        let patterns = vec!["node_modules/".to_string()];
+
+        let rules = IgnoreRules::new(root, false, &patterns).descend(root);
+        assert!(rules.is_ignored(&root.join("a/node_modules"), true));
+        assert!(!rules.is_ignored(&root.join("a/node_modules"), false));
+    }
+}
@@ -15,16 +15,22 @@
 //!
 //! - [`Tab`] - Trait for tab implementations
 //! - [`AppCoordinator`] - Trait for the main application loop
+//! - [`AppContext`] - Theme, message bus and [`AppConfig`] shared with tabs
 //! - [`PaneLayout`] - Layout management for split panes
 //! - [`Message`] - Event/message types for framework communication
 //! - [`MessageBus`] - Publish-subscribe message distribution
+//! - [`Recorder`] / [`Replay`] - Recording input sessions and playing them back
 //! - [`Theme`] - Theming system with colors and border styles
 //! - [`CoreError`] - Error types for framework operations
+//! - [`IgnoreRules`] - `.gitignore` and extra patterns for directory walks
+//! - [`format_bytes`] - Human-readable sizes
 //!
 //! ## Example
 //!
 //! ```ignore
-//! use saorsa_cli_core::{Tab, TabId, PaneLayout, PaneNode, Message, MessageBus, Theme};
+//! use saorsa_cli_core::{
+//!     AppContext, CoreResult, Tab, TabId, PaneLayout, PaneNode, Message, MessageBus, Theme,
+//! };
 //! use ratatui::prelude::*;
 //!
 //! // Define a custom tab
@@ -36,20 +42,19 @@
 //! impl Tab for MyTab {
 //!     fn id(&self) -> TabId { self.id }
 //!     fn title(&self) -> &str { "My Tab" }
-//!     fn focus(&mut self) { /* handle focus */ }
+//!     fn focus(&mut self, ctx: &AppContext) { /* handle focus */ }
 //!     fn blur(&mut self) { /* handle blur */ }
-//!     fn view(&self, frame: &mut Frame, area: Rect) {
+//!     fn view(&self, frame: &mut Frame, area: Rect, ctx: &AppContext) -> CoreResult<()> {
 //!         // Render based on self.layout
+//!         Ok(())
 //!     }
 //! }
 //!
 //! // Create a split layout
-//! let layout = PaneLayout {
-//!     root: PaneNode::vsplit(30, vec![
-//!         PaneNode::leaf(0),  // sidebar
-//!         PaneNode::leaf(1),  // main content
-//!     ]),
-//! };
+//! let layout = PaneLayout::new(PaneNode::vsplit(30, vec![
+//!     PaneNode::leaf(0),  // sidebar
+//!     PaneNode::leaf(1),  // main content
+//! ]));
 //!
 //! // Set up message bus for event handling
 //! let bus = MessageBus::new(100);
@@ -61,25 +66,33 @@
 //! ```
 
 pub mod app;
+pub mod context;
 pub mod error;
 pub mod event;
+pub mod ignore_rules;
 pub mod pane;
 pub mod plugin;
 pub mod plugin_history;
+pub mod record;
 pub mod tab;
 pub mod theme;
+pub mod units;
 
 pub use app::AppCoordinator;
+pub use context::{AppConfig, AppContext};
 pub use error::{CoreError, CoreResult};
-pub use event::{InputEvent, Message, MessageBus};
-pub use pane::{PaneId, PaneLayout, PaneNode, Split};
+pub use event::{InputEvent, Message, MessageBus, Notification, NotifyLevel};
+pub use ignore_rules::IgnoreRules;
+pub use pane::{FocusDirection, PaneId, PaneLayout, PaneNode, Split};
 pub use plugin::{
     Plugin, PluginContext, PluginDescriptor, PluginManager, PluginManifest, PluginMetadata,
-    PluginSecurityPolicy,
+    PluginReload, PluginSecurityPolicy,
 };
 pub use plugin_history::{PluginHistory, PluginRunStats};
-pub use tab::{Tab, TabId};
-pub use theme::{BorderStyle, Theme, ThemeColors};
+pub use record::{Recorder, Replay, ReplayPace};
+pub use tab::{Tab, TabBadge, TabId};
+pub use theme::{nearest_ansi256, to_monochrome, BorderStyle, ColorDepth, Theme, ThemeColors};
+pub use units::format_bytes;
 
 #[cfg(test)]
 mod tests {
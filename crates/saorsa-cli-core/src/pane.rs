@@ -2,7 +2,10 @@
 //!
 //! This module provides types for managing split pane layouts within tabs.
 //! Panes can be arranged in a tree structure with horizontal and vertical
-//! splits at various ratios.
+//! splits at various ratios, and one pane can be zoomed to fill the whole
+//! layout for a while with [`PaneLayout::toggle_zoom`].
+
+use serde::{Deserialize, Serialize};
 
 /// Unique identifier for a pane.
 ///
@@ -30,7 +33,7 @@ pub type PaneId = u32;
 /// assert!(vsplit.is_vertical());
 /// assert_eq!(vsplit.ratio(), 30);
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Split {
     /// Horizontal split (top/bottom) with percentage for first pane.
     ///
@@ -97,6 +100,23 @@ impl Split {
     }
 }
 
+/// Screen direction for moving focus to a neighbouring pane.
+///
+/// The coordinator picks the pane that lies next to the focused one in
+/// this direction, going by where the panes are drawn rather than by their
+/// order in the tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FocusDirection {
+    /// Towards the left edge of the tab.
+    Left,
+    /// Towards the right edge of the tab.
+    Right,
+    /// Towards the top of the tab.
+    Up,
+    /// Towards the bottom of the tab.
+    Down,
+}
+
 /// A node in the pane layout tree.
 ///
 /// The layout is represented as a tree where leaf nodes contain pane IDs
@@ -286,7 +306,8 @@ impl PaneNode {
 /// Root layout structure for panes within a tab.
 ///
 /// The `PaneLayout` wraps a [`PaneNode`] tree and provides methods
-/// for querying and manipulating the overall layout.
+/// for querying and manipulating the overall layout. Zooming a pane
+/// leaves the tree as it is, so unzooming brings the same splits back.
 ///
 /// # Examples
 ///
@@ -298,24 +319,55 @@ impl PaneNode {
 /// assert_eq!(layout.pane_ids(), vec![0]);
 ///
 /// // Create a more complex layout
-/// let complex = PaneLayout {
-///     root: PaneNode::vsplit(30, vec![
-///         PaneNode::leaf(0),
-///         PaneNode::hsplit(50, vec![
-///             PaneNode::leaf(1),
-///             PaneNode::leaf(2),
-///         ]),
+/// let complex = PaneLayout::new(PaneNode::vsplit(30, vec![
+///     PaneNode::leaf(0),
+///     PaneNode::hsplit(50, vec![
+///         PaneNode::leaf(1),
+///         PaneNode::leaf(2),
 ///     ]),
-/// };
+/// ]));
 /// assert_eq!(complex.pane_ids(), vec![0, 1, 2]);
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PaneLayout {
     /// The root node of the layout tree.
     pub root: PaneNode,
+    /// Pane drawn over the whole layout instead of the tree, if any.
+    pub zoomed: Option<PaneId>,
+    /// Pane holding the keyboard focus, if the tab reports it.
+    pub focused: Option<PaneId>,
 }
 
 impl PaneLayout {
+    /// Creates a layout from a tree of panes, with nothing zoomed or
+    /// focused.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The root node of the layout tree
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use saorsa_cli_core::{PaneLayout, PaneNode};
+    ///
+    /// let layout = PaneLayout::new(PaneNode::vsplit(
+    ///     30,
+    ///     vec![PaneNode::leaf(0), PaneNode::leaf(1)],
+    /// ));
+    /// assert_eq!(layout.pane_ids(), vec![0, 1]);
+    /// assert_eq!(layout.zoomed, None);
+    /// assert_eq!(layout.focused, None);
+    /// ```
+    #[must_use]
+    pub fn new(root: PaneNode) -> Self {
+        PaneLayout {
+            root,
+            zoomed: None,
+            focused: None,
+        }
+    }
+
     /// Creates a new layout with a single pane.
     ///
     /// This is the simplest layout containing just one pane
@@ -335,9 +387,7 @@ impl PaneLayout {
     /// ```
     #[must_use]
     pub fn single(pane_id: PaneId) -> Self {
-        PaneLayout {
-            root: PaneNode::Leaf(pane_id),
-        }
+        PaneLayout::new(PaneNode::Leaf(pane_id))
     }
 
     /// Returns all pane IDs in this layout.
@@ -365,12 +415,10 @@ impl PaneLayout {
     /// let single = PaneLayout::single(0);
     /// assert_eq!(single.pane_count(), 1);
     ///
-    /// let split = PaneLayout {
-    ///     root: PaneNode::hsplit(50, vec![
-    ///         PaneNode::leaf(0),
-    ///         PaneNode::leaf(1),
-    ///     ]),
-    /// };
+    /// let split = PaneLayout::new(PaneNode::hsplit(50, vec![
+    ///     PaneNode::leaf(0),
+    ///     PaneNode::leaf(1),
+    /// ]));
     /// assert_eq!(split.pane_count(), 2);
     /// ```
     #[must_use]
@@ -397,6 +445,74 @@ impl PaneLayout {
     pub fn contains(&self, pane_id: PaneId) -> bool {
         self.pane_ids().contains(&pane_id)
     }
+
+    /// Returns the Nth leaf pane in layout order, counting from 1.
+    ///
+    /// Numbering matches the Alt+Shift+digit pane shortcuts.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - One-based position of the pane
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use saorsa_cli_core::{PaneLayout, PaneNode};
+    ///
+    /// let layout = PaneLayout::new(PaneNode::vsplit(
+    ///     30,
+    ///     vec![PaneNode::leaf(7), PaneNode::leaf(9)],
+    /// ));
+    /// assert_eq!(layout.nth_pane(2), Some(9));
+    /// assert_eq!(layout.nth_pane(0), None);
+    /// ```
+    #[must_use]
+    pub fn nth_pane(&self, n: usize) -> Option<PaneId> {
+        let index = n.checked_sub(1)?;
+        self.pane_ids().get(index).copied()
+    }
+
+    /// Zooms `pane` to fill the layout, or restores the splits if it is
+    /// already zoomed.
+    ///
+    /// Zooming a pane that is not in the layout does nothing. Zooming a
+    /// different pane while one is zoomed switches the zoom to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `pane` - The pane to zoom or unzoom
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use saorsa_cli_core::{PaneLayout, PaneNode};
+    ///
+    /// let mut layout = PaneLayout::new(PaneNode::vsplit(
+    ///     30,
+    ///     vec![PaneNode::leaf(0), PaneNode::leaf(1)],
+    /// ));
+    /// layout.toggle_zoom(1);
+    /// assert_eq!(layout.zoomed_pane(), Some(1));
+    /// layout.toggle_zoom(1);
+    /// assert_eq!(layout.zoomed_pane(), None);
+    /// assert_eq!(layout.pane_ids(), vec![0, 1]);
+    /// ```
+    pub fn toggle_zoom(&mut self, pane: PaneId) {
+        if self.zoomed == Some(pane) {
+            self.zoomed = None;
+        } else if self.contains(pane) {
+            self.zoomed = Some(pane);
+        }
+    }
+
+    /// Returns the zoomed pane, if it is still part of the layout.
+    ///
+    /// A zoom outlives changes to the tree, so a pane that was removed
+    /// since it was zoomed leaves the layout drawn as split.
+    #[must_use]
+    pub fn zoomed_pane(&self) -> Option<PaneId> {
+        self.zoomed.filter(|&pane| self.contains(pane))
+    }
 }
 
 impl Default for PaneLayout {
@@ -503,21 +619,50 @@ mod tests {
 
     #[test]
     fn test_pane_layout_complex() {
-        let layout = PaneLayout {
-            root: PaneNode::vsplit(
-                25,
-                vec![
-                    PaneNode::leaf(0),
-                    PaneNode::hsplit(50, vec![PaneNode::leaf(1), PaneNode::leaf(2)]),
-                ],
-            ),
-        };
+        let layout = PaneLayout::new(PaneNode::vsplit(
+            25,
+            vec![
+                PaneNode::leaf(0),
+                PaneNode::hsplit(50, vec![PaneNode::leaf(1), PaneNode::leaf(2)]),
+            ],
+        ));
         assert_eq!(layout.pane_ids(), vec![0, 1, 2]);
         assert_eq!(layout.pane_count(), 3);
         assert!(layout.contains(0));
         assert!(layout.contains(1));
         assert!(layout.contains(2));
         assert!(!layout.contains(3));
+        assert_eq!(layout.nth_pane(1), Some(0));
+        assert_eq!(layout.nth_pane(3), Some(2));
+        assert_eq!(layout.nth_pane(4), None);
+    }
+
+    #[test]
+    fn test_pane_layout_toggle_zoom() {
+        let mut layout = PaneLayout::new(PaneNode::vsplit(
+            30,
+            vec![
+                PaneNode::leaf(0),
+                PaneNode::hsplit(50, vec![PaneNode::leaf(1), PaneNode::leaf(2)]),
+            ],
+        ));
+        let splits = layout.root.clone();
+
+        layout.toggle_zoom(2);
+        assert_eq!(layout.zoomed_pane(), Some(2));
+        assert_eq!(layout.root, splits);
+        layout.toggle_zoom(0);
+        assert_eq!(layout.zoomed_pane(), Some(0));
+        layout.toggle_zoom(9);
+        assert_eq!(layout.zoomed_pane(), Some(0));
+        layout.toggle_zoom(0);
+        assert_eq!(layout.zoomed_pane(), None);
+
+        // A zoomed pane that leaves the tree no longer counts
+        layout.toggle_zoom(2);
+        layout.root = PaneNode::leaf(0);
+        assert_eq!(layout.zoomed, Some(2));
+        assert_eq!(layout.zoomed_pane(), None);
     }
 
     #[test]
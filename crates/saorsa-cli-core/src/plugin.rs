@@ -1,8 +1,8 @@
-use crate::event::MessageBus;
+use crate::event::{Message, MessageBus};
 use crate::{CoreError, CoreResult};
 use hex;
 use libloading::{Library, Symbol};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::ffi::OsStr;
@@ -10,7 +10,11 @@ use std::fs;
 use std::fs::File;
 use std::io::Read;
 use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
 use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
 
 mod builtin_plugins;
 use builtin_plugins::builtin_plugins;
@@ -18,8 +22,14 @@ use builtin_plugins::builtin_plugins;
 /// Default filename expected inside a plugin directory.
 pub const MANIFEST_NAME: &str = "saorsa-plugin.toml";
 
+/// Quiet period after the last change in a watched directory before reloading.
+pub const PLUGIN_WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Longest a burst of changes can hold back a reload while events keep arriving.
+pub const PLUGIN_WATCH_MAX_DELAY: Duration = Duration::from_secs(2);
+
 /// Metadata describing a plugin for UI display and selection.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct PluginMetadata {
     pub name: String,
     pub version: String,
@@ -28,10 +38,32 @@ pub struct PluginMetadata {
     pub help: Option<String>,
     pub manifest_path: PathBuf,
     pub library_path: PathBuf,
+    /// Arguments passed before any supplied on the command line.
+    pub default_args: Vec<String>,
+    /// Environment variables set for the plugin run.
+    pub env: HashMap<String, String>,
+}
+
+impl PluginMetadata {
+    /// Manifest default arguments followed by `args`.
+    #[must_use]
+    pub fn resolve_args(&self, args: &[String]) -> Vec<String> {
+        self.default_args.iter().chain(args).cloned().collect()
+    }
+
+    /// Adds the manifest environment to `ctx`; variables already set on the
+    /// context take precedence.
+    #[must_use]
+    pub fn resolve_context<'a>(&self, mut ctx: PluginContext<'a>) -> PluginContext<'a> {
+        for (key, value) in &self.env {
+            ctx.env.entry(key.clone()).or_insert_with(|| value.clone());
+        }
+        ctx
+    }
 }
 
 /// Runtime descriptor for an instantiated plugin.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct PluginDescriptor {
     pub metadata: PluginMetadata,
 }
@@ -52,18 +84,35 @@ pub struct PluginManifest {
     pub entry_symbol: Option<String>,
     #[serde(default)]
     pub sha256: Option<String>,
+    #[serde(default)]
+    pub default_args: Vec<String>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
 }
 
 /// Context passed to plugins during execution.
 #[derive(Debug, Clone, Default)]
 pub struct PluginContext<'a> {
     pub message_bus: Option<&'a MessageBus>,
+    /// Environment variables for the run, including the manifest's `env`
+    /// once resolved. Plugins that spawn processes should pass these on.
+    pub env: HashMap<String, String>,
 }
 
 impl<'a> PluginContext<'a> {
     #[must_use]
     pub fn new(message_bus: Option<&'a MessageBus>) -> Self {
-        Self { message_bus }
+        Self {
+            message_bus,
+            env: HashMap::new(),
+        }
+    }
+
+    /// Sets an environment variable, overriding the manifest's value.
+    #[must_use]
+    pub fn with_env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.insert(key.into(), value.into());
+        self
     }
 }
 
@@ -84,14 +133,22 @@ struct LoadedPlugin {
     descriptor: PluginDescriptor,
     instance: Arc<dyn Plugin>,
     _library: Option<Library>,
+    /// Declared after the library so the copy outlives the mapping
+    _shadow: Option<ShadowCopy>,
 }
 
 impl LoadedPlugin {
-    fn dynamic(descriptor: PluginDescriptor, instance: Box<dyn Plugin>, library: Library) -> Self {
+    fn dynamic(
+        descriptor: PluginDescriptor,
+        instance: Box<dyn Plugin>,
+        library: Library,
+        shadow: Option<ShadowCopy>,
+    ) -> Self {
         Self {
             descriptor,
             instance: Arc::from(instance),
             _library: Some(library),
+            _shadow: shadow,
         }
     }
 
@@ -100,15 +157,63 @@ impl LoadedPlugin {
             descriptor,
             instance: Arc::from(instance),
             _library: None,
+            _shadow: None,
         }
     }
 }
 
+/// Private copy of a plugin library, removed again when dropped.
+///
+/// The dynamic loader hands back a library it already has mapped under the
+/// same path, so a new version is loaded from a copy while the last good
+/// version is still in use. The copy lives in a fresh directory only the
+/// current user can enter, so nobody can plant or swap the file under us.
+struct ShadowCopy {
+    path: PathBuf,
+    _dir: TempDir,
+}
+
+impl ShadowCopy {
+    fn of(library_path: &Path) -> CoreResult<Self> {
+        let mut builder = tempfile::Builder::new();
+        builder.prefix("saorsa-plugin-");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            builder.permissions(fs::Permissions::from_mode(0o700));
+        }
+        let dir = builder.tempdir()?;
+        let name = library_path
+            .file_name()
+            .unwrap_or_else(|| OsStr::new("plugin"));
+        let path = dir.path().join(name);
+        fs::copy(library_path, &path)?;
+        Ok(Self { path, _dir: dir })
+    }
+}
+
+/// Outcome of reloading plugins after their directories changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PluginReload {
+    /// Number of plugins available after the reload.
+    pub loaded: usize,
+    /// Manifests that failed to load, and whether a last good version was kept.
+    pub warnings: Vec<String>,
+}
+
+/// Filesystem watcher over the search paths, reporting debounced bursts.
+struct PluginWatcher {
+    _watcher: notify::RecommendedWatcher,
+    changes: Receiver<()>,
+}
+
 /// Manages discovery and execution of Saorsa plugins.
 pub struct PluginManager {
     search_paths: Vec<PathBuf>,
     plugins: HashMap<String, LoadedPlugin>,
     security_policy: PluginSecurityPolicy,
+    watcher: Option<PluginWatcher>,
+    events: Option<MessageBus>,
 }
 
 impl PluginManager {
@@ -118,6 +223,8 @@ impl PluginManager {
             search_paths: default_paths(),
             plugins: HashMap::new(),
             security_policy: PluginSecurityPolicy::default(),
+            watcher: None,
+            events: None,
         }
     }
 
@@ -134,6 +241,8 @@ impl PluginManager {
             search_paths: default_paths(),
             plugins: HashMap::new(),
             security_policy: policy,
+            watcher: None,
+            events: None,
         }
     }
 
@@ -164,7 +273,10 @@ impl PluginManager {
 
         for path in self.search_paths.clone() {
             if path.exists() {
-                loaded += self.load_from_path(&path)?;
+                for manifest_path in manifests_in(&path)? {
+                    self.load_manifest(&manifest_path)?;
+                    loaded += 1;
+                }
             }
         }
 
@@ -173,6 +285,159 @@ impl PluginManager {
         Ok(loaded)
     }
 
+    /// Sets the bus that receives [`Message::PluginsReloaded`] after a
+    /// watched change, or `None` to stop publishing.
+    pub fn set_event_bus(&mut self, bus: Option<MessageBus>) {
+        self.events = bus;
+    }
+
+    /// Watch the existing search directories for changes.
+    ///
+    /// Changes are picked up by [`PluginManager::poll_changes`]. Search
+    /// paths created after this call are not watched until it is called
+    /// again.
+    pub fn watch(&mut self) -> CoreResult<()> {
+        use notify::{EventKind, RecursiveMode, Watcher};
+
+        let (raw_tx, raw_rx) = mpsc::channel::<()>();
+        let mut watcher =
+            notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+                if let Ok(event) = res {
+                    if matches!(
+                        event.kind,
+                        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+                    ) {
+                        let _ = raw_tx.send(());
+                    }
+                }
+            })?;
+        for path in self.search_paths.iter().filter(|path| path.is_dir()) {
+            watcher.watch(path, RecursiveMode::Recursive)?;
+        }
+
+        // Editors and build tools touch several files per save; report
+        // each burst once, but no later than PLUGIN_WATCH_MAX_DELAY
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            while raw_rx.recv().is_ok() {
+                let deadline = Instant::now() + PLUGIN_WATCH_MAX_DELAY;
+                loop {
+                    let left = deadline.saturating_duration_since(Instant::now());
+                    if left.is_zero()
+                        || raw_rx
+                            .recv_timeout(PLUGIN_WATCH_DEBOUNCE.min(left))
+                            .is_err()
+                    {
+                        break;
+                    }
+                }
+                if tx.send(()).is_err() {
+                    break;
+                }
+            }
+        });
+        self.watcher = Some(PluginWatcher {
+            _watcher: watcher,
+            changes: rx,
+        });
+        Ok(())
+    }
+
+    /// Stop watching the search directories.
+    pub fn unwatch(&mut self) {
+        self.watcher = None;
+    }
+
+    /// Returns whether the search directories are being watched.
+    pub fn is_watching(&self) -> bool {
+        self.watcher.is_some()
+    }
+
+    /// Reload plugins if a watched directory changed since the last call.
+    ///
+    /// Unlike [`PluginManager::load`], a manifest that fails to load does
+    /// not abort the reload: the last good version of that plugin stays
+    /// available and the failure is reported in [`PluginReload::warnings`].
+    /// Callers must not poll while a plugin is executing, since reloading
+    /// unloads the libraries of changed plugins.
+    pub fn poll_changes(&mut self) -> Option<PluginReload> {
+        let watcher = self.watcher.as_ref()?;
+        let mut changed = false;
+        while watcher.changes.try_recv().is_ok() {
+            changed = true;
+        }
+        if !changed {
+            return None;
+        }
+
+        let report = self.reload_keeping_last_good();
+        if let Some(bus) = self
+            .events
+            .as_ref()
+            .filter(|bus| bus.subscriber_count() > 0)
+        {
+            let _ = bus.send(Message::PluginsReloaded {
+                loaded: report.loaded,
+            });
+        }
+        Some(report)
+    }
+
+    /// Reload every manifest, falling back to the previously loaded plugin
+    /// for manifests that no longer load.
+    fn reload_keeping_last_good(&mut self) -> PluginReload {
+        let mut previous: HashMap<PathBuf, LoadedPlugin> = self
+            .plugins
+            .drain()
+            .map(|(_, plugin)| (plugin.descriptor.metadata.manifest_path.clone(), plugin))
+            .collect();
+        let mut report = PluginReload::default();
+
+        let manifests: Vec<PathBuf> = self
+            .search_paths
+            .iter()
+            .filter(|path| path.exists())
+            .filter_map(|path| manifests_in(path).ok())
+            .flatten()
+            .collect();
+        for manifest_path in manifests {
+            let result = self
+                .read_manifest(&manifest_path)
+                .and_then(|(manifest, library_path)| {
+                    // The last good version stays loaded until this one has
+                    let shadow = match previous.get(&manifest_path) {
+                        Some(plugin) if plugin._library.is_some() => {
+                            let shadow = ShadowCopy::of(&library_path)?;
+                            self.verify_integrity(&manifest, &manifest_path, &shadow.path)?;
+                            Some(shadow)
+                        }
+                        _ => None,
+                    };
+                    self.load_library(&manifest, &manifest_path, library_path, shadow)
+                });
+            let Err(err) = result else {
+                previous.remove(&manifest_path);
+                continue;
+            };
+            match previous.remove(&manifest_path) {
+                Some(plugin) => {
+                    let name = plugin.descriptor.metadata.name.clone();
+                    report
+                        .warnings
+                        .push(format!("{name}: {err}; keeping the last good version"));
+                    self.plugins.insert(name, plugin);
+                }
+                None => report
+                    .warnings
+                    .push(format!("{}: {err}", manifest_path.display())),
+            }
+        }
+
+        self.register_builtin_plugins();
+        report.loaded = self.plugins.len();
+        report
+    }
+
     /// Returns metadata for all loaded plugins.
     pub fn descriptors(&self) -> Vec<PluginDescriptor> {
         self.plugins
@@ -190,14 +455,23 @@ impl PluginManager {
     }
 
     /// Execute plugin by name.
+    ///
+    /// The manifest's `default_args` come before `args`, and its `env` is
+    /// merged under any variables already set on `ctx`.
     pub fn execute_plugin(
         &self,
         name: &str,
         args: &[String],
         ctx: PluginContext<'_>,
     ) -> CoreResult<()> {
-        let plugin = self.plugin_instance(name)?;
-        plugin.execute(args, ctx)
+        let plugin = self
+            .plugins
+            .get(name)
+            .ok_or_else(|| crate::CoreError::PluginNotFound(name.to_string()))?;
+        let metadata = &plugin.descriptor.metadata;
+        plugin
+            .instance
+            .execute(&metadata.resolve_args(args), metadata.resolve_context(ctx))
     }
 
     /// Returns detailed help text for a plugin if available.
@@ -207,27 +481,6 @@ impl PluginManager {
             .map(|p| p.instance.help().to_string())
     }
 
-    fn load_from_path(&mut self, root: &Path) -> CoreResult<usize> {
-        let mut count = 0usize;
-        if root.is_dir() {
-            for entry in fs::read_dir(root)? {
-                let entry = entry?;
-                let path = entry.path();
-                if path.is_dir() {
-                    let manifest_path = path.join(MANIFEST_NAME);
-                    if manifest_path.is_file() {
-                        self.load_manifest(&manifest_path)?;
-                        count += 1;
-                    }
-                } else if is_manifest(&path) {
-                    self.load_manifest(&path)?;
-                    count += 1;
-                }
-            }
-        }
-        Ok(count)
-    }
-
     fn register_builtin_plugins(&mut self) -> usize {
         let mut count = 0;
         for (descriptor, instance) in builtin_plugins() {
@@ -243,6 +496,13 @@ impl PluginManager {
     }
 
     fn load_manifest(&mut self, manifest_path: &Path) -> CoreResult<()> {
+        let (manifest, library_path) = self.read_manifest(manifest_path)?;
+        self.load_library(&manifest, manifest_path, library_path, None)
+    }
+
+    /// Parse a manifest and check its library exists and passes the
+    /// integrity policy, without loading it.
+    fn read_manifest(&self, manifest_path: &Path) -> CoreResult<(PluginManifest, PathBuf)> {
         let data = fs::read_to_string(manifest_path)?;
         let manifest: PluginManifest =
             toml::from_str(&data).map_err(|err| crate::CoreError::PluginManifest {
@@ -265,7 +525,18 @@ impl PluginManager {
         }
 
         self.verify_integrity(&manifest, manifest_path, &library_path)?;
+        Ok((manifest, library_path))
+    }
 
+    /// Load the plugin library at `library_path`, or from `shadow`, a copy
+    /// of it, when given.
+    fn load_library(
+        &mut self,
+        manifest: &PluginManifest,
+        manifest_path: &Path,
+        library_path: PathBuf,
+        shadow: Option<ShadowCopy>,
+    ) -> CoreResult<()> {
         let symbol_name = manifest
             .entry_symbol
             .as_deref()
@@ -273,8 +544,9 @@ impl PluginManager {
             .as_bytes();
 
         unsafe {
+            let load_path = shadow.as_ref().map_or(library_path.as_path(), |s| &s.path);
             let library =
-                Library::new(&library_path).map_err(|err| crate::CoreError::PluginLoadFailed {
+                Library::new(load_path).map_err(|err| crate::CoreError::PluginLoadFailed {
                     path: library_path.clone(),
                     source: err,
                 })?;
@@ -294,6 +566,8 @@ impl PluginManager {
                 help: manifest.help.clone(),
                 manifest_path: manifest_path.to_path_buf(),
                 library_path: library_path.clone(),
+                default_args: manifest.default_args.clone(),
+                env: manifest.env.clone(),
             };
             let descriptor = PluginDescriptor {
                 metadata: metadata.clone(),
@@ -305,7 +579,7 @@ impl PluginManager {
 
             self.plugins.insert(
                 metadata.name.clone(),
-                LoadedPlugin::dynamic(descriptor, boxed, library),
+                LoadedPlugin::dynamic(descriptor, boxed, library, shadow),
             );
         }
 
@@ -368,6 +642,26 @@ fn default_paths() -> Vec<PathBuf> {
     paths
 }
 
+/// Manifest files in `root`, either directly or one directory down.
+fn manifests_in(root: &Path) -> CoreResult<Vec<PathBuf>> {
+    let mut manifests = Vec::new();
+    if root.is_dir() {
+        for entry in fs::read_dir(root)? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.is_dir() {
+                let manifest_path = path.join(MANIFEST_NAME);
+                if manifest_path.is_file() {
+                    manifests.push(manifest_path);
+                }
+            } else if is_manifest(&path) {
+                manifests.push(path);
+            }
+        }
+    }
+    Ok(manifests)
+}
+
 fn is_manifest(path: &Path) -> bool {
     matches!(path.file_name().and_then(OsStr::to_str), Some(name) if name == MANIFEST_NAME)
 }
@@ -486,10 +780,248 @@ mod tests {
             homepage: None,
             entry_symbol: None,
             sha256: Some("aaaaaaaa".into()),
+            default_args: Vec::new(),
+            env: HashMap::new(),
         };
         let err = manager
             .verify_integrity(&manifest, manifest_path, &library_path)
             .unwrap_err();
         assert!(matches!(err, CoreError::PluginHashMismatch { .. }));
     }
+
+    #[test]
+    fn manifest_defaults_args_and_env() {
+        let manifest: PluginManifest = toml::from_str(
+            r#"
+            name = "fmt"
+            version = "0.1.0"
+            description = "format"
+            author = "tester"
+            library = "libfmt.so"
+            default_args = ["--check", "src"]
+
+            [env]
+            RUST_LOG = "info"
+            FMT_STYLE = "compact"
+            "#,
+        )
+        .expect("manifest");
+        let metadata = PluginMetadata {
+            name: manifest.name,
+            version: manifest.version,
+            description: manifest.description,
+            author: manifest.author,
+            help: None,
+            manifest_path: PathBuf::from("saorsa-plugin.toml"),
+            library_path: manifest.library,
+            default_args: manifest.default_args,
+            env: manifest.env,
+        };
+
+        assert_eq!(
+            metadata.resolve_args(&["lib".to_string()]),
+            vec!["--check", "src", "lib"]
+        );
+
+        let ctx = metadata.resolve_context(PluginContext::default().with_env("RUST_LOG", "debug"));
+        assert_eq!(ctx.env.get("RUST_LOG").map(String::as_str), Some("debug"));
+        assert_eq!(
+            ctx.env.get("FMT_STYLE").map(String::as_str),
+            Some("compact")
+        );
+    }
+
+    #[test]
+    fn manifest_without_defaults_parses() {
+        let manifest: PluginManifest = toml::from_str(
+            r#"
+            name = "plain"
+            version = "0.1.0"
+            description = "plain"
+            author = "tester"
+            library = "libplain.so"
+            "#,
+        )
+        .expect("manifest");
+        assert!(manifest.default_args.is_empty());
+        assert!(manifest.env.is_empty());
+    }
+
+    #[test]
+    fn metadata_serializes_to_json() {
+        let descriptor = PluginDescriptor {
+            metadata: PluginMetadata {
+                name: "fmt".into(),
+                version: "0.2.0".into(),
+                description: "Formats code".into(),
+                author: "tester".into(),
+                help: None,
+                manifest_path: PathBuf::from("saorsa-plugin.toml"),
+                library_path: PathBuf::from("libfmt.so"),
+                default_args: vec!["--check".into()],
+                env: HashMap::new(),
+            },
+        };
+
+        let json = serde_json::to_value(&descriptor).expect("serialize");
+        assert_eq!(json["metadata"]["name"], "fmt");
+        assert_eq!(json["metadata"]["version"], "0.2.0");
+        assert_eq!(json["metadata"]["default_args"][0], "--check");
+    }
+
+    struct StubPlugin;
+
+    impl Plugin for StubPlugin {
+        fn name(&self) -> &str {
+            "stub"
+        }
+        fn description(&self) -> &str {
+            "stub"
+        }
+        fn version(&self) -> &str {
+            "0.1.0"
+        }
+        fn author(&self) -> &str {
+            "tester"
+        }
+        fn help(&self) -> &str {
+            ""
+        }
+        fn execute(&self, _args: &[String], _ctx: PluginContext<'_>) -> CoreResult<()> {
+            Ok(())
+        }
+    }
+
+    const BROKEN_MANIFEST: &str = r#"
+        name = "stub"
+        version = "0.2.0"
+        description = "stub"
+        author = "tester"
+        library = "libmissing.so"
+    "#;
+
+    /// Registers version 0.1.0 of the stub as if loaded from `manifest_path`
+    fn insert_stub(manager: &mut PluginManager, manifest_path: &Path) {
+        let descriptor = PluginDescriptor {
+            metadata: PluginMetadata {
+                name: "stub".into(),
+                version: "0.1.0".into(),
+                description: "stub".into(),
+                author: "tester".into(),
+                help: None,
+                manifest_path: manifest_path.to_path_buf(),
+                library_path: manifest_path.with_file_name("libstub.so"),
+                default_args: Vec::new(),
+                env: HashMap::new(),
+            },
+        };
+        manager.plugins.insert(
+            "stub".into(),
+            LoadedPlugin::builtin(descriptor, Box::new(StubPlugin)),
+        );
+    }
+
+    fn assert_stub_kept(manager: &PluginManager, report: &PluginReload) {
+        assert_eq!(report.warnings.len(), 1);
+        assert!(report.warnings[0].contains("keeping the last good version"));
+        let stub = manager
+            .descriptors()
+            .into_iter()
+            .find(|d| d.metadata.name == "stub")
+            .expect("stub kept");
+        assert_eq!(stub.metadata.version, "0.1.0");
+        assert_eq!(report.loaded, manager.descriptors().len());
+    }
+
+    #[test]
+    fn reload_keeps_last_good_version_of_broken_manifest() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let manifest_path = dir.path().join(MANIFEST_NAME);
+        std::fs::write(&manifest_path, BROKEN_MANIFEST).expect("write manifest");
+
+        let mut manager = PluginManager::with_paths(vec![dir.path().to_path_buf()]);
+        insert_stub(&mut manager, &manifest_path);
+
+        let report = manager.reload_keeping_last_good();
+        assert_stub_kept(&manager, &report);
+    }
+
+    #[test]
+    fn reload_keeps_last_good_version_when_library_fails_to_load() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let library_path = dir.path().join("libstub.so");
+        std::fs::write(&library_path, b"not a shared library").expect("write library");
+        let sha256 = hex::encode(compute_sha256(&library_path).expect("hash"));
+        let manifest_path = dir.path().join(MANIFEST_NAME);
+        let manifest = format!(
+            r#"
+            name = "stub"
+            version = "0.2.0"
+            description = "stub"
+            author = "tester"
+            library = "libstub.so"
+            sha256 = "{sha256}"
+        "#
+        );
+        std::fs::write(&manifest_path, manifest).expect("write manifest");
+
+        let mut manager = PluginManager::with_paths(vec![dir.path().to_path_buf()]);
+        insert_stub(&mut manager, &manifest_path);
+
+        let report = manager.reload_keeping_last_good();
+        assert!(report.warnings[0].contains("libstub.so"));
+        assert_stub_kept(&manager, &report);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn shadow_copy_lives_in_a_private_directory() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let library = write_temp_file(b"library bytes");
+        let shadow = ShadowCopy::of(library.path()).expect("copy");
+        let dir = shadow.path.parent().expect("dir").to_path_buf();
+        let mode = fs::metadata(&dir).expect("metadata").permissions().mode();
+        assert_eq!(mode & 0o777, 0o700);
+        assert_eq!(fs::read(&shadow.path).expect("read"), b"library bytes");
+
+        drop(shadow);
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn watch_reports_new_manifests() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut manager = PluginManager::with_paths(vec![dir.path().to_path_buf()]);
+        let bus = MessageBus::new(8);
+        let mut rx = bus.subscribe();
+        manager.set_event_bus(Some(bus));
+        manager.watch().expect("watch");
+        assert!(manager.is_watching());
+        assert!(manager.poll_changes().is_none());
+
+        let plugin_dir = dir.path().join("stub");
+        std::fs::create_dir(&plugin_dir).expect("plugin dir");
+        std::fs::write(plugin_dir.join(MANIFEST_NAME), BROKEN_MANIFEST).expect("manifest");
+
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        let report = loop {
+            if let Some(report) = manager.poll_changes() {
+                break report;
+            }
+            assert!(std::time::Instant::now() < deadline, "no change seen");
+            thread::sleep(Duration::from_millis(20));
+        };
+        assert!(report
+            .warnings
+            .iter()
+            .any(|warning| warning.contains("plugin library missing")));
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(Message::PluginsReloaded { loaded }) if loaded == report.loaded
+        ));
+
+        manager.unwatch();
+        assert!(!manager.is_watching());
+    }
 }
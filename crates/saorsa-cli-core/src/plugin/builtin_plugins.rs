@@ -1,4 +1,5 @@
 use crate::{CoreError, CoreResult, Plugin, PluginContext, PluginDescriptor, PluginMetadata};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::process::{Command, Stdio};
 
@@ -49,6 +50,8 @@ fn build_plugin(
         help: Some(help.clone()),
         manifest_path: PathBuf::from(format!("builtin://{name}/manifest")),
         library_path: PathBuf::from(format!("builtin://{name}/library")),
+        default_args: Vec::new(),
+        env: HashMap::new(),
     };
 
     let descriptor = PluginDescriptor {
@@ -93,9 +96,10 @@ impl Plugin for ExternalCommandPlugin {
         &self.help_text
     }
 
-    fn execute(&self, args: &[String], _ctx: PluginContext<'_>) -> CoreResult<()> {
+    fn execute(&self, args: &[String], ctx: PluginContext<'_>) -> CoreResult<()> {
         let mut command = Command::new(&self.command);
         command
+            .envs(&ctx.env)
             .stdin(Stdio::inherit())
             .stdout(Stdio::inherit())
             .stderr(Stdio::inherit());
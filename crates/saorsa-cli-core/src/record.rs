@@ -0,0 +1,234 @@
+//! Recording and replaying input sessions
+//!
+//! A [`Recorder`] writes every message an application dispatches to a file,
+//! one JSON object per line with the milliseconds since recording started.
+//! A [`Replay`] reads such a file back and hands the messages out again at
+//! their recorded timings, or all at once, so a bug seen in a live session
+//! can be reproduced with
+//! [`AppCoordinator::replay_from`](crate::AppCoordinator::replay_from).
+//!
+//! # Example
+//!
+//! ```
+//! use saorsa_cli_core::record::{Recorder, Replay, ReplayPace};
+//! use saorsa_cli_core::Message;
+//!
+//! let dir = std::env::temp_dir().join("saorsa-record-doc");
+//! std::fs::create_dir_all(&dir).unwrap();
+//! let path = dir.join("session.jsonl");
+//!
+//! let mut recorder = Recorder::create(&path).unwrap();
+//! recorder.record(&Message::NextTab).unwrap();
+//! recorder.record(&Message::Quit).unwrap();
+//! drop(recorder);
+//!
+//! let mut replay = Replay::open(&path, ReplayPace::Immediate).unwrap();
+//! assert_eq!(replay.next_due(), Some(Message::NextTab));
+//! assert_eq!(replay.next_due(), Some(Message::Quit));
+//! assert!(replay.is_finished());
+//! ```
+
+use crate::error::{CoreError, CoreResult};
+use crate::event::Message;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// One line of a recording
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedMessage {
+    /// Milliseconds between the start of recording and the dispatch
+    pub at_ms: u64,
+    /// The message as it was dispatched
+    pub message: Message,
+}
+
+/// Appends dispatched messages to a recording file
+///
+/// Every line is flushed as it is written, so a recording survives the
+/// crash it is meant to reproduce.
+pub struct Recorder {
+    writer: BufWriter<File>,
+    started: Instant,
+}
+
+impl Recorder {
+    /// Creates or truncates the recording at `path` and starts the clock
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created.
+    pub fn create(path: &Path) -> CoreResult<Self> {
+        Ok(Recorder {
+            writer: BufWriter::new(File::create(path)?),
+            started: Instant::now(),
+        })
+    }
+
+    /// Writes `message`, stamped with the time since the recorder was created
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the line cannot be written.
+    pub fn record(&mut self, message: &Message) -> CoreResult<()> {
+        let entry = RecordedMessage {
+            at_ms: self.started.elapsed().as_millis() as u64,
+            message: message.clone(),
+        };
+        let line = serde_json::to_string(&entry).map_err(io::Error::other)?;
+        writeln!(self.writer, "{line}")?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// How fast a [`Replay`] hands out its messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReplayPace {
+    /// At the timings they were recorded with
+    #[default]
+    AsRecorded,
+    /// As fast as they are asked for, e.g. in tests
+    Immediate,
+}
+
+/// Messages read back from a recording, handed out as they fall due
+pub struct Replay {
+    messages: VecDeque<RecordedMessage>,
+    pace: ReplayPace,
+    started: Instant,
+}
+
+impl Replay {
+    /// Creates a replay of `messages`, starting the clock now
+    pub fn new(messages: Vec<RecordedMessage>, pace: ReplayPace) -> Self {
+        Replay {
+            messages: messages.into(),
+            pace,
+            started: Instant::now(),
+        }
+    }
+
+    /// Reads the recording at `path`
+    ///
+    /// Blank lines are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or a line is not a
+    /// recorded message.
+    pub fn open(path: &Path, pace: ReplayPace) -> CoreResult<Self> {
+        let text = fs::read_to_string(path)?;
+        let messages = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                serde_json::from_str(line).map_err(|source| CoreError::RecordingInvalid {
+                    path: path.to_path_buf(),
+                    line: i + 1,
+                    source,
+                })
+            })
+            .collect::<CoreResult<Vec<_>>>()?;
+        Ok(Self::new(messages, pace))
+    }
+
+    /// Number of messages not yet handed out
+    pub fn remaining(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Returns true once every message has been handed out
+    pub fn is_finished(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// How long until the next message falls due
+    ///
+    /// # Returns
+    ///
+    /// `None` once the replay is finished, and zero when a message is due.
+    pub fn until_next(&self) -> Option<Duration> {
+        let next = self.messages.front()?;
+        Some(match self.pace {
+            ReplayPace::AsRecorded => {
+                Duration::from_millis(next.at_ms).saturating_sub(self.started.elapsed())
+            }
+            ReplayPace::Immediate => Duration::ZERO,
+        })
+    }
+
+    /// Takes the next message if it is due
+    pub fn next_due(&mut self) -> Option<Message> {
+        if self.until_next()? > Duration::ZERO {
+            return None;
+        }
+        self.messages.pop_front().map(|entry| entry.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyEvent};
+
+    #[test]
+    fn test_record_and_replay_roundtrip() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("session.jsonl");
+        let messages = vec![
+            Message::Key(KeyEvent::from(KeyCode::Char('j'))),
+            Message::Resize(100, 40),
+            Message::Notify(crate::Notification::info("saved")),
+            Message::SetTheme(crate::Theme::light()),
+        ];
+
+        let mut recorder = Recorder::create(&path).expect("create");
+        for message in &messages {
+            recorder.record(message).expect("record");
+        }
+
+        let mut replay = Replay::open(&path, ReplayPace::Immediate).expect("open");
+        assert_eq!(replay.remaining(), messages.len());
+        let replayed: Vec<_> = std::iter::from_fn(|| replay.next_due()).collect();
+        assert_eq!(replayed, messages);
+        assert_eq!(replay.until_next(), None);
+    }
+
+    #[test]
+    fn test_replay_waits_for_recorded_timing() {
+        let mut replay = Replay::new(
+            vec![
+                RecordedMessage {
+                    at_ms: 0,
+                    message: Message::NextTab,
+                },
+                RecordedMessage {
+                    at_ms: 60_000,
+                    message: Message::Quit,
+                },
+            ],
+            ReplayPace::AsRecorded,
+        );
+        assert_eq!(replay.next_due(), Some(Message::NextTab));
+        assert_eq!(replay.next_due(), None);
+        assert!(replay.until_next().expect("pending") > Duration::from_secs(59));
+        assert!(!replay.is_finished());
+    }
+
+    #[test]
+    fn test_replay_reports_bad_line() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("session.jsonl");
+        fs::write(&path, "{\"at_ms\":0,\"message\":\"Quit\"}\n\nnot json\n").expect("write");
+
+        let err = Replay::open(&path, ReplayPace::Immediate)
+            .err()
+            .expect("bad line");
+        assert!(matches!(err, CoreError::RecordingInvalid { line: 3, .. }));
+    }
+}
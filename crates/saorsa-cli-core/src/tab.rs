@@ -3,8 +3,13 @@
 //! This module defines the [`Tab`] trait which all tab implementations
 //! must satisfy to be used in the saorsa TUI framework.
 
-use crate::event::Message;
+use crate::context::AppContext;
+use crate::error::CoreResult;
+use crate::event::{Message, Notification};
+use crate::pane::PaneLayout;
 use ratatui::prelude::*;
+use std::borrow::Cow;
+use std::time::Duration;
 
 /// Unique identifier for a tab.
 ///
@@ -12,6 +17,35 @@ use ratatui::prelude::*;
 /// used for lookup and management operations.
 pub type TabId = u32;
 
+/// Activity marker shown next to a tab's title in the tab bar.
+///
+/// Tabs raise a badge to signal that something happened while the
+/// user was looking elsewhere, such as a background job finishing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TabBadge {
+    /// Generic "something changed" dot.
+    Dot,
+    /// Number of unseen items.
+    Count(u32),
+    /// A background operation completed successfully.
+    Success,
+    /// A background operation failed.
+    Failure,
+}
+
+impl TabBadge {
+    /// Returns the short marker text rendered in the tab bar.
+    pub fn marker(&self) -> String {
+        match self {
+            TabBadge::Dot => "\u{25cf}".to_string(),
+            TabBadge::Count(n) if *n > 99 => "99+".to_string(),
+            TabBadge::Count(n) => n.to_string(),
+            TabBadge::Success => "\u{2713}".to_string(),
+            TabBadge::Failure => "\u{2717}".to_string(),
+        }
+    }
+}
+
 /// Trait that all tabs must implement.
 ///
 /// The `Tab` trait defines the interface for tab components in the
@@ -26,7 +60,7 @@ pub type TabId = u32;
 /// # Example
 ///
 /// ```ignore
-/// use saorsa_cli_core::{Tab, TabId};
+/// use saorsa_cli_core::{AppContext, CoreResult, Tab, TabId};
 /// use ratatui::prelude::*;
 ///
 /// struct MyTab {
@@ -38,10 +72,11 @@ pub type TabId = u32;
 /// impl Tab for MyTab {
 ///     fn id(&self) -> TabId { self.id }
 ///     fn title(&self) -> &str { &self.title }
-///     fn focus(&mut self) { self.focused = true; }
+///     fn focus(&mut self, _ctx: &AppContext) { self.focused = true; }
 ///     fn blur(&mut self) { self.focused = false; }
-///     fn view(&self, frame: &mut Frame, area: Rect) {
-///         // Render tab content
+///     fn view(&self, frame: &mut Frame, area: Rect, ctx: &AppContext) -> CoreResult<()> {
+///         // Render tab content with ctx.theme
+///         Ok(())
 ///     }
 /// }
 /// ```
@@ -58,6 +93,18 @@ pub trait Tab {
     /// that reference this tab.
     fn title(&self) -> &str;
 
+    /// Returns the title shown in the tab bar, which may reflect state.
+    ///
+    /// Override this to show computed titles such as an open file name or
+    /// an unsaved-changes marker; [`Tab::title`] stays the stable name.
+    ///
+    /// # Returns
+    ///
+    /// [`Tab::title`] by default.
+    fn display_title(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self.title())
+    }
+
     /// Returns an optional icon character for the tab bar.
     ///
     /// If provided, this icon is displayed alongside the tab title
@@ -70,6 +117,19 @@ pub trait Tab {
         None
     }
 
+    /// Returns a key identifying what this tab shows, if any.
+    ///
+    /// Two tabs with the same identity (for example the same canonical
+    /// directory) are duplicates; `TabManager::add_or_focus` focuses the
+    /// existing one instead of adding another.
+    ///
+    /// # Returns
+    ///
+    /// `None` by default, meaning the tab is never treated as a duplicate.
+    fn identity(&self) -> Option<String> {
+        None
+    }
+
     /// Returns whether this tab can be closed by the user.
     ///
     /// Some tabs (like a main dashboard) may be permanent and
@@ -82,19 +142,43 @@ pub trait Tab {
         true
     }
 
+    /// Returns whether the tab holds changes that closing it would lose.
+    ///
+    /// The coordinator asks for confirmation before closing a dirty tab.
+    ///
+    /// # Returns
+    ///
+    /// `false` by default; override for tabs with editable state.
+    fn is_dirty(&self) -> bool {
+        false
+    }
+
     /// Called when the tab receives focus.
     ///
     /// Use this method to update internal state, start animations,
     /// or begin any operations that should only occur when the tab
     /// is the active tab.
-    fn focus(&mut self);
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Shared state: the theme, the message bus and the
+    ///   application settings in [`AppContext::config`]
+    fn focus(&mut self, ctx: &AppContext);
 
     /// Called when the tab loses focus.
     ///
     /// Use this method to pause animations, release resources,
     /// or perform cleanup when the user switches away from this tab.
+    /// Blurring is temporary: the tab may be focused again later.
     fn blur(&mut self);
 
+    /// Called once when the tab is closed, just before it is dropped.
+    ///
+    /// Unlike [`Tab::blur`], closing is permanent. Use this method to stop
+    /// background work, players, and file watchers that would otherwise
+    /// outlive the tab.
+    fn on_close(&mut self) {}
+
     /// Renders the tab content to the given area.
     ///
     /// This method is called during the render phase of the TUI
@@ -105,7 +189,20 @@ pub trait Tab {
     ///
     /// * `frame` - The ratatui frame to render to
     /// * `area` - The rectangular area available for this tab content
-    fn view(&self, frame: &mut Frame, area: Rect);
+    /// * `ctx` - Shared state: the theme to draw with, the message bus and
+    ///   the application settings
+    ///
+    /// Draw with the semantic roles in [`ThemeColors`](crate::ThemeColors)
+    /// of `ctx.theme` rather than hardcoded colors, so theme changes apply
+    /// on the next frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CoreError::Render`](crate::CoreError::Render) when part of
+    /// the content could not be drawn, such as a file that fails to parse.
+    /// Draw what you can first; the coordinator shows the error text as a
+    /// notification.
+    fn view(&self, frame: &mut Frame, area: Rect, ctx: &AppContext) -> CoreResult<()>;
 
     /// Optional handler invoked when the active tab receives a message.
     ///
@@ -116,7 +213,115 @@ pub trait Tab {
     }
 
     /// Optional per-tick update invoked by the coordinator.
+    ///
+    /// Every tab is ticked, not only the active one, so background
+    /// work can finish and raise a [`badge`](Tab::badge) while the
+    /// tab is hidden.
     fn tick(&mut self) {}
+
+    /// Takes the next notification the tab wants shown, if any.
+    ///
+    /// The coordinator drains this after every [`tick`](Tab::tick), so
+    /// background work can report failures from any tab. Tabs may also
+    /// return [`Message::Notify`] from [`handle_message`](Tab::handle_message).
+    ///
+    /// # Returns
+    ///
+    /// `None` by default.
+    fn take_notification(&mut self) -> Option<Notification> {
+        None
+    }
+
+    /// Returns how soon the tab needs its next [`tick`](Tab::tick).
+    ///
+    /// The coordinator wakes at the shortest interval requested by any
+    /// tab, so override this while animating or waiting on a worker.
+    ///
+    /// # Returns
+    ///
+    /// `None` by default, meaning the tab is idle.
+    fn tick_interval(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Returns the activity badge to display next to the title.
+    ///
+    /// # Returns
+    ///
+    /// `None` by default; override to signal unseen activity.
+    fn badge(&self) -> Option<TabBadge> {
+        None
+    }
+
+    /// Clears the activity badge.
+    ///
+    /// Called by the coordinator whenever the tab becomes active,
+    /// since the user is now looking at it.
+    fn clear_badge(&mut self) {}
+
+    /// Describes background work that is currently in progress.
+    ///
+    /// The coordinator shows this next to an animated spinner in the
+    /// status bar, e.g. `"Scanning... 42%"`.
+    ///
+    /// # Returns
+    ///
+    /// `None` by default; override while a background task is running.
+    fn progress(&self) -> Option<String> {
+        None
+    }
+
+    /// Returns a short shortcut hint for the status bar.
+    ///
+    /// While this tab is active the coordinator shows the hint in the
+    /// status bar's right section, e.g. `"j/k:move  ?:keys"`.
+    ///
+    /// # Returns
+    ///
+    /// An empty string by default, which keeps the global hint.
+    fn help_hint(&self) -> &str {
+        ""
+    }
+
+    /// Returns context about the tab's content for the status bar.
+    ///
+    /// While this tab is active the coordinator shows it after the status
+    /// bar's center text, e.g. the Git branch as `"⎇ main ↑2↓0"`.
+    ///
+    /// # Returns
+    ///
+    /// `None` by default.
+    fn status_context(&self) -> Option<String> {
+        None
+    }
+
+    /// Returns the layout of the tab's panes, if it has several.
+    ///
+    /// The coordinator resolves [`Message::SwitchPane`] and
+    /// [`Message::FocusAdjacentPane`] against this layout and sends the
+    /// tab a [`Message::FocusPane`] for the match. Moving to an adjacent
+    /// pane starts from [`PaneLayout::focused`], so tabs should fill it in.
+    ///
+    /// # Returns
+    ///
+    /// `None` by default, in which case both messages are passed to
+    /// [`handle_message`](Tab::handle_message) unchanged.
+    fn pane_layout(&self) -> Option<PaneLayout> {
+        None
+    }
+
+    /// Returns the tab's key bindings as `(keys, description)` pairs.
+    ///
+    /// The coordinator lists these in its help overlay, which the tab
+    /// opens by returning [`Message::ToggleHelp`] from
+    /// [`handle_message`](Tab::handle_message).
+    ///
+    /// # Returns
+    ///
+    /// An empty list by default.
+    fn key_bindings(&self) -> Vec<(&str, &str)> {
+        Vec::new()
+    }
 }
 
 #[cfg(test)]
@@ -171,7 +376,7 @@ mod tests {
             self.closeable
         }
 
-        fn focus(&mut self) {
+        fn focus(&mut self, _ctx: &AppContext) {
             self.focused = true;
         }
 
@@ -179,8 +384,9 @@ mod tests {
             self.focused = false;
         }
 
-        fn view(&self, _frame: &mut Frame, _area: Rect) {
+        fn view(&self, _frame: &mut Frame, _area: Rect, _ctx: &AppContext) -> CoreResult<()> {
             // Test implementation does nothing
+            Ok(())
         }
     }
 
@@ -196,6 +402,12 @@ mod tests {
         assert_eq!(tab.title(), "My Tab");
     }
 
+    #[test]
+    fn test_tab_display_title_defaults_to_title() {
+        let tab = TestTab::new(1, "My Tab");
+        assert_eq!(tab.display_title(), "My Tab");
+    }
+
     #[test]
     fn test_tab_icon_default() {
         let tab = TestTab::new(1, "Test");
@@ -214,6 +426,36 @@ mod tests {
         assert!(tab.can_close());
     }
 
+    #[test]
+    fn test_tab_tick_interval_default() {
+        let tab = TestTab::new(1, "Test");
+        assert!(tab.tick_interval().is_none());
+    }
+
+    #[test]
+    fn test_tab_status_context_default() {
+        let tab = TestTab::new(1, "Test");
+        assert!(tab.status_context().is_none());
+    }
+
+    #[test]
+    fn test_tab_pane_layout_default() {
+        let tab = TestTab::new(1, "Test");
+        assert!(tab.pane_layout().is_none());
+    }
+
+    #[test]
+    fn test_tab_take_notification_default() {
+        let mut tab = TestTab::new(1, "Test");
+        assert!(tab.take_notification().is_none());
+    }
+
+    #[test]
+    fn test_tab_is_dirty_default() {
+        let tab = TestTab::new(1, "Test");
+        assert!(!tab.is_dirty());
+    }
+
     #[test]
     fn test_tab_can_close_false() {
         let tab = TestTab::new(1, "Test").uncloseable();
@@ -225,13 +467,49 @@ mod tests {
         let mut tab = TestTab::new(1, "Test");
         assert!(!tab.focused);
 
-        tab.focus();
+        tab.focus(&AppContext::default());
         assert!(tab.focused);
 
         tab.blur();
         assert!(!tab.focused);
     }
 
+    #[test]
+    fn test_tab_badge_default() {
+        let mut tab = TestTab::new(1, "Test");
+        assert!(tab.badge().is_none());
+        tab.clear_badge();
+        assert!(tab.badge().is_none());
+    }
+
+    #[test]
+    fn test_tab_help_defaults() {
+        let tab = TestTab::new(1, "Test");
+        assert_eq!(tab.help_hint(), "");
+        assert!(tab.key_bindings().is_empty());
+    }
+
+    #[test]
+    fn test_tab_identity_default() {
+        let tab = TestTab::new(1, "Test");
+        assert_eq!(tab.identity(), None);
+    }
+
+    #[test]
+    fn test_tab_progress_default() {
+        let tab = TestTab::new(1, "Test");
+        assert!(tab.progress().is_none());
+    }
+
+    #[test]
+    fn test_tab_badge_marker() {
+        assert_eq!(TabBadge::Dot.marker(), "\u{25cf}");
+        assert_eq!(TabBadge::Count(3).marker(), "3");
+        assert_eq!(TabBadge::Count(250).marker(), "99+");
+        assert_eq!(TabBadge::Success.marker(), "\u{2713}");
+        assert_eq!(TabBadge::Failure.marker(), "\u{2717}");
+    }
+
     #[test]
     fn test_tab_trait_is_object_safe() {
         // This test verifies Tab can be used as a trait object
@@ -2,8 +2,17 @@
 //!
 //! Themes define colors, borders, and styling for the entire application.
 //! They can be loaded from TOML files or constructed programmatically.
+//!
+//! Terminals without truecolor support can use [`Theme::quantize_to_256`],
+//! which maps RGB colors to the nearest ANSI-256 palette entry. Use
+//! [`ColorDepth::detect`] to find out what the terminal supports.
+//!
+//! Setting `NO_COLOR` selects [`ColorDepth::Monochrome`], drawn with
+//! [`Theme::monochrome`] and [`to_monochrome`]; `SAORSA_FORCE_COLOR`
+//! overrides both ways.
 
-use ratatui::style::Color;
+use ratatui::buffer::Buffer;
+use ratatui::style::{Color, Modifier};
 use serde::{Deserialize, Serialize};
 
 /// Complete theme definition
@@ -18,6 +27,12 @@ pub struct Theme {
 }
 
 /// Color palette for the theme
+///
+/// Besides the base colors, the palette names semantic roles (error,
+/// warning, success, info, selection, borders) so tabs and plugins can
+/// color their content consistently instead of hardcoding colors.
+/// Roles added after the original palette default to the dark theme's
+/// values when missing from a TOML file.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ThemeColors {
     /// Main background color
@@ -44,6 +59,44 @@ pub struct ThemeColors {
     /// Muted/secondary text
     #[serde(with = "color_serde")]
     pub muted: Color,
+    /// Informational indicators (renames, hints, links)
+    #[serde(with = "color_serde", default = "role_defaults::info")]
+    pub info: Color,
+    /// Borders of unfocused panels
+    #[serde(with = "color_serde", default = "role_defaults::border")]
+    pub border: Color,
+    /// Border of the focused panel
+    #[serde(with = "color_serde", default = "role_defaults::border_focused")]
+    pub border_focused: Color,
+}
+
+impl ThemeColors {
+    /// Returns the border color for a panel with the given focus
+    #[must_use]
+    pub fn border_for(&self, focused: bool) -> Color {
+        if focused {
+            self.border_focused
+        } else {
+            self.border
+        }
+    }
+}
+
+/// Defaults for roles missing from older theme files
+mod role_defaults {
+    use ratatui::style::Color;
+
+    pub fn info() -> Color {
+        Color::Rgb(137, 220, 235) // Catppuccin sky
+    }
+
+    pub fn border() -> Color {
+        Color::Rgb(69, 71, 90) // Catppuccin surface1
+    }
+
+    pub fn border_focused() -> Color {
+        Color::Rgb(180, 190, 254) // Catppuccin lavender
+    }
 }
 
 /// Border style for panels and widgets
@@ -61,6 +114,143 @@ pub enum BorderStyle {
     None,
 }
 
+/// Color capability of the terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorDepth {
+    /// 24-bit RGB colors are rendered as-is
+    #[default]
+    TrueColor,
+    /// Only the 256-color palette is available
+    Ansi256,
+    /// No colors at all, as asked for with `NO_COLOR`
+    Monochrome,
+}
+
+impl ColorDepth {
+    /// Detects the color depth from the `COLORTERM` and `TERM` variables
+    ///
+    /// Terminals advertise truecolor with `COLORTERM=truecolor` (or
+    /// `24bit`), or with a `-direct` terminfo entry such as
+    /// `xterm-direct`. Anything else is treated as 256-color. `NO_COLOR`
+    /// and `SAORSA_FORCE_COLOR` are applied on top, see
+    /// [`ColorDepth::with_overrides`].
+    #[must_use]
+    pub fn detect() -> Self {
+        let colorterm = std::env::var("COLORTERM").ok();
+        let term = std::env::var("TERM").ok();
+        let no_color = std::env::var("NO_COLOR").ok();
+        let force_color = std::env::var("SAORSA_FORCE_COLOR").ok();
+        Self::from_env(colorterm.as_deref(), term.as_deref())
+            .with_overrides(no_color.as_deref(), force_color.as_deref())
+    }
+
+    /// Applies `NO_COLOR` and `SAORSA_FORCE_COLOR` values to a detected depth
+    ///
+    /// A non-empty `NO_COLOR` turns colors off, as <https://no-color.org>
+    /// asks. `SAORSA_FORCE_COLOR` wins over it: `0`, `false`, `no` or
+    /// `never` turn colors off, `256` and `truecolor` (or `24bit`) pick a
+    /// depth, and any other non-empty value keeps the detected one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use saorsa_cli_core::ColorDepth;
+    ///
+    /// let depth = ColorDepth::TrueColor;
+    /// assert_eq!(depth.with_overrides(Some("1"), None), ColorDepth::Monochrome);
+    /// assert_eq!(depth.with_overrides(Some("1"), Some("1")), depth);
+    /// assert_eq!(depth.with_overrides(None, Some("256")), ColorDepth::Ansi256);
+    /// ```
+    #[must_use]
+    pub fn with_overrides(self, no_color: Option<&str>, force_color: Option<&str>) -> Self {
+        let force_color = force_color.unwrap_or_default().trim().to_ascii_lowercase();
+        match force_color.as_str() {
+            "0" | "false" | "no" | "never" => return ColorDepth::Monochrome,
+            "256" => return ColorDepth::Ansi256,
+            "truecolor" | "24bit" => return ColorDepth::TrueColor,
+            "" => {}
+            _ => return self,
+        }
+        if no_color.is_some_and(|value| !value.is_empty()) {
+            return ColorDepth::Monochrome;
+        }
+        self
+    }
+
+    /// Determines the color depth from `COLORTERM` and `TERM` values
+    #[must_use]
+    pub fn from_env(colorterm: Option<&str>, term: Option<&str>) -> Self {
+        let colorterm = colorterm.unwrap_or_default().to_ascii_lowercase();
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            return ColorDepth::TrueColor;
+        }
+        let term = term.unwrap_or_default().to_ascii_lowercase();
+        if term.ends_with("-direct") || term.contains("truecolor") || term.contains("24bit") {
+            return ColorDepth::TrueColor;
+        }
+        ColorDepth::Ansi256
+    }
+}
+
+/// Channel levels of the 6x6x6 color cube in the ANSI-256 palette
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Returns the RGB value of an ANSI-256 palette entry in `16..=255`
+fn ansi256_rgb(index: u8) -> (u8, u8, u8) {
+    if index >= 232 {
+        let level = 8 + (index - 232) * 10;
+        return (level, level, level);
+    }
+    let cube = index - 16;
+    (
+        CUBE_LEVELS[(cube / 36) as usize],
+        CUBE_LEVELS[(cube / 6 % 6) as usize],
+        CUBE_LEVELS[(cube % 6) as usize],
+    )
+}
+
+/// Finds the ANSI-256 palette entry closest to an RGB color
+///
+/// Searches the color cube and grayscale ramp (indices 16-255) by
+/// squared distance, weighting channels by their perceived brightness.
+/// The first 16 entries are skipped because terminals customise them.
+#[must_use]
+pub fn nearest_ansi256(r: u8, g: u8, b: u8) -> u8 {
+    let distance = |index: u8| {
+        let (pr, pg, pb) = ansi256_rgb(index);
+        let dr = i32::from(r) - i32::from(pr);
+        let dg = i32::from(g) - i32::from(pg);
+        let db = i32::from(b) - i32::from(pb);
+        3 * dr * dr + 4 * dg * dg + 2 * db * db
+    };
+    (16..=255u8)
+        .min_by_key(|&index| distance(index))
+        .unwrap_or(16)
+}
+
+/// Strips every color from `buf`, keeping highlights visible
+///
+/// Cells that had a background color are drawn reversed instead, so
+/// selections and the status bar still stand out. Bold, underline and the
+/// other modifiers are left alone.
+pub fn to_monochrome(buf: &mut Buffer) {
+    for cell in &mut buf.content {
+        if cell.bg != Color::Reset {
+            cell.modifier.insert(Modifier::REVERSED);
+        }
+        cell.fg = Color::Reset;
+        cell.bg = Color::Reset;
+    }
+}
+
+/// Maps an RGB color to the 256-color palette, leaving other colors alone
+fn quantize_color(color: Color) -> Color {
+    match color {
+        Color::Rgb(r, g, b) => Color::Indexed(nearest_ansi256(r, g, b)),
+        other => other,
+    }
+}
+
 /// Custom serde module for ratatui Color
 mod color_serde {
     use ratatui::style::Color;
@@ -160,6 +350,9 @@ impl Theme {
                 warning: Color::Rgb(249, 226, 175),    // Catppuccin yellow
                 success: Color::Rgb(166, 227, 161),    // Catppuccin green
                 muted: Color::Rgb(147, 153, 178),      // Catppuccin subtext1
+                info: role_defaults::info(),
+                border: role_defaults::border(),
+                border_focused: role_defaults::border_focused(),
             },
             borders: BorderStyle::Rounded,
         }
@@ -178,6 +371,9 @@ impl Theme {
                 warning: Color::Rgb(223, 142, 29),     // Catppuccin latte yellow
                 success: Color::Rgb(64, 160, 43),      // Catppuccin latte green
                 muted: Color::Rgb(108, 111, 133),      // Catppuccin latte subtext1
+                info: Color::Rgb(4, 165, 229),         // Catppuccin latte sky
+                border: Color::Rgb(172, 176, 190),     // Catppuccin latte surface1
+                border_focused: Color::Rgb(114, 135, 253), // Catppuccin latte lavender
             },
             borders: BorderStyle::Rounded,
         }
@@ -188,19 +384,68 @@ impl Theme {
         Theme {
             name: "Nord".to_string(),
             colors: ThemeColors {
-                background: Color::Rgb(46, 52, 64),    // Nord polar night
-                foreground: Color::Rgb(236, 239, 244), // Nord snow storm
-                accent: Color::Rgb(136, 192, 208),     // Nord frost
-                selection: Color::Rgb(67, 76, 94),     // Nord polar night lighter
-                error: Color::Rgb(191, 97, 106),       // Nord aurora red
-                warning: Color::Rgb(235, 203, 139),    // Nord aurora yellow
-                success: Color::Rgb(163, 190, 140),    // Nord aurora green
-                muted: Color::Rgb(76, 86, 106),        // Nord polar night lightest
+                background: Color::Rgb(46, 52, 64),        // Nord polar night
+                foreground: Color::Rgb(236, 239, 244),     // Nord snow storm
+                accent: Color::Rgb(136, 192, 208),         // Nord frost
+                selection: Color::Rgb(67, 76, 94),         // Nord polar night lighter
+                error: Color::Rgb(191, 97, 106),           // Nord aurora red
+                warning: Color::Rgb(235, 203, 139),        // Nord aurora yellow
+                success: Color::Rgb(163, 190, 140),        // Nord aurora green
+                muted: Color::Rgb(76, 86, 106),            // Nord polar night lightest
+                info: Color::Rgb(129, 161, 193),           // Nord frost blue
+                border: Color::Rgb(76, 86, 106),           // Nord polar night lightest
+                border_focused: Color::Rgb(136, 192, 208), // Nord frost
             },
             borders: BorderStyle::Rounded,
         }
     }
 
+    /// Creates a theme without colors, for `NO_COLOR`
+    ///
+    /// Everything uses the terminal's own colors. Roles that must stand out,
+    /// the accent, selection and focused border, are plain white, and borders
+    /// are square so they draw with the most basic box characters.
+    pub fn monochrome() -> Self {
+        Theme {
+            name: "Monochrome".to_string(),
+            colors: ThemeColors {
+                background: Color::Reset,
+                foreground: Color::Reset,
+                accent: Color::White,
+                selection: Color::White,
+                error: Color::Reset,
+                warning: Color::Reset,
+                success: Color::Reset,
+                muted: Color::Reset,
+                info: Color::Reset,
+                border: Color::Reset,
+                border_focused: Color::White,
+            },
+            borders: BorderStyle::Square,
+        }
+    }
+
+    /// Returns the light theme for the dark one, and the dark theme otherwise
+    ///
+    /// Used to cycle between the two at runtime.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use saorsa_cli_core::Theme;
+    ///
+    /// assert_eq!(Theme::dark().toggled().name, "Light");
+    /// assert_eq!(Theme::light().toggled().name, "Dark");
+    /// ```
+    #[must_use]
+    pub fn toggled(&self) -> Theme {
+        if self.name == Theme::dark().name {
+            Theme::light()
+        } else {
+            Theme::dark()
+        }
+    }
+
     /// Parses a theme from TOML string
     ///
     /// # Errors
@@ -218,6 +463,51 @@ impl Theme {
     pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
         toml::to_string_pretty(self)
     }
+
+    /// Returns a copy with every RGB color mapped to the nearest ANSI-256 entry
+    ///
+    /// Named and indexed colors are kept as they are.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::style::Color;
+    /// use saorsa_cli_core::Theme;
+    ///
+    /// let theme = Theme::dark().quantize_to_256();
+    /// assert!(matches!(theme.colors.background, Color::Indexed(_)));
+    /// ```
+    #[must_use]
+    pub fn quantize_to_256(&self) -> Theme {
+        let c = &self.colors;
+        Theme {
+            name: self.name.clone(),
+            colors: ThemeColors {
+                background: quantize_color(c.background),
+                foreground: quantize_color(c.foreground),
+                accent: quantize_color(c.accent),
+                selection: quantize_color(c.selection),
+                error: quantize_color(c.error),
+                warning: quantize_color(c.warning),
+                success: quantize_color(c.success),
+                muted: quantize_color(c.muted),
+                info: quantize_color(c.info),
+                border: quantize_color(c.border),
+                border_focused: quantize_color(c.border_focused),
+            },
+            borders: self.borders,
+        }
+    }
+
+    /// Returns the theme adapted to the given terminal color depth
+    #[must_use]
+    pub fn for_color_depth(&self, depth: ColorDepth) -> Theme {
+        match depth {
+            ColorDepth::TrueColor => self.clone(),
+            ColorDepth::Ansi256 => self.quantize_to_256(),
+            ColorDepth::Monochrome => Theme::monochrome(),
+        }
+    }
 }
 
 impl Default for Theme {
@@ -262,6 +552,13 @@ mod tests {
         assert_eq!(BorderStyle::default(), BorderStyle::Rounded);
     }
 
+    #[test]
+    fn test_theme_toggled() {
+        assert_eq!(Theme::dark().toggled(), Theme::light());
+        assert_eq!(Theme::light().toggled(), Theme::dark());
+        assert_eq!(Theme::nord().toggled(), Theme::dark());
+    }
+
     #[test]
     fn test_theme_default_is_dark() {
         let theme = Theme::default();
@@ -362,6 +659,163 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_nearest_ansi256_known_colors() {
+        assert_eq!(nearest_ansi256(0, 0, 0), 16);
+        assert_eq!(nearest_ansi256(255, 255, 255), 231);
+        assert_eq!(nearest_ansi256(255, 0, 0), 196);
+        assert_eq!(nearest_ansi256(0, 255, 0), 46);
+        assert_eq!(nearest_ansi256(0, 0, 255), 21);
+        assert_eq!(nearest_ansi256(95, 135, 175), 67);
+        assert_eq!(nearest_ansi256(128, 128, 128), 244);
+        // Near-black grays land on the grayscale ramp, not the cube
+        assert_eq!(nearest_ansi256(30, 30, 30), 234);
+    }
+
+    #[test]
+    fn test_ansi256_palette_roundtrip() {
+        for index in 16..=255u8 {
+            let (r, g, b) = ansi256_rgb(index);
+            assert_eq!(ansi256_rgb(nearest_ansi256(r, g, b)), (r, g, b));
+        }
+    }
+
+    #[test]
+    fn test_quantize_to_256() {
+        let mut theme = Theme::dark();
+        theme.colors.muted = Color::DarkGray;
+        theme.colors.error = Color::Indexed(9);
+
+        let quantized = theme.quantize_to_256();
+        assert_eq!(quantized.name, "Dark");
+        assert_eq!(quantized.colors.background, Color::Indexed(235));
+        assert_eq!(quantized.colors.muted, Color::DarkGray);
+        assert_eq!(quantized.colors.error, Color::Indexed(9));
+        assert!(!matches!(quantized.colors.accent, Color::Rgb(..)));
+    }
+
+    #[test]
+    fn test_color_depth_from_env() {
+        assert_eq!(
+            ColorDepth::from_env(Some("truecolor"), Some("xterm-256color")),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(
+            ColorDepth::from_env(Some("24bit"), None),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(
+            ColorDepth::from_env(None, Some("xterm-direct")),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(
+            ColorDepth::from_env(None, Some("xterm-256color")),
+            ColorDepth::Ansi256
+        );
+        assert_eq!(ColorDepth::from_env(None, None), ColorDepth::Ansi256);
+    }
+
+    #[test]
+    fn test_for_color_depth() {
+        let theme = Theme::nord();
+        assert_eq!(theme.for_color_depth(ColorDepth::TrueColor), theme);
+        assert_eq!(
+            theme.for_color_depth(ColorDepth::Ansi256),
+            theme.quantize_to_256()
+        );
+    }
+
+    #[test]
+    fn test_no_color_and_force_color_overrides() {
+        let detected = ColorDepth::Ansi256;
+        assert_eq!(detected.with_overrides(None, None), detected);
+        assert_eq!(detected.with_overrides(Some(""), None), detected);
+        assert_eq!(
+            detected.with_overrides(Some("1"), None),
+            ColorDepth::Monochrome
+        );
+        assert_eq!(detected.with_overrides(Some("1"), Some("yes")), detected);
+        assert_eq!(
+            detected.with_overrides(Some("1"), Some("truecolor")),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(
+            ColorDepth::TrueColor.with_overrides(None, Some("never")),
+            ColorDepth::Monochrome
+        );
+        assert_eq!(
+            ColorDepth::Monochrome.with_overrides(None, Some("256")),
+            ColorDepth::Ansi256
+        );
+    }
+
+    #[test]
+    fn test_monochrome_theme_and_buffer() {
+        let theme = Theme::nord().for_color_depth(ColorDepth::Monochrome);
+        assert_eq!(theme, Theme::monochrome());
+        assert_eq!(theme.colors.background, Color::Reset);
+
+        let mut buf = Buffer::empty(ratatui::layout::Rect::new(0, 0, 2, 1));
+        buf[(0, 0)].set_fg(Color::Red).set_bg(Color::Blue);
+        buf[(1, 0)]
+            .set_fg(Color::Green)
+            .set_style(ratatui::style::Style::new().add_modifier(Modifier::BOLD));
+        to_monochrome(&mut buf);
+        assert_eq!(buf[(0, 0)].fg, Color::Reset);
+        assert_eq!(buf[(0, 0)].bg, Color::Reset);
+        assert!(buf[(0, 0)].modifier.contains(Modifier::REVERSED));
+        assert_eq!(buf[(1, 0)].fg, Color::Reset);
+        assert_eq!(buf[(1, 0)].modifier, Modifier::BOLD);
+    }
+
+    #[test]
+    fn test_missing_roles_use_defaults() {
+        let toml_str = r##"
+            name = "Legacy"
+            borders = "rounded"
+
+            [colors]
+            background = "black"
+            foreground = "white"
+            accent = "blue"
+            selection = "gray"
+            error = "red"
+            warning = "yellow"
+            success = "green"
+            muted = "darkgray"
+        "##;
+        let theme = Theme::from_toml(toml_str).expect("should parse without new roles");
+        let dark = Theme::dark();
+        assert_eq!(theme.colors.info, dark.colors.info);
+        assert_eq!(theme.colors.border, dark.colors.border);
+        assert_eq!(theme.colors.border_focused, dark.colors.border_focused);
+    }
+
+    #[test]
+    fn test_role_colors_parse() {
+        let toml_str = r##"
+            name = "Roles"
+            borders = "rounded"
+
+            [colors]
+            background = "black"
+            foreground = "white"
+            accent = "blue"
+            selection = "gray"
+            error = "red"
+            warning = "yellow"
+            success = "green"
+            muted = "darkgray"
+            info = "cyan"
+            border = "indexed:8"
+            border_focused = "#ffffff"
+        "##;
+        let theme = Theme::from_toml(toml_str).expect("should parse role colors");
+        assert_eq!(theme.colors.info, Color::Cyan);
+        assert_eq!(theme.colors.border_for(false), Color::Indexed(8));
+        assert_eq!(theme.colors.border_for(true), Color::Rgb(255, 255, 255));
+    }
+
     #[test]
     fn test_light_theme_roundtrip() {
         let original = Theme::light();
@@ -0,0 +1,97 @@
+//! Age buckets for recency coloring
+//!
+//! The Disk tab can tint entries by how long ago they were last modified.
+//! [`AgeBucket::of`] sorts a modification time into a few coarse buckets,
+//! each with a color on a green (recent) to red (old) scale.
+
+use ratatui::style::Color;
+use std::time::{Duration, SystemTime};
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// How long ago an entry was last modified
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AgeBucket {
+    /// Within the last day
+    Day,
+    /// Within the last week
+    Week,
+    /// Within the last 30 days
+    Month,
+    /// Within the last year
+    Year,
+    /// More than a year ago
+    Older,
+}
+
+impl AgeBucket {
+    /// Every bucket, most recent first
+    pub const ALL: [AgeBucket; 5] = [
+        AgeBucket::Day,
+        AgeBucket::Week,
+        AgeBucket::Month,
+        AgeBucket::Year,
+        AgeBucket::Older,
+    ];
+
+    /// Bucket for an entry modified at `modified`, as seen at `now`
+    ///
+    /// Times in the future count as recent.
+    #[must_use]
+    pub fn of(modified: SystemTime, now: SystemTime) -> Self {
+        let age = now.duration_since(modified).unwrap_or_default();
+        if age < DAY {
+            AgeBucket::Day
+        } else if age < DAY * 7 {
+            AgeBucket::Week
+        } else if age < DAY * 30 {
+            AgeBucket::Month
+        } else if age < DAY * 365 {
+            AgeBucket::Year
+        } else {
+            AgeBucket::Older
+        }
+    }
+
+    /// Short label for the legend, e.g. `<1w`
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            AgeBucket::Day => "<1d",
+            AgeBucket::Week => "<1w",
+            AgeBucket::Month => "<1mo",
+            AgeBucket::Year => "<1y",
+            AgeBucket::Older => "older",
+        }
+    }
+
+    /// Color on the green-to-red scale
+    #[must_use]
+    pub fn color(self) -> Color {
+        match self {
+            AgeBucket::Day => Color::Rgb(0x4c, 0xaf, 0x50),
+            AgeBucket::Week => Color::Rgb(0x9c, 0xcc, 0x65),
+            AgeBucket::Month => Color::Rgb(0xfd, 0xd8, 0x35),
+            AgeBucket::Year => Color::Rgb(0xfb, 0x8c, 0x00),
+            AgeBucket::Older => Color::Rgb(0xe5, 0x39, 0x35),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_age_buckets() {
+        let now = SystemTime::now();
+        let ago = |days: u64| now - DAY * days as u32;
+        assert_eq!(AgeBucket::of(now, now), AgeBucket::Day);
+        assert_eq!(AgeBucket::of(now + DAY, now), AgeBucket::Day);
+        assert_eq!(AgeBucket::of(ago(3), now), AgeBucket::Week);
+        assert_eq!(AgeBucket::of(ago(7), now), AgeBucket::Month);
+        assert_eq!(AgeBucket::of(ago(200), now), AgeBucket::Year);
+        assert_eq!(AgeBucket::of(ago(365), now), AgeBucket::Older);
+        assert!(AgeBucket::ALL.windows(2).all(|w| w[0] < w[1]));
+    }
+}
@@ -2,13 +2,34 @@
 //!
 //! Provides utilities for analyzing disk usage, finding large files,
 //! and identifying stale files.
+//!
+//! Directory traversal runs on a work-stealing thread pool: each directory
+//! scans its subdirectories in parallel. Symbolic links are never followed,
+//! directories already visited (by device and inode) are skipped, and files
+//! with several hard links are counted once, under their first path in
+//! sorted order, so results do not depend on scheduling.
+//!
+//! Entries matched by `.gitignore` files are skipped unless disabled with
+//! [`DiskAnalyzer::with_gitignore`]; extra gitignore-style patterns can be
+//! added with [`DiskAnalyzer::with_ignore_patterns`].
+//!
+//! [`DiskAnalyzer::delete`] removes an entry below the root, or moves it to
+//! the OS trash, and [`DirNode::remove`] updates a scanned tree to match
+//! without another traversal.
 
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, Metadata};
+use std::io;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use std::time::SystemTime;
 
-use humansize::{format_size, BINARY};
+use rayon::prelude::*;
+use saorsa_cli_core::IgnoreRules;
 use sysinfo::Disks;
-use walkdir::WalkDir;
+
+use crate::error::{DiskError, DiskResult};
+use crate::size::{format_count, format_size, format_size_in, SizeUnits};
 
 /// A file entry with size and metadata
 #[derive(Debug, Clone)]
@@ -27,7 +48,13 @@ impl FileEntry {
     /// Format the file size as a human-readable string
     #[must_use]
     pub fn format_size(&self) -> String {
-        format_size(self.size, BINARY)
+        format_size(self.size)
+    }
+
+    /// Format the file size with the given unit system
+    #[must_use]
+    pub fn format_size_in(&self, units: SizeUnits) -> String {
+        format_size_in(self.size, units)
     }
 
     /// Get the filename (last component of path)
@@ -68,7 +95,7 @@ impl DiskInfo {
     /// Format bytes as human-readable string
     #[must_use]
     pub fn format_bytes(bytes: u64) -> String {
-        format_size(bytes, BINARY)
+        format_size(bytes)
     }
 
     /// Get a short display name for the disk
@@ -82,16 +109,191 @@ impl DiskInfo {
     }
 }
 
+/// A directory with the aggregated size of everything below it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirNode {
+    /// Path to the directory
+    pub path: PathBuf,
+    /// Total size in bytes of all files below this directory
+    pub size: u64,
+    /// Number of files below this directory
+    pub file_count: usize,
+    /// Number of directories below this directory, not counting itself
+    pub dir_count: usize,
+    /// Last modified time of the most recently modified file below this
+    /// directory, if it holds any
+    pub modified: Option<SystemTime>,
+    /// Subdirectories, largest first (ties broken by path)
+    pub children: Vec<DirNode>,
+}
+
+impl DirNode {
+    /// Format the file and directory counts, e.g. `4,301 files · 12 dirs`
+    #[must_use]
+    pub fn format_counts(&self) -> String {
+        let plural = |count: usize, one: &str, many: &str| {
+            format!(
+                "{} {}",
+                format_count(count),
+                if count == 1 { one } else { many }
+            )
+        };
+        format!(
+            "{} · {}",
+            plural(self.file_count, "file", "files"),
+            plural(self.dir_count, "dir", "dirs")
+        )
+    }
+
+    /// Get the directory name (last component of path)
+    #[must_use]
+    pub fn filename(&self) -> &str {
+        self.path
+            .file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or("(unknown)")
+    }
+
+    /// Account for `path` having been deleted
+    ///
+    /// A directory in the tree is dropped along with its totals; any other
+    /// path is taken to be a file holding `reclaimed`. Every directory
+    /// above it shrinks accordingly and is re-sorted among its siblings.
+    /// `modified` is left as is unless a directory is left without files.
+    ///
+    /// # Returns
+    ///
+    /// Whether `path` was below this directory.
+    pub fn remove(&mut self, path: &Path, reclaimed: Reclaimed) -> bool {
+        if path == self.path || !path.starts_with(&self.path) {
+            return false;
+        }
+
+        let (size, file_count, dir_count) = if let Some(i) =
+            self.children.iter().position(|c| c.path == path)
+        {
+            let removed = self.children.remove(i);
+            (removed.size, removed.file_count, removed.dir_count + 1)
+        } else if let Some(child) = self.children.iter_mut().find(|c| path.starts_with(&c.path)) {
+            let before = (child.size, child.file_count, child.dir_count);
+            child.remove(path, reclaimed);
+            (
+                before.0 - child.size,
+                before.1 - child.file_count,
+                before.2 - child.dir_count,
+            )
+        } else {
+            (reclaimed.size, reclaimed.file_count, 0)
+        };
+
+        self.size = self.size.saturating_sub(size);
+        self.file_count = self.file_count.saturating_sub(file_count);
+        self.dir_count = self.dir_count.saturating_sub(dir_count);
+        if self.file_count == 0 {
+            self.modified = None;
+        }
+        self.children
+            .sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
+        true
+    }
+}
+
+/// Space freed by [`DiskAnalyzer::delete`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Reclaimed {
+    /// Total size in bytes of the files removed
+    pub size: u64,
+    /// Number of files removed
+    pub file_count: usize,
+}
+
+/// Device and inode pair identifying a file or directory
+type InodeKey = (u64, u64);
+
+/// Size, file count and newest modified time of the files below a directory
+type Totals = (u64, usize, Option<SystemTime>);
+
+/// A regular file found during traversal
+struct ScannedFile {
+    entry: FileEntry,
+    /// Set for files with more than one hard link
+    link: Option<InodeKey>,
+}
+
+/// Files and directories collected by one traversal
+#[derive(Default)]
+struct Scan {
+    files: Vec<ScannedFile>,
+    dirs: Vec<PathBuf>,
+}
+
+impl Scan {
+    fn merge(mut self, other: Scan) -> Scan {
+        self.files.extend(other.files);
+        self.dirs.extend(other.dirs);
+        self
+    }
+}
+
 /// Disk analyzer for scanning and analyzing files
 pub struct DiskAnalyzer {
     root: PathBuf,
+    threads: usize,
+    respect_gitignore: bool,
+    ignore_patterns: Vec<String>,
 }
 
 impl DiskAnalyzer {
     /// Create a new disk analyzer for the given root path
+    ///
+    /// Scans use one thread per available CPU; see [`DiskAnalyzer::with_threads`].
     #[must_use]
     pub fn new(root: impl Into<PathBuf>) -> Self {
-        Self { root: root.into() }
+        Self {
+            root: root.into(),
+            threads: default_threads(),
+            respect_gitignore: true,
+            ignore_patterns: Vec::new(),
+        }
+    }
+
+    /// Set the number of scanning threads (0 means one per CPU)
+    #[must_use]
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = if threads == 0 {
+            default_threads()
+        } else {
+            threads
+        };
+        self
+    }
+
+    /// Set whether `.gitignore` files are respected (on by default)
+    ///
+    /// Turn this off to include ignored build artifacts such as `target/`.
+    #[must_use]
+    pub fn with_gitignore(mut self, respect: bool) -> Self {
+        self.respect_gitignore = respect;
+        self
+    }
+
+    /// Skip entries matching extra gitignore-style patterns
+    ///
+    /// These apply even when `.gitignore` files are not respected.
+    #[must_use]
+    pub fn with_ignore_patterns<I, S>(mut self, patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.ignore_patterns = patterns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Number of threads used for scanning
+    #[must_use]
+    pub fn threads(&self) -> usize {
+        self.threads
     }
 
     /// Get the root path being analyzed
@@ -127,22 +329,8 @@ impl DiskAnalyzer {
     /// A vector of `FileEntry` sorted by size (largest first)
     #[must_use]
     pub fn find_largest(&self, count: usize) -> Vec<FileEntry> {
-        let mut entries = Vec::new();
-
-        for entry in WalkDir::new(&self.root).into_iter().filter_map(Result::ok) {
-            if entry.file_type().is_file() {
-                if let Ok(meta) = entry.metadata() {
-                    entries.push(FileEntry {
-                        path: entry.path().to_path_buf(),
-                        size: meta.len(),
-                        modified: meta.modified().ok(),
-                        accessed: meta.accessed().ok(),
-                    });
-                }
-            }
-        }
-
-        entries.sort_by(|a, b| b.size.cmp(&a.size));
+        let mut entries = self.files();
+        entries.sort_by_key(|e| std::cmp::Reverse(e.size));
         entries.truncate(count);
         entries
     }
@@ -163,26 +351,13 @@ impl DiskAnalyzer {
             .checked_sub(std::time::Duration::from_secs(days * 24 * 60 * 60))
             .unwrap_or(SystemTime::UNIX_EPOCH);
 
-        let mut entries = Vec::new();
-
-        for entry in WalkDir::new(&self.root).into_iter().filter_map(Result::ok) {
-            if entry.file_type().is_file() {
-                if let Ok(meta) = entry.metadata() {
-                    if let Ok(accessed) = meta.accessed() {
-                        if accessed < cutoff {
-                            entries.push(FileEntry {
-                                path: entry.path().to_path_buf(),
-                                size: meta.len(),
-                                modified: meta.modified().ok(),
-                                accessed: Some(accessed),
-                            });
-                        }
-                    }
-                }
-            }
-        }
+        let mut entries: Vec<FileEntry> = self
+            .files()
+            .into_iter()
+            .filter(|e| e.accessed.is_some_and(|accessed| accessed < cutoff))
+            .collect();
 
-        entries.sort_by(|a, b| a.accessed.cmp(&b.accessed));
+        entries.sort_by_key(|e| e.accessed);
         entries.truncate(count);
         entries
     }
@@ -194,22 +369,291 @@ impl DiskAnalyzer {
     /// A tuple of (file_count, total_size_bytes)
     #[must_use]
     pub fn count_files(&self) -> (usize, u64) {
-        let mut count = 0usize;
-        let mut total_size = 0u64;
-
-        for entry in WalkDir::new(&self.root).into_iter().filter_map(Result::ok) {
-            if entry.file_type().is_file() {
-                if let Ok(meta) = entry.metadata() {
-                    count += 1;
-                    total_size += meta.len();
+        let files = self.files();
+        let total_size = files.iter().map(|e| e.size).sum();
+        (files.len(), total_size)
+    }
+
+    /// Build the directory tree below the root with aggregated sizes
+    ///
+    /// Each node's size and counts cover everything beneath it, tallied
+    /// from the same traversal. Children are sorted
+    /// largest first, with ties broken by path, so the tree is the same
+    /// regardless of thread count.
+    #[must_use]
+    pub fn scan_tree(&self) -> DirNode {
+        let scan = self.scan();
+
+        // Charge every file to each of its ancestors up to the root
+        let mut totals: HashMap<&Path, Totals> = HashMap::new();
+        for file in &scan.files {
+            for dir in file.entry.path.ancestors().skip(1) {
+                let total = totals.entry(dir).or_default();
+                total.0 += file.entry.size;
+                total.1 += 1;
+                total.2 = total.2.max(file.entry.modified);
+                if dir == self.root {
+                    break;
+                }
+            }
+        }
+
+        let mut children: HashMap<&Path, Vec<&Path>> = HashMap::new();
+        for dir in &scan.dirs {
+            if dir != &self.root {
+                if let Some(parent) = dir.parent() {
+                    children.entry(parent).or_default().push(dir);
+                }
+            }
+        }
+
+        build_node(&self.root, &totals, &children)
+    }
+
+    /// Delete a file or directory below the root
+    ///
+    /// With `to_trash` the entry is moved to the OS trash, otherwise it is
+    /// removed for good. Symbolic links are removed, not followed.
+    ///
+    /// # Errors
+    ///
+    /// Refuses the root itself and paths outside it with
+    /// [`DiskError::OutsideRoot`].
+    ///
+    /// # Returns
+    ///
+    /// The size and number of files the entry held, `.gitignore`d ones
+    /// included.
+    pub fn delete(&self, path: &Path, to_trash: bool) -> DiskResult<Reclaimed> {
+        let meta = fs::symlink_metadata(path).map_err(|source| match source.kind() {
+            io::ErrorKind::NotFound => DiskError::PathNotFound(path.to_path_buf()),
+            _ => DiskError::Metadata {
+                path: path.to_path_buf(),
+                source,
+            },
+        })?;
+        let root = fs::canonicalize(&self.root).map_err(|source| DiskError::Metadata {
+            path: self.root.clone(),
+            source,
+        })?;
+        let target = canonical_entry(path).map_err(|source| DiskError::Metadata {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        if target == root || !target.starts_with(&root) {
+            return Err(DiskError::OutsideRoot(path.to_path_buf()));
+        }
+
+        let reclaimed = if meta.is_dir() {
+            let (file_count, size) = DiskAnalyzer::new(path)
+                .with_threads(self.threads)
+                .with_gitignore(false)
+                .count_files();
+            Reclaimed { size, file_count }
+        } else if meta.is_file() {
+            Reclaimed {
+                size: meta.len(),
+                file_count: 1,
+            }
+        } else {
+            Reclaimed::default()
+        };
+
+        if to_trash {
+            trash::delete(path).map_err(|source| DiskError::Trash {
+                path: path.to_path_buf(),
+                source,
+            })?;
+        } else {
+            let removed = if meta.is_dir() {
+                fs::remove_dir_all(path)
+            } else {
+                fs::remove_file(path)
+            };
+            removed.map_err(|source| DiskError::Delete {
+                path: path.to_path_buf(),
+                source,
+            })?;
+        }
+        Ok(reclaimed)
+    }
+
+    /// All regular files below the root, hard links counted once
+    fn files(&self) -> Vec<FileEntry> {
+        self.scan().files.into_iter().map(|f| f.entry).collect()
+    }
+
+    /// Traverse the root in parallel, returning files sorted by path
+    fn scan(&self) -> Scan {
+        let visited = Mutex::new(HashSet::new());
+        let rules = IgnoreRules::new(&self.root, self.respect_gitignore, &self.ignore_patterns);
+        let mut scan = match fs::metadata(&self.root) {
+            Ok(meta) if meta.is_dir() => {
+                match rayon::ThreadPoolBuilder::new()
+                    .num_threads(self.threads)
+                    .build()
+                {
+                    Ok(pool) => pool.install(|| scan_dir(&self.root, &meta, &rules, &visited)),
+                    Err(_) => scan_dir(&self.root, &meta, &rules, &visited),
                 }
             }
+            Ok(meta) if meta.is_file() => Scan {
+                files: vec![scanned_file(self.root.clone(), &meta)],
+                dirs: Vec::new(),
+            },
+            _ => Scan::default(),
+        };
+
+        scan.files.sort_by(|a, b| a.entry.path.cmp(&b.entry.path));
+        scan.dirs.sort();
+
+        let mut seen_links = HashSet::new();
+        scan.files
+            .retain(|f| f.link.is_none_or(|key| seen_links.insert(key)));
+        scan
+    }
+}
+
+/// Canonical form of `path`, leaving a final symbolic link unresolved
+fn canonical_entry(path: &Path) -> io::Result<PathBuf> {
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => {
+            let parent = if parent.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                parent
+            };
+            Ok(fs::canonicalize(parent)?.join(name))
         }
+        _ => fs::canonicalize(path),
+    }
+}
+
+fn default_threads() -> usize {
+    std::thread::available_parallelism().map_or(1, |n| n.get())
+}
 
-        (count, total_size)
+/// Scan one directory, recursing into subdirectories in parallel
+fn scan_dir(
+    dir: &Path,
+    meta: &Metadata,
+    rules: &IgnoreRules,
+    visited: &Mutex<HashSet<InodeKey>>,
+) -> Scan {
+    let mut scan = Scan::default();
+    if let Some(key) = inode_key(meta) {
+        let first_visit = visited
+            .lock()
+            .map(|mut visited| visited.insert(key))
+            .unwrap_or(true);
+        if !first_visit {
+            return scan;
+        }
+    }
+    scan.dirs.push(dir.to_path_buf());
+
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        return scan;
+    };
+    let rules = rules.descend(dir);
+
+    let mut subdirs = Vec::new();
+    for entry in read_dir.flatten() {
+        // `file_type` and `metadata` do not follow symlinks, so links are
+        // neither descended into nor counted
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if !(file_type.is_dir() || file_type.is_file()) {
+            continue;
+        }
+        let path = entry.path();
+        if rules.is_ignored(&path, file_type.is_dir()) {
+            continue;
+        }
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            subdirs.push((path, meta));
+        } else {
+            scan.files.push(scanned_file(path, &meta));
+        }
+    }
+
+    let nested = subdirs
+        .par_iter()
+        .map(|(path, meta)| scan_dir(path, meta, &rules, visited))
+        .reduce(Scan::default, Scan::merge);
+    scan.merge(nested)
+}
+
+fn scanned_file(path: PathBuf, meta: &Metadata) -> ScannedFile {
+    ScannedFile {
+        entry: FileEntry {
+            path,
+            size: meta.len(),
+            modified: meta.modified().ok(),
+            accessed: meta.accessed().ok(),
+        },
+        link: hard_link_key(meta),
     }
 }
 
+fn build_node(
+    dir: &Path,
+    totals: &HashMap<&Path, Totals>,
+    children: &HashMap<&Path, Vec<&Path>>,
+) -> DirNode {
+    let (size, file_count, modified) = totals.get(dir).copied().unwrap_or_default();
+    let mut nodes: Vec<DirNode> = children
+        .get(dir)
+        .map(|subdirs| {
+            subdirs
+                .iter()
+                .map(|subdir| build_node(subdir, totals, children))
+                .collect()
+        })
+        .unwrap_or_default();
+    nodes.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
+    let dir_count = nodes.iter().map(|node| node.dir_count + 1).sum();
+
+    DirNode {
+        path: dir.to_path_buf(),
+        size,
+        file_count,
+        dir_count,
+        modified,
+        children: nodes,
+    }
+}
+
+#[cfg(unix)]
+fn inode_key(meta: &Metadata) -> Option<InodeKey> {
+    use std::os::unix::fs::MetadataExt;
+    Some((meta.dev(), meta.ino()))
+}
+
+#[cfg(not(unix))]
+fn inode_key(_meta: &Metadata) -> Option<InodeKey> {
+    None
+}
+
+#[cfg(unix)]
+fn hard_link_key(meta: &Metadata) -> Option<InodeKey> {
+    use std::os::unix::fs::MetadataExt;
+    if meta.nlink() > 1 {
+        inode_key(meta)
+    } else {
+        None
+    }
+}
+
+#[cfg(not(unix))]
+fn hard_link_key(_meta: &Metadata) -> Option<InodeKey> {
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -272,6 +716,206 @@ mod tests {
         assert_eq!(count, 3);
     }
 
+    fn write_file(path: &Path, len: usize) {
+        let mut file = File::create(path).expect("create file");
+        file.write_all(&vec![0u8; len]).expect("write file");
+    }
+
+    #[test]
+    fn test_with_threads() {
+        let analyzer = DiskAnalyzer::new(".");
+        assert!(analyzer.threads() >= 1);
+        assert_eq!(DiskAnalyzer::new(".").with_threads(3).threads(), 3);
+        assert_eq!(
+            DiskAnalyzer::new(".").with_threads(0).threads(),
+            analyzer.threads()
+        );
+    }
+
+    #[test]
+    fn test_scan_tree_aggregates_sizes() {
+        let dir = tempdir().expect("create temp dir");
+        let root = dir.path();
+        fs::create_dir_all(root.join("a/deep")).expect("create a/deep");
+        fs::create_dir(root.join("b")).expect("create b");
+        fs::create_dir(root.join("empty")).expect("create empty");
+        write_file(&root.join("top.bin"), 10);
+        write_file(&root.join("a/one.bin"), 100);
+        write_file(&root.join("a/deep/two.bin"), 200);
+        write_file(&root.join("b/three.bin"), 50);
+
+        let tree = DiskAnalyzer::new(root).scan_tree();
+        assert_eq!(tree.path, root);
+        assert_eq!(tree.size, 360);
+        assert_eq!(tree.file_count, 4);
+        assert_eq!(tree.dir_count, 4);
+
+        let names: Vec<_> = tree
+            .children
+            .iter()
+            .map(|c| c.path.file_name().and_then(|n| n.to_str()).unwrap_or(""))
+            .collect();
+        assert_eq!(names, ["a", "b", "empty"]);
+        assert_eq!(tree.children[0].size, 300);
+        assert_eq!(tree.children[0].children[0].size, 200);
+        assert_eq!(tree.children[2].size, 0);
+        assert_eq!(tree.children[0].file_count, 2);
+        assert_eq!(tree.children[0].dir_count, 1);
+        assert_eq!(tree.children[0].format_counts(), "2 files · 1 dir");
+        assert_eq!(tree.children[2].format_counts(), "0 files · 0 dirs");
+        assert!(tree.modified.is_some());
+        assert!(tree.children[2].modified.is_none());
+    }
+
+    #[test]
+    fn test_scan_is_deterministic_across_threads() {
+        let dir = tempdir().expect("create temp dir");
+        for i in 0..8 {
+            let sub = dir.path().join(format!("dir{i}"));
+            fs::create_dir(&sub).expect("create subdir");
+            for j in 0..5 {
+                // Equal sizes across directories exercise the path tie-break
+                write_file(&sub.join(format!("f{j}")), (j % 2) * 64);
+            }
+        }
+
+        let single = DiskAnalyzer::new(dir.path()).with_threads(1);
+        let parallel = DiskAnalyzer::new(dir.path()).with_threads(4);
+        assert_eq!(single.scan_tree(), parallel.scan_tree());
+        assert_eq!(single.count_files(), parallel.count_files());
+        let paths = |a: &DiskAnalyzer| -> Vec<PathBuf> {
+            a.find_largest(100).into_iter().map(|e| e.path).collect()
+        };
+        assert_eq!(paths(&single), paths(&parallel));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_hard_links_counted_once() {
+        let dir = tempdir().expect("create temp dir");
+        write_file(&dir.path().join("original"), 500);
+        fs::hard_link(dir.path().join("original"), dir.path().join("link"))
+            .expect("create hard link");
+
+        let analyzer = DiskAnalyzer::new(dir.path());
+        assert_eq!(analyzer.count_files(), (1, 500));
+        assert_eq!(analyzer.scan_tree().size, 500);
+        // The first path in sorted order is kept
+        assert_eq!(analyzer.find_largest(10)[0].filename(), "link");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_loops_are_not_followed() {
+        let dir = tempdir().expect("create temp dir");
+        let sub = dir.path().join("sub");
+        fs::create_dir(&sub).expect("create sub");
+        write_file(&sub.join("file"), 42);
+        std::os::unix::fs::symlink(dir.path(), sub.join("loop")).expect("create symlink");
+
+        let analyzer = DiskAnalyzer::new(dir.path());
+        assert_eq!(analyzer.count_files(), (1, 42));
+        assert_eq!(analyzer.scan_tree().children.len(), 1);
+    }
+
+    #[test]
+    fn test_gitignored_entries_skipped() {
+        let dir = tempdir().expect("create temp dir");
+        let root = dir.path();
+        fs::create_dir_all(root.join("target/debug")).expect("create target");
+        fs::create_dir(root.join("node_modules")).expect("create node_modules");
+        fs::write(root.join(".gitignore"), "target/\n").expect("write .gitignore");
+        write_file(&root.join("target/debug/app"), 1000);
+        write_file(&root.join("node_modules/dep.js"), 300);
+        write_file(&root.join("main.rs"), 10);
+
+        // .gitignore itself plus main.rs and node_modules/dep.js
+        let analyzer = DiskAnalyzer::new(root);
+        assert_eq!(analyzer.count_files().0, 3);
+        assert!(analyzer
+            .scan_tree()
+            .children
+            .iter()
+            .all(|c| !c.path.ends_with("target")));
+
+        let with_extra = DiskAnalyzer::new(root).with_ignore_patterns(["node_modules/"]);
+        assert_eq!(with_extra.count_files().0, 2);
+
+        let everything = DiskAnalyzer::new(root).with_gitignore(false);
+        assert_eq!(everything.count_files().0, 4);
+        assert_eq!(everything.find_largest(1)[0].filename(), "app");
+    }
+
+    #[test]
+    fn test_delete_updates_tree() {
+        let dir = tempdir().expect("create temp dir");
+        let root = dir.path();
+        fs::create_dir_all(root.join("a/deep")).expect("create a/deep");
+        fs::create_dir(root.join("b")).expect("create b");
+        write_file(&root.join("a/one.bin"), 100);
+        write_file(&root.join("a/deep/two.bin"), 200);
+        write_file(&root.join("b/three.bin"), 250);
+
+        let analyzer = DiskAnalyzer::new(root);
+        let mut tree = analyzer.scan_tree();
+        assert_eq!(tree.children[0].filename(), "a");
+
+        // A directory takes its whole subtree with it
+        let deep = root.join("a/deep");
+        let reclaimed = analyzer.delete(&deep, false).expect("delete a/deep");
+        assert_eq!(
+            reclaimed,
+            Reclaimed {
+                size: 200,
+                file_count: 1
+            }
+        );
+        assert!(!deep.exists());
+        assert!(tree.remove(&deep, reclaimed));
+        assert_eq!((tree.size, tree.file_count, tree.dir_count), (350, 2, 2));
+        // `b` is now the largest and sorts first
+        assert_eq!(tree.children[0].filename(), "b");
+        assert_eq!(tree.children[1].dir_count, 0);
+
+        // A file only shrinks its ancestors
+        let one = root.join("a/one.bin");
+        let reclaimed = analyzer.delete(&one, false).expect("delete one.bin");
+        assert!(tree.remove(&one, reclaimed));
+        assert_eq!(tree.size, 250);
+        assert_eq!(tree.children[1].file_count, 0);
+        assert_eq!(tree, analyzer.scan_tree());
+
+        assert!(!tree.remove(Path::new("/elsewhere"), reclaimed));
+    }
+
+    #[test]
+    fn test_delete_refuses_root_and_outside() {
+        let dir = tempdir().expect("create temp dir");
+        let outside = tempdir().expect("create temp dir");
+        write_file(&outside.path().join("keep"), 1);
+        let analyzer = DiskAnalyzer::new(dir.path());
+
+        assert!(matches!(
+            analyzer.delete(dir.path(), false),
+            Err(DiskError::OutsideRoot(_))
+        ));
+        assert!(matches!(
+            analyzer.delete(&outside.path().join("keep"), false),
+            Err(DiskError::OutsideRoot(_))
+        ));
+        fs::create_dir(dir.path().join("sub")).expect("create sub");
+        assert!(matches!(
+            analyzer.delete(&dir.path().join("sub/.."), false),
+            Err(DiskError::OutsideRoot(_))
+        ));
+        assert!(dir.path().exists());
+        assert!(outside.path().join("keep").exists());
+        assert!(matches!(
+            analyzer.delete(&dir.path().join("missing"), false),
+            Err(DiskError::PathNotFound(_))
+        ));
+    }
+
     #[test]
     fn test_file_entry_format() {
         let entry = FileEntry {
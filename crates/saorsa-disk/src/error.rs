@@ -33,6 +33,26 @@ pub enum DiskError {
     /// Analysis was cancelled
     #[error("analysis cancelled")]
     Cancelled,
+
+    /// Deletion of the scan root itself, or of a path outside it
+    #[error("refusing to delete {0}: not below the scan root")]
+    OutsideRoot(PathBuf),
+
+    /// Failed to remove a file or directory
+    #[error("failed to delete: {path}")]
+    Delete {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// Failed to move a file or directory to the OS trash
+    #[error("failed to move to trash: {path}")]
+    Trash {
+        path: PathBuf,
+        #[source]
+        source: trash::Error,
+    },
 }
 
 /// Result type for disk operations
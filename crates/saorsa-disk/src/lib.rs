@@ -2,10 +2,14 @@
 //!
 //! Provides disk usage analysis functionality for the saorsa TUI framework.
 
+pub mod age;
 pub mod analyzer;
 pub mod error;
+pub mod size;
 mod tab;
 
-pub use analyzer::{DiskAnalyzer, DiskInfo, FileEntry};
+pub use age::AgeBucket;
+pub use analyzer::{DirNode, DiskAnalyzer, DiskInfo, FileEntry, Reclaimed};
 pub use error::{DiskError, DiskResult};
+pub use size::{format_count, format_size, format_size_in, SizeUnits};
 pub use tab::{DiskTab, DiskView};
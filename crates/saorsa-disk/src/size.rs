@@ -0,0 +1,99 @@
+//! Human-readable size formatting
+//!
+//! Every size shown by the disk analyzer goes through [`format_size_in`],
+//! so entries stay comparable at a glance: one decimal place and the
+//! largest unit that keeps the value above 1. Counts go through
+//! [`format_count`], which groups digits in thousands.
+
+use humansize::{FormatSizeOptions, BINARY, DECIMAL};
+
+/// Unit system used when formatting sizes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SizeUnits {
+    /// Powers of 1024 (KiB, MiB, GiB)
+    #[default]
+    Binary,
+    /// Powers of 1000 (kB, MB, GB)
+    Decimal,
+}
+
+impl SizeUnits {
+    /// Returns the other unit system
+    #[must_use]
+    pub fn toggled(self) -> Self {
+        match self {
+            SizeUnits::Binary => SizeUnits::Decimal,
+            SizeUnits::Decimal => SizeUnits::Binary,
+        }
+    }
+
+    fn options(self) -> FormatSizeOptions {
+        let base = match self {
+            SizeUnits::Binary => BINARY,
+            SizeUnits::Decimal => DECIMAL,
+        };
+        FormatSizeOptions::from(base).decimal_places(1)
+    }
+}
+
+/// Formats a byte count with binary units, e.g. `1.4 GiB`
+#[must_use]
+pub fn format_size(bytes: u64) -> String {
+    format_size_in(bytes, SizeUnits::Binary)
+}
+
+/// Formats a byte count with the given unit system
+#[must_use]
+pub fn format_size_in(bytes: u64, units: SizeUnits) -> String {
+    humansize::format_size(bytes, units.options())
+}
+
+/// Formats a count with thousands separators, e.g. `4,301`
+#[must_use]
+pub fn format_count(count: usize) -> String {
+    let digits = count.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(digit);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_size_binary() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1024), "1 KiB");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(1_503_238_554), "1.4 GiB");
+    }
+
+    #[test]
+    fn test_format_size_decimal() {
+        assert_eq!(format_size_in(1000, SizeUnits::Decimal), "1 kB");
+        assert_eq!(format_size_in(1_503_238_554, SizeUnits::Decimal), "1.5 GB");
+        assert_eq!(format_size_in(999, SizeUnits::Decimal), "999 B");
+    }
+
+    #[test]
+    fn test_format_count() {
+        assert_eq!(format_count(0), "0");
+        assert_eq!(format_count(999), "999");
+        assert_eq!(format_count(4301), "4,301");
+        assert_eq!(format_count(1_234_567), "1,234,567");
+    }
+
+    #[test]
+    fn test_size_units_toggle() {
+        assert_eq!(SizeUnits::default(), SizeUnits::Binary);
+        assert_eq!(SizeUnits::Binary.toggled(), SizeUnits::Decimal);
+        assert_eq!(SizeUnits::Decimal.toggled(), SizeUnits::Binary);
+    }
+}
@@ -1,14 +1,23 @@
 //! DiskTab - Disk analyzer as a Tab
 //!
-//! Provides a TUI interface for analyzing disk usage.
+//! Provides a TUI interface for analyzing disk usage, and for deleting
+//! what it finds after a confirmation.
 
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::prelude::*;
 use ratatui::widgets::{Block, Borders, Gauge, List, ListItem, ListState, Paragraph};
-use saorsa_cli_core::{Message, Tab, TabId};
-use std::path::PathBuf;
+use saorsa_cli_core::{AppContext, CoreResult, Message, Notification, Tab, TabId, Theme};
+use saorsa_ui::renderer::{pane_block, render_pane_frame, render_scrollbar};
+use saorsa_ui::widgets::ConfirmDialog;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::SystemTime;
 
-use crate::analyzer::{DiskAnalyzer, DiskInfo, FileEntry};
+use crate::age::AgeBucket;
+use crate::analyzer::{DirNode, DiskAnalyzer, DiskInfo, FileEntry, Reclaimed};
+use crate::error::DiskResult;
+use crate::size::{format_size_in, SizeUnits};
 
 /// View mode for the disk tab
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -20,6 +29,41 @@ pub enum DiskView {
     Largest,
     /// List of stale (old) files
     Stale,
+    /// Subdirectories of the root with their sizes and counts
+    Directories,
+}
+
+/// Key bindings listed in the coordinator's help overlay
+const KEY_BINDINGS: &[(&str, &str)] = &[
+    ("o", "Disk overview"),
+    ("l", "Largest files"),
+    ("s", "Stale files"),
+    ("t", "Directories with file / dir counts"),
+    ("d", "Delete selected entry (asks first)"),
+    ("r", "Refresh disk info"),
+    ("u", "Toggle GiB / GB units"),
+    ("i", "Show / hide .gitignored files"),
+    ("a", "Color entries by last-modified age"),
+    ("j/k", "Move selection"),
+    ("g/G", "First / last entry"),
+    ("?", "Toggle this help"),
+];
+
+/// One line of a file or directory list
+struct Row<'a> {
+    /// Columns shown before the path
+    columns: String,
+    path: &'a Path,
+    modified: Option<SystemTime>,
+}
+
+/// Outcome of a job on the scan worker
+enum ScanResult {
+    Largest(Vec<FileEntry>),
+    Stale(Vec<FileEntry>),
+    Directories(DirNode),
+    /// A confirmed deletion finished
+    Deleted(PathBuf, DiskResult<Reclaimed>),
 }
 
 /// Disk analyzer tab
@@ -30,9 +74,27 @@ pub struct DiskTab {
     disk_info: Vec<DiskInfo>,
     largest_files: Vec<FileEntry>,
     stale_files: Vec<FileEntry>,
+    /// Tree below the root; the directories view lists its children
+    tree: Option<DirNode>,
     list_state: ListState,
     stale_days: u64,
     focused: bool,
+    scan: Option<Receiver<ScanResult>>,
+    /// Entry the scan worker is deleting
+    deleting: Option<PathBuf>,
+    /// Finished deletion, or scan that finished while the tab was hidden,
+    /// to announce
+    scan_finished: Option<Notification>,
+    size_units: SizeUnits,
+    respect_gitignore: bool,
+    ignore_patterns: Vec<String>,
+    /// Entry waiting for the user to confirm its deletion
+    pending_delete: Option<PathBuf>,
+    /// Whether deletions go to the OS trash instead of being permanent,
+    /// as set in the [`AppConfig`](saorsa_cli_core::AppConfig)
+    use_trash: bool,
+    /// Whether rows are tinted by how long ago they were modified
+    age_colors: bool,
 }
 
 impl DiskTab {
@@ -54,9 +116,19 @@ impl DiskTab {
             disk_info,
             largest_files: Vec::new(),
             stale_files: Vec::new(),
+            tree: None,
             list_state: ListState::default(),
             stale_days: 30,
             focused: false,
+            scan: None,
+            deleting: None,
+            scan_finished: None,
+            size_units: SizeUnits::default(),
+            respect_gitignore: true,
+            ignore_patterns: Vec::new(),
+            pending_delete: None,
+            use_trash: false,
+            age_colors: false,
         }
     }
 
@@ -70,28 +142,236 @@ impl DiskTab {
         self.stale_days = days;
     }
 
-    /// Analyze and display largest files
+    /// Set whether sizes are shown in binary (GiB) or decimal (GB) units
+    pub fn set_size_units(&mut self, units: SizeUnits) {
+        self.size_units = units;
+    }
+
+    /// Unit system used for displayed sizes
+    #[must_use]
+    pub fn size_units(&self) -> SizeUnits {
+        self.size_units
+    }
+
+    /// Set whether scans skip files matched by `.gitignore`
+    pub fn set_respect_gitignore(&mut self, respect: bool) {
+        self.respect_gitignore = respect;
+    }
+
+    /// Set extra gitignore-style patterns skipped by scans
+    pub fn set_ignore_patterns(&mut self, patterns: Vec<String>) {
+        self.ignore_patterns = patterns;
+    }
+
+    /// Analyzer for the root with the tab's ignore settings
+    fn analyzer(&self) -> DiskAnalyzer {
+        DiskAnalyzer::new(&self.root)
+            .with_gitignore(self.respect_gitignore)
+            .with_ignore_patterns(self.ignore_patterns.clone())
+    }
+
+    /// Switch to the largest-files view and scan for them in the background
+    ///
+    /// Results are picked up by [`Tab::tick`]; until then the previous
+    /// list stays visible and [`Tab::progress`] reports the scan.
     pub fn analyze_largest(&mut self, count: usize) {
-        let analyzer = DiskAnalyzer::new(&self.root);
-        self.largest_files = analyzer.find_largest(count);
+        let analyzer = self.analyzer();
         self.view = DiskView::Largest;
-        if !self.largest_files.is_empty() {
-            self.list_state.select(Some(0));
-        }
+        self.spawn_scan(move || ScanResult::Largest(analyzer.find_largest(count)));
     }
 
-    /// Analyze and display stale files
+    /// Switch to the stale-files view and scan for them in the background
     pub fn analyze_stale(&mut self, count: usize) {
-        let analyzer = DiskAnalyzer::new(&self.root);
-        self.stale_files = analyzer.find_stale(self.stale_days, count);
+        let analyzer = self.analyzer();
+        let days = self.stale_days;
         self.view = DiskView::Stale;
-        if !self.stale_files.is_empty() {
-            self.list_state.select(Some(0));
+        self.spawn_scan(move || ScanResult::Stale(analyzer.find_stale(days, count)));
+    }
+
+    /// Switch to the directories view and size the root's subdirectories
+    /// in the background
+    pub fn analyze_directories(&mut self) {
+        let analyzer = self.analyzer();
+        self.view = DiskView::Directories;
+        self.spawn_scan(move || ScanResult::Directories(analyzer.scan_tree()));
+    }
+
+    /// Whether a background scan is still running
+    #[must_use]
+    pub fn is_scanning(&self) -> bool {
+        self.scan.is_some()
+    }
+
+    /// Run a scan on a worker thread, replacing any scan in flight
+    fn spawn_scan(&mut self, scan: impl FnOnce() -> ScanResult + Send + 'static) {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = tx.send(scan());
+        });
+        self.scan = Some(rx);
+    }
+
+    /// Apply the result of a finished background scan, if any
+    fn poll_scan(&mut self) {
+        let Some(rx) = self.scan.as_ref() else {
+            return;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => {
+                self.scan = None;
+                self.deleting = None;
+                return;
+            }
+        };
+        self.scan = None;
+        self.deleting = None;
+
+        let (found, kind) = match result {
+            ScanResult::Largest(files) => {
+                self.largest_files = files;
+                (self.largest_files.len(), "Largest files")
+            }
+            ScanResult::Stale(files) => {
+                self.stale_files = files;
+                (self.stale_files.len(), "Stale files")
+            }
+            ScanResult::Directories(tree) => {
+                let found = tree.children.len();
+                self.tree = Some(tree);
+                (found, "Directories")
+            }
+            ScanResult::Deleted(path, result) => {
+                self.scan_finished = Some(self.deleted(&path, result));
+                return;
+            }
+        };
+        if !self.focused {
+            self.scan_finished = Some(Notification::info(format!(
+                "{kind} scan finished: {found} found in {}",
+                self.root.display()
+            )));
+        }
+        self.list_state
+            .select(if found == 0 { None } else { Some(0) });
+    }
+
+    /// Path of the selected entry in the current list
+    fn selected_path(&self) -> Option<PathBuf> {
+        let i = self.list_state.selected()?;
+        match self.view {
+            DiskView::Overview => None,
+            DiskView::Largest => self.largest_files.get(i).map(|f| f.path.clone()),
+            DiskView::Stale => self.stale_files.get(i).map(|f| f.path.clone()),
+            DiskView::Directories => self.directories().get(i).map(|d| d.path.clone()),
         }
     }
 
+    /// Subdirectories of the root from the last directories scan
+    fn directories(&self) -> &[DirNode] {
+        self.tree.as_ref().map_or(&[], |tree| &tree.children)
+    }
+
+    /// Delete `path` on the scan worker
+    ///
+    /// Large trees take a while to remove, so [`Tab::progress`] reports the
+    /// deletion and keys wait until it is done.
+    fn delete(&mut self, path: PathBuf) {
+        let analyzer = self.analyzer();
+        let to_trash = self.use_trash;
+        self.deleting = Some(path.clone());
+        self.spawn_scan(move || {
+            let result = analyzer.delete(&path, to_trash);
+            ScanResult::Deleted(path, result)
+        });
+    }
+
+    /// Drop a deleted entry from the lists and the tree
+    ///
+    /// Sizes are updated in place; nothing is rescanned.
+    fn deleted(&mut self, path: &Path, result: DiskResult<Reclaimed>) -> Notification {
+        let reclaimed = match result {
+            Ok(reclaimed) => reclaimed,
+            Err(e) => return Notification::error(format!("Failed to delete: {e}")),
+        };
+        self.forget(path, reclaimed);
+
+        let verb = if self.use_trash {
+            "Moved to trash"
+        } else {
+            "Deleted"
+        };
+        Notification::info(format!(
+            "{verb} {}, reclaimed {}",
+            path.display(),
+            format_size_in(reclaimed.size, self.size_units)
+        ))
+    }
+
+    /// Drop a deleted entry and everything below it from the tab's data
+    fn forget(&mut self, path: &Path, reclaimed: Reclaimed) {
+        self.largest_files.retain(|f| !f.path.starts_with(path));
+        self.stale_files.retain(|f| !f.path.starts_with(path));
+        if let Some(tree) = self.tree.as_mut() {
+            tree.remove(path, reclaimed);
+        }
+
+        let len = self.current_list_len();
+        let selected = self
+            .list_state
+            .selected()
+            .map(|i| i.min(len.saturating_sub(1)));
+        self.list_state
+            .select(if len == 0 { None } else { selected });
+    }
+
+    /// Title and text of the confirmation shown before deleting `path`
+    fn delete_prompt(&self, path: &Path) -> (&'static str, String) {
+        let title = if path.is_dir() {
+            "Delete directory"
+        } else {
+            "Delete file"
+        };
+        let size = self
+            .largest_files
+            .iter()
+            .chain(&self.stale_files)
+            .find(|f| f.path == path)
+            .map(|f| f.size)
+            .or_else(|| {
+                self.directories()
+                    .iter()
+                    .find(|d| d.path == path)
+                    .map(|d| d.size)
+            });
+        let size = size.map_or_else(String::new, |size| {
+            format!(" ({})", format_size_in(size, self.size_units))
+        });
+        let action = if self.use_trash {
+            "Move to the trash"
+        } else {
+            "Permanently delete"
+        };
+        (title, format!("{action} {}{size}?", path.display()))
+    }
+
     /// Handle a key event
     pub fn handle_key(&mut self, key: KeyEvent) -> Option<Message> {
+        // A new scan would drop the result, so keys wait for the deletion
+        if self.deleting.is_some() {
+            return None;
+        }
+        // The delete confirmation swallows keys until answered
+        if let Some(path) = self.pending_delete.take() {
+            match key.code {
+                KeyCode::Char('y' | 'Y') | KeyCode::Enter => self.delete(path),
+                KeyCode::Char('n' | 'N') | KeyCode::Esc => {}
+                _ => self.pending_delete = Some(path),
+            }
+            return None;
+        }
+
         match key.code {
             KeyCode::Char('o') | KeyCode::Char('O') => {
                 self.view = DiskView::Overview;
@@ -103,9 +383,30 @@ impl DiskTab {
             KeyCode::Char('s') | KeyCode::Char('S') => {
                 self.analyze_stale(50);
             }
+            KeyCode::Char('t') | KeyCode::Char('T') => {
+                self.analyze_directories();
+            }
+            KeyCode::Char('d') | KeyCode::Char('D') => {
+                self.pending_delete = self.selected_path();
+            }
             KeyCode::Char('r') | KeyCode::Char('R') => {
                 self.refresh();
             }
+            KeyCode::Char('u') | KeyCode::Char('U') => {
+                self.size_units = self.size_units.toggled();
+            }
+            KeyCode::Char('i') | KeyCode::Char('I') => {
+                self.respect_gitignore = !self.respect_gitignore;
+                match self.view {
+                    DiskView::Overview => {}
+                    DiskView::Largest => self.analyze_largest(50),
+                    DiskView::Stale => self.analyze_stale(50),
+                    DiskView::Directories => self.analyze_directories(),
+                }
+            }
+            KeyCode::Char('a') | KeyCode::Char('A') => {
+                self.age_colors = !self.age_colors;
+            }
             KeyCode::Up | KeyCode::Char('k') => {
                 self.move_selection_up();
             }
@@ -118,6 +419,9 @@ impl DiskTab {
             KeyCode::End | KeyCode::Char('G') => {
                 self.select_last();
             }
+            KeyCode::Char('?') => {
+                return Some(Message::ToggleHelp);
+            }
             _ => {}
         }
         None
@@ -155,30 +459,21 @@ impl DiskTab {
             DiskView::Overview => 0,
             DiskView::Largest => self.largest_files.len(),
             DiskView::Stale => self.stale_files.len(),
+            DiskView::Directories => self.directories().len(),
         }
     }
 
     /// Render the overview with disk usage gauges
-    fn render_overview(&self, frame: &mut Frame, area: Rect) {
+    fn render_overview(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
         if self.disk_info.is_empty() {
             let msg = Paragraph::new("No disk information available")
-                .block(
-                    Block::default()
-                        .title(" Disk Overview ")
-                        .borders(Borders::ALL),
-                )
+                .block(pane_block(" Disk Overview ", self.focused, theme))
                 .alignment(Alignment::Center);
             frame.render_widget(msg, area);
             return;
         }
 
-        let block = Block::default()
-            .title(" Disk Overview ")
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Cyan));
-
-        let inner = block.inner(area);
-        frame.render_widget(block, area);
+        let inner = render_pane_frame(frame, area, " Disk Overview ", self.focused, theme);
 
         // Calculate constraints for disk gauges
         let gauge_height = 3u16;
@@ -203,11 +498,11 @@ impl DiskTab {
         for (i, info) in self.disk_info.iter().enumerate().take(num_disks) {
             let percent = info.usage_percent();
             let color = if percent >= 90.0 {
-                Color::Red
+                theme.colors.error
             } else if percent >= 70.0 {
-                Color::Yellow
+                theme.colors.warning
             } else {
-                Color::Green
+                theme.colors.success
             };
 
             let gauge = Gauge::default()
@@ -220,8 +515,8 @@ impl DiskTab {
                 .percent(percent as u16)
                 .label(format!(
                     "{} / {} ({:.1}%)",
-                    DiskInfo::format_bytes(info.used),
-                    DiskInfo::format_bytes(info.total),
+                    format_size_in(info.used, self.size_units),
+                    format_size_in(info.total, self.size_units),
                     percent
                 ));
 
@@ -231,19 +526,64 @@ impl DiskTab {
 
     /// Render a file list (largest or stale)
     fn render_file_list(
-        &mut self,
+        &self,
         frame: &mut Frame,
         area: Rect,
+        theme: &Theme,
         files: &[FileEntry],
         title: &str,
     ) {
-        let items: Vec<ListItem> = files
+        let rows: Vec<Row> = files
+            .iter()
+            .map(|f| Row {
+                columns: format!("{:>10}", f.format_size_in(self.size_units)),
+                path: &f.path,
+                modified: f.modified,
+            })
+            .collect();
+        let title = format!(" {} ({} files) ", title, files.len());
+        self.render_list(frame, area, theme, &rows, title);
+    }
+
+    /// Render the root's subdirectories with their sizes and counts
+    fn render_dir_list(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let dirs = self.directories();
+        let rows: Vec<Row> = dirs
             .iter()
-            .map(|f| {
-                let size_str = f.format_size();
-                let path_str = f.path.display().to_string();
+            .map(|d| {
+                let size = format_size_in(d.size, self.size_units);
+                Row {
+                    columns: format!("{:>10} · {:<26}", size, d.format_counts()),
+                    path: &d.path,
+                    modified: d.modified,
+                }
+            })
+            .collect();
+        let title = format!(" Directories ({}) ", dirs.len());
+        self.render_list(frame, area, theme, &rows, title);
+    }
+
+    /// Render rows of leading columns followed by a path
+    ///
+    /// With age coloring on, each row takes the color of its age bucket.
+    fn render_list(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        theme: &Theme,
+        rows: &[Row],
+        title: String,
+    ) {
+        let now = SystemTime::now();
+        let items: Vec<ListItem> = rows
+            .iter()
+            .map(|row| {
+                let path_str = row.path.display().to_string();
                 // Truncate path if too long
-                let max_path_len = area.width.saturating_sub(15) as usize;
+                let max_path_len = area
+                    .width
+                    .saturating_sub(5 + row.columns.chars().count() as u16)
+                    .max(4) as usize;
                 let display_path = if path_str.len() > max_path_len {
                     format!(
                         "...{}",
@@ -252,25 +592,37 @@ impl DiskTab {
                 } else {
                     path_str
                 };
-                ListItem::new(format!("{:>10}  {}", size_str, display_path))
+                let item = ListItem::new(format!("{}  {}", row.columns, display_path));
+                match (self.age_colors, row.modified) {
+                    (true, Some(modified)) => {
+                        item.style(Style::default().fg(AgeBucket::of(modified, now).color()))
+                    }
+                    (true, None) => item.style(Style::default().fg(theme.colors.muted)),
+                    (false, _) => item,
+                }
             })
             .collect();
 
         let list = List::new(items)
-            .block(
-                Block::default()
-                    .title(format!(" {} ({} files) ", title, files.len()))
-                    .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Cyan)),
-            )
+            .block(pane_block(title, self.focused, theme))
             .highlight_style(
                 Style::default()
                     .add_modifier(Modifier::REVERSED)
-                    .fg(Color::Yellow),
+                    .fg(theme.colors.warning),
             )
             .highlight_symbol("> ");
 
-        frame.render_stateful_widget(list, area, &mut self.list_state);
+        // Rendering settles the offset on a copy; selection moves keep the
+        // offset the last frame settled on
+        let mut list_state = self.list_state;
+        frame.render_stateful_widget(list, area, &mut list_state);
+        render_scrollbar(
+            frame,
+            area.inner(Margin::new(0, 1)),
+            list_state.offset(),
+            rows.len(),
+            usize::from(area.height.saturating_sub(2)),
+        );
     }
 }
 
@@ -291,8 +643,9 @@ impl Tab for DiskTab {
         true
     }
 
-    fn focus(&mut self) {
+    fn focus(&mut self, ctx: &AppContext) {
         self.focused = true;
+        self.use_trash = ctx.config.use_trash;
         self.refresh();
     }
 
@@ -300,45 +653,53 @@ impl Tab for DiskTab {
         self.focused = false;
     }
 
-    fn view(&self, frame: &mut Frame, area: Rect) {
+    fn view(&self, frame: &mut Frame, area: Rect, ctx: &AppContext) -> CoreResult<()> {
         // Layout: main content + help line
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([Constraint::Min(0), Constraint::Length(1)])
             .split(area);
 
-        // We need mutable access for list rendering, so clone self for the mutable part
-        let mut this = DiskTab {
-            id: self.id,
-            root: self.root.clone(),
-            view: self.view,
-            disk_info: self.disk_info.clone(),
-            largest_files: self.largest_files.clone(),
-            stale_files: self.stale_files.clone(),
-            list_state: self.list_state,
-            stale_days: self.stale_days,
-            focused: self.focused,
-        };
-
-        match this.view {
-            DiskView::Overview => this.render_overview(frame, chunks[0]),
+        let theme = &ctx.theme;
+        match self.view {
+            DiskView::Overview => self.render_overview(frame, chunks[0], theme),
             DiskView::Largest => {
-                let files = this.largest_files.clone();
-                this.render_file_list(frame, chunks[0], &files, "Largest Files");
+                self.render_file_list(
+                    frame,
+                    chunks[0],
+                    theme,
+                    &self.largest_files,
+                    "Largest Files",
+                );
             }
             DiskView::Stale => {
-                let files = this.stale_files.clone();
-                this.render_file_list(frame, chunks[0], &files, "Stale Files");
+                self.render_file_list(frame, chunks[0], theme, &self.stale_files, "Stale Files");
             }
+            DiskView::Directories => self.render_dir_list(frame, chunks[0], theme),
+        }
+
+        if let Some(path) = &self.pending_delete {
+            let (title, message) = self.delete_prompt(path);
+            frame.render_widget(ConfirmDialog::new(title, &message, theme), chunks[0]);
         }
 
-        // Help line
-        let help_text =
-            " [o]verview  [l]argest  [s]tale  [r]efresh  [j/k] navigate  [g/G] first/last";
+        // Help line, or the age legend while rows are colored by age
+        let help_text = if self.pending_delete.is_some() {
+            " [y] delete  [n] cancel"
+        } else if self.age_colors {
+            frame.render_widget(
+                Paragraph::new(age_legend(theme)).alignment(Alignment::Center),
+                chunks[1],
+            );
+            return Ok(());
+        } else {
+            " [o]verview  [l]argest  [s]tale  [t]ree  [d]elete  [r]efresh  [u]nits  [i]gnored  [a]ges  [j/k] navigate  [g/G] first/last"
+        };
         let help = Paragraph::new(help_text)
-            .style(Style::default().fg(Color::DarkGray))
+            .style(Style::default().fg(theme.colors.muted))
             .alignment(Alignment::Center);
         frame.render_widget(help, chunks[1]);
+        Ok(())
     }
 
     fn handle_message(&mut self, message: &Message) -> Option<Message> {
@@ -347,6 +708,54 @@ impl Tab for DiskTab {
         }
         None
     }
+
+    fn tick(&mut self) {
+        self.poll_scan();
+    }
+
+    fn take_notification(&mut self) -> Option<Notification> {
+        self.scan_finished.take()
+    }
+
+    fn help_hint(&self) -> &str {
+        "o:overview  l:largest  s:stale  t:tree  d:delete  ?:keys"
+    }
+
+    fn key_bindings(&self) -> Vec<(&str, &str)> {
+        KEY_BINDINGS.to_vec()
+    }
+
+    fn progress(&self) -> Option<String> {
+        if !self.is_scanning() {
+            return None;
+        }
+        if let Some(path) = &self.deleting {
+            return Some(if self.use_trash {
+                format!("Moving {} to the trash...", path.display())
+            } else {
+                format!("Deleting {}...", path.display())
+            });
+        }
+        Some(match self.view {
+            DiskView::Stale => format!("Scanning {} for stale files...", self.root.display()),
+            DiskView::Directories => {
+                format!("Scanning {} for directory sizes...", self.root.display())
+            }
+            _ => format!("Scanning {} for large files...", self.root.display()),
+        })
+    }
+}
+
+/// Legend mapping each age color to its bucket
+fn age_legend(theme: &Theme) -> Line<'static> {
+    let muted = Style::default().fg(theme.colors.muted);
+    let mut spans = vec![Span::styled(" modified: ", muted)];
+    for bucket in AgeBucket::ALL {
+        spans.push(Span::styled("■ ", Style::default().fg(bucket.color())));
+        spans.push(Span::styled(format!("{}  ", bucket.label()), muted));
+    }
+    spans.push(Span::styled("[a] hide ages", muted));
+    Line::from(spans)
 }
 
 #[cfg(test)]
@@ -365,6 +774,46 @@ mod tests {
         assert_eq!(tab.view, DiskView::Overview);
     }
 
+    #[test]
+    fn test_disk_tab_size_units_toggle() {
+        let dir = tempdir().expect("create temp dir");
+        let mut tab = DiskTab::new(1, dir.path());
+        assert_eq!(tab.size_units(), SizeUnits::Binary);
+
+        let key = KeyEvent::from(KeyCode::Char('u'));
+        tab.handle_key(key);
+        assert_eq!(tab.size_units(), SizeUnits::Decimal);
+
+        tab.set_size_units(SizeUnits::Binary);
+        assert_eq!(tab.size_units(), SizeUnits::Binary);
+    }
+
+    #[test]
+    fn test_disk_tab_gitignore_toggle_rescans() {
+        let dir = tempdir().expect("create temp dir");
+        std::fs::write(dir.path().join(".gitignore"), "build/\n").expect("write .gitignore");
+        std::fs::create_dir(dir.path().join("build")).expect("create build");
+        std::fs::write(dir.path().join("build/out.bin"), [0u8; 64]).expect("write out.bin");
+
+        let mut tab = DiskTab::new(1, dir.path());
+        tab.analyze_largest(10);
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while tab.is_scanning() && std::time::Instant::now() < deadline {
+            tab.tick();
+            thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert!(tab.largest_files.iter().all(|f| f.filename() != "out.bin"));
+
+        tab.handle_key(KeyEvent::from(KeyCode::Char('i')));
+        assert!(!tab.respect_gitignore);
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while tab.is_scanning() && std::time::Instant::now() < deadline {
+            tab.tick();
+            thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert!(tab.largest_files.iter().any(|f| f.filename() == "out.bin"));
+    }
+
     #[test]
     fn test_disk_tab_icon() {
         let dir = tempdir().expect("create temp dir");
@@ -378,13 +827,24 @@ mod tests {
         let dir = tempdir().expect("create temp dir");
         let mut tab = DiskTab::new(1, dir.path());
 
-        tab.focus();
+        tab.focus(&AppContext::default());
         assert!(tab.focused);
 
         tab.blur();
         assert!(!tab.focused);
     }
 
+    #[test]
+    fn test_focus_reads_trash_setting() {
+        let dir = tempdir().expect("create temp dir");
+        let mut tab = DiskTab::new(1, dir.path());
+        let mut ctx = AppContext::default();
+        ctx.config.use_trash = true;
+
+        tab.focus(&ctx);
+        assert!(tab.use_trash);
+    }
+
     #[test]
     fn test_view_switching() {
         let dir = tempdir().expect("create temp dir");
@@ -399,6 +859,180 @@ mod tests {
         assert_eq!(tab.view, DiskView::Stale);
     }
 
+    #[test]
+    fn test_background_scan_completes() {
+        let dir = tempdir().expect("create temp dir");
+        std::fs::write(dir.path().join("big.bin"), vec![0u8; 4096]).expect("write file");
+        std::fs::write(dir.path().join("small.txt"), "hi").expect("write file");
+        let mut tab = DiskTab::new(1, dir.path());
+
+        tab.analyze_largest(10);
+        assert!(tab.progress().is_some());
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while tab.is_scanning() && std::time::Instant::now() < deadline {
+            tab.tick();
+            thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        assert!(!tab.is_scanning());
+        assert!(tab.progress().is_none());
+        assert_eq!(tab.largest_files.len(), 2);
+        assert_eq!(tab.largest_files[0].filename(), "big.bin");
+        assert_eq!(tab.list_state.selected(), Some(0));
+    }
+
+    #[test]
+    fn test_background_scan_announces_when_hidden() {
+        let dir = tempdir().expect("create temp dir");
+        std::fs::write(dir.path().join("big.bin"), vec![0u8; 4096]).expect("write file");
+        let mut tab = DiskTab::new(1, dir.path());
+
+        // Hidden tab: the finished scan is announced once
+        tab.analyze_largest(10);
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while tab.is_scanning() && std::time::Instant::now() < deadline {
+            tab.tick();
+            thread::sleep(std::time::Duration::from_millis(10));
+        }
+        let notification = tab.take_notification().expect("notification");
+        assert!(notification
+            .message
+            .starts_with("Largest files scan finished: 1 found"));
+        assert!(tab.take_notification().is_none());
+
+        // Focused tab: the list itself shows the result
+        tab.focus(&AppContext::default());
+        tab.analyze_stale(10);
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while tab.is_scanning() && std::time::Instant::now() < deadline {
+            tab.tick();
+            thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert!(tab.take_notification().is_none());
+    }
+
+    #[test]
+    fn test_directories_view_shows_counts() {
+        let dir = tempdir().expect("create temp dir");
+        std::fs::create_dir_all(dir.path().join("many/nested")).expect("create dirs");
+        for i in 0..3 {
+            std::fs::write(dir.path().join(format!("many/f{i}")), "x").expect("write file");
+        }
+        std::fs::write(dir.path().join("many/nested/g"), "x").expect("write file");
+        let mut tab = DiskTab::new(1, dir.path());
+        tab.focus(&AppContext::default());
+
+        tab.handle_key(KeyEvent::from(KeyCode::Char('t')));
+        assert_eq!(tab.view, DiskView::Directories);
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while tab.is_scanning() && std::time::Instant::now() < deadline {
+            tab.tick();
+            thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert_eq!(tab.directories().len(), 1);
+        assert_eq!(tab.directories()[0].filename(), "many");
+
+        let backend = ratatui::backend::TestBackend::new(80, 6);
+        let mut terminal = Terminal::new(backend).expect("create terminal");
+        terminal
+            .draw(|frame| {
+                tab.view(frame, frame.area(), &AppContext::default())
+                    .expect("view");
+            })
+            .expect("draw");
+        let buffer = terminal.backend().buffer();
+        let row: String = (0..80)
+            .map(|x| buffer[(x, 1)].symbol().to_string())
+            .collect();
+        assert!(row.contains("4 B · 4 files · 1 dir"), "{row}");
+    }
+
+    #[test]
+    fn test_delete_asks_then_updates_in_place() {
+        let dir = tempdir().expect("create temp dir");
+        std::fs::create_dir(dir.path().join("hog")).expect("create hog");
+        std::fs::write(dir.path().join("hog/big.bin"), vec![0u8; 4096]).expect("write file");
+        std::fs::create_dir(dir.path().join("small")).expect("create small");
+        std::fs::write(dir.path().join("small/a.txt"), "hi").expect("write file");
+        let mut tab = DiskTab::new(1, dir.path());
+        tab.focus(&AppContext::default());
+
+        tab.analyze_directories();
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while tab.is_scanning() && std::time::Instant::now() < deadline {
+            tab.tick();
+            thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert_eq!(tab.directories()[0].filename(), "hog");
+
+        // Cancelling leaves everything alone
+        tab.handle_key(KeyEvent::from(KeyCode::Char('d')));
+        assert!(tab.pending_delete.is_some());
+        tab.handle_key(KeyEvent::from(KeyCode::Char('x')));
+        assert!(tab.pending_delete.is_some());
+        tab.handle_key(KeyEvent::from(KeyCode::Esc));
+        assert!(tab.pending_delete.is_none());
+        assert!(dir.path().join("hog").exists());
+
+        tab.handle_key(KeyEvent::from(KeyCode::Char('d')));
+        assert!(tab.handle_key(KeyEvent::from(KeyCode::Char('y'))).is_none());
+        assert!(tab.progress().expect("progress").starts_with("Deleting "));
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while tab.is_scanning() && std::time::Instant::now() < deadline {
+            tab.tick();
+            thread::sleep(std::time::Duration::from_millis(10));
+        }
+        let notification = tab.take_notification().expect("notification");
+        assert!(notification.message.ends_with("reclaimed 4 KiB"));
+        assert!(!dir.path().join("hog").exists());
+        let tree = tab.tree.as_ref().expect("tree");
+        assert_eq!((tree.size, tree.file_count, tree.dir_count), (2, 1, 1));
+        assert_eq!(tab.directories()[0].filename(), "small");
+        assert_eq!(tab.list_state.selected(), Some(0));
+    }
+
+    #[test]
+    fn test_age_colors_tint_rows() {
+        let dir = tempdir().expect("create temp dir");
+        let mut tab = DiskTab::new(1, dir.path());
+        let day = std::time::Duration::from_secs(24 * 60 * 60);
+        tab.view = DiskView::Largest;
+        tab.largest_files = vec![
+            FileEntry {
+                path: dir.path().join("new"),
+                size: 2,
+                modified: Some(SystemTime::now()),
+                accessed: None,
+            },
+            FileEntry {
+                path: dir.path().join("ancient"),
+                size: 1,
+                modified: Some(SystemTime::now() - day * 400),
+                accessed: None,
+            },
+        ];
+        tab.handle_key(KeyEvent::from(KeyCode::Char('a')));
+        assert!(tab.age_colors);
+
+        let backend = ratatui::backend::TestBackend::new(60, 6);
+        let mut terminal = Terminal::new(backend).expect("create terminal");
+        terminal
+            .draw(|frame| {
+                tab.view(frame, frame.area(), &AppContext::default())
+                    .expect("view");
+            })
+            .expect("draw");
+        let buffer = terminal.backend().buffer();
+        assert_eq!(buffer[(15, 1)].fg, AgeBucket::Day.color());
+        assert_eq!(buffer[(15, 2)].fg, AgeBucket::Older.color());
+        let legend: String = (0..60)
+            .map(|x| buffer[(x, 5)].symbol().to_string())
+            .collect();
+        assert!(legend.contains("<1d"), "{legend}");
+        assert!(legend.contains("older"), "{legend}");
+    }
+
     #[test]
     fn test_stale_days_config() {
         let dir = tempdir().expect("create temp dir");
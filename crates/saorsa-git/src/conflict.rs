@@ -0,0 +1,181 @@
+//! Merge conflict markers
+//!
+//! Finds the `<<<<<<<` / `=======` / `>>>>>>>` regions a merge leaves in a
+//! file and rewrites a region with our side, their side, or both. Regions
+//! written with `merge.conflictStyle = diff3` carry a `|||||||` base section,
+//! which is dropped when resolving.
+
+use std::ops::Range;
+
+/// One conflict region, as line indices into the file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    /// Line of the `<<<<<<<` marker
+    pub start: usize,
+    /// Line of the `>>>>>>>` marker
+    pub end: usize,
+    /// Label after `<<<<<<<`, usually `HEAD`
+    pub ours_label: String,
+    /// Label after `>>>>>>>`, usually the branch being merged
+    pub theirs_label: String,
+    /// Lines of our side
+    pub ours: Range<usize>,
+    /// Lines of the common ancestor, empty unless diff3-style
+    pub base: Range<usize>,
+    /// Lines of their side
+    pub theirs: Range<usize>,
+}
+
+/// Which side replaces a conflict region
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    /// Keep our lines
+    Ours,
+    /// Keep their lines
+    Theirs,
+    /// Keep our lines followed by theirs
+    Both,
+}
+
+/// Label after a marker of seven `marker` characters, if `line` is one
+fn marker_label(line: &str, marker: &str) -> Option<String> {
+    let rest = line.trim_end_matches('\r').strip_prefix(marker)?;
+    if rest.is_empty() || rest.starts_with(' ') {
+        Some(rest.trim().to_string())
+    } else {
+        None
+    }
+}
+
+/// Find the complete conflict regions in `text`, in file order
+///
+/// A region missing its `=======` or `>>>>>>>` marker is not reported.
+pub fn find_conflicts(text: &str) -> Vec<Conflict> {
+    let mut conflicts = Vec::new();
+    // Start line, label, base marker line and separator line of the open region
+    let mut open: Option<(usize, String, Option<usize>, Option<usize>)> = None;
+
+    for (i, line) in text.lines().enumerate() {
+        if let Some(label) = marker_label(line, "<<<<<<<") {
+            open = Some((i, label, None, None));
+            continue;
+        }
+        let Some((start, label, base, separator)) = &mut open else {
+            continue;
+        };
+        if separator.is_none() && base.is_none() && marker_label(line, "|||||||").is_some() {
+            *base = Some(i);
+        } else if separator.is_none() && line.trim_end_matches('\r') == "=======" {
+            *separator = Some(i);
+        } else if let (Some(separator), Some(theirs_label)) =
+            (*separator, marker_label(line, ">>>>>>>"))
+        {
+            let ours_end = base.unwrap_or(separator);
+            conflicts.push(Conflict {
+                start: *start,
+                end: i,
+                ours_label: std::mem::take(label),
+                theirs_label,
+                ours: *start + 1..ours_end,
+                base: base.map_or(separator..separator, |base| base + 1..separator),
+                theirs: separator + 1..i,
+            });
+            open = None;
+        }
+    }
+
+    conflicts
+}
+
+/// Replace `conflict` in `text` with the lines `resolution` keeps
+///
+/// `conflict` must come from [`find_conflicts`] on the same `text`. Line
+/// endings are kept as they are.
+pub fn resolve(text: &str, conflict: &Conflict, resolution: Resolution) -> String {
+    let lines: Vec<&str> = text.split_inclusive('\n').collect();
+    let side = |range: &Range<usize>| lines[range.clone()].concat();
+
+    let mut out = lines[..conflict.start].concat();
+    match resolution {
+        Resolution::Ours => out.push_str(&side(&conflict.ours)),
+        Resolution::Theirs => out.push_str(&side(&conflict.theirs)),
+        Resolution::Both => {
+            out.push_str(&side(&conflict.ours));
+            out.push_str(&side(&conflict.theirs));
+        }
+    }
+    out.push_str(&lines[conflict.end + 1..].concat());
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MERGED: &str = "\
+fn main() {
+<<<<<<< HEAD
+    println!(\"ours\");
+=======
+    println!(\"theirs\");
+    println!(\"more\");
+>>>>>>> feature
+}
+";
+
+    #[test]
+    fn test_find_conflicts() {
+        let conflicts = find_conflicts(MERGED);
+        assert_eq!(
+            conflicts,
+            vec![Conflict {
+                start: 1,
+                end: 6,
+                ours_label: "HEAD".into(),
+                theirs_label: "feature".into(),
+                ours: 2..3,
+                base: 3..3,
+                theirs: 4..6,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_find_conflicts_diff3_and_unterminated() {
+        let text = "<<<<<<< ours\na\n||||||| base\nb\n=======\nc\n>>>>>>> theirs\n\
+                    <<<<<<< HEAD\nleft open\n=======\n";
+        let conflicts = find_conflicts(text);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].ours, 1..2);
+        assert_eq!(conflicts[0].base, 3..4);
+        assert_eq!(conflicts[0].theirs, 5..6);
+    }
+
+    #[test]
+    fn test_marker_needs_exact_length() {
+        assert!(find_conflicts("<<<<<<<< not\n=======\n>>>>>>> x\n").is_empty());
+    }
+
+    #[test]
+    fn test_resolve() {
+        let conflict = &find_conflicts(MERGED)[0];
+        assert_eq!(
+            resolve(MERGED, conflict, Resolution::Ours),
+            "fn main() {\n    println!(\"ours\");\n}\n"
+        );
+        assert_eq!(
+            resolve(MERGED, conflict, Resolution::Theirs),
+            "fn main() {\n    println!(\"theirs\");\n    println!(\"more\");\n}\n"
+        );
+        let both = resolve(MERGED, conflict, Resolution::Both);
+        assert!(both.contains("\"ours\");\n    println!(\"theirs\")"));
+        assert!(find_conflicts(&both).is_empty());
+    }
+
+    #[test]
+    fn test_resolve_keeps_crlf() {
+        let text = "a\r\n<<<<<<< HEAD\r\nb\r\n=======\r\nc\r\n>>>>>>> x\r\nd";
+        let conflict = &find_conflicts(text)[0];
+        assert_eq!(resolve(text, conflict, Resolution::Theirs), "a\r\nc\r\nd");
+    }
+}
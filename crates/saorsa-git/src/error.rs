@@ -24,6 +24,14 @@ pub enum GitError {
     /// No HEAD commit found
     #[error("no HEAD commit")]
     NoHead,
+
+    /// The commit message is empty once comment lines are removed
+    #[error("empty commit message")]
+    EmptyCommitMessage,
+
+    /// The file has no conflict region with this index
+    #[error("no conflict #{0} in file")]
+    NoConflict(usize),
 }
 
 /// Result type alias for Git operations
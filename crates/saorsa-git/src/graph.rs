@@ -0,0 +1,171 @@
+//! Commit graph for the history view
+//!
+//! [`CommitGraph`] lays out commits one row at a time, in the order
+//! [`GitRepo::log`](crate::GitRepo::log) returns them, like
+//! `git log --graph` squeezed into a single line per commit. Every open
+//! branch keeps a lane, the commit it waits for, until that commit arrives.
+//! Lanes are only reused once they are free, so rows already laid out never
+//! change when more history is loaded.
+//!
+//! A row draws two columns per lane: `●` is the commit, `│` a branch passing
+//! by, `╯` a branch ending in the commit, `╮` a merged parent starting a new
+//! lane and `┤`/`├` a merged parent already waiting in another lane.
+
+/// One commit's row of the graph
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphRow {
+    /// Lane of the commit itself
+    pub lane: usize,
+    /// The row as drawn, two columns per lane, without trailing blanks
+    pub cells: String,
+}
+
+/// Lane layout carried from one commit to the next
+#[derive(Debug, Clone, Default)]
+pub struct CommitGraph {
+    /// Commit each lane waits for; `None` is a free lane
+    lanes: Vec<Option<String>>,
+}
+
+impl CommitGraph {
+    /// Empty graph, for the newest commit of a history
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Lay out the next commit
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Full id of the commit
+    /// * `parents` - Full ids of its parents, first parent first
+    pub fn next_row(&mut self, id: &str, parents: &[String]) -> GraphRow {
+        let targets: Vec<usize> = self
+            .lanes
+            .iter()
+            .enumerate()
+            .filter(|(_, lane)| lane.as_deref() == Some(id))
+            .map(|(i, _)| i)
+            .collect();
+        let lane = match targets.first() {
+            Some(&lane) => lane,
+            // A branch tip nobody waits for
+            None => self.free_lane(0, &[]),
+        };
+
+        let mut cells = vec![' '; 2 * self.lanes.len()];
+        for (i, waiting) in self.lanes.iter().enumerate() {
+            if waiting.is_some() && !targets.contains(&i) {
+                cells[2 * i] = '│';
+            }
+        }
+
+        let mut links = Vec::new();
+        for &other in targets.iter().skip(1) {
+            self.lanes[other] = None;
+            links.push((other, '╯'));
+        }
+        self.lanes[lane] = parents.first().cloned();
+        for parent in parents.iter().skip(1) {
+            match self.lanes.iter().position(|l| l.as_ref() == Some(parent)) {
+                Some(j) if j == lane => {}
+                Some(j) => links.push((j, if j > lane { '┤' } else { '├' })),
+                None => {
+                    let j = self.free_lane(lane + 1, &targets);
+                    self.lanes[j] = Some(parent.clone());
+                    links.push((j, '╮'));
+                }
+            }
+        }
+
+        cells.resize(2 * self.lanes.len(), ' ');
+        cells[2 * lane] = '●';
+        for &(j, glyph) in &links {
+            cells[2 * j] = glyph;
+        }
+        for &(j, _) in &links {
+            let (from, to) = (2 * lane.min(j) + 1, 2 * lane.max(j));
+            for cell in &mut cells[from..to] {
+                *cell = match *cell {
+                    ' ' => '─',
+                    '│' | '┤' | '├' => '┼',
+                    '╮' => '┬',
+                    '╯' => '┴',
+                    other => other,
+                };
+            }
+        }
+
+        while self.lanes.last().is_some_and(Option::is_none) {
+            self.lanes.pop();
+        }
+        GraphRow {
+            lane,
+            cells: cells.into_iter().collect::<String>().trim_end().to_string(),
+        }
+    }
+
+    /// First free lane at or right of `from`, skipping `busy`, adding one if
+    /// there is none
+    fn free_lane(&mut self, from: usize, busy: &[usize]) -> usize {
+        let free =
+            (from..self.lanes.len()).find(|&i| self.lanes[i].is_none() && !busy.contains(&i));
+        free.unwrap_or_else(|| {
+            self.lanes.push(None);
+            self.lanes.len() - 1
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(history: &[(&str, &[&str])]) -> Vec<String> {
+        let mut graph = CommitGraph::new();
+        history
+            .iter()
+            .map(|(id, parents)| {
+                let parents: Vec<String> = parents.iter().map(|p| p.to_string()).collect();
+                graph.next_row(id, &parents).cells
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_linear_history_stays_in_one_lane() {
+        assert_eq!(
+            rows(&[("c", &["b"]), ("b", &["a"]), ("a", &[])]),
+            ["●", "●", "●"]
+        );
+    }
+
+    #[test]
+    fn test_merge_opens_and_closes_a_lane() {
+        let history: &[(&str, &[&str])] = &[
+            ("m", &["c", "f"]),
+            ("f", &["b"]),
+            ("c", &["b"]),
+            ("b", &["a"]),
+            ("a", &[]),
+        ];
+        assert_eq!(rows(history), ["●─╮", "│ ●", "● │", "●─╯", "●"]);
+    }
+
+    #[test]
+    fn test_merge_into_waiting_lane_and_crossing() {
+        // Two branches off `a`; `m` merges `y`, which `x` also waits for
+        let history: &[(&str, &[&str])] = &[
+            ("x", &["y"]),
+            ("t", &["u"]),
+            ("m", &["a", "y"]),
+            ("y", &["a"]),
+            ("u", &["a"]),
+            ("a", &[]),
+        ];
+        assert_eq!(
+            rows(history),
+            ["●", "│ ●", "├─┼─●", "● │ │", "│ ● │", "●─┴─╯"]
+        );
+    }
+}
@@ -7,8 +7,11 @@
 //!
 //! - Status view with staged/unstaged/untracked sections
 //! - Diff viewer with syntax highlighting
-//! - Stage/unstage individual files or all changes
+//! - Stage/unstage individual files, single hunks, or all changes
 //! - Discard changes with confirmation
+//! - Resolve merge conflicts one region at a time
+//! - Commits with an optional template and `Signed-off-by` trailer
+//! - Commit history with a commit graph and per-commit diffs
 //!
 //! # Example
 //!
@@ -20,11 +23,18 @@
 //! let tab = GitTab::new(1, Path::new("."));
 //! ```
 
+pub mod conflict;
 pub mod error;
+pub mod graph;
 pub mod repo;
 mod tab;
 pub mod widgets;
 
+pub use conflict::{Conflict, Resolution};
 pub use error::{GitError, GitResult};
-pub use repo::{CommitInfo, Diff, DiffHunk, DiffLine, FileStatus, GitRepo, StatusEntry};
+pub use graph::{CommitGraph, GraphRow};
+pub use repo::{
+    CommitInfo, CommitOptions, CommitSignature, Diff, DiffHunk, DiffLine, FileStatus, GitRepo,
+    StatusEntry,
+};
 pub use tab::GitTab;
@@ -2,9 +2,10 @@
 //!
 //! Provides an enhanced wrapper around git2 with gitui-like operations.
 
+use crate::conflict::{self, Resolution};
 use crate::error::{GitError, GitResult};
 use chrono::{DateTime, TimeZone, Utc};
-use git2::{DiffOptions, Repository, Status, StatusOptions};
+use git2::{DiffOptions, ErrorCode, Repository, Status, StatusOptions};
 use std::path::{Path, PathBuf};
 
 /// File status in the repository
@@ -79,17 +80,43 @@ pub struct StatusEntry {
     pub staged: bool,
 }
 
+/// Who authored or committed a commit, and when
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitSignature {
+    /// Name, or `Unknown` when it is not valid UTF-8
+    pub name: String,
+    /// Email address; empty when it is not valid UTF-8
+    pub email: String,
+    /// When the signature was made
+    pub time: DateTime<Utc>,
+}
+
 /// Information about a commit
 #[derive(Debug, Clone)]
 pub struct CommitInfo {
-    /// Commit SHA (short form)
+    /// Full commit SHA
     pub id: String,
-    /// Commit message (first line)
-    pub message: String,
-    /// Author name
-    pub author: String,
-    /// Commit timestamp
-    pub time: DateTime<Utc>,
+    /// Commit SHA shortened to 7 characters
+    pub short_id: String,
+    /// First line of the message
+    pub summary: String,
+    /// Message after the summary and the blank lines below it; empty for a
+    /// one-line message
+    pub body: String,
+    /// Who wrote the change
+    pub author: CommitSignature,
+    /// Who created the commit, e.g. when rebasing or applying a patch
+    pub committer: CommitSignature,
+    /// Full SHAs of the parents, first parent first; empty for a root
+    /// commit and two or more for a merge
+    pub parents: Vec<String>,
+}
+
+impl CommitInfo {
+    /// Whether the commit has more than one parent
+    pub fn is_merge(&self) -> bool {
+        self.parents.len() > 1
+    }
 }
 
 /// A diff hunk representing a change
@@ -123,6 +150,20 @@ pub struct Diff {
     pub hunks: Vec<DiffHunk>,
 }
 
+/// Template checked in with the project, preferred over `commit.template`
+const COMMIT_TEMPLATE_FILE: &str = ".saorsa/commit-template";
+
+/// Git config key that turns sign-off on by default
+const SIGN_OFF_CONFIG: &str = "saorsa.signOff";
+
+/// Options for [`GitRepo::commit`]
+#[derive(Debug, Clone, Default)]
+pub struct CommitOptions {
+    /// Append a `Signed-off-by` trailer for the configured `user.name` and
+    /// `user.email`
+    pub sign_off: bool,
+}
+
 /// Git repository wrapper with enhanced operations
 pub struct GitRepo {
     repo: Repository,
@@ -172,28 +213,34 @@ impl GitRepo {
         }
     }
 
+    /// Count the commits HEAD's branch and its upstream each lack
+    ///
+    /// # Returns
+    ///
+    /// `(ahead, behind)`: commits only on the branch, and commits only on
+    /// the upstream. `(0, 0)` when HEAD is detached or has no upstream.
+    pub fn ahead_behind(&self) -> GitResult<(usize, usize)> {
+        let head = self.repo.head()?;
+        if !head.is_branch() {
+            return Ok((0, 0));
+        }
+        let branch = git2::Branch::wrap(head);
+        let upstream = match branch.upstream() {
+            Ok(upstream) => upstream,
+            Err(e) if e.code() == ErrorCode::NotFound => return Ok((0, 0)),
+            Err(e) => return Err(e.into()),
+        };
+        match (branch.get().target(), upstream.get().target()) {
+            (Some(local), Some(remote)) => Ok(self.repo.graph_ahead_behind(local, remote)?),
+            _ => Ok((0, 0)),
+        }
+    }
+
     /// Get information about the HEAD commit
     pub fn head_commit(&self) -> GitResult<CommitInfo> {
         let head = self.repo.head()?;
         let commit = head.peel_to_commit()?;
-        let author = commit.author();
-        let time = Utc
-            .timestamp_opt(commit.time().seconds(), 0)
-            .single()
-            .unwrap_or_else(Utc::now);
-
-        Ok(CommitInfo {
-            id: format!("{:.7}", commit.id()),
-            message: commit
-                .message()
-                .unwrap_or("")
-                .lines()
-                .next()
-                .unwrap_or("")
-                .to_string(),
-            author: author.name().unwrap_or("Unknown").to_string(),
-            time,
-        })
+        Ok(commit_info(&commit))
     }
 
     /// Get all status entries
@@ -211,6 +258,16 @@ impl GitRepo {
                 let status = entry.status();
                 let path = PathBuf::from(path);
 
+                // Conflicts are unstaged until the resolved file is added
+                if status.is_conflicted() {
+                    entries.push(StatusEntry {
+                        path,
+                        status: FileStatus::Conflicted,
+                        staged: false,
+                    });
+                    continue;
+                }
+
                 // Check if staged (index) or unstaged (worktree)
                 let has_index_changes = status.intersects(
                     Status::INDEX_NEW
@@ -332,6 +389,43 @@ impl GitRepo {
         Ok(())
     }
 
+    /// Get the files with unresolved merge conflicts in the index
+    pub fn conflicted_files(&self) -> GitResult<Vec<PathBuf>> {
+        let mut paths = Vec::new();
+        for conflict in self.repo.index()?.conflicts()? {
+            let conflict = conflict?;
+            if let Some(entry) = conflict.our.or(conflict.their).or(conflict.ancestor) {
+                paths.push(PathBuf::from(
+                    String::from_utf8_lossy(&entry.path).into_owned(),
+                ));
+            }
+        }
+        Ok(paths)
+    }
+
+    /// Resolve one conflict region in a file's working copy
+    ///
+    /// `index` counts the regions [`conflict::find_conflicts`] reports for
+    /// the file as it is now. The file is rewritten in place; stage it once
+    /// no regions are left to mark the conflict resolved.
+    ///
+    /// # Returns
+    ///
+    /// The number of conflict regions left in the file.
+    pub fn resolve_conflict(
+        &self,
+        path: &Path,
+        index: usize,
+        resolution: Resolution,
+    ) -> GitResult<usize> {
+        let full_path = self.root.join(path);
+        let text = std::fs::read_to_string(&full_path)?;
+        let conflicts = conflict::find_conflicts(&text);
+        let region = conflicts.get(index).ok_or(GitError::NoConflict(index))?;
+        std::fs::write(&full_path, conflict::resolve(&text, region, resolution))?;
+        Ok(conflicts.len() - 1)
+    }
+
     /// Stage all changes
     pub fn stage_all(&self) -> GitResult<()> {
         let mut index = self.repo.index()?;
@@ -360,82 +454,153 @@ impl GitRepo {
         Ok(())
     }
 
-    /// Discard changes in a file (checkout from index or HEAD)
-    pub fn discard_changes(&self, path: &Path) -> GitResult<()> {
+    /// Revert a file in the working tree to its staged version, like
+    /// `git restore <path>`
+    ///
+    /// Staged changes are kept. A file that isn't in the index, such as an
+    /// untracked file, is deleted.
+    pub fn discard_file(&self, path: &Path) -> GitResult<()> {
+        if self.repo.index()?.get_path(path, 0).is_none() {
+            let full_path = self.root.join(path);
+            if full_path.exists() {
+                std::fs::remove_file(full_path)?;
+            }
+            return Ok(());
+        }
+
         let mut opts = git2::build::CheckoutBuilder::new();
-        opts.path(path);
-        opts.force();
-        self.repo.checkout_head(Some(&mut opts))?;
+        opts.path(path).disable_pathspec_match(true).force();
+        self.repo.checkout_index(None, Some(&mut opts))?;
         Ok(())
     }
 
-    /// Get diff for a file
-    pub fn file_diff(&self, path: &Path, staged: bool) -> GitResult<Diff> {
-        let mut diff_opts = DiffOptions::new();
-        diff_opts.pathspec(path);
-        diff_opts.context_lines(3);
+    /// Revert a file to HEAD in both the index and the working tree, like
+    /// `git restore --staged --worktree <path>`
+    ///
+    /// A file that isn't in HEAD is removed from the index and deleted.
+    pub fn discard_file_force(&self, path: &Path) -> GitResult<()> {
+        self.unstage_file(path)?;
+        self.discard_file(path)
+    }
 
-        let diff = if staged {
-            // Staged: diff between HEAD and index
-            let tree = self.repo.head()?.peel_to_tree()?;
-            self.repo
-                .diff_tree_to_index(Some(&tree), None, Some(&mut diff_opts))?
+    /// Get the staged or unstaged diff for a file
+    ///
+    /// See [`GitRepo::diff_staged`] and [`GitRepo::diff_unstaged`].
+    pub fn file_diff(&self, path: &Path, staged: bool) -> GitResult<Diff> {
+        if staged {
+            self.diff_staged(path)
         } else {
-            // Unstaged: diff between index and worktree
-            self.repo
-                .diff_index_to_workdir(None, Some(&mut diff_opts))?
-        };
+            self.diff_unstaged(path)
+        }
+    }
 
-        let mut result = Diff {
-            path: path.to_path_buf(),
-            hunks: Vec::new(),
+    /// Get a file's staged changes: the index against HEAD
+    ///
+    /// Before the first commit everything in the index is staged, so the
+    /// index is diffed against the empty tree.
+    pub fn diff_staged(&self, path: &Path) -> GitResult<Diff> {
+        let tree = match self.repo.head() {
+            Ok(head) => Some(head.peel_to_tree()?),
+            Err(e) if e.code() == ErrorCode::UnbornBranch => None,
+            Err(e) => return Err(e.into()),
         };
+        let mut diff_opts = file_diff_options(path);
+        let diff = self
+            .repo
+            .diff_tree_to_index(tree.as_ref(), None, Some(&mut diff_opts))?;
+        single_file_diff(path, &diff)
+    }
 
-        let mut current_hunk: Option<DiffHunk> = None;
+    /// Get a file's unstaged changes: the working tree against the index
+    ///
+    /// An untracked file shows its whole content as added.
+    pub fn diff_unstaged(&self, path: &Path) -> GitResult<Diff> {
+        let mut diff_opts = file_diff_options(path);
+        diff_opts
+            .include_untracked(true)
+            .recurse_untracked_dirs(true)
+            .show_untracked_content(true);
+        let diff = self
+            .repo
+            .diff_index_to_workdir(None, Some(&mut diff_opts))?;
+        single_file_diff(path, &diff)
+    }
 
-        diff.print(git2::DiffFormat::Patch, |_delta, hunk, line| {
-            // Start new hunk if header provided
-            if let Some(h) = hunk {
-                if let Some(hunk) = current_hunk.take() {
-                    result.hunks.push(hunk);
-                }
-                current_hunk = Some(DiffHunk {
-                    header: format!(
-                        "@@ -{},{} +{},{} @@",
-                        h.old_start(),
-                        h.old_lines(),
-                        h.new_start(),
-                        h.new_lines()
-                    ),
-                    lines: Vec::new(),
-                });
-            }
+    /// Stage a single hunk of a file's unstaged changes
+    ///
+    /// `hunk` must come from [`GitRepo::diff_unstaged`], so its old-side
+    /// line numbers refer to the index.
+    pub fn stage_hunk(&self, file: &Path, hunk: &DiffHunk) -> GitResult<()> {
+        self.apply_hunk_to_index(file, hunk, false)
+    }
 
-            // Add line to current hunk
-            if let Some(ref mut hunk) = current_hunk {
-                let origin = line.origin();
-                if origin == '+' || origin == '-' || origin == ' ' {
-                    let content = std::str::from_utf8(line.content())
-                        .unwrap_or("")
-                        .to_string();
-                    hunk.lines.push(DiffLine {
-                        origin,
-                        content,
-                        old_lineno: line.old_lineno(),
-                        new_lineno: line.new_lineno(),
-                    });
-                }
-            }
+    /// Remove a single staged hunk from the index
+    ///
+    /// `hunk` must come from [`GitRepo::diff_staged`]; it is applied in
+    /// reverse so the index matches HEAD for those lines.
+    pub fn unstage_hunk(&self, file: &Path, hunk: &DiffHunk) -> GitResult<()> {
+        self.apply_hunk_to_index(file, hunk, true)
+    }
+
+    fn apply_hunk_to_index(&self, file: &Path, hunk: &DiffHunk, reverse: bool) -> GitResult<()> {
+        let patch = hunk_patch(file, hunk, reverse)?;
+        let diff = git2::Diff::from_buffer(patch.as_bytes())?;
+        self.repo.apply(&diff, git2::ApplyLocation::Index, None)?;
+        Ok(())
+    }
 
-            true
-        })?;
+    /// Get up to `max` commits reachable from HEAD, newest first
+    pub fn log(&self, max: usize) -> GitResult<Vec<CommitInfo>> {
+        self.log_page(0, max)
+    }
 
-        // Push final hunk
-        if let Some(hunk) = current_hunk {
-            result.hunks.push(hunk);
+    /// Get a page of HEAD's history, skipping the first `skip` commits
+    ///
+    /// The revision walk is lazy, so only `skip + max` commits are visited
+    /// no matter how large the history is. An unborn HEAD yields no commits.
+    pub fn log_page(&self, skip: usize, max: usize) -> GitResult<Vec<CommitInfo>> {
+        if self.repo.head().is_err() {
+            return Ok(Vec::new());
         }
 
-        Ok(result)
+        let mut walk = self.repo.revwalk()?;
+        walk.push_head()?;
+        walk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+
+        let mut commits = Vec::new();
+        for oid in walk.skip(skip).take(max) {
+            let commit = self.repo.find_commit(oid?)?;
+            commits.push(commit_info(&commit));
+        }
+
+        Ok(commits)
+    }
+
+    /// Look up a commit by full or abbreviated id, or any revision git accepts
+    pub fn find_commit(&self, rev: &str) -> GitResult<CommitInfo> {
+        let commit = self.repo.revparse_single(rev)?.peel_to_commit()?;
+        Ok(commit_info(&commit))
+    }
+
+    /// Get the changes introduced by a commit, one [`Diff`] per file
+    ///
+    /// `hash` may be a full or abbreviated commit id. Root commits are
+    /// diffed against the empty tree.
+    pub fn commit_diff(&self, hash: &str) -> GitResult<Vec<Diff>> {
+        let commit = self.repo.revparse_single(hash)?.peel_to_commit()?;
+        let tree = commit.tree()?;
+        let parent_tree = match commit.parents().next() {
+            Some(parent) => Some(parent.tree()?),
+            None => None,
+        };
+
+        let mut diff_opts = DiffOptions::new();
+        diff_opts.context_lines(3);
+        let diff =
+            self.repo
+                .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut diff_opts))?;
+
+        collect_diffs(&diff)
     }
 
     /// Check if there are any staged changes
@@ -449,6 +614,97 @@ impl GitRepo {
         Ok(entries.iter().any(|e| !e.staged))
     }
 
+    /// Commit the index with `message`
+    ///
+    /// Lines starting with `#`, as left by a template, are dropped. While a
+    /// merge is in progress the merged commit becomes the second parent and
+    /// the merge state is cleaned up afterwards.
+    ///
+    /// # Returns
+    ///
+    /// The new commit.
+    pub fn commit(&self, message: &str, options: &CommitOptions) -> GitResult<CommitInfo> {
+        let mut message = clean_commit_message(message);
+        if message.is_empty() {
+            return Err(GitError::EmptyCommitMessage);
+        }
+        if options.sign_off {
+            let trailer = self.sign_off_line()?;
+            if !message.lines().any(|line| line == trailer) {
+                let separator = if last_paragraph_is_trailers(&message) {
+                    "\n"
+                } else {
+                    "\n\n"
+                };
+                message = format!("{message}{separator}{trailer}");
+            }
+        }
+        message.push('\n');
+
+        let signature = self.repo.signature()?;
+        let tree = self.repo.find_tree(self.repo.index()?.write_tree()?)?;
+        let mut parents = Vec::new();
+        if let Ok(head) = self.repo.head() {
+            parents.push(head.peel_to_commit()?);
+        }
+        let merge_head = self.repo.find_reference("MERGE_HEAD").ok();
+        if let Some(merge_head) = &merge_head {
+            parents.push(merge_head.peel_to_commit()?);
+        }
+        let parents: Vec<&git2::Commit<'_>> = parents.iter().collect();
+
+        let id = self.repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            &message,
+            &tree,
+            &parents,
+        )?;
+        if merge_head.is_some() {
+            self.repo.cleanup_state()?;
+        }
+        Ok(commit_info(&self.repo.find_commit(id)?))
+    }
+
+    /// Get the commit message template, if the project or git config has one
+    ///
+    /// `.saorsa/commit-template` in the repository wins over the file named
+    /// by git's `commit.template`.
+    pub fn commit_template(&self) -> GitResult<Option<String>> {
+        let project = self.root.join(COMMIT_TEMPLATE_FILE);
+        let path = if project.is_file() {
+            project
+        } else {
+            match self.repo.config()?.get_path("commit.template") {
+                Ok(path) if path.is_relative() => self.root.join(path),
+                Ok(path) => path,
+                Err(e) if e.code() == ErrorCode::NotFound => return Ok(None),
+                Err(e) => return Err(e.into()),
+            }
+        };
+        Ok(Some(std::fs::read_to_string(path)?))
+    }
+
+    /// Whether commits are signed off unless the user says otherwise, from
+    /// the `saorsa.signOff` git config
+    pub fn sign_off_by_default(&self) -> bool {
+        self.repo
+            .config()
+            .and_then(|config| config.get_bool(SIGN_OFF_CONFIG))
+            .unwrap_or(false)
+    }
+
+    /// Get the `Signed-off-by` trailer for the configured identity
+    pub fn sign_off_line(&self) -> GitResult<String> {
+        let signature = self.repo.signature()?;
+        Ok(format!(
+            "Signed-off-by: {} <{}>",
+            signature.name().unwrap_or("Unknown"),
+            signature.email().unwrap_or("")
+        ))
+    }
+
     /// Get a status summary string
     pub fn status_summary(&self) -> GitResult<String> {
         let staged = self.staged_files()?.len();
@@ -474,6 +730,185 @@ impl GitRepo {
     }
 }
 
+/// Parse the start lines out of a `@@ -a,b +c,d @@` hunk header
+fn hunk_starts(header: &str) -> Option<(u32, u32)> {
+    let ranges = header.strip_prefix("@@ -")?;
+    let (old, rest) = ranges.split_once(" +")?;
+    let new = rest.split_whitespace().next()?;
+    let start = |range: &str| range.split(',').next()?.parse().ok();
+    Some((start(old)?, start(new)?))
+}
+
+/// Build a standalone single-hunk patch suitable for `git_apply`
+///
+/// Line counts are recomputed from the hunk's lines rather than trusted
+/// from the header. With `reverse`, additions and removals swap sides so
+/// the patch undoes the hunk.
+fn hunk_patch(file: &Path, hunk: &DiffHunk, reverse: bool) -> GitResult<String> {
+    let (mut old_start, mut new_start) = hunk_starts(&hunk.header).ok_or_else(|| {
+        GitError::Git(git2::Error::from_str(&format!(
+            "malformed hunk header: {}",
+            hunk.header
+        )))
+    })?;
+    if reverse {
+        std::mem::swap(&mut old_start, &mut new_start);
+    }
+
+    let flip = |origin: char| match (origin, reverse) {
+        ('+', true) => '-',
+        ('-', true) => '+',
+        (origin, _) => origin,
+    };
+    let old_lines = hunk.lines.iter().filter(|l| flip(l.origin) != '+').count();
+    let new_lines = hunk.lines.iter().filter(|l| flip(l.origin) != '-').count();
+
+    let path = file.to_string_lossy().replace('\\', "/");
+    let mut patch = format!(
+        "diff --git a/{path} b/{path}\n--- a/{path}\n+++ b/{path}\n@@ -{},{} +{},{} @@\n",
+        old_start, old_lines, new_start, new_lines
+    );
+    for line in &hunk.lines {
+        patch.push(flip(line.origin));
+        patch.push_str(&line.content);
+        if !line.content.ends_with('\n') {
+            patch.push_str("\n\\ No newline at end of file\n");
+        }
+    }
+
+    Ok(patch)
+}
+
+/// Build a [`CommitInfo`] from a git2 commit
+fn commit_info(commit: &git2::Commit<'_>) -> CommitInfo {
+    let message = String::from_utf8_lossy(commit.message_bytes());
+    let (summary, body) = message.split_once('\n').unwrap_or((&message, ""));
+
+    CommitInfo {
+        id: commit.id().to_string(),
+        short_id: format!("{:.7}", commit.id()),
+        summary: summary.trim_end().to_string(),
+        body: body.trim().to_string(),
+        author: commit_signature(&commit.author()),
+        committer: commit_signature(&commit.committer()),
+        parents: commit.parent_ids().map(|id| id.to_string()).collect(),
+    }
+}
+
+fn commit_signature(signature: &git2::Signature<'_>) -> CommitSignature {
+    CommitSignature {
+        name: signature.name().unwrap_or("Unknown").to_string(),
+        email: signature.email().unwrap_or("").to_string(),
+        time: Utc
+            .timestamp_opt(signature.when().seconds(), 0)
+            .single()
+            .unwrap_or_else(Utc::now),
+    }
+}
+
+/// Drop `#` comment lines and surrounding blank lines, like git's default
+/// message cleanup
+fn clean_commit_message(message: &str) -> String {
+    let lines: Vec<&str> = message
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .map(str::trim_end)
+        .collect();
+    lines.join("\n").trim_matches('\n').to_string()
+}
+
+/// Whether the message already ends in a block of `Key: value` trailers,
+/// which a sign-off joins without a blank line
+fn last_paragraph_is_trailers(message: &str) -> bool {
+    let Some((_, last)) = message.rsplit_once("\n\n") else {
+        return false;
+    };
+    last.lines().all(|line| {
+        line.split_once(": ")
+            .is_some_and(|(key, _)| !key.is_empty() && !key.contains(' '))
+    })
+}
+
+/// Diff options limited to one file, with three lines of context
+fn file_diff_options(path: &Path) -> DiffOptions {
+    let mut diff_opts = DiffOptions::new();
+    diff_opts.pathspec(path);
+    diff_opts.context_lines(3);
+    diff_opts
+}
+
+/// Collect the hunks of a diff limited to `path` into one [`Diff`]
+fn single_file_diff(path: &Path, diff: &git2::Diff<'_>) -> GitResult<Diff> {
+    let mut result = Diff {
+        path: path.to_path_buf(),
+        hunks: Vec::new(),
+    };
+    for file in collect_diffs(diff)? {
+        result.hunks.extend(file.hunks);
+    }
+    Ok(result)
+}
+
+/// Split a git2 diff into per-file [`Diff`]s with parsed hunks
+fn collect_diffs(diff: &git2::Diff<'_>) -> GitResult<Vec<Diff>> {
+    let mut files: Vec<Diff> = Vec::new();
+
+    diff.print(git2::DiffFormat::Patch, |delta, hunk, line| {
+        let path = delta
+            .new_file()
+            .path()
+            .or_else(|| delta.old_file().path())
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        if files.last().is_none_or(|f| f.path != path) {
+            files.push(Diff {
+                path,
+                hunks: Vec::new(),
+            });
+        }
+        let Some(file) = files.last_mut() else {
+            return true;
+        };
+
+        // Start new hunk if header provided and not already started
+        if let Some(h) = hunk {
+            let header = format!(
+                "@@ -{},{} +{},{} @@",
+                h.old_start(),
+                h.old_lines(),
+                h.new_start(),
+                h.new_lines()
+            );
+            if file.hunks.last().is_none_or(|last| last.header != header) {
+                file.hunks.push(DiffHunk {
+                    header,
+                    lines: Vec::new(),
+                });
+            }
+        }
+
+        // Add line to current hunk
+        if let Some(hunk) = file.hunks.last_mut() {
+            let origin = line.origin();
+            if origin == '+' || origin == '-' || origin == ' ' {
+                let content = std::str::from_utf8(line.content())
+                    .unwrap_or("")
+                    .to_string();
+                hunk.lines.push(DiffLine {
+                    origin,
+                    content,
+                    old_lineno: line.old_lineno(),
+                    new_lineno: line.new_lineno(),
+                });
+            }
+        }
+
+        true
+    })?;
+
+    Ok(files)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -595,4 +1030,416 @@ mod tests {
         let summary = repo.status_summary().expect("get summary");
         assert!(summary.contains("untracked"));
     }
+
+    fn commit_all(temp: &TempDir, message: &str) {
+        let repo = Repository::open(temp.path()).expect("open raw repo");
+        let mut index = repo.index().expect("index");
+        index
+            .add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)
+            .expect("add all");
+        index.write().expect("write index");
+        let tree_id = index.write_tree().expect("write tree");
+        let tree = repo.find_tree(tree_id).expect("find tree");
+        let sig = repo.signature().expect("signature");
+        let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+            .expect("commit");
+    }
+
+    #[test]
+    fn test_ahead_behind_upstream() {
+        let (temp, repo) = init_test_repo();
+        fs::write(temp.path().join("file.txt"), "base").expect("write file");
+        commit_all(&temp, "base");
+        let raw = Repository::open(temp.path()).expect("open raw repo");
+        let base = raw.head().expect("head").peel_to_commit().expect("commit");
+        raw.branch("upstream", &base, false).expect("branch");
+        assert_eq!(repo.ahead_behind().expect("no upstream"), (0, 0));
+
+        let name = repo.current_branch().expect("branch name");
+        raw.find_branch(&name, git2::BranchType::Local)
+            .expect("find branch")
+            .set_upstream(Some("upstream"))
+            .expect("set upstream");
+        for i in 0..2 {
+            fs::write(temp.path().join("file.txt"), format!("v{i}")).expect("write file");
+            commit_all(&temp, &format!("local {i}"));
+        }
+        let sig = raw.signature().expect("signature");
+        raw.commit(
+            Some("refs/heads/upstream"),
+            &sig,
+            &sig,
+            "remote",
+            &base.tree().expect("tree"),
+            &[&base],
+        )
+        .expect("commit upstream");
+
+        assert_eq!(repo.ahead_behind().expect("ahead/behind"), (2, 1));
+    }
+
+    #[test]
+    fn test_log_empty_repo() {
+        let (_temp, repo) = init_test_repo();
+        assert!(repo.log(10).expect("log").is_empty());
+    }
+
+    #[test]
+    fn test_log_pagination() {
+        let (temp, repo) = init_test_repo();
+        for i in 0..5 {
+            fs::write(temp.path().join("file.txt"), format!("v{}", i)).expect("write file");
+            commit_all(&temp, &format!("commit {}", i));
+        }
+
+        let all = repo.log(10).expect("log");
+        assert_eq!(all.len(), 5);
+        assert_eq!(all[0].summary, "commit 4");
+        assert_eq!(all[0].author.name, "Test");
+        assert_eq!(all[0].parents, [all[1].id.clone()]);
+        assert!(all[4].parents.is_empty());
+
+        let page = repo.log_page(2, 2).expect("log page");
+        assert_eq!(page.len(), 2);
+        assert_eq!(page[0].summary, "commit 2");
+        assert_eq!(page[1].summary, "commit 1");
+    }
+
+    #[test]
+    fn test_commit_diff() {
+        let (temp, repo) = init_test_repo();
+        fs::write(temp.path().join("a.txt"), "one\n").expect("write a");
+        commit_all(&temp, "initial");
+        fs::write(temp.path().join("a.txt"), "one\ntwo\n").expect("write a");
+        fs::write(temp.path().join("b.txt"), "bee\n").expect("write b");
+        commit_all(&temp, "second");
+
+        let head = repo.head_commit().expect("head");
+        let diffs = repo.commit_diff(&head.id).expect("commit diff");
+        assert_eq!(diffs.len(), 2);
+        assert_eq!(diffs[0].path, PathBuf::from("a.txt"));
+        assert_eq!(diffs[0].hunks.len(), 1);
+        assert!(diffs[0].hunks[0]
+            .lines
+            .iter()
+            .any(|l| l.origin == '+' && l.content == "two\n"));
+        assert_eq!(diffs[1].path, PathBuf::from("b.txt"));
+
+        let root = repo.log(10).expect("log").pop().expect("root commit");
+        let diffs = repo.commit_diff(&root.id).expect("root diff");
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].hunks[0].lines.len(), 1);
+    }
+
+    #[test]
+    fn test_hunk_starts() {
+        assert_eq!(hunk_starts("@@ -3,4 +5,6 @@"), Some((3, 5)));
+        assert_eq!(hunk_starts("@@ -1 +1 @@"), Some((1, 1)));
+        assert_eq!(hunk_starts("not a header"), None);
+    }
+
+    #[test]
+    fn test_stage_and_unstage_hunk() {
+        let (temp, repo) = init_test_repo();
+        let original: String = (1..=20).map(|i| format!("line {}\n", i)).collect();
+        fs::write(temp.path().join("a.txt"), &original).expect("write a");
+        commit_all(&temp, "initial");
+
+        // Two far-apart edits produce two hunks
+        let edited = original
+            .replace("line 2\n", "line two\n")
+            .replace("line 19\n", "line nineteen\n");
+        fs::write(temp.path().join("a.txt"), &edited).expect("edit a");
+
+        let path = Path::new("a.txt");
+        let unstaged = repo.file_diff(path, false).expect("unstaged diff");
+        assert_eq!(unstaged.hunks.len(), 2);
+
+        repo.stage_hunk(path, &unstaged.hunks[1])
+            .expect("stage second hunk");
+
+        let staged = repo.file_diff(path, true).expect("staged diff");
+        assert_eq!(staged.hunks.len(), 1);
+        assert!(staged.hunks[0]
+            .lines
+            .iter()
+            .any(|l| l.origin == '+' && l.content == "line nineteen\n"));
+        let unstaged = repo.file_diff(path, false).expect("unstaged diff");
+        assert_eq!(unstaged.hunks.len(), 1);
+        assert!(unstaged.hunks[0]
+            .lines
+            .iter()
+            .any(|l| l.origin == '+' && l.content == "line two\n"));
+
+        repo.unstage_hunk(path, &staged.hunks[0])
+            .expect("unstage hunk");
+        assert!(repo.file_diff(path, true).expect("staged").hunks.is_empty());
+        assert_eq!(
+            repo.file_diff(path, false).expect("unstaged").hunks.len(),
+            2
+        );
+        // The working tree is never touched
+        assert_eq!(
+            fs::read_to_string(temp.path().join("a.txt")).expect("read a"),
+            edited
+        );
+    }
+
+    #[test]
+    fn test_staged_and_unstaged_diffs_differ() {
+        let (temp, repo) = init_test_repo();
+        fs::write(temp.path().join("a.txt"), "one\n").expect("write a");
+        commit_all(&temp, "initial");
+
+        fs::write(temp.path().join("a.txt"), "one\ntwo\n").expect("edit a");
+        repo.stage_file(Path::new("a.txt")).expect("stage a");
+        fs::write(temp.path().join("a.txt"), "one\ntwo\nthree\n").expect("edit a again");
+
+        let added = |diff: &Diff| -> Vec<String> {
+            diff.hunks
+                .iter()
+                .flat_map(|h| &h.lines)
+                .filter(|l| l.origin == '+')
+                .map(|l| l.content.clone())
+                .collect()
+        };
+        let path = Path::new("a.txt");
+        assert_eq!(added(&repo.diff_staged(path).expect("staged")), ["two\n"]);
+        assert_eq!(
+            added(&repo.diff_unstaged(path).expect("unstaged")),
+            ["three\n"]
+        );
+    }
+
+    #[test]
+    fn test_diffs_before_first_commit() {
+        let (temp, repo) = init_test_repo();
+        fs::write(temp.path().join("staged.txt"), "staged\n").expect("write staged");
+        fs::write(temp.path().join("new.txt"), "new\n").expect("write new");
+        repo.stage_file(Path::new("staged.txt")).expect("stage");
+
+        let staged = repo.diff_staged(Path::new("staged.txt")).expect("staged");
+        assert_eq!(staged.hunks.len(), 1);
+        assert_eq!(staged.hunks[0].lines[0].content, "staged\n");
+
+        // Untracked files show their content as added
+        let untracked = repo.diff_unstaged(Path::new("new.txt")).expect("untracked");
+        assert_eq!(untracked.hunks.len(), 1);
+        assert_eq!(untracked.hunks[0].lines[0].origin, '+');
+    }
+
+    #[test]
+    fn test_discard_file_restores_index_version() {
+        let (temp, repo) = init_test_repo();
+        fs::write(temp.path().join("a.txt"), "one\n").expect("write a");
+        commit_all(&temp, "initial");
+
+        fs::write(temp.path().join("a.txt"), "staged\n").expect("edit a");
+        repo.stage_file(Path::new("a.txt")).expect("stage a");
+        fs::write(temp.path().join("a.txt"), "unstaged\n").expect("edit a again");
+
+        repo.discard_file(Path::new("a.txt")).expect("discard");
+        assert_eq!(
+            fs::read_to_string(temp.path().join("a.txt")).expect("read a"),
+            "staged\n"
+        );
+        assert_eq!(repo.staged_files().expect("staged").len(), 1);
+
+        repo.discard_file_force(Path::new("a.txt"))
+            .expect("discard staged");
+        assert_eq!(
+            fs::read_to_string(temp.path().join("a.txt")).expect("read a"),
+            "one\n"
+        );
+        assert!(repo.status().expect("status").is_empty());
+    }
+
+    #[test]
+    fn test_discard_file_deletes_new_files() {
+        let (temp, repo) = init_test_repo();
+        fs::write(temp.path().join("a.txt"), "one\n").expect("write a");
+        commit_all(&temp, "initial");
+        fs::write(temp.path().join("untracked.txt"), "x").expect("write untracked");
+        fs::write(temp.path().join("added.txt"), "x").expect("write added");
+        repo.stage_file(Path::new("added.txt"))
+            .expect("stage added");
+
+        repo.discard_file(Path::new("untracked.txt"))
+            .expect("discard untracked");
+        assert!(!temp.path().join("untracked.txt").exists());
+
+        repo.discard_file_force(Path::new("added.txt"))
+            .expect("discard added");
+        assert!(!temp.path().join("added.txt").exists());
+        assert!(repo.status().expect("status").is_empty());
+    }
+
+    /// Merge a `feature` branch that edits the same line as the current one
+    fn merge_with_conflict(temp: &TempDir) {
+        fs::write(temp.path().join("a.txt"), "base\n").expect("write a");
+        commit_all(temp, "base");
+        let raw = Repository::open(temp.path()).expect("open raw repo");
+        let head = raw.head().expect("head");
+        let main = head.name().expect("branch name").to_string();
+        let base = head.peel_to_commit().expect("base");
+        raw.branch("feature", &base, false).expect("branch");
+
+        fs::write(temp.path().join("a.txt"), "ours\n").expect("edit a");
+        commit_all(temp, "ours");
+
+        raw.set_head("refs/heads/feature")
+            .expect("switch to feature");
+        raw.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .expect("checkout feature");
+        fs::write(temp.path().join("a.txt"), "theirs\n").expect("edit a");
+        commit_all(temp, "theirs");
+        let theirs = raw.head().expect("head").peel_to_commit().expect("theirs");
+
+        raw.set_head(&main).expect("switch back");
+        raw.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .expect("checkout main branch");
+        let annotated = raw.find_annotated_commit(theirs.id()).expect("annotate");
+        raw.merge(&[&annotated], None, None).expect("merge");
+    }
+
+    #[test]
+    fn test_conflicted_files_and_resolve() {
+        let (temp, repo) = init_test_repo();
+        merge_with_conflict(&temp);
+
+        let path = Path::new("a.txt");
+        assert_eq!(repo.conflicted_files().expect("conflicts"), vec![path]);
+        let status = repo.status().expect("status");
+        assert_eq!(status.len(), 1);
+        assert_eq!(status[0].status, FileStatus::Conflicted);
+
+        assert!(matches!(
+            repo.resolve_conflict(path, 1, Resolution::Ours),
+            Err(GitError::NoConflict(1))
+        ));
+        assert_eq!(
+            repo.resolve_conflict(path, 0, Resolution::Both)
+                .expect("resolve"),
+            0
+        );
+        assert_eq!(
+            fs::read_to_string(temp.path().join("a.txt")).expect("read a"),
+            "ours\ntheirs\n"
+        );
+
+        repo.stage_file(path).expect("stage resolved file");
+        assert!(repo.conflicted_files().expect("conflicts").is_empty());
+    }
+
+    #[test]
+    fn test_commit_with_sign_off() {
+        let (temp, repo) = init_test_repo();
+        fs::write(temp.path().join("a.txt"), "one\n").expect("write a");
+        repo.stage_file(Path::new("a.txt")).expect("stage a");
+
+        let options = CommitOptions { sign_off: true };
+        assert!(matches!(
+            repo.commit("# only a comment\n\n", &options),
+            Err(GitError::EmptyCommitMessage)
+        ));
+        let commit = repo
+            .commit("Add a\n# Why:\n\nBody\n", &options)
+            .expect("commit");
+
+        let raw = Repository::open(temp.path()).expect("open raw repo");
+        let head = raw.head().expect("head").peel_to_commit().expect("commit");
+        assert_eq!(head.id().to_string(), commit.id);
+        assert_eq!(format!("{:.7}", head.id()), commit.short_id);
+        assert_eq!(commit.summary, "Add a");
+        assert_eq!(commit.body, "Body\n\nSigned-off-by: Test <test@test.com>");
+        assert_eq!(commit.author.email, "test@test.com");
+        assert_eq!(commit.committer.name, "Test");
+        assert!(commit.parents.is_empty());
+        assert_eq!(
+            head.message(),
+            Some("Add a\n\nBody\n\nSigned-off-by: Test <test@test.com>\n")
+        );
+        assert!(repo.status().expect("status").is_empty());
+    }
+
+    #[test]
+    fn test_sign_off_joins_existing_trailers() {
+        let (temp, repo) = init_test_repo();
+        fs::write(temp.path().join("a.txt"), "one\n").expect("write a");
+        repo.stage_file(Path::new("a.txt")).expect("stage a");
+        repo.commit(
+            "Fix\n\nReviewed-by: Someone <s@example.com>",
+            &CommitOptions { sign_off: true },
+        )
+        .expect("commit");
+
+        let raw = Repository::open(temp.path()).expect("open raw repo");
+        let head = raw.head().expect("head").peel_to_commit().expect("commit");
+        assert!(head.message().is_some_and(|m| m.ends_with(
+            "Reviewed-by: Someone <s@example.com>\nSigned-off-by: Test <test@test.com>\n"
+        )));
+    }
+
+    #[test]
+    fn test_commit_template_sources() {
+        let (temp, repo) = init_test_repo();
+        assert_eq!(repo.commit_template().expect("template"), None);
+
+        fs::write(temp.path().join("git-template"), "# from git\n").expect("write template");
+        Repository::open(temp.path())
+            .expect("open raw repo")
+            .config()
+            .expect("config")
+            .set_str("commit.template", "git-template")
+            .expect("set template");
+        assert_eq!(
+            repo.commit_template().expect("template").as_deref(),
+            Some("# from git\n")
+        );
+
+        fs::create_dir_all(temp.path().join(".saorsa")).expect("create .saorsa");
+        fs::write(temp.path().join(COMMIT_TEMPLATE_FILE), "feat: \n").expect("write template");
+        assert_eq!(
+            repo.commit_template().expect("template").as_deref(),
+            Some("feat: \n")
+        );
+    }
+
+    #[test]
+    fn test_commit_concludes_merge() {
+        let (temp, repo) = init_test_repo();
+        merge_with_conflict(&temp);
+        repo.resolve_conflict(Path::new("a.txt"), 0, Resolution::Theirs)
+            .expect("resolve");
+        repo.stage_file(Path::new("a.txt")).expect("stage");
+        let merge = repo
+            .commit("Merge feature", &CommitOptions::default())
+            .expect("commit");
+        assert!(merge.is_merge());
+
+        let raw = Repository::open(temp.path()).expect("open raw repo");
+        let head = raw.head().expect("head").peel_to_commit().expect("commit");
+        assert_eq!(head.parent_count(), 2);
+        let parents: Vec<_> = head.parent_ids().map(|id| id.to_string()).collect();
+        assert_eq!(merge.parents, parents);
+        assert_eq!(repo.find_commit("HEAD^2").expect("find").id, parents[1]);
+        assert_eq!(raw.state(), git2::RepositoryState::Clean);
+    }
+
+    #[test]
+    fn test_stage_hunk_without_trailing_newline() {
+        let (temp, repo) = init_test_repo();
+        fs::write(temp.path().join("a.txt"), "one\ntwo").expect("write a");
+        commit_all(&temp, "initial");
+        fs::write(temp.path().join("a.txt"), "one\nthree").expect("edit a");
+
+        let path = Path::new("a.txt");
+        let diff = repo.file_diff(path, false).expect("diff");
+        repo.stage_hunk(path, &diff.hunks[0]).expect("stage hunk");
+
+        assert!(repo.file_diff(path, false).expect("diff").hunks.is_empty());
+        assert_eq!(repo.file_diff(path, true).expect("diff").hunks.len(), 1);
+    }
 }
@@ -1,15 +1,24 @@
 //! Git tab implementation
 
-use crate::repo::{Diff, GitRepo, StatusEntry};
+use crate::conflict::{find_conflicts, Conflict, Resolution};
+use crate::graph::{CommitGraph, GraphRow};
+use crate::repo::{CommitInfo, CommitOptions, Diff, DiffHunk, FileStatus, GitRepo, StatusEntry};
+use crate::widgets::diff::{hunk_header_style, render_side_by_side};
 use crate::widgets::{DiffWidgetState, Section, StatusWidgetState};
+use chrono::{DateTime, Utc};
 use crossterm::event::{KeyCode, KeyModifiers};
 use parking_lot::Mutex;
-use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::layout::{Constraint, Direction, Layout, Margin, Rect};
 use ratatui::style::{Color, Modifier, Style};
-use ratatui::widgets::{Block, Borders};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Clear, Paragraph};
 use ratatui::Frame;
-use saorsa_cli_core::{Message, Tab, TabId};
-use std::path::Path;
+use saorsa_cli_core::{
+    AppContext, CoreResult, Message, Notification, Tab, TabId, Theme, ThemeColors,
+};
+use saorsa_ui::renderer::{pane_block, render_pane_frame, render_scrollbar};
+use saorsa_ui::ConfirmDialog;
+use std::path::{Path, PathBuf};
 
 /// Focus state within the Git tab
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -21,6 +30,96 @@ enum GitFocus {
     Diff,
 }
 
+/// Which list the left-hand panel shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum GitView {
+    /// Working-tree status
+    #[default]
+    Status,
+    /// Commit history of HEAD
+    History,
+}
+
+/// Key bindings listed in the coordinator's help overlay
+const KEY_BINDINGS: &[(&str, &str)] = &[
+    ("j/k", "Move selection / scroll diff"),
+    ("Enter/Space", "Stage or unstage file"),
+    ("s / u", "Stage all / unstage all"),
+    ("d", "Discard changes (confirm)"),
+    ("c", "Resolve conflicts: o/t/b take ours/theirs/both"),
+    ("C", "Commit staged changes"),
+    ("r", "Refresh"),
+    ("→ / h", "Focus diff / status"),
+    ("l", "Toggle commit history"),
+    ("] / [", "Next / previous hunk"),
+    ("Space (diff)", "Stage or unstage hunk"),
+    ("v", "Unified / side-by-side diff"),
+    ("PgUp/PgDn", "Page diff"),
+    ("g", "Go to top"),
+    ("?", "Toggle this help"),
+];
+
+/// Conflict regions of one file, shown in place of the diff
+struct ConflictView {
+    path: PathBuf,
+    lines: Vec<String>,
+    conflicts: Vec<Conflict>,
+    selected: usize,
+}
+
+/// Commit message being written in the commit overlay
+struct CommitEditor {
+    message: String,
+    /// Byte offset of the cursor in `message`
+    cursor: usize,
+    sign_off: bool,
+    /// Trailer appended when `sign_off` is set
+    trailer: String,
+}
+
+impl CommitEditor {
+    /// Start from `template`, with the cursor at the end of its first line
+    fn new(template: String, sign_off: bool, trailer: String) -> Self {
+        let cursor = template.find('\n').unwrap_or(template.len());
+        CommitEditor {
+            message: template,
+            cursor,
+            sign_off,
+            trailer,
+        }
+    }
+
+    fn insert(&mut self, c: char) {
+        self.message.insert(self.cursor, c);
+        self.cursor += c.len_utf8();
+    }
+
+    fn backspace(&mut self) {
+        if let Some(c) = self.message[..self.cursor].chars().next_back() {
+            self.cursor -= c.len_utf8();
+            self.message.remove(self.cursor);
+        }
+    }
+
+    fn move_left(&mut self) {
+        if let Some(c) = self.message[..self.cursor].chars().next_back() {
+            self.cursor -= c.len_utf8();
+        }
+    }
+
+    fn move_right(&mut self) {
+        if let Some(c) = self.message[self.cursor..].chars().next() {
+            self.cursor += c.len_utf8();
+        }
+    }
+}
+
+/// Number of commits fetched per history page
+const LOG_PAGE_SIZE: usize = 100;
+
+/// Fetch the next history page when the selection gets this close to the end
+const LOG_PREFETCH_MARGIN: usize = 10;
+
 /// Internal mutable state for the Git tab
 struct GitTabState {
     repo: Option<GitRepo>,
@@ -29,11 +128,28 @@ struct GitTabState {
     untracked: Vec<StatusEntry>,
     current_diff: Diff,
     branch: String,
+    /// Commits the branch is ahead of and behind its upstream
+    ahead_behind: (usize, usize),
     focus: GitFocus,
     status_state: StatusWidgetState,
     diff_state: DiffWidgetState,
     error_message: Option<String>,
+    /// Failed operation waiting to be shown by the coordinator
+    notice: Option<Notification>,
+    /// File whose changes are discarded once the user confirms
+    pending_discard: Option<StatusEntry>,
+    /// Conflicted file being resolved, if any
+    conflict_view: Option<ConflictView>,
+    /// Commit message being written, if the commit overlay is open
+    commit_editor: Option<CommitEditor>,
     last_area_height: u16,
+    view: GitView,
+    commits: Vec<CommitInfo>,
+    /// Graph row of each loaded commit, laid out as pages arrive
+    graph_rows: Vec<GraphRow>,
+    graph: CommitGraph,
+    history_selected: usize,
+    history_exhausted: bool,
 }
 
 impl GitTabState {
@@ -45,11 +161,22 @@ impl GitTabState {
             untracked: Vec::new(),
             current_diff: Diff::default(),
             branch: String::from("(no repo)"),
+            ahead_behind: (0, 0),
             focus: GitFocus::Status,
             status_state: StatusWidgetState::new(),
             diff_state: DiffWidgetState::new(),
             error_message: None,
+            notice: None,
+            pending_discard: None,
+            conflict_view: None,
+            commit_editor: None,
             last_area_height: 20,
+            view: GitView::Status,
+            commits: Vec::new(),
+            graph_rows: Vec::new(),
+            graph: CommitGraph::new(),
+            history_selected: 0,
+            history_exhausted: false,
         };
 
         // Try to open repository
@@ -71,6 +198,7 @@ impl GitTabState {
             self.branch = repo
                 .current_branch()
                 .unwrap_or_else(|_| "(detached)".into());
+            self.ahead_behind = repo.ahead_behind().unwrap_or_default();
             self.staged = repo.staged_files().unwrap_or_default();
             self.unstaged = repo.unstaged_files().unwrap_or_default();
             self.untracked = repo.untracked_files().unwrap_or_default();
@@ -83,26 +211,32 @@ impl GitTabState {
     }
 
     fn update_diff(&mut self) {
+        if self.view == GitView::History {
+            self.update_commit_diff();
+            return;
+        }
+
         let entry = self.selected_entry();
         if let (Some(ref repo), Some(entry)) = (&self.repo, entry) {
-            let staged = entry.staged;
-            match repo.file_diff(&entry.path, staged) {
+            // A file in both sections shows what the selected section holds
+            let diff = if entry.staged {
+                repo.diff_staged(&entry.path)
+            } else {
+                repo.diff_unstaged(&entry.path)
+            };
+            match diff {
                 Ok(diff) => {
+                    self.diff_state.set_diff(&diff);
                     self.current_diff = diff;
-                    self.diff_state.total_lines = self
-                        .current_diff
-                        .hunks
-                        .iter()
-                        .map(|h| 1 + h.lines.len())
-                        .sum();
-                    self.diff_state.scroll = 0;
                 }
                 Err(_) => {
                     self.current_diff = Diff::default();
+                    self.diff_state.set_diff(&self.current_diff);
                 }
             }
         } else {
             self.current_diff = Diff::default();
+            self.diff_state.set_diff(&self.current_diff);
         }
     }
 
@@ -114,6 +248,102 @@ impl GitTabState {
         }
     }
 
+    fn open_history(&mut self) {
+        self.view = GitView::History;
+        self.reload_history();
+    }
+
+    fn close_history(&mut self) {
+        self.view = GitView::Status;
+        self.focus = GitFocus::Status;
+        self.update_diff();
+    }
+
+    fn reload_history(&mut self) {
+        self.commits.clear();
+        self.graph_rows.clear();
+        self.graph = CommitGraph::new();
+        self.history_selected = 0;
+        self.history_exhausted = false;
+        self.load_more_commits();
+        self.update_diff();
+    }
+
+    /// Fetch the next page of history; a short page means we reached the root
+    fn load_more_commits(&mut self) {
+        if self.history_exhausted {
+            return;
+        }
+        if let Some(ref repo) = self.repo {
+            match repo.log_page(self.commits.len(), LOG_PAGE_SIZE) {
+                Ok(page) => {
+                    self.history_exhausted = page.len() < LOG_PAGE_SIZE;
+                    let graph = &mut self.graph;
+                    self.graph_rows.extend(
+                        page.iter()
+                            .map(|commit| graph.next_row(&commit.id, &commit.parents)),
+                    );
+                    self.commits.extend(page);
+                }
+                Err(e) => {
+                    self.history_exhausted = true;
+                    self.fail(format!("Failed to read history: {}", e));
+                }
+            }
+        }
+    }
+
+    fn history_move_down(&mut self) {
+        if self.history_selected + 1 < self.commits.len() {
+            self.history_selected += 1;
+        }
+        if self.history_selected + LOG_PREFETCH_MARGIN >= self.commits.len() {
+            self.load_more_commits();
+        }
+        self.update_diff();
+    }
+
+    fn history_move_up(&mut self) {
+        self.history_selected = self.history_selected.saturating_sub(1);
+        self.update_diff();
+    }
+
+    /// Show the selected commit's changes, one hunk group per file
+    fn update_commit_diff(&mut self) {
+        self.current_diff = Diff::default();
+        self.diff_state.set_diff(&self.current_diff);
+
+        let (Some(repo), Some(commit)) = (&self.repo, self.commits.get(self.history_selected))
+        else {
+            return;
+        };
+        let Ok(files) = repo.commit_diff(&commit.id) else {
+            return;
+        };
+
+        let mut diff = Diff {
+            path: format!("{} {}", commit.short_id, commit.summary).into(),
+            hunks: Vec::new(),
+        };
+        for file in files {
+            let path = file.path.display().to_string();
+            diff.hunks
+                .extend(file.hunks.into_iter().map(|hunk| DiffHunk {
+                    header: format!("{} {}", path, hunk.header),
+                    lines: hunk.lines,
+                }));
+        }
+
+        self.diff_state.set_diff(&diff);
+        self.current_diff = diff;
+    }
+
+    /// Record a failed operation and queue it as a notification
+    fn fail(&mut self, message: String) {
+        self.notice = Some(Notification::error(message.clone()));
+        self.error_message = Some(message);
+    }
+
     fn toggle_stage(&mut self) {
         if let Some(ref repo) = self.repo {
             if let Some(entry) = self.selected_entry() {
@@ -124,7 +354,7 @@ impl GitTabState {
                 };
 
                 if let Err(e) = result {
-                    self.error_message = Some(format!("Failed: {}", e));
+                    self.fail(format!("Failed: {}", e));
                 } else {
                     self.refresh();
                 }
@@ -132,10 +362,175 @@ impl GitTabState {
         }
     }
 
+    /// Stage or unstage the hunk under the diff cursor
+    fn toggle_stage_hunk(&mut self) {
+        if self.view != GitView::Status {
+            return;
+        }
+        let (Some(repo), Some(entry)) = (&self.repo, self.selected_entry()) else {
+            return;
+        };
+        let index = self.diff_state.selected_hunk;
+        let Some(hunk) = self.current_diff.hunks.get(index) else {
+            return;
+        };
+
+        let result = if entry.staged {
+            repo.unstage_hunk(&entry.path, hunk)
+        } else {
+            repo.stage_hunk(&entry.path, hunk)
+        };
+
+        if let Err(e) = result {
+            self.fail(format!("Failed: {}", e));
+        } else {
+            self.refresh();
+            // Stay on the same hunk position so repeated presses walk the file
+            let height = self.last_area_height;
+            self.diff_state.select_hunk(index, height);
+        }
+    }
+
+    /// Open the commit overlay, prefilled with the commit template
+    fn open_commit_editor(&mut self) {
+        let Some(ref repo) = self.repo else {
+            return;
+        };
+        if self.staged.is_empty() {
+            self.notice = Some(Notification::info("Nothing staged to commit"));
+            return;
+        }
+        let template = match repo.commit_template() {
+            Ok(template) => template.unwrap_or_default(),
+            Err(e) => {
+                self.fail(format!("Failed to read commit template: {}", e));
+                return;
+            }
+        };
+        let trailer = repo.sign_off_line().unwrap_or_default();
+        self.commit_editor = Some(CommitEditor::new(
+            template,
+            repo.sign_off_by_default(),
+            trailer,
+        ));
+    }
+
+    /// Commit the index with the overlay's message
+    fn commit(&mut self) {
+        let (Some(repo), Some(editor)) = (&self.repo, &self.commit_editor) else {
+            return;
+        };
+        let options = CommitOptions {
+            sign_off: editor.sign_off,
+        };
+        match repo.commit(&editor.message, &options) {
+            Ok(commit) => {
+                self.commit_editor = None;
+                self.notice = Some(Notification::info(format!("Committed {}", commit.short_id)));
+                self.refresh();
+                if self.view == GitView::History {
+                    self.reload_history();
+                }
+            }
+            Err(e) => self.fail(format!("Failed to commit: {}", e)),
+        }
+    }
+
+    /// Show the conflict regions of the selected file
+    fn open_conflicts(&mut self) {
+        let (Some(repo), Some(entry)) = (&self.repo, self.selected_entry()) else {
+            return;
+        };
+        let text = match std::fs::read_to_string(repo.root().join(&entry.path)) {
+            Ok(text) => text,
+            Err(e) => {
+                self.fail(format!("Failed to read {}: {}", entry.path.display(), e));
+                return;
+            }
+        };
+        let conflicts = find_conflicts(&text);
+        if conflicts.is_empty() {
+            self.notice = Some(Notification::info(format!(
+                "No conflict markers in {}",
+                entry.path.display()
+            )));
+            return;
+        }
+        self.conflict_view = Some(ConflictView {
+            path: entry.path,
+            lines: text.lines().map(str::to_string).collect(),
+            conflicts,
+            selected: 0,
+        });
+        self.focus = GitFocus::Diff;
+    }
+
+    /// Replace the selected conflict region and move on to the next one
+    fn resolve_conflict(&mut self, resolution: Resolution) {
+        let (Some(repo), Some(view)) = (&self.repo, &self.conflict_view) else {
+            return;
+        };
+        let path = view.path.clone();
+        let selected = view.selected;
+        match repo.resolve_conflict(&path, selected, resolution) {
+            Ok(0) => {
+                self.conflict_view = None;
+                self.focus = GitFocus::Status;
+                self.notice = Some(Notification::info(format!(
+                    "{} has no conflicts left; stage it to mark it resolved",
+                    path.display()
+                )));
+                self.refresh();
+            }
+            Ok(_) => {
+                self.open_conflicts_at(&path, selected);
+                self.update_diff();
+            }
+            Err(e) => self.fail(format!("Failed to resolve {}: {}", path.display(), e)),
+        }
+    }
+
+    /// Reload the conflict view of `path`, keeping the region cursor near `selected`
+    fn open_conflicts_at(&mut self, path: &Path, selected: usize) {
+        let Some(ref repo) = self.repo else {
+            return;
+        };
+        let text = std::fs::read_to_string(repo.root().join(path)).unwrap_or_default();
+        let conflicts = find_conflicts(&text);
+        if conflicts.is_empty() {
+            self.conflict_view = None;
+            return;
+        }
+        self.conflict_view = Some(ConflictView {
+            path: path.to_path_buf(),
+            lines: text.lines().map(str::to_string).collect(),
+            selected: selected.min(conflicts.len() - 1),
+            conflicts,
+        });
+    }
+
+    /// Discard the entry's changes: staged entries go back to HEAD,
+    /// unstaged ones to the index, and untracked files are deleted
+    fn discard(&mut self, entry: &StatusEntry) {
+        let Some(ref repo) = self.repo else {
+            return;
+        };
+        let result = if entry.staged {
+            repo.discard_file_force(&entry.path)
+        } else {
+            repo.discard_file(&entry.path)
+        };
+        if let Err(e) = result {
+            self.fail(format!("Failed to discard {}: {}", entry.path.display(), e));
+        } else {
+            self.refresh();
+        }
+    }
+
     fn stage_all(&mut self) {
         if let Some(ref repo) = self.repo {
             if let Err(e) = repo.stage_all() {
-                self.error_message = Some(format!("Failed to stage all: {}", e));
+                self.fail(format!("Failed to stage all: {}", e));
             } else {
                 self.refresh();
             }
@@ -145,7 +540,7 @@ impl GitTabState {
     fn unstage_all(&mut self) {
         if let Some(ref repo) = self.repo {
             if let Err(e) = repo.unstage_all() {
-                self.error_message = Some(format!("Failed to unstage all: {}", e));
+                self.fail(format!("Failed to unstage all: {}", e));
             } else {
                 self.refresh();
             }
@@ -157,6 +552,8 @@ impl GitTabState {
 pub struct GitTab {
     id: TabId,
     state: Mutex<GitTabState>,
+    /// Whether the tab can be closed; the startup Git tab stays open
+    closable: bool,
 }
 
 impl GitTab {
@@ -165,14 +562,91 @@ impl GitTab {
         GitTab {
             id,
             state: Mutex::new(GitTabState::new(path)),
+            closable: false,
         }
     }
 
+    /// Set whether the tab can be closed, as tabs opened at runtime can
+    pub fn set_closable(&mut self, closable: bool) {
+        self.closable = closable;
+    }
+
     /// Handle a key event
     pub fn handle_key(&self, code: KeyCode, modifiers: KeyModifiers) {
         let mut state = self.state.lock();
 
+        // The discard confirmation takes every key until it is answered
+        if let Some(entry) = state.pending_discard.take() {
+            match code {
+                KeyCode::Char('y' | 'Y') | KeyCode::Enter => state.discard(&entry),
+                KeyCode::Char('n' | 'N') | KeyCode::Esc => {}
+                _ => state.pending_discard = Some(entry),
+            }
+            return;
+        }
+
+        // And the commit overlay
+        if let Some(editor) = state.commit_editor.as_mut() {
+            let ctrl = modifiers.contains(KeyModifiers::CONTROL);
+            match code {
+                KeyCode::Esc => state.commit_editor = None,
+                KeyCode::Char('s') if ctrl => state.commit(),
+                KeyCode::Char('o') if ctrl => editor.sign_off = !editor.sign_off,
+                KeyCode::Char(c) if !ctrl => editor.insert(c),
+                KeyCode::Enter => editor.insert('\n'),
+                KeyCode::Backspace => editor.backspace(),
+                KeyCode::Left => editor.move_left(),
+                KeyCode::Right => editor.move_right(),
+                _ => {}
+            }
+            return;
+        }
+
+        // So does the conflict view, until it is closed
+        if let Some(view) = state.conflict_view.as_mut() {
+            let last = view.conflicts.len() - 1;
+            match code {
+                KeyCode::Char(']' | 'n' | 'j') | KeyCode::Down => {
+                    view.selected = (view.selected + 1).min(last);
+                }
+                KeyCode::Char('[' | 'p' | 'k') | KeyCode::Up => {
+                    view.selected = view.selected.saturating_sub(1);
+                }
+                KeyCode::Char('o') => state.resolve_conflict(Resolution::Ours),
+                KeyCode::Char('t') => state.resolve_conflict(Resolution::Theirs),
+                KeyCode::Char('b') => state.resolve_conflict(Resolution::Both),
+                KeyCode::Esc | KeyCode::Char('h') | KeyCode::Left => {
+                    state.conflict_view = None;
+                    state.focus = GitFocus::Status;
+                }
+                _ => {}
+            }
+            return;
+        }
+
         match state.focus {
+            GitFocus::Status if state.view == GitView::History => match (modifiers, code) {
+                (KeyModifiers::NONE, KeyCode::Char('j') | KeyCode::Down) => {
+                    state.history_move_down();
+                }
+                (KeyModifiers::NONE, KeyCode::Char('k') | KeyCode::Up) => {
+                    state.history_move_up();
+                }
+                (KeyModifiers::NONE, KeyCode::Char('g')) => {
+                    state.history_selected = 0;
+                    state.update_diff();
+                }
+                (KeyModifiers::NONE, KeyCode::Enter | KeyCode::Right) => {
+                    state.focus = GitFocus::Diff;
+                }
+                (KeyModifiers::NONE, KeyCode::Char('l') | KeyCode::Esc) => {
+                    state.close_history();
+                }
+                (KeyModifiers::NONE, KeyCode::Char('r')) => {
+                    state.reload_history();
+                }
+                _ => {}
+            },
             GitFocus::Status => {
                 // Pre-compute lengths to avoid borrow issues
                 let staged_len = state.staged.len();
@@ -195,10 +669,15 @@ impl GitTab {
                     }
 
                     // Focus switching
-                    (KeyModifiers::NONE, KeyCode::Char('l')) => {
+                    (KeyModifiers::NONE, KeyCode::Right) => {
                         state.focus = GitFocus::Diff;
                     }
 
+                    // Commit history
+                    (KeyModifiers::NONE, KeyCode::Char('l')) => {
+                        state.open_history();
+                    }
+
                     // Stage/unstage
                     (KeyModifiers::NONE, KeyCode::Enter)
                     | (KeyModifiers::NONE, KeyCode::Char(' ')) => {
@@ -213,6 +692,21 @@ impl GitTab {
                         state.unstage_all();
                     }
 
+                    // Discard, once confirmed
+                    (KeyModifiers::NONE, KeyCode::Char('d')) => {
+                        state.pending_discard = state.selected_entry();
+                    }
+
+                    // Walk the selected file's conflict regions
+                    (KeyModifiers::NONE, KeyCode::Char('c')) => {
+                        state.open_conflicts();
+                    }
+
+                    // Commit what is staged
+                    (_, KeyCode::Char('C')) => {
+                        state.open_commit_editor();
+                    }
+
                     // Refresh
                     (KeyModifiers::NONE, KeyCode::Char('r')) => {
                         state.refresh();
@@ -236,7 +730,7 @@ impl GitTab {
 
                 match (modifiers, code) {
                     // Focus switching
-                    (KeyModifiers::NONE, KeyCode::Char('h')) => {
+                    (KeyModifiers::NONE, KeyCode::Char('h') | KeyCode::Left) => {
                         state.focus = GitFocus::Status;
                     }
 
@@ -257,6 +751,23 @@ impl GitTab {
                         state.diff_state.scroll_to_top();
                     }
 
+                    // Hunk cursor and staging
+                    (KeyModifiers::NONE, KeyCode::Char(']') | KeyCode::Char('n')) => {
+                        state.diff_state.next_hunk(area_height);
+                    }
+                    (KeyModifiers::NONE, KeyCode::Char('[') | KeyCode::Char('p')) => {
+                        state.diff_state.prev_hunk(area_height);
+                    }
+                    (KeyModifiers::NONE, KeyCode::Enter)
+                    | (KeyModifiers::NONE, KeyCode::Char(' ')) => {
+                        state.toggle_stage_hunk();
+                    }
+
+                    // Unified / side-by-side layout
+                    (KeyModifiers::NONE, KeyCode::Char('v')) => {
+                        state.diff_state.toggle_side_by_side(area_height);
+                    }
+
                     _ => {}
                 }
             }
@@ -278,10 +789,10 @@ impl Tab for GitTab {
     }
 
     fn can_close(&self) -> bool {
-        false // Git tab is a core tab
+        self.closable
     }
 
-    fn focus(&mut self) {
+    fn focus(&mut self, _ctx: &AppContext) {
         self.state.lock().refresh();
     }
 
@@ -289,44 +800,115 @@ impl Tab for GitTab {
         // Nothing to do
     }
 
-    fn view(&self, frame: &mut Frame, area: Rect) {
-        let state = self.state.lock();
+    fn view(&self, frame: &mut Frame, area: Rect, ctx: &AppContext) -> CoreResult<()> {
+        let mut state = self.state.lock();
+        let theme = &ctx.theme;
 
         // Split into status (40%) and diff (60%)
         let chunks = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
             .split(area);
+        // Diff scrolling stops once the last line is in view
+        state.last_area_height = chunks[1].height.saturating_sub(2);
+
+        // Render status or history panel
+        match state.view {
+            GitView::Status => render_status_panel(frame, chunks[0], &state, theme),
+            GitView::History => render_history_panel(frame, chunks[0], &state, theme),
+        }
+
+        // Render diff panel, or the conflicts being resolved
+        match &state.conflict_view {
+            Some(view) => render_conflict_panel(frame, chunks[1], view, theme),
+            None => render_diff_panel(frame, chunks[1], &state, theme),
+        }
 
-        // Render status panel
-        render_status_panel(frame, chunks[0], &state);
+        if let Some(entry) = &state.pending_discard {
+            let (title, message) = discard_prompt(entry);
+            let dialog = ConfirmDialog::new(title, &message, theme);
+            frame.render_widget(dialog, area);
+        }
+        if let Some(editor) = &state.commit_editor {
+            render_commit_editor(frame, area, editor, theme);
+        }
+        Ok(())
+    }
 
-        // Render diff panel
-        render_diff_panel(frame, chunks[1], &state);
+    fn help_hint(&self) -> &str {
+        let state = self.state.lock();
+        if state.pending_discard.is_some() {
+            return "y:discard  n:cancel";
+        }
+        if state.commit_editor.is_some() {
+            return "Ctrl+S:commit  Ctrl+O:sign-off  Esc:cancel";
+        }
+        if state.conflict_view.is_some() {
+            return "o:ours  t:theirs  b:both  ]/[:conflict  Esc:back";
+        }
+        match state.focus {
+            GitFocus::Status => "space:stage  l:log  →:diff  ?:keys",
+            GitFocus::Diff => "space:stage hunk  ]/[:hunk  v:split  ?:keys",
+        }
+    }
+
+    fn key_bindings(&self) -> Vec<(&str, &str)> {
+        KEY_BINDINGS.to_vec()
+    }
+
+    fn status_context(&self) -> Option<String> {
+        let state = self.state.lock();
+        state.repo.as_ref()?;
+        let (ahead, behind) = state.ahead_behind;
+        Some(format!("⎇ {} ↑{ahead}↓{behind}", state.branch))
     }
 
     fn handle_message(&mut self, message: &Message) -> Option<Message> {
         if let Message::Key(key) = message {
+            // `?` is just text while a commit message is being written
+            if key.code == KeyCode::Char('?') && self.state.lock().commit_editor.is_none() {
+                return Some(Message::ToggleHelp);
+            }
             self.handle_key(key.code, key.modifiers);
+            // Failures are otherwise only kept in the tab's own state
+            return self.state.lock().notice.take().map(Message::Notify);
         }
         None
     }
 }
 
-fn render_status_panel(frame: &mut Frame, area: Rect, state: &GitTabState) {
-    let border_style = if state.focus == GitFocus::Status {
-        Style::default().fg(Color::Cyan)
+/// Title and question for confirming a discard of `entry`
+fn discard_prompt(entry: &StatusEntry) -> (&'static str, String) {
+    let path = entry.path.display();
+    if entry.status == FileStatus::Untracked {
+        (
+            "Delete file",
+            format!("Delete untracked file {path}? It is not in Git and can't be restored."),
+        )
+    } else if entry.staged {
+        (
+            "Discard changes",
+            format!(
+                "Discard all changes to {path}, staged and unstaged, and restore it from HEAD?"
+            ),
+        )
     } else {
-        Style::default().fg(Color::DarkGray)
-    };
-
-    let block = Block::default()
-        .title(format!(" {} ", state.branch))
-        .borders(Borders::ALL)
-        .border_style(border_style);
+        (
+            "Discard changes",
+            format!("Discard unstaged changes to {path}? Staged changes are kept."),
+        )
+    }
+}
 
-    let inner = block.inner(area);
-    frame.render_widget(block, area);
+fn render_status_panel(frame: &mut Frame, area: Rect, state: &GitTabState, theme: &Theme) {
+    let colors = &theme.colors;
+    let inner = render_pane_frame(
+        frame,
+        area,
+        &format!(" {} ", state.branch),
+        state.focus == GitFocus::Status,
+        theme,
+    );
 
     if inner.height < 3 {
         return;
@@ -339,7 +921,7 @@ fn render_status_panel(frame: &mut Frame, area: Rect, state: &GitTabState) {
         let msg = "Working tree clean";
         let x = inner.x + (inner.width.saturating_sub(msg.len() as u16)) / 2;
         let y = inner.y + inner.height / 2;
-        buf.set_string(x, y, msg, Style::default().fg(Color::Green));
+        buf.set_string(x, y, msg, Style::default().fg(colors.success));
         return;
     }
 
@@ -348,7 +930,7 @@ fn render_status_panel(frame: &mut Frame, area: Rect, state: &GitTabState) {
     // Render staged section
     if !state.staged.is_empty() {
         let header_style = Style::default()
-            .fg(Color::Green)
+            .fg(colors.success)
             .add_modifier(Modifier::BOLD);
         buf.set_string(
             inner.x,
@@ -364,7 +946,15 @@ fn render_status_panel(frame: &mut Frame, area: Rect, state: &GitTabState) {
             }
             let selected =
                 state.status_state.section == Section::Staged && state.status_state.selected == i;
-            render_status_entry(buf, inner.x, y, inner.width, entry, selected, Color::Green);
+            render_status_entry(
+                buf,
+                inner.x,
+                y,
+                inner.width,
+                entry,
+                selected.then_some(Section::Staged),
+                colors,
+            );
             y += 1;
         }
         y += 1;
@@ -373,7 +963,7 @@ fn render_status_panel(frame: &mut Frame, area: Rect, state: &GitTabState) {
     // Render unstaged section
     if !state.unstaged.is_empty() && y < inner.y + inner.height {
         let header_style = Style::default()
-            .fg(Color::Yellow)
+            .fg(colors.warning)
             .add_modifier(Modifier::BOLD);
         buf.set_string(
             inner.x,
@@ -389,7 +979,15 @@ fn render_status_panel(frame: &mut Frame, area: Rect, state: &GitTabState) {
             }
             let selected =
                 state.status_state.section == Section::Unstaged && state.status_state.selected == i;
-            render_status_entry(buf, inner.x, y, inner.width, entry, selected, Color::Yellow);
+            render_status_entry(
+                buf,
+                inner.x,
+                y,
+                inner.width,
+                entry,
+                selected.then_some(Section::Unstaged),
+                colors,
+            );
             y += 1;
         }
         y += 1;
@@ -398,7 +996,7 @@ fn render_status_panel(frame: &mut Frame, area: Rect, state: &GitTabState) {
     // Render untracked section
     if !state.untracked.is_empty() && y < inner.y + inner.height {
         let header_style = Style::default()
-            .fg(Color::Gray)
+            .fg(colors.muted)
             .add_modifier(Modifier::BOLD);
         buf.set_string(
             inner.x,
@@ -414,36 +1012,164 @@ fn render_status_panel(frame: &mut Frame, area: Rect, state: &GitTabState) {
             }
             let selected = state.status_state.section == Section::Untracked
                 && state.status_state.selected == i;
-            render_status_entry(buf, inner.x, y, inner.width, entry, selected, Color::Gray);
+            render_status_entry(
+                buf,
+                inner.x,
+                y,
+                inner.width,
+                entry,
+                selected.then_some(Section::Untracked),
+                colors,
+            );
             y += 1;
         }
     }
 }
 
+fn render_history_panel(frame: &mut Frame, area: Rect, state: &GitTabState, theme: &Theme) {
+    let colors = &theme.colors;
+    let inner = render_pane_frame(
+        frame,
+        area,
+        &format!(" {} history ", state.branch),
+        state.focus == GitFocus::Status,
+        theme,
+    );
+
+    let buf = frame.buffer_mut();
+
+    if state.commits.is_empty() {
+        let msg = "No commits yet";
+        let x = inner.x + (inner.width.saturating_sub(msg.len() as u16)) / 2;
+        let y = inner.y + inner.height / 2;
+        buf.set_string(x, y, msg, Style::default().fg(colors.muted));
+        return;
+    }
+
+    // Keep the selection on screen
+    let height = inner.height as usize;
+    let first = state
+        .history_selected
+        .saturating_sub(height.saturating_sub(1));
+    let now = Utc::now();
+
+    // Lanes take turns through a few colors so branches are told apart
+    let lane_colors = [
+        colors.accent,
+        colors.success,
+        colors.warning,
+        colors.info,
+        colors.error,
+    ];
+    // Wide graphs are cut short so the summaries stay on screen
+    let max_graph = usize::from(inner.width / 3);
+
+    for (row, (i, commit)) in state
+        .commits
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(height)
+        .enumerate()
+    {
+        let y = inner.y + row as u16;
+        let selected = i == state.history_selected;
+        let graph: String = state
+            .graph_rows
+            .get(i)
+            .map_or("", |row| row.cells.as_str())
+            .chars()
+            .take(max_graph)
+            .collect();
+        let graph_width = graph.chars().count() + 1;
+        let when = relative_time(commit.committer.time, now);
+        let text = format!(
+            " {graph} {} {} ({}, {})",
+            commit.short_id, commit.summary, commit.author.name, when
+        );
+        let text: String = text.chars().take(inner.width as usize).collect();
+        let id_x = inner.x.saturating_add(graph_width as u16 + 1);
+
+        if selected {
+            let style = Style::default()
+                .fg(colors.foreground)
+                .bg(colors.selection)
+                .add_modifier(Modifier::BOLD);
+            buf.set_string(inner.x, y, &text, style);
+        } else {
+            buf.set_string(inner.x, y, &text, Style::default().fg(colors.foreground));
+            // The graph starts one cell in, so one cell less fits
+            let fits = usize::from(inner.width.saturating_sub(1));
+            for (col, glyph) in graph.chars().enumerate().take(fits) {
+                let color = lane_colors[(col / 2) % lane_colors.len()];
+                let mut utf8 = [0; 4];
+                buf.set_string(
+                    inner.x + 1 + col as u16,
+                    y,
+                    glyph.encode_utf8(&mut utf8),
+                    Style::default().fg(color),
+                );
+            }
+            if id_x < inner.x + inner.width {
+                buf.set_stringn(
+                    id_x,
+                    y,
+                    &commit.short_id,
+                    (inner.x + inner.width - id_x) as usize,
+                    Style::default().fg(colors.warning),
+                );
+            }
+        }
+    }
+}
+
+/// Format how long ago `time` was, e.g. `3 days ago`
+fn relative_time(time: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let secs = (now - time).num_seconds().max(0);
+    let (value, unit) = match secs {
+        0..=59 => return "just now".to_string(),
+        60..=3_599 => (secs / 60, "minute"),
+        3_600..=86_399 => (secs / 3_600, "hour"),
+        86_400..=2_591_999 => (secs / 86_400, "day"),
+        2_592_000..=31_535_999 => (secs / 2_592_000, "month"),
+        _ => (secs / 31_536_000, "year"),
+    };
+    let plural = if value == 1 { "" } else { "s" };
+    format!("{} {}{} ago", value, unit, plural)
+}
+
+/// Render one status row; `selected` carries the section of a selected row
 fn render_status_entry(
     buf: &mut ratatui::buffer::Buffer,
     x: u16,
     y: u16,
     width: u16,
     entry: &StatusEntry,
-    selected: bool,
-    section_color: Color,
+    selected: Option<Section>,
+    colors: &ThemeColors,
 ) {
     let status_char = entry.status.indicator();
     let path_str = entry.path.display().to_string();
 
-    let style = if selected {
+    let style = if let Some(section) = selected {
+        let section_color = match section {
+            Section::Staged => colors.success,
+            Section::Unstaged => colors.warning,
+            Section::Untracked => colors.muted,
+        };
         Style::default()
-            .fg(Color::Black)
+            .fg(colors.background)
             .bg(section_color)
             .add_modifier(Modifier::BOLD)
     } else {
         Style::default().fg(match entry.status {
-            crate::repo::FileStatus::Added => Color::Green,
-            crate::repo::FileStatus::Modified => Color::Yellow,
-            crate::repo::FileStatus::Deleted => Color::Red,
-            crate::repo::FileStatus::Untracked => Color::Gray,
-            _ => Color::White,
+            crate::repo::FileStatus::Added => colors.success,
+            crate::repo::FileStatus::Modified => colors.warning,
+            crate::repo::FileStatus::Deleted => colors.error,
+            crate::repo::FileStatus::Renamed => colors.info,
+            crate::repo::FileStatus::Untracked => colors.muted,
+            crate::repo::FileStatus::Conflicted => colors.error,
+            _ => colors.foreground,
         })
     };
 
@@ -457,12 +1183,8 @@ fn render_status_entry(
     buf.set_string(x, y, &text, style);
 }
 
-fn render_diff_panel(frame: &mut Frame, area: Rect, state: &GitTabState) {
-    let border_style = if state.focus == GitFocus::Diff {
-        Style::default().fg(Color::Cyan)
-    } else {
-        Style::default().fg(Color::DarkGray)
-    };
+fn render_diff_panel(frame: &mut Frame, area: Rect, state: &GitTabState, theme: &Theme) {
+    let colors = &theme.colors;
 
     let title = if state.current_diff.path.as_os_str().is_empty() {
         " Diff ".to_string()
@@ -470,20 +1192,21 @@ fn render_diff_panel(frame: &mut Frame, area: Rect, state: &GitTabState) {
         format!(" {} ", state.current_diff.path.display())
     };
 
-    let block = Block::default()
-        .title(title)
-        .borders(Borders::ALL)
-        .border_style(border_style);
-
-    let inner = block.inner(area);
-    frame.render_widget(block, area);
+    let inner = render_pane_frame(frame, area, &title, state.focus == GitFocus::Diff, theme);
+    render_scrollbar(
+        frame,
+        area.inner(Margin::new(0, 1)),
+        usize::from(state.diff_state.scroll),
+        state.diff_state.total_lines,
+        usize::from(inner.height),
+    );
 
     if state.current_diff.hunks.is_empty() {
         let msg = "No changes to display";
         let buf = frame.buffer_mut();
         let x = inner.x + (inner.width.saturating_sub(msg.len() as u16)) / 2;
         let y = inner.y + inner.height / 2;
-        buf.set_string(x, y, msg, Style::default().fg(Color::DarkGray));
+        buf.set_string(x, y, msg, Style::default().fg(colors.muted));
         return;
     }
 
@@ -491,7 +1214,21 @@ fn render_diff_panel(frame: &mut Frame, area: Rect, state: &GitTabState) {
     let mut y = inner.y;
     let mut line_idx: u16 = 0;
 
-    'outer: for hunk in &state.current_diff.hunks {
+    let hunk_cursor = (state.focus == GitFocus::Diff && state.view == GitView::Status)
+        .then_some(state.diff_state.selected_hunk);
+
+    if state.diff_state.side_by_side {
+        render_side_by_side(
+            &state.current_diff,
+            state.diff_state.scroll,
+            hunk_cursor,
+            inner,
+            buf,
+        );
+        return;
+    }
+
+    'outer: for (hunk_idx, hunk) in state.current_diff.hunks.iter().enumerate() {
         if line_idx < state.diff_state.scroll {
             line_idx += 1 + hunk.lines.len() as u16;
             continue;
@@ -499,9 +1236,7 @@ fn render_diff_panel(frame: &mut Frame, area: Rect, state: &GitTabState) {
 
         // Render hunk header
         if line_idx >= state.diff_state.scroll {
-            let header_style = Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD);
+            let header_style = hunk_header_style(hunk_cursor == Some(hunk_idx));
             buf.set_string(inner.x, y, &hunk.header, header_style);
             y += 1;
             if y >= inner.y + inner.height {
@@ -522,9 +1257,9 @@ fn render_diff_panel(frame: &mut Frame, area: Rect, state: &GitTabState) {
             }
 
             let (fg_color, bg_color) = match line.origin {
-                '+' => (Color::Green, Some(Color::Rgb(0, 40, 0))),
-                '-' => (Color::Red, Some(Color::Rgb(40, 0, 0))),
-                _ => (Color::White, None),
+                '+' => (colors.success, Some(Color::Rgb(0, 40, 0))),
+                '-' => (colors.error, Some(Color::Rgb(40, 0, 0))),
+                _ => (colors.foreground, None),
             };
 
             let style = if let Some(bg) = bg_color {
@@ -548,6 +1283,117 @@ fn render_diff_panel(frame: &mut Frame, area: Rect, state: &GitTabState) {
     }
 }
 
+/// Draw the commit overlay centered over `area`
+fn render_commit_editor(frame: &mut Frame, area: Rect, editor: &CommitEditor, theme: &Theme) {
+    let colors = &theme.colors;
+    let width = (area.width * 7 / 10).max(40).min(area.width);
+    let height = 14.min(area.height);
+    let popup = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+
+    let text_style = Style::default().fg(colors.foreground);
+    let cursor_style = Style::default().add_modifier(Modifier::REVERSED);
+    let (before, after) = editor.message.split_at(editor.cursor);
+    let mut lines: Vec<Line> = Vec::new();
+    let mut current: Vec<Span> = Vec::new();
+    for (i, part) in before.split('\n').enumerate() {
+        if i > 0 {
+            lines.push(Line::from(std::mem::take(&mut current)));
+        }
+        current.push(Span::styled(part, text_style));
+    }
+    let mut rest = after.split('\n');
+    let first = rest.next().unwrap_or("");
+    let mut first_chars = first.chars();
+    let under_cursor = first_chars.next().map_or(" ".to_string(), String::from);
+    current.push(Span::styled(under_cursor, cursor_style));
+    current.push(Span::styled(first_chars.as_str(), text_style));
+    lines.push(Line::from(current));
+    lines.extend(rest.map(|line| Line::styled(line, text_style)));
+    if editor.sign_off {
+        lines.push(Line::default());
+        lines.push(Line::styled(
+            editor.trailer.as_str(),
+            Style::default().fg(colors.muted),
+        ));
+    }
+
+    // Keep the cursor line in view
+    let cursor_row = before.matches('\n').count() as u16;
+    let scroll = cursor_row.saturating_sub(height.saturating_sub(3));
+
+    let hint = if editor.sign_off {
+        " Ctrl+S commit  Ctrl+O sign-off: on  Esc cancel "
+    } else {
+        " Ctrl+S commit  Ctrl+O sign-off: off  Esc cancel "
+    };
+    let block = pane_block(" Commit ", true, theme)
+        .title_bottom(Line::from(hint).right_aligned())
+        .style(Style::default().bg(colors.background));
+    frame.render_widget(Clear, popup);
+    frame.render_widget(
+        Paragraph::new(lines).block(block).scroll((scroll, 0)),
+        popup,
+    );
+}
+
+/// Draw a conflicted file around the selected region, marking each side
+fn render_conflict_panel(frame: &mut Frame, area: Rect, view: &ConflictView, theme: &Theme) {
+    let colors = &theme.colors;
+    let title = format!(
+        " {} conflict {}/{} ",
+        view.path.display(),
+        view.selected + 1,
+        view.conflicts.len()
+    );
+    let inner = render_pane_frame(frame, area, &title, true, theme);
+
+    // Keep a little context above the selected region
+    let selected = &view.conflicts[view.selected];
+    let top = selected.start.saturating_sub(3);
+    render_scrollbar(
+        frame,
+        area.inner(Margin::new(0, 1)),
+        top,
+        view.lines.len(),
+        usize::from(inner.height),
+    );
+
+    let buf = frame.buffer_mut();
+    for (row, (i, line)) in view
+        .lines
+        .iter()
+        .enumerate()
+        .skip(top)
+        .take(usize::from(inner.height))
+        .enumerate()
+    {
+        let region = view.conflicts.iter().find(|c| c.start <= i && i <= c.end);
+        let style = match region {
+            None => Style::default().fg(colors.foreground),
+            Some(c) if c.ours.contains(&i) => Style::default().fg(colors.success),
+            Some(c) if c.theirs.contains(&i) => Style::default().fg(colors.accent),
+            Some(c) if c.base.contains(&i) => Style::default().fg(colors.muted),
+            // Marker lines; the selected region's stand out
+            Some(c) if std::ptr::eq(c, selected) => Style::default()
+                .fg(colors.warning)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+            Some(_) => Style::default().fg(colors.warning),
+        };
+        buf.set_stringn(
+            inner.x,
+            inner.y + row as u16,
+            line,
+            usize::from(inner.width),
+            style,
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -602,13 +1448,272 @@ mod tests {
     }
 
     #[test]
-    fn test_git_tab_properties() {
+    fn test_git_tab_status_context() {
+        let temp = TempDir::new().unwrap();
+        assert_eq!(GitTab::new(1, temp.path()).status_context(), None);
+
+        let temp = init_test_repo();
+        let tab = GitTab::new(1, temp.path());
+        let context = tab.status_context().expect("repo is open");
+        assert!(context.starts_with("⎇ "));
+        assert!(context.ends_with(" ↑0↓0"));
+    }
+
+    #[test]
+    fn test_git_tab_reports_failed_stage() {
+        let temp = init_test_repo();
+        let mut tab = GitTab::new(1, temp.path());
+        fs::write(temp.path().join("test.txt"), "hello").expect("write file");
+        tab.state.lock().refresh();
+
+        // Another git process holds the index lock
+        fs::write(temp.path().join(".git/index.lock"), "").expect("lock index");
+        let key = crossterm::event::KeyEvent::from(KeyCode::Char(' '));
+        let response = tab.handle_message(&Message::Key(key));
+        assert!(matches!(
+            response,
+            Some(Message::Notify(Notification { ref message, .. })) if message.starts_with("Failed")
+        ));
+        assert!(tab.state.lock().notice.is_none());
+    }
+
+    #[test]
+    fn test_git_tab_discard_untracked_after_confirming() {
+        let temp = init_test_repo();
+        fs::write(temp.path().join("scratch.txt"), "x").expect("write file");
+        let tab = GitTab::new(1, temp.path());
+        {
+            let state = tab.state.lock();
+            let entry = state.selected_entry().expect("selected entry");
+            assert_eq!(discard_prompt(&entry).0, "Delete file");
+        }
+
+        tab.handle_key(KeyCode::Char('d'), KeyModifiers::NONE);
+        assert_eq!(tab.help_hint(), "y:discard  n:cancel");
+        // Other keys leave the question open
+        tab.handle_key(KeyCode::Char('j'), KeyModifiers::NONE);
+        tab.handle_key(KeyCode::Esc, KeyModifiers::NONE);
+        assert!(tab.state.lock().pending_discard.is_none());
+        assert!(temp.path().join("scratch.txt").exists());
+
+        tab.handle_key(KeyCode::Char('d'), KeyModifiers::NONE);
+        tab.handle_key(KeyCode::Char('y'), KeyModifiers::NONE);
+        assert!(!temp.path().join("scratch.txt").exists());
+        assert!(tab.state.lock().untracked.is_empty());
+    }
+
+    #[test]
+    fn test_git_tab_resolves_conflicts_region_by_region() {
         let temp = init_test_repo();
+        fs::write(
+            temp.path().join("a.txt"),
+            "<<<<<<< HEAD\none\n=======\n1\n>>>>>>> b\nmid\n<<<<<<< HEAD\ntwo\n=======\n2\n>>>>>>> b\n",
+        )
+        .expect("write file");
         let tab = GitTab::new(1, temp.path());
 
+        tab.handle_key(KeyCode::Char('c'), KeyModifiers::NONE);
+        assert_eq!(
+            tab.help_hint(),
+            "o:ours  t:theirs  b:both  ]/[:conflict  Esc:back"
+        );
+        tab.handle_key(KeyCode::Char(']'), KeyModifiers::NONE);
+        tab.handle_key(KeyCode::Char('t'), KeyModifiers::NONE);
+        {
+            let state = tab.state.lock();
+            let view = state.conflict_view.as_ref().expect("conflicts left");
+            assert_eq!(view.conflicts.len(), 1);
+            assert_eq!(view.selected, 0);
+        }
+
+        tab.handle_key(KeyCode::Char('o'), KeyModifiers::NONE);
+        assert_eq!(
+            fs::read_to_string(temp.path().join("a.txt")).expect("read file"),
+            "one\nmid\n2\n"
+        );
+        let mut state = tab.state.lock();
+        assert!(state.conflict_view.is_none());
+        assert!(state.notice.take().is_some());
+    }
+
+    #[test]
+    fn test_git_tab_commit_overlay() {
+        let temp = init_test_repo();
+        fs::write(temp.path().join("a.txt"), "one\n").expect("write file");
+        fs::create_dir_all(temp.path().join(".saorsa")).expect("create .saorsa");
+        fs::write(
+            temp.path().join(".saorsa/commit-template"),
+            "fix: \n\n# Explain why\n",
+        )
+        .expect("write template");
+        let mut tab = GitTab::new(1, temp.path());
+
+        // Nothing is staged yet
+        tab.handle_key(KeyCode::Char('C'), KeyModifiers::SHIFT);
+        assert!(tab.state.lock().commit_editor.is_none());
+
+        tab.handle_key(KeyCode::Char('s'), KeyModifiers::NONE);
+        tab.handle_key(KeyCode::Char('C'), KeyModifiers::SHIFT);
+        for c in "typo?".chars() {
+            let key = crossterm::event::KeyEvent::from(KeyCode::Char(c));
+            tab.handle_message(&Message::Key(key));
+        }
+        tab.handle_key(KeyCode::Char('o'), KeyModifiers::CONTROL);
+        assert_eq!(
+            tab.help_hint(),
+            "Ctrl+S:commit  Ctrl+O:sign-off  Esc:cancel"
+        );
+        tab.handle_key(KeyCode::Char('s'), KeyModifiers::CONTROL);
+
+        let state = tab.state.lock();
+        assert!(state.commit_editor.is_none());
+        assert!(state.staged.is_empty());
+        let raw = Repository::open(temp.path()).expect("open repo");
+        let head = raw.head().expect("head").peel_to_commit().expect("commit");
+        assert_eq!(
+            head.message(),
+            Some("fix: typo?\n\nSigned-off-by: Test <test@test.com>\n")
+        );
+    }
+
+    #[test]
+    fn test_git_tab_properties() {
+        let temp = init_test_repo();
+        let mut tab = GitTab::new(1, temp.path());
+
         assert_eq!(tab.id(), 1);
         assert_eq!(tab.title(), "Git");
         assert!(!tab.can_close());
         assert!(tab.icon().is_some());
+        tab.set_closable(true);
+        assert!(tab.can_close());
+    }
+
+    #[test]
+    fn test_relative_time() {
+        let now = Utc::now();
+        assert_eq!(relative_time(now, now), "just now");
+        assert_eq!(
+            relative_time(now - chrono::Duration::minutes(1), now),
+            "1 minute ago"
+        );
+        assert_eq!(
+            relative_time(now - chrono::Duration::hours(5), now),
+            "5 hours ago"
+        );
+        assert_eq!(
+            relative_time(now - chrono::Duration::days(3), now),
+            "3 days ago"
+        );
+        assert_eq!(
+            relative_time(now - chrono::Duration::days(800), now),
+            "2 years ago"
+        );
+    }
+
+    #[test]
+    fn test_git_tab_history_view() {
+        let temp = init_test_repo();
+        fs::write(temp.path().join("test.txt"), "hello\n").expect("write file");
+        {
+            let repo = Repository::open(temp.path()).expect("open repo");
+            let mut index = repo.index().expect("index");
+            index.add_path(Path::new("test.txt")).expect("add test.txt");
+            index.write().expect("write index");
+            let tree = repo
+                .find_tree(index.write_tree().expect("write tree"))
+                .expect("find tree");
+            let sig = repo.signature().expect("signature");
+            repo.commit(Some("HEAD"), &sig, &sig, "initial", &tree, &[])
+                .expect("commit");
+        }
+
+        let tab = GitTab::new(1, temp.path());
+        tab.handle_key(KeyCode::Char('l'), KeyModifiers::NONE);
+        {
+            let state = tab.state.lock();
+            assert_eq!(state.view, GitView::History);
+            assert_eq!(state.commits.len(), 1);
+            assert_eq!(state.graph_rows[0].cells, "●");
+            assert!(state.history_exhausted);
+            assert_eq!(state.current_diff.hunks.len(), 1);
+            assert!(state.current_diff.hunks[0]
+                .header
+                .starts_with("test.txt @@"));
+        }
+
+        tab.handle_key(KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(tab.state.lock().view, GitView::Status);
+    }
+
+    #[test]
+    fn test_git_tab_history_graph_fits_the_pane() {
+        use ratatui::backend::TestBackend;
+        use ratatui::Terminal;
+
+        let temp = init_test_repo();
+        {
+            let repo = Repository::open(temp.path()).expect("open repo");
+            let sig = repo.signature().expect("signature");
+            let tree = repo
+                .find_tree(repo.index().expect("index").write_tree().expect("tree"))
+                .expect("find tree");
+            let first = repo
+                .commit(Some("HEAD"), &sig, &sig, "first", &tree, &[])
+                .expect("commit");
+            let parent = repo.find_commit(first).expect("find commit");
+            repo.commit(Some("HEAD"), &sig, &sig, "second", &tree, &[&parent])
+                .expect("commit");
+        }
+        let tab = GitTab::new(1, temp.path());
+        tab.handle_key(KeyCode::Char('l'), KeyModifiers::NONE);
+        // The unselected second row draws its graph in lane colors
+        tab.state.lock().graph_rows[1].cells = "│ ".repeat(60) + "●";
+
+        let mut terminal = Terminal::new(TestBackend::new(40, 6)).expect("terminal");
+        terminal
+            .draw(|frame| {
+                let state = tab.state.lock();
+                render_history_panel(frame, frame.area(), &state, &Theme::dark());
+            })
+            .expect("draw");
+        let buffer = terminal.backend().buffer();
+        let row: String = (0..40).map(|x| buffer[(x, 2)].symbol()).collect();
+        assert!(row.contains("first"), "summary pushed off: {row:?}");
+        // The right border is untouched
+        assert_eq!(buffer[(39, 2)].symbol(), buffer[(39, 3)].symbol());
+    }
+
+    #[test]
+    fn test_git_tab_stage_hunk_from_diff() {
+        let temp = init_test_repo();
+        let original: String = (1..=20).map(|i| format!("line {}\n", i)).collect();
+        fs::write(temp.path().join("a.txt"), &original).expect("write file");
+        {
+            let repo = Repository::open(temp.path()).expect("open repo");
+            let mut index = repo.index().expect("index");
+            index.add_path(Path::new("a.txt")).expect("add a.txt");
+            index.write().expect("write index");
+            let tree = repo
+                .find_tree(index.write_tree().expect("write tree"))
+                .expect("find tree");
+            let sig = repo.signature().expect("signature");
+            repo.commit(Some("HEAD"), &sig, &sig, "initial", &tree, &[])
+                .expect("commit");
+        }
+        let edited = original
+            .replace("line 1\n", "line one\n")
+            .replace("line 20\n", "line twenty\n");
+        fs::write(temp.path().join("a.txt"), edited).expect("edit file");
+
+        let tab = GitTab::new(1, temp.path());
+        tab.handle_key(KeyCode::Right, KeyModifiers::NONE);
+        tab.handle_key(KeyCode::Char(']'), KeyModifiers::NONE);
+        assert_eq!(tab.state.lock().diff_state.selected_hunk, 1);
+        tab.handle_key(KeyCode::Char(' '), KeyModifiers::NONE);
+
+        let state = tab.state.lock();
+        assert_eq!(state.staged.len(), 1);
+        assert_eq!(state.unstaged.len(), 1);
     }
 }
@@ -1,6 +1,9 @@
 //! Diff viewer widget with syntax highlighting
+//!
+//! Diffs render either unified (one column, `+`/`-` prefixes) or side by
+//! side, with old content on the left and new content on the right.
 
-use crate::repo::Diff;
+use crate::repo::{Diff, DiffHunk, DiffLine};
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
@@ -14,6 +17,8 @@ pub struct DiffWidget<'a> {
     scroll_offset: u16,
     focused: bool,
     show_line_numbers: bool,
+    selected_hunk: Option<usize>,
+    side_by_side: bool,
 }
 
 impl<'a> DiffWidget<'a> {
@@ -24,6 +29,8 @@ impl<'a> DiffWidget<'a> {
             scroll_offset: 0,
             focused: false,
             show_line_numbers: true,
+            selected_hunk: None,
+            side_by_side: false,
         }
     }
 
@@ -45,12 +52,24 @@ impl<'a> DiffWidget<'a> {
         self
     }
 
+    /// Highlight the header of the given hunk
+    pub fn selected_hunk(mut self, hunk: Option<usize>) -> Self {
+        self.selected_hunk = hunk;
+        self
+    }
+
+    /// Render old and new content in separate columns
+    pub fn side_by_side(mut self, side_by_side: bool) -> Self {
+        self.side_by_side = side_by_side;
+        self
+    }
+
     /// Get the total number of lines in the diff
     pub fn total_lines(&self) -> usize {
         self.diff
             .hunks
             .iter()
-            .map(|h| 1 + h.lines.len()) // +1 for header
+            .map(|h| 1 + hunk_rows(h, self.side_by_side)) // +1 for header
             .sum()
     }
 }
@@ -85,6 +104,17 @@ impl Widget for DiffWidget<'_> {
             return;
         }
 
+        if self.side_by_side {
+            render_side_by_side(
+                self.diff,
+                self.scroll_offset,
+                self.selected_hunk,
+                inner,
+                buf,
+            );
+            return;
+        }
+
         // Calculate line number width
         let max_lineno = self
             .diff
@@ -105,16 +135,14 @@ impl Widget for DiffWidget<'_> {
         let mut y = inner.y;
         let mut line_idx: u16 = 0;
 
-        'outer: for hunk in &self.diff.hunks {
+        'outer: for (hunk_idx, hunk) in self.diff.hunks.iter().enumerate() {
+            let header_style = hunk_header_style(self.selected_hunk == Some(hunk_idx));
             // Skip lines before scroll offset
             if line_idx < self.scroll_offset {
                 line_idx += 1;
                 // Check if we need to skip hunk header
                 if line_idx > self.scroll_offset {
                     // Render hunk header
-                    let header_style = Style::default()
-                        .fg(Color::Cyan)
-                        .add_modifier(Modifier::BOLD);
                     let header = &hunk.header;
                     let x = inner.x + lineno_width;
                     buf.set_string(x, y, header, header_style);
@@ -125,9 +153,6 @@ impl Widget for DiffWidget<'_> {
                 }
             } else {
                 // Render hunk header
-                let header_style = Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD);
                 let header = &hunk.header;
                 let x = inner.x + lineno_width;
                 buf.set_string(x, y, header, header_style);
@@ -201,6 +226,242 @@ impl Widget for DiffWidget<'_> {
     }
 }
 
+/// One row of a side-by-side diff
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SplitRow<'a> {
+    /// Old-side line (context or removal)
+    pub old: Option<&'a DiffLine>,
+    /// New-side line (context or addition)
+    pub new: Option<&'a DiffLine>,
+}
+
+impl SplitRow<'_> {
+    /// Gutter marker: `+`/`-` when only one side has a line, `|` otherwise
+    fn gutter(&self) -> char {
+        match (self.old, self.new) {
+            (None, Some(_)) => '+',
+            (Some(_), None) => '-',
+            _ => '|',
+        }
+    }
+}
+
+/// Pair a hunk's lines into side-by-side rows
+///
+/// Context lines appear on both sides. A run of removals followed by a run
+/// of additions is zipped row by row so edited lines sit next to each
+/// other; the longer run continues with a blank opposite side.
+pub(crate) fn split_rows(hunk: &DiffHunk) -> Vec<SplitRow<'_>> {
+    let mut rows = Vec::new();
+    let mut removed: Vec<&DiffLine> = Vec::new();
+    let mut added: Vec<&DiffLine> = Vec::new();
+
+    fn flush<'a>(
+        rows: &mut Vec<SplitRow<'a>>,
+        removed: &mut Vec<&'a DiffLine>,
+        added: &mut Vec<&'a DiffLine>,
+    ) {
+        for i in 0..removed.len().max(added.len()) {
+            rows.push(SplitRow {
+                old: removed.get(i).copied(),
+                new: added.get(i).copied(),
+            });
+        }
+        removed.clear();
+        added.clear();
+    }
+
+    for line in &hunk.lines {
+        match line.origin {
+            '-' => {
+                // A removal after additions starts a new change block
+                if !added.is_empty() {
+                    flush(&mut rows, &mut removed, &mut added);
+                }
+                removed.push(line);
+            }
+            '+' => added.push(line),
+            _ => {
+                flush(&mut rows, &mut removed, &mut added);
+                rows.push(SplitRow {
+                    old: Some(line),
+                    new: Some(line),
+                });
+            }
+        }
+    }
+    flush(&mut rows, &mut removed, &mut added);
+
+    rows
+}
+
+/// Number of body rows a hunk occupies in the given layout
+pub(crate) fn hunk_rows(hunk: &DiffHunk, side_by_side: bool) -> usize {
+    if side_by_side {
+        split_rows(hunk).len()
+    } else {
+        hunk.lines.len()
+    }
+}
+
+/// Render a diff as two columns separated by a `|`/`+`/`-` gutter
+///
+/// Both columns get the same width and long lines are truncated with `…`
+/// on either side, so rows stay aligned however the area is resized.
+pub(crate) fn render_side_by_side(
+    diff: &Diff,
+    scroll: u16,
+    selected_hunk: Option<usize>,
+    area: Rect,
+    buf: &mut Buffer,
+) {
+    const GUTTER: u16 = 3;
+    let column = area.width.saturating_sub(GUTTER) / 2;
+    if column == 0 || area.height == 0 {
+        return;
+    }
+
+    let max_lineno = diff
+        .hunks
+        .iter()
+        .flat_map(|h| h.lines.iter())
+        .filter_map(|l| l.new_lineno.max(l.old_lineno))
+        .max()
+        .unwrap_or(1);
+    let lineno_width = max_lineno.to_string().len();
+
+    let bottom = area.y + area.height;
+    let mut y = area.y;
+    let mut row_idx: usize = 0;
+    let scroll = scroll as usize;
+
+    for (hunk_idx, hunk) in diff.hunks.iter().enumerate() {
+        let rows = split_rows(hunk);
+        if row_idx + 1 + rows.len() <= scroll {
+            row_idx += 1 + rows.len();
+            continue;
+        }
+
+        if row_idx >= scroll {
+            let style = hunk_header_style(selected_hunk == Some(hunk_idx));
+            buf.set_stringn(area.x, y, &hunk.header, area.width as usize, style);
+            y += 1;
+            if y >= bottom {
+                return;
+            }
+        }
+        row_idx += 1;
+
+        for row in rows {
+            if row_idx < scroll {
+                row_idx += 1;
+                continue;
+            }
+
+            render_split_side(buf, area.x, y, column, lineno_width, row.old, false);
+            let gutter_style = match row.gutter() {
+                '+' => Style::default().fg(Color::Green),
+                '-' => Style::default().fg(Color::Red),
+                _ => Style::default().fg(Color::DarkGray),
+            };
+            buf.set_string(
+                area.x + column,
+                y,
+                format!(" {} ", row.gutter()),
+                gutter_style,
+            );
+            render_split_side(
+                buf,
+                area.x + column + GUTTER,
+                y,
+                column,
+                lineno_width,
+                row.new,
+                true,
+            );
+
+            y += 1;
+            row_idx += 1;
+            if y >= bottom {
+                return;
+            }
+        }
+    }
+}
+
+/// Render one column of a side-by-side row
+fn render_split_side(
+    buf: &mut Buffer,
+    x: u16,
+    y: u16,
+    width: u16,
+    lineno_width: usize,
+    line: Option<&DiffLine>,
+    new_side: bool,
+) {
+    let Some(line) = line else {
+        return;
+    };
+
+    let lineno = if new_side {
+        line.new_lineno
+    } else {
+        line.old_lineno
+    };
+    let prefix = match lineno {
+        Some(n) => format!("{:>w$} ", n, w = lineno_width),
+        None => " ".repeat(lineno_width + 1),
+    };
+    let prefix_len = (prefix.len() as u16).min(width);
+    buf.set_stringn(
+        x,
+        y,
+        &prefix,
+        prefix_len as usize,
+        Style::default().fg(Color::DarkGray),
+    );
+
+    let style = match line.origin {
+        '+' => Style::default().fg(Color::Green).bg(Color::Rgb(0, 40, 0)),
+        '-' => Style::default().fg(Color::Red).bg(Color::Rgb(40, 0, 0)),
+        _ => Style::default().fg(Color::White),
+    };
+    let content_width = width.saturating_sub(prefix_len) as usize;
+    buf.set_string(
+        x + prefix_len,
+        y,
+        truncate_to_width(line.content.trim_end_matches('\n'), content_width),
+        style,
+    );
+}
+
+/// Truncate `text` to `width` characters, marking the cut with `…`
+fn truncate_to_width(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    if width == 0 {
+        return String::new();
+    }
+    let mut out: String = text.chars().take(width - 1).collect();
+    out.push('…');
+    out
+}
+
+/// Style for a hunk header line, inverted when the hunk is selected
+pub(crate) fn hunk_header_style(selected: bool) -> Style {
+    if selected {
+        Style::default()
+            .fg(Color::Black)
+            .bg(Color::Cyan)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD)
+    }
+}
+
 /// State for the diff widget
 #[derive(Debug, Default)]
 pub struct DiffWidgetState {
@@ -208,6 +469,14 @@ pub struct DiffWidgetState {
     pub scroll: u16,
     /// Total lines in the diff
     pub total_lines: usize,
+    /// Index of the hunk under the cursor
+    pub selected_hunk: usize,
+    /// Render old and new content in separate columns
+    pub side_by_side: bool,
+    /// Line offset of each hunk header
+    hunk_starts: Vec<usize>,
+    /// Body rows per hunk as (unified, side-by-side)
+    hunk_rows: Vec<(usize, usize)>,
 }
 
 impl DiffWidgetState {
@@ -216,25 +485,96 @@ impl DiffWidgetState {
         DiffWidgetState::default()
     }
 
+    /// Reset the state for a newly loaded diff
+    pub fn set_diff(&mut self, diff: &Diff) {
+        self.hunk_rows = diff
+            .hunks
+            .iter()
+            .map(|h| (hunk_rows(h, false), hunk_rows(h, true)))
+            .collect();
+        self.layout();
+        self.scroll = 0;
+        self.selected_hunk = 0;
+    }
+
+    /// Switch between unified and side-by-side rendering
+    ///
+    /// The hunk under the cursor stays at the top of the view.
+    pub fn toggle_side_by_side(&mut self, visible_height: u16) {
+        self.side_by_side = !self.side_by_side;
+        self.layout();
+        self.select_hunk(self.selected_hunk, visible_height);
+    }
+
+    /// Recompute hunk offsets and the line total for the current layout
+    fn layout(&mut self) {
+        self.hunk_starts.clear();
+        let mut offset = 0;
+        for &(unified, split) in &self.hunk_rows {
+            self.hunk_starts.push(offset);
+            offset += 1 + if self.side_by_side { split } else { unified };
+        }
+        self.total_lines = offset;
+    }
+
+    /// Number of hunks in the current diff
+    pub fn hunk_count(&self) -> usize {
+        self.hunk_starts.len()
+    }
+
+    /// Select a hunk and scroll its header to the top of the view
+    pub fn select_hunk(&mut self, index: usize, visible_height: u16) {
+        let Some(last) = self.hunk_starts.len().checked_sub(1) else {
+            return;
+        };
+        self.selected_hunk = index.min(last);
+        let max_scroll = self.total_lines.saturating_sub(visible_height as usize);
+        self.scroll = self.hunk_starts[self.selected_hunk].min(max_scroll) as u16;
+    }
+
+    /// Move the cursor to the next hunk
+    pub fn next_hunk(&mut self, visible_height: u16) {
+        self.select_hunk(self.selected_hunk + 1, visible_height);
+    }
+
+    /// Move the cursor to the previous hunk
+    pub fn prev_hunk(&mut self, visible_height: u16) {
+        self.select_hunk(self.selected_hunk.saturating_sub(1), visible_height);
+    }
+
+    /// Point the cursor at the hunk containing the top visible line
+    fn sync_hunk_to_scroll(&mut self) {
+        let top = self.scroll as usize;
+        self.selected_hunk = self
+            .hunk_starts
+            .iter()
+            .rposition(|&start| start <= top)
+            .unwrap_or(0);
+    }
+
     /// Scroll down by the given amount
     pub fn scroll_down(&mut self, amount: u16, visible_height: u16) {
         let max_scroll = self.total_lines.saturating_sub(visible_height as usize) as u16;
         self.scroll = (self.scroll + amount).min(max_scroll);
+        self.sync_hunk_to_scroll();
     }
 
     /// Scroll up by the given amount
     pub fn scroll_up(&mut self, amount: u16) {
         self.scroll = self.scroll.saturating_sub(amount);
+        self.sync_hunk_to_scroll();
     }
 
     /// Scroll to top
     pub fn scroll_to_top(&mut self) {
         self.scroll = 0;
+        self.sync_hunk_to_scroll();
     }
 
     /// Scroll to bottom
     pub fn scroll_to_bottom(&mut self, visible_height: u16) {
         self.scroll = self.total_lines.saturating_sub(visible_height as usize) as u16;
+        self.sync_hunk_to_scroll();
     }
 
     /// Page down
@@ -336,4 +676,161 @@ mod tests {
         let widget = DiffWidget::new(&diff);
         assert_eq!(widget.total_lines(), 0);
     }
+
+    fn two_hunk_diff() -> Diff {
+        let mut diff = sample_diff();
+        let mut second = diff.hunks[0].clone();
+        second.header = "@@ -20,3 +21,4 @@".to_string();
+        diff.hunks.push(second);
+        diff
+    }
+
+    #[test]
+    fn test_diff_state_set_diff() {
+        let mut state = DiffWidgetState::new();
+        state.scroll = 3;
+        state.selected_hunk = 1;
+        state.set_diff(&two_hunk_diff());
+        assert_eq!(state.total_lines, 8);
+        assert_eq!(state.hunk_count(), 2);
+        assert_eq!(state.scroll, 0);
+        assert_eq!(state.selected_hunk, 0);
+    }
+
+    #[test]
+    fn test_diff_state_hunk_navigation() {
+        let mut state = DiffWidgetState::new();
+        state.set_diff(&two_hunk_diff());
+
+        state.next_hunk(2);
+        assert_eq!(state.selected_hunk, 1);
+        assert_eq!(state.scroll, 4);
+
+        state.next_hunk(2);
+        assert_eq!(state.selected_hunk, 1);
+
+        state.prev_hunk(2);
+        assert_eq!(state.selected_hunk, 0);
+        assert_eq!(state.scroll, 0);
+    }
+
+    #[test]
+    fn test_diff_state_scroll_tracks_hunk() {
+        let mut state = DiffWidgetState::new();
+        state.set_diff(&two_hunk_diff());
+
+        state.scroll_down(3, 2);
+        assert_eq!(state.selected_hunk, 0);
+        state.scroll_down(1, 2);
+        assert_eq!(state.selected_hunk, 1);
+        state.scroll_up(1);
+        assert_eq!(state.selected_hunk, 0);
+    }
+
+    fn line(origin: char, content: &str, old: Option<u32>, new: Option<u32>) -> DiffLine {
+        DiffLine {
+            origin,
+            content: format!("{}\n", content),
+            old_lineno: old,
+            new_lineno: new,
+        }
+    }
+
+    fn modified_hunk() -> DiffHunk {
+        DiffHunk {
+            header: "@@ -1,4 +1,4 @@".to_string(),
+            lines: vec![
+                line(' ', "a", Some(1), Some(1)),
+                line('-', "b", Some(2), None),
+                line('-', "c", Some(3), None),
+                line('+', "B", None, Some(2)),
+                line(' ', "d", Some(4), Some(3)),
+                line('+', "e", None, Some(4)),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_split_rows_pairs_changes() {
+        let hunk = modified_hunk();
+        let rows = split_rows(&hunk);
+        let render: Vec<(Option<&str>, char, Option<&str>)> = rows
+            .iter()
+            .map(|r| {
+                (
+                    r.old.map(|l| l.content.trim_end()),
+                    r.gutter(),
+                    r.new.map(|l| l.content.trim_end()),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            render,
+            vec![
+                (Some("a"), '|', Some("a")),
+                (Some("b"), '|', Some("B")),
+                (Some("c"), '-', None),
+                (Some("d"), '|', Some("d")),
+                (None, '+', Some("e")),
+            ]
+        );
+        assert_eq!(hunk_rows(&hunk, true), 5);
+        assert_eq!(hunk_rows(&hunk, false), 6);
+    }
+
+    #[test]
+    fn test_truncate_to_width() {
+        assert_eq!(truncate_to_width("short", 10), "short");
+        assert_eq!(truncate_to_width("longer line", 6), "longe…");
+        assert_eq!(truncate_to_width("anything", 0), "");
+    }
+
+    #[test]
+    fn test_diff_state_toggle_side_by_side() {
+        let diff = Diff {
+            path: PathBuf::from("test.rs"),
+            hunks: vec![modified_hunk(), modified_hunk()],
+        };
+        let mut state = DiffWidgetState::new();
+        state.set_diff(&diff);
+        assert_eq!(state.total_lines, 14);
+
+        state.next_hunk(2);
+        assert_eq!(state.scroll, 7);
+
+        state.toggle_side_by_side(2);
+        assert!(state.side_by_side);
+        assert_eq!(state.total_lines, 12);
+        assert_eq!(state.selected_hunk, 1);
+        assert_eq!(state.scroll, 6);
+
+        state.toggle_side_by_side(2);
+        assert_eq!(state.total_lines, 14);
+        assert_eq!(state.scroll, 7);
+    }
+
+    #[test]
+    fn test_side_by_side_render() {
+        let diff = Diff {
+            path: PathBuf::from("test.rs"),
+            hunks: vec![modified_hunk()],
+        };
+        let widget = DiffWidget::new(&diff).side_by_side(true);
+        assert_eq!(widget.total_lines(), 6);
+
+        let area = Rect::new(0, 0, 31, 8);
+        let mut buf = Buffer::empty(area);
+        widget.render(area, &mut buf);
+
+        let row = |y: u16| -> String {
+            (0..area.width)
+                .map(|x| buf[(x, y)].symbol().to_string())
+                .collect()
+        };
+        // Border, header, then the paired b/B row
+        assert!(row(1).contains("@@ -1,4 +1,4 @@"));
+        assert_eq!(row(3), "│2 b           | 2 B          │");
+        assert_eq!(row(6), "│              + 4 e          │");
+    }
 }
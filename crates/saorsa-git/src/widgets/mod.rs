@@ -2,7 +2,7 @@
 //!
 //! This module provides widgets for displaying git status and diffs.
 
-mod diff;
+pub(crate) mod diff;
 mod status;
 
 pub use diff::{DiffWidget, DiffWidgetState};
@@ -3,13 +3,95 @@
 //! Wraps the sb markdown browser App for integration with the saorsa TUI framework.
 
 use anyhow::Result;
-use crossterm::event::{KeyEvent, MouseEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use parking_lot::Mutex;
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Borders, Paragraph};
-use saorsa_cli_core::{Message, Tab, TabId};
-use sb::{handle_key_event, App};
+use ratatui::widgets::Paragraph;
+use saorsa_cli_core::{
+    AppContext, CoreError, CoreResult, Message, Notification, PaneId, PaneLayout, PaneNode, Tab,
+    TabId,
+};
+use saorsa_ui::renderer::calculate_pane_areas;
+use sb::{handle_key_event, App, Focus, Settings};
+use std::borrow::Cow;
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// Key bindings listed in the coordinator's help overlay
+const KEY_BINDINGS: &[(&str, &str)] = &[
+    ("Tab/Shift+Tab", "Switch focus"),
+    ("Up/Down, j/k", "Navigate tree"),
+    ("Enter", "Open file"),
+    ("Left/Right", "Collapse/expand"),
+    ("e", "Edit mode"),
+    ("Ctrl+S", "Save"),
+    ("t", "Jump to heading"),
+    ("v", "Select in preview"),
+    ("w", "Wrap/scroll long lines"),
+    ("PgUp/PgDn", "Page through preview"),
+    ("gg/G", "Top/bottom of preview"),
+    ("Home/End", "Line text start/end"),
+    ("Ctrl+C/Ctrl+V", "Copy/paste"),
+    ("Esc", "Exit edit mode"),
+    ("n", "New file"),
+    ("I", "Show/hide .gitignored files"),
+    ("O", "Sort tree by name, modified time, size or type"),
+    (".", "Show/hide dotfiles"),
+    ("g", "Go to path"),
+    ("F3", "Split raw editor and preview"),
+    ("Alt+Z", "Zoom the focused pane"),
+    ("Ctrl+P", "Find file by name"),
+    ("d", "Delete"),
+    ("u", "Undo delete/move"),
+    ("F2", "File picker"),
+    ("?", "Toggle help"),
+    ("q", "Close tab"),
+];
+
+/// Pane holding the file tree
+const TREE_PANE: PaneId = 0;
+
+/// Pane holding the preview or raw editor
+const CONTENT_PANE: PaneId = 1;
+
+/// Pane holding the rendered preview beside the raw editor in split view
+const PREVIEW_PANE: PaneId = 2;
+
+/// The tab's panes, with `zoomed` filling the whole tab if it is set
+fn layout_of(app: &App, zoomed: Option<PaneId>) -> PaneLayout {
+    let mut layout = if app.show_left_pane {
+        PaneLayout::new(PaneNode::vsplit(
+            app.left_pane_width,
+            vec![PaneNode::leaf(TREE_PANE), content_node(app)],
+        ))
+    } else {
+        PaneLayout::new(content_node(app))
+    };
+    layout.zoomed = zoomed;
+    layout.focused = Some(focused_pane(app));
+    layout
+}
+
+/// Pane holding the keyboard focus
+fn focused_pane(app: &App) -> PaneId {
+    match app.focus {
+        Focus::Left => TREE_PANE,
+        Focus::Preview if app.split_view_active() => PREVIEW_PANE,
+        Focus::Editor | Focus::Preview => CONTENT_PANE,
+    }
+}
+
+/// Right-hand side of the layout: one pane, or editor and preview in split view
+fn content_node(app: &App) -> PaneNode {
+    if app.split_view_active() {
+        PaneNode::vsplit(
+            50,
+            vec![PaneNode::leaf(CONTENT_PANE), PaneNode::leaf(PREVIEW_PANE)],
+        )
+    } else {
+        PaneNode::leaf(CONTENT_PANE)
+    }
+}
 
 /// Markdown browser tab wrapping the sb App
 ///
@@ -20,6 +102,8 @@ pub struct SbTab {
     title: String,
     app: Mutex<App>,
     focused: bool,
+    /// Pane maximized over the others, until zoomed again or focus moves
+    zoomed: Option<PaneId>,
 }
 
 impl SbTab {
@@ -34,13 +118,19 @@ impl SbTab {
     ///
     /// Returns an error if the App fails to initialize (e.g., invalid path).
     pub fn new(id: TabId, root: impl Into<PathBuf>) -> Result<Self> {
-        let root = root.into();
-        let app = App::new(root)?;
+        Self::with_settings(id, root, Settings::load())
+    }
+
+    /// Creates a new SbTab that reads and saves `settings` instead of the
+    /// user's own, e.g. `Settings::default()` to keep them in memory only
+    pub fn with_settings(id: TabId, root: impl Into<PathBuf>, settings: Settings) -> Result<Self> {
+        let app = App::with_settings(root.into(), settings)?;
         Ok(Self {
             id,
             title: "Files".to_string(),
             app: Mutex::new(app),
             focused: false,
+            zoomed: None,
         })
     }
 
@@ -64,15 +154,76 @@ impl SbTab {
         if handle_key_event(&mut app, key).is_none() {
             return Some(Message::Quit);
         }
+        // sb's `?` help is shown by the coordinator's overlay instead
+        if app.show_help {
+            app.show_help = false;
+            return Some(Message::ToggleHelp);
+        }
         None
     }
 
     /// Handle a mouse event
     ///
     /// Routes mouse events to the internal sb App.
-    pub fn handle_mouse(&self, _mouse: MouseEvent) -> Option<Message> {
-        // Mouse handling would go here if sb supports it
-        // For now, pass through without action
+    /// Handle a mouse event over the file tree
+    ///
+    /// As in the sb binary, a click selects the entry under the pointer
+    /// (`Ctrl`+click adds it to the multi-selection), a double click opens
+    /// it and dragging it moves it.
+    pub fn handle_mouse(&self, mouse: MouseEvent) -> Option<Message> {
+        let mut app = self.app.lock();
+        let (column, row) = (mouse.column, mouse.row);
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) if app.tree_select_at(column, row) => {
+                app.focus = Focus::Left;
+                if mouse.modifiers.contains(KeyModifiers::CONTROL) {
+                    app.tree_toggle_selection();
+                } else {
+                    app.tree_clear_selection();
+                    if let Err(e) = app.tree_mouse_down(column, row, Instant::now()) {
+                        app.status = format!("Open failed: {e}");
+                    }
+                }
+            }
+            MouseEventKind::Drag(MouseButton::Left) => {
+                app.tree_mouse_drag(column, row);
+            }
+            MouseEventKind::Up(_) => app.tree_mouse_up(),
+            _ => {}
+        }
+        None
+    }
+
+    /// Handle a mouse wheel scroll
+    ///
+    /// Over the file tree the tree view scrolls; elsewhere the wheel moves
+    /// the raw editor's or the preview's cursor, as in the sb binary.
+    pub fn handle_scroll(&self, lines: i16, area_hint: Option<PaneId>) -> Option<Message> {
+        let mut app = self.app.lock();
+        let count = usize::from(lines.unsigned_abs());
+        let down = lines > 0;
+
+        if area_hint == Some(TREE_PANE) && app.show_left_pane {
+            if down {
+                app.left_state.scroll_down(count);
+            } else {
+                app.left_state.scroll_up(count);
+            }
+        } else if app.show_raw_editor {
+            let code = if down { KeyCode::Down } else { KeyCode::Up };
+            for _ in 0..count {
+                app.editor
+                    .handle_key_event(KeyEvent::new(code, KeyModifiers::NONE));
+            }
+        } else if app.opened.is_some() {
+            for _ in 0..count {
+                if down {
+                    app.move_cursor_down();
+                } else {
+                    app.move_cursor_up();
+                }
+            }
+        }
         None
     }
 
@@ -96,15 +247,39 @@ impl Tab for SbTab {
         &self.title
     }
 
+    fn display_title(&self) -> Cow<'_, str> {
+        let app = self.app.lock();
+        let Some(name) = app.opened.as_ref().and_then(|path| path.file_name()) else {
+            return Cow::Borrowed(&self.title);
+        };
+        let dirty = if app.has_unsaved_changes() { "*" } else { "" };
+        Cow::Owned(format!(
+            "{}: {}{}",
+            self.title,
+            name.to_string_lossy(),
+            dirty
+        ))
+    }
+
     fn icon(&self) -> Option<&str> {
         Some("\u{1F4C1}") // Folder emoji
     }
 
+    fn is_dirty(&self) -> bool {
+        self.app.lock().has_unsaved_changes()
+    }
+
+    fn identity(&self) -> Option<String> {
+        let root = self.root();
+        let root = root.canonicalize().unwrap_or(root);
+        Some(format!("files:{}", root.display()))
+    }
+
     fn can_close(&self) -> bool {
         true
     }
 
-    fn focus(&mut self) {
+    fn focus(&mut self, _ctx: &AppContext) {
         self.focused = true;
         // Could resume any paused operations here
     }
@@ -116,9 +291,15 @@ impl Tab for SbTab {
         app.pause_video();
     }
 
-    fn view(&self, frame: &mut Frame, area: Rect) {
+    fn on_close(&mut self) {
+        self.focused = false;
+        self.app.lock().release_resources();
+    }
+
+    fn view(&self, frame: &mut Frame, area: Rect, ctx: &AppContext) -> CoreResult<()> {
         // Lock the app for rendering
         let mut app = self.app.lock();
+        let theme = &ctx.theme;
 
         // Calculate layout
         let main_chunks = Layout::default()
@@ -129,7 +310,7 @@ impl Tab for SbTab {
             ])
             .split(area);
 
-        let chunks = Layout::default()
+        let mut chunks = Layout::default()
             .direction(Direction::Horizontal)
             .constraints(if app.show_left_pane {
                 let left_width = app.calculate_left_pane_width(main_chunks[0].width);
@@ -137,76 +318,37 @@ impl Tab for SbTab {
             } else {
                 [Constraint::Length(0), Constraint::Min(40)]
             })
-            .split(main_chunks[0]);
+            .split(main_chunks[0])
+            .to_vec();
+
+        // A zoomed pane takes the whole content area from the other side
+        let zoomed = layout_of(&app, self.zoomed).zoomed_pane();
+        let hidden = Rect::new(main_chunks[0].x, main_chunks[0].y, 0, 0);
+        match zoomed {
+            Some(TREE_PANE) => chunks = vec![main_chunks[0], hidden],
+            Some(_) => chunks = vec![hidden, main_chunks[0]],
+            None => {}
+        }
 
         // Render left pane (file tree) if visible
-        if app.show_left_pane {
-            let left_border = if matches!(app.focus, sb::Focus::Left) {
-                Color::Cyan
-            } else {
-                Color::Blue
-            };
-            let left_block = Block::default()
-                .title("Files")
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(left_border));
-
-            // Clone tree items to avoid borrow conflict with tree state
-            let tree_items = app.left_tree.clone();
-
-            // Render tree widget
-            if let Ok(tree) = tui_tree_widget::Tree::new(&tree_items) {
-                let tree = tree
-                    .block(left_block)
-                    .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
-                frame.render_stateful_widget(tree, chunks[0], &mut app.left_state);
-            } else {
-                // Fallback if tree fails
-                frame.render_widget(left_block, chunks[0]);
-            }
+        if app.show_left_pane && !chunks[0].is_empty() {
+            sb::view::render_tree_pane(frame, chunks[0], &mut app, theme)
+                .map_err(|e| CoreError::Render(e.to_string()))?;
         }
 
-        // Render right pane (preview or editor)
-        let right_border = if matches!(app.focus, sb::Focus::Preview) {
-            Color::Cyan
-        } else {
-            Color::Blue
+        // Render right pane (preview, editor, or both in split view)
+        let mut content = PaneLayout::new(content_node(&app));
+        content.zoomed = zoomed;
+        let halves = match calculate_pane_areas(&content, chunks[1])[..] {
+            [(_, edit), (_, view)] => (edit, view),
+            _ => (chunks[1], chunks[1]),
         };
-
-        if app.show_raw_editor {
-            let block = Block::default()
-                .title("Edit (raw)")
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Green));
-            frame.render_widget(block.clone(), chunks[1]);
-            let inner = block.inner(chunks[1]);
-            let view = app.editor.view();
-            frame.render_widget(view, inner);
-        } else {
-            // Render preview
-            let text = app.editor.text();
-            if let Some(path) = app.opened.as_ref() {
-                if let Ok(preview) = sb::preview::Preview::from_markdown(path, &text) {
-                    sb::preview::render_preview(frame, chunks[1], &preview);
-                } else {
-                    let block = Block::default()
-                        .title("Preview")
-                        .borders(Borders::ALL)
-                        .border_style(Style::default().fg(right_border));
-                    let content = Paragraph::new("(preview error)").block(block);
-                    frame.render_widget(content, chunks[1]);
-                }
-            } else {
-                let block = Block::default()
-                    .title("Preview")
-                    .borders(Borders::ALL)
-                    .border_style(Style::default().fg(right_border));
-                let content = Paragraph::new("Select a file to preview").block(block);
-                frame.render_widget(content, chunks[1]);
-            }
-        }
+        // A preview that fails to parse is drawn as a note and reported
+        let result = sb::view::render_content(frame, chunks[1], halves, &mut app, theme)
+            .map_err(|e| CoreError::Render(format!("{e:#}")));
 
         // Render status bar
+        let hex_status = app.hex_status();
         let file_label = app
             .opened
             .as_ref()
@@ -214,79 +356,100 @@ impl Tab for SbTab {
             .and_then(|s| s.to_str())
             .unwrap_or("(no file)");
 
-        let status_text = format!(" {} | {} | ? help", file_label, app.status);
+        let status_text = match hex_status {
+            Some(bytes) => format!(" {} | {} | {} | ? help", file_label, app.status, bytes),
+            None => format!(" {} | {} | ? help", file_label, app.status),
+        };
         let status = Paragraph::new(status_text)
             .style(
                 Style::default()
-                    .fg(Color::Black)
-                    .bg(Color::Cyan)
+                    .fg(theme.colors.background)
+                    .bg(theme.colors.accent)
                     .add_modifier(Modifier::BOLD),
             )
             .alignment(Alignment::Center);
         frame.render_widget(status, main_chunks[1]);
+        result
+    }
 
-        // Render overlays if active
-        if app.show_help {
-            render_help_overlay(frame, area);
-        }
+    fn tick(&mut self) {
+        // Drain background loaders and file-change notifications
+        self.app.lock().poll_background_tasks();
+    }
+
+    fn take_notification(&mut self) -> Option<Notification> {
+        self.app.lock().last_error.take().map(Notification::error)
+    }
+
+    fn tick_interval(&self) -> Option<Duration> {
+        self.app.lock().desired_tick_interval()
+    }
+
+    fn progress(&self) -> Option<String> {
+        self.app.lock().background_activity().map(str::to_string)
+    }
+
+    fn status_context(&self) -> Option<String> {
+        self.app.lock().git_branch.clone()
+    }
+
+    fn help_hint(&self) -> &str {
+        "e:edit  Ctrl+S:save  t:outline  ?:keys"
+    }
+
+    fn key_bindings(&self) -> Vec<(&str, &str)> {
+        KEY_BINDINGS.to_vec()
+    }
+
+    fn pane_layout(&self) -> Option<PaneLayout> {
+        Some(layout_of(&self.app.lock(), self.zoomed))
     }
 
     fn handle_message(&mut self, message: &Message) -> Option<Message> {
         match message {
-            Message::Key(key) => self.handle_key(*key),
+            Message::Key(key) => {
+                let response = self.handle_key(*key);
+                // Moving focus to another pane ends the zoom, as in tmux
+                if self.zoomed != Some(focused_pane(&self.app.lock())) {
+                    self.zoomed = None;
+                }
+                response
+            }
             Message::Mouse(mouse) => self.handle_mouse(*mouse),
+            Message::Scroll { lines, area_hint } => self.handle_scroll(*lines, *area_hint),
+            Message::FocusPane(pane) => {
+                let mut app = self.app.lock();
+                app.focus = match *pane {
+                    TREE_PANE if app.show_left_pane => Focus::Left,
+                    CONTENT_PANE if app.split_view_active() => {
+                        app.show_raw_editor = true;
+                        Focus::Editor
+                    }
+                    CONTENT_PANE if app.show_raw_editor && app.opened.is_some() => Focus::Editor,
+                    CONTENT_PANE => Focus::Preview,
+                    PREVIEW_PANE if app.split_view_active() => {
+                        app.show_raw_editor = false;
+                        Focus::Preview
+                    }
+                    _ => return None,
+                };
+                if self.zoomed != Some(*pane) {
+                    self.zoomed = None;
+                }
+                None
+            }
+            Message::ToggleZoom => {
+                let app = self.app.lock();
+                let mut layout = layout_of(&app, self.zoomed);
+                layout.toggle_zoom(focused_pane(&app));
+                self.zoomed = layout.zoomed_pane();
+                None
+            }
             _ => None,
         }
     }
 }
 
-/// Render a centered help overlay
-fn render_help_overlay(frame: &mut Frame, area: Rect) {
-    use ratatui::widgets::{Clear, Wrap};
-
-    let help = [
-        "sb - Markdown TUI",
-        "",
-        "Navigation:",
-        "  Tab/Shift+Tab  Switch focus",
-        "  Up/Down, j/k   Navigate tree",
-        "  Enter          Open file",
-        "  Left/Right     Collapse/expand",
-        "",
-        "Editing:",
-        "  e              Edit mode",
-        "  Ctrl+S         Save",
-        "  Esc            Exit edit mode",
-        "",
-        "Files:",
-        "  n              New file",
-        "  d              Delete",
-        "  F2             File picker",
-        "",
-        "Other:",
-        "  ?              Toggle help",
-        "  q              Close tab",
-    ]
-    .join("\n");
-
-    let w = area.width.min(50);
-    let h = area.height.min(20);
-    let x = area.x + (area.width.saturating_sub(w)) / 2;
-    let y = area.y + (area.height.saturating_sub(h)) / 2;
-    let popup = Rect {
-        x,
-        y,
-        width: w,
-        height: h,
-    };
-
-    frame.render_widget(Clear, popup);
-    let paragraph = Paragraph::new(help)
-        .block(Block::default().title(" Help ").borders(Borders::ALL))
-        .wrap(Wrap { trim: false });
-    frame.render_widget(paragraph, popup);
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -295,7 +458,7 @@ mod tests {
     #[test]
     fn test_sb_tab_creation() {
         let dir = tempdir().expect("create temp dir");
-        let tab = SbTab::new(1, dir.path()).expect("create tab");
+        let tab = SbTab::with_settings(1, dir.path(), Settings::default()).expect("create tab");
 
         assert_eq!(tab.id(), 1);
         assert_eq!(tab.title(), "Files");
@@ -310,20 +473,196 @@ mod tests {
         assert_eq!(tab.title(), "My Files");
     }
 
+    #[test]
+    fn test_sb_tab_display_title_shows_open_file() {
+        let dir = tempdir().expect("create temp dir");
+        let file = dir.path().join("notes.md");
+        std::fs::write(&file, "# Notes\n").expect("write file");
+        let tab = SbTab::with_settings(1, dir.path(), Settings::default()).expect("create tab");
+        assert_eq!(tab.display_title(), "Files");
+
+        {
+            let mut app = tab.app.lock();
+            app.editor.set_text("# Notes\n");
+            app.last_saved_text = Some("# Notes\n".to_string());
+            app.opened = Some(file);
+        }
+        assert_eq!(tab.display_title(), "Files: notes.md");
+
+        assert!(!tab.is_dirty());
+
+        tab.app.lock().editor.set_text("# Notes\nmore\n");
+        assert_eq!(tab.display_title(), "Files: notes.md*");
+        assert!(tab.is_dirty());
+    }
+
+    #[test]
+    fn test_sb_tab_double_click_opens_tree_entry() {
+        use ratatui::backend::TestBackend;
+        use ratatui::Terminal;
+        use saorsa_cli_core::{MessageBus, Theme};
+
+        let dir = tempdir().expect("create temp dir");
+        std::fs::write(dir.path().join("notes.md"), "# Notes\n").expect("write file");
+        let mut tab = SbTab::with_settings(1, dir.path(), Settings::default()).expect("create tab");
+        {
+            let mut app = tab.app.lock();
+            app.refresh_tree().expect("refresh tree");
+            app.left_state.open(vec![dir.path().display().to_string()]);
+        }
+        let ctx = AppContext::new(Theme::dark(), MessageBus::new(8));
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).expect("terminal");
+        terminal
+            .draw(|frame| {
+                let _ = tab.view(frame, frame.area(), &ctx);
+            })
+            .expect("draw");
+        let buffer = terminal.backend().buffer();
+        let (column, row) = (0..buffer.area.height)
+            .find_map(|y| {
+                let text_at = |x: u16| {
+                    (x..x + 8)
+                        .map(|x| buffer[(x, y)].symbol())
+                        .collect::<String>()
+                };
+                (0..40).find(|&x| text_at(x) == "notes.md").map(|x| (x, y))
+            })
+            .expect("entry drawn");
+        let click = Message::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        });
+
+        tab.handle_message(&click);
+        assert!(tab.opened_file().is_none());
+        tab.handle_message(&click);
+        assert_eq!(tab.opened_file(), Some(dir.path().join("notes.md")));
+    }
+
+    #[test]
+    fn test_sb_tab_reports_failed_save() {
+        let dir = tempdir().expect("create temp dir");
+        let mut tab = SbTab::with_settings(1, dir.path(), Settings::default()).expect("create tab");
+        assert!(tab.take_notification().is_none());
+
+        {
+            let mut app = tab.app.lock();
+            app.opened = Some(dir.path().join("gone/notes.md"));
+            assert!(app.save().is_err());
+        }
+        let notification = tab.take_notification().expect("notification");
+        assert_eq!(notification.level, saorsa_cli_core::NotifyLevel::Error);
+        assert!(notification.message.starts_with("Save failed"));
+        assert!(tab.take_notification().is_none());
+    }
+
     #[test]
     fn test_sb_tab_icon() {
         let dir = tempdir().expect("create temp dir");
-        let tab = SbTab::new(1, dir.path()).expect("create tab");
+        let tab = SbTab::with_settings(1, dir.path(), Settings::default()).expect("create tab");
 
         assert!(tab.icon().is_some());
     }
 
+    #[test]
+    fn test_sb_tab_focus_pane() {
+        let dir = tempdir().expect("create temp dir");
+        let mut tab = SbTab::with_settings(1, dir.path(), Settings::default()).expect("create tab");
+
+        let layout = tab.pane_layout().expect("layout");
+        assert_eq!(layout.pane_ids(), vec![TREE_PANE, CONTENT_PANE]);
+
+        tab.handle_message(&Message::FocusPane(CONTENT_PANE));
+        assert_eq!(tab.app.lock().focus, Focus::Preview);
+        tab.handle_message(&Message::FocusPane(TREE_PANE));
+        assert_eq!(tab.app.lock().focus, Focus::Left);
+    }
+
+    #[test]
+    fn test_sb_tab_split_view_adds_preview_pane() {
+        let dir = tempdir().expect("create temp dir");
+        let mut tab = SbTab::with_settings(1, dir.path(), Settings::default()).expect("create tab");
+        {
+            let mut app = tab.app.lock();
+            app.editor.set_text("# Notes\n\nbody\n");
+            app.opened = Some(dir.path().join("notes.md"));
+            app.toggle_split_view();
+        }
+
+        let layout = tab.pane_layout().expect("layout");
+        assert_eq!(
+            layout.pane_ids(),
+            vec![TREE_PANE, CONTENT_PANE, PREVIEW_PANE]
+        );
+
+        tab.handle_message(&Message::FocusPane(PREVIEW_PANE));
+        assert_eq!(tab.app.lock().focus, Focus::Preview);
+        assert!(!tab.app.lock().show_raw_editor);
+        tab.handle_message(&Message::FocusPane(CONTENT_PANE));
+        assert_eq!(tab.app.lock().focus, Focus::Editor);
+        assert!(tab.app.lock().show_raw_editor);
+    }
+
+    #[test]
+    fn test_sb_tab_zooms_focused_pane() {
+        let dir = tempdir().expect("create temp dir");
+        let mut tab = SbTab::with_settings(1, dir.path(), Settings::default()).expect("create tab");
+        tab.handle_message(&Message::FocusPane(CONTENT_PANE));
+        assert_eq!(
+            tab.pane_layout().expect("layout").focused,
+            Some(CONTENT_PANE)
+        );
+
+        tab.handle_message(&Message::ToggleZoom);
+        let layout = tab.pane_layout().expect("layout");
+        assert_eq!(layout.zoomed_pane(), Some(CONTENT_PANE));
+        assert_eq!(layout.pane_ids(), vec![TREE_PANE, CONTENT_PANE]);
+        tab.handle_message(&Message::ToggleZoom);
+        assert_eq!(tab.pane_layout().expect("layout").zoomed_pane(), None);
+
+        // Focusing another pane ends the zoom
+        tab.handle_message(&Message::ToggleZoom);
+        tab.handle_message(&Message::FocusPane(TREE_PANE));
+        assert_eq!(tab.pane_layout().expect("layout").zoomed_pane(), None);
+    }
+
+    #[test]
+    fn test_sb_tab_wheel_scrolls_preview() {
+        let dir = tempdir().expect("create temp dir");
+        let mut tab = SbTab::with_settings(1, dir.path(), Settings::default()).expect("create tab");
+        let text: String = (1..=40).map(|i| format!("line {i}\n")).collect();
+        {
+            let mut app = tab.app.lock();
+            app.editor.set_text(&text);
+            app.opened = Some(dir.path().join("notes.md"));
+            app.preview_viewport = 5;
+        }
+
+        let scroll = |lines| Message::Scroll {
+            lines,
+            area_hint: Some(CONTENT_PANE),
+        };
+        for _ in 0..3 {
+            tab.handle_message(&scroll(3));
+        }
+        {
+            let app = tab.app.lock();
+            assert_eq!(app.preview_cursor, 9);
+            assert_eq!(app.preview_scroll, 5);
+        }
+
+        tab.handle_message(&scroll(-3));
+        assert_eq!(tab.app.lock().preview_cursor, 6);
+    }
+
     #[test]
     fn test_sb_tab_focus_blur() {
         let dir = tempdir().expect("create temp dir");
-        let mut tab = SbTab::new(1, dir.path()).expect("create tab");
+        let mut tab = SbTab::with_settings(1, dir.path(), Settings::default()).expect("create tab");
 
-        tab.focus();
+        tab.focus(&AppContext::default());
         assert!(tab.focused);
 
         tab.blur();
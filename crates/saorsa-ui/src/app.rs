@@ -8,9 +8,16 @@
 //! The App struct provides:
 //!
 //! - **Tab Management**: Adding, removing, and navigating between tabs
-//! - **Theme Support**: Customizable theming for the entire application
+//! - **Theme Support**: Customizable theming for the entire application,
+//!   quantized to 256 colors on terminals without truecolor
 //! - **Message Bus**: Publish-subscribe messaging for component communication
-//! - **Status Bar**: Configurable status information display
+//! - **Shared Context**: The [`AppContext`] handed to every tab as it gains
+//!   focus and renders, carrying the adapted theme, the message bus and the
+//!   [`AppConfig`]
+//! - **Status Bar**: Configurable status information display, including a
+//!   spinner for background work reported by tabs
+//! - **Notifications**: Timed toasts for failures and other events, see
+//!   [`App::notify`]
 //!
 //! # Example
 //!
@@ -33,12 +40,47 @@
 //!     }
 //! }
 //! ```
+//!
+//! # Headless use
+//!
+//! [`App::dispatch_all`] and [`App::render_to_buffer`] drive the app without a
+//! terminal, which is how tab switching and layout are tested:
+//!
+//! ```
+//! use saorsa_ui::App;
+//! use saorsa_cli_core::Message;
+//!
+//! let mut app = App::new();
+//! app.set_status_left("NORMAL");
+//! app.dispatch_all(&[Message::NextTab, Message::ToggleHelp]);
+//!
+//! let buffer = app.render_to_buffer(60, 10);
+//! let status_row: String = (0..60).map(|x| buffer[(x, 9)].symbol()).collect();
+//! assert!(status_row.contains("NORMAL"));
+//! ```
 
-use crate::renderer::AppLayout;
+use crate::notifications::{Notifications, NOTIFICATION_TIMEOUT};
+use crate::renderer::{
+    adjacent_pane, calculate_pane_areas, AppLayout, LayoutConfig, TabBarPosition,
+};
 use crate::tab_manager::TabManager;
-use crate::widgets::{StatusBar, TabBar};
+use crate::widgets::status_bar::SPINNER_INTERVAL;
+use crate::widgets::{ConfirmDialog, HelpOverlay, StatusBar, TabBar, TabHit, Toasts};
+use crossterm::event::{KeyCode, MouseButton, MouseEventKind};
+use ratatui::backend::TestBackend;
 use ratatui::prelude::*;
-use saorsa_cli_core::{AppCoordinator, CoreResult, Message, MessageBus, Tab, TabId, Theme};
+use saorsa_cli_core::{
+    to_monochrome, AppConfig, AppContext, AppCoordinator, ColorDepth, CoreResult, Message,
+    MessageBus, NotifyLevel, Recorder, Tab, TabId, Theme,
+};
+use std::cell::{Cell, RefCell};
+use std::time::{Duration, Instant};
+
+/// Quiet period after the last resize before tabs are told the new size
+pub const RESIZE_DEBOUNCE: Duration = Duration::from_millis(50);
+
+/// Rows scrolled by one notch of the mouse wheel
+pub const SCROLL_LINES: i16 = 3;
 
 /// Main application state
 ///
@@ -71,10 +113,10 @@ use saorsa_cli_core::{AppCoordinator, CoreResult, Message, MessageBus, Tab, TabI
 pub struct App {
     /// Manages the collection of tabs
     tab_manager: TabManager,
-    /// Current theme for styling
+    /// Current theme for styling, as configured; the tab manager's
+    /// [`AppContext`] holds it adapted to the terminal's color depth, which
+    /// is what gets drawn
     theme: Theme,
-    /// Message bus for component communication
-    message_bus: MessageBus,
     /// Flag indicating the application should quit
     should_quit: bool,
     /// Left section of status bar (typically mode)
@@ -83,6 +125,29 @@ pub struct App {
     status_center: String,
     /// Right section of status bar (typically help hints)
     status_right: String,
+    /// Explicit progress text set by the application
+    status_progress: Option<String>,
+    /// Current spinner frame, advanced on tick while work is active
+    spinner_frame: usize,
+    /// Whether the active tab's key binding overlay is shown
+    show_help: bool,
+    /// Dirty tab waiting for the user to confirm closing it
+    pending_close: Option<TabId>,
+    /// Toasts drawn over the content until they expire
+    notifications: Notifications,
+    /// Placement of the tab bar and size of the status bar
+    layout_config: LayoutConfig,
+    /// Latest terminal size and when it arrived, held back until resizing settles
+    pending_resize: Option<(u16, u16, Instant)>,
+    /// Frame area of the last render, for mapping clicks to the tab bar
+    last_area: Cell<Rect>,
+    /// Writes every dispatched message while a session is recorded
+    recorder: Option<Recorder>,
+    /// Error the active tab reported the last time it was drawn
+    render_error: RefCell<Option<String>>,
+    /// Render error already shown, so a tab failing on every frame
+    /// notifies once
+    shown_render_error: Option<String>,
 }
 
 impl App {
@@ -99,19 +164,14 @@ impl App {
     /// ```
     #[must_use]
     pub fn new() -> Self {
-        App {
-            tab_manager: TabManager::new(),
-            theme: Theme::dark(),
-            message_bus: MessageBus::new(256),
-            should_quit: false,
-            status_left: String::new(),
-            status_center: String::new(),
-            status_right: "?:help  q:quit".to_string(),
-        }
+        Self::with_theme(Theme::dark())
     }
 
     /// Creates a new app with a custom theme
     ///
+    /// The terminal's color depth is detected with [`ColorDepth::detect`];
+    /// use [`App::set_color_depth`] to override it.
+    ///
     /// # Arguments
     ///
     /// * `theme` - The theme to use for styling
@@ -127,14 +187,30 @@ impl App {
     /// ```
     #[must_use]
     pub fn with_theme(theme: Theme) -> Self {
+        let message_bus = MessageBus::new(256);
+        let mut tab_manager = TabManager::new();
+        tab_manager.set_event_bus(Some(message_bus.clone()));
+        let context = tab_manager.context_mut();
+        *context = AppContext::new(theme.clone(), message_bus);
+        context.set_theme(&theme, ColorDepth::detect());
         App {
-            tab_manager: TabManager::new(),
+            tab_manager,
             theme,
-            message_bus: MessageBus::new(256),
             should_quit: false,
             status_left: String::new(),
             status_center: String::new(),
             status_right: "?:help  q:quit".to_string(),
+            status_progress: None,
+            spinner_frame: 0,
+            show_help: false,
+            pending_close: None,
+            notifications: Notifications::new(),
+            layout_config: LayoutConfig::default(),
+            pending_resize: None,
+            last_area: Cell::new(Rect::default()),
+            recorder: None,
+            render_error: RefCell::new(None),
+            shown_render_error: None,
         }
     }
 
@@ -161,6 +237,17 @@ impl App {
         self.tab_manager.add_tab(tab)
     }
 
+    /// Adds a tab, or focuses an open tab with the same identity
+    ///
+    /// See [`TabManager::add_or_focus`].
+    ///
+    /// # Returns
+    ///
+    /// The TabId of the existing duplicate or the newly added tab.
+    pub fn add_or_focus(&mut self, tab: Box<dyn Tab>) -> TabId {
+        self.tab_manager.add_or_focus(tab)
+    }
+
     /// Removes a tab by ID
     ///
     /// # Arguments
@@ -186,7 +273,9 @@ impl App {
     /// Gets a reference to the message bus
     ///
     /// The message bus can be used to subscribe to messages or send
-    /// messages to all subscribers.
+    /// messages to all subscribers. Besides dispatched messages, it carries
+    /// [`Message::TabOpened`], [`Message::TabFocused`] and
+    /// [`Message::TabClosed`] for every change to the tabs.
     ///
     /// # Example
     ///
@@ -199,7 +288,29 @@ impl App {
     /// ```
     #[must_use]
     pub fn message_bus(&self) -> &MessageBus {
-        &self.message_bus
+        &self.context().bus
+    }
+
+    /// Gets the context passed to tabs as they gain focus and render
+    #[must_use]
+    pub fn context(&self) -> &AppContext {
+        self.tab_manager.context()
+    }
+
+    /// Sets the application settings tabs read from their [`AppContext`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use saorsa_ui::App;
+    /// use saorsa_cli_core::AppConfig;
+    ///
+    /// let mut app = App::new();
+    /// app.set_config(AppConfig { use_trash: true });
+    /// assert!(app.context().config.use_trash);
+    /// ```
+    pub fn set_config(&mut self, config: AppConfig) {
+        self.tab_manager.context_mut().config = config;
     }
 
     /// Sets the status bar left section (typically mode)
@@ -256,6 +367,47 @@ impl App {
         self.status_right = text.into();
     }
 
+    /// Sets or clears the status bar progress segment
+    ///
+    /// While set, the status bar shows an animated spinner next to the
+    /// text. Tabs can also report work via [`Tab::progress`]; an explicit
+    /// value set here takes precedence.
+    ///
+    /// # Arguments
+    ///
+    /// * `progress` - Description of the running task, or `None` to clear
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use saorsa_ui::App;
+    ///
+    /// let mut app = App::new();
+    /// app.set_status_progress(Some("Scanning... 42%"));
+    /// app.set_status_progress(None);
+    /// ```
+    pub fn set_status_progress(&mut self, progress: Option<&str>) {
+        self.status_progress = progress.map(str::to_string);
+    }
+
+    /// Returns the progress text currently shown in the status bar
+    ///
+    /// This is the explicit progress, or else the first tab reporting
+    /// background work (the active tab is asked first).
+    #[must_use]
+    pub fn status_progress(&self) -> Option<String> {
+        if let Some(progress) = &self.status_progress {
+            return Some(progress.clone());
+        }
+        let active = self.tab_manager.active_tab();
+        active.and_then(|tab| tab.progress()).or_else(|| {
+            self.tab_manager
+                .tabs()
+                .iter()
+                .find_map(|tab| tab.progress())
+        })
+    }
+
     /// Sets the theme
     ///
     /// # Arguments
@@ -273,9 +425,62 @@ impl App {
     /// assert_eq!(app.theme().name, "Nord");
     /// ```
     pub fn set_theme(&mut self, theme: Theme) {
+        let context = self.tab_manager.context_mut();
+        let depth = context.color_depth;
+        context.set_theme(&theme, depth);
         self.theme = theme;
     }
 
+    /// Overrides the detected terminal color depth
+    ///
+    /// With [`ColorDepth::Ansi256`] the theme's RGB colors are drawn as
+    /// their nearest 256-color palette entries, and with
+    /// [`ColorDepth::Monochrome`] nothing is colored at all.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use saorsa_ui::App;
+    /// use saorsa_cli_core::ColorDepth;
+    ///
+    /// let mut app = App::new();
+    /// app.set_color_depth(ColorDepth::TrueColor);
+    /// assert_eq!(app.color_depth(), ColorDepth::TrueColor);
+    /// ```
+    pub fn set_color_depth(&mut self, depth: ColorDepth) {
+        self.tab_manager.context_mut().set_theme(&self.theme, depth);
+    }
+
+    /// Gets the color depth used for drawing
+    #[must_use]
+    pub fn color_depth(&self) -> ColorDepth {
+        self.context().color_depth
+    }
+
+    /// Sets the tab bar position and status bar height
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use saorsa_ui::{App, LayoutConfig, TabBarPosition};
+    ///
+    /// let mut app = App::new();
+    /// app.set_layout_config(LayoutConfig {
+    ///     tab_bar: TabBarPosition::Left,
+    ///     ..LayoutConfig::default()
+    /// });
+    /// assert_eq!(app.layout_config().tab_bar, TabBarPosition::Left);
+    /// ```
+    pub fn set_layout_config(&mut self, config: LayoutConfig) {
+        self.layout_config = config;
+    }
+
+    /// Gets the current layout configuration
+    #[must_use]
+    pub fn layout_config(&self) -> &LayoutConfig {
+        &self.layout_config
+    }
+
     /// Gets the tab manager
     ///
     /// # Example
@@ -318,27 +523,214 @@ impl App {
     /// terminal.draw(|f| app.render(f))?;
     /// ```
     pub fn render(&self, frame: &mut Frame) {
-        let layout = AppLayout::new(frame.area());
+        self.last_area.set(frame.area());
+        let layout = AppLayout::with_config(frame.area(), &self.layout_config);
 
         // Render tab bar
-        let tab_bar = TabBar::new(
-            self.tab_manager.tabs(),
-            self.tab_manager.active_index(),
-            &self.theme,
-        );
-        frame.render_widget(tab_bar, layout.tab_bar);
+        frame.render_widget(self.tab_bar(), layout.tab_bar);
+
+        // Render active tab content; tick turns a failure into a notification
+        let active = self.tab_manager.active_tab();
+        let failed = active.and_then(|tab| {
+            tab.view(frame, layout.content, self.context())
+                .err()
+                .map(|e| format!("{}: {e}", tab.title()))
+        });
+        self.render_error.replace(failed);
+
+        // Render the help overlay on top of the tab content
+        if let (true, Some(tab)) = (self.show_help, active) {
+            let bindings = tab.key_bindings();
+            let overlay = HelpOverlay::new(tab.title(), &bindings, &self.context().theme);
+            frame.render_widget(overlay, layout.content);
+        }
+
+        // Ask before closing a tab with unsaved changes
+        if let Some(tab) = self.pending_close.and_then(|id| self.tab_manager.get(id)) {
+            let message = format!(
+                "{} has unsaved changes. Close it anyway?",
+                tab.display_title()
+            );
+            let dialog = ConfirmDialog::new("Close tab", &message, &self.context().theme);
+            frame.render_widget(dialog, layout.content);
+        }
 
-        // Render active tab content
-        if let Some(tab) = self.tab_manager.active_tab() {
-            tab.view(frame, layout.content);
+        // Notifications go over everything else in the content area
+        if !self.notifications.is_empty() {
+            let toasts = Toasts::new(&self.notifications, &self.context().theme);
+            frame.render_widget(toasts, layout.content);
         }
 
-        // Render status bar
-        let status = StatusBar::new(&self.theme)
+        // Render status bar, preferring the active tab's own hint
+        let progress = self.status_progress();
+        let right = active
+            .map(|tab| tab.help_hint())
+            .filter(|hint| !hint.is_empty())
+            .unwrap_or(&self.status_right);
+        // The active tab's context, such as its Git branch, follows the center text
+        let center = match active.and_then(|tab| tab.status_context()) {
+            Some(context) if self.status_center.is_empty() => context,
+            Some(context) => format!("{}  {context}", self.status_center),
+            None => self.status_center.clone(),
+        };
+        let status = StatusBar::new(&self.context().theme)
             .left(&self.status_left)
-            .center(&self.status_center)
-            .right(&self.status_right);
+            .progress(progress.as_deref(), self.spinner_frame)
+            .center(&center)
+            .right(right);
         frame.render_widget(status, layout.status_bar);
+
+        // Tabs and plugins may still draw their own colors; drop them all
+        if self.context().color_depth == ColorDepth::Monochrome {
+            to_monochrome(frame.buffer_mut());
+        }
+    }
+
+    /// Dispatches each message in order, as if they arrived one at a time
+    ///
+    /// # Arguments
+    ///
+    /// * `msgs` - Messages to dispatch
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use saorsa_ui::App;
+    /// use saorsa_cli_core::{AppCoordinator, Message};
+    ///
+    /// let mut app = App::new();
+    /// app.dispatch_all(&[Message::ToggleHelp, Message::Quit]);
+    /// assert!(app.should_quit());
+    /// ```
+    pub fn dispatch_all(&mut self, msgs: &[Message]) {
+        for msg in msgs {
+            self.dispatch(msg.clone());
+        }
+    }
+
+    /// Renders the app off-screen into a `width` x `height` buffer
+    ///
+    /// Draws through a ratatui [`TestBackend`], exactly as [`App::render`]
+    /// draws to a terminal, and returns the buffer for inspection.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use saorsa_ui::App;
+    ///
+    /// let buffer = App::new().render_to_buffer(40, 5);
+    /// assert_eq!(buffer.area.width, 40);
+    /// ```
+    #[must_use]
+    pub fn render_to_buffer(&self, width: u16, height: u16) -> Buffer {
+        let Ok(mut terminal) = Terminal::new(TestBackend::new(width, height));
+        let Ok(_) = terminal.draw(|frame| self.render(frame));
+        terminal.backend().buffer().clone()
+    }
+
+    /// Returns whether the key binding overlay is shown
+    #[must_use]
+    pub fn is_help_visible(&self) -> bool {
+        self.show_help
+    }
+
+    /// Clears the badge of the active tab, which the user is looking at
+    fn clear_active_badge(&mut self) {
+        if let Some(tab) = self.tab_manager.active_tab_mut() {
+            tab.clear_badge();
+        }
+    }
+
+    /// Tab bar widget for the current tabs and layout
+    fn tab_bar(&self) -> TabBar<'_> {
+        TabBar::new(
+            self.tab_manager.tabs(),
+            self.tab_manager.active_index(),
+            &self.context().theme,
+        )
+        .vertical(self.layout_config.tab_bar == TabBarPosition::Left)
+        .truncation(self.layout_config.tab_titles)
+        .close_buttons(self.layout_config.tab_close_buttons)
+    }
+
+    /// Shows a notification over the content for [`NOTIFICATION_TIMEOUT`]
+    ///
+    /// Any key press dismisses all notifications early. Tabs can raise
+    /// them too, through [`Message::Notify`] or
+    /// [`Tab::take_notification`]; use [`App::notifications_mut`] for a
+    /// different duration.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - Severity, which picks the toast color
+    /// * `message` - Text to show; only the first line is displayed
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use saorsa_ui::App;
+    /// use saorsa_cli_core::NotifyLevel;
+    ///
+    /// let mut app = App::new();
+    /// app.notify(NotifyLevel::Error, "Could not open Files tab");
+    /// assert_eq!(app.notifications().len(), 1);
+    /// ```
+    pub fn notify(&mut self, level: NotifyLevel, message: impl Into<String>) {
+        self.notifications
+            .push(level, message, NOTIFICATION_TIMEOUT);
+    }
+
+    /// Returns the notifications currently shown
+    #[must_use]
+    pub fn notifications(&self) -> &Notifications {
+        &self.notifications
+    }
+
+    /// Returns the notification queue for pushing or dismissing entries
+    pub fn notifications_mut(&mut self) -> &mut Notifications {
+        &mut self.notifications
+    }
+
+    /// Returns the tab waiting for close confirmation, if any
+    #[must_use]
+    pub fn pending_close(&self) -> Option<TabId> {
+        self.pending_close
+    }
+
+    /// Closes a closeable tab, asking first if it has unsaved changes
+    fn request_close(&mut self, id: TabId) {
+        let Some(tab) = self.tab_manager.get(id) else {
+            return;
+        };
+        if !tab.can_close() {
+            return;
+        }
+        if tab.is_dirty() {
+            self.pending_close = Some(id);
+            return;
+        }
+        self.close_tab(id);
+    }
+
+    /// Removes a tab, quitting once none of the tabs left can be closed
+    ///
+    /// Tabs such as Git stay open for good, so waiting for an empty tab
+    /// list would never quit.
+    fn close_tab(&mut self, id: TabId) {
+        if self.tab_manager.remove_tab(id).is_ok()
+            && !self.tab_manager.tabs().iter().any(|tab| tab.can_close())
+        {
+            self.should_quit = true;
+        }
+    }
+
+    /// Lets the active tab handle a message, dispatching its response
+    fn forward_to_active_tab(&mut self, msg: &Message) {
+        if let Some(tab) = self.tab_manager.active_tab_mut() {
+            if let Some(response) = tab.handle_message(msg) {
+                self.process(response);
+            }
+        }
     }
 
     /// Returns whether the app has any tabs
@@ -372,22 +764,72 @@ impl App {
     pub fn tab_count(&self) -> usize {
         self.tab_manager.len()
     }
-}
 
-impl AppCoordinator for App {
-    fn tabs(&self) -> &[Box<dyn Tab>] {
-        self.tab_manager.tabs()
+    /// Starts writing every dispatched message to `recorder`
+    ///
+    /// Replaces any recording already in progress. Play the file back with
+    /// [`AppCoordinator::replay_from`] to reproduce the session.
+    pub fn start_recording(&mut self, recorder: Recorder) {
+        self.recorder = Some(recorder);
     }
 
-    fn active_tab(&self) -> TabId {
-        self.tab_manager.active_id().unwrap_or(0)
+    /// Stops recording, returning the recorder if one was running
+    pub fn stop_recording(&mut self) -> Option<Recorder> {
+        self.recorder.take()
     }
 
-    fn theme(&self) -> &Theme {
-        &self.theme
+    /// Returns whether dispatched messages are being recorded
+    #[must_use]
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_some()
     }
 
-    fn dispatch(&mut self, msg: Message) {
+    /// Writes `msg` to the recording, stopping it if the write fails
+    fn record(&mut self, msg: &Message) {
+        let Some(recorder) = &mut self.recorder else {
+            return;
+        };
+        if let Err(e) = recorder.record(msg) {
+            self.recorder = None;
+            self.notify(NotifyLevel::Error, format!("Recording stopped: {e}"));
+        }
+    }
+
+    /// Handles a message, including those it gives rise to
+    ///
+    /// Only messages from outside reach [`AppCoordinator::dispatch`] and get
+    /// recorded; the ones handled here on their behalf would be produced
+    /// again on replay.
+    fn process(&mut self, msg: Message) {
+        // Any key dismisses notifications, then goes on as usual
+        if let Message::Key(_) = msg {
+            self.notifications.clear();
+        }
+
+        // The help overlay is modal: keys close it instead of reaching the tab
+        if let (true, Message::Key(key)) = (self.show_help, &msg) {
+            if matches!(
+                key.code,
+                KeyCode::Esc | KeyCode::Char('?') | KeyCode::Char('q') | KeyCode::Enter
+            ) {
+                self.show_help = false;
+            }
+            return;
+        }
+
+        // So is the close confirmation
+        if let (Some(id), Message::Key(key)) = (self.pending_close, &msg) {
+            match key.code {
+                KeyCode::Char('y' | 'Y') | KeyCode::Enter => {
+                    self.pending_close = None;
+                    self.close_tab(id);
+                }
+                KeyCode::Char('n' | 'N' | 'q') | KeyCode::Esc => self.pending_close = None,
+                _ => {}
+            }
+            return;
+        }
+
         let mut handled = false;
         match &msg {
             Message::Quit => {
@@ -396,33 +838,137 @@ impl AppCoordinator for App {
             }
             Message::NextTab => {
                 self.tab_manager.next_tab();
+                self.clear_active_badge();
                 handled = true;
             }
             Message::PrevTab => {
                 self.tab_manager.prev_tab();
+                self.clear_active_badge();
                 handled = true;
             }
             Message::SwitchTab(id) => {
                 let _ = self.tab_manager.switch_to(*id);
+                self.clear_active_badge();
                 handled = true;
             }
             Message::CloseTab(id) => {
-                let _ = self.tab_manager.remove_tab(*id);
+                self.request_close(*id);
                 handled = true;
             }
             Message::ToggleHelp => {
-                // Toggle help hint in status bar
-                if self.status_right.contains("help") {
-                    self.status_right = "Press ? for help".to_string();
-                } else {
-                    self.status_right = "?:help  q:quit".to_string();
-                }
+                self.show_help = !self.show_help;
+                handled = true;
+            }
+            Message::SetTheme(theme) => {
+                self.set_theme(theme.clone());
+                handled = true;
+            }
+            Message::Notify(notification) => {
+                self.notifications
+                    .push_notification(notification.clone(), NOTIFICATION_TIMEOUT);
                 handled = true;
             }
+            Message::SwitchPane(n) => {
+                // Tabs that expose a layout get the resolved pane; others
+                // receive the message as is
+                let layout = self
+                    .tab_manager
+                    .active_tab()
+                    .and_then(|tab| tab.pane_layout());
+                if let Some(layout) = layout {
+                    if let Some(pane) = layout.nth_pane(*n as usize) {
+                        self.forward_to_active_tab(&Message::FocusPane(pane));
+                    }
+                    handled = true;
+                }
+            }
+            Message::FocusAdjacentPane(direction) => {
+                // Resolved by where the panes are drawn; a zoom is looked
+                // through so moving away from a zoomed pane ends it
+                let layout = self
+                    .tab_manager
+                    .active_tab()
+                    .and_then(|tab| tab.pane_layout());
+                if let Some(mut layout) = layout {
+                    layout.zoomed = None;
+                    let content =
+                        AppLayout::with_config(self.last_area.get(), &self.layout_config).content;
+                    let areas = calculate_pane_areas(&layout, content);
+                    let target = layout
+                        .focused
+                        .and_then(|from| adjacent_pane(&areas, from, *direction));
+                    if let Some(pane) = target {
+                        self.forward_to_active_tab(&Message::FocusPane(pane));
+                    }
+                    handled = true;
+                }
+            }
+            Message::Mouse(mouse) if mouse.kind == MouseEventKind::Down(MouseButton::Left) => {
+                // Clicks on the tab bar switch tabs, or close them on the
+                // close button; the hit test measures titles as they were
+                // last drawn
+                let layout = AppLayout::with_config(self.last_area.get(), &self.layout_config);
+                let clicked = self
+                    .tab_bar()
+                    .hit_test(layout.tab_bar, mouse.column, mouse.row)
+                    .and_then(|hit| {
+                        let tab = self.tab_manager.tabs().get(hit.index())?;
+                        Some((hit, tab.id()))
+                    });
+                match clicked {
+                    Some((TabHit::Close(_), id)) => {
+                        self.process(Message::CloseTab(id));
+                        handled = true;
+                    }
+                    Some((TabHit::Title(_), id)) => {
+                        let _ = self.tab_manager.switch_to(id);
+                        self.clear_active_badge();
+                        handled = true;
+                    }
+                    None => {}
+                }
+            }
+            Message::Mouse(mouse)
+                if matches!(
+                    mouse.kind,
+                    MouseEventKind::ScrollUp | MouseEventKind::ScrollDown
+                ) =>
+            {
+                // Wheel events over the content reach the active tab as a
+                // Scroll naming the pane under the pointer
+                let content =
+                    AppLayout::with_config(self.last_area.get(), &self.layout_config).content;
+                let pointer = Position::new(mouse.column, mouse.row);
+                if content.contains(pointer) {
+                    let lines = if mouse.kind == MouseEventKind::ScrollUp {
+                        -SCROLL_LINES
+                    } else {
+                        SCROLL_LINES
+                    };
+                    let area_hint = self
+                        .tab_manager
+                        .active_tab()
+                        .and_then(|tab| tab.pane_layout())
+                        .and_then(|layout| {
+                            calculate_pane_areas(&layout, content)
+                                .into_iter()
+                                .find(|(_, area)| area.contains(pointer))
+                                .map(|(pane, _)| pane)
+                        });
+                    self.process(Message::Scroll { lines, area_hint });
+                    handled = true;
+                }
+            }
+            Message::Resize(width, height) => {
+                // Dragging a window corner emits a storm of these; only the
+                // settled size is forwarded, from tick
+                self.pending_resize = Some((*width, *height, Instant::now()));
+                return;
+            }
             Message::Batch(messages) => {
                 // Process batch messages recursively
                 for m in messages.clone() {
-                    self.dispatch(m);
+                    self.process(m);
                 }
                 return; // Skip broadcasting the batch itself
             }
@@ -433,21 +979,96 @@ impl AppCoordinator for App {
         }
 
         if !handled {
-            if let Some(tab) = self.tab_manager.active_tab_mut() {
-                if let Some(response) = tab.handle_message(&msg) {
-                    self.dispatch(response);
-                }
-            }
+            self.forward_to_active_tab(&msg);
         }
 
         // Broadcast message to all subscribers
-        let _ = self.message_bus.send(msg);
+        let _ = self.context().bus.send(msg);
+    }
+}
+
+impl AppCoordinator for App {
+    /// Closes every tab so players, watchers and background work stop
+    /// before the terminal is restored
+    fn on_exit(&mut self) {
+        self.pending_close = None;
+        self.notifications.clear();
+        self.tab_manager.close_all();
+    }
+
+    fn tabs(&self) -> &[Box<dyn Tab>] {
+        self.tab_manager.tabs()
+    }
+
+    fn active_tab(&self) -> TabId {
+        self.tab_manager.active_id().unwrap_or(0)
+    }
+
+    fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    fn dispatch(&mut self, msg: Message) {
+        self.record(&msg);
+        self.process(msg);
     }
 
     fn tick(&mut self) {
-        if let Some(tab) = self.tab_manager.active_tab_mut() {
+        if let Some((width, height, at)) = self.pending_resize {
+            if at.elapsed() >= RESIZE_DEBOUNCE {
+                self.pending_resize = None;
+                let msg = Message::Resize(width, height);
+                self.forward_to_active_tab(&msg);
+                let _ = self.context().bus.send(msg);
+            }
+        }
+
+        // Tick every tab so hidden tabs can finish background work and badge
+        let mut raised = Vec::new();
+        for tab in self.tab_manager.tabs_mut() {
             tab.tick();
+            raised.extend(std::iter::from_fn(|| tab.take_notification()));
+        }
+        self.clear_active_badge();
+
+        self.notifications.tick();
+        let failed = self.render_error.borrow().clone();
+        if failed != self.shown_render_error {
+            if let Some(message) = &failed {
+                self.notifications
+                    .push(NotifyLevel::Error, message.clone(), NOTIFICATION_TIMEOUT);
+            }
+            self.shown_render_error = failed;
+        }
+        for notification in raised {
+            self.notifications
+                .push_notification(notification, NOTIFICATION_TIMEOUT);
         }
+
+        // Only animate the spinner while something is running
+        if self.status_progress().is_some() {
+            self.spinner_frame = self.spinner_frame.wrapping_add(1);
+        } else {
+            self.spinner_frame = 0;
+        }
+    }
+
+    fn desired_tick_interval(&self) -> Option<Duration> {
+        // Wake for the most demanding tab, the spinner while work runs,
+        // when a held-back resize is due, or when a notification expires
+        let spinner = self.status_progress().map(|_| SPINNER_INTERVAL);
+        let resize = self
+            .pending_resize
+            .map(|(_, _, at)| RESIZE_DEBOUNCE.saturating_sub(at.elapsed()));
+        let expiry = self.notifications.next_expiry();
+        self.tab_manager
+            .tabs()
+            .iter()
+            .filter_map(|tab| tab.tick_interval())
+            .chain(spinner)
+            .chain(resize)
+            .chain(expiry)
+            .min()
     }
 
     fn should_quit(&self) -> bool {
@@ -464,7 +1085,10 @@ impl Default for App {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use saorsa_cli_core::Tab;
+    use saorsa_cli_core::{
+        CoreError, FocusDirection, Notification, PaneId, PaneLayout, PaneNode, Tab, TabBadge,
+    };
+    use std::sync::Arc;
 
     /// Test implementation of the Tab trait
     struct MockTab {
@@ -490,11 +1114,27 @@ mod tests {
             &self.title
         }
 
-        fn focus(&mut self) {}
+        fn focus(&mut self, _ctx: &AppContext) {}
 
         fn blur(&mut self) {}
 
-        fn view(&self, _frame: &mut Frame, _area: Rect) {}
+        fn view(&self, frame: &mut Frame, area: Rect, _ctx: &AppContext) -> CoreResult<()> {
+            frame.render_widget(format!("{} content", self.title), area);
+            Ok(())
+        }
+    }
+
+    fn row_text(buffer: &Buffer, y: u16) -> String {
+        (0..buffer.area.width)
+            .map(|x| buffer[(x, y)].symbol())
+            .collect()
+    }
+
+    fn buffer_text(buffer: &Buffer) -> String {
+        (0..buffer.area.height)
+            .map(|y| row_text(buffer, y))
+            .collect::<Vec<_>>()
+            .join("\n")
     }
 
     #[test]
@@ -585,36 +1225,317 @@ mod tests {
     }
 
     #[test]
-    fn test_app_dispatch_toggle_help() {
-        let mut app = App::new();
-        let initial = app.status_right.clone();
-
+    fn test_app_record_and_replay_session() {
+        use saorsa_cli_core::ReplayPace;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("session.jsonl");
+        let new_app = || {
+            let mut app = App::new();
+            app.add_tab(Box::new(MockTab::new(1, "Tab1")));
+            app.add_tab(Box::new(MockTab::new(2, "Tab2")));
+            app.add_tab(Box::new(MockTab::new(3, "Tab3")));
+            app
+        };
+
+        let mut app = new_app();
+        app.start_recording(Recorder::create(&path).expect("create"));
+        assert!(app.is_recording());
+        app.dispatch(Message::Batch(vec![Message::NextTab, Message::NextTab]));
+        app.dispatch(Message::CloseTab(1));
         app.dispatch(Message::ToggleHelp);
-        assert_ne!(app.status_right, initial);
+        assert!(app.stop_recording().is_some());
+        // Not recorded
+        app.dispatch(Message::Quit);
 
-        app.dispatch(Message::ToggleHelp);
-        // Should toggle back
+        let mut replayed = new_app();
+        let count = replayed
+            .replay_from(&path, ReplayPace::Immediate)
+            .expect("replay");
+        // The batch is recorded once, not as its parts
+        assert_eq!(count, 3);
+        assert_eq!(replayed.active_tab(), 3);
+        assert_eq!(replayed.tab_count(), 2);
+        assert!(replayed.show_help);
+        assert!(!replayed.should_quit());
     }
 
-    #[test]
-    fn test_app_dispatch_none() {
-        let mut app = App::new();
-        app.dispatch(Message::None);
-        // Should not crash or change state
-        assert!(!app.should_quit());
+    /// Tab with configurable close behaviour
+    struct EditedTab {
+        id: TabId,
+        dirty: bool,
+        closeable: bool,
     }
 
-    #[test]
-    fn test_app_dispatch_batch() {
-        let mut app = App::new();
-        app.add_tab(Box::new(MockTab::new(1, "Tab1")));
-        app.add_tab(Box::new(MockTab::new(2, "Tab2")));
-
+    impl Tab for EditedTab {
+        fn id(&self) -> TabId {
+            self.id
+        }
+
+        fn title(&self) -> &str {
+            "Edited"
+        }
+
+        fn can_close(&self) -> bool {
+            self.closeable
+        }
+
+        fn is_dirty(&self) -> bool {
+            self.dirty
+        }
+
+        fn focus(&mut self, _ctx: &AppContext) {}
+
+        fn blur(&mut self) {}
+
+        fn view(&self, _frame: &mut Frame, _area: Rect, _ctx: &AppContext) -> CoreResult<()> {
+            Ok(())
+        }
+    }
+
+    fn key(code: KeyCode) -> Message {
+        Message::Key(crossterm::event::KeyEvent::from(code))
+    }
+
+    #[test]
+    fn test_app_closing_last_tab_quits() {
+        let mut app = App::new();
+        app.add_tab(Box::new(MockTab::new(1, "Tab1")));
+        app.add_tab(Box::new(MockTab::new(2, "Tab2")));
+
+        app.dispatch(Message::CloseTab(2));
+        assert!(!app.should_quit());
+        app.dispatch(Message::CloseTab(1));
+        assert_eq!(app.tab_count(), 0);
+        assert!(app.should_quit());
+
+        // Tabs that can't be closed don't keep the app open
+        let mut app = App::new();
+        app.add_tab(Box::new(MockTab::new(1, "Tab1")));
+        app.add_tab(Box::new(EditedTab {
+            id: 2,
+            dirty: false,
+            closeable: false,
+        }));
+        app.dispatch(Message::CloseTab(1));
+        assert_eq!(app.tab_count(), 1);
+        assert!(app.should_quit());
+    }
+
+    #[test]
+    fn test_app_close_dirty_tab_asks_first() {
+        let mut app = App::new();
+        app.add_tab(Box::new(MockTab::new(1, "Tab1")));
+        app.add_tab(Box::new(EditedTab {
+            id: 2,
+            dirty: true,
+            closeable: true,
+        }));
+
+        app.dispatch(Message::CloseTab(2));
+        assert_eq!(app.pending_close(), Some(2));
+        assert_eq!(app.tab_count(), 2);
+        let text = buffer_text(&app.render_to_buffer(80, 12));
+        assert!(text.contains("Edited has unsaved changes"));
+
+        // Other keys are swallowed while the prompt is up
+        app.dispatch(key(KeyCode::Char('x')));
+        assert_eq!(app.pending_close(), Some(2));
+        app.dispatch(key(KeyCode::Esc));
+        assert_eq!(app.pending_close(), None);
+        assert_eq!(app.tab_count(), 2);
+
+        app.dispatch(Message::CloseTab(2));
+        app.dispatch(key(KeyCode::Char('y')));
+        assert_eq!(app.pending_close(), None);
+        assert_eq!(app.tab_count(), 1);
+    }
+
+    #[test]
+    fn test_app_close_respects_can_close() {
+        let mut app = App::new();
+        app.add_tab(Box::new(MockTab::new(1, "Tab1")));
+        app.add_tab(Box::new(EditedTab {
+            id: 2,
+            dirty: false,
+            closeable: false,
+        }));
+
+        app.dispatch(Message::CloseTab(2));
+        assert_eq!(app.tab_count(), 2);
+        assert_eq!(app.pending_close(), None);
+    }
+
+    #[test]
+    fn test_app_dispatch_toggle_help() {
+        let mut app = App::new();
+        assert!(!app.is_help_visible());
+
+        app.dispatch(Message::ToggleHelp);
+        assert!(app.is_help_visible());
+
+        app.dispatch(Message::ToggleHelp);
+        assert!(!app.is_help_visible());
+    }
+
+    #[test]
+    fn test_app_help_overlay_swallows_keys() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let mut app = App::new();
+        app.add_tab(Box::new(MockTab::new(1, "Tab1")));
+        app.dispatch(Message::ToggleHelp);
+
+        app.dispatch(Message::Key(KeyEvent::new(
+            KeyCode::Char('j'),
+            KeyModifiers::NONE,
+        )));
+        assert!(app.is_help_visible());
+
+        app.dispatch(Message::Key(KeyEvent::new(
+            KeyCode::Esc,
+            KeyModifiers::NONE,
+        )));
+        assert!(!app.is_help_visible());
+    }
+
+    #[test]
+    fn test_app_dispatch_none() {
+        let mut app = App::new();
+        app.dispatch(Message::None);
+        // Should not crash or change state
+        assert!(!app.should_quit());
+    }
+
+    #[test]
+    fn test_app_dispatch_batch() {
+        let mut app = App::new();
+        app.add_tab(Box::new(MockTab::new(1, "Tab1")));
+        app.add_tab(Box::new(MockTab::new(2, "Tab2")));
+
         app.dispatch(Message::Batch(vec![Message::NextTab, Message::ToggleHelp]));
 
         assert_eq!(app.active_tab(), 2);
     }
 
+    #[test]
+    fn test_app_dispatch_all_switches_rendered_tab() {
+        let mut app = App::new();
+        app.add_tab(Box::new(MockTab::new(1, "Tab1")));
+        app.add_tab(Box::new(MockTab::new(2, "Tab2")));
+        assert!(buffer_text(&app.render_to_buffer(60, 10)).contains("Tab1 content"));
+
+        app.dispatch_all(&[Message::NextTab, Message::NextTab, Message::NextTab]);
+        assert_eq!(app.active_tab(), 2);
+        let text = buffer_text(&app.render_to_buffer(60, 10));
+        assert!(text.contains("Tab2 content"));
+        assert!(!text.contains("Tab1 content"));
+    }
+
+    #[test]
+    fn test_app_click_on_truncated_tab_switches() {
+        use crate::widgets::tab_bar::TitleTruncation;
+        use crossterm::event::{KeyModifiers, MouseEvent};
+
+        let mut app = App::new();
+        app.set_layout_config(LayoutConfig {
+            tab_titles: TitleTruncation::Middle,
+            ..LayoutConfig::default()
+        });
+        app.add_tab(Box::new(MockTab::new(1, "docs/guide/install.md")));
+        app.add_tab(Box::new(MockTab::new(2, "src/renderer.rs")));
+        let buffer = app.render_to_buffer(30, 5);
+        assert_eq!(
+            row_text(&buffer, 0).trim_end(),
+            "  docs…l.md   |   src/…r.rs"
+        );
+
+        let click = |column| {
+            Message::Mouse(MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Left),
+                column,
+                row: 0,
+                modifiers: KeyModifiers::NONE,
+            })
+        };
+        app.dispatch(click(16));
+        assert_eq!(app.active_tab(), 2);
+        // The divider belongs to neither tab
+        app.dispatch(click(14));
+        assert_eq!(app.active_tab(), 2);
+        app.dispatch(click(12));
+        assert_eq!(app.active_tab(), 1);
+    }
+
+    #[test]
+    fn test_app_click_on_close_button_closes_tab() {
+        use crossterm::event::{KeyModifiers, MouseEvent};
+
+        let mut app = App::new();
+        app.set_layout_config(LayoutConfig {
+            tab_close_buttons: true,
+            ..LayoutConfig::default()
+        });
+        app.add_tab(Box::new(MockTab::new(1, "Tab1")));
+        app.add_tab(Box::new(MockTab::new(2, "Tab2")));
+        let buffer = app.render_to_buffer(30, 5);
+        assert_eq!(row_text(&buffer, 0).trim_end(), "  Tab1 ×   |   Tab2 ×");
+
+        let click = |column| {
+            Message::Mouse(MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Left),
+                column,
+                row: 0,
+                modifiers: KeyModifiers::NONE,
+            })
+        };
+        // The title still switches tabs
+        app.dispatch(click(3));
+        assert_eq!(app.active_tab(), 1);
+        app.dispatch(click(7));
+        assert_eq!(app.tab_manager.len(), 1);
+        assert_eq!(app.active_tab(), 2);
+    }
+
+    #[test]
+    fn test_app_bus_reports_tab_focus() {
+        let mut app = App::new();
+        let mut rx = app.message_bus().subscribe();
+        app.add_tab(Box::new(MockTab::new(1, "Tab1")));
+        app.add_tab(Box::new(MockTab::new(2, "Tab2")));
+        app.dispatch(Message::NextTab);
+
+        let events: Vec<Message> = std::iter::from_fn(|| rx.try_recv().ok()).collect();
+        assert_eq!(
+            events,
+            vec![
+                Message::TabOpened(1),
+                Message::TabFocused(1),
+                Message::TabOpened(2),
+                Message::TabFocused(2),
+                Message::NextTab,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_app_render_layout_rows() {
+        let mut app = App::new();
+        app.add_tab(Box::new(MockTab::new(1, "Files")));
+        app.set_status_left("NORMAL");
+
+        let buffer = app.render_to_buffer(60, 10);
+        let content_row = (0..10)
+            .find(|&y| row_text(&buffer, y).contains("Files content"))
+            .expect("tab content is drawn");
+        let status_row = (0..10)
+            .find(|&y| row_text(&buffer, y).contains("NORMAL"))
+            .expect("status bar is drawn");
+        assert!(row_text(&buffer, 0).contains("Files"));
+        assert!(content_row > 0);
+        assert_eq!(status_row, 9);
+    }
+
     #[test]
     fn test_app_status_bar() {
         let mut app = App::new();
@@ -625,6 +1546,39 @@ mod tests {
         // Just verify no panics - actual rendering tested in widget tests
     }
 
+    #[test]
+    fn test_app_color_depth_quantizes_theme() {
+        let mut app = App::with_theme(Theme::nord());
+
+        app.set_color_depth(ColorDepth::Ansi256);
+        assert_eq!(app.context().theme, Theme::nord().quantize_to_256());
+        // The configured theme is kept so truecolor can be restored
+        assert_eq!(app.theme(), &Theme::nord());
+
+        app.set_theme(Theme::light());
+        assert_eq!(app.context().theme, Theme::light().quantize_to_256());
+
+        app.set_color_depth(ColorDepth::TrueColor);
+        assert_eq!(app.context().theme, Theme::light());
+    }
+
+    #[test]
+    fn test_app_monochrome_draws_without_colors() {
+        let mut app = App::with_theme(Theme::nord());
+        app.set_color_depth(ColorDepth::Monochrome);
+        assert_eq!(app.context().theme, Theme::monochrome());
+        app.set_status_left("hello");
+
+        let buffer = app.render_to_buffer(40, 10);
+        assert!(buffer
+            .content
+            .iter()
+            .all(|cell| cell.fg == Color::Reset && cell.bg == Color::Reset));
+        // The status bar still stands out
+        let status = &buffer[(0, 9)];
+        assert!(status.modifier.contains(Modifier::REVERSED));
+    }
+
     #[test]
     fn test_app_set_theme() {
         let mut app = App::new();
@@ -651,6 +1605,653 @@ mod tests {
         assert!(!app.tab_manager().is_empty());
     }
 
+    /// Tab that raises an activity badge on every tick
+    struct BusyTab {
+        id: TabId,
+        badge: Option<TabBadge>,
+    }
+
+    impl Tab for BusyTab {
+        fn id(&self) -> TabId {
+            self.id
+        }
+
+        fn title(&self) -> &str {
+            "Busy"
+        }
+
+        fn focus(&mut self, _ctx: &AppContext) {}
+
+        fn blur(&mut self) {}
+
+        fn view(&self, _frame: &mut Frame, _area: Rect, _ctx: &AppContext) -> CoreResult<()> {
+            Ok(())
+        }
+
+        fn tick(&mut self) {
+            self.badge = Some(TabBadge::Dot);
+        }
+
+        fn badge(&self) -> Option<TabBadge> {
+            self.badge
+        }
+
+        fn clear_badge(&mut self) {
+            self.badge = None;
+        }
+    }
+
+    /// Tab that records the context it was last focused and drawn with
+    struct ThemedTab {
+        id: TabId,
+        theme: Arc<std::sync::Mutex<Option<Theme>>>,
+        config: Arc<std::sync::Mutex<Option<AppConfig>>>,
+    }
+
+    impl ThemedTab {
+        fn new(id: TabId) -> Self {
+            ThemedTab {
+                id,
+                theme: Arc::default(),
+                config: Arc::default(),
+            }
+        }
+    }
+
+    impl Tab for ThemedTab {
+        fn id(&self) -> TabId {
+            self.id
+        }
+
+        fn title(&self) -> &str {
+            "Themed"
+        }
+
+        fn focus(&mut self, ctx: &AppContext) {
+            *self.config.lock().expect("config lock") = Some(ctx.config.clone());
+        }
+
+        fn blur(&mut self) {}
+
+        fn view(&self, _frame: &mut Frame, _area: Rect, ctx: &AppContext) -> CoreResult<()> {
+            *self.theme.lock().expect("theme lock") = Some(ctx.theme.clone());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_app_draws_tabs_with_the_current_theme() {
+        let mut app = App::with_theme(Theme::nord());
+        app.set_color_depth(ColorDepth::TrueColor);
+        let tab = ThemedTab::new(1);
+        let theme = tab.theme.clone();
+        app.add_tab(Box::new(tab));
+        let seen = || theme.lock().expect("lock").clone();
+
+        let _ = app.render_to_buffer(80, 24);
+        assert_eq!(seen(), Some(Theme::nord()));
+
+        app.set_theme(Theme::light());
+        let _ = app.render_to_buffer(80, 24);
+        assert_eq!(seen(), Some(Theme::light()));
+
+        app.set_color_depth(ColorDepth::Ansi256);
+        let _ = app.render_to_buffer(80, 24);
+        assert_eq!(seen(), Some(Theme::light().quantize_to_256()));
+    }
+
+    #[test]
+    fn test_app_set_theme_message() {
+        let mut app = App::with_theme(Theme::dark());
+        app.set_color_depth(ColorDepth::TrueColor);
+        let tab = ThemedTab::new(1);
+        let theme = tab.theme.clone();
+        app.add_tab(Box::new(tab));
+
+        app.dispatch(Message::SetTheme(app.theme().toggled()));
+        assert_eq!(app.theme(), &Theme::light());
+        assert_eq!(app.context().theme, Theme::light());
+        let _ = app.render_to_buffer(80, 24);
+        assert_eq!(theme.lock().expect("lock").as_ref(), Some(&Theme::light()));
+    }
+
+    #[test]
+    fn test_app_focuses_tabs_with_the_config() {
+        let mut app = App::new();
+        app.set_config(AppConfig { use_trash: true });
+        let tab = ThemedTab::new(1);
+        let config = tab.config.clone();
+        app.add_tab(Box::new(tab));
+
+        assert_eq!(
+            config.lock().expect("lock").as_ref(),
+            Some(&AppConfig { use_trash: true })
+        );
+    }
+
+    /// Tab that reports background work until told to stop
+    struct WorkingTab {
+        id: TabId,
+        working: bool,
+    }
+
+    impl Tab for WorkingTab {
+        fn id(&self) -> TabId {
+            self.id
+        }
+
+        fn title(&self) -> &str {
+            "Working"
+        }
+
+        fn focus(&mut self, _ctx: &AppContext) {}
+
+        fn blur(&mut self) {}
+
+        fn view(&self, _frame: &mut Frame, _area: Rect, _ctx: &AppContext) -> CoreResult<()> {
+            Ok(())
+        }
+
+        fn progress(&self) -> Option<String> {
+            self.working.then(|| "Scanning...".to_string())
+        }
+    }
+
+    #[test]
+    fn test_app_status_progress_explicit() {
+        let mut app = App::new();
+        assert_eq!(app.status_progress(), None);
+
+        app.tick();
+        assert_eq!(app.spinner_frame, 0);
+
+        app.set_status_progress(Some("Indexing 42%"));
+        app.tick();
+        app.tick();
+        assert_eq!(app.status_progress().as_deref(), Some("Indexing 42%"));
+        assert_eq!(app.spinner_frame, 2);
+
+        app.set_status_progress(None);
+        app.tick();
+        assert_eq!(app.status_progress(), None);
+        assert_eq!(app.spinner_frame, 0);
+    }
+
+    #[test]
+    fn test_app_status_progress_from_hidden_tab() {
+        let mut app = App::new();
+        app.add_tab(Box::new(MockTab::new(1, "Idle")));
+        app.add_tab(Box::new(WorkingTab {
+            id: 2,
+            working: true,
+        }));
+
+        assert_eq!(app.status_progress().as_deref(), Some("Scanning..."));
+        app.tick();
+        assert_eq!(app.spinner_frame, 1);
+    }
+
+    /// Tab that reports the branch it is looking at
+    struct BranchTab {
+        id: TabId,
+    }
+
+    impl Tab for BranchTab {
+        fn id(&self) -> TabId {
+            self.id
+        }
+
+        fn title(&self) -> &str {
+            "Branch"
+        }
+
+        fn focus(&mut self, _ctx: &AppContext) {}
+
+        fn blur(&mut self) {}
+
+        fn view(&self, _frame: &mut Frame, _area: Rect, _ctx: &AppContext) -> CoreResult<()> {
+            Ok(())
+        }
+
+        fn status_context(&self) -> Option<String> {
+            Some("⎇ main ↑2↓0".to_string())
+        }
+    }
+
+    #[test]
+    fn test_app_status_bar_shows_active_tab_context() {
+        let mut app = App::new();
+        app.set_status_center("~/src");
+        app.add_tab(Box::new(MockTab::new(1, "Plain")));
+        app.add_tab(Box::new(BranchTab { id: 2 }));
+
+        let last_row = |app: &App| {
+            let buffer = app.render_to_buffer(80, 10);
+            row_text(&buffer, buffer.area.height - 1)
+        };
+        assert!(!last_row(&app).contains("⎇"));
+        app.tab_manager_mut().switch_to(2).expect("switch");
+        assert!(last_row(&app).contains("~/src  ⎇ main ↑2↓0"));
+    }
+
+    /// Tab that asks to be ticked at a fixed rate
+    struct AnimatedTab {
+        id: TabId,
+        interval: Duration,
+    }
+
+    impl Tab for AnimatedTab {
+        fn id(&self) -> TabId {
+            self.id
+        }
+
+        fn title(&self) -> &str {
+            "Animated"
+        }
+
+        fn focus(&mut self, _ctx: &AppContext) {}
+
+        fn blur(&mut self) {}
+
+        fn view(&self, _frame: &mut Frame, _area: Rect, _ctx: &AppContext) -> CoreResult<()> {
+            Ok(())
+        }
+
+        fn tick_interval(&self) -> Option<Duration> {
+            Some(self.interval)
+        }
+    }
+
+    #[test]
+    fn test_app_desired_tick_interval() {
+        let mut app = App::new();
+        app.add_tab(Box::new(MockTab::new(1, "Idle")));
+        assert_eq!(app.desired_tick_interval(), None);
+
+        app.set_status_progress(Some("Indexing"));
+        assert_eq!(app.desired_tick_interval(), Some(SPINNER_INTERVAL));
+
+        // A hidden tab animating faster than the spinner wins
+        app.add_tab(Box::new(AnimatedTab {
+            id: 2,
+            interval: Duration::from_millis(40),
+        }));
+        assert_eq!(app.desired_tick_interval(), Some(Duration::from_millis(40)));
+
+        app.set_status_progress(None);
+        app.add_tab(Box::new(AnimatedTab {
+            id: 3,
+            interval: Duration::from_secs(5),
+        }));
+        assert_eq!(app.desired_tick_interval(), Some(Duration::from_millis(40)));
+    }
+
+    /// Tab that records every resize it is told about
+    struct ResizeTab {
+        sizes: Arc<std::sync::Mutex<Vec<(u16, u16)>>>,
+    }
+
+    impl Tab for ResizeTab {
+        fn id(&self) -> TabId {
+            1
+        }
+
+        fn title(&self) -> &str {
+            "Resize"
+        }
+
+        fn focus(&mut self, _ctx: &AppContext) {}
+
+        fn blur(&mut self) {}
+
+        fn view(&self, _frame: &mut Frame, _area: Rect, _ctx: &AppContext) -> CoreResult<()> {
+            Ok(())
+        }
+
+        fn handle_message(&mut self, message: &Message) -> Option<Message> {
+            if let Message::Resize(width, height) = message {
+                if let Ok(mut sizes) = self.sizes.lock() {
+                    sizes.push((*width, *height));
+                }
+            }
+            None
+        }
+    }
+
+    #[test]
+    fn test_app_debounces_resize() {
+        let sizes = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut app = App::new();
+        app.add_tab(Box::new(ResizeTab {
+            sizes: sizes.clone(),
+        }));
+        let mut rx = app.message_bus().subscribe();
+
+        app.dispatch_all(&[
+            Message::Resize(80, 24),
+            Message::Resize(90, 30),
+            Message::Resize(100, 40),
+        ]);
+        app.tick();
+        assert!(sizes.lock().expect("sizes").is_empty());
+        assert!(rx.try_recv().is_err());
+        assert!(app
+            .desired_tick_interval()
+            .is_some_and(|interval| interval <= RESIZE_DEBOUNCE));
+
+        // Once resizing settles only the final size is forwarded
+        if let Some((_, _, at)) = app.pending_resize.as_mut() {
+            *at -= RESIZE_DEBOUNCE;
+        }
+        app.tick();
+        assert_eq!(*sizes.lock().expect("sizes"), vec![(100, 40)]);
+        assert!(matches!(rx.try_recv(), Ok(Message::Resize(100, 40))));
+        assert_eq!(app.desired_tick_interval(), None);
+
+        app.tick();
+        assert_eq!(sizes.lock().expect("sizes").len(), 1);
+    }
+
+    /// Tab whose background work fails on the first tick
+    struct FailingTab {
+        failure: Option<Notification>,
+    }
+
+    impl Tab for FailingTab {
+        fn id(&self) -> TabId {
+            1
+        }
+
+        fn title(&self) -> &str {
+            "Failing"
+        }
+
+        fn focus(&mut self, _ctx: &AppContext) {}
+
+        fn blur(&mut self) {}
+
+        fn view(&self, _frame: &mut Frame, _area: Rect, _ctx: &AppContext) -> CoreResult<()> {
+            Ok(())
+        }
+
+        fn take_notification(&mut self) -> Option<Notification> {
+            self.failure.take()
+        }
+    }
+
+    /// Tab whose content cannot be drawn
+    struct BrokenTab;
+
+    impl Tab for BrokenTab {
+        fn id(&self) -> TabId {
+            1
+        }
+
+        fn title(&self) -> &str {
+            "Broken"
+        }
+
+        fn focus(&mut self, _ctx: &AppContext) {}
+
+        fn blur(&mut self) {}
+
+        fn view(&self, _frame: &mut Frame, _area: Rect, _ctx: &AppContext) -> CoreResult<()> {
+            Err(CoreError::Render("unterminated code fence".into()))
+        }
+    }
+
+    #[test]
+    fn test_app_notifies_render_error_once() {
+        let mut app = App::new();
+        app.add_tab(Box::new(BrokenTab));
+        let _ = app.render_to_buffer(60, 10);
+        app.tick();
+        let shown: Vec<_> = app.notifications().iter().cloned().collect();
+        assert_eq!(
+            shown,
+            vec![Notification::error("Broken: unterminated code fence")]
+        );
+
+        // The same failure on later frames is not repeated
+        app.notifications_mut().clear();
+        let _ = app.render_to_buffer(60, 10);
+        app.tick();
+        assert!(app.notifications().is_empty());
+    }
+
+    #[test]
+    fn test_app_notify_renders_and_key_dismisses() {
+        let mut app = App::new();
+        app.add_tab(Box::new(MockTab::new(1, "Files")));
+        app.notify(NotifyLevel::Error, "Could not open Disk tab");
+        assert!(buffer_text(&app.render_to_buffer(60, 10)).contains("Could not open Disk tab"));
+
+        // The key still reaches the tab after dismissing the toast
+        let mut rx = app.message_bus().subscribe();
+        app.dispatch(key(KeyCode::Char('j')));
+        assert!(app.notifications().is_empty());
+        assert!(matches!(rx.try_recv(), Ok(Message::Key(_))));
+        assert!(!buffer_text(&app.render_to_buffer(60, 10)).contains("Could not open"));
+    }
+
+    #[test]
+    fn test_app_notifications_expire() {
+        let mut app = App::new();
+        app.dispatch(Message::Notify(Notification::info("Indexed 12 files")));
+        assert_eq!(app.notifications().len(), 1);
+        assert!(app
+            .desired_tick_interval()
+            .is_some_and(|interval| interval <= NOTIFICATION_TIMEOUT));
+
+        app.notifications_mut().clear();
+        app.notifications_mut()
+            .push(NotifyLevel::Warning, "Brief", Duration::ZERO);
+        assert_eq!(app.desired_tick_interval(), Some(Duration::ZERO));
+        app.tick();
+        assert!(app.notifications().is_empty());
+        assert_eq!(app.desired_tick_interval(), None);
+    }
+
+    #[test]
+    fn test_app_tick_collects_tab_notifications() {
+        let mut app = App::new();
+        app.add_tab(Box::new(FailingTab {
+            failure: Some(Notification::error("Save failed")),
+        }));
+        app.tick();
+        let shown: Vec<_> = app.notifications().iter().cloned().collect();
+        assert_eq!(shown, vec![Notification::error("Save failed")]);
+    }
+
+    /// Scroll messages a tab received, as `(lines, area_hint)`
+    type Scrolls = Arc<std::sync::Mutex<Vec<(i16, Option<PaneId>)>>>;
+
+    /// Tab split into a sidebar and two stacked panes
+    struct PanedTab {
+        focused_pane: Arc<std::sync::Mutex<Option<PaneId>>>,
+        scrolls: Scrolls,
+        zoomed: Option<PaneId>,
+    }
+
+    impl Tab for PanedTab {
+        fn id(&self) -> TabId {
+            1
+        }
+
+        fn title(&self) -> &str {
+            "Panes"
+        }
+
+        fn focus(&mut self, _ctx: &AppContext) {}
+
+        fn blur(&mut self) {}
+
+        fn view(&self, _frame: &mut Frame, _area: Rect, _ctx: &AppContext) -> CoreResult<()> {
+            Ok(())
+        }
+
+        fn pane_layout(&self) -> Option<PaneLayout> {
+            let mut layout = PaneLayout::new(PaneNode::vsplit(
+                25,
+                vec![
+                    PaneNode::leaf(10),
+                    PaneNode::hsplit(70, vec![PaneNode::leaf(20), PaneNode::leaf(30)]),
+                ],
+            ));
+            layout.zoomed = self.zoomed;
+            layout.focused = self.focused_pane.lock().ok().and_then(|pane| *pane);
+            Some(layout)
+        }
+
+        fn handle_message(&mut self, message: &Message) -> Option<Message> {
+            match message {
+                Message::FocusPane(pane) => {
+                    if let Ok(mut focused) = self.focused_pane.lock() {
+                        *focused = Some(*pane);
+                    }
+                }
+                Message::Scroll { lines, area_hint } => {
+                    if let Ok(mut scrolls) = self.scrolls.lock() {
+                        scrolls.push((*lines, *area_hint));
+                    }
+                }
+                Message::ToggleZoom => {
+                    let focused = self.focused_pane.lock().ok().and_then(|pane| *pane);
+                    if let (Some(pane), Some(mut layout)) = (focused, self.pane_layout()) {
+                        layout.toggle_zoom(pane);
+                        self.zoomed = layout.zoomed;
+                    }
+                }
+                _ => {}
+            }
+            None
+        }
+    }
+
+    #[test]
+    fn test_app_switch_pane_focuses_nth_leaf() {
+        let focused = Arc::new(std::sync::Mutex::new(None));
+        let mut app = App::new();
+        app.add_tab(Box::new(PanedTab {
+            focused_pane: focused.clone(),
+            scrolls: Arc::default(),
+            zoomed: None,
+        }));
+
+        app.dispatch(Message::SwitchPane(3));
+        assert_eq!(*focused.lock().expect("focused"), Some(30));
+        app.dispatch(Message::SwitchPane(1));
+        assert_eq!(*focused.lock().expect("focused"), Some(10));
+
+        // Out-of-range positions leave the focus alone
+        app.dispatch(Message::SwitchPane(4));
+        assert_eq!(*focused.lock().expect("focused"), Some(10));
+    }
+
+    #[test]
+    fn test_app_wheel_scrolls_pane_under_pointer() {
+        use crossterm::event::{KeyModifiers, MouseEvent};
+
+        let scrolls = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut app = App::new();
+        app.add_tab(Box::new(PanedTab {
+            focused_pane: Arc::default(),
+            scrolls: scrolls.clone(),
+            zoomed: None,
+        }));
+        let _ = app.render_to_buffer(100, 12);
+
+        let wheel = |kind, column, row| {
+            Message::Mouse(MouseEvent {
+                kind,
+                column,
+                row,
+                modifiers: KeyModifiers::NONE,
+            })
+        };
+        app.dispatch(wheel(MouseEventKind::ScrollDown, 5, 3));
+        app.dispatch(wheel(MouseEventKind::ScrollUp, 60, 2));
+        // The tab bar isn't part of any pane
+        app.dispatch(wheel(MouseEventKind::ScrollDown, 5, 0));
+
+        assert_eq!(
+            *scrolls.lock().expect("scrolls"),
+            vec![(SCROLL_LINES, Some(10)), (-SCROLL_LINES, Some(20))]
+        );
+    }
+
+    #[test]
+    fn test_app_wheel_reaches_zoomed_pane() {
+        use crossterm::event::{KeyModifiers, MouseEvent};
+
+        let scrolls = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut app = App::new();
+        app.add_tab(Box::new(PanedTab {
+            focused_pane: Arc::default(),
+            scrolls: scrolls.clone(),
+            zoomed: None,
+        }));
+        let _ = app.render_to_buffer(100, 12);
+        app.dispatch(Message::SwitchPane(3));
+        app.dispatch(Message::ToggleZoom);
+
+        let wheel = Message::Mouse(MouseEvent {
+            kind: MouseEventKind::ScrollDown,
+            column: 5,
+            row: 3,
+            modifiers: KeyModifiers::NONE,
+        });
+        app.dispatch(wheel.clone());
+        app.dispatch(Message::ToggleZoom);
+        app.dispatch(wheel);
+
+        assert_eq!(
+            *scrolls.lock().expect("scrolls"),
+            vec![(SCROLL_LINES, Some(30)), (SCROLL_LINES, Some(10))]
+        );
+    }
+
+    #[test]
+    fn test_app_focus_adjacent_pane_moves_by_position() {
+        let focused = Arc::new(std::sync::Mutex::new(None));
+        let mut app = App::new();
+        app.add_tab(Box::new(PanedTab {
+            focused_pane: focused.clone(),
+            scrolls: Arc::default(),
+            zoomed: None,
+        }));
+        let _ = app.render_to_buffer(100, 12);
+
+        app.dispatch(Message::SwitchPane(1));
+        app.dispatch(Message::FocusAdjacentPane(FocusDirection::Right));
+        assert_eq!(*focused.lock().expect("focused"), Some(20));
+        app.dispatch(Message::FocusAdjacentPane(FocusDirection::Down));
+        assert_eq!(*focused.lock().expect("focused"), Some(30));
+
+        // Nothing lies further down; the focus stays put
+        app.dispatch(Message::FocusAdjacentPane(FocusDirection::Down));
+        assert_eq!(*focused.lock().expect("focused"), Some(30));
+
+        // A zoomed pane still has its neighbours
+        app.dispatch(Message::ToggleZoom);
+        app.dispatch(Message::FocusAdjacentPane(FocusDirection::Left));
+        assert_eq!(*focused.lock().expect("focused"), Some(10));
+    }
+
+    #[test]
+    fn test_app_tick_badges_hidden_tabs_only() {
+        let mut app = App::new();
+        app.add_tab(Box::new(BusyTab { id: 1, badge: None }));
+        app.add_tab(Box::new(BusyTab { id: 2, badge: None }));
+
+        app.tick();
+        assert_eq!(app.tabs()[0].badge(), None);
+        assert_eq!(app.tabs()[1].badge(), Some(TabBadge::Dot));
+
+        app.dispatch(Message::NextTab);
+        assert_eq!(app.tabs()[1].badge(), None);
+    }
+
     #[test]
     fn test_app_tick() {
         let mut app = App::new();
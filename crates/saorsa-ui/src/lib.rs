@@ -10,6 +10,7 @@
 //! - [`StatusBar`] - Widget for status information
 //! - [`App`] - Main application coordinator implementing [`saorsa_cli_core::AppCoordinator`]
 //! - [`AppLayout`] - Layout calculation utilities
+//! - [`snapshot`] - Rendering widgets to text for snapshot tests
 //!
 //! ## Overview
 //!
@@ -80,11 +81,15 @@
 //! ```
 
 pub mod app;
+pub mod notifications;
 pub mod renderer;
+pub mod snapshot;
 pub mod tab_manager;
 pub mod widgets;
 
 pub use app::App;
-pub use renderer::{calculate_pane_areas, AppLayout};
+pub use notifications::Notifications;
+pub use renderer::{adjacent_pane, calculate_pane_areas, AppLayout, LayoutConfig, TabBarPosition};
+pub use snapshot::{buffer_to_string, render_widget};
 pub use tab_manager::TabManager;
-pub use widgets::{StatusBar, TabBar};
+pub use widgets::{ConfirmDialog, HelpOverlay, StatusBar, TabBar, TabHit, TitleTruncation, Toasts};
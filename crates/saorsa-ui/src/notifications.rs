@@ -0,0 +1,168 @@
+//! Queue of transient notifications
+//!
+//! [`Notifications`] holds the toasts [`App`](crate::App) draws over the
+//! content area. Each entry expires on its own schedule; [`App::tick`]
+//! drops expired entries and [`App::desired_tick_interval`] wakes the loop
+//! when the next one is due.
+//!
+//! [`App::tick`]: saorsa_cli_core::AppCoordinator::tick
+//! [`App::desired_tick_interval`]: saorsa_cli_core::AppCoordinator::desired_tick_interval
+//!
+//! # Example
+//!
+//! ```
+//! use saorsa_ui::Notifications;
+//! use saorsa_cli_core::NotifyLevel;
+//! use std::time::Duration;
+//!
+//! let mut notifications = Notifications::new();
+//! notifications.push(NotifyLevel::Warning, "Index is stale", Duration::from_secs(2));
+//! assert_eq!(notifications.len(), 1);
+//! assert!(notifications.next_expiry().is_some());
+//! ```
+
+use saorsa_cli_core::{Notification, NotifyLevel};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// How long a notification stays up unless a key dismisses it first
+pub const NOTIFICATION_TIMEOUT: Duration = Duration::from_secs(4);
+
+/// Most notifications shown at once; older ones are dropped first
+pub const MAX_NOTIFICATIONS: usize = 3;
+
+/// Notifications on screen, oldest first, each with its expiry time
+#[derive(Debug, Clone)]
+pub struct Notifications {
+    entries: VecDeque<(Notification, Instant)>,
+    capacity: usize,
+}
+
+impl Notifications {
+    /// Creates an empty queue holding up to [`MAX_NOTIFICATIONS`]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_capacity(MAX_NOTIFICATIONS)
+    }
+
+    /// Creates an empty queue holding up to `capacity` notifications
+    ///
+    /// A capacity of zero is treated as one.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Notifications {
+            entries: VecDeque::new(),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Shows a notification for `duration`
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - Severity, which picks the toast color
+    /// * `text` - Text to show; only the first line is displayed
+    /// * `duration` - How long before the notification expires
+    pub fn push(&mut self, level: NotifyLevel, text: impl Into<String>, duration: Duration) {
+        self.push_notification(Notification::new(level, text), duration);
+    }
+
+    /// Shows an existing notification for `duration`
+    ///
+    /// The oldest notification is dropped once the queue is full.
+    pub fn push_notification(&mut self, notification: Notification, duration: Duration) {
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries
+            .push_back((notification, Instant::now() + duration));
+    }
+
+    /// Drops notifications whose time is up
+    pub fn tick(&mut self) {
+        let now = Instant::now();
+        self.entries.retain(|(_, expires)| *expires > now);
+    }
+
+    /// Returns how long until the next notification expires
+    ///
+    /// # Returns
+    ///
+    /// `None` when the queue is empty.
+    #[must_use]
+    pub fn next_expiry(&self) -> Option<Duration> {
+        let now = Instant::now();
+        self.entries
+            .iter()
+            .map(|(_, expires)| expires.saturating_duration_since(now))
+            .min()
+    }
+
+    /// Dismisses every notification
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Returns the notifications, oldest first
+    pub fn iter(&self) -> impl Iterator<Item = &Notification> {
+        self.entries.iter().map(|(notification, _)| notification)
+    }
+
+    /// Returns the number of notifications shown
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether no notifications are shown
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl Default for Notifications {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notifications_drop_oldest_when_full() {
+        let mut notifications = Notifications::with_capacity(2);
+        for text in ["one", "two", "three"] {
+            notifications.push(NotifyLevel::Info, text, NOTIFICATION_TIMEOUT);
+        }
+        let texts: Vec<_> = notifications.iter().map(|n| n.message.as_str()).collect();
+        assert_eq!(texts, vec!["two", "three"]);
+    }
+
+    #[test]
+    fn test_notifications_expire_individually() {
+        let mut notifications = Notifications::new();
+        notifications.push(NotifyLevel::Error, "gone", Duration::ZERO);
+        notifications.push(NotifyLevel::Info, "stays", Duration::from_secs(60));
+        assert_eq!(notifications.next_expiry(), Some(Duration::ZERO));
+
+        notifications.tick();
+        let texts: Vec<_> = notifications.iter().map(|n| n.message.as_str()).collect();
+        assert_eq!(texts, vec!["stays"]);
+        assert!(notifications
+            .next_expiry()
+            .is_some_and(|d| d > Duration::from_secs(50)));
+    }
+
+    #[test]
+    fn test_notifications_clear() {
+        let mut notifications = Notifications::default();
+        notifications.push(NotifyLevel::Warning, "careful", NOTIFICATION_TIMEOUT);
+        assert!(!notifications.is_empty());
+        notifications.clear();
+        assert!(notifications.is_empty());
+        assert_eq!(notifications.next_expiry(), None);
+    }
+}
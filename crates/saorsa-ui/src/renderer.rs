@@ -5,13 +5,19 @@
 //!
 //! # Overview
 //!
-//! The renderer module handles two main concerns:
+//! The renderer module handles three main concerns:
 //!
 //! 1. **Application Layout**: Dividing the terminal into tab bar, content,
-//!    and status bar regions via [`AppLayout`].
+//!    and status bar regions via [`AppLayout`], optionally customised with
+//!    a [`LayoutConfig`].
 //!
 //! 2. **Pane Layout**: Calculating areas for panes within the content region
-//!    based on a [`PaneLayout`] tree via [`calculate_pane_areas`].
+//!    based on a [`PaneLayout`] tree via [`calculate_pane_areas`], and
+//!    finding the pane beside another one on screen via [`adjacent_pane`].
+//!
+//! 3. **Pane Frames**: Drawing the themed border around a pane via
+//!    [`render_pane_frame`], so focus looks the same in every tab, and a
+//!    scrollbar over its right edge via [`render_scrollbar`].
 //!
 //! # Example
 //!
@@ -23,12 +29,10 @@
 //! let app_layout = AppLayout::new(frame.area());
 //!
 //! // Calculate pane areas within the content region
-//! let pane_layout = PaneLayout {
-//!     root: PaneNode::vsplit(30, vec![
-//!         PaneNode::leaf(0),
-//!         PaneNode::leaf(1),
-//!     ]),
-//! };
+//! let pane_layout = PaneLayout::new(PaneNode::vsplit(30, vec![
+//!     PaneNode::leaf(0),
+//!     PaneNode::leaf(1),
+//! ]));
 //! let pane_areas = calculate_pane_areas(&pane_layout, app_layout.content);
 //!
 //! // Render each pane in its calculated area
@@ -38,7 +42,69 @@
 //! ```
 
 use ratatui::prelude::*;
-use saorsa_cli_core::{PaneId, PaneLayout, PaneNode, Split};
+use ratatui::widgets::{
+    Block, BorderType, Borders, Scrollbar, ScrollbarOrientation, ScrollbarState,
+};
+use saorsa_cli_core::{BorderStyle, FocusDirection, PaneId, PaneLayout, PaneNode, Split, Theme};
+
+use crate::widgets::tab_bar::TitleTruncation;
+
+/// Where the tab bar is placed relative to the content area
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TabBarPosition {
+    /// One row above the content (default)
+    #[default]
+    Top,
+    /// One row below the content, above the status bar
+    Bottom,
+    /// A column to the left of the content, one tab per row
+    Left,
+}
+
+/// Configuration for [`AppLayout::with_config`]
+///
+/// # Example
+///
+/// ```
+/// use saorsa_ui::renderer::{AppLayout, LayoutConfig, TabBarPosition};
+/// use ratatui::prelude::Rect;
+///
+/// let config = LayoutConfig {
+///     tab_bar: TabBarPosition::Left,
+///     status_bar_height: 2,
+///     ..LayoutConfig::default()
+/// };
+/// let layout = AppLayout::with_config(Rect::new(0, 0, 120, 30), &config);
+///
+/// assert_eq!(layout.tab_bar, Rect::new(0, 0, 20, 28));
+/// assert_eq!(layout.content, Rect::new(20, 0, 100, 28));
+/// assert_eq!(layout.status_bar, Rect::new(0, 28, 120, 2));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LayoutConfig {
+    /// Placement of the tab bar
+    pub tab_bar: TabBarPosition,
+    /// Rows reserved for the status bar (0 hides it)
+    pub status_bar_height: u16,
+    /// Columns used by the tab bar when placed on the left
+    pub side_tab_bar_width: u16,
+    /// How tab titles are shortened when the tab bar runs out of room
+    pub tab_titles: TitleTruncation,
+    /// Draw a `×` on closable tabs that closes them when clicked
+    pub tab_close_buttons: bool,
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        LayoutConfig {
+            tab_bar: TabBarPosition::Top,
+            status_bar_height: 1,
+            side_tab_bar_width: 20,
+            tab_titles: TitleTruncation::End,
+            tab_close_buttons: false,
+        }
+    }
+}
 
 /// Main application layout areas
 ///
@@ -117,7 +183,49 @@ impl AppLayout {
     /// ```
     #[must_use]
     pub fn new(area: Rect) -> Self {
-        if area.height < 3 {
+        Self::with_config(area, &LayoutConfig::default())
+    }
+
+    /// Calculate layout areas for a custom tab bar position and status height
+    ///
+    /// With `s` = `config.status_bar_height`, the content area is:
+    /// - [`TabBarPosition::Top`]: `area.height - 1 - s` rows, below the tab bar
+    /// - [`TabBarPosition::Bottom`]: `area.height - 1 - s` rows, at the top
+    /// - [`TabBarPosition::Left`]: `area.height - s` rows and
+    ///   `area.width - config.side_tab_bar_width` columns, right of the tab bar
+    ///
+    /// When the area is too small for the decorations, content takes the
+    /// whole area, as with [`AppLayout::new`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use saorsa_ui::renderer::{AppLayout, LayoutConfig, TabBarPosition};
+    /// use ratatui::prelude::Rect;
+    ///
+    /// let config = LayoutConfig {
+    ///     tab_bar: TabBarPosition::Bottom,
+    ///     status_bar_height: 2,
+    ///     ..LayoutConfig::default()
+    /// };
+    /// let layout = AppLayout::with_config(Rect::new(0, 0, 80, 24), &config);
+    ///
+    /// assert_eq!(layout.content, Rect::new(0, 0, 80, 21));
+    /// assert_eq!(layout.tab_bar, Rect::new(0, 21, 80, 1));
+    /// assert_eq!(layout.status_bar, Rect::new(0, 22, 80, 2));
+    /// ```
+    #[must_use]
+    pub fn with_config(area: Rect, config: &LayoutConfig) -> Self {
+        let status_height = config.status_bar_height;
+
+        // The tab bar row plus the status bar plus at least one content row
+        let min_height = match config.tab_bar {
+            TabBarPosition::Top | TabBarPosition::Bottom => status_height + 2,
+            TabBarPosition::Left => status_height + 1,
+        };
+        let side_width = config.side_tab_bar_width;
+        let too_narrow = config.tab_bar == TabBarPosition::Left && area.width <= side_width;
+        if area.height < min_height || too_narrow {
             // Minimal space - give everything to content
             return AppLayout {
                 tab_bar: Rect::new(area.x, area.y, area.width, 1.min(area.height)),
@@ -126,14 +234,33 @@ impl AppLayout {
             };
         }
 
-        let tab_bar = Rect::new(area.x, area.y, area.width, 1);
-        let status_bar = Rect::new(area.x, area.y + area.height - 1, area.width, 1);
-        let content = Rect::new(
-            area.x,
-            area.y + 1,
-            area.width,
-            area.height.saturating_sub(2),
-        );
+        let bottom = area.y + area.height;
+        let status_bar = if status_height == 0 {
+            Rect::default()
+        } else {
+            Rect::new(area.x, bottom - status_height, area.width, status_height)
+        };
+        let body_height = area.height - status_height;
+
+        let (tab_bar, content) = match config.tab_bar {
+            TabBarPosition::Top => (
+                Rect::new(area.x, area.y, area.width, 1),
+                Rect::new(area.x, area.y + 1, area.width, body_height - 1),
+            ),
+            TabBarPosition::Bottom => (
+                Rect::new(area.x, area.y + body_height - 1, area.width, 1),
+                Rect::new(area.x, area.y, area.width, body_height - 1),
+            ),
+            TabBarPosition::Left => (
+                Rect::new(area.x, area.y, side_width, body_height),
+                Rect::new(
+                    area.x + side_width,
+                    area.y,
+                    area.width - side_width,
+                    body_height,
+                ),
+            ),
+        };
 
         AppLayout {
             tab_bar,
@@ -142,6 +269,11 @@ impl AppLayout {
         }
     }
 
+    /// Whether the tab bar sits beside the content rather than above/below
+    fn tab_bar_beside_content(&self) -> bool {
+        self.tab_bar.width > 0 && self.tab_bar.x + self.tab_bar.width <= self.content.x
+    }
+
     /// Returns the total width of the layout
     ///
     /// # Example
@@ -155,12 +287,17 @@ impl AppLayout {
     /// ```
     #[must_use]
     pub fn width(&self) -> u16 {
-        self.content.width
+        if self.tab_bar_beside_content() {
+            self.tab_bar.width + self.content.width
+        } else {
+            self.content.width
+        }
     }
 
     /// Returns the total height of the layout
     ///
-    /// This is the sum of all three regions.
+    /// This is the sum of all three regions, or of content and status
+    /// bar when the tab bar sits beside the content.
     ///
     /// # Example
     ///
@@ -173,7 +310,11 @@ impl AppLayout {
     /// ```
     #[must_use]
     pub fn height(&self) -> u16 {
-        self.tab_bar.height + self.content.height + self.status_bar.height
+        if self.tab_bar_beside_content() {
+            self.content.height + self.status_bar.height
+        } else {
+            self.tab_bar.height + self.content.height + self.status_bar.height
+        }
     }
 }
 
@@ -190,7 +331,9 @@ impl AppLayout {
 /// # Returns
 ///
 /// A vector of (PaneId, Rect) pairs for each leaf pane in the layout.
-/// The order matches a depth-first traversal of the layout tree.
+/// The order matches a depth-first traversal of the layout tree. While a
+/// pane is zoomed it gets the whole area and every other pane an empty
+/// `Rect` at the area's corner.
 ///
 /// # Example
 ///
@@ -214,12 +357,10 @@ impl AppLayout {
 /// use ratatui::prelude::Rect;
 ///
 /// // Vertical split - 30% left, 70% right
-/// let layout = PaneLayout {
-///     root: PaneNode::vsplit(30, vec![
-///         PaneNode::leaf(0),
-///         PaneNode::leaf(1),
-///     ]),
-/// };
+/// let layout = PaneLayout::new(PaneNode::vsplit(30, vec![
+///     PaneNode::leaf(0),
+///     PaneNode::leaf(1),
+/// ]));
 /// let area = Rect::new(0, 0, 100, 24);
 /// let areas = calculate_pane_areas(&layout, area);
 ///
@@ -229,11 +370,185 @@ impl AppLayout {
 /// ```
 #[must_use]
 pub fn calculate_pane_areas(layout: &PaneLayout, area: Rect) -> Vec<(PaneId, Rect)> {
+    if let Some(zoomed) = layout.zoomed_pane() {
+        let hidden = Rect::new(area.x, area.y, 0, 0);
+        return layout
+            .pane_ids()
+            .into_iter()
+            .map(|id| (id, if id == zoomed { area } else { hidden }))
+            .collect();
+    }
     let mut result = Vec::new();
     calculate_node_areas(&layout.root, area, &mut result);
     result
 }
 
+/// Finds the pane next to `from` on screen in `direction`
+///
+/// `areas` are pane areas as returned by [`calculate_pane_areas`]. Only
+/// panes lying wholly beyond `from`'s edge in `direction` count. Of those,
+/// panes that share rows (or columns, going up and down) with `from` win
+/// over ones that are merely diagonal, then the closest one, then the one
+/// whose middle lines up best with `from`'s. Empty areas, such as panes
+/// hidden by a zoom, are never picked.
+///
+/// # Returns
+///
+/// `None` if `from` is not among `areas`, is hidden, or has no neighbour
+/// in that direction.
+///
+/// # Example
+///
+/// ```
+/// use saorsa_ui::renderer::{adjacent_pane, calculate_pane_areas};
+/// use saorsa_cli_core::{FocusDirection, PaneLayout, PaneNode};
+/// use ratatui::prelude::Rect;
+///
+/// // Sidebar on the left, two panes stacked on the right
+/// let layout = PaneLayout::new(PaneNode::vsplit(30, vec![
+///     PaneNode::leaf(0),
+///     PaneNode::hsplit(50, vec![PaneNode::leaf(1), PaneNode::leaf(2)]),
+/// ]));
+/// let areas = calculate_pane_areas(&layout, Rect::new(0, 0, 100, 24));
+///
+/// assert_eq!(adjacent_pane(&areas, 2, FocusDirection::Up), Some(1));
+/// assert_eq!(adjacent_pane(&areas, 2, FocusDirection::Left), Some(0));
+/// assert_eq!(adjacent_pane(&areas, 0, FocusDirection::Left), None);
+/// ```
+#[must_use]
+pub fn adjacent_pane(
+    areas: &[(PaneId, Rect)],
+    from: PaneId,
+    direction: FocusDirection,
+) -> Option<PaneId> {
+    let (_, current) = areas
+        .iter()
+        .find(|(id, area)| *id == from && !area.is_empty())?;
+    // Edges along the direction of travel and spans across it
+    let along = |area: &Rect| match direction {
+        FocusDirection::Left | FocusDirection::Right => (area.left(), area.right()),
+        FocusDirection::Up | FocusDirection::Down => (area.top(), area.bottom()),
+    };
+    let across = |area: &Rect| match direction {
+        FocusDirection::Left | FocusDirection::Right => (area.top(), area.bottom()),
+        FocusDirection::Up | FocusDirection::Down => (area.left(), area.right()),
+    };
+    let (start, end) = along(current);
+    let (low, high) = across(current);
+
+    areas
+        .iter()
+        .filter(|(id, area)| *id != from && !area.is_empty())
+        .filter_map(|(id, area)| {
+            let (other_start, other_end) = along(area);
+            let gap = match direction {
+                FocusDirection::Left | FocusDirection::Up => start.checked_sub(other_end)?,
+                FocusDirection::Right | FocusDirection::Down => other_start.checked_sub(end)?,
+            };
+            let (other_low, other_high) = across(area);
+            let overlaps = other_low < high && low < other_high;
+            // Twice the distance between the middles, to stay in integers
+            let offset =
+                (i32::from(other_low) + i32::from(other_high) - i32::from(low) - i32::from(high))
+                    .unsigned_abs();
+            Some(((!overlaps, gap, offset), *id))
+        })
+        .min_by_key(|(key, _)| *key)
+        .map(|(_, id)| id)
+}
+
+/// Builds the themed border block for a pane
+///
+/// The border follows [`Theme::borders`] and is drawn in the theme's
+/// focused border color when `focused`, and its plain border color
+/// otherwise. Use this when the block is handed to a widget; otherwise
+/// [`render_pane_frame`] draws it directly.
+///
+/// # Example
+///
+/// ```
+/// use saorsa_ui::renderer::pane_block;
+/// use saorsa_cli_core::Theme;
+/// use ratatui::prelude::Rect;
+///
+/// let theme = Theme::dark();
+/// let block = pane_block(" Files ", true, &theme);
+/// assert_eq!(block.inner(Rect::new(0, 0, 20, 10)), Rect::new(1, 1, 18, 8));
+/// ```
+#[must_use]
+pub fn pane_block<'a>(title: impl Into<Line<'a>>, focused: bool, theme: &Theme) -> Block<'a> {
+    let block = Block::default()
+        .title(title)
+        .border_style(Style::default().fg(theme.colors.border_for(focused)));
+    let border_type = match theme.borders {
+        BorderStyle::None => return block.borders(Borders::NONE),
+        BorderStyle::Rounded => BorderType::Rounded,
+        BorderStyle::Square => BorderType::Plain,
+        BorderStyle::Double => BorderType::Double,
+    };
+    block.borders(Borders::ALL).border_type(border_type)
+}
+
+/// Draws a themed pane border and returns the area inside it
+///
+/// # Arguments
+///
+/// * `frame` - Frame to draw into
+/// * `area` - Area of the whole pane, border included
+/// * `title` - Title shown in the top border
+/// * `focused` - Whether the pane has focus, which brightens the border
+/// * `theme` - Theme for the border style and colors
+///
+/// # Returns
+///
+/// The content area inside the border.
+pub fn render_pane_frame(
+    frame: &mut Frame,
+    area: Rect,
+    title: &str,
+    focused: bool,
+    theme: &Theme,
+) -> Rect {
+    let block = pane_block(title, focused, theme);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    inner
+}
+
+/// Draws a vertical scrollbar in the rightmost column of `area`
+///
+/// Nothing is drawn when the content fits. Pass the pane area shrunk by
+/// one row at each end to draw over a pane's right border.
+///
+/// # Arguments
+///
+/// * `frame` - Frame to draw into
+/// * `area` - Area whose last column holds the scrollbar
+/// * `position` - Index of the first visible row, as used to scroll the content
+/// * `total` - Number of rows in the content
+/// * `viewport` - Number of rows visible at once
+pub fn render_scrollbar(
+    frame: &mut Frame,
+    area: Rect,
+    position: usize,
+    total: usize,
+    viewport: usize,
+) {
+    if total <= viewport || area.width == 0 || area.height == 0 {
+        return;
+    }
+    // One thumb position per possible scroll offset, so the thumb reaches
+    // the end of the track exactly when the last row is visible
+    let max_position = total - viewport;
+    let mut state = ScrollbarState::new(max_position + 1)
+        .position(position.min(max_position))
+        .viewport_content_length(viewport);
+    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(None)
+        .end_symbol(None);
+    frame.render_stateful_widget(scrollbar, area, &mut state);
+}
+
 /// Recursively calculates areas for nodes in the layout tree
 fn calculate_node_areas(node: &PaneNode, area: Rect, result: &mut Vec<(PaneId, Rect)>) {
     match node {
@@ -408,6 +723,86 @@ mod tests {
         assert_eq!(layout.status_bar, Rect::default());
     }
 
+    fn config(tab_bar: TabBarPosition, status_bar_height: u16) -> LayoutConfig {
+        LayoutConfig {
+            tab_bar,
+            status_bar_height,
+            ..LayoutConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_app_layout_default_config_matches_new() {
+        let area = Rect::new(3, 2, 80, 24);
+        assert_eq!(
+            AppLayout::with_config(area, &LayoutConfig::default()),
+            AppLayout::new(area)
+        );
+    }
+
+    #[test]
+    fn test_app_layout_tall_status_bar() {
+        let area = Rect::new(0, 0, 80, 24);
+        let layout = AppLayout::with_config(area, &config(TabBarPosition::Top, 2));
+
+        assert_eq!(layout.tab_bar, Rect::new(0, 0, 80, 1));
+        assert_eq!(layout.content, Rect::new(0, 1, 80, 21));
+        assert_eq!(layout.status_bar, Rect::new(0, 22, 80, 2));
+        assert_eq!(layout.height(), 24);
+    }
+
+    #[test]
+    fn test_app_layout_bottom_tab_bar() {
+        let area = Rect::new(0, 5, 80, 20);
+        let layout = AppLayout::with_config(area, &config(TabBarPosition::Bottom, 1));
+
+        assert_eq!(layout.content, Rect::new(0, 5, 80, 18));
+        assert_eq!(layout.tab_bar, Rect::new(0, 23, 80, 1));
+        assert_eq!(layout.status_bar, Rect::new(0, 24, 80, 1));
+        assert_eq!(layout.height(), 20);
+    }
+
+    #[test]
+    fn test_app_layout_left_tab_bar() {
+        let area = Rect::new(0, 0, 200, 40);
+        let layout = AppLayout::with_config(area, &config(TabBarPosition::Left, 1));
+
+        assert_eq!(layout.tab_bar, Rect::new(0, 0, 20, 39));
+        assert_eq!(layout.content, Rect::new(20, 0, 180, 39));
+        assert_eq!(layout.status_bar, Rect::new(0, 39, 200, 1));
+        assert_eq!(layout.width(), 200);
+        assert_eq!(layout.height(), 40);
+
+        // Panes slot into the shifted content area
+        let panes = PaneLayout::new(PaneNode::vsplit(
+            50,
+            vec![PaneNode::leaf(0), PaneNode::leaf(1)],
+        ));
+        let areas = calculate_pane_areas(&panes, layout.content);
+        assert_eq!(areas[0].1.x, 20);
+        assert_eq!(areas[0].1.width + areas[1].1.width, 180);
+    }
+
+    #[test]
+    fn test_app_layout_hidden_status_bar() {
+        let area = Rect::new(0, 0, 80, 24);
+        let layout = AppLayout::with_config(area, &config(TabBarPosition::Top, 0));
+
+        assert_eq!(layout.content, Rect::new(0, 1, 80, 23));
+        assert_eq!(layout.status_bar, Rect::default());
+    }
+
+    #[test]
+    fn test_app_layout_config_degrades() {
+        let area = Rect::new(0, 0, 15, 3);
+        let layout = AppLayout::with_config(area, &config(TabBarPosition::Left, 3));
+        assert_eq!(layout.content, area);
+
+        let layout = AppLayout::with_config(area, &config(TabBarPosition::Top, 2));
+        assert_eq!(layout.content, area);
+        assert_eq!(layout.status_bar, Rect::default());
+    }
+
     #[test]
     fn test_pane_areas_single() {
         let layout = PaneLayout::single(0);
@@ -420,9 +815,10 @@ mod tests {
 
     #[test]
     fn test_pane_areas_vsplit() {
-        let layout = PaneLayout {
-            root: PaneNode::vsplit(30, vec![PaneNode::leaf(0), PaneNode::leaf(1)]),
-        };
+        let layout = PaneLayout::new(PaneNode::vsplit(
+            30,
+            vec![PaneNode::leaf(0), PaneNode::leaf(1)],
+        ));
         let area = Rect::new(0, 0, 100, 24);
         let areas = calculate_pane_areas(&layout, area);
 
@@ -437,9 +833,10 @@ mod tests {
 
     #[test]
     fn test_pane_areas_hsplit() {
-        let layout = PaneLayout {
-            root: PaneNode::hsplit(50, vec![PaneNode::leaf(0), PaneNode::leaf(1)]),
-        };
+        let layout = PaneLayout::new(PaneNode::hsplit(
+            50,
+            vec![PaneNode::leaf(0), PaneNode::leaf(1)],
+        ));
         let area = Rect::new(0, 0, 80, 20);
         let areas = calculate_pane_areas(&layout, area);
 
@@ -454,15 +851,13 @@ mod tests {
     #[test]
     fn test_pane_areas_nested() {
         // Layout: left pane (30%) | right side with top/bottom split (70%)
-        let layout = PaneLayout {
-            root: PaneNode::vsplit(
-                30,
-                vec![
-                    PaneNode::leaf(0),
-                    PaneNode::hsplit(50, vec![PaneNode::leaf(1), PaneNode::leaf(2)]),
-                ],
-            ),
-        };
+        let layout = PaneLayout::new(PaneNode::vsplit(
+            30,
+            vec![
+                PaneNode::leaf(0),
+                PaneNode::hsplit(50, vec![PaneNode::leaf(1), PaneNode::leaf(2)]),
+            ],
+        ));
         let area = Rect::new(0, 0, 100, 20);
         let areas = calculate_pane_areas(&layout, area);
 
@@ -475,12 +870,10 @@ mod tests {
 
     #[test]
     fn test_pane_areas_empty_children() {
-        let layout = PaneLayout {
-            root: PaneNode::Split {
-                direction: Split::Vertical(50),
-                children: vec![],
-            },
-        };
+        let layout = PaneLayout::new(PaneNode::Split {
+            direction: Split::Vertical(50),
+            children: vec![],
+        });
         let area = Rect::new(0, 0, 80, 24);
         let areas = calculate_pane_areas(&layout, area);
 
@@ -498,11 +891,86 @@ mod tests {
         assert_eq!(areas[0].0, 0);
     }
 
+    #[test]
+    fn test_pane_areas_zoomed_pane_fills_area() {
+        let mut layout = PaneLayout::new(PaneNode::vsplit(
+            30,
+            vec![
+                PaneNode::leaf(0),
+                PaneNode::hsplit(50, vec![PaneNode::leaf(1), PaneNode::leaf(2)]),
+            ],
+        ));
+        let area = Rect::new(2, 1, 100, 24);
+        let split = calculate_pane_areas(&layout, area);
+
+        layout.toggle_zoom(1);
+        let hidden = Rect::new(2, 1, 0, 0);
+        assert_eq!(
+            calculate_pane_areas(&layout, area),
+            vec![(0, hidden), (1, area), (2, hidden)]
+        );
+
+        layout.toggle_zoom(1);
+        assert_eq!(calculate_pane_areas(&layout, area), split);
+    }
+
+    #[test]
+    fn test_adjacent_pane_follows_screen_positions() {
+        // +---+-------+---+
+        // |   |   1   |   |
+        // | 0 +-------+ 3 |
+        // |   |   2   |   |
+        // +---+-------+---+
+        let layout = PaneLayout::new(PaneNode::vsplit(
+            25,
+            vec![
+                PaneNode::leaf(0),
+                PaneNode::vsplit(
+                    66,
+                    vec![
+                        PaneNode::hsplit(50, vec![PaneNode::leaf(1), PaneNode::leaf(2)]),
+                        PaneNode::leaf(3),
+                    ],
+                ),
+            ],
+        ));
+        let areas = calculate_pane_areas(&layout, Rect::new(0, 0, 100, 40));
+
+        assert_eq!(adjacent_pane(&areas, 0, FocusDirection::Right), Some(1));
+        assert_eq!(adjacent_pane(&areas, 1, FocusDirection::Down), Some(2));
+        assert_eq!(adjacent_pane(&areas, 2, FocusDirection::Up), Some(1));
+        assert_eq!(adjacent_pane(&areas, 2, FocusDirection::Right), Some(3));
+        assert_eq!(adjacent_pane(&areas, 3, FocusDirection::Left), Some(1));
+        assert_eq!(adjacent_pane(&areas, 1, FocusDirection::Up), None);
+        assert_eq!(adjacent_pane(&areas, 3, FocusDirection::Right), None);
+        assert_eq!(adjacent_pane(&areas, 9, FocusDirection::Left), None);
+    }
+
+    #[test]
+    fn test_adjacent_pane_prefers_overlap_and_skips_hidden_panes() {
+        let areas = [
+            (0, Rect::new(0, 0, 10, 10)),
+            // Diagonal but closer than pane 2
+            (1, Rect::new(10, 10, 10, 10)),
+            (2, Rect::new(30, 5, 10, 10)),
+        ];
+        assert_eq!(adjacent_pane(&areas, 0, FocusDirection::Right), Some(2));
+        // Both overlap going back, so the closer one wins
+        assert_eq!(adjacent_pane(&areas, 2, FocusDirection::Left), Some(1));
+
+        let mut layout = PaneLayout::new(PaneNode::vsplit(
+            50,
+            vec![PaneNode::leaf(0), PaneNode::leaf(1)],
+        ));
+        layout.toggle_zoom(0);
+        let areas = calculate_pane_areas(&layout, Rect::new(0, 0, 80, 24));
+        assert_eq!(adjacent_pane(&areas, 0, FocusDirection::Right), None);
+        assert_eq!(adjacent_pane(&areas, 1, FocusDirection::Left), None);
+    }
+
     #[test]
     fn test_pane_areas_single_child_split() {
-        let layout = PaneLayout {
-            root: PaneNode::vsplit(50, vec![PaneNode::leaf(0)]),
-        };
+        let layout = PaneLayout::new(PaneNode::vsplit(50, vec![PaneNode::leaf(0)]));
         let area = Rect::new(0, 0, 80, 24);
         let areas = calculate_pane_areas(&layout, area);
 
@@ -513,12 +981,10 @@ mod tests {
 
     #[test]
     fn test_pane_areas_three_way_split() {
-        let layout = PaneLayout {
-            root: PaneNode::vsplit(
-                33,
-                vec![PaneNode::leaf(0), PaneNode::leaf(1), PaneNode::leaf(2)],
-            ),
-        };
+        let layout = PaneLayout::new(PaneNode::vsplit(
+            33,
+            vec![PaneNode::leaf(0), PaneNode::leaf(1), PaneNode::leaf(2)],
+        ));
         let area = Rect::new(0, 0, 90, 24);
         let areas = calculate_pane_areas(&layout, area);
 
@@ -563,6 +1029,65 @@ mod tests {
         assert_eq!(calculate_first_dimension(100, 100), 99);
     }
 
+    #[test]
+    fn test_render_pane_frame_focus_and_style() {
+        use ratatui::backend::TestBackend;
+        use ratatui::Terminal;
+
+        let mut theme = Theme::dark();
+        let mut terminal = Terminal::new(TestBackend::new(20, 6)).unwrap();
+        let mut inner = Rect::default();
+        terminal
+            .draw(|frame| {
+                inner = render_pane_frame(frame, Rect::new(0, 0, 10, 6), "A", true, &theme);
+                render_pane_frame(frame, Rect::new(10, 0, 10, 6), "B", false, &theme);
+            })
+            .unwrap();
+
+        assert_eq!(inner, Rect::new(1, 1, 8, 4));
+        let buf = terminal.backend().buffer();
+        assert_eq!(buf[(0, 0)].symbol(), "╭");
+        assert_eq!(buf[(0, 0)].fg, theme.colors.border_focused);
+        assert_eq!(buf[(10, 0)].fg, theme.colors.border);
+
+        theme.borders = BorderStyle::None;
+        terminal
+            .draw(|frame| {
+                inner = render_pane_frame(frame, Rect::new(0, 0, 10, 6), "A", true, &theme);
+            })
+            .unwrap();
+        // Only the title row is taken
+        assert_eq!(inner, Rect::new(0, 1, 10, 5));
+    }
+
+    fn scrollbar_column(position: usize, total: usize, viewport: usize) -> String {
+        use ratatui::backend::TestBackend;
+        use ratatui::Terminal;
+
+        let mut terminal = Terminal::new(TestBackend::new(3, 10)).unwrap();
+        terminal
+            .draw(|frame| render_scrollbar(frame, frame.area(), position, total, viewport))
+            .unwrap();
+        let buf = terminal.backend().buffer();
+        (0..10).map(|y| buf[(2, y)].symbol().to_string()).collect()
+    }
+
+    #[test]
+    fn test_render_scrollbar_only_when_overflowing() {
+        assert_eq!(scrollbar_column(0, 10, 10), " ".repeat(10));
+        assert_eq!(scrollbar_column(0, 0, 10), " ".repeat(10));
+    }
+
+    #[test]
+    fn test_render_scrollbar_tracks_position() {
+        // 20 rows, 10 visible: the thumb covers half the track
+        assert_eq!(scrollbar_column(0, 20, 10), "█████║║║║║");
+        // Scrolled to the last page, the thumb sits at the bottom
+        assert_eq!(scrollbar_column(10, 20, 10), "║║║║║█████");
+        // Offsets past the end are clamped
+        assert_eq!(scrollbar_column(50, 20, 10), "║║║║║█████");
+    }
+
     #[test]
     fn test_app_layout_clone() {
         let area = Rect::new(0, 0, 80, 24);
@@ -0,0 +1,159 @@
+//! Rendering widgets to text for snapshot tests
+//!
+//! [`render_widget`] draws any ratatui widget, such as [`TabBar`] or
+//! [`StatusBar`], into an off-screen [`Buffer`], and [`buffer_to_string`]
+//! flattens a buffer to plain lines that can be compared with `assert_eq!`
+//! or a snapshot library. Together with [`App::render_to_buffer`] this lets
+//! crates building their own tabs check what they draw without a terminal.
+//!
+//! Only the text is kept; colors and modifiers can still be checked on the
+//! buffer's cells.
+//!
+//! ## Example
+//!
+//! ```
+//! use saorsa_cli_core::Theme;
+//! use saorsa_ui::snapshot::{buffer_to_string, render_widget};
+//! use saorsa_ui::StatusBar;
+//!
+//! let theme = Theme::dark();
+//! let status = StatusBar::new(&theme).left("NORMAL").right("?:help");
+//! let text = buffer_to_string(&render_widget(status, 24, 1));
+//! assert!(text.starts_with(" NORMAL"));
+//! assert!(text.ends_with("?:help "));
+//! ```
+//!
+//! [`TabBar`]: crate::TabBar
+//! [`StatusBar`]: crate::StatusBar
+//! [`App::render_to_buffer`]: crate::App::render_to_buffer
+
+use ratatui::prelude::*;
+
+/// Renders `widget` into a new `width` x `height` buffer at the origin
+///
+/// # Example
+///
+/// ```
+/// use saorsa_ui::snapshot::render_widget;
+///
+/// let buffer = render_widget("hello", 8, 1);
+/// assert_eq!(buffer[(0, 0)].symbol(), "h");
+/// ```
+#[must_use]
+pub fn render_widget(widget: impl Widget, width: u16, height: u16) -> Buffer {
+    let area = Rect::new(0, 0, width, height);
+    let mut buffer = Buffer::empty(area);
+    widget.render(area, &mut buffer);
+    buffer
+}
+
+/// Flattens a buffer to its text, one line per row joined with `\n`
+///
+/// Every row keeps the buffer's full width, trailing blanks included, so
+/// alignment shows up in the snapshot. A wide character such as `🦀` stands
+/// for the cells it covers, so each line is as wide on screen as the buffer.
+///
+/// # Example
+///
+/// ```
+/// use ratatui::buffer::Buffer;
+/// use saorsa_ui::snapshot::buffer_to_string;
+///
+/// let buffer = Buffer::with_lines(["ab", "c"]);
+/// assert_eq!(buffer_to_string(&buffer), "ab\nc ");
+/// ```
+#[must_use]
+pub fn buffer_to_string(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    (area.top()..area.bottom())
+        .map(|y| {
+            let mut line = String::new();
+            let mut hidden = 0;
+            for x in area.left()..area.right() {
+                let symbol = buffer[(x, y)].symbol();
+                if hidden == 0 {
+                    line.push_str(symbol);
+                    hidden = Span::raw(symbol).width().max(1);
+                }
+                hidden -= 1;
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{StatusBar, TabBar};
+    use saorsa_cli_core::{AppContext, CoreResult, Tab, TabId, Theme};
+
+    struct IconTab {
+        id: TabId,
+        title: &'static str,
+        icon: Option<&'static str>,
+    }
+
+    impl Tab for IconTab {
+        fn id(&self) -> TabId {
+            self.id
+        }
+
+        fn title(&self) -> &str {
+            self.title
+        }
+
+        fn icon(&self) -> Option<&str> {
+            self.icon
+        }
+
+        fn focus(&mut self, _ctx: &AppContext) {}
+
+        fn blur(&mut self) {}
+
+        fn view(&self, _frame: &mut Frame, _area: Rect, _ctx: &AppContext) -> CoreResult<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_tab_bar_snapshot() {
+        let tabs: Vec<Box<dyn Tab>> = vec![
+            Box::new(IconTab {
+                id: 1,
+                title: "Files",
+                icon: Some("🦀"),
+            }),
+            Box::new(IconTab {
+                id: 2,
+                title: "Git",
+                icon: None,
+            }),
+        ];
+        let theme = Theme::dark();
+        let buffer = render_widget(TabBar::new(&tabs, 0, &theme), 24, 1);
+        assert_eq!(buffer_to_string(&buffer), "  🦀 Files   |   Git    ");
+    }
+
+    #[test]
+    fn test_status_bar_snapshot() {
+        let theme = Theme::dark();
+        let status = StatusBar::new(&theme)
+            .left("NORMAL")
+            .center("main.rs")
+            .right("?:help");
+        assert_eq!(
+            buffer_to_string(&render_widget(status, 30, 1)),
+            " NORMAL    main.rs     ?:help "
+        );
+    }
+
+    #[test]
+    fn test_buffer_to_string_keeps_rows_and_offset_areas() {
+        let mut buffer = Buffer::empty(Rect::new(2, 3, 3, 2));
+        buffer.set_string(2, 3, "x", Style::default());
+        buffer.set_string(3, 4, "yz", Style::default());
+        assert_eq!(buffer_to_string(&buffer), "x  \n yz");
+    }
+}
@@ -14,7 +14,7 @@
 //! manager.switch_to(tab_id)?;
 //! ```
 
-use saorsa_cli_core::{CoreError, CoreResult, Message, Tab, TabId};
+use saorsa_cli_core::{AppContext, CoreError, CoreResult, Message, MessageBus, Tab, TabId};
 use std::collections::HashMap;
 
 /// Manages a collection of tabs in the TUI.
@@ -23,7 +23,10 @@ use std::collections::HashMap;
 /// - Adding and removing tabs
 /// - Switching between tabs (by ID, next, previous)
 /// - Focus management (calling focus/blur on tabs)
+/// - Holding the [`AppContext`] tabs receive as they gain focus and render
 /// - Message routing for tab-related operations
+/// - Publishing [`Message::TabOpened`], [`Message::TabFocused`] and
+///   [`Message::TabClosed`] to an optional event bus
 ///
 /// # Thread Safety
 ///
@@ -53,6 +56,10 @@ pub struct TabManager {
     active_index: usize,
     /// Mapping from TabId to index for O(1) lookup
     tab_indices: HashMap<TabId, usize>,
+    /// Bus that receives tab lifecycle events, if any
+    events: Option<MessageBus>,
+    /// State handed to tabs as they gain focus and render
+    context: AppContext,
 }
 
 impl TabManager {
@@ -72,6 +79,61 @@ impl TabManager {
             tabs: Vec::new(),
             active_index: 0,
             tab_indices: HashMap::new(),
+            events: None,
+            context: AppContext::default(),
+        }
+    }
+
+    /// Gets the context handed to tabs as they gain focus and render.
+    #[must_use]
+    pub fn context(&self) -> &AppContext {
+        &self.context
+    }
+
+    /// Gets the context mutably, to change the theme or settings tabs see.
+    ///
+    /// Tabs pick up the change the next time they are focused or drawn.
+    pub fn context_mut(&mut self) -> &mut AppContext {
+        &mut self.context
+    }
+
+    /// Publishes tab lifecycle events to `bus`, or stops publishing with `None`.
+    ///
+    /// Every add, removal and change of the active tab is sent as
+    /// [`Message::TabOpened`], [`Message::TabClosed`] or [`Message::TabFocused`].
+    /// Nothing is sent while the bus has no subscribers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use saorsa_ui::TabManager;
+    /// use saorsa_cli_core::MessageBus;
+    ///
+    /// let bus = MessageBus::new(16);
+    /// let mut events = bus.subscribe();
+    /// let mut manager = TabManager::new();
+    /// manager.set_event_bus(Some(bus));
+    /// assert!(events.try_recv().is_err());
+    /// ```
+    pub fn set_event_bus(&mut self, bus: Option<MessageBus>) {
+        self.events = bus;
+    }
+
+    /// Sends a lifecycle event if anyone is listening
+    fn publish(&self, msg: Message) {
+        if let Some(bus) = self
+            .events
+            .as_ref()
+            .filter(|bus| bus.subscriber_count() > 0)
+        {
+            let _ = bus.send(msg);
+        }
+    }
+
+    /// Publishes [`Message::TabFocused`] if the active tab is no longer `before`
+    fn publish_focus_change(&self, before: Option<TabId>) {
+        if let Some(id) = self.active_id().filter(|id| Some(*id) != before) {
+            self.publish(Message::TabFocused(id));
         }
     }
 
@@ -100,14 +162,56 @@ impl TabManager {
 
         // If first tab, give it focus
         if self.tabs.is_empty() {
-            tab.focus();
+            tab.focus(&self.context);
         }
 
+        let before = self.active_id();
         self.tab_indices.insert(id, index);
         self.tabs.push(tab);
+        self.publish(Message::TabOpened(id));
+        self.publish_focus_change(before);
         id
     }
 
+    /// Adds a tab unless one with the same identity is already open.
+    ///
+    /// If an existing tab reports the same [`Tab::identity`], it is focused
+    /// and its ID returned; the new tab is dropped without being added.
+    /// Tabs without an identity are always added, as with [`add_tab`].
+    ///
+    /// [`add_tab`]: TabManager::add_tab
+    ///
+    /// # Arguments
+    ///
+    /// * `tab` - The tab to add, boxed as a trait object
+    ///
+    /// # Returns
+    ///
+    /// The ID of the existing duplicate, or of the newly added tab
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let first = manager.add_or_focus(Box::new(files_tab("/home")));
+    /// let again = manager.add_or_focus(Box::new(files_tab("/home")));
+    /// assert_eq!(first, again);
+    /// assert_eq!(manager.len(), 1);
+    /// ```
+    pub fn add_or_focus(&mut self, tab: Box<dyn Tab>) -> TabId {
+        if let Some(identity) = tab.identity() {
+            let existing = self
+                .tabs
+                .iter()
+                .find(|t| t.identity().as_deref() == Some(identity.as_str()))
+                .map(|t| t.id());
+            if let Some(id) = existing {
+                let _ = self.switch_to(id);
+                return id;
+            }
+        }
+        self.add_tab(tab)
+    }
+
     /// Removes a tab by ID.
     ///
     /// If the removed tab was active, focus shifts to an adjacent tab.
@@ -123,6 +227,8 @@ impl TabManager {
     /// Returns `CoreError::InvalidLayout` if trying to remove the last tab
     /// when it cannot be closed.
     ///
+    /// The tab's [`Tab::on_close`] hook runs just before it is dropped.
+    ///
     /// # Example
     ///
     /// ```ignore
@@ -142,14 +248,18 @@ impl TabManager {
             ));
         }
 
+        let before = self.active_id();
+
         // Blur if this was the active tab
         if index == self.active_index {
             self.tabs[index].blur();
         }
 
-        // Remove the tab
-        self.tabs.remove(index);
+        // Remove the tab, letting it release resources first
+        let mut tab = self.tabs.remove(index);
         self.tab_indices.remove(&id);
+        tab.on_close();
+        drop(tab);
 
         // Update indices for tabs after the removed one
         for (_, idx) in self.tab_indices.iter_mut() {
@@ -164,12 +274,32 @@ impl TabManager {
                 self.active_index = self.tabs.len() - 1;
             }
             // Focus the new active tab
-            self.tabs[self.active_index].focus();
+            self.tabs[self.active_index].focus(&self.context);
         }
 
+        self.publish(Message::TabClosed(id));
+        self.publish_focus_change(before);
         Ok(())
     }
 
+    /// Closes every tab, including ones that cannot be closed by the user.
+    ///
+    /// Used when the application exits: each tab's [`Tab::on_close`] hook
+    /// runs before it is dropped, and the manager is left empty.
+    pub fn close_all(&mut self) {
+        if let Some(tab) = self.tabs.get_mut(self.active_index) {
+            tab.blur();
+        }
+        for mut tab in std::mem::take(&mut self.tabs) {
+            let id = tab.id();
+            tab.on_close();
+            drop(tab);
+            self.publish(Message::TabClosed(id));
+        }
+        self.tab_indices.clear();
+        self.active_index = 0;
+    }
+
     /// Gets a reference to the active tab.
     ///
     /// # Returns
@@ -274,7 +404,8 @@ impl TabManager {
             // Update index
             self.active_index = new_index;
             // Focus new tab
-            self.tabs[self.active_index].focus();
+            self.tabs[self.active_index].focus(&self.context);
+            self.publish(Message::TabFocused(id));
         }
 
         Ok(())
@@ -298,7 +429,8 @@ impl TabManager {
 
         self.tabs[self.active_index].blur();
         self.active_index = (self.active_index + 1) % self.tabs.len();
-        self.tabs[self.active_index].focus();
+        self.tabs[self.active_index].focus(&self.context);
+        self.publish_focus_change(None);
     }
 
     /// Switches to the previous tab (wraps around).
@@ -323,7 +455,8 @@ impl TabManager {
         } else {
             self.active_index - 1
         };
-        self.tabs[self.active_index].focus();
+        self.tabs[self.active_index].focus(&self.context);
+        self.publish_focus_change(None);
     }
 
     /// Returns a slice of all tabs.
@@ -344,6 +477,19 @@ impl TabManager {
         &self.tabs
     }
 
+    /// Returns a mutable slice of all tabs.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// for tab in manager.tabs_mut() {
+    ///     tab.tick();
+    /// }
+    /// ```
+    pub fn tabs_mut(&mut self) -> &mut [Box<dyn Tab>] {
+        &mut self.tabs
+    }
+
     /// Returns the number of tabs.
     ///
     /// # Example
@@ -478,6 +624,7 @@ impl Default for TabManager {
 mod tests {
     use super::*;
     use ratatui::prelude::*;
+    use saorsa_cli_core::AppContext;
     use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
     use std::sync::Arc;
 
@@ -489,6 +636,8 @@ mod tests {
         focused: Arc<AtomicBool>,
         focus_count: Arc<AtomicU32>,
         blur_count: Arc<AtomicU32>,
+        close_count: Arc<AtomicU32>,
+        identity: Option<String>,
     }
 
     impl MockTab {
@@ -500,6 +649,8 @@ mod tests {
                 focused: Arc::new(AtomicBool::new(false)),
                 focus_count: Arc::new(AtomicU32::new(0)),
                 blur_count: Arc::new(AtomicU32::new(0)),
+                close_count: Arc::new(AtomicU32::new(0)),
+                identity: None,
             }
         }
 
@@ -507,6 +658,11 @@ mod tests {
             self.can_close = can_close;
             self
         }
+
+        fn with_identity(mut self, identity: &str) -> Self {
+            self.identity = Some(identity.to_string());
+            self
+        }
     }
 
     impl Tab for MockTab {
@@ -522,7 +678,11 @@ mod tests {
             self.can_close
         }
 
-        fn focus(&mut self) {
+        fn identity(&self) -> Option<String> {
+            self.identity.clone()
+        }
+
+        fn focus(&mut self, _ctx: &AppContext) {
             self.focused.store(true, Ordering::SeqCst);
             self.focus_count.fetch_add(1, Ordering::SeqCst);
         }
@@ -532,8 +692,13 @@ mod tests {
             self.blur_count.fetch_add(1, Ordering::SeqCst);
         }
 
-        fn view(&self, _frame: &mut Frame, _area: Rect) {
+        fn on_close(&mut self) {
+            self.close_count.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn view(&self, _frame: &mut Frame, _area: Rect, _ctx: &AppContext) -> CoreResult<()> {
             // Test implementation does nothing
+            Ok(())
         }
     }
 
@@ -614,6 +779,57 @@ mod tests {
         assert_eq!(manager.len(), 5);
     }
 
+    #[test]
+    fn test_add_or_focus_focuses_duplicate() {
+        let mut manager = TabManager::new();
+
+        manager.add_tab(Box::new(MockTab::new(1, "Files").with_identity("/home")));
+        manager.add_tab(Box::new(MockTab::new(2, "Git")));
+        manager.switch_to(2).expect("should switch");
+
+        let duplicate = MockTab::new(3, "Files").with_identity("/home");
+        let duplicate_focused = duplicate.focus_count.clone();
+        let id = manager.add_or_focus(Box::new(duplicate));
+
+        assert_eq!(id, 1);
+        assert_eq!(manager.len(), 2);
+        assert_eq!(manager.active_id(), Some(1));
+        assert_eq!(duplicate_focused.load(Ordering::SeqCst), 0);
+        assert!(manager.get(3).is_none());
+    }
+
+    #[test]
+    fn test_add_or_focus_adds_distinct_identity() {
+        let mut manager = TabManager::new();
+
+        manager.add_tab(Box::new(MockTab::new(1, "Files").with_identity("/home")));
+        let id = manager.add_or_focus(Box::new(MockTab::new(2, "Files").with_identity("/tmp")));
+
+        assert_eq!(id, 2);
+        assert_eq!(manager.len(), 2);
+        assert_eq!(manager.active_id(), Some(1));
+    }
+
+    #[test]
+    fn test_add_or_focus_without_identity_always_adds() {
+        let mut manager = TabManager::new();
+
+        manager.add_tab(Box::new(MockTab::new(1, "Scratch")));
+        manager.add_or_focus(Box::new(MockTab::new(2, "Scratch")));
+
+        assert_eq!(manager.len(), 2);
+    }
+
+    #[test]
+    fn test_add_tab_ignores_identity() {
+        let mut manager = TabManager::new();
+
+        manager.add_tab(Box::new(MockTab::new(1, "Files").with_identity("/home")));
+        manager.add_tab(Box::new(MockTab::new(2, "Files").with_identity("/home")));
+
+        assert_eq!(manager.len(), 2);
+    }
+
     // ==================== Remove Tab Tests ====================
 
     #[test]
@@ -703,6 +919,62 @@ mod tests {
         assert!(manager.is_empty());
     }
 
+    #[test]
+    fn test_remove_tab_calls_on_close_once() {
+        let mut manager = TabManager::new();
+
+        let tab1 = MockTab::new(1, "Tab 1");
+        let tab2 = MockTab::new(2, "Tab 2");
+        let tab1_closed = tab1.close_count.clone();
+        let tab2_closed = tab2.close_count.clone();
+        manager.add_tab(Box::new(tab1));
+        manager.add_tab(Box::new(tab2));
+
+        manager.remove_tab(1).expect("should remove");
+        assert_eq!(tab1_closed.load(Ordering::SeqCst), 1);
+        assert_eq!(tab2_closed.load(Ordering::SeqCst), 0);
+
+        // Removing again fails without re-running the hook
+        assert!(manager.remove_tab(1).is_err());
+        assert_eq!(tab1_closed.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_remove_uncloseable_tab_skips_on_close() {
+        let mut manager = TabManager::new();
+
+        let tab = MockTab::new(1, "Tab 1").with_can_close(false);
+        let closed = tab.close_count.clone();
+        manager.add_tab(Box::new(tab));
+
+        assert!(manager.remove_tab(1).is_err());
+        assert_eq!(closed.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_close_all_runs_every_on_close() {
+        let mut manager = TabManager::new();
+
+        let tab1 = MockTab::new(1, "Tab 1").with_can_close(false);
+        let tab2 = MockTab::new(2, "Tab 2");
+        let tab1_closed = tab1.close_count.clone();
+        let tab2_closed = tab2.close_count.clone();
+        let tab1_blurred = tab1.blur_count.clone();
+        manager.add_tab(Box::new(tab1));
+        manager.add_tab(Box::new(tab2));
+
+        manager.close_all();
+        assert!(manager.is_empty());
+        assert!(manager.active_id().is_none());
+        assert_eq!(tab1_closed.load(Ordering::SeqCst), 1);
+        assert_eq!(tab2_closed.load(Ordering::SeqCst), 1);
+        assert_eq!(tab1_blurred.load(Ordering::SeqCst), 1);
+
+        // Nothing left to close
+        manager.close_all();
+        assert_eq!(tab1_closed.load(Ordering::SeqCst), 1);
+    }
+
     // ==================== Active Tab Tests ====================
 
     #[test]
@@ -1001,6 +1273,20 @@ mod tests {
         assert_eq!(manager.active_id(), Some(1));
     }
 
+    #[test]
+    fn test_handle_message_close_tab_calls_on_close() {
+        let mut manager = TabManager::new();
+
+        let tab = MockTab::new(1, "Tab 1");
+        let closed = tab.close_count.clone();
+        manager.add_tab(Box::new(tab));
+
+        let result = manager.handle_message(&Message::CloseTab(1));
+        assert!(result.is_none());
+        assert!(manager.is_empty());
+        assert_eq!(closed.load(Ordering::SeqCst), 1);
+    }
+
     #[test]
     fn test_handle_message_unhandled() {
         let mut manager = TabManager::new();
@@ -1021,6 +1307,51 @@ mod tests {
         assert!(matches!(result, Some(Message::ToggleHelp)));
     }
 
+    // ==================== Event Bus Tests ====================
+
+    #[test]
+    fn test_event_bus_records_navigation() {
+        let bus = MessageBus::new(32);
+        let mut rx = bus.subscribe();
+        let mut drain = || std::iter::from_fn(|| rx.try_recv().ok()).collect::<Vec<_>>();
+        let mut manager = TabManager::new();
+        manager.set_event_bus(Some(bus));
+
+        manager.add_tab(Box::new(MockTab::new(1, "Tab 1")));
+        manager.add_tab(Box::new(MockTab::new(2, "Tab 2")));
+        assert_eq!(
+            drain(),
+            vec![
+                Message::TabOpened(1),
+                Message::TabFocused(1),
+                Message::TabOpened(2),
+            ]
+        );
+
+        manager.next_tab();
+        manager.switch_to(2).expect("switch");
+        manager.prev_tab();
+        assert_eq!(
+            drain(),
+            vec![Message::TabFocused(2), Message::TabFocused(1)]
+        );
+
+        manager.remove_tab(1).expect("remove");
+        assert_eq!(drain(), vec![Message::TabClosed(1), Message::TabFocused(2)]);
+    }
+
+    #[test]
+    fn test_event_bus_detached() {
+        let bus = MessageBus::new(32);
+        let mut rx = bus.subscribe();
+        let mut manager = TabManager::new();
+        manager.set_event_bus(Some(bus));
+        manager.set_event_bus(None);
+
+        manager.add_tab(Box::new(MockTab::new(1, "Tab 1")));
+        assert!(rx.try_recv().is_err());
+    }
+
     // ==================== Edge Cases ====================
 
     #[test]
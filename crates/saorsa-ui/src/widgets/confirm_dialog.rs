@@ -0,0 +1,154 @@
+//! Confirmation dialog widget
+//!
+//! The [`ConfirmDialog`] widget renders a centered popup asking a yes/no
+//! question, such as whether to close a tab with unsaved changes. Handling
+//! the answer is up to the caller.
+//!
+//! ## Example
+//!
+//! ```ignore
+//! use saorsa_ui::widgets::ConfirmDialog;
+//!
+//! let dialog = ConfirmDialog::new("Close tab", "Files has unsaved changes.", &theme);
+//! frame.render_widget(dialog, content_area);
+//! ```
+
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Widget, Wrap};
+use saorsa_cli_core::Theme;
+
+/// Key hint shown along the bottom border
+const KEY_HINT: &str = " y confirm  n/Esc cancel ";
+
+/// Centered popup asking the user to confirm an action
+pub struct ConfirmDialog<'a> {
+    /// Short title shown in the top border
+    title: &'a str,
+    /// Question or explanation shown in the body
+    message: &'a str,
+    /// Theme for styling
+    theme: &'a Theme,
+}
+
+impl<'a> ConfirmDialog<'a> {
+    /// Creates a new confirmation dialog
+    ///
+    /// # Arguments
+    ///
+    /// * `title` - Short title, shown in the popup border
+    /// * `message` - Body text; long lines wrap
+    /// * `theme` - Theme for styling the popup
+    pub fn new(title: &'a str, message: &'a str, theme: &'a Theme) -> Self {
+        ConfirmDialog {
+            title,
+            message,
+            theme,
+        }
+    }
+
+    /// Returns the popup area for the given container
+    ///
+    /// The popup is wide enough for the key hint, at most 60 columns, and
+    /// tall enough for the wrapped message, shrinking to the container if
+    /// it is too small.
+    pub fn popup_area(&self, area: Rect) -> Rect {
+        let min_width = KEY_HINT.chars().count().max(self.title.chars().count() + 4) + 2;
+        let text_width = self.message.chars().count() + 4;
+        let width = text_width.clamp(min_width, 60.max(min_width)) as u16;
+        let width = width.min(area.width);
+
+        let inner_width = usize::from(width.saturating_sub(4)).max(1);
+        let lines: usize = self
+            .message
+            .lines()
+            .map(|line| line.chars().count().div_ceil(inner_width).max(1))
+            .sum();
+        let height = (lines as u16 + 2).min(area.height);
+        Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        )
+    }
+}
+
+impl Widget for ConfirmDialog<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width < 4 || area.height < 3 {
+            return;
+        }
+
+        let popup = self.popup_area(area);
+        Clear.render(popup, buf);
+
+        let block = Block::default()
+            .title(format!(" {} ", self.title))
+            .title_bottom(Line::from(KEY_HINT).right_aligned())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.colors.warning))
+            .style(Style::default().bg(self.theme.colors.background));
+        let inner = block.inner(popup);
+        block.render(popup, buf);
+
+        let body = inner.inner(Margin::new(1, 0));
+        Paragraph::new(self.message)
+            .style(Style::default().fg(self.theme.colors.foreground))
+            .wrap(Wrap { trim: true })
+            .render(body, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn buffer_text(buf: &Buffer, area: Rect) -> String {
+        (area.y..area.bottom())
+            .map(|y| {
+                (area.x..area.right())
+                    .map(|x| buf[(x, y)].symbol().to_string())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_confirm_dialog_shows_message_and_keys() {
+        let theme = Theme::dark();
+        let dialog = ConfirmDialog::new("Close tab", "Files has unsaved changes.", &theme);
+
+        let area = Rect::new(0, 0, 60, 10);
+        let mut buf = Buffer::empty(area);
+        dialog.render(area, &mut buf);
+
+        let text = buffer_text(&buf, area);
+        assert!(text.contains("Close tab"));
+        assert!(text.contains("Files has unsaved changes."));
+        assert!(text.contains("y confirm"));
+    }
+
+    #[test]
+    fn test_confirm_dialog_popup_wraps_long_messages() {
+        let theme = Theme::dark();
+        let message = "word ".repeat(30);
+        let dialog = ConfirmDialog::new("Close tab", &message, &theme);
+
+        let popup = dialog.popup_area(Rect::new(0, 0, 80, 20));
+        assert_eq!(popup.width, 60);
+        assert!(popup.height > 3);
+        assert_eq!(popup.x, 10);
+    }
+
+    #[test]
+    fn test_confirm_dialog_tiny_area() {
+        let theme = Theme::dark();
+        let dialog = ConfirmDialog::new("Close tab", "Unsaved changes.", &theme);
+
+        let area = Rect::new(0, 0, 8, 3);
+        let mut buf = Buffer::empty(area);
+        dialog.render(area, &mut buf);
+        // Should clip without panicking
+    }
+}
@@ -0,0 +1,187 @@
+//! Help overlay widget listing a tab's key bindings
+//!
+//! The [`HelpOverlay`] widget renders a centered popup with one row per
+//! key binding, as advertised by [`Tab::key_bindings`].
+//!
+//! ## Example
+//!
+//! ```ignore
+//! use saorsa_ui::widgets::HelpOverlay;
+//!
+//! let bindings = tab.key_bindings();
+//! let overlay = HelpOverlay::new(tab.title(), &bindings, &theme);
+//! frame.render_widget(overlay, content_area);
+//! ```
+//!
+//! [`Tab::key_bindings`]: saorsa_cli_core::Tab::key_bindings
+
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Clear, Widget};
+use saorsa_cli_core::Theme;
+
+/// Centered popup listing key bindings for the active tab
+pub struct HelpOverlay<'a> {
+    /// Title of the tab the bindings belong to
+    title: &'a str,
+    /// `(keys, description)` pairs in display order
+    bindings: &'a [(&'a str, &'a str)],
+    /// Theme for styling
+    theme: &'a Theme,
+}
+
+impl<'a> HelpOverlay<'a> {
+    /// Creates a new help overlay
+    ///
+    /// # Arguments
+    ///
+    /// * `title` - Name of the tab, shown in the popup title
+    /// * `bindings` - `(keys, description)` pairs to list
+    /// * `theme` - Theme for styling the popup
+    pub fn new(title: &'a str, bindings: &'a [(&'a str, &'a str)], theme: &'a Theme) -> Self {
+        HelpOverlay {
+            title,
+            bindings,
+            theme,
+        }
+    }
+
+    /// Returns the popup area for the given container
+    ///
+    /// The popup is sized to fit the longest binding and centered,
+    /// shrinking to the container if it is too small.
+    pub fn popup_area(&self, area: Rect) -> Rect {
+        let key_width = self.key_width();
+        let desc_width = self
+            .bindings
+            .iter()
+            .map(|(_, desc)| desc.chars().count())
+            .max()
+            .unwrap_or(0);
+        let title_width = self.title.chars().count() + 10;
+
+        // Borders + one space padding on each side + gap between columns
+        let width = (key_width + desc_width + 6).max(title_width) as u16;
+        let height = self.bindings.len() as u16 + 2;
+
+        let width = width.min(area.width);
+        let height = height.min(area.height);
+        Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        )
+    }
+
+    fn key_width(&self) -> usize {
+        self.bindings
+            .iter()
+            .map(|(keys, _)| keys.chars().count())
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl Widget for HelpOverlay<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if self.bindings.is_empty() || area.width < 4 || area.height < 3 {
+            return;
+        }
+
+        let popup = self.popup_area(area);
+        Clear.render(popup, buf);
+
+        let block = Block::default()
+            .title(format!(" {} keys ", self.title))
+            .title_bottom(Line::from(" ?/Esc close ").right_aligned())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.colors.accent))
+            .style(Style::default().bg(self.theme.colors.background));
+        let inner = block.inner(popup);
+        block.render(popup, buf);
+
+        let key_width = self.key_width();
+        let key_style = Style::default()
+            .fg(self.theme.colors.accent)
+            .add_modifier(Modifier::BOLD);
+        let desc_style = Style::default().fg(self.theme.colors.foreground);
+
+        for (row, (keys, desc)) in self.bindings.iter().enumerate() {
+            if row as u16 >= inner.height {
+                break;
+            }
+            let y = inner.y + row as u16;
+            let line = Line::from(vec![
+                Span::styled(format!(" {:<key_width$}  ", keys), key_style),
+                Span::styled(*desc, desc_style),
+            ]);
+            buf.set_line(inner.x, y, &line, inner.width);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BINDINGS: [(&str, &str); 2] = [("j/k", "Move selection"), ("Enter", "Open item")];
+
+    fn buffer_text(buf: &Buffer, area: Rect) -> String {
+        (area.y..area.bottom())
+            .map(|y| {
+                (area.x..area.right())
+                    .map(|x| buf[(x, y)].symbol().to_string())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_help_overlay_lists_bindings() {
+        let theme = Theme::dark();
+        let overlay = HelpOverlay::new("Git", &BINDINGS, &theme);
+
+        let area = Rect::new(0, 0, 60, 10);
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+
+        let text = buffer_text(&buf, area);
+        assert!(text.contains("Git keys"));
+        assert!(text.contains(" j/k    Move selection"));
+        assert!(text.contains(" Enter  Open item"));
+    }
+
+    #[test]
+    fn test_help_overlay_popup_is_centered() {
+        let theme = Theme::dark();
+        let overlay = HelpOverlay::new("Git", &BINDINGS, &theme);
+
+        let popup = overlay.popup_area(Rect::new(0, 0, 60, 10));
+        assert_eq!(popup.height, 4);
+        assert_eq!(popup.x, (60 - popup.width) / 2);
+    }
+
+    #[test]
+    fn test_help_overlay_empty_renders_nothing() {
+        let theme = Theme::dark();
+        let overlay = HelpOverlay::new("Empty", &[], &theme);
+
+        let area = Rect::new(0, 0, 40, 10);
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+
+        assert!(buffer_text(&buf, area).trim().is_empty());
+    }
+
+    #[test]
+    fn test_help_overlay_tiny_area() {
+        let theme = Theme::dark();
+        let overlay = HelpOverlay::new("Git", &BINDINGS, &theme);
+
+        let area = Rect::new(0, 0, 8, 3);
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+        // Should clip without panicking
+    }
+}
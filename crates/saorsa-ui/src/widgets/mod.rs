@@ -7,6 +7,9 @@
 //!
 //! - [`TabBar`] - Horizontal bar displaying tab titles with active tab highlighting
 //! - [`StatusBar`] - Three-section status bar for mode, context, and help hints
+//! - [`HelpOverlay`] - Popup listing the active tab's key bindings
+//! - [`ConfirmDialog`] - Yes/no popup for confirming an action
+//! - [`Toasts`] - Stacked, timed notifications in the corner of the screen
 //!
 //! ## Example
 //!
@@ -26,8 +29,14 @@
 //!     .right("?:help");
 //! ```
 
+pub mod confirm_dialog;
+pub mod help_overlay;
 pub mod status_bar;
 pub mod tab_bar;
+pub mod toasts;
 
+pub use confirm_dialog::ConfirmDialog;
+pub use help_overlay::HelpOverlay;
 pub use status_bar::StatusBar;
-pub use tab_bar::TabBar;
+pub use tab_bar::{TabBar, TabHit, TitleTruncation};
+pub use toasts::Toasts;
@@ -6,6 +6,7 @@
 //! ## Features
 //!
 //! - Three sections: left (mode), center (context), right (help)
+//! - Optional spinner segment for background work
 //! - Builder pattern for easy configuration
 //! - Theme-aware styling with accent and muted colors
 //! - Graceful handling of overflow and empty sections
@@ -26,6 +27,14 @@
 use ratatui::prelude::*;
 use ratatui::widgets::Widget;
 use saorsa_cli_core::Theme;
+use std::time::Duration;
+use unicode_width::UnicodeWidthStr;
+
+/// Braille spinner frames used by the progress segment
+pub const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// How often the spinner advances to its next frame
+pub const SPINNER_INTERVAL: Duration = Duration::from_millis(100);
 
 /// Status bar with left, center, and right sections
 ///
@@ -53,6 +62,10 @@ pub struct StatusBar<'a> {
     center: &'a str,
     /// Right section text (typically help/position)
     right: &'a str,
+    /// Background task description shown after the left section
+    progress: Option<&'a str>,
+    /// Index into [`SPINNER_FRAMES`] for the progress segment
+    spinner_frame: usize,
     /// Theme for styling
     theme: &'a Theme,
 }
@@ -74,6 +87,8 @@ impl<'a> StatusBar<'a> {
             left: "",
             center: "",
             right: "",
+            progress: None,
+            spinner_frame: 0,
             theme,
         }
     }
@@ -132,6 +147,27 @@ impl<'a> StatusBar<'a> {
         self.right = text;
         self
     }
+
+    /// Sets the progress segment shown while background work runs
+    ///
+    /// The segment is drawn after the left section as a spinner
+    /// followed by the text. `None` hides it.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Description of the running task
+    /// * `frame` - Spinner animation frame (wraps around)
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let status = StatusBar::new(&theme).progress(Some("Scanning... 42%"), 3);
+    /// ```
+    pub fn progress(mut self, text: Option<&'a str>, frame: usize) -> Self {
+        self.progress = text;
+        self.spinner_frame = frame;
+        self
+    }
 }
 
 impl Widget for StatusBar<'_> {
@@ -151,21 +187,35 @@ impl Widget for StatusBar<'_> {
         let width = area.width as usize;
 
         // Render left section (left-aligned) with padding
+        let mut left_end = area.x;
         if !self.left.is_empty() {
             let text = format!(" {} ", self.left);
-            if text.len() <= width {
+            if text.width() <= width {
                 let style = Style::default()
                     .fg(self.theme.colors.accent)
                     .bg(self.theme.colors.selection)
                     .add_modifier(Modifier::BOLD);
 
                 buf.set_string(area.x, area.y, &text, style);
+                left_end += text.width() as u16;
             }
         }
 
+        // Render progress segment right after the left section
+        if let Some(progress) = self.progress {
+            let spinner = SPINNER_FRAMES[self.spinner_frame % SPINNER_FRAMES.len()];
+            let text = format!(" {} {} ", spinner, progress);
+            let available = area.right().saturating_sub(left_end) as usize;
+            let style = Style::default()
+                .fg(self.theme.colors.warning)
+                .bg(self.theme.colors.selection);
+
+            buf.set_stringn(left_end, area.y, &text, available, style);
+        }
+
         // Render center section (centered)
         if !self.center.is_empty() {
-            let center_len = self.center.len();
+            let center_len = self.center.width();
             if center_len < width {
                 let start_x = area.x + (width.saturating_sub(center_len) / 2) as u16;
                 let style = Style::default()
@@ -179,8 +229,8 @@ impl Widget for StatusBar<'_> {
         // Render right section (right-aligned) with padding
         if !self.right.is_empty() {
             let text = format!(" {} ", self.right);
-            if text.len() <= width {
-                let start_x = area.right().saturating_sub(text.len() as u16);
+            if text.width() <= width {
+                let start_x = area.right().saturating_sub(text.width() as u16);
                 let style = Style::default()
                     .fg(self.theme.colors.muted)
                     .bg(self.theme.colors.selection);
@@ -278,6 +328,25 @@ mod tests {
         assert!(content.contains("help"));
     }
 
+    #[test]
+    fn test_status_bar_progress_segment() {
+        let theme = Theme::dark();
+        let status = StatusBar::new(&theme)
+            .left("NORMAL")
+            .progress(Some("Scanning... 42%"), 11);
+
+        let area = Rect::new(0, 0, 60, 1);
+        let mut buf = Buffer::empty(area);
+        status.render(area, &mut buf);
+
+        let content: String = (0..area.width)
+            .map(|x| buf[(x, 0)].symbol().to_string())
+            .collect();
+
+        assert!(content.contains("NORMAL"));
+        assert!(content.contains(&format!("{} Scanning... 42%", SPINNER_FRAMES[1])));
+    }
+
     #[test]
     fn test_status_bar_empty_sections() {
         let theme = Theme::dark();
@@ -390,6 +459,24 @@ mod tests {
         assert_eq!(cell.symbol(), "c");
     }
 
+    #[test]
+    fn test_status_bar_measures_wide_text() {
+        let theme = Theme::dark();
+        let status = StatusBar::new(&theme)
+            .left("模式")
+            .center("中心")
+            .right("状态");
+
+        let area = Rect::new(0, 0, 40, 1);
+        let mut buf = Buffer::empty(area);
+        status.render(area, &mut buf);
+
+        // " 状态 " takes six columns, so it starts six from the right edge
+        assert_eq!(buf[(35, 0)].symbol(), "状");
+        assert_eq!(buf[(18, 0)].symbol(), "中");
+        assert_eq!(buf[(1, 0)].symbol(), "模");
+    }
+
     #[test]
     fn test_status_bar_builder_chain() {
         let theme = Theme::dark();
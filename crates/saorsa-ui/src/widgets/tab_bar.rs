@@ -5,9 +5,14 @@
 //!
 //! ## Features
 //!
-//! - Displays tab titles with optional icons
+//! - Displays each tab's [`Tab::display_title`] with optional icons
 //! - Highlights active tab with bold accent color
 //! - Uses muted color for inactive tabs
+//! - Shows activity badges (dot, count, ✓/✗) after tab titles
+//! - Horizontal (one row) or vertical (one tab per row) layout
+//! - Shortens titles that don't fit using a [`TitleTruncation`] strategy
+//! - Optional `×` close button on tabs that [`Tab::can_close`]
+//! - Maps clicks back to tabs and close buttons with [`TabBar::hit_test`]
 //! - Gracefully handles empty tab lists
 //!
 //! ## Example
@@ -22,7 +27,153 @@
 
 use ratatui::prelude::*;
 use ratatui::widgets::{Tabs, Widget};
-use saorsa_cli_core::{Tab, Theme};
+use saorsa_cli_core::{Tab, TabBadge, Theme};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Columns `Tabs` pads around each title (one space on each side)
+const TAB_PADDING: usize = 2;
+
+/// Separator drawn between tabs in the horizontal layout
+const DIVIDER: &str = " | ";
+
+/// Close button drawn after a closable tab's label
+pub const CLOSE_GLYPH: &str = "×";
+
+/// What part of the tab bar a click landed on
+///
+/// # Example
+///
+/// ```
+/// use saorsa_ui::widgets::tab_bar::TabHit;
+///
+/// assert_eq!(TabHit::Close(2).index(), 2);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TabHit {
+    /// The label of the tab at this index: icon, title or badge
+    Title(usize),
+    /// The close button of the tab at this index
+    Close(usize),
+}
+
+impl TabHit {
+    /// Index of the tab that was hit
+    #[must_use]
+    pub fn index(self) -> usize {
+        match self {
+            TabHit::Title(index) | TabHit::Close(index) => index,
+        }
+    }
+}
+
+/// How tab titles are shortened when the bar runs out of room
+///
+/// # Example
+///
+/// ```
+/// use saorsa_ui::widgets::tab_bar::TitleTruncation;
+///
+/// let title = "src/widgets/tab_bar.rs";
+/// assert_eq!(TitleTruncation::End.apply(title, 10), "src/widge…");
+/// assert_eq!(TitleTruncation::Middle.apply(title, 10), "src/…ar.rs");
+/// assert_eq!(TitleTruncation::AbbreviatePath.apply(title, 14), "s/w/tab_bar.rs");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TitleTruncation {
+    /// Keep the start of the title and end with an ellipsis
+    #[default]
+    End,
+    /// Keep both ends of the title, so file names and extensions stay visible
+    Middle,
+    /// Shorten leading path components to their first letter, then fall
+    /// back to [`TitleTruncation::Middle`]
+    AbbreviatePath,
+}
+
+impl TitleTruncation {
+    /// Shortens `title` to at most `width` columns
+    ///
+    /// Titles that already fit are returned unchanged. Wide characters,
+    /// such as CJK, count as the two columns they take on screen.
+    #[must_use]
+    pub fn apply(self, title: &str, width: usize) -> String {
+        if title.width() <= width {
+            return title.to_string();
+        }
+        if width == 0 {
+            return String::new();
+        }
+        match self {
+            TitleTruncation::End => format!("{}…", head_within(title, width - 1)),
+            TitleTruncation::Middle => {
+                let keep = width - 1;
+                let head = head_within(title, keep / 2);
+                let tail = tail_within(title, keep - head.width());
+                format!("{head}…{tail}")
+            }
+            TitleTruncation::AbbreviatePath => {
+                let mut parts: Vec<String> = title.split('/').map(str::to_string).collect();
+                let last = parts.len() - 1;
+                for i in 0..last {
+                    if parts.join("/").width() <= width {
+                        break;
+                    }
+                    // Keep a leading dot so `.config` becomes `.c`
+                    let keep = if parts[i].starts_with('.') { 2 } else { 1 };
+                    parts[i] = parts[i].chars().take(keep).collect();
+                }
+                TitleTruncation::Middle.apply(&parts.join("/"), width)
+            }
+        }
+    }
+}
+
+/// The longest start of `text` at most `width` columns wide
+fn head_within(text: &str, width: usize) -> &str {
+    let mut used = 0;
+    for (i, c) in text.char_indices() {
+        used += c.width().unwrap_or(0);
+        if used > width {
+            return &text[..i];
+        }
+    }
+    text
+}
+
+/// The longest end of `text` at most `width` columns wide
+fn tail_within(text: &str, width: usize) -> &str {
+    let mut used = 0;
+    for (i, c) in text.char_indices().rev() {
+        used += c.width().unwrap_or(0);
+        if used > width {
+            return &text[i + c.len_utf8()..];
+        }
+    }
+    text
+}
+
+/// Largest title widths that fit in `available` columns, shortening the
+/// longest titles first
+fn fit_widths(widths: &[usize], available: usize) -> Vec<usize> {
+    if widths.iter().sum::<usize>() <= available {
+        return widths.to_vec();
+    }
+    let mut sorted = widths.to_vec();
+    sorted.sort_unstable();
+    let mut remaining = available;
+    let mut left = sorted.len();
+    let mut cap = 0;
+    for width in sorted {
+        let share = remaining / left;
+        if width > share {
+            cap = share;
+            break;
+        }
+        remaining -= width;
+        left -= 1;
+    }
+    widths.iter().map(|width| (*width).min(cap)).collect()
+}
 
 /// Tab bar widget that displays tab titles
 ///
@@ -46,6 +197,12 @@ pub struct TabBar<'a> {
     active_index: usize,
     /// Theme for styling
     theme: &'a Theme,
+    /// List tabs one per row instead of in a single row
+    vertical: bool,
+    /// How titles are shortened when they don't fit
+    truncation: TitleTruncation,
+    /// Draw a close button on tabs that can be closed
+    close_buttons: bool,
 }
 
 impl<'a> TabBar<'a> {
@@ -67,7 +224,91 @@ impl<'a> TabBar<'a> {
             tabs,
             active_index,
             theme,
+            vertical: false,
+            truncation: TitleTruncation::default(),
+            close_buttons: false,
+        }
+    }
+
+    /// Lists tabs one per row, for a tab bar placed beside the content
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let tab_bar = TabBar::new(&tabs, 0, &theme).vertical(true);
+    /// ```
+    pub fn vertical(mut self, vertical: bool) -> Self {
+        self.vertical = vertical;
+        self
+    }
+
+    /// Sets how titles are shortened when the bar runs out of room
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let tab_bar = TabBar::new(&tabs, 0, &theme).truncation(TitleTruncation::Middle);
+    /// ```
+    pub fn truncation(mut self, truncation: TitleTruncation) -> Self {
+        self.truncation = truncation;
+        self
+    }
+
+    /// Draws a [`CLOSE_GLYPH`] after the label of every tab that
+    /// [`Tab::can_close`]
+    ///
+    /// The button's width is taken from the room left for titles, and
+    /// [`TabBar::hit_test`] reports clicks on it as [`TabHit::Close`].
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let tab_bar = TabBar::new(&tabs, 0, &theme).close_buttons(true);
+    /// ```
+    pub fn close_buttons(mut self, close_buttons: bool) -> Self {
+        self.close_buttons = close_buttons;
+        self
+    }
+
+    /// Returns the index of the tab drawn at `(column, row)` when the bar is
+    /// rendered into `area`
+    ///
+    /// Titles are measured after truncation, exactly as they are drawn, so a
+    /// click maps to the tab under the pointer. Dividers map to no tab; a
+    /// close button maps to its tab.
+    pub fn tab_at(&self, area: Rect, column: u16, row: u16) -> Option<usize> {
+        self.hit_test(area, column, row).map(TabHit::index)
+    }
+
+    /// Returns what is drawn at `(column, row)` when the bar is rendered into
+    /// `area`: a tab's label, its close button, or nothing
+    ///
+    /// Labels are measured after truncation, exactly as they are drawn, so
+    /// a click on the `×` of a tab is told apart from one on its title.
+    pub fn hit_test(&self, area: Rect, column: u16, row: u16) -> Option<TabHit> {
+        if !area.contains(Position::new(column, row)) {
+            return None;
         }
+        let titles = self.titles(area);
+        let column = usize::from(column);
+        if self.vertical {
+            let index = usize::from(row - area.y);
+            let title = titles.get(index)?;
+            return Some(self.hit_in_label(index, usize::from(area.x), title.width(), column));
+        }
+        let mut x = usize::from(area.x);
+        for (index, title) in titles.iter().enumerate() {
+            let end = x + TAB_PADDING + title.width();
+            if column < end {
+                // `Tabs` pads one column before the label
+                return Some(self.hit_in_label(index, x + 1, title.width(), column));
+            }
+            x = end + DIVIDER.width();
+            if column < x {
+                return None;
+            }
+        }
+        None
     }
 
     /// Returns the number of tabs in this bar
@@ -83,21 +324,65 @@ impl<'a> TabBar<'a> {
     }
 }
 
-impl Widget for TabBar<'_> {
-    fn render(self, area: Rect, buf: &mut Buffer) {
-        // Handle empty tabs gracefully
-        if self.tabs.is_empty() {
-            return;
+impl TabBar<'_> {
+    /// Whether `tab` gets a close button
+    fn shows_close(&self, tab: &dyn Tab) -> bool {
+        self.close_buttons && tab.can_close()
+    }
+
+    /// Columns a tab uses besides its title: spaces, icon, badge and close
+    /// button
+    fn chrome_width(&self, tab: &dyn Tab) -> usize {
+        let icon = tab.icon().map_or(0, |icon| icon.width() + 1);
+        let badge = tab.badge().map_or(0, |badge| badge.marker().width() + 1);
+        let close = if self.shows_close(tab) {
+            CLOSE_GLYPH.width() + 1
+        } else {
+            0
+        };
+        2 + icon + badge + close
+    }
+
+    /// Whether `column` is on the close button of the tab at `index`, whose
+    /// label of `width` columns starts at `start`
+    fn hit_in_label(&self, index: usize, start: usize, width: usize, column: usize) -> TabHit {
+        // The label ends with the glyph and one space
+        let glyph = (start + width).saturating_sub(CLOSE_GLYPH.width() + 1);
+        let on_glyph = (glyph..glyph + CLOSE_GLYPH.width()).contains(&column);
+        if on_glyph && self.shows_close(self.tabs[index].as_ref()) {
+            TabHit::Close(index)
+        } else {
+            TabHit::Title(index)
         }
+    }
 
-        // Build tab titles with optional icons
-        let titles: Vec<Line> = self
-            .tabs
+    /// Styled tab labels, with titles truncated to fit `area`
+    fn titles(&self, area: Rect) -> Vec<Line<'static>> {
+        let area_width = usize::from(area.width);
+        let names: Vec<_> = self.tabs.iter().map(|tab| tab.display_title()).collect();
+        let title_widths: Vec<usize> = names.iter().map(|name| name.width()).collect();
+        let budgets = if self.vertical {
+            self.tabs
+                .iter()
+                .map(|tab| area_width.saturating_sub(self.chrome_width(tab.as_ref())))
+                .collect()
+        } else {
+            let chrome: usize = self
+                .tabs
+                .iter()
+                .map(|tab| self.chrome_width(tab.as_ref()) + TAB_PADDING)
+                .sum::<usize>()
+                + DIVIDER.width() * self.tabs.len().saturating_sub(1);
+            fit_widths(&title_widths, area_width.saturating_sub(chrome))
+        };
+
+        self.tabs
             .iter()
+            .zip(names.iter().zip(budgets))
             .enumerate()
-            .map(|(i, tab)| {
+            .map(|(i, (tab, (name, budget)))| {
                 let icon = tab.icon().unwrap_or("");
-                let title = tab.title();
+                let title = self.truncation.apply(name, budget);
                 let content = if icon.is_empty() {
                     format!(" {} ", title)
                 } else {
@@ -105,7 +390,7 @@ impl Widget for TabBar<'_> {
                 };
 
                 // Style based on whether this is the active tab
-                if i == self.active_index {
+                let mut line = if i == self.active_index {
                     Line::from(content).style(
                         Style::default()
                             .fg(self.theme.colors.accent)
@@ -113,13 +398,54 @@ impl Widget for TabBar<'_> {
                     )
                 } else {
                     Line::from(content).style(Style::default().fg(self.theme.colors.muted))
+                };
+
+                if let Some(badge) = tab.badge() {
+                    let color = match badge {
+                        TabBadge::Success => self.theme.colors.success,
+                        TabBadge::Failure => self.theme.colors.error,
+                        TabBadge::Dot | TabBadge::Count(_) => self.theme.colors.warning,
+                    };
+                    line.push_span(Span::styled(
+                        format!("{} ", badge.marker()),
+                        Style::default().fg(color).add_modifier(Modifier::BOLD),
+                    ));
                 }
+
+                if self.shows_close(tab.as_ref()) {
+                    line.push_span(Span::styled(
+                        format!("{CLOSE_GLYPH} "),
+                        Style::default().fg(self.theme.colors.muted),
+                    ));
+                }
+
+                line
             })
-            .collect();
+            .collect()
+    }
+}
+
+impl Widget for TabBar<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        // Handle empty tabs gracefully
+        if self.tabs.is_empty() {
+            return;
+        }
+
+        let titles = self.titles(area);
+
+        if self.vertical {
+            let background = Style::default().bg(self.theme.colors.background);
+            buf.set_style(area, background);
+            for (row, title) in titles.iter().enumerate().take(area.height as usize) {
+                buf.set_line(area.x, area.y + row as u16, title, area.width);
+            }
+            return;
+        }
 
         let tabs_widget = Tabs::new(titles)
             .select(self.active_index)
-            .divider(" | ")
+            .divider(DIVIDER)
             .style(Style::default().bg(self.theme.colors.background));
 
         tabs_widget.render(area, buf);
@@ -129,13 +455,16 @@ impl Widget for TabBar<'_> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use saorsa_cli_core::{Tab, TabId, Theme};
+    use saorsa_cli_core::AppContext;
+    use saorsa_cli_core::{CoreResult, Tab, TabId, Theme};
 
     /// Test implementation of the Tab trait
     struct TestTab {
         id: TabId,
         title: String,
         icon: Option<String>,
+        badge: Option<TabBadge>,
+        closeable: bool,
     }
 
     impl TestTab {
@@ -144,6 +473,8 @@ mod tests {
                 id,
                 title: title.to_string(),
                 icon: None,
+                badge: None,
+                closeable: true,
             }
         }
 
@@ -166,11 +497,21 @@ mod tests {
             self.icon.as_deref()
         }
 
-        fn focus(&mut self) {}
+        fn badge(&self) -> Option<TabBadge> {
+            self.badge
+        }
+
+        fn can_close(&self) -> bool {
+            self.closeable
+        }
+
+        fn focus(&mut self, _ctx: &AppContext) {}
 
         fn blur(&mut self) {}
 
-        fn view(&self, _frame: &mut Frame, _area: Rect) {}
+        fn view(&self, _frame: &mut Frame, _area: Rect, _ctx: &AppContext) -> CoreResult<()> {
+            Ok(())
+        }
     }
 
     fn create_test_tabs() -> Vec<Box<dyn Tab>> {
@@ -234,6 +575,44 @@ mod tests {
         assert!(content.contains("Tab 3"));
     }
 
+    #[test]
+    fn test_tab_bar_renders_display_title() {
+        struct CountingTab;
+
+        impl Tab for CountingTab {
+            fn id(&self) -> TabId {
+                1
+            }
+
+            fn title(&self) -> &str {
+                "Files"
+            }
+
+            fn display_title(&self) -> std::borrow::Cow<'_, str> {
+                format!("{} (3 selected)", self.title()).into()
+            }
+
+            fn focus(&mut self, _ctx: &AppContext) {}
+
+            fn blur(&mut self) {}
+
+            fn view(&self, _frame: &mut Frame, _area: Rect, _ctx: &AppContext) -> CoreResult<()> {
+                Ok(())
+            }
+        }
+
+        let tabs: Vec<Box<dyn Tab>> = vec![Box::new(CountingTab)];
+        let theme = Theme::dark();
+        let tab_bar = TabBar::new(&tabs, 0, &theme);
+
+        let area = Rect::new(0, 0, 40, 1);
+        let mut buf = Buffer::empty(area);
+        tab_bar.render(area, &mut buf);
+
+        let content: String = (0..area.width).map(|x| buf[(x, 0)].symbol()).collect();
+        assert!(content.contains("Files (3 selected)"));
+    }
+
     #[test]
     fn test_tab_bar_with_icons() {
         let tabs: Vec<Box<dyn Tab>> = vec![
@@ -255,6 +634,32 @@ mod tests {
         assert!(content.contains("Files"));
     }
 
+    #[test]
+    fn test_tab_bar_renders_badges() {
+        let mut plugins = TestTab::new(2, "Plugins");
+        plugins.badge = Some(TabBadge::Success);
+        let mut files = TestTab::new(3, "Files");
+        files.badge = Some(TabBadge::Count(4));
+        let tabs: Vec<Box<dyn Tab>> = vec![
+            Box::new(TestTab::new(1, "Git")),
+            Box::new(plugins),
+            Box::new(files),
+        ];
+        let theme = Theme::dark();
+        let tab_bar = TabBar::new(&tabs, 0, &theme);
+
+        let area = Rect::new(0, 0, 60, 1);
+        let mut buf = Buffer::empty(area);
+        tab_bar.render(area, &mut buf);
+
+        let content: String = (0..area.width)
+            .map(|x| buf[(x, 0)].symbol().chars().next().unwrap_or(' '))
+            .collect();
+
+        assert!(content.contains("Plugins \u{2713}"));
+        assert!(content.contains("Files 4"));
+    }
+
     #[test]
     fn test_tab_bar_active_tab_highlighted() {
         let tabs = create_test_tabs();
@@ -294,6 +699,169 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_tab_bar_vertical() {
+        let tabs = create_test_tabs();
+        let theme = Theme::dark();
+        let tab_bar = TabBar::new(&tabs, 1, &theme).vertical(true);
+
+        let area = Rect::new(0, 0, 12, 5);
+        let mut buf = Buffer::empty(area);
+        tab_bar.render(area, &mut buf);
+
+        let row = |y: u16| -> String {
+            (0..area.width)
+                .map(|x| buf[(x, y)].symbol().to_string())
+                .collect()
+        };
+        assert_eq!(row(0).trim(), "Tab 1");
+        assert_eq!(row(1).trim(), "Tab 2");
+        assert_eq!(row(2).trim(), "Tab 3");
+        assert_eq!(buf[(1, 1)].fg, theme.colors.accent);
+    }
+
+    #[test]
+    fn test_title_truncation_strategies() {
+        let title = "crates/saorsa-ui/src/app.rs";
+        assert_eq!(TitleTruncation::End.apply(title, 40), title);
+        assert_eq!(TitleTruncation::End.apply(title, 8), "crates/…");
+        assert_eq!(TitleTruncation::Middle.apply(title, 8), "cra…p.rs");
+        assert_eq!(
+            TitleTruncation::AbbreviatePath.apply(title, 20),
+            "c/s/src/app.rs".to_string()
+        );
+        assert_eq!(
+            TitleTruncation::AbbreviatePath.apply(".config/sb/settings.json", 20),
+            ".c/sb/settings.json"
+        );
+        assert_eq!(TitleTruncation::AbbreviatePath.apply(title, 8), "c/s…p.rs");
+        assert_eq!(TitleTruncation::Middle.apply(title, 1), "…");
+        assert_eq!(TitleTruncation::Middle.apply(title, 0), "");
+    }
+
+    #[test]
+    fn test_title_truncation_counts_wide_characters() {
+        let title = "文档/说明书.md";
+        assert_eq!(TitleTruncation::End.apply(title, 14), title);
+        assert_eq!(TitleTruncation::End.apply(title, 7), "文档/…");
+        assert_eq!(TitleTruncation::Middle.apply(title, 7), "文….md");
+        assert_eq!(
+            TitleTruncation::AbbreviatePath.apply(title, 12),
+            "文/说明书.md"
+        );
+    }
+
+    #[test]
+    fn test_fit_widths_shortens_longest_first() {
+        assert_eq!(fit_widths(&[5, 30, 10], 60), vec![5, 30, 10]);
+        assert_eq!(fit_widths(&[5, 30, 10], 30), vec![5, 15, 10]);
+        assert_eq!(fit_widths(&[20, 20], 10), vec![5, 5]);
+    }
+
+    #[test]
+    fn test_tab_bar_truncates_to_fit() {
+        let tabs: Vec<Box<dyn Tab>> = vec![
+            Box::new(TestTab::new(1, "Git")),
+            Box::new(TestTab::new(2, "notes/projects/roadmap.md")),
+        ];
+        let theme = Theme::dark();
+        let tab_bar = TabBar::new(&tabs, 0, &theme).truncation(TitleTruncation::Middle);
+
+        let area = Rect::new(0, 0, 28, 1);
+        let mut buf = Buffer::empty(area);
+        tab_bar.render(area, &mut buf);
+
+        let content: String = (0..area.width).map(|x| buf[(x, 0)].symbol()).collect();
+        assert_eq!(content, "  Git   |   notes/…dmap.md  ");
+    }
+
+    #[test]
+    fn test_tab_bar_tab_at_matches_rendered_titles() {
+        let mut files = TestTab::new(2, "notes/projects/roadmap.md").with_icon("F");
+        files.badge = Some(TabBadge::Count(3));
+        let tabs: Vec<Box<dyn Tab>> = vec![Box::new(TestTab::new(1, "Git")), Box::new(files)];
+        let theme = Theme::dark();
+        let tab_bar = TabBar::new(&tabs, 0, &theme).truncation(TitleTruncation::End);
+
+        let area = Rect::new(2, 1, 30, 1);
+        // "  Git   |   F notes/projec… 3  " starting at column 2
+        assert_eq!(tab_bar.tab_at(area, 2, 1), Some(0));
+        assert_eq!(tab_bar.tab_at(area, 8, 1), Some(0));
+        assert_eq!(tab_bar.tab_at(area, 10, 1), None);
+        assert_eq!(tab_bar.tab_at(area, 12, 1), Some(1));
+        assert_eq!(tab_bar.tab_at(area, 31, 1), Some(1));
+        assert_eq!(tab_bar.tab_at(area, 12, 2), None);
+
+        let vertical = TabBar::new(&tabs, 0, &theme).vertical(true);
+        let area = Rect::new(0, 0, 12, 5);
+        assert_eq!(vertical.tab_at(area, 3, 1), Some(1));
+        assert_eq!(vertical.tab_at(area, 3, 2), None);
+    }
+
+    #[test]
+    fn test_tab_bar_tab_at_measures_wide_titles() {
+        let tabs: Vec<Box<dyn Tab>> = vec![
+            Box::new(TestTab::new(1, "Git")),
+            Box::new(TestTab::new(2, "设置")),
+        ];
+        let theme = Theme::dark();
+        let tab_bar = TabBar::new(&tabs, 0, &theme);
+
+        // "  Git   |   设置  ": the second tab is eight columns wide
+        let area = Rect::new(0, 0, 30, 1);
+        let mut buf = Buffer::empty(area);
+        TabBar::new(&tabs, 0, &theme).render(area, &mut buf);
+        assert_eq!(buf[(12, 0)].symbol(), "设");
+        assert_eq!(tab_bar.tab_at(area, 17, 0), Some(1));
+        assert_eq!(tab_bar.tab_at(area, 18, 0), None);
+    }
+
+    #[test]
+    fn test_tab_bar_close_buttons() {
+        let mut pinned = TestTab::new(2, "Home");
+        pinned.closeable = false;
+        let tabs: Vec<Box<dyn Tab>> = vec![Box::new(TestTab::new(1, "Git")), Box::new(pinned)];
+        let theme = Theme::dark();
+        let tab_bar = TabBar::new(&tabs, 0, &theme).close_buttons(true);
+
+        let area = Rect::new(0, 0, 20, 1);
+        // "  Git ×   |   Home  " - only the closable tab gets a button
+        assert_eq!(tab_bar.hit_test(area, 6, 0), Some(TabHit::Close(0)));
+        assert_eq!(tab_bar.hit_test(area, 2, 0), Some(TabHit::Title(0)));
+        assert_eq!(tab_bar.hit_test(area, 7, 0), Some(TabHit::Title(0)));
+        assert_eq!(tab_bar.hit_test(area, 9, 0), None);
+        assert_eq!(tab_bar.hit_test(area, 17, 0), Some(TabHit::Title(1)));
+        assert_eq!(tab_bar.tab_at(area, 6, 0), Some(0));
+
+        let mut buf = Buffer::empty(area);
+        TabBar::new(&tabs, 0, &theme)
+            .close_buttons(true)
+            .render(area, &mut buf);
+        assert_eq!(buf[(6, 0)].symbol(), CLOSE_GLYPH);
+        assert_eq!(buf[(6, 0)].fg, theme.colors.muted);
+
+        // The button's width comes out of the room left for titles
+        let single = TabBar::new(&tabs[..1], 0, &theme).close_buttons(true);
+        assert_eq!(
+            single.titles(Rect::new(0, 0, 9, 1))[0].to_string(),
+            " Git × "
+        );
+        assert_eq!(
+            single.titles(Rect::new(0, 0, 8, 1))[0].to_string(),
+            " G… × "
+        );
+
+        let vertical = TabBar::new(&tabs, 0, &theme)
+            .vertical(true)
+            .close_buttons(true);
+        let side = Rect::new(0, 0, 12, 5);
+        // " Git × " on the first row
+        assert_eq!(vertical.hit_test(side, 5, 0), Some(TabHit::Close(0)));
+        assert_eq!(vertical.hit_test(side, 4, 0), Some(TabHit::Title(0)));
+        assert_eq!(vertical.hit_test(side, 1, 0), Some(TabHit::Title(0)));
+        assert_eq!(vertical.hit_test(side, 4, 1), Some(TabHit::Title(1)));
+    }
+
     #[test]
     fn test_tab_bar_narrow_area() {
         let tabs = create_test_tabs();
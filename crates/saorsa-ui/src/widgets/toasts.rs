@@ -0,0 +1,157 @@
+//! Transient notification widget
+//!
+//! The [`Toasts`] widget stacks the entries of a [`Notifications`] queue
+//! in the top-right corner of an area, newest at the bottom, each in a
+//! small box colored by its [`NotifyLevel`].
+//!
+//! ## Example
+//!
+//! ```ignore
+//! use saorsa_ui::widgets::Toasts;
+//!
+//! let toasts = Toasts::new(&notifications, &theme);
+//! frame.render_widget(toasts, content_area);
+//! ```
+
+use crate::notifications::Notifications;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Widget};
+use saorsa_cli_core::{NotifyLevel, Theme};
+
+/// Widest a toast grows, borders included
+const MAX_WIDTH: usize = 50;
+
+/// Rows used by one toast: the message between two borders
+const TOAST_HEIGHT: u16 = 3;
+
+/// Stack of notifications drawn over the top-right corner of an area
+pub struct Toasts<'a> {
+    /// Notifications to show, oldest first
+    notifications: &'a Notifications,
+    /// Theme for styling
+    theme: &'a Theme,
+}
+
+impl<'a> Toasts<'a> {
+    /// Creates a toast stack
+    ///
+    /// # Arguments
+    ///
+    /// * `notifications` - Queue to show, oldest first
+    /// * `theme` - Theme for styling the boxes
+    pub fn new(notifications: &'a Notifications, theme: &'a Theme) -> Self {
+        Toasts {
+            notifications,
+            theme,
+        }
+    }
+}
+
+impl Widget for Toasts<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width < 6 {
+            return;
+        }
+        let mut y = area.y;
+        for notification in self.notifications.iter() {
+            if y + TOAST_HEIGHT > area.bottom() {
+                break;
+            }
+            let (title, color) = match notification.level {
+                NotifyLevel::Info => (" Info ", self.theme.colors.accent),
+                NotifyLevel::Warning => (" Warning ", self.theme.colors.warning),
+                NotifyLevel::Error => (" Error ", self.theme.colors.error),
+            };
+
+            // Single line, cut with an ellipsis when it doesn't fit
+            let max_text = MAX_WIDTH.min(usize::from(area.width)) - 4;
+            let mut text: String = notification.message.lines().next().unwrap_or("").into();
+            if text.chars().count() > max_text {
+                text = text.chars().take(max_text - 1).collect();
+                text.push('…');
+            }
+            // The title may be cut in areas narrower than it
+            let width = ((text.chars().count().max(title.len()) + 4) as u16).min(area.width);
+            let toast = Rect::new(area.right() - width, y, width, TOAST_HEIGHT);
+
+            Clear.render(toast, buf);
+            let block = Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(color))
+                .style(Style::default().bg(self.theme.colors.background));
+            Paragraph::new(format!(" {text}"))
+                .style(Style::default().fg(self.theme.colors.foreground))
+                .block(block)
+                .render(toast, buf);
+            y += TOAST_HEIGHT;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notifications::NOTIFICATION_TIMEOUT;
+
+    fn row(buf: &Buffer, y: u16) -> String {
+        (buf.area.x..buf.area.right())
+            .map(|x| buf[(x, y)].symbol().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_toasts_stack_in_top_right() {
+        let theme = Theme::dark();
+        let mut notifications = Notifications::new();
+        notifications.push(NotifyLevel::Info, "Saved notes.md", NOTIFICATION_TIMEOUT);
+        notifications.push(
+            NotifyLevel::Error,
+            "Plugin fmt failed",
+            NOTIFICATION_TIMEOUT,
+        );
+
+        let area = Rect::new(0, 0, 60, 10);
+        let mut buf = Buffer::empty(area);
+        Toasts::new(&notifications, &theme).render(area, &mut buf);
+
+        assert!(row(&buf, 1).ends_with(" Saved notes.md │"));
+        assert!(row(&buf, 3).contains("Error"));
+        assert!(row(&buf, 4).contains("Plugin fmt failed"));
+        assert_eq!(buf[(59, 3)].fg, theme.colors.error);
+        assert!(row(&buf, 6).trim().is_empty());
+    }
+
+    #[test]
+    fn test_toasts_truncate_long_messages() {
+        let theme = Theme::dark();
+        let mut notifications = Notifications::new();
+        notifications.push(NotifyLevel::Warning, "x".repeat(200), NOTIFICATION_TIMEOUT);
+
+        let area = Rect::new(0, 0, 30, 5);
+        let mut buf = Buffer::empty(area);
+        Toasts::new(&notifications, &theme).render(area, &mut buf);
+
+        assert!(row(&buf, 1).contains("x…"));
+    }
+
+    #[test]
+    fn test_toasts_tiny_area() {
+        let theme = Theme::dark();
+        let mut notifications = Notifications::new();
+        notifications.push(NotifyLevel::Info, "hello", NOTIFICATION_TIMEOUT);
+        notifications.push(NotifyLevel::Warning, "careful", NOTIFICATION_TIMEOUT);
+
+        let area = Rect::new(0, 0, 5, 2);
+        let mut buf = Buffer::empty(area);
+        Toasts::new(&notifications, &theme).render(area, &mut buf);
+        // Should clip without panicking
+
+        // Wide enough to draw, but narrower than the titles
+        let area = Rect::new(2, 0, 8, 6);
+        let mut buf = Buffer::empty(area);
+        Toasts::new(&notifications, &theme).render(area, &mut buf);
+        assert_eq!(buf[(2, 0)].symbol(), "┌");
+        assert_eq!(buf[(9, 3)].symbol(), "┐");
+    }
+}
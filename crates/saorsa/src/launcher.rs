@@ -0,0 +1,353 @@
+//! New-tab launcher popup
+//!
+//! Ctrl+T opens a small menu for adding a Files, Disk or Git tab, followed
+//! by a directory prompt for where the tab should start. The host builds
+//! the tab from the returned [`LauncherAction::Open`].
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use saorsa_cli_core::Theme;
+use std::path::{Path, PathBuf};
+
+/// Kinds of tab the launcher can open
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TabKind {
+    Files,
+    Disk,
+    Git,
+}
+
+impl TabKind {
+    const ALL: [TabKind; 3] = [TabKind::Files, TabKind::Disk, TabKind::Git];
+
+    fn name(self) -> &'static str {
+        match self {
+            TabKind::Files => "Files",
+            TabKind::Disk => "Disk",
+            TabKind::Git => "Git",
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            TabKind::Files => "browse and edit markdown",
+            TabKind::Disk => "analyze disk usage",
+            TabKind::Git => "status, diffs and commits",
+        }
+    }
+}
+
+/// What the host should do after the launcher handled a key
+pub enum LauncherAction {
+    /// Keep the launcher open
+    Pending,
+    /// Close the launcher without opening anything
+    Cancel,
+    /// Close the launcher and open a tab of this kind in the directory
+    Open(TabKind, PathBuf),
+}
+
+enum Stage {
+    /// Choosing the kind of tab
+    Menu,
+    /// Typing the directory for the chosen kind
+    Directory {
+        kind: TabKind,
+        input: String,
+        error: Option<String>,
+    },
+}
+
+/// Modal menu for opening another tab
+pub struct Launcher {
+    selected: usize,
+    stage: Stage,
+    /// Directory the prompt starts from; relative input resolves against it
+    base_dir: PathBuf,
+}
+
+impl Launcher {
+    pub fn new(base_dir: PathBuf) -> Self {
+        Self {
+            selected: 0,
+            stage: Stage::Menu,
+            base_dir,
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> LauncherAction {
+        match &mut self.stage {
+            Stage::Menu => match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => return LauncherAction::Cancel,
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.selected = self.selected.saturating_sub(1);
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.selected = (self.selected + 1).min(TabKind::ALL.len() - 1);
+                }
+                KeyCode::Char(c @ '1'..='3') => {
+                    self.selected = c as usize - '1' as usize;
+                    self.choose();
+                }
+                KeyCode::Enter => self.choose(),
+                _ => {}
+            },
+            Stage::Directory { kind, input, error } => {
+                let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+                match key.code {
+                    KeyCode::Esc => self.stage = Stage::Menu,
+                    KeyCode::Enter => {
+                        let dir = resolve_dir(&self.base_dir, input);
+                        if dir.is_dir() {
+                            return LauncherAction::Open(*kind, dir.canonicalize().unwrap_or(dir));
+                        }
+                        *error = Some(format!("Not a directory: {}", dir.display()));
+                    }
+                    KeyCode::Tab => {
+                        if let Some(completed) = complete_dir(&self.base_dir, input) {
+                            *input = completed;
+                        }
+                        *error = None;
+                    }
+                    KeyCode::Backspace => {
+                        input.pop();
+                        *error = None;
+                    }
+                    KeyCode::Char('u') if ctrl => {
+                        input.clear();
+                        *error = None;
+                    }
+                    KeyCode::Char(c) if !ctrl => {
+                        input.push(c);
+                        *error = None;
+                    }
+                    _ => {}
+                }
+            }
+        }
+        LauncherAction::Pending
+    }
+
+    /// Move on to the directory prompt for the selected kind
+    fn choose(&mut self) {
+        let mut input = self.base_dir.display().to_string();
+        if !input.ends_with(std::path::MAIN_SEPARATOR) {
+            input.push(std::path::MAIN_SEPARATOR);
+        }
+        self.stage = Stage::Directory {
+            kind: TabKind::ALL[self.selected],
+            input,
+            error: None,
+        };
+    }
+
+    pub fn render(&self, frame: &mut Frame, theme: &Theme) {
+        let colors = &theme.colors;
+        let (title, hint, lines) = match &self.stage {
+            Stage::Menu => {
+                let lines = TabKind::ALL
+                    .iter()
+                    .enumerate()
+                    .map(|(i, kind)| {
+                        let style = if i == self.selected {
+                            Style::default()
+                                .fg(colors.accent)
+                                .add_modifier(Modifier::BOLD)
+                        } else {
+                            Style::default().fg(colors.foreground)
+                        };
+                        Line::from(vec![
+                            Span::styled(format!(" {}  {:<6}", i + 1, kind.name()), style),
+                            Span::styled(kind.description(), Style::default().fg(colors.muted)),
+                        ])
+                    })
+                    .collect();
+                (" New tab ".to_string(), " Enter select  Esc cancel ", lines)
+            }
+            Stage::Directory { kind, input, error } => {
+                let mut lines = vec![
+                    Line::styled(" Directory:", Style::default().fg(colors.muted)),
+                    Line::from(vec![
+                        Span::styled(format!(" {input}"), Style::default().fg(colors.foreground)),
+                        Span::styled("█", Style::default().fg(colors.accent)),
+                    ]),
+                ];
+                if let Some(error) = error {
+                    lines.push(Line::styled(
+                        format!(" {error}"),
+                        Style::default().fg(colors.error),
+                    ));
+                }
+                (
+                    format!(" New {} tab ", kind.name()),
+                    " Tab complete  Enter open  Esc back ",
+                    lines,
+                )
+            }
+        };
+
+        let area = frame.area();
+        let width = 60.min(area.width);
+        let height = (lines.len() as u16 + 2).min(area.height);
+        let popup = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+        let block = Block::default()
+            .title(title)
+            .title_bottom(Line::from(hint).right_aligned())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(colors.accent))
+            .style(Style::default().bg(colors.background));
+        frame.render_widget(Clear, popup);
+        frame.render_widget(Paragraph::new(lines).block(block), popup);
+    }
+}
+
+/// Expand `~` and resolve relative input against `base`
+fn resolve_dir(base: &Path, input: &str) -> PathBuf {
+    let input = input.trim();
+    if let Some(rest) = input.strip_prefix('~') {
+        if let Some(home) = dirs::home_dir() {
+            return home.join(rest.trim_start_matches(['/', '\\']));
+        }
+    }
+    base.join(input)
+}
+
+/// Complete the last component of `input` to the longest prefix shared by
+/// the matching subdirectories
+fn complete_dir(base: &Path, input: &str) -> Option<String> {
+    let (parent, partial) = match input.rfind(['/', '\\']) {
+        Some(i) => (&input[..=i], &input[i + 1..]),
+        None => ("", input),
+    };
+    let mut matches: Vec<String> = std::fs::read_dir(resolve_dir(base, parent))
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| name.starts_with(partial))
+        .collect();
+    matches.sort();
+    let first = matches.first()?;
+    let common = matches.iter().fold(first.len(), |len, name| {
+        first
+            .char_indices()
+            .zip(name.chars())
+            .take_while(|((_, a), b)| a == b)
+            .last()
+            .map_or(0, |((i, c), _)| i + c.len_utf8())
+            .min(len)
+    });
+    let mut completed = format!("{parent}{}", &first[..common]);
+    if matches.len() == 1 {
+        completed.push(std::path::MAIN_SEPARATOR);
+    }
+    Some(completed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(launcher: &mut Launcher, code: KeyCode) -> LauncherAction {
+        launcher.handle_key(KeyEvent::from(code))
+    }
+
+    fn input(launcher: &Launcher) -> &str {
+        match &launcher.stage {
+            Stage::Directory { input, .. } => input,
+            Stage::Menu => panic!("still in the menu"),
+        }
+    }
+
+    #[test]
+    fn menu_picks_a_kind_and_opens_its_directory() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::create_dir(dir.path().join("repo")).expect("create repo");
+        let mut launcher = Launcher::new(dir.path().to_path_buf());
+
+        press(&mut launcher, KeyCode::Down);
+        press(&mut launcher, KeyCode::Down);
+        press(&mut launcher, KeyCode::Down);
+        assert_eq!(launcher.selected, 2);
+        press(&mut launcher, KeyCode::Enter);
+        assert!(input(&launcher).ends_with(std::path::MAIN_SEPARATOR));
+
+        // Esc goes back to the menu, and a digit chooses directly
+        press(&mut launcher, KeyCode::Esc);
+        assert!(matches!(launcher.stage, Stage::Menu));
+        press(&mut launcher, KeyCode::Char('2'));
+        launcher.handle_key(KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL));
+        assert_eq!(input(&launcher), "");
+        for c in "repo".chars() {
+            press(&mut launcher, KeyCode::Char(c));
+        }
+        let LauncherAction::Open(kind, path) = press(&mut launcher, KeyCode::Enter) else {
+            panic!("expected the tab to open");
+        };
+        assert_eq!(kind, TabKind::Disk);
+        assert_eq!(path, dir.path().join("repo").canonicalize().unwrap());
+    }
+
+    #[test]
+    fn missing_directory_keeps_the_prompt_open() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut launcher = Launcher::new(dir.path().to_path_buf());
+        press(&mut launcher, KeyCode::Enter);
+        press(&mut launcher, KeyCode::Char('x'));
+
+        assert!(matches!(
+            press(&mut launcher, KeyCode::Enter),
+            LauncherAction::Pending
+        ));
+        assert!(matches!(
+            &launcher.stage,
+            Stage::Directory { error: Some(e), .. } if e.starts_with("Not a directory")
+        ));
+        press(&mut launcher, KeyCode::Backspace);
+        assert!(matches!(
+            &launcher.stage,
+            Stage::Directory { error: None, .. }
+        ));
+        press(&mut launcher, KeyCode::Esc);
+        assert!(matches!(
+            press(&mut launcher, KeyCode::Char('q')),
+            LauncherAction::Cancel
+        ));
+    }
+
+    #[test]
+    fn tab_completes_directory_names() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let base = dir.path();
+        std::fs::create_dir(base.join("projects")).expect("create projects");
+        std::fs::create_dir(base.join("progress")).expect("create progress");
+        std::fs::create_dir(base.join("notes")).expect("create notes");
+        std::fs::write(base.join("programs.txt"), "").expect("write file");
+        let sep = std::path::MAIN_SEPARATOR;
+
+        // Several matches complete to what they share; files are skipped
+        assert_eq!(complete_dir(base, "pr").as_deref(), Some("pro"));
+        assert_eq!(complete_dir(base, "proj"), Some(format!("projects{sep}")));
+        // A single match gets a trailing separator
+        assert_eq!(complete_dir(base, "n"), Some(format!("notes{sep}")));
+        assert_eq!(complete_dir(base, "zzz"), None);
+        assert_eq!(
+            complete_dir(base, "notes/").as_deref(),
+            None,
+            "an empty directory has nothing to complete"
+        );
+
+        let mut launcher = Launcher::new(base.to_path_buf());
+        press(&mut launcher, KeyCode::Enter);
+        launcher.handle_key(KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL));
+        press(&mut launcher, KeyCode::Char('n'));
+        press(&mut launcher, KeyCode::Tab);
+        assert_eq!(input(&launcher), format!("notes{sep}"));
+    }
+}
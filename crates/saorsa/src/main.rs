@@ -2,26 +2,41 @@
 //!
 //! A tabbed terminal interface combining file browser, disk analyzer, and more.
 
+mod launcher;
 mod plugins_tab;
 
 use clap::Parser;
 use color_eyre::Result;
 use crossterm::{
-    event::{self, Event, KeyCode, KeyModifiers},
+    event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
+use launcher::{Launcher, LauncherAction, TabKind};
 use parking_lot::Mutex;
 use plugins_tab::PluginsTab;
 use ratatui::prelude::*;
-use saorsa_cli_core::{AppCoordinator, Message, PluginManager};
+use saorsa_cli_core::{
+    to_monochrome, AppConfig, AppCoordinator, ColorDepth, FocusDirection, Message, NotifyLevel,
+    PluginManager, Recorder, Replay, ReplayPace, Tab,
+};
 use saorsa_disk::DiskTab;
 use saorsa_git::GitTab;
 use saorsa_sb::SbTab;
-use saorsa_ui::App;
+use saorsa_ui::{App, LayoutConfig};
 use std::io::stdout;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How long the main loop sleeps waiting for input when no tab is animating
+const IDLE_POLL_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How long `Ctrl+W` waits for a direction or pane number before closing the tab
+const WINDOW_CHORD_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Alt+Shift, held for the pane shortcuts
+const ALT_SHIFT: KeyModifiers = KeyModifiers::ALT.union(KeyModifiers::SHIFT);
 
 /// Unified TUI workstation combining file browser, disk analyzer, and more
 #[derive(Parser)]
@@ -31,6 +46,22 @@ struct Cli {
     /// Starting directory
     #[arg(default_value = ".")]
     path: PathBuf,
+
+    /// Move entries deleted from the Disk tab to the OS trash
+    #[arg(long)]
+    trash: bool,
+
+    /// Record every input to FILE, to reproduce the session with --replay
+    #[arg(long, value_name = "FILE")]
+    record: Option<PathBuf>,
+
+    /// Play back the inputs recorded in FILE at their original timing
+    #[arg(long, value_name = "FILE", conflicts_with = "record")]
+    replay: Option<PathBuf>,
+
+    /// Draw a clickable `×` on each tab that can be closed
+    #[arg(long)]
+    tab_close_buttons: bool,
 }
 
 fn main() -> Result<()> {
@@ -43,6 +74,14 @@ fn main() -> Result<()> {
         .canonicalize()
         .unwrap_or_else(|_| dirs::home_dir().unwrap_or_else(|| PathBuf::from(".")));
 
+    // Open recordings before taking over the terminal, so errors show
+    let recorder = cli.record.as_deref().map(Recorder::create).transpose()?;
+    let replay = cli
+        .replay
+        .as_deref()
+        .map(|path| Replay::open(path, ReplayPace::AsRecorded))
+        .transpose()?;
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = stdout();
@@ -52,21 +91,27 @@ fn main() -> Result<()> {
 
     // Create app with tabs
     let mut app = App::new();
+    app.set_layout_config(LayoutConfig {
+        tab_close_buttons: cli.tab_close_buttons,
+        ..LayoutConfig::default()
+    });
+    app.set_config(AppConfig {
+        use_trash: cli.trash,
+    });
+    if let Some(recorder) = recorder {
+        app.start_recording(recorder);
+    }
 
-    // Add Files tab (sb)
+    // Add Files tab (sb); the other tabs still work without it
     match SbTab::new(1, &root) {
         Ok(files_tab) => {
             app.add_tab(Box::new(files_tab));
         }
         Err(e) => {
-            // Clean up terminal before printing error
-            disable_raw_mode()?;
-            execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-            terminal.show_cursor()?;
-            return Err(color_eyre::eyre::eyre!(
-                "Failed to initialize file browser: {}",
-                e
-            ));
+            app.notify(
+                NotifyLevel::Error,
+                format!("Failed to initialize file browser: {e}"),
+            );
         }
     }
 
@@ -83,16 +128,29 @@ fn main() -> Result<()> {
     if let Err(e) = plugin_manager.lock().load() {
         eprintln!("Failed to load plugins: {e}");
     }
+    // Reload plugins as they are rebuilt, so the tab stays current
+    {
+        let mut manager = plugin_manager.lock();
+        manager.set_event_bus(Some(app.message_bus().clone()));
+        if let Err(e) = manager.watch() {
+            app.notify(
+                NotifyLevel::Warning,
+                format!("Plugin changes not watched: {e}"),
+            );
+        }
+    }
     let plugins_tab = PluginsTab::new(4, plugin_manager.clone());
     app.add_tab(Box::new(plugins_tab));
 
     // Set initial status
     app.set_status_left("NORMAL");
     app.set_status_center(root.display().to_string());
-    app.set_status_right("Tab:switch  Ctrl+Q:quit");
+    app.set_status_right("Tab:switch  Ctrl+T:new  Ctrl+W:close  Ctrl+Q:quit");
 
-    // Main loop
-    let result = run_app(&mut terminal, &mut app);
+    // Main loop, bracketed by the app's lifecycle hooks
+    app.on_start();
+    let result = run_app(&mut terminal, &mut app, &root, replay);
+    app.on_exit();
 
     // Restore terminal
     disable_raw_mode()?;
@@ -102,38 +160,127 @@ fn main() -> Result<()> {
     result
 }
 
-fn run_app<B>(terminal: &mut Terminal<B>, app: &mut App) -> Result<()>
+/// Draw and dispatch until the app quits
+///
+/// Messages from `replay` are dispatched as they fall due, alongside live
+/// input.
+fn run_app<B>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+    root: &Path,
+    mut replay: Option<Replay>,
+) -> Result<()>
 where
     B: Backend,
     <B as Backend>::Error: std::error::Error + Send + Sync + 'static,
 {
+    let mut launcher: Option<Launcher> = None;
+    // Ctrl+W was pressed; it closes the tab at this deadline unless a
+    // direction follows first
+    let mut window_prefix: Option<Instant> = None;
     loop {
+        // Feed recorded input that has fallen due
+        if let Some(replay) = replay.as_mut() {
+            while let Some(msg) = replay.next_due() {
+                app.dispatch(msg);
+            }
+        }
+
+        if window_prefix.is_some_and(|deadline| Instant::now() >= deadline) {
+            window_prefix = None;
+            app.set_status_left("NORMAL");
+            app.dispatch(Message::CloseTab(app.active_tab()));
+        }
+
         // Render
-        terminal.draw(|frame| app.render(frame))?;
+        terminal.draw(|frame| {
+            app.render(frame);
+            if let Some(launcher) = &launcher {
+                launcher.render(frame, &app.context().theme);
+                if app.color_depth() == ColorDepth::Monochrome {
+                    to_monochrome(frame.buffer_mut());
+                }
+            }
+        })?;
 
         // Handle events
-        if event::poll(std::time::Duration::from_millis(100))? {
+        let mut timeout = app.desired_tick_interval().unwrap_or(IDLE_POLL_TIMEOUT);
+        if let Some(wait) = replay.as_ref().and_then(Replay::until_next) {
+            timeout = timeout.min(wait);
+        }
+        if let Some(deadline) = window_prefix {
+            timeout = timeout.min(deadline.saturating_duration_since(Instant::now()));
+        }
+        if event::poll(timeout)? {
             match event::read()? {
-                Event::Key(key) => {
-                    // Global shortcuts first
-                    match (key.modifiers, key.code) {
-                        (KeyModifiers::CONTROL, KeyCode::Char('c' | 'q')) => {
-                            app.dispatch(Message::Quit);
-                        }
-                        (KeyModifiers::NONE, KeyCode::Tab) => {
-                            app.dispatch(Message::NextTab);
-                        }
-                        (KeyModifiers::SHIFT, KeyCode::BackTab) => {
-                            app.dispatch(Message::PrevTab);
-                        }
-                        (KeyModifiers::ALT, KeyCode::Char(c)) if c.is_ascii_digit() => {
-                            // Alt+1-9 to switch tabs
-                            let idx = c.to_digit(10).unwrap_or(1);
-                            app.dispatch(Message::SwitchTab(idx));
+                Event::Key(key) if launcher.is_some() && !is_quit_key(key) => {
+                    // The new-tab launcher is modal, except for quitting
+                    let action = launcher
+                        .as_mut()
+                        .map_or(LauncherAction::Cancel, |l| l.handle_key(key));
+                    match action {
+                        LauncherAction::Pending => {}
+                        LauncherAction::Cancel => launcher = None,
+                        LauncherAction::Open(kind, dir) => {
+                            launcher = None;
+                            open_tab(app, kind, &dir);
                         }
-                        _ => {
-                            // Forward to active tab
-                            app.dispatch(Message::Key(key));
+                    }
+                }
+                Event::Key(key) => {
+                    // A pending Ctrl+W takes a direction or pane number; any
+                    // other key cancels it and is handled as usual
+                    let chord = if window_prefix.take().is_some() {
+                        app.set_status_left("NORMAL");
+                        window_command(key.code)
+                    } else {
+                        None
+                    };
+                    if let Some(msg) = chord {
+                        app.dispatch(msg);
+                    } else {
+                        // Global shortcuts first
+                        match (key.modifiers, key.code) {
+                            (KeyModifiers::CONTROL, KeyCode::Char('c' | 'q')) => {
+                                app.dispatch(Message::Quit);
+                            }
+                            (KeyModifiers::CONTROL, KeyCode::Char('w')) => {
+                                // Ctrl+W closes the tab, unless a direction or
+                                // pane number follows to move between panes
+                                window_prefix = Some(Instant::now() + WINDOW_CHORD_TIMEOUT);
+                                app.set_status_left("CTRL-W");
+                            }
+                            (KeyModifiers::CONTROL, KeyCode::Char('t')) => {
+                                launcher = Some(Launcher::new(root.to_path_buf()));
+                            }
+                            (KeyModifiers::NONE, KeyCode::Tab) => {
+                                app.dispatch(Message::NextTab);
+                            }
+                            (KeyModifiers::SHIFT, KeyCode::BackTab) => {
+                                app.dispatch(Message::PrevTab);
+                            }
+                            (KeyModifiers::ALT, KeyCode::Char('t')) => {
+                                // Alt+T to cycle dark/light
+                                app.dispatch(Message::SetTheme(app.theme().toggled()));
+                            }
+                            (KeyModifiers::ALT, KeyCode::Char('z')) => {
+                                // Alt+Z to maximize the focused pane and back
+                                app.dispatch(Message::ToggleZoom);
+                            }
+                            (ALT_SHIFT, KeyCode::Char(c @ '1'..='9')) => {
+                                // Alt+Shift+1-9 to focus a pane of the active tab,
+                                // where the terminal reports the digit with Shift
+                                app.dispatch(Message::SwitchPane(c as u32 - '0' as u32));
+                            }
+                            (KeyModifiers::ALT, KeyCode::Char(c)) if c.is_ascii_digit() => {
+                                // Alt+1-9 to switch tabs
+                                let idx = c.to_digit(10).unwrap_or(1);
+                                app.dispatch(Message::SwitchTab(idx));
+                            }
+                            _ => {
+                                // Forward to active tab
+                                app.dispatch(Message::Key(key));
+                            }
                         }
                     }
                 }
@@ -141,6 +288,9 @@ where
                     app.dispatch(Message::Mouse(mouse));
                 }
                 Event::Resize(w, h) => {
+                    // Redraw at the new size right away; tabs hear about it
+                    // once resizing settles
+                    terminal.autoresize()?;
                     app.dispatch(Message::Resize(w, h));
                 }
                 _ => {}
@@ -158,3 +308,55 @@ where
 
     Ok(())
 }
+
+/// Add a tab chosen in the launcher and switch to it
+fn open_tab(app: &mut App, kind: TabKind, dir: &Path) {
+    let id = app.tabs().iter().map(|tab| tab.id()).max().unwrap_or(0) + 1;
+    let tab: Box<dyn Tab> = match kind {
+        TabKind::Files => match SbTab::new(id, dir) {
+            Ok(tab) => Box::new(tab),
+            Err(e) => {
+                app.notify(
+                    NotifyLevel::Error,
+                    format!("Failed to open {}: {e}", dir.display()),
+                );
+                return;
+            }
+        },
+        TabKind::Disk => Box::new(DiskTab::new(id, dir)),
+        TabKind::Git => {
+            let mut tab = GitTab::new(id, dir);
+            tab.set_closable(true);
+            Box::new(tab)
+        }
+    };
+    let id = app.add_or_focus(tab);
+    app.dispatch(Message::SwitchTab(id));
+}
+
+/// Ctrl+Q or Ctrl+C, which quit from anywhere
+fn is_quit_key(key: KeyEvent) -> bool {
+    key.modifiers == KeyModifiers::CONTROL && matches!(key.code, KeyCode::Char('c' | 'q'))
+}
+
+/// Direction for `h`/`j`/`k`/`l` or an arrow
+fn pane_direction(code: KeyCode) -> Option<FocusDirection> {
+    match code {
+        KeyCode::Char('h') | KeyCode::Left => Some(FocusDirection::Left),
+        KeyCode::Char('j') | KeyCode::Down => Some(FocusDirection::Down),
+        KeyCode::Char('k') | KeyCode::Up => Some(FocusDirection::Up),
+        KeyCode::Char('l') | KeyCode::Right => Some(FocusDirection::Right),
+        _ => None,
+    }
+}
+
+/// What the key after Ctrl+W does: `h`/`j`/`k`/`l` or an arrow focuses
+/// the adjacent pane, `1`-`9` pane N
+///
+/// Modifiers are ignored, so Ctrl can stay held for the second key.
+fn window_command(code: KeyCode) -> Option<Message> {
+    match code {
+        KeyCode::Char(c @ '1'..='9') => Some(Message::SwitchPane(c as u32 - '0' as u32)),
+        _ => pane_direction(code).map(Message::FocusAdjacentPane),
+    }
+}
@@ -3,13 +3,15 @@ use parking_lot::Mutex;
 use ratatui::prelude::*;
 use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
 use saorsa_cli_core::{
-    CoreResult, Message, PluginContext, PluginDescriptor, PluginHistory, PluginManager,
-    PluginRunStats, Tab, TabId,
+    AppContext, CoreResult, Message, Notification, PluginContext, PluginDescriptor, PluginHistory,
+    PluginManager, PluginRunStats, Tab, TabBadge, TabId, ThemeColors,
 };
+use std::collections::VecDeque;
 use std::fmt::Write;
 use std::sync::mpsc::{self, Receiver, Sender};
 use std::sync::Arc;
 use std::thread;
+use std::time::Duration;
 
 pub struct PluginsTab {
     id: TabId,
@@ -22,8 +24,23 @@ pub struct PluginsTab {
     running: Option<String>,
     history: Arc<Mutex<PluginHistory>>,
     info_panel: Option<InfoPanel>,
+    focused: bool,
+    badge: Option<TabBadge>,
+    /// Failures waiting to be shown by the host
+    notifications: VecDeque<Notification>,
 }
 
+/// Key bindings listed in the coordinator's help overlay
+const KEY_BINDINGS: &[(&str, &str)] = &[
+    ("↑/↓", "Select plugin"),
+    ("Enter", "Run plugin"),
+    ("r", "Reload plugins"),
+    ("h/?", "Plugin help panel"),
+    ("i", "Plugin details"),
+    ("d", "Plugin directories"),
+    ("c/Esc", "Close info panel"),
+];
+
 enum PluginJobMessage {
     Finished {
         name: String,
@@ -70,6 +87,9 @@ impl PluginsTab {
             running: None,
             history: Arc::new(Mutex::new(PluginHistory::load())),
             info_panel: None,
+            focused: false,
+            badge: None,
+            notifications: VecDeque::new(),
         }
     }
 
@@ -95,18 +115,24 @@ impl PluginsTab {
         self.running = Some(name.clone());
         self.status = Some(format!("Running {name}..."));
 
-        let plugin = match self.manager.lock().plugin_instance(&name) {
+        let instance = match self.manager.lock().plugin_instance(&name) {
             Ok(plugin) => plugin,
             Err(err) => {
                 self.running = None;
                 self.status = Some(format!("Failed to prepare {name}: {err}"));
+                self.notifications.push_back(Notification::error(format!(
+                    "Failed to prepare {name}: {err}"
+                )));
                 return;
             }
         };
 
+        // Manifest defaults and environment, as `execute_plugin` applies them
+        let args = plugin.metadata.resolve_args(&[]);
+        let ctx = plugin.metadata.resolve_context(PluginContext::default());
         let sender = self.sender.clone();
         thread::spawn(move || {
-            let result = plugin.execute(&[], PluginContext::default());
+            let result = instance.execute(&args, ctx);
             let _ = sender.send(PluginJobMessage::Finished { name, result });
         });
     }
@@ -131,6 +157,32 @@ impl PluginsTab {
         }
     }
 
+    /// Pick up plugins rebuilt or edited on disk while the manager watches
+    fn poll_plugin_changes(&mut self) {
+        // Reloading unloads changed libraries, so wait for the run to end
+        if self.running.is_some() {
+            return;
+        }
+        let selected = self.selected_plugin().map(|p| p.metadata.name);
+        let Some(report) = self.manager.lock().poll_changes() else {
+            return;
+        };
+
+        let plugins = self.descriptors();
+        let index = selected
+            .and_then(|name| plugins.iter().position(|p| p.metadata.name == name))
+            .or(if plugins.is_empty() { None } else { Some(0) });
+        self.state.select(index);
+
+        self.status = Some(match report.warnings.first() {
+            Some(warning) => format!("⚠️ {warning}"),
+            None => format!("Reloaded {} plugins", report.loaded),
+        });
+        for warning in report.warnings {
+            self.notifications.push_back(Notification::warning(warning));
+        }
+    }
+
     fn selected_plugin(&self) -> Option<PluginDescriptor> {
         self.state
             .selected()
@@ -212,11 +264,17 @@ impl PluginsTab {
         self.info_panel = None;
     }
 
-    fn draw_info_panel(&self, frame: &mut Frame, area: Rect, panel: &InfoPanel) {
+    fn draw_info_panel(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        colors: &ThemeColors,
+        panel: &InfoPanel,
+    ) {
         let block = Block::default()
             .title(panel.title())
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Yellow));
+            .border_style(Style::default().fg(colors.info));
         let paragraph = Paragraph::new(panel.content())
             .wrap(Wrap { trim: true })
             .block(block);
@@ -241,11 +299,16 @@ impl Tab for PluginsTab {
         false
     }
 
-    fn focus(&mut self) {}
+    fn focus(&mut self, _ctx: &AppContext) {
+        self.focused = true;
+    }
 
-    fn blur(&mut self) {}
+    fn blur(&mut self) {
+        self.focused = false;
+    }
 
-    fn view(&self, frame: &mut Frame, area: Rect) {
+    fn view(&self, frame: &mut Frame, area: Rect, ctx: &AppContext) -> CoreResult<()> {
+        let colors = &ctx.theme.colors;
         let has_panel = self.info_panel.is_some();
         let mut constraints = vec![Constraint::Min(3)];
         if has_panel {
@@ -286,13 +349,13 @@ impl Tab for PluginsTab {
             Block::default()
                 .title(" Plugins ")
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan)),
+                .border_style(Style::default().fg(colors.border_focused)),
         );
         frame.render_stateful_widget(list, chunks[0], &mut state);
 
         if let (true, Some(panel)) = (has_panel, self.info_panel.as_ref()) {
             let panel_area = chunks[1];
-            self.draw_info_panel(frame, panel_area, panel);
+            self.draw_info_panel(frame, panel_area, colors, panel);
         }
 
         let mut status_lines = vec![
@@ -315,6 +378,7 @@ impl Tab for PluginsTab {
         let status_index = if has_panel { 2 } else { 1 };
         let status_area = chunks.get(status_index).copied().unwrap_or(area);
         frame.render_widget(status, status_area);
+        Ok(())
     }
 
     fn handle_message(&mut self, message: &Message) -> Option<Message> {
@@ -369,11 +433,31 @@ impl Tab for PluginsTab {
         None
     }
 
+    fn tick_interval(&self) -> Option<Duration> {
+        // Pick up the finished job promptly while a plugin runs, and
+        // changes on disk while the manager watches
+        if self.running.is_some() {
+            Some(Duration::from_millis(100))
+        } else if self.manager.lock().is_watching() {
+            Some(Duration::from_millis(500))
+        } else {
+            None
+        }
+    }
+
     fn tick(&mut self) {
+        self.poll_plugin_changes();
         while let Ok(msg) = self.receiver.try_recv() {
             match msg {
                 PluginJobMessage::Finished { name, result } => {
                     self.running = None;
+                    if !self.focused {
+                        self.badge = Some(if result.is_ok() {
+                            TabBadge::Success
+                        } else {
+                            TabBadge::Failure
+                        });
+                    }
                     match result {
                         Ok(_) => {
                             self.status = Some(format!("✅ {name} completed"));
@@ -390,12 +474,35 @@ impl Tab for PluginsTab {
                                 eprintln!("Failed to record plugin failure: {}", io_err);
                             }
                             self.status = Some(format!("❌ {name} failed: {e}"));
+                            self.notifications.push_back(Notification::error(format!(
+                                "Plugin {name} failed: {e}"
+                            )));
                         }
                     }
                 }
             }
         }
     }
+
+    fn take_notification(&mut self) -> Option<Notification> {
+        self.notifications.pop_front()
+    }
+
+    fn help_hint(&self) -> &str {
+        "enter:run  r:reload  i:info  ?:help"
+    }
+
+    fn key_bindings(&self) -> Vec<(&str, &str)> {
+        KEY_BINDINGS.to_vec()
+    }
+
+    fn badge(&self) -> Option<TabBadge> {
+        self.badge
+    }
+
+    fn clear_badge(&mut self) {
+        self.badge = None;
+    }
 }
 
 fn format_stats(stats: Option<&PluginRunStats>) -> String {
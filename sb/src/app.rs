@@ -1,8 +1,21 @@
-use super::git::{FileStatus, GitRepository};
-use crate::editor::MainEditor;
+use super::git::{summarize_status, FileStatus, GitRepository};
+use crate::config::{LayoutPrefs, Settings};
+use crate::copy::{spawn_copy, spawn_move, CopyEvent, CopyJob, CopyProgress};
+use crate::editor::{extract_selection, MainEditor};
+use crate::finder::{self, FinderMatch};
+use crate::fs::{
+    empty_trash, is_hidden, is_symlink, is_trash, move_to_trash, plan_bulk_rename, read_text_file,
+    restore_from_trash, should_descend, swap_path, write_atomic, FileContents, TreeSort,
+};
+use crate::icons::{FileKind, IconSet};
+use crate::preview;
+use crate::raster;
+use crate::search::{self, SearchEvent, SearchHit, SearchOptions};
 use anyhow::{anyhow, Context, Result};
 use ratatui::prelude::*;
 use ratatui::text::Text as RichText;
+use ratatui_image::picker::Picker;
+use saorsa_cli_core::IgnoreRules;
 use std::io;
 use std::{
     collections::{HashMap, HashSet},
@@ -11,18 +24,49 @@ use std::{
 };
 use std::{
     io::Read,
-    process::{Child, Command, Stdio},
+    process::{Child, Command, ExitStatus, Stdio},
     sync::{
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicU32, Ordering},
         mpsc::{self, Receiver, TryRecvError},
         Arc, Mutex,
     },
     thread,
-    time::Duration,
+    time::{Duration, Instant},
 };
 use tui_textarea::TextArea;
 use tui_tree_widget::{TreeItem, TreeState};
 
+/// Default decode rate for video previews; terminals rarely benefit from more.
+pub const DEFAULT_VIDEO_FPS: u32 = 10;
+
+/// Files larger than this are shown as a placeholder instead of being loaded.
+pub const DEFAULT_MAX_OPEN_SIZE: u64 = 5 * 1024 * 1024;
+
+/// Most recent file operations kept for undo.
+const FILE_OP_HISTORY: usize = 20;
+
+/// Selection checkmarks in the tree are redrawn at most this often.
+const TREE_SELECTION_REDRAW_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Background loaders are polled this often while they run.
+const BACKGROUND_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How long a git status snapshot is reused before the picker re-reads it.
+const GIT_STATUS_TTL: Duration = Duration::from_secs(2);
+
+/// Longest hex selection whose bytes the status bar spells out
+const HEX_STATUS_MAX_BYTES: u64 = 8;
+
+/// Quiet period used to coalesce the burst of filesystem events a single save produces.
+const FILE_WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Directory levels the tree reads up front; deeper ones are read when expanded.
+const TREE_INITIAL_LEVELS: usize = 1;
+
+/// Ends the identifier of the child standing in for an unread directory's
+/// entries; paths cannot contain a NUL, so it never matches a real entry.
+const UNLOADED_SUFFIX: char = '\0';
+
 // Vim mode removed — keep simple preview editing
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -40,6 +84,88 @@ pub enum OpMode {
     Mkdir,
 }
 
+/// Pending decision after the opened file changed on disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileChangePrompt {
+    /// Buffer is clean; offer to reload the new contents
+    Reload,
+    /// Buffer has unsaved edits that conflict with the on-disk change
+    Conflict,
+}
+
+/// A copy or move held back because it would replace existing files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverwritePrompt {
+    /// Where the entry would be written
+    pub dest: PathBuf,
+    /// Existing files the operation would replace
+    pub conflicts: usize,
+}
+
+/// A left click on a tree entry that may still become a double click or a
+/// drag.
+#[derive(Debug, Clone)]
+struct TreeClick {
+    /// Entry under the pointer
+    id: Vec<String>,
+    column: u16,
+    row: u16,
+    at: Instant,
+    /// The button has not been released yet
+    held: bool,
+    /// Moved far enough to start moving the entry
+    dragged: bool,
+}
+
+/// Editors that open their own window. Anything else is assumed to run in
+/// the terminal and is given the screen until it exits.
+const GUI_EDITORS: &[&str] = &[
+    "atom",
+    "code",
+    "codium",
+    "cursor",
+    "gedit",
+    "gvim",
+    "kate",
+    "mate",
+    "mousepad",
+    "mvim",
+    "notepad",
+    "notepad++",
+    "subl",
+    "xed",
+    "zed",
+];
+
+/// Whether `program` (from `$EDITOR`) opens its own window
+pub fn is_gui_editor(program: &str) -> bool {
+    Path::new(program)
+        .file_stem()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| GUI_EDITORS.contains(&name))
+}
+
+/// A terminal editor waiting for the host to hand it the terminal
+///
+/// Hosts that set [`App::suspend_for_editor`] take this with
+/// [`App::take_editor_launch`], leave the alternate screen and raw mode, run
+/// [`EditorLaunch::command`] to completion and report back through
+/// [`App::finish_editor_launch`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditorLaunch {
+    pub program: String,
+    pub args: Vec<String>,
+    pub paths: Vec<PathBuf>,
+}
+
+impl EditorLaunch {
+    pub fn command(&self) -> Command {
+        let mut command = Command::new(&self.program);
+        command.args(&self.args).args(&self.paths);
+        command
+    }
+}
+
 #[derive(Debug)]
 #[allow(dead_code)]
 pub struct FileNode {
@@ -56,6 +182,63 @@ struct DeleteCandidate {
     is_dir: bool,
 }
 
+/// A delete, move or bulk rename that `u` can reverse
+#[derive(Debug, Clone)]
+enum FileOp {
+    /// Entries moved into the workspace trash
+    Delete(Vec<TrashedEntry>),
+    /// A rename; `git` when it went through `git mv`
+    Move {
+        from: PathBuf,
+        to: PathBuf,
+        git: bool,
+        // What was at `to` before, moved into the trash to make room
+        replaced: Option<TrashedEntry>,
+    },
+    /// Operations done together and undone together, last first
+    Batch(Vec<FileOp>),
+}
+
+impl FileOp {
+    /// Whether undoing this needs entries from the workspace trash
+    fn uses_trash(&self) -> bool {
+        match self {
+            FileOp::Delete(_) => true,
+            FileOp::Move { replaced, .. } => replaced.is_some(),
+            FileOp::Batch(ops) => ops.iter().any(FileOp::uses_trash),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TrashedEntry {
+    original: PathBuf,
+    trashed: PathBuf,
+    // Also removed from the git index, so undo stages it again
+    staged: bool,
+}
+
+/// Bytes the hex dump last read and the status text last made from them
+#[derive(Debug, Clone, Default)]
+struct HexCache {
+    /// Offset and requested length of the rows on screen, and their bytes
+    window: Option<(u64, u64, Vec<u8>)>,
+    /// Selection the status text was made for
+    status: Option<((u64, u64), Option<String>)>,
+}
+
+/// How the drawn preview lines wrap, kept from frame to frame so cursor
+/// motion and the cursor highlight count rows over the same text
+#[derive(Debug, Clone, Default)]
+struct PreviewRows {
+    width: usize,
+    lines: Vec<String>,
+    /// Where each line's screen rows start, from [`preview::wrap_starts`]
+    starts: Vec<Vec<usize>>,
+    /// Screen rows above each line, and the total as the last entry
+    above: Vec<usize>,
+}
+
 pub struct App {
     pub root: PathBuf,
     pub focus: Focus,
@@ -68,14 +251,45 @@ pub struct App {
     // Multi-selection for main file tree
     pub tree_selection: HashSet<String>, // Using file paths as keys
     pub tree_selection_anchor: Option<String>,
+    // Selection changed since the tree text was last rebuilt
+    tree_selection_dirty: bool,
+    tree_selection_drawn: Option<Instant>,
+    // Last left click in the tree, for double clicks and drags
+    tree_click: Option<TreeClick>,
+    // Everything under the root, waiting for the user to confirm selecting it
+    pending_select_all: Option<HashSet<String>>,
     pub editor: MainEditor,
     pub opened: Option<PathBuf>,
     pub last_saved_text: Option<String>,
+    // Unsaved edits are copied to a swap file this often; `None` is off
+    pub autosave_interval: Option<Duration>,
+    autosave_checked: Instant,
+    // What the opened file's swap file holds, when sb wrote or recovered it
+    swap_text: Option<String>,
+    // Swap file being written off the UI thread, with the text going into it
+    autosave_writer: Option<Receiver<(PathBuf, String, io::Result<()>)>>,
+    // Swap file newer than the opened file, waiting for recover (y) or discard (n)
+    pub swap_prompt: Option<PathBuf>,
     pub status: String,
+    // Latest failure worth more than the status line, taken by hosts that
+    // show notifications
+    pub last_error: Option<String>,
     pub show_help: bool,
     pub show_left_pane: bool,
+    // The terminal reports keys with the kitty protocol, so Ctrl+I is not Tab
+    pub keyboard_enhanced: bool,
+    // Draws images and video frames; hosts replace the halfblocks default
+    // with preview::detect_picker once the terminal is in raw mode
+    pub image_picker: Picker,
+    // The host can suspend the TUI, so terminal editors are queued for it
+    // instead of being spawned over the screen
+    pub suspend_for_editor: bool,
+    pending_editor: Option<EditorLaunch>,
     pub creating_file: bool,
     pub filename_input: TextArea<'static>,
+    // Go-to-path prompt shown in place of the tree breadcrumb
+    pub going_to_path: bool,
+    pub goto_input: TextArea<'static>,
     pub confirming_delete: bool,
     pub delete_target: Option<PathBuf>,
     // File picker overlay
@@ -86,6 +300,10 @@ pub struct App {
     // Multi-selection support
     pub picker_selection: std::collections::HashSet<usize>,
     pub picker_selection_anchor: Option<usize>,
+    // Bulk rename prompt over the picker, applied to `rename_sources`
+    pub bulk_renaming: bool,
+    pub rename_input: TextArea<'static>,
+    rename_sources: Vec<PathBuf>,
     pub op_mode: OpMode,
     pub op_input: TextArea<'static>,
     pub op_source: Option<PathBuf>,
@@ -97,6 +315,9 @@ pub struct App {
     pub show_raw_editor: bool,
     // Remember user preference for raw editor when switching focus
     pub prefer_raw_editor: bool,
+    // Raw editor and rendered preview side by side
+    pub split_view: bool,
+    split_scroll: usize,
     // Editor command mode (minimal)
     pub editor_cmd_mode: bool,
     pub editor_cmd_input: TextArea<'static>,
@@ -104,25 +325,109 @@ pub struct App {
     pub preview_col: usize,
     pub preview_scroll: usize,
     pub preview_viewport: usize,
+    // Columns the preview text is wrapped to
+    pub preview_width: usize,
+    // Screen rows of the drawn preview lines at that width
+    preview_rows: PreviewRows,
+    // First `g` of a `gg` jump to the top of the preview
+    pub preview_pending_g: bool,
+    // Line wrapping toggled this session, overriding the per-extension default;
+    // unwrapped previews scroll sideways by preview_hscroll
+    pub wrap_overrides: HashMap<PathBuf, bool>,
+    pub preview_hscroll: usize,
+    // Visual selection in the preview: anchor (line, col), the cursor is the other end
+    pub preview_selection_anchor: Option<(usize, usize)>,
+    // Internal copy register, used when the system clipboard is unavailable
+    pub clipboard_register: Option<String>,
     #[allow(dead_code)]
     pub undo_stack: Vec<Vec<String>>,
+    // Deletes and moves that can be undone, most recent last
+    file_op_history: Vec<FileOp>,
     #[allow(dead_code)]
     pub redo_stack: Vec<Vec<String>>,
     pub autoplay_video: bool,
     // Video playback
     pub video_player: Option<VideoPlayer>,
     pub video_path: Option<PathBuf>,
+    // Target decode rate handed to ffmpeg (`-r`)
+    pub video_fps: u32,
+    // Largest file loaded into the editor; bigger ones get a placeholder
+    pub max_open_size: u64,
+    // Draw without colors, for `NO_COLOR`
+    pub no_color: bool,
+    // The opened file is binary or too large and only a placeholder is shown
+    opened_placeholder: bool,
+    // How the opened file is previewed, picked once when it is loaded
+    pub renderer: preview::Renderer,
+    // Length of the opened file when the preview shows it as a hex dump
+    hex_len: Option<u64>,
+    // What the hex dump last read, so frames only touch the file once the
+    // view moves
+    hex_cache: HexCache,
+    // Set once ffmpeg is found missing so we report it a single time
+    ffmpeg_missing: bool,
     // Git integration
     pub git_repo: Option<GitRepository>,
     pub git_status: HashMap<PathBuf, FileStatus>,
+    // When `git_status` was last read; `None` marks it stale
+    git_status_refreshed: Option<Instant>,
+    // Branch and upstream counts for the status bar, e.g. `⎇ main ↑2↓0`
+    pub git_branch: Option<String>,
     tree_loader: Option<Receiver<Result<Vec<TreeItem<'static, String>>>>>,
-    git_status_loader: Option<Receiver<Result<HashMap<PathBuf, FileStatus>>>>,
+    // Hide entries matched by .gitignore files (toggled with `I` in the tree)
+    pub respect_gitignore: bool,
+    // Extra gitignore-style patterns hidden from the tree
+    pub ignore_patterns: Vec<String>,
+    // Descend into symlinked directories (cycles are still cut off)
+    pub follow_symlinks: bool,
+    // Directory levels below the root the tree shows; `None` for no limit
+    pub tree_max_depth: Option<usize>,
+    // Glyphs drawn before tree entries
+    pub tree_icons: IconSet,
+    // Order of the entries within each directory of the tree (cycled with `o`)
+    pub tree_sort: TreeSort,
+    // Show dotfiles in the tree and pickers (toggled with `.`)
+    pub show_hidden: bool,
+    git_status_loader: Option<Receiver<Result<(HashMap<PathBuf, FileStatus>, Option<String>)>>>,
+    // External change detection for the opened file
+    file_watcher: Option<notify::RecommendedWatcher>,
+    file_change_loader: Option<Receiver<()>>,
+    watched_path: Option<PathBuf>,
+    pub file_change_prompt: Option<FileChangePrompt>,
+    // Copy/move waiting for overwrite (y), keep both (r) or cancel (n)
+    pub overwrite_prompt: Option<OverwritePrompt>,
     // Move destination picker
     pub showing_move_dest: bool,
     pub move_dest_dir: PathBuf,
     pub move_dest_items: Vec<PathBuf>,
     pub move_dest_index: usize,
     pub move_source: Option<PathBuf>,
+    // Heading outline (table of contents) overlay
+    pub showing_toc: bool,
+    pub toc_filter: TextArea<'static>,
+    pub toc_index: usize,
+    // Workspace content search overlay
+    pub showing_search: bool,
+    pub search_input: TextArea<'static>,
+    pub search_results: Vec<SearchHit>,
+    pub search_index: usize,
+    // Files larger than this are not searched
+    pub search_max_file_size: u64,
+    search_query: Option<String>,
+    search_loader: Option<Receiver<SearchEvent>>,
+    // Fuzzy file finder overlay
+    pub showing_finder: bool,
+    pub finder_input: TextArea<'static>,
+    pub finder_results: Vec<FinderMatch>,
+    pub finder_index: usize,
+    finder_files: Arc<[String]>,
+    finder_loader: Option<finder::FileIndex>,
+    finder_ranker: Option<finder::Ranker>,
+    copy_job: Option<CopyJob>,
+    // Source of the move running as copy_job, and what it replaced
+    pending_move: Option<(PathBuf, Option<TrashedEntry>)>,
+    // Blame gutter in the code preview
+    pub show_blame: bool,
     // Git status display
     pub showing_git_status: bool,
     pub git_status_text: String,
@@ -131,6 +436,8 @@ pub struct App {
     pub min_pane_width: u16,   // Minimum pane width (percentage)
     pub max_pane_width: u16,   // Maximum pane width (percentage)
     pub pane_resize_step: u16, // Step size for keyboard resize (percentage)
+    // Remembers the pane layout per workspace root
+    settings: Settings,
 }
 
 impl App {
@@ -150,10 +457,32 @@ impl App {
         self.editor.line_at(idx)
     }
 
+    /// Open `root` with the settings saved in the user's config directory
     pub fn new(root: PathBuf) -> Result<Self> {
+        Self::with_settings(root, Settings::load())
+    }
+
+    /// Open `root` with `settings`, which are saved back wherever they were
+    /// loaded from; `Settings::default()` keeps them in memory only
+    pub fn with_settings(root: PathBuf, settings: Settings) -> Result<Self> {
+        let tree_max_depth = settings.tree_max_depth();
+        let tree_icons = settings.tree_icons();
+        let tree_sort = settings.tree_sort();
+        let show_hidden = settings.show_hidden();
+        let autosave_interval = settings.autosave_interval();
         let left_tree = placeholder_tree(&root);
         let right_tree = left_tree.clone();
-        let tree_loader = Some(spawn_tree_loader(root.clone()));
+        let tree_loader = Some(spawn_tree_loader(
+            root.clone(),
+            IgnoreRules::new(&root, true, &[]),
+            false,
+            TreeDisplay {
+                icons: tree_icons,
+                sort: tree_sort,
+                show_hidden,
+            },
+            TreeLevels::new(TREE_INITIAL_LEVELS, tree_max_depth),
+        ));
         let mut left_state = TreeState::<String>::default();
         let mut right_state = TreeState::<String>::default();
         left_state.select(vec![root.display().to_string()]);
@@ -169,7 +498,7 @@ impl App {
             None
         };
 
-        Ok(Self {
+        let mut app = Self {
             root: root.clone(),
             focus: Focus::Left,
             left_dir: root.clone(),
@@ -179,14 +508,30 @@ impl App {
             left_state,
             tree_selection: HashSet::new(),
             tree_selection_anchor: None,
+            tree_selection_dirty: false,
+            tree_selection_drawn: None,
+            tree_click: None,
+            pending_select_all: None,
             editor,
             opened: None,
             last_saved_text: None,
+            autosave_interval,
+            autosave_checked: Instant::now(),
+            swap_text: None,
+            autosave_writer: None,
+            swap_prompt: None,
             status: "Loading workspace...".into(),
+            last_error: None,
             show_help: false,
             show_left_pane: true,
+            keyboard_enhanced: false,
+            image_picker: Picker::halfblocks(),
+            suspend_for_editor: false,
+            pending_editor: None,
             creating_file: false,
             filename_input,
+            going_to_path: false,
+            goto_input: TextArea::default(),
             confirming_delete: false,
             delete_target: None,
             picking_file: false,
@@ -195,6 +540,9 @@ impl App {
             picker_index: 0,
             picker_selection: HashSet::new(),
             picker_selection_anchor: None,
+            bulk_renaming: false,
+            rename_input: TextArea::default(),
+            rename_sources: Vec::new(),
             op_mode: OpMode::None,
             op_input: TextArea::default(),
             op_source: None,
@@ -203,23 +551,63 @@ impl App {
             line_input: TextArea::default(),
             show_raw_editor: false,
             prefer_raw_editor: false,
+            split_view: false,
+            split_scroll: 0,
             editor_cmd_mode: false,
             editor_cmd_input: TextArea::default(),
             preview_col: 0,
             preview_scroll: 0,
             preview_viewport: 0,
+            preview_width: 0,
+            preview_rows: PreviewRows::default(),
+            preview_pending_g: false,
+            wrap_overrides: HashMap::new(),
+            preview_hscroll: 0,
+            preview_selection_anchor: None,
+            clipboard_register: None,
             undo_stack: Vec::new(),
+            file_op_history: Vec::new(),
             redo_stack: Vec::new(),
             autoplay_video: false,
             video_player: None,
             video_path: None,
+            video_fps: DEFAULT_VIDEO_FPS,
+            max_open_size: DEFAULT_MAX_OPEN_SIZE,
+            no_color: false,
+            renderer: preview::MARKDOWN,
+            hex_len: None,
+            hex_cache: HexCache::default(),
+            opened_placeholder: false,
+            ffmpeg_missing: false,
             git_repo,
             git_status,
+            git_status_refreshed: None,
+            git_branch: None,
             showing_move_dest: false,
             move_dest_dir: root.clone(),
             move_dest_items: vec![],
             move_dest_index: 0,
             move_source: None,
+            showing_toc: false,
+            toc_filter: TextArea::default(),
+            toc_index: 0,
+            showing_search: false,
+            search_input: TextArea::default(),
+            search_results: Vec::new(),
+            search_index: 0,
+            search_max_file_size: search::DEFAULT_MAX_FILE_SIZE,
+            search_query: None,
+            search_loader: None,
+            showing_finder: false,
+            finder_input: TextArea::default(),
+            finder_results: Vec::new(),
+            finder_index: 0,
+            finder_files: Arc::default(),
+            finder_loader: None,
+            finder_ranker: None,
+            copy_job: None,
+            pending_move: None,
+            show_blame: false,
             showing_git_status: false,
             git_status_text: String::new(),
             // Default pane settings: 30% left pane, 70% right pane
@@ -228,17 +616,85 @@ impl App {
             max_pane_width: 85,
             pane_resize_step: 5,
             tree_loader,
+            respect_gitignore: true,
+            ignore_patterns: Vec::new(),
+            follow_symlinks: false,
+            tree_max_depth,
+            tree_icons,
+            tree_sort,
+            show_hidden,
             git_status_loader,
-        })
+            file_watcher: None,
+            file_change_loader: None,
+            watched_path: None,
+            file_change_prompt: None,
+            overwrite_prompt: None,
+            settings,
+        };
+        app.restore_pane_layout();
+        Ok(app)
+    }
+
+    /// Describe the background loader still running, if any
+    pub fn background_activity(&self) -> Option<&'static str> {
+        if self.tree_loader.is_some() {
+            Some("Loading file tree...")
+        } else if self.git_status_loader.is_some() {
+            Some("Loading git status...")
+        } else if self.search_loader.is_some() {
+            Some("Searching files...")
+        } else if self.finder_loader.is_some() {
+            Some("Indexing files...")
+        } else if self
+            .finder_ranker
+            .as_ref()
+            .is_some_and(finder::Ranker::is_busy)
+        {
+            Some("Matching files...")
+        } else if self.pending_move.is_some() {
+            Some("Moving files...")
+        } else if self.copy_job.is_some() {
+            Some("Copying files...")
+        } else if raster::busy() {
+            Some("Rendering preview...")
+        } else {
+            None
+        }
+    }
+
+    /// How soon the main loop should wake up again without input
+    ///
+    /// A playing video wants one tick per frame and running loaders want
+    /// prompt polling; `None` means nothing is moving and the loop can sleep.
+    pub fn desired_tick_interval(&self) -> Option<Duration> {
+        if let Some(vp) = &self.video_player {
+            if !vp.is_paused() && !vp.is_finished() {
+                return Some(Duration::from_secs(1) / vp.fps().max(1));
+            }
+        }
+        if self.tree_selection_dirty {
+            Some(TREE_SELECTION_REDRAW_INTERVAL)
+        } else if self.background_activity().is_some() || self.autosave_writer.is_some() {
+            Some(BACKGROUND_POLL_INTERVAL)
+        } else {
+            self.autosave_wait()
+        }
     }
 
     pub fn poll_background_tasks(&mut self) {
+        // Finished PDF pages and diagrams show up on the next frame
+        raster::poll();
         if let Some(rx) = self.tree_loader.as_ref() {
             match rx.try_recv() {
                 Ok(Ok(tree)) => {
+                    let opened = self.left_state.get_all_opened();
+                    let selected = self.left_state.selected().to_vec();
                     let mirrored = tree.clone();
                     self.left_tree = tree;
                     self.right_tree = mirrored;
+                    self.restore_tree_state(opened, &selected);
+                    // The loader doesn't know the selection, so put the checkmarks back
+                    self.tree_selection_dirty |= !self.tree_selection.is_empty();
                     self.status = "File tree synced".into();
                     self.tree_loader = None;
                 }
@@ -253,16 +709,19 @@ impl App {
                 Err(TryRecvError::Empty) => {}
             }
         }
+        self.load_opened_dirs();
 
         if let Some(rx) = self.git_status_loader.as_ref() {
             match rx.try_recv() {
-                Ok(Ok(status_map)) => {
+                Ok(Ok((status_map, branch))) => {
                     let summary = if status_map.is_empty() {
                         "Working tree clean".to_string()
                     } else {
                         format!("Tracked {} files", status_map.len())
                     };
                     self.git_status = status_map;
+                    self.git_status_refreshed = Some(Instant::now());
+                    self.git_branch = branch;
                     self.git_status_text = summary;
                     self.git_status_loader = None;
                 }
@@ -277,6 +736,213 @@ impl App {
                 Err(TryRecvError::Empty) => {}
             }
         }
+
+        if let Some(rx) = self.search_loader.as_ref() {
+            loop {
+                match rx.try_recv() {
+                    Ok(SearchEvent::Hit(hit)) => self.search_results.push(hit),
+                    Ok(SearchEvent::Done { files, truncated }) => {
+                        self.status = format!(
+                            "{} matches in {files} files{}",
+                            self.search_results.len(),
+                            if truncated { " (stopped early)" } else { "" }
+                        );
+                        self.search_loader = None;
+                        break;
+                    }
+                    Err(TryRecvError::Disconnected) => {
+                        self.status = "Search worker disconnected".into();
+                        self.search_loader = None;
+                        break;
+                    }
+                    Err(TryRecvError::Empty) => break,
+                }
+            }
+        }
+
+        if let Some(rx) = self.finder_loader.as_ref() {
+            match rx.try_recv() {
+                Ok(files) => {
+                    self.status = format!("Indexed {} files", files.len());
+                    self.finder_files = files.into();
+                    self.finder_loader = None;
+                    self.finder_changed();
+                }
+                Err(TryRecvError::Disconnected) => {
+                    self.status = "File index worker disconnected".into();
+                    self.finder_loader = None;
+                }
+                Err(TryRecvError::Empty) => {}
+            }
+        }
+
+        if let Some(ranker) = self.finder_ranker.as_mut() {
+            match ranker.try_recv() {
+                Ok(matches) => {
+                    self.finder_results = matches;
+                    self.finder_index = 0;
+                }
+                Err(TryRecvError::Disconnected) => {
+                    self.status = "File finder worker disconnected".into();
+                    self.finder_ranker = None;
+                }
+                Err(TryRecvError::Empty) => {}
+            }
+        }
+
+        if let Some(job) = self.copy_job.as_ref() {
+            let (verb, doing) = if self.pending_move.is_some() {
+                ("Move", "Moving")
+            } else {
+                ("Copy", "Copying")
+            };
+            let mut finished = None;
+            loop {
+                match job.try_recv() {
+                    Ok(CopyEvent::Progress(progress)) => {
+                        self.status = format!("{doing} {} (Esc to cancel)", progress.summary());
+                    }
+                    Ok(CopyEvent::Done(progress)) => {
+                        finished = Some(Ok((job.dest.clone(), progress)));
+                        break;
+                    }
+                    Ok(CopyEvent::Cancelled { overwritten }) => {
+                        finished = Some(Err(match overwritten {
+                            0 => format!("{verb} cancelled; the partial copy was removed"),
+                            n => format!(
+                                "{verb} cancelled after overwriting {n} file(s); the new files were removed"
+                            ),
+                        }));
+                        break;
+                    }
+                    Ok(CopyEvent::Failed(err)) => {
+                        finished = Some(Err(format!("{verb} failed: {err}")));
+                        break;
+                    }
+                    Err(TryRecvError::Disconnected) => {
+                        finished = Some(Err(format!("{verb} worker disconnected")));
+                        break;
+                    }
+                    Err(TryRecvError::Empty) => break,
+                }
+            }
+            if let Some(result) = finished {
+                self.copy_job = None;
+                self.status = self.finish_transfer(result);
+                if let Err(e) = self.refresh_tree() {
+                    tracing::warn!(error = %e, "tree refresh after copy failed");
+                }
+            }
+        }
+
+        if self.watched_path != self.opened {
+            self.watch_opened_file();
+        }
+
+        if let Some(rx) = self.file_change_loader.as_ref() {
+            match rx.try_recv() {
+                Ok(()) => self.handle_opened_file_changed(),
+                Err(TryRecvError::Disconnected) => {
+                    self.file_watcher = None;
+                    self.file_change_loader = None;
+                }
+                Err(TryRecvError::Empty) => {}
+            }
+        }
+
+        if let Some(rx) = self.autosave_writer.as_ref() {
+            match rx.try_recv() {
+                Ok((swap, text, result)) => {
+                    self.autosave_writer = None;
+                    self.finish_autosave(&swap, text, result);
+                }
+                Err(TryRecvError::Disconnected) => self.autosave_writer = None,
+                Err(TryRecvError::Empty) => {}
+            }
+        }
+        self.autosave_if_due();
+
+        let redraw_due = self
+            .tree_selection_drawn
+            .is_none_or(|at| at.elapsed() >= TREE_SELECTION_REDRAW_INTERVAL);
+        if self.tree_selection_dirty && redraw_due {
+            self.flush_tree_selection_display();
+        }
+    }
+
+    /// (Re)arm the watcher so it follows whichever file is currently opened.
+    fn watch_opened_file(&mut self) {
+        self.file_watcher = None;
+        self.file_change_loader = None;
+        self.file_change_prompt = None;
+        self.watched_path = self.opened.clone();
+        let Some(path) = self.opened.clone() else {
+            return;
+        };
+        match spawn_file_watcher(path) {
+            Ok((watcher, rx)) => {
+                self.file_watcher = Some(watcher);
+                self.file_change_loader = Some(rx);
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "file watcher unavailable");
+            }
+        }
+    }
+
+    fn handle_opened_file_changed(&mut self) {
+        let Some(path) = self.opened.clone() else {
+            return;
+        };
+        if self.opened_placeholder {
+            return;
+        }
+        let Ok(on_disk) = fs::read_to_string(&path) else {
+            self.status = format!("{} was removed or is unreadable on disk", path.display());
+            return;
+        };
+        // Our own save (or a touch) leaves the contents we already know about
+        if self.last_saved_text.as_deref() == Some(on_disk.as_str()) {
+            return;
+        }
+        if self.has_unsaved_changes() {
+            self.file_change_prompt = Some(FileChangePrompt::Conflict);
+            self.status =
+                "Conflict: file changed on disk with unsaved edits — y reload (discard), n keep"
+                    .into();
+        } else {
+            self.file_change_prompt = Some(FileChangePrompt::Reload);
+            self.status = "File changed on disk — y reload, n ignore".into();
+        }
+    }
+
+    /// Whether the opened file's buffer differs from what was last loaded or saved.
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.opened.is_some()
+            && self.last_saved_text.as_deref() != Some(self.editor.text().as_str())
+    }
+
+    /// Replace the buffer with the on-disk contents of the opened file.
+    pub fn reload_opened_file(&mut self) -> Result<()> {
+        self.file_change_prompt = None;
+        if let Some(path) = self.opened.clone() {
+            self.load_buffer(&path)?;
+            let last = self.preview_line_count().saturating_sub(1);
+            self.preview_cursor = self.preview_cursor.min(last);
+            self.preview_selection_anchor = None;
+            self.status = "Reloaded from disk".into();
+        }
+        Ok(())
+    }
+
+    /// Keep the buffer as-is after an external change.
+    pub fn dismiss_file_change(&mut self) {
+        self.status = match self.file_change_prompt.take() {
+            Some(FileChangePrompt::Conflict) => {
+                "Kept local edits; saving will overwrite the disk version".into()
+            }
+            _ => "Ignored external change".into(),
+        };
     }
 
     pub fn open_selected(&mut self) -> Result<()> {
@@ -284,14 +950,177 @@ impl App {
             if path.is_dir() {
                 return Ok(());
             }
-            let text =
-                fs::read_to_string(&path).with_context(|| format!("Reading {}", path.display()))?;
-            self.editor.set_text(&text);
-            self.opened = Some(path);
-            self.last_saved_text = Some(text);
-            self.status = "File opened".into();
-            self.focus = Focus::Preview;
+            self.open_file(path)?;
+        }
+        Ok(())
+    }
+
+    fn open_file(&mut self, path: PathBuf) -> Result<()> {
+        self.load_buffer(&path)?;
+        self.opened = Some(path.clone());
+        self.swap_text = None;
+        self.swap_prompt = None;
+        self.status = if self.opened_placeholder {
+            self.editor.text()
+        } else {
+            "File opened".into()
+        };
+        self.focus = Focus::Preview;
+        if !self.opened_placeholder {
+            self.offer_recovery(&path);
+        }
+        Ok(())
+    }
+
+    /// Ask whether to recover edits left in a swap file newer than `path`
+    ///
+    /// Swap files that are not text are left alone.
+    fn offer_recovery(&mut self, path: &Path) {
+        let swap = swap_path(path);
+        let modified = |p: &Path| fs::metadata(p).and_then(|m| m.modified()).ok();
+        let newer = matches!(
+            (modified(&swap), modified(path)),
+            (Some(swapped), Some(saved)) if swapped > saved
+        );
+        if !newer {
+            return;
+        }
+        let Ok(FileContents::Text(text)) = read_text_file(&swap, self.max_open_size) else {
+            return;
+        };
+        if self.last_saved_text.as_deref() != Some(text.as_str()) {
+            self.swap_prompt = Some(swap);
+            self.status =
+                "Unsaved edits from an earlier session found — y recover, n discard".into();
+        }
+    }
+
+    /// Load the edits from the swap file into the buffer (y)
+    pub fn recover_swap(&mut self) -> Result<()> {
+        let Some(swap) = self.swap_prompt.take() else {
+            return Ok(());
+        };
+        let text =
+            fs::read_to_string(&swap).with_context(|| format!("Reading {}", swap.display()))?;
+        self.editor.set_text(&text);
+        self.swap_text = Some(text);
+        self.status = "Recovered unsaved edits; save to keep them".into();
+        Ok(())
+    }
+
+    /// Delete the swap file and keep the file as it is on disk (n)
+    pub fn discard_swap(&mut self) {
+        if let Some(swap) = self.swap_prompt.take() {
+            if let Err(e) = fs::remove_file(&swap) {
+                tracing::warn!(error = %e, "could not remove swap file");
+            }
+            self.status = "Discarded the unsaved edits".into();
+        }
+    }
+
+    /// Leave the swap file for later without recovering it (Esc)
+    pub fn dismiss_swap(&mut self) {
+        self.swap_prompt = None;
+        self.status = "Swap file kept; reopen the file to recover it".into();
+    }
+
+    /// How long until the next autosave, while there are edits to save
+    fn autosave_wait(&self) -> Option<Duration> {
+        let interval = self.autosave_interval?;
+        self.has_unsaved_changes()
+            .then(|| interval.saturating_sub(self.autosave_checked.elapsed()))
+    }
+
+    /// Copy unsaved edits to the swap file once the autosave interval has
+    /// passed, and drop the swap file once there are none
+    ///
+    /// The write and its fsync run on their own thread; [`Self::finish_autosave`]
+    /// takes the result.
+    fn autosave_if_due(&mut self) {
+        let Some(interval) = self.autosave_interval else {
+            return;
+        };
+        if self.autosave_writer.is_some() || self.autosave_checked.elapsed() < interval {
+            return;
+        }
+        self.autosave_checked = Instant::now();
+        let Some(path) = self.opened.clone() else {
+            return;
+        };
+        if self.opened_placeholder || self.swap_prompt.is_some() {
+            return;
+        }
+        if !self.has_unsaved_changes() {
+            self.remove_swap();
+            return;
+        }
+        let text = self.editor.text();
+        if self.swap_text.as_deref() == Some(text.as_str()) {
+            return;
+        }
+        let swap = swap_path(&path);
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let result = write_atomic(&swap, text.as_bytes());
+            let _ = tx.send((swap, text, result));
+        });
+        self.autosave_writer = Some(rx);
+    }
+
+    /// Take the result of a background swap write
+    ///
+    /// If the file was saved or closed while the write ran, the swap file is
+    /// stale and goes again.
+    fn finish_autosave(&mut self, swap: &Path, text: String, result: io::Result<()>) {
+        if let Err(e) = result {
+            tracing::warn!(error = %e, "autosave failed");
+            return;
+        }
+        let still_open = self.opened.as_deref().map(swap_path).as_deref() == Some(swap);
+        if still_open && self.has_unsaved_changes() {
+            self.swap_text = Some(text);
+            return;
         }
+        match fs::remove_file(swap) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => tracing::warn!(error = %e, "could not remove swap file"),
+        }
+    }
+
+    /// Remove the opened file's swap file if sb wrote or recovered it
+    fn remove_swap(&mut self) {
+        if self.swap_text.take().is_none() {
+            return;
+        }
+        let Some(path) = &self.opened else {
+            return;
+        };
+        match fs::remove_file(swap_path(path)) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => tracing::warn!(error = %e, "could not remove swap file"),
+        }
+    }
+
+    /// Fill the editor with `path`, or a placeholder if it is binary or too large
+    fn load_buffer(&mut self, path: &Path) -> Result<()> {
+        let contents = read_text_file(path, self.max_open_size)
+            .with_context(|| format!("Reading {}", path.display()))?;
+        self.opened_placeholder = !matches!(contents, FileContents::Text(_));
+        self.renderer = preview::RENDERERS.for_path(path);
+        self.hex_len = if self.renderer.name == preview::HEX.name {
+            Some(fs::metadata(path)?.len())
+        } else {
+            None
+        };
+        self.hex_cache = HexCache::default();
+        let text = match contents {
+            FileContents::Text(text) => text,
+            other => other.placeholder().unwrap_or_default(),
+        };
+        self.editor.set_text(&text);
+        self.last_saved_text = Some(text);
         Ok(())
     }
 
@@ -309,10 +1138,22 @@ impl App {
     }
 
     pub fn save(&mut self) -> Result<()> {
+        if self.opened_placeholder {
+            self.status = "Not saved: this file is not displayed".into();
+            return Ok(());
+        }
         if let Some(path) = &self.opened {
             let text = self.editor.text();
-            fs::write(path, text).with_context(|| format!("Saving {}", path.display()))?;
+            if let Err(e) = write_atomic(path, text.as_bytes())
+                .with_context(|| format!("Saving {}", path.display()))
+            {
+                self.status = format!("Save failed: {e:#}");
+                self.last_error = Some(self.status.clone());
+                return Err(e);
+            }
             self.last_saved_text = Some(self.editor.text());
+            self.git_status_refreshed = None;
+            self.remove_swap();
             self.status = "Saved".into();
         }
         Ok(())
@@ -330,28 +1171,84 @@ impl App {
         let editor = std::env::var("EDITOR")
             .or_else(|_| std::env::var("VISUAL"))
             .unwrap_or_else(|_| "opener".to_string());
-
-        if editor == "opener" {
+        // `$EDITOR` may carry arguments, e.g. `code --wait`
+        let mut words = editor.split_whitespace().map(str::to_string);
+        let program = words.next().unwrap_or_else(|| "opener".to_string());
+        let args: Vec<String> = words.collect();
+
+        let gui = self
+            .settings
+            .gui_editor()
+            .unwrap_or_else(|| is_gui_editor(&program));
+        if program == "opener" {
             // Fallback to system default application
             opener::open(path)?;
             self.status = format!("Opened {} with system default", path.display());
+        } else if !gui && self.suspend_for_editor {
+            // Files opened together go to one editor session
+            match &mut self.pending_editor {
+                Some(launch) if launch.program == program => launch.paths.push(path.to_path_buf()),
+                _ => {
+                    self.pending_editor = Some(EditorLaunch {
+                        program: program.clone(),
+                        args,
+                        paths: vec![path.to_path_buf()],
+                    });
+                }
+            }
+            self.status = format!("Opening {} with {}", path.display(), program);
         } else {
             // Launch the specified editor
-            match Command::new(&editor).arg(path).spawn() {
+            match Command::new(&program).args(&args).arg(path).spawn() {
                 Ok(_) => {
-                    self.status = format!("Opened {} with {}", path.display(), editor);
+                    self.status = format!("Opened {} with {}", path.display(), program);
                 }
                 Err(e) => {
                     // Fallback to opener if editor command fails
                     opener::open(path)?;
-                    self.status =
-                        format!("Failed to open with {}, used system default: {}", editor, e);
+                    self.status = format!(
+                        "Failed to open with {}, used system default: {}",
+                        program, e
+                    );
                 }
             }
         }
         Ok(())
     }
 
+    /// The terminal editor queued by [`Self::open_in_editor`], if any
+    pub fn take_editor_launch(&mut self) -> Option<EditorLaunch> {
+        self.pending_editor.take()
+    }
+
+    /// Report how a terminal editor run by the host went
+    ///
+    /// The opened file is reloaded when it was among the edited paths and
+    /// the buffer had no unsaved changes.
+    pub fn finish_editor_launch(&mut self, launch: &EditorLaunch, result: io::Result<ExitStatus>) {
+        let status = match result {
+            Ok(status) => status,
+            Err(e) => {
+                self.status = format!("Failed to run {}: {}", launch.program, e);
+                return;
+            }
+        };
+        if let Some(opened) = self.opened.clone() {
+            if !self.has_unsaved_changes() && launch.paths.contains(&opened) {
+                let _ = self.reload_opened_file();
+            }
+        }
+        self.status = if status.success() {
+            let edited = match launch.paths.as_slice() {
+                [path] => path.display().to_string(),
+                paths => format!("{} files", paths.len()),
+            };
+            format!("Edited {} with {}", edited, launch.program)
+        } else {
+            format!("{} exited with {}", launch.program, status)
+        };
+    }
+
     pub fn toggle_help(&mut self) {
         self.show_help = !self.show_help;
     }
@@ -361,6 +1258,7 @@ impl App {
         if !self.show_left_pane && matches!(self.focus, Focus::Left) {
             self.focus = Focus::Preview;
         }
+        self.save_pane_layout();
     }
 
     /// Increase left pane width
@@ -369,6 +1267,7 @@ impl App {
         if new_width != self.left_pane_width {
             self.left_pane_width = new_width;
             self.status = format!("Left pane width: {}%", self.left_pane_width);
+            self.save_pane_layout();
         }
     }
 
@@ -381,6 +1280,35 @@ impl App {
         if new_width != self.left_pane_width {
             self.left_pane_width = new_width;
             self.status = format!("Left pane width: {}%", self.left_pane_width);
+            self.save_pane_layout();
+        }
+    }
+
+    /// Apply the pane layout saved for this root, clamping a width that
+    /// falls outside the allowed range
+    fn restore_pane_layout(&mut self) {
+        if let Some(prefs) = self.settings.layout(&self.root) {
+            self.left_pane_width = prefs
+                .left_pane_width
+                .clamp(self.min_pane_width, self.max_pane_width);
+            self.show_left_pane = prefs.show_left_pane;
+            if !self.show_left_pane {
+                self.focus = Focus::Preview;
+            }
+        }
+    }
+
+    /// Remember the current pane layout for this root
+    ///
+    /// Mouse drags call this once the button is released rather than on
+    /// every step.
+    pub fn save_pane_layout(&mut self) {
+        let prefs = LayoutPrefs {
+            left_pane_width: self.left_pane_width,
+            show_left_pane: self.show_left_pane,
+        };
+        if let Err(e) = self.settings.set_layout(&self.root, prefs) {
+            tracing::warn!(error = %e, "failed to save pane layout");
         }
     }
 
@@ -402,7 +1330,8 @@ impl App {
         if terminal_width == 0 {
             return 30; // Default fallback
         }
-        (terminal_width * self.left_pane_width / 100).max(1)
+        let width = u32::from(terminal_width) * u32::from(self.left_pane_width) / 100;
+        u16::try_from(width).unwrap_or(terminal_width).max(1)
     }
 
     // --- Editor command mode ----------------------------------------------
@@ -433,14 +1362,23 @@ impl App {
                 self.status = "Saved (use F10/Q to quit)".into();
             }
             _ => {
-                self.status = format!("Unknown :{cmd}");
+                if cmd == "empty-trash" {
+                    if let Err(e) = self.empty_trash() {
+                        self.status = format!("{e:#}");
+                    }
+                } else if let Some(path) = cmd.strip_prefix("cd ") {
+                    if let Err(e) = self.go_to_path(path) {
+                        self.status = e.to_string();
+                    }
+                } else {
+                    self.status = format!("Unknown :{cmd}");
+                }
             }
         }
         self.editor_cmd_mode = false;
         Ok(())
     }
 
-    #[allow(dead_code)]
     fn push_undo(&mut self, lines: &[String]) {
         self.undo_stack.push(lines.to_vec());
         self.redo_stack.clear();
@@ -449,8 +1387,12 @@ impl App {
     #[allow(dead_code)]
     fn save_lines(&mut self, lines: Vec<String>) {
         self.set_editor_lines(lines.clone());
+        // Never write the placeholder over a file that was not loaded
+        if self.opened_placeholder {
+            return;
+        }
         if let Some(path) = &self.opened {
-            let _ = std::fs::write(path, lines.join("\n"));
+            let _ = write_atomic(path, lines.join("\n").as_bytes());
         }
     }
 
@@ -539,31 +1481,338 @@ impl App {
         self.preview_col = 0;
     }
 
+    /// Rebuild both trees from disk, keeping what was expanded and selected
     pub fn refresh_tree(&mut self) -> Result<()> {
-        self.left_tree = build_tree_with_selection(&self.left_dir, &self.tree_selection)?;
-        self.right_tree = build_tree(&self.right_dir)?;
+        let opened = self.left_state.get_all_opened();
+        let selected = self.left_state.selected().to_vec();
+        let left_rules = self.ignore_rules(&self.left_dir);
+        let right_rules = self.ignore_rules(&self.right_dir);
+        self.left_tree = build_tree_with_selection(
+            &self.left_dir,
+            &self.tree_selection,
+            self.tree_display(),
+            &left_rules,
+            self.follow_symlinks,
+            self.tree_levels_at(0),
+        )?;
+        self.right_tree = build_tree(
+            &self.right_dir,
+            &right_rules,
+            self.follow_symlinks,
+            self.tree_display(),
+            self.tree_levels_at(0),
+        )?;
+        self.tree_selection_dirty = false;
+        self.restore_tree_state(opened, &selected);
         Ok(())
     }
 
-    /// Fast update of tree selection display without full rebuild
-    pub fn update_tree_selection_display(&mut self) {
-        // Use a more efficient method that only updates text formatting
-        // without doing filesystem I/O for each selection change
-        if let Ok(new_tree) =
-            build_tree_with_selection_cached(&self.left_dir, &self.tree_selection, &self.left_tree)
-        {
-            self.left_tree = new_tree;
+    /// Re-open the directories in `opened` and re-select `selected` in a
+    /// rebuilt left tree, matching items by path
+    ///
+    /// Directories that are gone stay closed, and a selection that is gone
+    /// moves to its closest remaining ancestor.
+    fn restore_tree_state(&mut self, mut opened: Vec<Vec<String>>, selected: &[String]) {
+        self.left_state.close_all();
+        // Parents first, so each directory is read before its children are looked up
+        opened.sort_by_key(Vec::len);
+        for ids in opened {
+            self.load_tree_node(&ids);
+            if tree_node_mut(&mut self.left_tree, &ids).is_some() {
+                self.left_state.open(ids);
+            }
+        }
+        let ids = selected
+            .last()
+            .and_then(|target| tree_path_toward(&self.left_tree, Path::new(target)));
+        if let Some(ids) = ids {
+            self.left_state.select(ids);
         }
     }
 
-    pub fn begin_create_file(&mut self) {
-        self.creating_file = true;
-        self.filename_input = TextArea::default();
-        self.filename_input.set_placeholder_text("new-note.md");
+    /// How tree builds order and label entries
+    fn tree_display(&self) -> TreeDisplay {
+        TreeDisplay {
+            icons: self.tree_icons,
+            sort: self.tree_sort,
+            show_hidden: self.show_hidden,
+        }
     }
 
-    pub fn cancel_create_file(&mut self) {
-        self.creating_file = false;
+    /// Levels a tree build starting `depth` directories below the root reads
+    fn tree_levels_at(&self, depth: usize) -> TreeLevels {
+        TreeLevels::new(
+            TREE_INITIAL_LEVELS,
+            self.tree_max_depth.map(|max| max.saturating_sub(depth)),
+        )
+    }
+
+    /// Read the directories opened in the left tree whose entries are unread
+    fn load_opened_dirs(&mut self) {
+        let mut opened = self.left_state.get_all_opened();
+        // Parents first, so a directory opened inside an unread one is found
+        opened.sort_by_key(Vec::len);
+        for ids in opened {
+            self.load_tree_node(&ids);
+        }
+    }
+
+    /// Read the entries of the unread directory at `ids` in the left tree
+    ///
+    /// Returns false if `ids` does not lead to an unread directory.
+    fn load_tree_node(&mut self, ids: &[String]) -> bool {
+        if !tree_node_mut(&mut self.left_tree, ids).is_some_and(|node| is_unloaded(node)) {
+            return false;
+        }
+        let (root, dir) = (&ids[0], &ids[ids.len() - 1]);
+        // Rebuild the rules and ancestors a full build would have reached `dir` with
+        let mut rules = self.ignore_rules(Path::new(root));
+        let mut ancestors = Vec::new();
+        for ancestor in &ids[..ids.len() - 1] {
+            rules = rules.descend(Path::new(ancestor));
+            push_ancestor(&mut ancestors, Path::new(ancestor), self.follow_symlinks);
+        }
+        let item = build_node(
+            Path::new(dir),
+            &self.tree_selection,
+            self.tree_display(),
+            &rules,
+            self.follow_symlinks,
+            &mut ancestors,
+            self.tree_levels_at(ids.len() - 1),
+        );
+        if let Some(node) = tree_node_mut(&mut self.left_tree, ids) {
+            *node = item;
+        }
+        true
+    }
+
+    /// Tree identifiers down to `target`, reading unread directories on the way
+    fn reveal_ids(&mut self, target: &Path) -> Option<Vec<String>> {
+        loop {
+            let ids = tree_path_toward(&self.left_tree, target)?;
+            if ids.last().is_some_and(|id| Path::new(id) == target) {
+                return Some(ids);
+            }
+            if !self.load_tree_node(&ids) {
+                return None;
+            }
+        }
+    }
+
+    /// Ignore rules for a tree rooted at `dir`
+    fn ignore_rules(&self, dir: &Path) -> IgnoreRules {
+        IgnoreRules::new(dir, self.respect_gitignore, &self.ignore_patterns)
+    }
+
+    /// Change which entries the tree hides and reload it in the background
+    pub fn set_ignore_options(&mut self, respect_gitignore: bool, patterns: Vec<String>) {
+        self.respect_gitignore = respect_gitignore;
+        self.ignore_patterns = patterns;
+        self.reload_tree_in_background();
+    }
+
+    /// Choose whether the tree descends into symlinked directories
+    pub fn set_follow_symlinks(&mut self, follow: bool) {
+        self.follow_symlinks = follow;
+        self.reload_tree_in_background();
+    }
+
+    /// Limit how many directory levels below the root the tree shows
+    pub fn set_tree_max_depth(&mut self, max_depth: Option<usize>) {
+        self.tree_max_depth = max_depth;
+        self.reload_tree_in_background();
+    }
+
+    /// Choose the glyphs drawn before tree entries
+    pub fn set_tree_icons(&mut self, icons: IconSet) {
+        self.tree_icons = icons;
+        self.reload_tree_in_background();
+    }
+
+    /// Show or hide dotfiles in the tree and the pickers (`.`)
+    pub fn toggle_hidden(&mut self) {
+        self.show_hidden = !self.show_hidden;
+        let reloaded = self.refresh_tree().and_then(|()| {
+            if self.picking_file {
+                self.load_picker_dir(self.picker_dir.clone())?;
+            }
+            if self.showing_move_dest {
+                self.load_move_dest_dir(self.move_dest_dir.clone())?;
+            }
+            Ok(())
+        });
+        self.status = match reloaded {
+            Ok(()) if self.show_hidden => "Showing hidden files".into(),
+            Ok(()) => "Hiding hidden files".into(),
+            Err(e) => format!("Reload failed: {e}"),
+        };
+    }
+
+    /// Sort the tree by the next order (`O`) and remember the choice
+    pub fn cycle_tree_sort(&mut self) {
+        self.tree_sort = self.tree_sort.next();
+        if let Err(e) = self.settings.set_tree_sort(self.tree_sort) {
+            tracing::warn!(error = %e, "failed to save tree sort order");
+        }
+        self.status = match self.refresh_tree() {
+            Ok(()) => format!("Sorted by {}", self.tree_sort.label()),
+            Err(e) => format!("Tree refresh failed: {e}"),
+        };
+    }
+
+    fn reload_tree_in_background(&mut self) {
+        let rules = self.ignore_rules(&self.root);
+        self.tree_loader = Some(spawn_tree_loader(
+            self.root.clone(),
+            rules,
+            self.follow_symlinks,
+            self.tree_display(),
+            self.tree_levels_at(0),
+        ));
+    }
+
+    /// Show or hide entries matched by `.gitignore`
+    pub fn toggle_gitignore(&mut self) {
+        let patterns = std::mem::take(&mut self.ignore_patterns);
+        self.set_ignore_options(!self.respect_gitignore, patterns);
+        self.status = if self.respect_gitignore {
+            "Hiding .gitignored files".into()
+        } else {
+            "Showing .gitignored files".into()
+        };
+    }
+
+    /// Note that the tree selection changed
+    ///
+    /// The checkmarks are rebuilt by [`Self::poll_background_tasks`], so a
+    /// burst of Shift+arrow presses costs one rebuild per tick, not one each.
+    pub fn update_tree_selection_display(&mut self) {
+        self.tree_selection_dirty = true;
+    }
+
+    /// Rebuild the tree text for pending selection changes right away
+    pub fn flush_tree_selection_display(&mut self) {
+        if !self.tree_selection_dirty {
+            return;
+        }
+        // Only the text formatting changes, so reuse the existing tree
+        // instead of doing filesystem I/O
+        if let Ok(new_tree) = build_tree_with_selection_cached(
+            &self.left_dir,
+            &self.tree_selection,
+            self.tree_icons,
+            &self.left_tree,
+        ) {
+            self.left_tree = new_tree;
+        }
+        self.tree_selection_dirty = false;
+        self.tree_selection_drawn = Some(Instant::now());
+    }
+
+    /// Path from the tree root to the selection, e.g. `notes › work › plan.md`
+    ///
+    /// Leading components are replaced by `…` until it fits `max_width`.
+    pub fn breadcrumb(&self, max_width: usize) -> String {
+        let parts: Vec<String> = self
+            .left_state
+            .selected()
+            .iter()
+            .map(|id| {
+                Path::new(id)
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| id.clone())
+            })
+            .collect();
+        let full = parts.join(" › ");
+        if full.chars().count() <= max_width {
+            return full;
+        }
+        for skip in 1..parts.len() {
+            let text = format!("… › {}", parts[skip..].join(" › "));
+            if text.chars().count() <= max_width {
+                return text;
+            }
+        }
+        if max_width == 0 {
+            return String::new();
+        }
+        let last = parts.last().map(String::as_str).unwrap_or_default();
+        let tail: Vec<char> = last.chars().collect();
+        let keep = tail.len().min(max_width - 1);
+        let tail: String = tail[tail.len() - keep..].iter().collect();
+        format!("…{tail}")
+    }
+
+    pub fn begin_goto_path(&mut self) {
+        self.going_to_path = true;
+        self.goto_input = TextArea::default();
+        self.goto_input
+            .set_placeholder_text("Go to path (relative to the tree root)");
+    }
+
+    pub fn cancel_goto_path(&mut self) {
+        self.going_to_path = false;
+    }
+
+    pub fn confirm_goto_path(&mut self) {
+        self.going_to_path = false;
+        let input = self.goto_input.lines().join("");
+        if let Err(e) = self.go_to_path(&input) {
+            self.status = e.to_string();
+        }
+    }
+
+    /// Select `input` in the file tree, expanding its ancestors
+    ///
+    /// Relative paths are resolved against the tree root.
+    pub fn go_to_path(&mut self, input: &str) -> Result<()> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(anyhow!("Go to path: no path given"));
+        }
+        let root = self
+            .left_tree
+            .first()
+            .map(|item| PathBuf::from(item.identifier()))
+            .unwrap_or_else(|| self.left_dir.clone());
+        let candidate = Path::new(input);
+        let candidate = if candidate.is_absolute() {
+            candidate.to_path_buf()
+        } else {
+            root.join(candidate)
+        };
+        let target = candidate
+            .canonicalize()
+            .map_err(|_| anyhow!("No such path: {input}"))?;
+        let canonical_root = root.canonicalize().unwrap_or_else(|_| root.clone());
+        let relative = target
+            .strip_prefix(&canonical_root)
+            .map_err(|_| anyhow!("{input} is outside {}", root.display()))?;
+        let target = if relative.as_os_str().is_empty() {
+            root
+        } else {
+            root.join(relative)
+        };
+        let ids = self
+            .reveal_ids(&target)
+            .ok_or_else(|| anyhow!("{input} is not shown in the tree"))?;
+        reveal_in_tree(&mut self.left_state, &ids);
+        self.focus = Focus::Left;
+        self.show_left_pane = true;
+        self.status = format!("Jumped to {}", target.display());
+        Ok(())
+    }
+
+    pub fn begin_create_file(&mut self) {
+        self.creating_file = true;
+        self.filename_input = TextArea::default();
+        self.filename_input.set_placeholder_text("new-note.md");
+    }
+
+    pub fn cancel_create_file(&mut self) {
+        self.creating_file = false;
         self.status = "Create canceled".into();
     }
 
@@ -603,12 +1852,17 @@ impl App {
         );
         fs::write(&new_path, &initial)?;
         self.opened = Some(new_path.clone());
+        self.opened_placeholder = false;
+        self.renderer = preview::MARKDOWN;
+        self.hex_len = None;
         self.editor.set_text(&initial);
         self.last_saved_text = Some(initial);
         self.creating_file = false;
         self.refresh_tree()?;
         // Select the new file in the left tree
-        let _ = self.left_state.select(vec![new_path.display().to_string()]);
+        if let Some(ids) = self.reveal_ids(&new_path) {
+            reveal_in_tree(&mut self.left_state, &ids);
+        }
         self.status = "File created".into();
         self.focus = Focus::Editor;
         Ok(())
@@ -665,18 +1919,151 @@ impl App {
         })
     }
 
-    fn delete_candidate(&self, candidate: &DeleteCandidate) -> Result<()> {
-        if candidate.is_dir {
-            fs::remove_dir_all(&candidate.canonical_path).with_context(|| {
-                format!("Deleting directory {}", candidate.canonical_path.display())
-            })?;
-        } else if candidate.canonical_path.exists() {
-            fs::remove_file(&candidate.canonical_path)
-                .with_context(|| format!("Deleting file {}", candidate.canonical_path.display()))?;
+    /// Move a delete candidate into the workspace trash
+    ///
+    /// Tracked files are then taken out of the git index (like `git rm`); if
+    /// that fails the file is put back, so the index never loses a file that
+    /// is still on disk.
+    fn delete_candidate(&self, candidate: &DeleteCandidate) -> Result<TrashedEntry> {
+        let trashed = move_to_trash(&self.workspace_root()?, &candidate.canonical_path)
+            .with_context(|| format!("Deleting {}", candidate.canonical_path.display()))?;
+        let mut staged = false;
+        if !candidate.is_dir && self.is_in_git_repo(&candidate.canonical_path) {
+            if let Some(ref repo) = self.git_repo {
+                match repo.remove_from_index(&candidate.canonical_path) {
+                    Ok(removed) => staged = removed,
+                    Err(err) => {
+                        let _ = restore_from_trash(&trashed, &candidate.canonical_path);
+                        return Err(err).with_context(|| {
+                            format!(
+                                "Git delete failed for {}",
+                                candidate.original_path.display()
+                            )
+                        });
+                    }
+                }
+            }
+        }
+        Ok(TrashedEntry {
+            original: candidate.canonical_path.clone(),
+            trashed,
+            staged,
+        })
+    }
+
+    fn record_file_op(&mut self, op: FileOp) {
+        self.file_op_history.push(op);
+        if self.file_op_history.len() > FILE_OP_HISTORY {
+            self.file_op_history.remove(0);
         }
+    }
+
+    /// Delete the workspace trash for good; the deletes and overwriting
+    /// moves whose entries it held can no longer be undone
+    pub fn empty_trash(&mut self) -> Result<()> {
+        let count = empty_trash(&self.workspace_root()?).context("Emptying the trash")?;
+        self.file_op_history.retain(|op| !op.uses_trash());
+        self.status = match count {
+            1 => "Emptied the trash (1 entry)".into(),
+            n => format!("Emptied the trash ({n} entries)"),
+        };
+        Ok(())
+    }
+
+    /// Reverse the most recent delete or move
+    pub fn undo_file_op(&mut self) -> Result<()> {
+        let Some(op) = self.file_op_history.pop() else {
+            self.status = "Nothing to undo".into();
+            return Ok(());
+        };
+        match self.undo_op(&op) {
+            Ok(status) => self.status = status,
+            Err(err) => {
+                self.status = format!("Undo failed: {err}");
+                self.file_op_history.push(op);
+                return Ok(());
+            }
+        }
+        if self.picking_file {
+            self.load_picker_dir(self.picker_dir.clone())?;
+        } else {
+            self.refresh_tree()?;
+        }
+        self.refresh_git_status();
         Ok(())
     }
 
+    fn undo_op(&self, op: &FileOp) -> Result<String> {
+        match op {
+            FileOp::Delete(entries) => self.restore_trashed(entries),
+            FileOp::Move {
+                from,
+                to,
+                git,
+                replaced,
+            } => self.undo_move(from, to, *git, replaced.as_ref()),
+            FileOp::Batch(ops) => {
+                for op in ops.iter().rev() {
+                    self.undo_op(op)?;
+                }
+                Ok(format!("Undid {} renames", ops.len()))
+            }
+        }
+    }
+
+    fn restore_trashed(&self, entries: &[TrashedEntry]) -> Result<String> {
+        if let Some(taken) = entries
+            .iter()
+            .find(|entry| entry.original.symlink_metadata().is_ok())
+        {
+            anyhow::bail!("{} already exists", taken.original.display());
+        }
+        for entry in entries {
+            restore_from_trash(&entry.trashed, &entry.original)
+                .with_context(|| format!("Restoring {}", entry.original.display()))?;
+            if entry.staged {
+                if let Some(ref repo) = self.git_repo {
+                    repo.add_to_index(&entry.original)?;
+                }
+            }
+        }
+        Ok(match entries {
+            [entry] => format!(
+                "Restored {}",
+                entry
+                    .original
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+            ),
+            _ => format!("Restored {} files", entries.len()),
+        })
+    }
+
+    fn undo_move(
+        &self,
+        from: &Path,
+        to: &Path,
+        git: bool,
+        replaced: Option<&TrashedEntry>,
+    ) -> Result<String> {
+        if from.symlink_metadata().is_ok() {
+            anyhow::bail!("{} already exists", from.display());
+        }
+        match &self.git_repo {
+            Some(repo) if git => repo.move_file(to, from)?,
+            _ => fs::rename(to, from)?,
+        }
+        let name = from.file_name().unwrap_or_default().to_string_lossy();
+        match replaced {
+            Some(entry) => {
+                self.restore_trashed(std::slice::from_ref(entry))?;
+                Ok(format!("Moved back: {name}, and restored what it replaced"))
+            }
+            None => Ok(format!("Moved back: {name}")),
+        }
+    }
+
     fn clear_opened_for_deleted_path(&mut self, candidate: &DeleteCandidate) {
         let should_clear = self.opened.as_ref().is_some_and(|opened| {
             opened == &candidate.original_path
@@ -720,10 +2107,11 @@ impl App {
                     return Ok(());
                 }
             };
-            self.delete_candidate(&candidate)?;
+            let entry = self.delete_candidate(&candidate)?;
+            self.record_file_op(FileOp::Delete(vec![entry]));
             self.clear_opened_for_deleted_path(&candidate);
             self.refresh_tree()?;
-            self.status = format!("Deleted {}", path.display());
+            self.status = format!("Deleted {} (u to undo)", path.display());
         }
         self.confirming_delete = false;
         self.delete_target = None;
@@ -838,6 +2226,7 @@ impl App {
     pub fn tree_clear_selection(&mut self) {
         self.tree_selection.clear();
         self.tree_selection_anchor = None;
+        self.update_tree_selection_display();
     }
 
     pub fn tree_toggle_selection(&mut self) {
@@ -854,6 +2243,88 @@ impl App {
         }
     }
 
+    /// Select the tree entry drawn at `column`, `row`, if any
+    pub fn tree_select_at(&mut self, column: u16, row: u16) -> bool {
+        match self.tree_id_at(column, row) {
+            Some(id) => {
+                self.left_state.select(id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Left button pressed at `column`, `row`
+    ///
+    /// Selects the tree entry under the pointer. A second press on the same
+    /// entry within the double-click interval from the settings opens it,
+    /// as `Enter` does.
+    pub fn tree_mouse_down(&mut self, column: u16, row: u16, now: Instant) -> Result<()> {
+        let Some(id) = self.tree_id_at(column, row) else {
+            self.tree_click = None;
+            return Ok(());
+        };
+        let interval = self.settings.double_click_interval();
+        let double = self.tree_click.as_ref().is_some_and(|click| {
+            click.id == id && !click.dragged && now.saturating_duration_since(click.at) <= interval
+        });
+        self.left_state.select(id.clone());
+        if double {
+            self.tree_click = None;
+            return self.activate_on_tree();
+        }
+        self.tree_click = Some(TreeClick {
+            id,
+            column,
+            row,
+            at: now,
+            held: true,
+            dragged: false,
+        });
+        Ok(())
+    }
+
+    /// Pointer dragged to `column`, `row` with the button held
+    ///
+    /// Once the pointer is the drag threshold from the settings away from a
+    /// tree entry it was pressed on, that entry is moved as with `F6`.
+    ///
+    /// # Returns
+    ///
+    /// Whether the drag started on a tree entry, in which case it must not
+    /// also resize the panes.
+    pub fn tree_mouse_drag(&mut self, column: u16, row: u16) -> bool {
+        let threshold = self.settings.drag_threshold();
+        let Some(click) = self.tree_click.as_mut().filter(|click| click.held) else {
+            return false;
+        };
+        if !click.dragged {
+            let distance = column.abs_diff(click.column).max(row.abs_diff(click.row));
+            if distance < threshold {
+                return true;
+            }
+            click.dragged = true;
+            let id = click.id.clone();
+            self.left_state.select(id);
+            self.begin_move();
+        }
+        true
+    }
+
+    /// Mouse button released; ends a drag in the tree
+    pub fn tree_mouse_up(&mut self) {
+        if let Some(click) = &mut self.tree_click {
+            click.held = false;
+        }
+    }
+
+    /// Entry drawn at `column`, `row` when the tree was last rendered
+    fn tree_id_at(&self, column: u16, row: u16) -> Option<Vec<String>> {
+        self.left_state
+            .rendered_at(Position::new(column, row))
+            .map(<[String]>::to_vec)
+    }
+
     /// Toggle selection for 'S' key - accumulates multiple selections
     pub fn tree_accumulate_selection(&mut self) {
         if let Some(selected_item) = self.left_state.selected().last().cloned() {
@@ -868,16 +2339,58 @@ impl App {
         }
     }
 
+    /// Select every entry currently visible in the tree (Ctrl+A)
+    ///
+    /// Collapsed directories keep their contents unselected; use
+    /// [`Self::begin_select_all_recursive`] to take everything.
     pub fn tree_select_all(&mut self) {
-        self.tree_selection.clear();
+        let root = self.left_dir.display().to_string();
+        let visible: HashSet<String> = self
+            .left_state
+            .flatten(&self.left_tree)
+            .into_iter()
+            .filter_map(|item| item.identifier.last().cloned())
+            .filter(|id| *id != root)
+            .collect();
+        let count = visible.len();
+        self.set_tree_selection(visible);
+        self.status =
+            format!("Selected {count} visible entries (A selects everything, Esc clears)");
+    }
+
+    /// Ask before selecting every entry under the root, expanded or not (A)
+    pub fn begin_select_all_recursive(&mut self) {
+        let mut everything = HashSet::new();
+        self.collect_all_tree_items(&mut everything);
+        everything.remove(&self.left_dir.display().to_string());
+        self.status = format!(
+            "Select all {} entries under {}? (y/n)",
+            everything.len(),
+            self.left_dir.display()
+        );
+        self.pending_select_all = Some(everything);
+    }
+
+    pub fn is_confirming_select_all(&self) -> bool {
+        self.pending_select_all.is_some()
+    }
 
-        // Create a local HashSet to avoid borrow checker issues
-        let mut new_selection = HashSet::new();
-        self.collect_all_tree_items(&mut new_selection);
-        self.tree_selection = new_selection;
+    pub fn confirm_select_all_recursive(&mut self) {
+        if let Some(everything) = self.pending_select_all.take() {
+            let count = everything.len();
+            self.set_tree_selection(everything);
+            self.status = format!("Selected all {count} entries (Esc clears)");
+        }
+    }
+
+    pub fn cancel_select_all_recursive(&mut self) {
+        self.pending_select_all = None;
+        self.status = "Selection unchanged".into();
+    }
 
+    fn set_tree_selection(&mut self, selection: HashSet<String>) {
+        self.tree_selection = selection;
         self.tree_selection_anchor = None;
-        // Fast update of tree display to show selection changes
         self.update_tree_selection_display();
     }
 
@@ -885,10 +2398,25 @@ impl App {
         fn collect_recursive(items: &[TreeItem<String>], selection: &mut HashSet<String>) {
             for item in items {
                 selection.insert(item.identifier().clone());
-                collect_recursive(item.children(), selection);
+                // Select a symlink itself, not whatever it points into
+                if !is_symlink(Path::new(item.identifier())) {
+                    collect_recursive(item.children(), selection);
+                }
             }
         }
-        collect_recursive(&self.left_tree, selection);
+        // The shown tree only holds what was expanded, so read everything afresh
+        let levels = TreeLevels::new(usize::MAX, self.tree_max_depth);
+        let rules = self.ignore_rules(&self.left_dir);
+        let tree = build_tree(
+            &self.left_dir,
+            &rules,
+            self.follow_symlinks,
+            self.tree_display(),
+            levels,
+        );
+        if let Ok(tree) = tree {
+            collect_recursive(&tree, selection);
+        }
     }
 
     pub fn tree_up_with_selection(&mut self) {
@@ -897,15 +2425,11 @@ impl App {
                 // Start selection from current position
                 self.tree_selection_anchor = Some(current.clone());
                 self.tree_selection.insert(current);
-                // Only update display when starting selection
-                self.update_tree_selection_display();
             }
 
             // Move up in tree
             let _ = self.left_state.key_up();
             self.update_tree_selection_range();
-            // Update display only after range selection is complete
-            // This reduces the number of tree rebuilds during rapid navigation
             self.update_tree_selection_display();
         }
     }
@@ -916,15 +2440,11 @@ impl App {
                 // Start selection from current position
                 self.tree_selection_anchor = Some(current.clone());
                 self.tree_selection.insert(current);
-                // Only update display when starting selection
-                self.update_tree_selection_display();
             }
 
             // Move down in tree
             let _ = self.left_state.key_down();
             self.update_tree_selection_range();
-            // Update display only after range selection is complete
-            // This reduces the number of tree rebuilds during rapid navigation
             self.update_tree_selection_display();
         }
     }
@@ -963,6 +2483,9 @@ impl App {
     ) {
         for item in items {
             let id = item.identifier();
+            if id.ends_with(UNLOADED_SUFFIX) {
+                continue;
+            }
 
             // Check if this is one of our range endpoints
             if id == anchor || id == current {
@@ -1018,12 +2541,7 @@ impl App {
         let mut items: Vec<PathBuf> = std::fs::read_dir(&dir)?
             .filter_map(|e| e.ok())
             .map(|e| e.path())
-            .filter(|p| {
-                !p.file_name()
-                    .unwrap_or_default()
-                    .to_string_lossy()
-                    .starts_with('.')
-            })
+            .filter(|p| self.show_hidden || !is_hidden(p))
             .collect();
         items.sort_by_key(|p| {
             (
@@ -1048,7 +2566,7 @@ impl App {
         self.picker_clear_selection();
 
         // Refresh Git status when loading new directory
-        self.refresh_git_status();
+        self.refresh_git_status_if_stale();
 
         Ok(())
     }
@@ -1128,20 +2646,115 @@ impl App {
         Ok(())
     }
 
-    /// Show Git status (S command)
-    pub fn picker_show_git_status(&mut self) {
-        // Don't switch overlays while in picker - just show status in the status bar
-        if let Some(ref repo) = self.git_repo {
-            match repo.status_summary() {
-                Ok(summary) => {
-                    self.git_status_text = summary.clone();
-                    // Don't set showing_git_status = true to avoid overlay
-                    self.status = format!("Git: {}", summary);
+    /// Start renaming the selected files, or the current one (R command)
+    pub fn picker_begin_bulk_rename(&mut self) {
+        let mut indices: Vec<usize> = self.picker_selection.iter().copied().collect();
+        if indices.is_empty() {
+            indices.push(self.picker_index);
+        }
+        indices.sort_unstable();
+        let sources: Vec<PathBuf> = indices
+            .iter()
+            .filter_map(|&i| self.picker_items.get(i).cloned())
+            .filter(|p| p.is_file())
+            .collect();
+        if sources.is_empty() {
+            self.status = "No files selected to rename".to_string();
+            return;
+        }
+        self.status = format!(
+            "Rename {} files: {{name}} {{ext}} {{n}} or s/find/replace/",
+            sources.len()
+        );
+        self.rename_sources = sources;
+        self.rename_input = TextArea::default();
+        self.bulk_renaming = true;
+    }
+
+    pub fn cancel_bulk_rename(&mut self) {
+        self.bulk_renaming = false;
+        self.rename_sources.clear();
+        self.status = "Rename cancelled".to_string();
+    }
+
+    /// Files the bulk rename prompt applies to, in `{n}` order
+    pub fn rename_sources(&self) -> &[PathBuf] {
+        &self.rename_sources
+    }
+
+    /// The renames the typed pattern would make, or why it can't be applied
+    pub fn bulk_rename_preview(&self) -> std::io::Result<Vec<(PathBuf, PathBuf)>> {
+        plan_bulk_rename(&self.rename_sources, &self.rename_input.lines().join(""))
+    }
+
+    /// Apply the bulk rename; nothing is renamed unless every file can be
+    pub fn confirm_bulk_rename(&mut self) -> Result<()> {
+        let plan = match self.bulk_rename_preview() {
+            Ok(plan) if plan.is_empty() => {
+                self.status = "No names would change".to_string();
+                return Ok(());
+            }
+            Ok(plan) => plan,
+            Err(err) => {
+                self.status = format!("Rename aborted: {err}");
+                return Ok(());
+            }
+        };
+
+        let mut done = Vec::new();
+        for (from, to) in &plan {
+            match self.rename_path(from, to) {
+                Ok(git) => done.push(FileOp::Move {
+                    from: from.clone(),
+                    to: to.clone(),
+                    git,
+                    replaced: None,
+                }),
+                Err(err) => {
+                    // Put back what was already renamed so the batch is all or nothing
+                    let _ = self.undo_op(&FileOp::Batch(done));
+                    self.status = format!("Rename aborted: {err:#}");
+                    return Ok(());
                 }
-                Err(e) => {
-                    self.status = format!("Failed to get Git status: {}", e);
+            }
+        }
+
+        let count = done.len();
+        self.record_file_op(FileOp::Batch(done));
+        self.bulk_renaming = false;
+        self.rename_sources.clear();
+        self.picker_clear_selection();
+        self.load_picker_dir(self.picker_dir.clone())?;
+        self.refresh_git_status();
+        self.status = format!("Renamed {count} files (u to undo)");
+        Ok(())
+    }
+
+    /// Rename with `git mv` when the file is in the repository; true if git was used
+    fn rename_path(&self, from: &Path, to: &Path) -> Result<bool> {
+        if self.is_in_git_repo(from) {
+            if let Some(ref repo) = self.git_repo {
+                match repo.move_file(from, to) {
+                    Ok(()) => return Ok(true),
+                    // The rename itself may have happened before git gave up
+                    Err(_) if to.exists() => return Ok(false),
+                    Err(_) => {}
                 }
             }
+        }
+        fs::rename(from, to).with_context(|| format!("Renaming {}", from.display()))?;
+        Ok(false)
+    }
+
+    /// Show Git status (S command)
+    pub fn picker_show_git_status(&mut self) {
+        // Don't switch overlays while in picker - just show status in the status bar
+        if self.git_repo.is_some() {
+            self.refresh_git_status_if_stale();
+            let summary = summarize_status(&self.git_status);
+            self.git_status_text = summary.clone();
+            // Don't set showing_git_status = true to avoid overlay
+            self.status = format!("Git: {}", summary);
         } else {
             self.status = "Not a Git repository".to_string();
         }
@@ -1267,45 +2880,41 @@ impl App {
                 }
             };
 
-            let mut deleted_count = 0;
-            let mut git_deleted_count = 0;
-
+            let mut trashed = Vec::new();
+            let mut failure = None;
             for candidate in &selected_candidates {
-                if self.is_in_git_repo(&candidate.canonical_path) {
-                    if let Some(ref repo) = self.git_repo {
-                        if let Err(err) = repo.remove_file(&candidate.canonical_path) {
-                            self.status = format!(
-                                "Git delete failed for {}: {}",
-                                candidate.original_path.display(),
-                                err
-                            );
-                            self.confirming_delete = false;
-                            return Ok(());
-                        }
-                        git_deleted_count += 1;
-                        deleted_count += 1;
+                match self.delete_candidate(candidate) {
+                    Ok(entry) => trashed.push(entry),
+                    Err(err) => {
+                        failure = Some(err);
+                        break;
                     }
-                } else {
-                    self.delete_candidate(candidate)?;
-                    deleted_count += 1;
                 }
                 self.clear_opened_for_deleted_path(candidate);
             }
 
-            if git_deleted_count > 0 && deleted_count > git_deleted_count {
+            let deleted_count = trashed.len();
+            let git_deleted_count = trashed.iter().filter(|entry| entry.staged).count();
+            if !trashed.is_empty() {
+                self.record_file_op(FileOp::Delete(trashed));
+            }
+
+            if let Some(err) = failure {
+                self.status = format!("{err:#} ({deleted_count} deleted before it; u to undo)");
+            } else if git_deleted_count > 0 && deleted_count > git_deleted_count {
                 self.status = format!(
-                    "Deleted {} files ({} via git rm)",
+                    "Deleted {} files, {} via git rm (u to undo)",
                     deleted_count, git_deleted_count
                 );
             } else if git_deleted_count > 0 {
-                self.status = format!("Git removed {} files", git_deleted_count);
+                self.status = format!("Git removed {} files (u to undo)", git_deleted_count);
             } else {
-                self.status = format!("Deleted {} files", deleted_count);
+                self.status = format!("Deleted {} files (u to undo)", deleted_count);
             }
 
             // Remove all deleted files from picker cache or refresh tree
             if self.picking_file {
-                for candidate in &selected_candidates {
+                for candidate in selected_candidates.iter().take(deleted_count) {
                     self.remove_picker_item(&candidate.original_path);
                 }
             } else {
@@ -1316,8 +2925,7 @@ impl App {
             if !self.picker_selection.is_empty() {
                 self.picker_clear_selection();
             } else {
-                self.tree_selection.clear();
-                self.tree_selection_anchor = None;
+                self.tree_clear_selection();
             }
             self.refresh_git_status();
         } else if let Some(path) = self.delete_target.take() {
@@ -1330,31 +2938,23 @@ impl App {
                 }
             };
 
-            if candidate.is_dir {
-                self.delete_candidate(&candidate)?;
-                self.status = format!(
-                    "Deleted directory: {}",
-                    path.file_name().unwrap_or_default().to_string_lossy()
-                );
-            } else if self.is_in_git_repo(&candidate.canonical_path) {
-                if let Some(ref repo) = self.git_repo {
-                    if let Err(err) = repo.remove_file(&candidate.canonical_path) {
-                        self.status = format!("Git delete failed for {}: {}", path.display(), err);
-                        self.confirming_delete = false;
-                        return Ok(());
-                    }
-                    self.status = format!(
-                        "Git removed: {}",
-                        path.file_name().unwrap_or_default().to_string_lossy()
-                    );
+            let entry = match self.delete_candidate(&candidate) {
+                Ok(entry) => entry,
+                Err(err) => {
+                    self.status = format!("{err:#}");
+                    self.confirming_delete = false;
+                    return Ok(());
                 }
+            };
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            self.status = if candidate.is_dir {
+                format!("Deleted directory: {name} (u to undo)")
+            } else if entry.staged {
+                format!("Git removed: {name} (u to undo)")
             } else {
-                self.delete_candidate(&candidate)?;
-                self.status = format!(
-                    "Deleted: {}",
-                    path.file_name().unwrap_or_default().to_string_lossy()
-                );
-            }
+                format!("Deleted: {name} (u to undo)")
+            };
+            self.record_file_op(FileOp::Delete(vec![entry]));
 
             self.clear_opened_for_deleted_path(&candidate);
 
@@ -1388,12 +2988,7 @@ impl App {
             .filter_map(|e| e.ok())
             .map(|e| e.path())
             .filter(|p| p.is_dir()) // Only show directories for move destination
-            .filter(|p| {
-                !p.file_name()
-                    .unwrap_or_default()
-                    .to_string_lossy()
-                    .starts_with('.')
-            })
+            .filter(|p| self.show_hidden || !is_hidden(p))
             .collect();
 
         items.sort_by_key(|p| {
@@ -1444,13 +3039,15 @@ impl App {
             let filename = source.file_name().unwrap_or_default();
             let dest = dest_dir.join(filename);
 
+            let mut git = false;
             if self.is_in_git_repo(&source) && self.is_in_git_repo(&dest) {
                 // Both source and dest are in the Git repo, use git mv
                 if let Some(ref repo) = self.git_repo {
                     match repo.move_file(&source, &dest) {
                         Ok(()) => {
+                            git = true;
                             self.status = format!(
-                                "Git moved: {} -> {}",
+                                "Git moved: {} -> {} (u to undo)",
                                 source.file_name().unwrap_or_default().to_string_lossy(),
                                 dest.display()
                             );
@@ -1459,7 +3056,7 @@ impl App {
                             // Fallback to regular move
                             std::fs::rename(&source, &dest)?;
                             self.status = format!(
-                                "Moved: {} -> {}",
+                                "Moved: {} -> {} (u to undo)",
                                 source.file_name().unwrap_or_default().to_string_lossy(),
                                 dest.display()
                             );
@@ -1470,11 +3067,17 @@ impl App {
                 // Regular move
                 std::fs::rename(&source, &dest)?;
                 self.status = format!(
-                    "Moved: {} -> {}",
+                    "Moved: {} -> {} (u to undo)",
                     source.file_name().unwrap_or_default().to_string_lossy(),
                     dest.display()
                 );
             }
+            self.record_file_op(FileOp::Move {
+                from: source,
+                to: dest,
+                git,
+                replaced: None,
+            });
 
             // Refresh views
             self.load_picker_dir(self.picker_dir.clone())?;
@@ -1492,18 +3095,145 @@ impl App {
         self.status = "Move cancelled".to_string();
     }
 
-    /// Close Git status display
-    pub fn close_git_status(&mut self) {
-        self.showing_git_status = false;
-        self.status = "Ready".to_string();
+    /// Toggle the `git blame` gutter in the code preview
+    pub fn toggle_blame(&mut self) {
+        if self.git_repo.is_none() {
+            self.status = "Blame needs a Git repository".into();
+            return;
+        }
+        self.show_blame = !self.show_blame;
+        self.status = format!("Blame {}", if self.show_blame { "on" } else { "off" });
     }
 
-    /// Refresh Git status cache
-    pub fn refresh_git_status(&mut self) {
-        if let Some(ref repo) = self.git_repo {
-            if let Ok(status) = repo.status() {
-                self.git_status = status;
+    /// Whether the preview of the open file wraps long lines
+    ///
+    /// A toggle this session wins over the default for the file's
+    /// extension from the settings.
+    pub fn preview_wrap(&self) -> bool {
+        let Some(path) = &self.opened else {
+            return true;
+        };
+        self.wrap_overrides
+            .get(path)
+            .copied()
+            .unwrap_or_else(|| self.settings.wrap_lines(path))
+    }
+
+    /// Switch the open file's preview between wrapping long lines and
+    /// scrolling sideways
+    pub fn toggle_preview_wrap(&mut self) {
+        let Some(path) = self.opened.clone() else {
+            return;
+        };
+        let wrap = !self.preview_wrap();
+        self.wrap_overrides.insert(path, wrap);
+        self.preview_hscroll = 0;
+        self.status = format!("Line wrap {}", if wrap { "on" } else { "off" });
+    }
+
+    /// Scroll the unwrapped preview sideways so the cursor column stays
+    /// within `width` visible columns
+    pub fn sync_preview_hscroll(&mut self, width: usize) {
+        if self.preview_wrap() || width == 0 {
+            self.preview_hscroll = 0;
+        } else if self.preview_col < self.preview_hscroll {
+            self.preview_hscroll = self.preview_col;
+        } else if self.preview_col >= self.preview_hscroll + width {
+            self.preview_hscroll = self.preview_col + 1 - width;
+        }
+    }
+
+    /// Whether the right pane shows the raw editor and the preview together
+    pub fn split_view_active(&self) -> bool {
+        self.split_view && self.opened.is_some()
+    }
+
+    /// Show the raw editor beside the rendered preview, or stop doing so
+    ///
+    /// Opening the split starts editing at the preview cursor; closing it
+    /// leaves whichever half had focus on its own.
+    pub fn toggle_split_view(&mut self) {
+        if self.opened.is_none() {
+            self.status = "Open a file to split the view".into();
+            return;
+        }
+        self.split_view = !self.split_view;
+        if self.split_view {
+            self.focus = Focus::Editor;
+            self.show_raw_editor = true;
+            self.prefer_raw_editor = true;
+            self.editor
+                .set_cursor(self.preview_cursor, self.preview_col);
+            self.split_scroll = self.preview_scroll;
+            self.status = "Split view: raw | rendered (F3 to close)".into();
+        } else {
+            self.status = "Split view closed".into();
+        }
+    }
+
+    /// Line up the halves of the split view and return the preview's cursor
+    /// and scroll offset, in rows of `rendered`
+    ///
+    /// While editing, the preview follows the editor cursor to the row that
+    /// shows it and scrolls only as far as needed to keep that row among its
+    /// `viewport` rows, the way the editor scrolls. Otherwise the editor
+    /// follows the preview cursor.
+    pub fn sync_split_view(&mut self, rendered: &RichText<'_>, viewport: usize) -> (usize, usize) {
+        if !self.show_raw_editor {
+            self.editor
+                .set_cursor(self.preview_cursor, self.preview_col);
+            self.split_scroll = self.preview_scroll;
+            return (self.preview_cursor, self.preview_scroll);
+        }
+        let cursor = self.rendered_line(rendered, self.editor.cursor().0);
+        if cursor < self.split_scroll {
+            self.split_scroll = cursor;
+        } else if cursor >= self.split_scroll + viewport.max(1) {
+            self.split_scroll = cursor + 1 - viewport.max(1);
+        }
+        (cursor, self.split_scroll)
+    }
+
+    /// Row of the rendered preview that shows source line `line`
+    ///
+    /// Code and hex previews are drawn line for line; markdown is matched up
+    /// by its headings, see [`preview::rendered_line`].
+    pub fn rendered_line(&self, rendered: &RichText<'_>, line: usize) -> usize {
+        if self.opened.as_deref().is_some_and(preview::is_code_file) || self.hex_view().is_some() {
+            return line;
+        }
+        let headings = self.markdown_headings();
+        let anchors: Vec<(usize, &str)> = headings
+            .iter()
+            .map(|(_, title, line)| (*line, title.as_str()))
+            .collect();
+        preview::rendered_line(rendered, &anchors, self.editor.line_count(), line)
+    }
+
+    /// Close Git status display
+    pub fn close_git_status(&mut self) {
+        self.showing_git_status = false;
+        self.status = "Ready".to_string();
+    }
+
+    /// Re-read the Git status cache now, e.g. after a file operation
+    pub fn refresh_git_status(&mut self) {
+        if let Some(ref repo) = self.git_repo {
+            if let Ok(status) = repo.status() {
+                self.git_status = status;
+                self.git_status_refreshed = Some(Instant::now());
             }
+            self.git_branch = repo.branch_label().ok();
+        }
+    }
+
+    /// Re-read the Git status cache unless it is recent or already loading
+    pub fn refresh_git_status_if_stale(&mut self) {
+        let fresh = self
+            .git_status_refreshed
+            .is_some_and(|at| at.elapsed() < GIT_STATUS_TTL);
+        if !fresh && self.git_status_loader.is_none() {
+            self.refresh_git_status();
         }
     }
 
@@ -1598,7 +3328,7 @@ impl App {
     pub fn confirm_op(&mut self) -> Result<()> {
         match self.op_mode {
             OpMode::None => {}
-            OpMode::Copy => {
+            OpMode::Copy | OpMode::Move => {
                 if let Some(src) = self.op_source.clone() {
                     let name = self.op_input.lines().first().cloned().unwrap_or_default();
                     let target_dir = if matches!(self.focus, Focus::Left) {
@@ -1611,31 +3341,26 @@ impl App {
                     } else {
                         PathBuf::from(target_dir).join(name)
                     };
-                    if src.is_dir() {
-                        copy_dir_all(&src, &dest)?;
-                    } else {
-                        std::fs::create_dir_all(dest.parent().unwrap_or(Path::new(".")))?;
-                        std::fs::copy(&src, &dest)?;
+                    if self.copy_job.is_some() {
+                        self.cancel_op();
+                        self.status =
+                            "Wait for the running copy or move to finish or cancel it".into();
+                        return Ok(());
                     }
-                    self.status = format!("Copied → {}", dest.display());
-                }
-            }
-            OpMode::Move => {
-                if let Some(src) = self.op_source.clone() {
-                    let name = self.op_input.lines().first().cloned().unwrap_or_default();
-                    let target_dir = if matches!(self.focus, Focus::Left) {
-                        &self.right_dir
-                    } else {
-                        &self.left_dir
-                    };
-                    let dest = if name.is_empty() {
-                        PathBuf::from(target_dir).join(src.file_name().unwrap_or_default())
+                    let conflicts = if self.op_mode == OpMode::Move {
+                        usize::from(dest.symlink_metadata().is_ok())
                     } else {
-                        PathBuf::from(target_dir).join(name)
+                        copy_conflicts(&src, &dest)
                     };
-                    std::fs::create_dir_all(dest.parent().unwrap_or(Path::new(".")))?;
-                    std::fs::rename(&src, &dest)?;
-                    self.status = format!("Moved → {}", dest.display());
+                    if conflicts > 0 {
+                        self.status = format!(
+                            "{} exists: overwrite (y), keep both (r) or cancel (n)?",
+                            dest.display()
+                        );
+                        self.overwrite_prompt = Some(OverwritePrompt { dest, conflicts });
+                        return Ok(());
+                    }
+                    self.transfer(&src, &dest, false)?;
                 }
             }
             OpMode::Mkdir => {
@@ -1655,12 +3380,135 @@ impl App {
                 self.status = format!("Created dir {}", dir.display());
             }
         }
+        self.finish_op()
+    }
+
+    /// Copy or move `src` to `dest`, as the pending operation says
+    ///
+    /// Existing files at `dest` are only replaced when `overwrite` is set; a
+    /// move puts what it replaces in the workspace trash so undo can bring
+    /// it back. Both run in the background and report through
+    /// [`App::poll_background_tasks`].
+    fn transfer(&mut self, src: &Path, dest: &Path, overwrite: bool) -> Result<()> {
+        std::fs::create_dir_all(dest.parent().unwrap_or(Path::new(".")))?;
+        if self.op_mode == OpMode::Move {
+            let occupied = dest.symlink_metadata().is_ok();
+            if occupied && !overwrite {
+                return Err(anyhow!("{} already exists", dest.display()));
+            }
+            if occupied && src.starts_with(dest) {
+                return Err(anyhow!("{} is inside {}", src.display(), dest.display()));
+            }
+            let replaced = if occupied {
+                let trashed = move_to_trash(&self.workspace_root()?, dest)
+                    .with_context(|| format!("Moving {} to the trash", dest.display()))?;
+                Some(TrashedEntry {
+                    original: dest.to_path_buf(),
+                    trashed,
+                    staged: false,
+                })
+            } else {
+                None
+            };
+            self.copy_job = Some(spawn_move(src.to_path_buf(), dest.to_path_buf()));
+            self.pending_move = Some((src.to_path_buf(), replaced));
+            self.status = format!("Moving to {} (Esc to cancel)", dest.display());
+        } else {
+            self.copy_job = Some(spawn_copy(src.to_path_buf(), dest.to_path_buf(), overwrite));
+            self.status = format!("Copying to {} (Esc to cancel)", dest.display());
+        }
+        Ok(())
+    }
+
+    /// Status for a finished copy or move, recording a move for undo; a
+    /// move that didn't happen puts back what it would have replaced
+    fn finish_transfer(&mut self, result: Result<(PathBuf, CopyProgress), String>) -> String {
+        let Some((from, replaced)) = self.pending_move.take() else {
+            return match result {
+                Ok((dest, progress)) => {
+                    format!("Copied → {} ({})", dest.display(), progress.summary())
+                }
+                Err(status) => status,
+            };
+        };
+        let dest = match result {
+            Ok((dest, _)) => dest,
+            Err(status) => {
+                if let Some(entry) = &replaced {
+                    if let Err(e) = restore_from_trash(&entry.trashed, &entry.original) {
+                        return format!(
+                            "{status}; {} is still in the trash: {e}",
+                            entry.original.display()
+                        );
+                    }
+                }
+                return status;
+            }
+        };
+        let status = match replaced {
+            Some(_) => format!("Moved → {}, replacing it (u to undo)", dest.display()),
+            None => format!("Moved → {} (u to undo)", dest.display()),
+        };
+        self.record_file_op(FileOp::Move {
+            from,
+            to: dest,
+            git: false,
+            replaced,
+        });
+        status
+    }
+
+    /// Whether a copy or move is running in the background
+    pub fn copy_running(&self) -> bool {
+        self.copy_job.is_some()
+    }
+
+    /// Stop the running copy or move; the worker removes what it had copied
+    pub fn cancel_copy(&mut self) {
+        if let Some(job) = &self.copy_job {
+            job.cancel();
+            self.status = match self.pending_move {
+                Some(_) => "Cancelling move...".into(),
+                None => "Cancelling copy...".into(),
+            };
+        }
+    }
+
+    fn finish_op(&mut self) -> Result<()> {
         self.refresh_tree()?;
         self.op_mode = OpMode::None;
         self.op_source = None;
         Ok(())
     }
 
+    /// Finish the held-back copy or move, replacing what is there (y)
+    pub fn confirm_overwrite(&mut self) -> Result<()> {
+        let (Some(prompt), Some(src)) = (self.overwrite_prompt.take(), self.op_source.clone())
+        else {
+            return Ok(());
+        };
+        self.transfer(&src, &prompt.dest, true)?;
+        self.finish_op()
+    }
+
+    /// Finish the held-back copy or move next to the existing entry, as
+    /// `name (1).ext` or the next free number (r)
+    pub fn keep_both(&mut self) -> Result<()> {
+        let (Some(prompt), Some(src)) = (self.overwrite_prompt.take(), self.op_source.clone())
+        else {
+            return Ok(());
+        };
+        self.transfer(&src, &unique_path(&prompt.dest), false)?;
+        self.finish_op()
+    }
+
+    /// Drop the held-back copy or move (n)
+    pub fn cancel_overwrite(&mut self) {
+        self.overwrite_prompt = None;
+        self.cancel_op();
+        self.status = "Nothing was overwritten".into();
+    }
+
     // --- Inline editing in Preview ----------------------------------------
     #[allow(dead_code)]
     pub fn begin_line_edit(&mut self) {
@@ -1688,7 +3536,7 @@ impl App {
             // Immediate save if file open
             if let Some(path) = &self.opened {
                 let text = lines.join("\n");
-                let _ = std::fs::write(path, text);
+                let _ = write_atomic(path, text.as_bytes());
             }
             self.status = format!("Updated line {}", self.preview_cursor + 1);
         }
@@ -1697,22 +3545,273 @@ impl App {
 
     // --- Vim helpers -------------------------------------------------------
     pub fn move_cursor_up(&mut self) {
-        if self.preview_cursor > 0 {
+        if self.moves_by_display_line() {
+            self.move_display_row_up();
+        } else if self.preview_cursor > 0 {
             self.preview_cursor -= 1;
         }
-        let vp = self.preview_viewport.max(1);
-        if self.preview_cursor < self.preview_scroll {
-            self.preview_scroll = self.preview_cursor;
+        self.scroll_cursor_into_view();
+    }
+
+    pub fn move_cursor_down(&mut self) {
+        if self.moves_by_display_line() {
+            self.move_display_row_down();
+        } else if self.preview_cursor + 1 < self.preview_line_count() {
+            self.preview_cursor += 1;
         }
-        if self.preview_cursor >= self.preview_scroll.saturating_add(vp) {
-            self.preview_scroll = self.preview_cursor.saturating_sub(vp.saturating_sub(1));
+        self.scroll_cursor_into_view();
+    }
+
+    /// Whether up and down step through the screen rows of wrapped lines,
+    /// as `"move_by_display_line"` in the settings asks
+    pub fn moves_by_display_line(&self) -> bool {
+        self.settings.move_by_display_line()
+            && self.preview_wrap()
+            && self.preview_width > 0
+            && self.preview_rows.width == self.preview_width
+            && self.hex_view().is_none()
+    }
+
+    /// Note how the lines of the drawn preview wrap at the preview width;
+    /// the rows are only worked out again once the text or width changes
+    pub fn sync_preview_rows(&mut self, rendered: &RichText<'_>) {
+        let lines: Vec<String> = rendered.lines.iter().map(|l| l.to_string()).collect();
+        if self.preview_rows.width == self.preview_width && self.preview_rows.lines == lines {
+            return;
+        }
+        let starts: Vec<Vec<usize>> = lines
+            .iter()
+            .map(|l| preview::wrap_starts(l, self.preview_width))
+            .collect();
+        let mut above = Vec::with_capacity(starts.len() + 1);
+        above.push(0);
+        for row in &starts {
+            above.push(above[above.len() - 1] + row.len());
         }
+        self.preview_rows = PreviewRows {
+            width: self.preview_width,
+            lines,
+            starts,
+            above,
+        };
     }
 
-    pub fn move_cursor_down(&mut self) {
-        if self.preview_cursor + 1 < self.editor_line_count() {
+    /// Screen rows above the preview scroll position and above the
+    /// cursor's row, for the preview to scroll and highlight by
+    pub fn preview_wrap_rows(&self) -> (usize, usize) {
+        (
+            self.rows_above_line(self.preview_scroll),
+            self.rows_above_line(self.preview_cursor) + self.preview_wrap_row(),
+        )
+    }
+
+    /// Row of its wrapped line the preview cursor is on; always 0 unless
+    /// the cursor moves by display line
+    pub fn preview_wrap_row(&self) -> usize {
+        if !self.moves_by_display_line() {
+            return 0;
+        }
+        let (starts, _) = self.display_rows(self.preview_cursor);
+        preview::wrap_row_of(&starts, self.preview_col)
+    }
+
+    /// Where the screen rows of drawn line `idx` start, and the line's
+    /// length in characters
+    fn display_rows(&self, idx: usize) -> (Vec<usize>, usize) {
+        match self.preview_rows.lines.get(idx) {
+            Some(line) => (self.preview_rows.starts[idx].clone(), line.chars().count()),
+            None => (vec![0], 0),
+        }
+    }
+
+    /// Screen rows taken by the drawn lines above line `idx`
+    fn rows_above_line(&self, idx: usize) -> usize {
+        let above = &self.preview_rows.above;
+        match above.get(idx) {
+            Some(&rows) => rows,
+            // Lines past the drawn text take a row each
+            None => above.last().copied().unwrap_or(0) + idx + 1 - above.len(),
+        }
+    }
+
+    /// Down one screen row: to the next row of a wrapped line, or the first
+    /// row of the next line, keeping the cursor as far into the row
+    fn move_display_row_down(&mut self) {
+        let (starts, len) = self.display_rows(self.preview_cursor);
+        let row = preview::wrap_row_of(&starts, self.preview_col);
+        let offset = self.preview_col.min(len) - starts[row];
+        if row + 1 < starts.len() {
+            self.preview_col = column_in_row(&starts, len, row + 1, offset);
+        } else if self.preview_cursor + 1 < self.preview_line_count() {
             self.preview_cursor += 1;
+            let (starts, len) = self.display_rows(self.preview_cursor);
+            self.preview_col = column_in_row(&starts, len, 0, offset);
+        }
+    }
+
+    /// Up one screen row: to the previous row of a wrapped line, or the
+    /// last row of the previous line
+    fn move_display_row_up(&mut self) {
+        let (starts, len) = self.display_rows(self.preview_cursor);
+        let row = preview::wrap_row_of(&starts, self.preview_col);
+        let offset = self.preview_col.min(len) - starts[row];
+        if row > 0 {
+            self.preview_col = column_in_row(&starts, len, row - 1, offset);
+        } else if self.preview_cursor > 0 {
+            self.preview_cursor -= 1;
+            let (starts, len) = self.display_rows(self.preview_cursor);
+            self.preview_col = column_in_row(&starts, len, starts.len() - 1, offset);
+        }
+    }
+
+    /// Screen rows between the top of the preview and the cursor's row
+    fn rows_above_cursor(&self) -> usize {
+        let (scroll, cursor) = self.preview_wrap_rows();
+        cursor.saturating_sub(scroll)
+    }
+
+    /// Move the preview cursor and scroll up by one screen
+    pub fn page_up(&mut self) {
+        let vp = self.preview_viewport.max(1);
+        self.preview_cursor = self.preview_cursor.saturating_sub(vp);
+        self.preview_scroll = self.preview_scroll.saturating_sub(vp);
+        self.scroll_cursor_into_view();
+    }
+
+    /// Move the preview cursor and scroll down by one screen, stopping with
+    /// the last line at the bottom
+    pub fn page_down(&mut self) {
+        let vp = self.preview_viewport.max(1);
+        let last = self.preview_line_count().saturating_sub(1);
+        self.preview_cursor = (self.preview_cursor + vp).min(last);
+        self.preview_scroll = (self.preview_scroll + vp).min(self.max_preview_scroll());
+        self.scroll_cursor_into_view();
+    }
+
+    /// Jump to the first line of the preview (`Ctrl+Home`, `gg`)
+    pub fn move_cursor_top(&mut self) {
+        self.preview_cursor = 0;
+        self.preview_col = 0;
+        self.scroll_cursor_into_view();
+    }
+
+    /// Jump to the last line of the preview (`Ctrl+End`, `G`)
+    pub fn move_cursor_bottom(&mut self) {
+        self.preview_cursor = self.preview_line_count().saturating_sub(1);
+        self.preview_col = 0;
+        self.scroll_cursor_into_view();
+    }
+
+    /// Lines one mouse wheel notch scrolls the preview
+    pub fn scroll_step(&self) -> usize {
+        self.settings.scroll_step()
+    }
+
+    /// Furthest the preview scrolls, with the last line at the bottom
+    fn max_preview_scroll(&self) -> usize {
+        let vp = self.preview_viewport.max(1);
+        self.preview_line_count().saturating_sub(vp)
+    }
+
+    /// Rows the preview has: hex dump rows for binary files, lines otherwise
+    pub fn preview_line_count(&self) -> usize {
+        match self.hex_view() {
+            Some(len) => (len.div_ceil(self.hex_row_bytes() as u64) as usize).max(1),
+            None => self.editor_line_count(),
+        }
+    }
+
+    // --- Hex view ----------------------------------------------------------
+    /// Length of the opened file if the preview shows it as a hex dump
+    pub fn hex_view(&self) -> Option<u64> {
+        self.hex_len.filter(|_| self.opened.is_some())
+    }
+
+    /// Bytes per hex dump row, from `"hex_row_bytes"` in the settings file
+    pub fn hex_row_bytes(&self) -> usize {
+        self.settings.hex_row_bytes()
+    }
+
+    /// Offsets of the first and last selected byte of the hex dump, or of
+    /// the byte under the cursor when nothing is selected
+    ///
+    /// The preview cursor's line is the row and its column the byte within it.
+    pub fn hex_selection(&self) -> Option<(u64, u64)> {
+        let last = self.hex_view()?.checked_sub(1)?;
+        let row = self.hex_row_bytes() as u64;
+        let offset = |(line, col): (usize, usize)| (line as u64 * row + col as u64).min(last);
+        let cursor = (self.preview_cursor, self.preview_col);
+        let (start, end) = self.preview_selection_range().unwrap_or((cursor, cursor));
+        Some((offset(start), offset(end)))
+    }
+
+    /// Hex dump rows for the `rows` lines of the preview from the scroll
+    /// position, with the selection highlighted
+    ///
+    /// The file is only read again once the view scrolls or resizes.
+    pub fn hex_text(&mut self, rows: usize) -> RichText<'static> {
+        let Some(path) = self.opened.clone().filter(|_| self.hex_view().is_some()) else {
+            return RichText::default();
+        };
+        let row_bytes = self.hex_row_bytes();
+        let start = self.preview_scroll as u64 * row_bytes as u64;
+        let len = rows as u64 * row_bytes as u64;
+        let cached = matches!(&self.hex_cache.window, Some((s, l, _)) if (*s, *l) == (start, len));
+        if !cached {
+            match preview::read_hex_window(&path, start, len) {
+                Ok(bytes) => self.hex_cache.window = Some((start, len, bytes)),
+                Err(e) => return RichText::raw(format!("(hex preview failed: {e})")),
+            }
+        }
+        let bytes = self
+            .hex_cache
+            .window
+            .as_ref()
+            .map_or(&[][..], |(_, _, bytes)| bytes.as_slice());
+        RichText::from(preview::hex_lines(
+            bytes,
+            start,
+            row_bytes,
+            self.hex_selection(),
+        ))
+    }
+
+    /// The selected bytes in hex and as a little-endian number, for the
+    /// status bar; longer selections only give their length
+    ///
+    /// The bytes are only read again once the selection changes.
+    pub fn hex_status(&mut self) -> Option<String> {
+        let selection = self.hex_selection()?;
+        match &self.hex_cache.status {
+            Some((cached, status)) if *cached == selection => status.clone(),
+            _ => {
+                let status = self.read_hex_status(selection);
+                self.hex_cache.status = Some((selection, status.clone()));
+                status
+            }
+        }
+    }
+
+    fn read_hex_status(&self, (start, end): (u64, u64)) -> Option<String> {
+        let count = end - start + 1;
+        if count > HEX_STATUS_MAX_BYTES {
+            return Some(format!("0x{start:x}–0x{end:x}: {count} bytes"));
         }
+        let bytes = preview::read_hex_window(self.opened.as_deref()?, start, count).ok()?;
+        let hex: Vec<String> = bytes.iter().map(|b| format!("{b:02x}")).collect();
+        let value = bytes
+            .iter()
+            .rev()
+            .fold(0u64, |acc, &b| (acc << 8) | u64::from(b));
+        Some(if count == 1 {
+            format!("0x{start:x}: 0x{} = {value}", hex.join(""))
+        } else {
+            format!("0x{start:x}–0x{end:x}: {} = {value} LE", hex.join(" "))
+        })
+    }
+
+    /// Scroll just enough that the cursor line is on screen
+    fn scroll_cursor_into_view(&mut self) {
         let vp = self.preview_viewport.max(1);
         if self.preview_cursor < self.preview_scroll {
             self.preview_scroll = self.preview_cursor;
@@ -1720,6 +3819,12 @@ impl App {
         if self.preview_cursor >= self.preview_scroll.saturating_add(vp) {
             self.preview_scroll = self.preview_cursor.saturating_sub(vp.saturating_sub(1));
         }
+        if self.moves_by_display_line() {
+            // Wrapped lines above the cursor take more than one row each
+            while self.preview_scroll < self.preview_cursor && self.rows_above_cursor() >= vp {
+                self.preview_scroll += 1;
+            }
+        }
     }
 
     // Line operations (simple)
@@ -1737,14 +3842,34 @@ impl App {
     }
 
     // Column and word motions
-    #[allow(dead_code)]
+    /// Smart home: to the first non-whitespace character, or to column 0
+    /// when already there
     pub fn move_col_to_start(&mut self) {
-        self.preview_col = 0;
+        if let Some(line) = self.editor_line(self.preview_cursor) {
+            let indent = line.chars().take_while(|c| c.is_whitespace()).count();
+            self.preview_col = if self.preview_col == indent {
+                0
+            } else {
+                indent
+            };
+        }
     }
-    #[allow(dead_code)]
+    /// Smart end: past the last non-whitespace character, or to the true end
+    /// of the line when already there
     pub fn move_col_to_end(&mut self) {
+        if let Some(len) = self.hex_view() {
+            let row = self.hex_row_bytes() as u64;
+            let left = len.saturating_sub(self.preview_cursor as u64 * row);
+            self.preview_col = left.min(row).saturating_sub(1) as usize;
+            return;
+        }
         if let Some(line) = self.editor_line(self.preview_cursor) {
-            self.preview_col = line.chars().count();
+            let text_end = line.trim_end().chars().count();
+            self.preview_col = if self.preview_col == text_end {
+                line.chars().count()
+            } else {
+                text_end
+            };
         }
     }
     #[allow(dead_code)]
@@ -1806,6 +3931,14 @@ impl App {
     }
 
     pub fn move_col_right(&mut self) {
+        if let Some(len) = self.hex_view() {
+            let row = self.hex_row_bytes();
+            let next = (self.preview_cursor * row + self.preview_col + 1) as u64;
+            if self.preview_col + 1 < row && next < len {
+                self.preview_col += 1;
+            }
+            return;
+        }
         if let Some(line) = self.editor_line(self.preview_cursor) {
             let len = line.chars().count();
             if self.preview_col < len {
@@ -1863,87 +3996,551 @@ impl App {
         }
     }
 
-    // --- Video controls -----------------------------------------------------
-    pub fn start_video(&mut self, path: PathBuf) {
-        self.stop_video();
-        match VideoPlayer::spawn(path.clone()) {
-            Ok(vp) => {
-                self.video_player = Some(vp);
-                self.video_path = Some(path);
-                self.status = "Playing video".into();
+    // --- Heading outline ------------------------------------------------------
+    /// Markdown ATX headings in the buffer as `(level, title, line)`, skipping fenced code.
+    pub fn markdown_headings(&self) -> Vec<(usize, String, usize)> {
+        let mut headings = Vec::new();
+        let mut fence: Option<&str> = None;
+        let lines = self.editor_lines();
+        for (idx, line) in lines.iter().enumerate() {
+            let trimmed = line.trim_start();
+            if let Some(marker) = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m)) {
+                fence = match fence {
+                    Some(open) if open == marker => None,
+                    Some(open) => Some(open),
+                    None => Some(marker),
+                };
+                continue;
             }
-            Err(e) => {
-                self.status = format!("Video error: {e}");
-                self.video_player = None;
-                self.video_path = None;
+            if fence.is_some() || line.starts_with("    ") {
+                continue;
+            }
+            let level = trimmed.chars().take_while(|c| *c == '#').count();
+            if !(1..=6).contains(&level) {
+                continue;
             }
+            let rest = &trimmed[level..];
+            if !rest.is_empty() && !rest.starts_with([' ', '\t']) {
+                continue;
+            }
+            let title = rest.trim().trim_end_matches('#').trim_end().to_string();
+            headings.push((level, title, idx));
         }
+        headings
     }
 
-    pub fn stop_video(&mut self) {
-        if let Some(mut vp) = self.video_player.take() {
-            vp.stop();
+    /// Headings matching the current outline filter (case-insensitive substring).
+    pub fn toc_entries(&self) -> Vec<(usize, String, usize)> {
+        let query = self.toc_filter.lines().join("").to_lowercase();
+        self.markdown_headings()
+            .into_iter()
+            .filter(|(_, title, _)| query.is_empty() || title.to_lowercase().contains(&query))
+            .collect()
+    }
+
+    pub fn begin_toc(&mut self) {
+        if self.opened.is_none() {
+            self.status = "Open a file to list its headings".into();
+            return;
         }
-        self.video_path = None;
+        self.toc_filter = TextArea::default();
+        self.toc_filter.set_placeholder_text("filter headings");
+        // Start on the heading that contains the cursor
+        self.toc_index = self
+            .markdown_headings()
+            .iter()
+            .rposition(|(_, _, line)| *line <= self.preview_cursor)
+            .unwrap_or(0);
+        self.showing_toc = true;
     }
 
-    pub fn toggle_pause_video(&mut self) {
-        if let Some(vp) = &self.video_player {
-            vp.toggle_pause();
+    pub fn cancel_toc(&mut self) {
+        self.showing_toc = false;
+    }
+
+    pub fn toc_up(&mut self) {
+        self.toc_index = self.toc_index.saturating_sub(1);
+    }
+
+    pub fn toc_down(&mut self) {
+        if self.toc_index + 1 < self.toc_entries().len() {
+            self.toc_index += 1;
         }
     }
 
-    /// Pause video playback (called when tab loses focus)
-    pub fn pause_video(&mut self) {
-        // Stop video when losing focus to avoid background playback
-        self.stop_video();
+    /// Called after the filter text changes so the selection stays in range.
+    pub fn toc_filter_changed(&mut self) {
+        self.toc_index = 0;
     }
 
-    /// Check if the app wants to quit
-    pub fn wants_quit(&self) -> bool {
-        false // Quit is handled by parent application
+    pub fn toc_activate(&mut self) {
+        let entries = self.toc_entries();
+        if let Some((_, title, line)) = entries.get(self.toc_index) {
+            self.preview_cursor = *line;
+            self.preview_col = 0;
+            self.preview_scroll = *line;
+            self.preview_selection_anchor = None;
+            if self.show_raw_editor {
+                self.editor.set_cursor(*line, 0);
+            }
+            self.status = format!("Jumped to \"{title}\" (line {})", line + 1);
+        }
+        self.showing_toc = false;
     }
-}
 
-#[derive(Debug)]
-pub struct VideoPlayer {
-    child: Child,
-    last_frame: Arc<Mutex<Option<image::DynamicImage>>>,
-    stop_flag: Arc<AtomicBool>,
-    paused: Arc<AtomicBool>,
-    handle: Option<std::thread::JoinHandle<()>>,
-}
+    // --- Workspace search -----------------------------------------------------
+    pub fn begin_search(&mut self) {
+        self.search_input
+            .set_placeholder_text("search files (/regex/ for a pattern)");
+        self.showing_search = true;
+    }
 
-impl VideoPlayer {
-    pub fn spawn(path: PathBuf) -> anyhow::Result<Self> {
-        let mut child = Command::new("ffmpeg")
-            .arg("-hide_banner")
-            .arg("-loglevel")
-            .arg("error")
-            .arg("-i")
-            .arg(path)
-            .arg("-f")
-            .arg("image2pipe")
-            .arg("-vcodec")
-            .arg("mjpeg")
-            .arg("-")
-            .stdout(Stdio::piped())
+    /// Close the overlay, stopping a search that is still running
+    pub fn cancel_search(&mut self) {
+        self.showing_search = false;
+        self.search_loader = None;
+    }
+
+    pub fn search_up(&mut self) {
+        self.search_index = self.search_index.saturating_sub(1);
+    }
+
+    pub fn search_down(&mut self) {
+        if self.search_index + 1 < self.search_results.len() {
+            self.search_index += 1;
+        }
+    }
+
+    /// Enter runs a new query, or opens the selected match once it has run
+    pub fn search_activate(&mut self) -> Result<()> {
+        let query = self.search_input.lines().join("");
+        if query.trim().is_empty() {
+            return Ok(());
+        }
+        if self.search_query.as_deref() == Some(query.as_str()) && !self.search_results.is_empty() {
+            return self.open_search_result();
+        }
+        self.run_search(query);
+        Ok(())
+    }
+
+    fn run_search(&mut self, query: String) {
+        let pattern = match search::compile_query(&query) {
+            Ok(pattern) => pattern,
+            Err(e) => {
+                self.status = format!("Invalid pattern: {e}");
+                return;
+            }
+        };
+        let options = SearchOptions {
+            respect_gitignore: self.respect_gitignore,
+            ignore_patterns: self.ignore_patterns.clone(),
+            max_file_size: self.search_max_file_size,
+        };
+        self.search_results.clear();
+        self.search_index = 0;
+        self.search_loader = Some(search::spawn_search(self.root.clone(), pattern, options));
+        self.status = format!("Searching for {query}...");
+        self.search_query = Some(query);
+    }
+
+    fn open_search_result(&mut self) -> Result<()> {
+        let Some(hit) = self.search_results.get(self.search_index).cloned() else {
+            return Ok(());
+        };
+        self.showing_search = false;
+        if let Some(ids) = self.reveal_ids(&hit.path) {
+            reveal_in_tree(&mut self.left_state, &ids);
+        }
+        self.open_file(hit.path.clone())?;
+        self.preview_cursor = hit.line;
+        self.preview_col = 0;
+        self.preview_scroll = hit.line;
+        self.preview_selection_anchor = None;
+        if self.show_raw_editor {
+            self.editor.set_cursor(hit.line, 0);
+        }
+        self.status = format!("{}:{}", hit.path.display(), hit.line + 1);
+        Ok(())
+    }
+
+    // --- Fuzzy file finder ----------------------------------------------------
+    /// Open the finder and index the workspace again in the background
+    ///
+    /// The previous index answers queries until the new one arrives, so
+    /// reopening the finder in a large tree is instant.
+    pub fn begin_finder(&mut self) {
+        self.finder_input = TextArea::default();
+        self.finder_input.set_placeholder_text("find file by name");
+        self.finder_loader = Some(finder::spawn_file_index(
+            self.root.clone(),
+            self.respect_gitignore,
+            self.ignore_patterns.clone(),
+            self.show_hidden,
+        ));
+        self.finder_ranker = Some(finder::Ranker::spawn());
+        self.finder_changed();
+        self.showing_finder = true;
+    }
+
+    /// Close the overlay, stopping an index that is still being built
+    pub fn cancel_finder(&mut self) {
+        self.showing_finder = false;
+        self.finder_loader = None;
+        self.finder_ranker = None;
+    }
+
+    pub fn finder_up(&mut self) {
+        self.finder_index = self.finder_index.saturating_sub(1);
+    }
+
+    pub fn finder_down(&mut self) {
+        if self.finder_index + 1 < self.finder_results.len() {
+            self.finder_index += 1;
+        }
+    }
+
+    /// Rank the index against the query again after it or the index changed
+    ///
+    /// The matches replace the results once the ranker sends them back.
+    pub fn finder_changed(&mut self) {
+        let query = self.finder_input.lines().join("");
+        if let Some(ranker) = self.finder_ranker.as_mut() {
+            ranker.send(query, Arc::clone(&self.finder_files));
+        }
+    }
+
+    /// Open the selected match and reveal it in the tree
+    pub fn finder_activate(&mut self) -> Result<()> {
+        let Some(found) = self.finder_results.get(self.finder_index) else {
+            return Ok(());
+        };
+        let path = self.root.join(&found.path);
+        self.cancel_finder();
+        if let Some(ids) = self.reveal_ids(&path) {
+            reveal_in_tree(&mut self.left_state, &ids);
+        }
+        self.open_file(path)
+    }
+
+    // --- Clipboard ------------------------------------------------------------
+    /// Start or clear a visual selection anchored at the preview cursor.
+    pub fn toggle_preview_selection(&mut self) {
+        if self.preview_selection_anchor.take().is_some() {
+            self.status = "Selection cleared".into();
+        } else {
+            self.preview_selection_anchor = Some((self.preview_cursor, self.preview_col));
+            self.status = "Selection started (move to extend, Ctrl+C copy, Esc clear)".into();
+        }
+    }
+
+    pub fn clear_preview_selection(&mut self) {
+        self.preview_selection_anchor = None;
+    }
+
+    /// Ordered (start, end) of the preview selection, both ends inclusive.
+    pub fn preview_selection_range(&self) -> Option<((usize, usize), (usize, usize))> {
+        let anchor = self.preview_selection_anchor?;
+        let cursor = (self.preview_cursor, self.preview_col);
+        Some(if anchor <= cursor {
+            (anchor, cursor)
+        } else {
+            (cursor, anchor)
+        })
+    }
+
+    fn selected_text(&self) -> Option<String> {
+        if self.show_raw_editor || matches!(self.focus, Focus::Editor) {
+            return self.editor.selected_text();
+        }
+        let (start, end) = self.preview_selection_range()?;
+        Some(extract_selection(&self.editor_lines(), start, end))
+    }
+
+    /// Copy the active selection (or the current line when nothing is selected)
+    /// to the system clipboard, falling back to the internal register.
+    pub fn copy_selection(&mut self) {
+        let (text, what) = match self.selected_text() {
+            Some(text) => (text, "selection"),
+            None => {
+                let row = if self.show_raw_editor || matches!(self.focus, Focus::Editor) {
+                    self.editor.cursor().0
+                } else {
+                    self.preview_cursor
+                };
+                (self.editor_line(row).unwrap_or_default(), "line")
+            }
+        };
+        let chars = text.chars().count();
+        self.clipboard_register = Some(text.clone());
+        self.preview_selection_anchor = None;
+        match arboard::Clipboard::new().and_then(|mut cb| cb.set_text(text)) {
+            Ok(()) => {
+                self.status = format!("Copied {what} ({chars} chars)");
+            }
+            Err(e) => {
+                tracing::debug!(error = %e, "system clipboard unavailable");
+                self.status = format!("Clipboard unavailable; copied {what} to internal register");
+            }
+        }
+    }
+
+    /// Paste from the system clipboard (or the internal register) at the cursor.
+    pub fn paste(&mut self) {
+        let text = match arboard::Clipboard::new().and_then(|mut cb| cb.get_text()) {
+            Ok(text) => text,
+            Err(e) => {
+                tracing::debug!(error = %e, "system clipboard unavailable");
+                match self.clipboard_register.clone() {
+                    Some(text) => {
+                        self.status = "Clipboard unavailable; pasting internal register".into();
+                        text
+                    }
+                    None => {
+                        self.status = "Clipboard unavailable and register is empty".into();
+                        return;
+                    }
+                }
+            }
+        };
+        if text.is_empty() {
+            self.status = "Nothing to paste".into();
+            return;
+        }
+        self.insert_pasted(&text);
+    }
+
+    /// Insert pasted text at the editor or preview cursor in one edit
+    pub fn insert_pasted(&mut self, text: &str) {
+        let text = text.replace("\r\n", "\n").replace('\r', "\n");
+        if self.show_raw_editor || matches!(self.focus, Focus::Editor) {
+            self.editor.insert_text(&text);
+        } else {
+            self.paste_into_preview(&text);
+        }
+        self.status = format!("Pasted {} chars", text.chars().count());
+    }
+
+    fn paste_into_preview(&mut self, text: &str) {
+        let mut lines = self.editor_lines();
+        if lines.is_empty() {
+            lines.push(String::new());
+        }
+        let row = self.preview_cursor.min(lines.len() - 1);
+        self.push_undo(&lines);
+        let line = &lines[row];
+        let split = line
+            .char_indices()
+            .nth(self.preview_col)
+            .map(|(i, _)| i)
+            .unwrap_or(line.len());
+        let tail = line[split..].to_string();
+        let mut pasted: Vec<String> = text.split('\n').map(str::to_string).collect();
+        let last_len = pasted.last().map(|l| l.chars().count()).unwrap_or(0);
+        let added_rows = pasted.len() - 1;
+        pasted[0] = format!("{}{}", &line[..split], pasted[0]);
+        let new_col = if added_rows == 0 {
+            self.preview_col + last_len
+        } else {
+            last_len
+        };
+        if let Some(last) = pasted.last_mut() {
+            last.push_str(&tail);
+        }
+        lines.splice(row..=row, pasted);
+        self.set_editor_lines(lines);
+        self.preview_cursor = row + added_rows;
+        self.preview_col = new_col;
+    }
+
+    // --- Video controls -----------------------------------------------------
+    pub fn start_video(&mut self, path: PathBuf) {
+        self.stop_video();
+        if self.ffmpeg_missing {
+            return;
+        }
+        match VideoPlayer::spawn_with_fps(path.clone(), self.video_fps) {
+            Ok(vp) => {
+                self.video_player = Some(vp);
+                self.video_path = Some(path);
+                self.status = "Playing video".into();
+            }
+            Err(e) => {
+                let not_found = e
+                    .downcast_ref::<io::Error>()
+                    .is_some_and(|io| io.kind() == io::ErrorKind::NotFound);
+                if not_found {
+                    self.ffmpeg_missing = true;
+                    self.status =
+                        "Video playback needs ffmpeg on PATH; install it to enable video".into();
+                } else {
+                    self.status = format!("Video error: {e}");
+                }
+                self.video_player = None;
+                self.video_path = None;
+            }
+        }
+    }
+
+    /// Skip the playing video by `seconds` (negative seeks backwards).
+    pub fn seek_video(&mut self, seconds: i64) {
+        if let Some(vp) = &self.video_player {
+            match vp.seek(seconds) {
+                Ok(()) => {
+                    self.status = format!("Video at {}", format_timestamp(vp.position()));
+                }
+                Err(e) => {
+                    self.status = format!("Seek failed: {e}");
+                }
+            }
+        }
+    }
+
+    pub fn stop_video(&mut self) {
+        if let Some(mut vp) = self.video_player.take() {
+            vp.stop();
+        }
+        self.video_path = None;
+    }
+
+    pub fn toggle_pause_video(&mut self) {
+        if let Some(vp) = &self.video_player {
+            vp.toggle_pause();
+        }
+    }
+
+    /// Pause video playback (called when tab loses focus)
+    pub fn pause_video(&mut self) {
+        // Stop video when losing focus to avoid background playback
+        self.stop_video();
+    }
+
+    /// Called by the standalone run loop once the terminal is set up
+    ///
+    /// From here on, launching a terminal editor suspends the TUI.
+    pub fn on_start(&mut self) {
+        self.suspend_for_editor = true;
+    }
+
+    /// Called by the standalone run loop before the terminal is restored
+    ///
+    /// Stops playback and background work and saves the pane layout.
+    pub fn on_exit(&mut self) {
+        self.release_resources();
+        self.save_pane_layout();
+    }
+
+    /// Stop video playback and drop the file watcher (called when the tab closes)
+    ///
+    /// The swap file goes too unless it holds edits that were never saved.
+    pub fn release_resources(&mut self) {
+        // Let a swap write finish so it isn't left behind half done
+        if let Some(rx) = self.autosave_writer.take() {
+            if let Ok((swap, text, result)) = rx.recv() {
+                self.finish_autosave(&swap, text, result);
+            }
+        }
+        if !self.has_unsaved_changes() {
+            self.remove_swap();
+        }
+        self.stop_video();
+        self.file_watcher = None;
+        self.file_change_loader = None;
+        self.file_change_prompt = None;
+        self.search_loader = None;
+        self.finder_loader = None;
+        self.finder_ranker = None;
+    }
+
+    /// Check if the app wants to quit
+    pub fn wants_quit(&self) -> bool {
+        false // Quit is handled by parent application
+    }
+}
+
+/// Wall-clock playback position that can be frozen while paused or ended.
+#[derive(Debug)]
+struct PlaybackClock {
+    offset: f64,
+    started: Option<Instant>,
+}
+
+impl PlaybackClock {
+    fn position(&self) -> f64 {
+        self.offset + self.started.map_or(0.0, |t| t.elapsed().as_secs_f64())
+    }
+
+    fn freeze(&mut self) {
+        self.offset = self.position();
+        self.started = None;
+    }
+
+    fn restart_at(&mut self, offset: f64) {
+        self.offset = offset;
+        self.started = Some(Instant::now());
+    }
+}
+
+/// One running `ffmpeg` process and the thread decoding its MJPEG output.
+#[derive(Debug)]
+struct FfmpegPipe {
+    child: Child,
+    stop_flag: Arc<AtomicBool>,
+    handle: Option<std::thread::JoinHandle<()>>,
+}
+
+impl FfmpegPipe {
+    fn spawn(
+        path: &Path,
+        start: f64,
+        fps: u32,
+        last_frame: Arc<Mutex<Option<image::DynamicImage>>>,
+        finished: Arc<AtomicBool>,
+        clock: Arc<Mutex<PlaybackClock>>,
+    ) -> anyhow::Result<Self> {
+        let mut child = Command::new("ffmpeg")
+            .arg("-hide_banner")
+            .arg("-loglevel")
+            .arg("error")
+            .arg("-ss")
+            .arg(format!("{start:.3}"))
+            // Emit frames at the source's native rate so the clock stays in sync
+            .arg("-re")
+            .arg("-i")
+            .arg(path)
+            // Drop frames in ffmpeg rather than decoding more than we can show
+            .arg("-r")
+            .arg(fps.to_string())
+            .arg("-f")
+            .arg("image2pipe")
+            .arg("-vcodec")
+            .arg("mjpeg")
+            .arg("-")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
             .spawn()
             .context("spawning ffmpeg")?;
         let mut stdout = child.stdout.take().context("ffmpeg stdout")?;
-        let last_frame = Arc::new(Mutex::new(None));
         let stop_flag = Arc::new(AtomicBool::new(false));
-        let paused = Arc::new(AtomicBool::new(false));
-        let lf = Arc::clone(&last_frame);
         let sf = Arc::clone(&stop_flag);
-        let pf = Arc::clone(&paused);
+        let frame_interval = Duration::from_secs_f64(1.0 / f64::from(fps.max(1)));
         let handle = thread::spawn(move || {
             let mut buf: Vec<u8> = Vec::with_capacity(1 << 20);
             let mut chunk = [0u8; 8192];
             let mut frame_start = None;
+            let mut next_frame_at = Instant::now();
             while !sf.load(Ordering::Relaxed) {
                 match stdout.read(&mut chunk) {
-                    Ok(0) => break,
+                    Ok(0) => {
+                        // End of stream that we did not cause ourselves
+                        if !sf.load(Ordering::Relaxed) {
+                            finished.store(true, Ordering::Relaxed);
+                            if let Ok(mut clock) = clock.lock() {
+                                clock.freeze();
+                            }
+                        }
+                        break;
+                    }
                     Ok(n) => {
                         buf.extend_from_slice(&chunk[..n]);
                         // scan for JPEG SOI/EOI markers
@@ -1956,17 +4553,24 @@ impl VideoPlayer {
                                 if let Some(start) = frame_start {
                                     let end = i + 2;
                                     let frame = &buf[start..end];
-                                    if !pf.load(Ordering::Relaxed) {
-                                        if let Ok(img) = image::load_from_memory(frame) {
-                                            if let Ok(mut guard) = lf.lock() {
-                                                *guard = Some(img);
-                                            }
+                                    if let Ok(img) = image::load_from_memory(frame) {
+                                        if let Ok(mut guard) = last_frame.lock() {
+                                            *guard = Some(img);
                                         }
                                     }
                                     // drain consumed bytes
                                     buf.drain(..end);
                                     frame_start = None;
                                     i = 0;
+                                    // Pace decoding to the target rate; the pipe
+                                    // back-pressures ffmpeg while we sleep
+                                    next_frame_at += frame_interval;
+                                    let now = Instant::now();
+                                    if next_frame_at > now {
+                                        thread::sleep(next_frame_at - now);
+                                    } else {
+                                        next_frame_at = now;
+                                    }
                                     continue;
                                 }
                             }
@@ -1981,28 +4585,153 @@ impl VideoPlayer {
         });
         Ok(Self {
             child,
-            last_frame,
             stop_flag,
-            paused,
             handle: Some(handle),
         })
     }
 
+    fn stop(&mut self) {
+        self.stop_flag.store(true, Ordering::Relaxed);
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        if let Some(h) = self.handle.take() {
+            let _ = h.join();
+        }
+    }
+}
+
+/// Plays a video by piping `ffmpeg` MJPEG output into decoded frames.
+///
+/// Pausing stops the decoder entirely; resuming and seeking restart it with an
+/// `-ss` offset taken from the tracked playback position.
+#[derive(Debug)]
+pub struct VideoPlayer {
+    path: PathBuf,
+    pipe: Mutex<Option<FfmpegPipe>>,
+    last_frame: Arc<Mutex<Option<image::DynamicImage>>>,
+    paused: Arc<AtomicBool>,
+    finished: Arc<AtomicBool>,
+    clock: Arc<Mutex<PlaybackClock>>,
+    fps: AtomicU32,
+}
+
+impl VideoPlayer {
+    pub fn spawn(path: PathBuf) -> anyhow::Result<Self> {
+        Self::spawn_with_fps(path, DEFAULT_VIDEO_FPS)
+    }
+
+    /// Start playback decoding at most `fps` frames per second.
+    pub fn spawn_with_fps(path: PathBuf, fps: u32) -> anyhow::Result<Self> {
+        let player = Self {
+            fps: AtomicU32::new(fps.max(1)),
+            path,
+            pipe: Mutex::new(None),
+            last_frame: Arc::new(Mutex::new(None)),
+            paused: Arc::new(AtomicBool::new(false)),
+            finished: Arc::new(AtomicBool::new(false)),
+            clock: Arc::new(Mutex::new(PlaybackClock {
+                offset: 0.0,
+                started: None,
+            })),
+        };
+        player.restart_at(0.0)?;
+        Ok(player)
+    }
+
+    /// Replace the running decoder with one starting at `offset` seconds.
+    fn restart_at(&self, offset: f64) -> anyhow::Result<()> {
+        let mut pipe = self
+            .pipe
+            .lock()
+            .map_err(|_| anyhow!("video pipe lock poisoned"))?;
+        if let Some(mut old) = pipe.take() {
+            old.stop();
+        }
+        self.finished.store(false, Ordering::Relaxed);
+        if let Ok(mut clock) = self.clock.lock() {
+            clock.restart_at(offset);
+        }
+        *pipe = Some(FfmpegPipe::spawn(
+            &self.path,
+            offset,
+            self.fps(),
+            Arc::clone(&self.last_frame),
+            Arc::clone(&self.finished),
+            Arc::clone(&self.clock),
+        )?);
+        Ok(())
+    }
+
+    fn stop_pipe(&self) {
+        if let Ok(mut pipe) = self.pipe.lock() {
+            if let Some(mut old) = pipe.take() {
+                old.stop();
+            }
+        }
+    }
+
     pub fn last_frame(&self) -> Option<image::DynamicImage> {
         self.last_frame.lock().ok().and_then(|g| g.clone())
     }
 
+    /// Current playback position in seconds.
+    pub fn position(&self) -> f64 {
+        self.clock.lock().map(|c| c.position()).unwrap_or(0.0)
+    }
+
+    pub fn fps(&self) -> u32 {
+        self.fps.load(Ordering::Relaxed)
+    }
+
+    /// Change the decode rate, restarting the decoder at the current position.
+    pub fn set_fps(&self, fps: u32) -> anyhow::Result<()> {
+        self.fps.store(fps.max(1), Ordering::Relaxed);
+        if self.is_paused() || self.is_finished() {
+            return Ok(());
+        }
+        self.restart_at(self.position())
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    /// True once ffmpeg reached the end of the stream.
+    pub fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::Relaxed)
+    }
+
+    /// Skip `seconds` forwards (or backwards when negative) from the current position.
+    pub fn seek(&self, seconds: i64) -> anyhow::Result<()> {
+        let target = (self.position() + seconds as f64).max(0.0);
+        if self.is_paused() {
+            self.finished.store(false, Ordering::Relaxed);
+            if let Ok(mut clock) = self.clock.lock() {
+                clock.offset = target;
+                clock.started = None;
+            }
+            return Ok(());
+        }
+        self.restart_at(target)
+    }
+
     pub fn toggle_pause(&self) {
-        self.paused
-            .store(!self.paused.load(Ordering::Relaxed), Ordering::Relaxed);
+        if self.is_paused() {
+            self.paused.store(false, Ordering::Relaxed);
+            if let Err(e) = self.restart_at(self.position()) {
+                tracing::warn!(error = %e, "failed to resume video");
+            }
+        } else {
+            self.paused.store(true, Ordering::Relaxed);
+            self.stop_pipe();
+            if let Ok(mut clock) = self.clock.lock() {
+                clock.freeze();
+            }
+        }
     }
 
     pub fn stop(&mut self) {
-        self.stop_flag.store(true, Ordering::Relaxed);
-        let _ = self.child.kill();
-        if let Some(h) = self.handle.take() {
-            let _ = h.join();
-        }
+        self.stop_pipe();
     }
 }
 
@@ -2012,6 +4741,27 @@ impl Drop for VideoPlayer {
     }
 }
 
+/// Format seconds as `m:ss` (or `h:mm:ss` for long videos).
+pub fn format_timestamp(seconds: f64) -> String {
+    let total = seconds.max(0.0) as u64;
+    let (h, m, s) = (total / 3600, (total / 60) % 60, total % 60);
+    if h > 0 {
+        format!("{h}:{m:02}:{s:02}")
+    } else {
+        format!("{m}:{s:02}")
+    }
+}
+
+/// Column `offset` characters into row `row` of a wrapped line, kept on
+/// that row; `starts` come from [`preview::wrap_starts`] and `len` is the
+/// line's length
+fn column_in_row(starts: &[usize], len: usize, row: usize, offset: usize) -> usize {
+    let end = starts
+        .get(row + 1)
+        .map_or(len, |&next| next.saturating_sub(1));
+    (starts[row] + offset).min(end)
+}
+
 fn placeholder_tree(root: &Path) -> Vec<TreeItem<'static, String>> {
     let display_name = root
         .file_name()
@@ -2023,66 +4773,196 @@ fn placeholder_tree(root: &Path) -> Vec<TreeItem<'static, String>> {
     vec![TreeItem::new_leaf(root.display().to_string(), text)]
 }
 
-fn spawn_tree_loader(root: PathBuf) -> Receiver<Result<Vec<TreeItem<'static, String>>>> {
+fn spawn_tree_loader(
+    root: PathBuf,
+    rules: IgnoreRules,
+    follow_symlinks: bool,
+    display: TreeDisplay,
+    levels: TreeLevels,
+) -> Receiver<Result<Vec<TreeItem<'static, String>>>> {
     let (tx, rx) = mpsc::channel();
     thread::spawn(move || {
-        let result = build_tree(&root);
+        let result = build_tree(&root, &rules, follow_symlinks, display, levels);
         let _ = tx.send(result);
     });
     rx
 }
 
-fn spawn_git_status_loader(root: PathBuf) -> Receiver<Result<HashMap<PathBuf, FileStatus>>> {
+/// Read the status map and the branch label on a background thread
+fn spawn_git_status_loader(
+    root: PathBuf,
+) -> Receiver<Result<(HashMap<PathBuf, FileStatus>, Option<String>)>> {
     let (tx, rx) = mpsc::channel();
     thread::spawn(move || {
         let result = GitRepository::open(&root)
             .map_err(|e| anyhow!(e))
-            .and_then(|repo| repo.status().map_err(|e| anyhow!(e)));
+            .and_then(|repo| {
+                let status = repo.status().map_err(|e| anyhow!(e))?;
+                Ok((status, repo.branch_label().ok()))
+            });
         let _ = tx.send(result);
     });
     rx
 }
 
-// --- Tree helpers -----------------------------------------------------------
+/// Watch the parent directory of `path` (editors often save by rename) and emit one
+/// message per debounced burst of changes to that file.
+fn spawn_file_watcher(path: PathBuf) -> Result<(notify::RecommendedWatcher, Receiver<()>)> {
+    use notify::{EventKind, RecursiveMode, Watcher};
+
+    let dir = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."));
+    let file_name = path.file_name().map(|n| n.to_os_string());
+    let (raw_tx, raw_rx) = mpsc::channel::<()>();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        if let Ok(event) = res {
+            let relevant = matches!(
+                event.kind,
+                EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+            ) && event
+                .paths
+                .iter()
+                .any(|p| p.file_name() == file_name.as_deref());
+            if relevant {
+                let _ = raw_tx.send(());
+            }
+        }
+    })?;
+    watcher.watch(&dir, RecursiveMode::NonRecursive)?;
 
-/// Fast tree building that reuses existing tree structure and only updates text
-fn build_tree_with_selection_cached(
-    _root: &Path,
-    selection: &HashSet<String>,
-    existing_tree: &[TreeItem<String>],
-) -> Result<Vec<TreeItem<'static, String>>> {
-    use ratatui::style::{Color, Stylize};
-    use ratatui::text::Line;
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        while raw_rx.recv().is_ok() {
+            while raw_rx.recv_timeout(FILE_WATCH_DEBOUNCE).is_ok() {}
+            if tx.send(()).is_err() {
+                break;
+            }
+        }
+    });
+    Ok((watcher, rx))
+}
+
+// --- Tree helpers -----------------------------------------------------------
+
+/// Tree identifiers from a top-level item down to the deepest item that is
+/// `target` or one of its ancestors, one per level
+fn tree_path_toward(items: &[TreeItem<String>], target: &Path) -> Option<Vec<String>> {
+    let item = items
+        .iter()
+        .find(|item| target.starts_with(Path::new(item.identifier())))?;
+    let mut ids = vec![item.identifier().clone()];
+    if let Some(rest) = tree_path_toward(item.children(), target) {
+        ids.extend(rest);
+    }
+    Some(ids)
+}
+
+/// The item at the end of `ids`, one identifier per level from the top
+fn tree_node_mut<'a>(
+    items: &'a mut [TreeItem<'static, String>],
+    ids: &[String],
+) -> Option<&'a mut TreeItem<'static, String>> {
+    let (first, rest) = ids.split_first()?;
+    let mut node = items.iter_mut().find(|item| item.identifier() == first)?;
+    for id in rest {
+        let index = node
+            .children()
+            .iter()
+            .position(|child| child.identifier() == id)?;
+        node = node.child_mut(index)?;
+    }
+    Some(node)
+}
+
+/// How a tree build orders, labels and filters entries
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct TreeDisplay {
+    icons: IconSet,
+    sort: TreeSort,
+    show_hidden: bool,
+}
+
+/// Levels of directories a tree build reads
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TreeLevels {
+    /// Levels read now; directories below them get an unloaded placeholder
+    eager: usize,
+    /// Levels that may be read at all, `None` for no limit
+    max: Option<usize>,
+}
+
+impl TreeLevels {
+    fn new(eager: usize, max: Option<usize>) -> Self {
+        Self { eager, max }
+    }
+
+    /// Levels left for the entries of a directory read with these
+    fn below(self) -> Self {
+        Self::new(
+            self.eager.saturating_sub(1),
+            self.max.map(|max| max.saturating_sub(1)),
+        )
+    }
+
+    /// Whether the directory is read now
+    fn reads(self) -> bool {
+        self.eager > 0 && self.max != Some(0)
+    }
+
+    /// Whether the directory can be expanded, now or later
+    fn expandable(self) -> bool {
+        self.max != Some(0)
+    }
+}
+
+/// Child standing in for the entries of `dir` until it is expanded
+fn unloaded_placeholder(dir: &Path) -> TreeItem<'static, String> {
+    TreeItem::new_leaf(
+        format!("{}{UNLOADED_SUFFIX}", dir.display()),
+        RichText::from(Line::from("loading…".dark_gray())),
+    )
+}
+
+/// Whether `item` is a directory whose entries have not been read yet
+fn is_unloaded(item: &TreeItem<String>) -> bool {
+    matches!(item.children(), [only] if only.identifier().ends_with(UNLOADED_SUFFIX))
+}
 
+/// Opens every ancestor along `ids` and selects the last one
+fn reveal_in_tree(state: &mut TreeState<String>, ids: &[String]) {
+    for depth in 1..ids.len() {
+        state.open(ids[..depth].to_vec());
+    }
+    state.select(ids.to_vec());
+}
+
+/// Fast tree building that reuses existing tree structure and only updates text
+fn build_tree_with_selection_cached(
+    _root: &Path,
+    selection: &HashSet<String>,
+    icons: IconSet,
+    existing_tree: &[TreeItem<String>],
+) -> Result<Vec<TreeItem<'static, String>>> {
     fn update_node_cached(
         item: &TreeItem<String>,
         selection: &HashSet<String>,
+        icons: IconSet,
     ) -> TreeItem<'static, String> {
         let path_str = item.identifier();
+        if path_str.ends_with(UNLOADED_SUFFIX) {
+            return unloaded_placeholder(Path::new(path_str.trim_end_matches(UNLOADED_SUFFIX)));
+        }
         let path = Path::new(path_str);
-
-        // Create new display text based on selection state
-        let new_text = if let Some(filename) = path.file_name() {
-            let filename_str = filename.to_string_lossy().to_string();
-            if selection.contains(path_str) {
-                // Add checkmark for selected items
-                Line::from(vec![
-                    "✓ ".fg(Color::Green).bold(),
-                    filename_str.fg(Color::Yellow).bold(),
-                ])
-            } else {
-                Line::from(filename_str)
-            }
-        } else {
-            Line::from(path_str.to_string())
-        };
-        let text = RichText::from(new_text.clone());
+        let text = entry_label(path, selection.contains(path_str), icons);
 
         // Recursively update children
         let updated_children: Vec<TreeItem<'static, String>> = item
             .children()
             .iter()
-            .map(|child| update_node_cached(child, selection))
+            .map(|child| update_node_cached(child, selection, icons))
             .collect();
 
         // Create new TreeItem with updated text and children
@@ -2097,122 +4977,202 @@ fn build_tree_with_selection_cached(
     // Update all nodes in the existing tree
     let updated_tree: Vec<TreeItem<'static, String>> = existing_tree
         .iter()
-        .map(|item| update_node_cached(item, selection))
+        .map(|item| update_node_cached(item, selection, icons))
         .collect();
 
     Ok(updated_tree)
 }
 
-fn build_tree(root: &Path) -> Result<Vec<TreeItem<'static, String>>> {
-    fn build_node(dir: &Path) -> TreeItem<'static, String> {
-        let mut children: Vec<TreeItem<'static, String>> = std::fs::read_dir(dir)
-            .ok()
-            .into_iter()
-            .flatten()
-            .filter_map(|e| e.ok())
-            .filter(|e| !e.file_name().to_string_lossy().starts_with('.'))
-            .map(|e| {
-                let p = e.path();
-                if p.is_dir() {
-                    build_node(&p)
-                } else {
-                    let text = Line::from(e.file_name().to_string_lossy().to_string());
-                    TreeItem::new_leaf(p.display().to_string(), RichText::from(text))
-                }
-            })
-            .collect();
-        children.sort_by_key(|item| item.identifier().clone());
-        let text = Line::from(
-            dir.file_name()
-                .map(|s| s.to_string_lossy().to_string())
-                .unwrap_or_else(|| dir.display().to_string()),
-        );
-        let text = RichText::from(text);
-        TreeItem::new(dir.display().to_string(), text.clone(), children)
-            .unwrap_or_else(|_| TreeItem::new_leaf(dir.display().to_string(), text))
+/// Label of a tree entry: a checkmark when selected, its icon and its name,
+/// dimmed for dotfiles
+fn entry_label(path: &Path, selected: bool, icons: IconSet) -> RichText<'static> {
+    let name = path
+        .file_name()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string());
+    let kind = FileKind::of(path);
+    let icon = format!("{} ", icons.icon(kind));
+    let icon = if kind == FileKind::Directory {
+        icon.fg(Color::Blue)
+    } else {
+        icon.into()
+    };
+    let mut line = if selected {
+        Line::from(vec![
+            "✓ ".fg(Color::Green).bold(),
+            icon,
+            name.fg(Color::Yellow).bold(),
+        ])
+    } else {
+        Line::from(vec![icon, name.into()])
+    };
+    if is_hidden(path) {
+        line = line.dim();
+    }
+    RichText::from(with_link_marker(line, path))
+}
+
+/// Appends ` → target` to the label of a symbolic link
+fn with_link_marker(mut line: Line<'static>, path: &Path) -> Line<'static> {
+    if is_symlink(path) {
+        let target = std::fs::read_link(path)
+            .map(|t| t.display().to_string())
+            .unwrap_or_else(|_| "?".into());
+        line.push_span(format!(" → {target}").fg(Color::Cyan));
+    }
+    line
+}
+
+/// Canonical paths of the directories on the branch being built, tracked only
+/// while following symlinks since nothing else can form a cycle
+fn push_ancestor(ancestors: &mut Vec<PathBuf>, dir: &Path, follow_symlinks: bool) {
+    if follow_symlinks {
+        ancestors.push(dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf()));
     }
+}
 
-    let root_item = build_node(root);
+fn build_tree(
+    root: &Path,
+    rules: &IgnoreRules,
+    follow_symlinks: bool,
+    display: TreeDisplay,
+    levels: TreeLevels,
+) -> Result<Vec<TreeItem<'static, String>>> {
+    let root_item = build_node(
+        root,
+        &HashSet::new(),
+        display,
+        rules,
+        follow_symlinks,
+        &mut Vec::new(),
+        levels,
+    );
     Ok(vec![root_item])
 }
 
 fn build_tree_with_selection(
     root: &Path,
     selection: &HashSet<String>,
+    display: TreeDisplay,
+    rules: &IgnoreRules,
+    follow_symlinks: bool,
+    levels: TreeLevels,
 ) -> Result<Vec<TreeItem<'static, String>>> {
-    fn build_node(dir: &Path, selection: &HashSet<String>) -> TreeItem<'static, String> {
+    let root_item = build_node(
+        root,
+        selection,
+        display,
+        rules,
+        follow_symlinks,
+        &mut Vec::new(),
+        levels,
+    );
+    Ok(vec![root_item])
+}
+
+/// Tree item for `dir`, with its entries read `levels` deep
+///
+/// Directories past `levels.eager` get an [`unloaded_placeholder`] child, and
+/// ones past `levels.max` none. Entries in `selection` get a checkmark.
+fn build_node(
+    dir: &Path,
+    selection: &HashSet<String>,
+    display: TreeDisplay,
+    rules: &IgnoreRules,
+    follow_symlinks: bool,
+    ancestors: &mut Vec<PathBuf>,
+    levels: TreeLevels,
+) -> TreeItem<'static, String> {
+    let children: Vec<TreeItem<'static, String>> = if levels.reads() {
+        let rules = rules.descend(dir);
+        push_ancestor(ancestors, dir, follow_symlinks);
         let mut children: Vec<TreeItem<'static, String>> = std::fs::read_dir(dir)
             .ok()
             .into_iter()
             .flatten()
             .filter_map(|e| e.ok())
-            .filter(|e| !e.file_name().to_string_lossy().starts_with('.'))
+            .filter(|e| display.show_hidden || !is_hidden(&e.path()))
+            .filter(|e| !is_trash(&e.path()))
+            .filter(|e| !rules.is_ignored(&e.path(), e.path().is_dir()))
             .map(|e| {
                 let p = e.path();
-                if p.is_dir() {
-                    build_node(&p, selection)
+                if should_descend(&p, follow_symlinks, ancestors) {
+                    build_node(
+                        &p,
+                        selection,
+                        display,
+                        &rules,
+                        follow_symlinks,
+                        ancestors,
+                        levels.below(),
+                    )
                 } else {
                     let path_str = p.display().to_string();
-                    let filename = e.file_name().to_string_lossy().to_string();
-                    let text = if selection.contains(&path_str) {
-                        // Add checkmark for selected items
-                        Line::from(vec![
-                            "✓ ".fg(Color::Green).bold(),
-                            filename.fg(Color::Yellow).bold(),
-                        ])
-                    } else {
-                        Line::from(filename)
-                    };
-                    TreeItem::new_leaf(path_str, RichText::from(text))
+                    let text = entry_label(&p, selection.contains(&path_str), display.icons);
+                    TreeItem::new_leaf(path_str, text)
                 }
             })
             .collect();
-        children.sort_by_key(|item| item.identifier().clone());
-
-        let path_str = dir.display().to_string();
-        let dir_name = dir
-            .file_name()
-            .map(|s| s.to_string_lossy().to_string())
-            .unwrap_or_else(|| dir.display().to_string());
-
-        let text_line = if selection.contains(&path_str) {
-            // Add checkmark for selected directories
-            Line::from(vec![
-                "✓ ".fg(Color::Green).bold(),
-                "📁 ".fg(Color::Blue),
-                dir_name.fg(Color::Yellow).bold(),
-            ])
-        } else {
-            Line::from(vec!["📁 ".fg(Color::Blue), dir_name.into()])
-        };
-        let text = RichText::from(text_line);
-        TreeItem::new(path_str.clone(), text.clone(), children)
-            .unwrap_or_else(|_| TreeItem::new_leaf(path_str, text))
-    }
+        if follow_symlinks {
+            ancestors.pop();
+        }
+        display
+            .sort
+            .sort_by_path(&mut children, |item| Path::new(item.identifier()));
+        children
+    } else if levels.expandable() {
+        vec![unloaded_placeholder(dir)]
+    } else {
+        Vec::new()
+    };
+
+    let path_str = dir.display().to_string();
+    let text = entry_label(dir, selection.contains(&path_str), display.icons);
+    TreeItem::new(path_str.clone(), text.clone(), children)
+        .unwrap_or_else(|_| TreeItem::new_leaf(path_str, text))
+}
 
-    let root_item = build_node(root, selection);
-    Ok(vec![root_item])
+/// Existing files that copying `src` to `dest` would replace, counted per
+/// file so copying a directory over another only conflicts where they overlap
+fn copy_conflicts(src: &Path, dest: &Path) -> usize {
+    if src.is_dir() {
+        std::fs::read_dir(src)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| copy_conflicts(&entry.path(), &dest.join(entry.file_name())))
+            .sum()
+    } else {
+        usize::from(dest.symlink_metadata().is_ok())
+    }
 }
 
-fn copy_dir_all(src: &Path, dst: &Path) -> io::Result<()> {
-    if !dst.exists() {
-        std::fs::create_dir_all(dst)?;
-    }
-    for entry in std::fs::read_dir(src)? {
-        let entry = entry?;
-        let ty = entry.file_type()?;
-        let from = entry.path();
-        let to = dst.join(entry.file_name());
-        if ty.is_dir() {
-            copy_dir_all(&from, &to)?;
-        } else {
-            if let Some(parent) = to.parent() {
-                std::fs::create_dir_all(parent)?;
-            }
-            std::fs::copy(&from, &to)?;
+/// `path` with ` (1)`, ` (2)`, … added to its name until nothing is there
+///
+/// The number goes before a file's extension, and after the whole name of
+/// a directory, so `v1.2` becomes `v1.2 (1)`.
+fn unique_path(path: &Path) -> PathBuf {
+    let (stem, ext) = if path.is_dir() {
+        (path.file_name(), None)
+    } else {
+        (path.file_stem(), path.extension())
+    };
+    let stem = stem
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let ext = ext.map(|e| e.to_string_lossy().into_owned());
+    let mut n = 1;
+    loop {
+        let name = match &ext {
+            Some(ext) => format!("{stem} ({n}).{ext}"),
+            None => format!("{stem} ({n})"),
+        };
+        let candidate = path.with_file_name(name);
+        if candidate.symlink_metadata().is_err() {
+            return candidate;
         }
+        n += 1;
     }
-    Ok(())
 }
 
 #[cfg(test)]
@@ -2220,10 +5180,15 @@ mod tests {
     use super::*;
     use tempfile::tempdir;
 
+    const ALL_LEVELS: TreeLevels = TreeLevels {
+        eager: usize::MAX,
+        max: None,
+    };
+
     #[test]
     fn delete_candidate_rejects_workspace_root() {
         let root = tempdir().expect("tempdir");
-        let app = App::new(root.path().to_path_buf()).expect("app");
+        let app = App::with_settings(root.path().to_path_buf(), Settings::default()).expect("app");
 
         let err = app
             .validate_delete_candidate(root.path())
@@ -2238,7 +5203,8 @@ mod tests {
         std::fs::create_dir(&nested_dir).expect("create dir");
         std::fs::write(nested_dir.join("temp.txt"), "data").expect("write file");
 
-        let mut app = App::new(root.path().to_path_buf()).expect("app");
+        let mut app =
+            App::with_settings(root.path().to_path_buf(), Settings::default()).expect("app");
         app.delete_target = Some(nested_dir.clone());
         app.confirming_delete = true;
 
@@ -2248,6 +5214,188 @@ mod tests {
         assert!(app.status.contains("Deleted directory"));
     }
 
+    #[test]
+    fn deleted_files_go_to_trash_and_undo_restores_them() {
+        let root = tempdir().expect("tempdir");
+        let note = root.path().join("note.md");
+        std::fs::write(&note, "# Keep me").expect("write note");
+        let mut app =
+            App::with_settings(root.path().to_path_buf(), Settings::default()).expect("app");
+        app.delete_target = Some(note.clone());
+        app.confirming_delete = true;
+
+        app.confirm_delete_with_git().expect("delete");
+        assert!(!note.exists());
+        assert_eq!(app.status, "Deleted: note.md (u to undo)");
+        assert!(root.path().join(crate::fs::TRASH_DIR).is_dir());
+
+        app.undo_file_op().expect("undo");
+        assert_eq!(
+            std::fs::read_to_string(&note).expect("restored"),
+            "# Keep me"
+        );
+        assert_eq!(app.status, "Restored note.md");
+
+        app.undo_file_op().expect("nothing left");
+        assert_eq!(app.status, "Nothing to undo");
+    }
+
+    #[test]
+    fn trash_is_hidden_and_can_be_emptied() {
+        let root = tempdir().expect("tempdir");
+        let note = root.path().join("note.md");
+        std::fs::write(&note, "# Gone").expect("write note");
+        std::fs::write(root.path().join(".hidden"), "").expect("write dotfile");
+        let mut app =
+            App::with_settings(root.path().to_path_buf(), Settings::default()).expect("app");
+        app.show_hidden = true;
+        app.delete_target = Some(note.clone());
+        app.confirming_delete = true;
+        app.confirm_delete_with_git().expect("delete");
+        app.refresh_tree().expect("refresh");
+        let trash = root.path().join(crate::fs::TRASH_DIR);
+        assert!(trash.is_dir());
+        let mut ids = Vec::new();
+        tree_ids(&app.left_tree, &mut ids);
+        assert!(ids.iter().any(|id| id.ends_with(".hidden")));
+        assert!(!ids.iter().any(|id| id.contains(crate::fs::TRASH_DIR)));
+
+        app.empty_trash().expect("empty trash");
+        assert!(!trash.exists());
+        assert_eq!(app.status, "Emptied the trash (1 entry)");
+        app.undo_file_op().expect("nothing left");
+        assert_eq!(app.status, "Nothing to undo");
+    }
+
+    #[test]
+    fn undo_delete_restages_tracked_files() {
+        let root = tempdir().expect("tempdir");
+        let repo = git2::Repository::init(root.path()).expect("init repo");
+        std::fs::write(root.path().join("tracked.md"), "# Tracked").expect("write");
+        let mut index = repo.index().expect("index");
+        index.add_path(Path::new("tracked.md")).expect("stage");
+        index.write().expect("write index");
+
+        let mut app =
+            App::with_settings(root.path().to_path_buf(), Settings::default()).expect("app");
+        app.delete_target = Some(root.path().join("tracked.md"));
+        app.confirming_delete = true;
+        app.confirm_delete_with_git().expect("delete");
+        assert_eq!(app.status, "Git removed: tracked.md (u to undo)");
+        let in_index = || {
+            let mut index = repo.index().expect("index");
+            index.read(true).expect("reload index");
+            index.get_path(Path::new("tracked.md"), 0).is_some()
+        };
+        assert!(!in_index());
+
+        app.undo_file_op().expect("undo");
+        assert!(root.path().join("tracked.md").exists());
+        assert!(in_index());
+    }
+
+    #[test]
+    fn bulk_rename_plan_supports_templates_and_find_replace() {
+        let root = tempdir().expect("tempdir");
+        let sources: Vec<PathBuf> = ["a.txt", "b.txt"]
+            .iter()
+            .map(|name| root.path().join(name))
+            .collect();
+        for source in &sources {
+            std::fs::write(source, "").expect("write source");
+        }
+
+        let plan = plan_bulk_rename(&sources, "{name}-{n}.md").expect("template");
+        assert_eq!(
+            plan,
+            vec![
+                (sources[0].clone(), root.path().join("a-1.md")),
+                (sources[1].clone(), root.path().join("b-2.md")),
+            ]
+        );
+
+        let plan = plan_bulk_rename(&sources, "s/a/draft-a/").expect("find/replace");
+        assert_eq!(
+            plan,
+            vec![(sources[0].clone(), root.path().join("draft-a.txt"))]
+        );
+
+        let err = plan_bulk_rename(&sources, "same.md").expect_err("duplicate names");
+        assert!(err.to_string().contains("same.md"));
+        std::fs::write(root.path().join("b.md"), "").expect("write existing");
+        let err = plan_bulk_rename(&sources, "{name}.md").expect_err("existing name");
+        assert_eq!(err.to_string(), "b.md already exists");
+        assert!(plan_bulk_rename(&sources, "{name}/x").is_err());
+        assert!(plan_bulk_rename(&sources, "s/x").is_err());
+    }
+
+    #[test]
+    fn bulk_rename_is_all_or_nothing_and_undoable() {
+        let root = tempdir().expect("tempdir");
+        for name in ["one.txt", "two.txt", "taken.md"] {
+            std::fs::write(root.path().join(name), name).expect("write file");
+        }
+        let mut app =
+            App::with_settings(root.path().to_path_buf(), Settings::default()).expect("app");
+        app.picking_file = true;
+        app.load_picker_dir(root.path().to_path_buf())
+            .expect("picker");
+        for (i, path) in app.picker_items.clone().iter().enumerate() {
+            if path.extension().is_some_and(|ext| ext == "txt") {
+                app.picker_selection.insert(i);
+            }
+        }
+
+        app.picker_begin_bulk_rename();
+        assert!(app.bulk_renaming);
+        app.rename_input.insert_str("s/one.txt/taken.md/");
+        app.confirm_bulk_rename().expect("collision");
+        assert!(app.status.starts_with("Rename aborted"));
+        assert!(app.bulk_renaming);
+
+        app.rename_input = TextArea::default();
+        app.rename_input.insert_str("{name}.md");
+        app.confirm_bulk_rename().expect("rename");
+        assert!(!app.bulk_renaming);
+        assert_eq!(app.status, "Renamed 2 files (u to undo)");
+        assert!(root.path().join("one.md").exists());
+        assert!(root.path().join("two.md").exists());
+        assert!(!root.path().join("one.txt").exists());
+
+        app.undo_file_op().expect("undo");
+        assert!(root.path().join("one.txt").exists());
+        assert!(root.path().join("two.txt").exists());
+        assert!(!root.path().join("one.md").exists());
+    }
+
+    #[test]
+    fn undo_reverses_a_move() {
+        let root = tempdir().expect("tempdir");
+        let source = root.path().join("draft.md");
+        std::fs::write(&source, "draft").expect("write draft");
+        std::fs::create_dir(root.path().join("archive")).expect("create archive");
+        let mut app =
+            App::with_settings(root.path().to_path_buf(), Settings::default()).expect("app");
+        app.move_source = Some(source.clone());
+        app.move_dest_dir = root.path().join("archive");
+
+        app.confirm_move().expect("move");
+        assert!(root.path().join("archive/draft.md").exists());
+        assert!(app.status.ends_with("(u to undo)"));
+
+        std::fs::write(&source, "replacement").expect("reuse name");
+        app.undo_file_op().expect("undo blocked");
+        assert!(app.status.starts_with("Undo failed"));
+
+        std::fs::remove_file(&source).expect("clear name");
+        app.undo_file_op().expect("undo");
+        assert_eq!(
+            std::fs::read_to_string(&source).expect("moved back"),
+            "draft"
+        );
+        assert_eq!(app.status, "Moved back: draft.md");
+    }
+
     #[cfg(unix)]
     #[test]
     fn delete_candidate_rejects_symlink() {
@@ -2259,10 +5407,1259 @@ mod tests {
         std::fs::write(&target, "target").expect("write target");
         symlink(&target, &link).expect("symlink");
 
-        let app = App::new(root.path().to_path_buf()).expect("app");
+        let app = App::with_settings(root.path().to_path_buf(), Settings::default()).expect("app");
         let err = app
             .validate_delete_candidate(&link)
             .expect_err("symlink deletion should be blocked");
         assert!(err.to_string().contains("symlink"));
     }
+
+    fn tree_ids(items: &[TreeItem<'static, String>], out: &mut Vec<String>) {
+        for item in items {
+            out.push(item.identifier().clone());
+            tree_ids(item.children(), out);
+        }
+    }
+
+    #[test]
+    fn build_tree_respects_gitignore_and_extra_patterns() {
+        let root = tempdir().expect("tempdir");
+        let dir = root.path();
+        std::fs::write(dir.join(".gitignore"), "target/\n").expect("write .gitignore");
+        std::fs::create_dir(dir.join("target")).expect("create target");
+        std::fs::write(dir.join("target/out.bin"), "bin").expect("write out.bin");
+        std::fs::create_dir(dir.join("node_modules")).expect("create node_modules");
+        std::fs::write(dir.join("notes.md"), "# Notes").expect("write notes");
+
+        let ids = |rules: &IgnoreRules| {
+            let mut ids = Vec::new();
+            tree_ids(
+                &build_tree(dir, rules, false, TreeDisplay::default(), ALL_LEVELS).expect("tree"),
+                &mut ids,
+            );
+            ids
+        };
+        let contains = |ids: &[String], name: &str| ids.iter().any(|id| id.ends_with(name));
+
+        let hidden = ids(&IgnoreRules::new(dir, true, &[]));
+        assert!(!contains(&hidden, "target"));
+        assert!(contains(&hidden, "node_modules"));
+        assert!(contains(&hidden, "notes.md"));
+
+        let extra = ids(&IgnoreRules::new(dir, true, &["node_modules/".to_string()]));
+        assert!(!contains(&extra, "node_modules"));
+
+        let shown = ids(&IgnoreRules::new(dir, false, &[]));
+        assert!(contains(&shown, "target"));
+        assert!(contains(&shown, "out.bin"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn build_tree_does_not_loop_through_symlinks() {
+        let root = tempdir().expect("tempdir");
+        let dir = root.path();
+        std::fs::create_dir(dir.join("docs")).expect("create docs");
+        std::fs::write(dir.join("docs/a.md"), "# A").expect("write a.md");
+        std::os::unix::fs::symlink(dir, dir.join("docs/loop")).expect("symlink loop");
+        std::os::unix::fs::symlink(dir.join("docs"), dir.join("alias")).expect("symlink alias");
+        let rules = IgnoreRules::new(dir, true, &[]);
+
+        let mut ids = Vec::new();
+        tree_ids(
+            &build_tree(dir, &rules, false, TreeDisplay::default(), ALL_LEVELS).expect("tree"),
+            &mut ids,
+        );
+        let alias = dir.join("alias").display().to_string();
+        assert!(ids.contains(&alias));
+        assert!(!ids.iter().any(|id| id.starts_with(&format!("{alias}/"))));
+
+        let mut followed = Vec::new();
+        tree_ids(
+            &build_tree(dir, &rules, true, TreeDisplay::default(), ALL_LEVELS).expect("tree"),
+            &mut followed,
+        );
+        assert!(followed.contains(&format!("{alias}/a.md")));
+        // Both links lead back to a directory already on the branch
+        let looped = dir.join("docs/loop").display().to_string();
+        assert!(followed.contains(&looped));
+        assert!(!followed
+            .iter()
+            .any(|id| id.starts_with(&format!("{looped}/"))));
+    }
+
+    fn loaded_app(root: &Path) -> App {
+        let mut app = App::with_settings(root.to_path_buf(), Settings::default()).expect("app");
+        app.refresh_tree().expect("refresh tree");
+        app
+    }
+
+    #[test]
+    fn pane_layout_is_remembered_per_root_and_clamped() {
+        let root = tempdir().expect("tempdir");
+        let config = tempdir().expect("config dir");
+        let settings_path = config.path().join("settings.json");
+
+        let mut app =
+            App::with_settings(root.path().to_path_buf(), Settings::default()).expect("app");
+        app.settings = Settings::load_from(Some(settings_path.clone()));
+        app.increase_left_pane_width();
+        app.toggle_left_pane();
+
+        let mut reopened =
+            App::with_settings(root.path().to_path_buf(), Settings::default()).expect("app");
+        reopened.settings = Settings::load_from(Some(settings_path.clone()));
+        reopened.restore_pane_layout();
+        assert_eq!(reopened.left_pane_width, 35);
+        assert!(!reopened.show_left_pane);
+        assert_eq!(reopened.focus, Focus::Preview);
+
+        // Another workspace keeps the defaults
+        let other = tempdir().expect("other root");
+        let mut fresh =
+            App::with_settings(other.path().to_path_buf(), Settings::default()).expect("app");
+        fresh.settings = Settings::load_from(Some(settings_path.clone()));
+        fresh.restore_pane_layout();
+        assert_eq!(fresh.left_pane_width, 30);
+        assert!(fresh.show_left_pane);
+
+        // Widths saved outside the allowed range are clamped on load
+        let mut settings = Settings::load_from(Some(settings_path.clone()));
+        settings
+            .set_layout(
+                root.path(),
+                LayoutPrefs {
+                    left_pane_width: 99,
+                    show_left_pane: true,
+                },
+            )
+            .expect("save settings");
+        reopened.settings = Settings::load_from(Some(settings_path));
+        reopened.restore_pane_layout();
+        assert_eq!(reopened.left_pane_width, reopened.max_pane_width);
+    }
+
+    #[test]
+    fn left_pane_width_handles_wide_terminals() {
+        let root = tempdir().expect("tempdir");
+        let mut app =
+            App::with_settings(root.path().to_path_buf(), Settings::default()).expect("app");
+        app.left_pane_width = 70;
+        assert_eq!(app.calculate_left_pane_width(100), 70);
+        assert_eq!(app.calculate_left_pane_width(1000), 700);
+        assert_eq!(app.calculate_left_pane_width(u16::MAX), 45874);
+        assert_eq!(app.calculate_left_pane_width(1), 1);
+    }
+
+    #[test]
+    fn select_all_is_scoped_to_visible_entries_unless_confirmed() {
+        let root = tempdir().expect("tempdir");
+        std::fs::create_dir(root.path().join("docs")).expect("create docs");
+        std::fs::write(root.path().join("docs/guide.md"), "").expect("write guide");
+        std::fs::write(root.path().join("readme.md"), "").expect("write readme");
+        let mut app = loaded_app(root.path());
+        let id = |name: &str| root.path().join(name).display().to_string();
+        app.left_state.open(vec![root.path().display().to_string()]);
+
+        app.tree_select_all();
+        let expected: HashSet<String> = [id("docs"), id("readme.md")].into();
+        assert_eq!(app.tree_selection, expected);
+        assert!(app.status.starts_with("Selected 2 visible entries"));
+
+        app.begin_select_all_recursive();
+        assert!(app.is_confirming_select_all());
+        assert!(app.status.starts_with("Select all 3 entries"));
+        app.cancel_select_all_recursive();
+        assert_eq!(app.tree_selection, expected);
+
+        app.begin_select_all_recursive();
+        app.confirm_select_all_recursive();
+        assert!(!app.is_confirming_select_all());
+        assert!(app.tree_selection.contains(&id("docs/guide.md")));
+        assert_eq!(app.tree_selection.len(), 3);
+        assert_eq!(app.status, "Selected all 3 entries (Esc clears)");
+    }
+
+    /// Render the file tree and return the rows showing a checkmark
+    fn checked_rows(app: &mut App) -> Vec<String> {
+        let area = Rect::new(0, 0, 40, 10);
+        let mut buf = Buffer::empty(area);
+        let tree = tui_tree_widget::Tree::new(&app.left_tree).expect("tree");
+        StatefulWidget::render(tree, area, &mut buf, &mut app.left_state);
+        (0..area.height)
+            .map(|y| {
+                (0..area.width)
+                    .map(|x| buf[(x, y)].symbol().to_string())
+                    .collect::<String>()
+            })
+            .filter(|row| row.contains('✓'))
+            .map(|row| row.replace('✓', "").trim().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn tree_clicks_select_open_and_drag_entries() {
+        let root = tempdir().expect("tempdir");
+        std::fs::create_dir(root.path().join("docs")).expect("create docs");
+        std::fs::write(root.path().join("readme.md"), "# Readme").expect("write readme");
+        let mut app = loaded_app(root.path());
+        let id = |name: &str| root.path().join(name).display().to_string();
+        app.left_state.open(vec![root.path().display().to_string()]);
+        checked_rows(&mut app);
+        let row_of = |app: &App, name: &str| {
+            (0..10)
+                .find(|&y| app.tree_id_at(2, y).and_then(|id| id.last().cloned()) == Some(id(name)))
+                .expect("entry drawn")
+        };
+        let (docs, readme) = (row_of(&app, "docs"), row_of(&app, "readme.md"));
+        let start = Instant::now();
+        let later = |ms| start + Duration::from_millis(ms);
+
+        // A single click selects, a slow second click does not open
+        app.tree_mouse_down(2, readme, start).expect("click");
+        app.tree_mouse_up();
+        assert_eq!(
+            app.current_selection_path(),
+            Some(root.path().join("readme.md"))
+        );
+        app.tree_mouse_down(2, readme, later(1000)).expect("click");
+        app.tree_mouse_up();
+        assert_eq!(app.opened, None);
+        app.tree_mouse_down(2, readme, later(1100))
+            .expect("double click");
+        app.tree_mouse_up();
+        assert_eq!(app.opened, Some(root.path().join("readme.md")));
+
+        // Moving less than the threshold is still a click
+        app.tree_mouse_down(2, docs, later(5000)).expect("press");
+        assert!(app.tree_mouse_drag(3, docs));
+        assert_eq!(app.op_mode, OpMode::None);
+        assert!(app.tree_mouse_drag(4, docs));
+        assert_eq!(app.op_mode, OpMode::Move);
+        assert_eq!(app.op_source, Some(root.path().join("docs")));
+        app.tree_mouse_up();
+        app.cancel_op();
+
+        // Drags that did not start on an entry are left to the panes
+        assert!(!app.tree_mouse_drag(6, docs));
+        app.tree_mouse_down(2, 40, later(6000)).expect("miss");
+        assert!(!app.tree_mouse_drag(8, 40));
+    }
+
+    #[test]
+    fn tick_interval_is_fast_only_while_work_is_pending() {
+        let root = tempdir().expect("tempdir");
+        std::fs::write(root.path().join("a.md"), "").expect("write file");
+        let mut app = loaded_app(root.path());
+        assert_eq!(
+            app.desired_tick_interval(),
+            Some(BACKGROUND_POLL_INTERVAL),
+            "the startup tree loader is still pending"
+        );
+
+        app.tree_loader = None;
+        assert_eq!(app.desired_tick_interval(), None);
+
+        app.tree_selection
+            .insert(root.path().join("a.md").display().to_string());
+        app.update_tree_selection_display();
+        assert_eq!(
+            app.desired_tick_interval(),
+            Some(TREE_SELECTION_REDRAW_INTERVAL)
+        );
+        app.flush_tree_selection_display();
+        assert_eq!(app.desired_tick_interval(), None);
+    }
+
+    #[test]
+    fn tree_selection_checkmarks_are_rebuilt_once_per_tick() {
+        let root = tempdir().expect("tempdir");
+        for name in ["a.md", "b.md", "c.md", "d.md"] {
+            std::fs::write(root.path().join(name), "").expect("write file");
+        }
+        let mut app = loaded_app(root.path());
+        app.tree_loader = None;
+        app.tree_icons = IconSet::Ascii;
+        app.refresh_tree().expect("refresh tree");
+        let root_id = root.path().display().to_string();
+        let id = |name: &str| root.path().join(name).display().to_string();
+        app.left_state.open(vec![root_id.clone()]);
+        app.left_state.select(vec![root_id.clone(), id("a.md")]);
+        assert!(checked_rows(&mut app).is_empty());
+
+        app.tree_down_with_selection();
+        app.tree_down_with_selection();
+        // Nothing is rebuilt until the next tick
+        assert!(checked_rows(&mut app).is_empty());
+        app.poll_background_tasks();
+        assert_eq!(checked_rows(&mut app), ["# a.md", "# b.md", "# c.md"]);
+
+        // Changes inside the redraw interval wait, but are never lost
+        app.tree_up_with_selection();
+        app.poll_background_tasks();
+        std::thread::sleep(TREE_SELECTION_REDRAW_INTERVAL);
+        app.poll_background_tasks();
+        assert_eq!(checked_rows(&mut app), ["# a.md", "# b.md"]);
+
+        app.tree_clear_selection();
+        app.flush_tree_selection_display();
+        assert!(checked_rows(&mut app).is_empty());
+    }
+
+    #[test]
+    fn go_to_path_expands_ancestors_and_selects() {
+        let root = tempdir().expect("tempdir");
+        let dir = root.path();
+        std::fs::create_dir_all(dir.join("notes/work")).expect("create dirs");
+        std::fs::write(dir.join("notes/work/plan.md"), "# Plan").expect("write plan");
+        let mut app = loaded_app(dir);
+
+        app.go_to_path("notes/work/plan.md").expect("go to path");
+        let plan = dir.join("notes/work/plan.md").display().to_string();
+        assert_eq!(app.left_state.selected().last(), Some(&plan));
+        assert_eq!(app.left_state.selected().len(), 4);
+        let root_name = dir.file_name().unwrap().to_string_lossy().into_owned();
+        assert_eq!(
+            app.breadcrumb(200),
+            format!("{root_name} › notes › work › plan.md")
+        );
+        assert_eq!(app.breadcrumb(20), "… › work › plan.md");
+        assert_eq!(app.breadcrumb(5), "…n.md");
+    }
+
+    #[test]
+    fn go_to_path_reports_invalid_paths() {
+        let root = tempdir().expect("tempdir");
+        let mut app = loaded_app(root.path());
+        let selected = app.left_state.selected().to_vec();
+
+        assert!(app.go_to_path("missing/file.md").is_err());
+        assert!(app.go_to_path("").is_err());
+        assert_eq!(app.left_state.selected(), selected.as_slice());
+
+        app.begin_goto_path();
+        app.goto_input.insert_str("missing");
+        app.confirm_goto_path();
+        assert!(!app.going_to_path);
+        assert_eq!(app.status, "No such path: missing");
+    }
+
+    #[test]
+    fn tree_reads_directories_as_they_are_expanded() {
+        let root = tempdir().expect("tempdir");
+        let dir = root.path();
+        std::fs::create_dir_all(dir.join("a/b")).expect("create dirs");
+        std::fs::write(dir.join("a/b/c.md"), "# C").expect("write c");
+        let mut app = loaded_app(dir);
+        let id = |name: &str| dir.join(name).display().to_string();
+        let ids = |app: &App| {
+            let mut ids = Vec::new();
+            tree_ids(&app.left_tree, &mut ids);
+            ids
+        };
+
+        let shown = ids(&app);
+        assert!(shown.contains(&id("a")));
+        assert!(shown.contains(&format!("{}{UNLOADED_SUFFIX}", id("a"))));
+        assert!(!shown.contains(&id("a/b")));
+
+        app.left_state
+            .open(vec![dir.display().to_string(), id("a")]);
+        app.poll_background_tasks();
+        let shown = ids(&app);
+        assert!(shown.contains(&id("a/b")));
+        assert!(!shown.contains(&id("a/b/c.md")));
+
+        // Jumping to a path reads the directories on the way
+        app.go_to_path("a/b/c.md").expect("go to path");
+        assert!(ids(&app).contains(&id("a/b/c.md")));
+        assert!(!ids(&app).iter().any(|id| id.ends_with(UNLOADED_SUFFIX)));
+    }
+
+    #[test]
+    fn refresh_keeps_expanded_dirs_and_selection() {
+        let root = tempdir().expect("tempdir");
+        let dir = root.path();
+        std::fs::create_dir_all(dir.join("a/b")).expect("create dirs");
+        std::fs::write(dir.join("a/b/x.md"), "# X").expect("write x");
+        std::fs::write(dir.join("a/y.md"), "# Y").expect("write y");
+        let mut app = loaded_app(dir);
+        let id = |name: &str| dir.join(name).display().to_string();
+        let branch = |names: &[&str]| {
+            let mut ids = vec![dir.display().to_string()];
+            ids.extend(names.iter().map(|name| id(name)));
+            ids
+        };
+
+        app.go_to_path("a/b/x.md").expect("go to path");
+        app.refresh_tree().expect("refresh tree");
+        assert!(app
+            .left_state
+            .get_all_opened()
+            .contains(&branch(&["a", "a/b"])));
+        assert_eq!(app.left_state.selected(), branch(&["a", "a/b", "a/b/x.md"]));
+
+        // A deleted selection falls back to its directory
+        std::fs::remove_file(dir.join("a/b/x.md")).expect("remove x");
+        app.refresh_tree().expect("refresh tree");
+        assert_eq!(app.left_state.selected(), branch(&["a", "a/b"]));
+
+        // Directories that are gone are no longer open
+        std::fs::remove_dir(dir.join("a/b")).expect("remove b");
+        app.refresh_tree().expect("refresh tree");
+        assert!(!app
+            .left_state
+            .get_all_opened()
+            .contains(&branch(&["a", "a/b"])));
+        assert!(app.left_state.get_all_opened().contains(&branch(&["a"])));
+        assert_eq!(app.left_state.selected(), branch(&["a"]));
+    }
+
+    fn wait_for_copy(app: &mut App) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while app.copy_running() {
+            assert!(Instant::now() < deadline, "copy did not finish");
+            std::thread::sleep(Duration::from_millis(10));
+            app.poll_background_tasks();
+        }
+    }
+
+    #[test]
+    fn copy_over_existing_file_asks_first() {
+        let root = tempdir().expect("tempdir");
+        let dir = root.path();
+        let docs = dir.join("docs");
+        std::fs::create_dir(&docs).expect("create docs");
+        std::fs::write(dir.join("a.md"), "new").expect("write a");
+        std::fs::write(docs.join("a.md"), "old").expect("write docs/a");
+        let mut app = loaded_app(dir);
+        app.right_dir = docs.clone();
+        let copy_a = |app: &mut App| {
+            app.op_mode = OpMode::Copy;
+            app.op_source = Some(dir.join("a.md"));
+            app.op_input = TextArea::default();
+            app.confirm_op().expect("confirm");
+        };
+
+        copy_a(&mut app);
+        assert_eq!(app.overwrite_prompt.as_ref().map(|p| p.conflicts), Some(1));
+        assert_eq!(std::fs::read_to_string(docs.join("a.md")).unwrap(), "old");
+        app.keep_both().expect("keep both");
+        wait_for_copy(&mut app);
+        assert_eq!(
+            std::fs::read_to_string(docs.join("a (1).md")).unwrap(),
+            "new"
+        );
+        assert_eq!(app.op_mode, OpMode::None);
+
+        copy_a(&mut app);
+        app.cancel_overwrite();
+        assert_eq!(std::fs::read_to_string(docs.join("a.md")).unwrap(), "old");
+
+        copy_a(&mut app);
+        app.confirm_overwrite().expect("overwrite");
+        wait_for_copy(&mut app);
+        assert!(app.status.starts_with("Copied → "), "{}", app.status);
+        assert_eq!(std::fs::read_to_string(docs.join("a.md")).unwrap(), "new");
+        assert!(app.overwrite_prompt.is_none());
+
+        std::fs::create_dir_all(dir.join("notes")).expect("create notes");
+        std::fs::write(dir.join("notes/x.md"), "x").expect("write x");
+        std::fs::write(dir.join("notes/y.md"), "y").expect("write y");
+        std::fs::create_dir_all(docs.join("notes")).expect("create docs/notes");
+        std::fs::write(docs.join("notes/x.md"), "old x").expect("write docs x");
+        assert_eq!(copy_conflicts(&dir.join("notes"), &docs.join("notes")), 1);
+        assert_eq!(unique_path(&docs.join("notes")), docs.join("notes (1)"));
+        std::fs::create_dir_all(docs.join("v1.2")).expect("create docs/v1.2");
+        assert_eq!(unique_path(&docs.join("v1.2")), docs.join("v1.2 (1)"));
+    }
+
+    #[test]
+    fn move_over_existing_entry_can_be_undone() {
+        let root = tempdir().expect("tempdir");
+        let dir = root.path();
+        let docs = dir.join("docs");
+        std::fs::create_dir_all(docs.join("notes")).expect("create docs/notes");
+        std::fs::write(docs.join("notes/old.md"), "old").expect("write old");
+        std::fs::create_dir(dir.join("notes")).expect("create notes");
+        std::fs::write(dir.join("notes/new.md"), "new").expect("write new");
+        let mut app = loaded_app(dir);
+        app.right_dir = docs.clone();
+        app.op_mode = OpMode::Move;
+        app.op_source = Some(dir.join("notes"));
+        app.op_input = TextArea::default();
+        app.confirm_op().expect("confirm");
+        assert!(app.overwrite_prompt.is_some());
+
+        // A non-empty directory is replaced, and kept in the trash
+        app.confirm_overwrite().expect("overwrite");
+        wait_for_copy(&mut app);
+        assert!(app.status.contains("replacing it"), "{}", app.status);
+        assert!(docs.join("notes/new.md").exists());
+        assert!(!docs.join("notes/old.md").exists());
+
+        app.undo_file_op().expect("undo");
+        assert_eq!(
+            std::fs::read_to_string(docs.join("notes/old.md")).unwrap(),
+            "old"
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.join("notes/new.md")).unwrap(),
+            "new"
+        );
+    }
+
+    #[test]
+    fn hidden_files_toggle_in_tree_and_pickers() {
+        let root = tempdir().expect("tempdir");
+        let dir = root.path();
+        std::fs::write(dir.join(".env"), "KEY=1").expect("write .env");
+        std::fs::create_dir(dir.join(".config")).expect("create .config");
+        std::fs::write(dir.join("a.md"), "# A").expect("write a");
+        let mut app = loaded_app(dir);
+        let env = dir.join(".env");
+        let shown = |app: &App| {
+            let mut ids = Vec::new();
+            tree_ids(&app.left_tree, &mut ids);
+            ids.contains(&env.display().to_string())
+        };
+
+        assert!(!shown(&app));
+        app.load_picker_dir(dir.to_path_buf()).expect("picker");
+        assert!(!app.picker_items.contains(&env));
+
+        app.picking_file = true;
+        app.toggle_hidden();
+        assert_eq!(app.status, "Showing hidden files");
+        assert!(shown(&app));
+        assert!(app.picker_items.contains(&env));
+        app.load_move_dest_dir(dir.to_path_buf())
+            .expect("move dest");
+        assert!(app.move_dest_items.contains(&dir.join(".config")));
+
+        app.toggle_hidden();
+        assert!(!shown(&app));
+        assert_eq!(app.status, "Hiding hidden files");
+    }
+
+    #[test]
+    fn tree_sort_orders_entries_and_cycles() {
+        let root = tempdir().expect("tempdir");
+        let dir = root.path();
+        std::fs::create_dir(dir.join("sub")).expect("create sub");
+        std::fs::write(dir.join("file10.md"), "").expect("write file10");
+        std::fs::write(dir.join("file2.md"), "").expect("write file2");
+        std::fs::write(dir.join("Big.bin"), vec![0u8; 64 * 1024]).expect("write big");
+        let mut app = loaded_app(dir);
+        let names = |app: &App| -> Vec<String> {
+            app.left_tree[0]
+                .children()
+                .iter()
+                .map(|item| {
+                    let path = Path::new(item.identifier());
+                    path.file_name().unwrap().to_string_lossy().into_owned()
+                })
+                .collect()
+        };
+
+        assert_eq!(app.tree_sort, TreeSort::Name);
+        assert_eq!(names(&app), ["Big.bin", "file2.md", "file10.md", "sub"]);
+
+        app.cycle_tree_sort();
+        assert_eq!(app.tree_sort, TreeSort::Modified);
+        assert_eq!(app.status, "Sorted by modified time");
+        app.cycle_tree_sort();
+        assert_eq!(names(&app), ["sub", "Big.bin", "file2.md", "file10.md"]);
+        app.cycle_tree_sort();
+        assert_eq!(names(&app), ["sub", "Big.bin", "file2.md", "file10.md"]);
+        app.cycle_tree_sort();
+        assert_eq!(app.tree_sort, TreeSort::Name);
+
+        assert_eq!(
+            crate::fs::natural_cmp("v1.9", "v1.10"),
+            std::cmp::Ordering::Less
+        );
+        assert_eq!(
+            crate::fs::natural_cmp("a007", "A7"),
+            std::cmp::Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn tree_max_depth_caps_expansion() {
+        let root = tempdir().expect("tempdir");
+        let dir = root.path();
+        std::fs::create_dir_all(dir.join("a/b")).expect("create dirs");
+        std::fs::write(dir.join("a/b/c.md"), "# C").expect("write c");
+        let mut app = loaded_app(dir);
+        app.tree_max_depth = Some(2);
+        app.refresh_tree().expect("refresh tree");
+
+        assert!(app.go_to_path("a/b").is_ok());
+        assert!(app.go_to_path("a/b/c.md").is_err());
+        let b = tree_node_mut(
+            &mut app.left_tree,
+            &[
+                dir.display().to_string(),
+                dir.join("a").display().to_string(),
+                dir.join("a/b").display().to_string(),
+            ],
+        )
+        .expect("a/b is shown");
+        assert!(b.children().is_empty());
+
+        app.begin_select_all_recursive();
+        assert!(app.status.starts_with("Select all 2 entries"));
+    }
+
+    #[test]
+    fn search_streams_results_and_opens_the_match() {
+        let root = tempdir().expect("tempdir");
+        let dir = root.path();
+        std::fs::create_dir(dir.join("notes")).expect("create notes");
+        std::fs::write(dir.join("notes/a.md"), "# A\n\nfind the needle\n").expect("write a");
+        std::fs::write(dir.join("b.md"), "nothing here").expect("write b");
+        let mut app = loaded_app(dir);
+        // The startup tree load would report itself first
+        app.tree_loader = None;
+
+        app.begin_search();
+        app.search_input.insert_str("needle");
+        app.search_activate().expect("run search");
+        assert_eq!(app.background_activity(), Some("Searching files..."));
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while app.search_loader.is_some() && Instant::now() < deadline {
+            app.poll_background_tasks();
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(app.search_results.len(), 1);
+        assert!(app.status.starts_with("1 matches"));
+
+        app.search_activate().expect("open match");
+        assert!(!app.showing_search);
+        assert_eq!(
+            app.opened.as_deref(),
+            Some(dir.join("notes/a.md").as_path())
+        );
+        assert_eq!(app.preview_cursor, 2);
+        let opened = dir.join("notes/a.md").display().to_string();
+        assert_eq!(app.left_state.selected().last(), Some(&opened));
+    }
+
+    #[test]
+    fn finder_ranks_files_and_opens_the_selection() {
+        let root = tempdir().expect("tempdir");
+        let dir = root.path();
+        std::fs::create_dir_all(dir.join("src/ui")).expect("create src");
+        std::fs::write(dir.join("src/ui/event_handler.rs"), "fn main() {}\n").expect("write");
+        std::fs::write(dir.join("src/every.rs"), "").expect("write every");
+        std::fs::write(dir.join("notes.md"), "# Notes\n").expect("write notes");
+        let mut app = loaded_app(dir);
+        app.tree_loader = None;
+
+        app.begin_finder();
+        assert_eq!(app.background_activity(), Some("Indexing files..."));
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while app.background_activity().is_some() && Instant::now() < deadline {
+            app.poll_background_tasks();
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(app.finder_results.len(), 3);
+
+        app.finder_input.insert_str("evh");
+        app.finder_changed();
+        assert_eq!(app.background_activity(), Some("Matching files..."));
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while app.background_activity().is_some() && Instant::now() < deadline {
+            app.poll_background_tasks();
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(app.finder_results.len(), 1);
+        assert_eq!(app.finder_results[0].path, "src/ui/event_handler.rs");
+
+        app.finder_activate().expect("open match");
+        assert!(!app.showing_finder);
+        let opened = dir.join("src/ui/event_handler.rs");
+        assert_eq!(app.opened.as_deref(), Some(opened.as_path()));
+        let id = opened.display().to_string();
+        assert_eq!(app.left_state.selected().last(), Some(&id));
+    }
+
+    #[test]
+    fn search_reports_invalid_regex() {
+        let root = tempdir().expect("tempdir");
+        let mut app = loaded_app(root.path());
+        app.begin_search();
+        app.search_input.insert_str("/(/");
+        app.search_activate().expect("activate");
+        assert!(app.status.starts_with("Invalid pattern"));
+        assert!(app.search_loader.is_none());
+    }
+
+    #[test]
+    fn binary_and_oversized_files_open_as_placeholders() {
+        let root = tempdir().expect("tempdir");
+        let dir = root.path();
+        let blob = dir.join("blob.bin");
+        std::fs::write(&blob, b"PK\x03\x04\0\0data").expect("write blob");
+        std::fs::write(dir.join("big.md"), "x".repeat(64)).expect("write big");
+        std::fs::write(dir.join("small.md"), "# Small").expect("write small");
+        let mut app = loaded_app(dir);
+        app.max_open_size = 32;
+
+        app.go_to_path("blob.bin").expect("select blob");
+        app.open_selected().expect("open blob");
+        assert_eq!(app.editor.text(), "(binary file, 10 bytes — not displayed)");
+        assert_eq!(app.status, app.editor.text());
+
+        // Saving must never overwrite the file with the placeholder
+        app.save().expect("save");
+        assert_eq!(
+            std::fs::read(&blob).expect("read blob"),
+            b"PK\x03\x04\0\0data"
+        );
+
+        app.go_to_path("big.md").expect("select big");
+        app.open_selected().expect("open big");
+        assert_eq!(
+            app.editor.text(),
+            "(file too large, 64 bytes — not displayed)"
+        );
+
+        app.go_to_path("small.md").expect("select small");
+        app.open_selected().expect("open small");
+        assert_eq!(app.editor.text(), "# Small");
+        assert_eq!(app.status, "File opened");
+    }
+
+    #[test]
+    fn binary_files_open_as_a_scrollable_hex_dump() {
+        let root = tempdir().expect("tempdir");
+        let dir = root.path();
+        let mut bytes = vec![0u8; 40];
+        bytes[17] = 0x2a;
+        bytes[18] = 0x01;
+        std::fs::write(dir.join("blob.bin"), &bytes).expect("write blob");
+        let mut app = loaded_app(dir);
+        app.go_to_path("blob.bin").expect("select blob");
+        app.open_selected().expect("open blob");
+
+        assert_eq!(app.hex_view(), Some(40));
+        assert_eq!(app.preview_line_count(), 3);
+        app.move_cursor_down();
+        app.move_col_right();
+        assert_eq!(app.hex_selection(), Some((17, 17)));
+        assert_eq!(app.hex_status().as_deref(), Some("0x11: 0x2a = 42"));
+        app.preview_scroll = 0;
+        let rows = app.hex_text(2);
+        assert_eq!(rows.lines.len(), 2);
+        assert!(
+            rows.lines[1].to_string().starts_with("00000010"),
+            "{rows:?}"
+        );
+        // Drawing again leaves the file alone until the view moves
+        std::fs::write(dir.join("blob.bin"), b"").expect("truncate blob");
+        assert_eq!(app.hex_text(2).lines.len(), 2);
+        assert_eq!(app.hex_status().as_deref(), Some("0x11: 0x2a = 42"));
+        std::fs::write(dir.join("blob.bin"), &bytes).expect("restore blob");
+
+        app.toggle_preview_selection();
+        app.move_col_right();
+        assert_eq!(
+            app.hex_status().as_deref(),
+            Some("0x11–0x12: 2a 01 = 298 LE")
+        );
+
+        app.clear_preview_selection();
+        app.move_cursor_down();
+        app.move_col_to_end();
+        assert_eq!((app.preview_cursor, app.preview_col), (2, 7));
+        app.move_cursor_down();
+        assert_eq!(app.preview_cursor, 2);
+
+        // Edits aimed at the placeholder never reach the file
+        app.move_cursor_top();
+        app.delete_char_under();
+        assert_eq!(
+            std::fs::read(dir.join("blob.bin")).expect("read blob"),
+            bytes
+        );
+
+        // Without a known extension the contents decide, once on open
+        std::fs::write(dir.join("blob"), &bytes).expect("write blob");
+        std::fs::write(dir.join("Makefile"), "all:\n").expect("write Makefile");
+        app.open_file(dir.join("blob")).expect("open blob");
+        assert_eq!(app.renderer.name, "hex");
+        app.open_file(dir.join("Makefile")).expect("open Makefile");
+        assert_eq!(app.renderer.name, "markdown");
+        assert_eq!(app.hex_view(), None);
+    }
+
+    #[test]
+    fn no_color_and_force_color_decide_whether_to_draw_colors() {
+        use crate::config::colors_disabled;
+        assert!(!colors_disabled(None, None));
+        assert!(!colors_disabled(Some(""), None));
+        assert!(colors_disabled(Some("1"), None));
+        assert!(!colors_disabled(Some("1"), Some("1")));
+        assert!(colors_disabled(None, Some("never")));
+        assert!(!colors_disabled(None, Some("")));
+    }
+
+    #[test]
+    fn git_status_is_cached_until_stale() {
+        let root = tempdir().expect("tempdir");
+        let dir = root.path();
+        git2::Repository::init(dir).expect("init repo");
+        std::fs::write(dir.join("a.md"), "# A").expect("write a");
+        let mut app = App::with_settings(dir.to_path_buf(), Settings::default()).expect("app");
+        app.git_status_loader = None;
+        app.refresh_git_status();
+        assert_eq!(
+            app.get_file_git_status(&dir.join("a.md")),
+            Some(FileStatus::Added)
+        );
+        assert!(app
+            .git_branch
+            .as_deref()
+            .is_some_and(|label| label.starts_with("⎇ ") && label.ends_with(" ↑0↓0")));
+
+        // A fresh snapshot is reused rather than re-reading the repository
+        std::fs::write(dir.join("b.md"), "# B").expect("write b");
+        app.refresh_git_status_if_stale();
+        assert_eq!(app.get_file_git_status(&dir.join("b.md")), None);
+
+        // Saving marks the snapshot stale
+        app.opened = Some(dir.join("a.md"));
+        app.editor.set_text("# A2");
+        app.save().expect("save");
+        app.refresh_git_status_if_stale();
+        assert_eq!(
+            app.get_file_git_status(&dir.join("b.md")),
+            Some(FileStatus::Added)
+        );
+    }
+
+    #[test]
+    fn failed_save_records_error() {
+        let root = tempdir().expect("tempdir");
+        let mut app =
+            App::with_settings(root.path().to_path_buf(), Settings::default()).expect("app");
+        app.opened = Some(root.path().join("gone/notes.md"));
+        app.editor.set_text("# Notes");
+
+        assert!(app.save().is_err());
+        assert!(app.status.starts_with("Save failed: Saving"));
+        assert_eq!(app.last_error.as_deref(), Some(app.status.as_str()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn save_replaces_file_atomically() {
+        use std::os::unix::fs::{symlink, PermissionsExt};
+
+        let root = tempdir().expect("tempdir");
+        let notes = root.path().join("notes.md");
+        std::fs::write(&notes, "# Old").expect("write notes");
+        std::fs::set_permissions(&notes, std::fs::Permissions::from_mode(0o640)).expect("chmod");
+        let link = root.path().join("latest.md");
+        symlink(&notes, &link).expect("symlink");
+        let mut app =
+            App::with_settings(root.path().to_path_buf(), Settings::default()).expect("app");
+        app.opened = Some(link.clone());
+        app.editor.set_text("# New");
+
+        app.save().expect("save");
+        assert_eq!(std::fs::read_to_string(&notes).unwrap(), "# New");
+        assert!(is_symlink(&link));
+        let mode = std::fs::metadata(&notes).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o640);
+
+        // Renaming over a directory fails; nothing is left behind
+        let dir = root.path().join("dir.md");
+        std::fs::create_dir(&dir).expect("create dir");
+        assert!(write_atomic(&dir, b"# Lost").is_err());
+        assert!(dir.is_dir());
+        let mut names: Vec<_> = std::fs::read_dir(root.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        assert_eq!(names, ["dir.md", "latest.md", "notes.md"]);
+    }
+
+    #[test]
+    fn autosave_writes_swap_file_and_offers_recovery() {
+        let root = tempdir().expect("tempdir");
+        let notes = root.path().join("notes.md");
+        let swap = root.path().join(".notes.md.sb-swap");
+        std::fs::write(&notes, "# A").expect("write notes");
+        let mut app =
+            App::with_settings(root.path().to_path_buf(), Settings::default()).expect("app");
+        app.autosave_interval = Some(Duration::ZERO);
+        app.open_file(notes.clone()).expect("open");
+        assert_eq!(app.autosave_wait(), None);
+
+        app.editor.set_text("# B");
+        app.poll_background_tasks();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while app.swap_text.is_none() {
+            assert!(Instant::now() < deadline, "autosave never finished");
+            std::thread::sleep(Duration::from_millis(5));
+            app.poll_background_tasks();
+        }
+        assert_eq!(std::fs::read_to_string(&swap).unwrap(), "# B");
+        app.save().expect("save");
+        assert!(!swap.exists());
+
+        // A swap file newer than the file is offered on open
+        let older = std::time::SystemTime::now() - Duration::from_secs(60);
+        let set_modified = |path: &Path, at: std::time::SystemTime| {
+            let file = std::fs::File::options().write(true).open(path).unwrap();
+            file.set_modified(at).unwrap();
+        };
+        std::fs::write(&swap, "# C").expect("write swap");
+        set_modified(&notes, older);
+        app.open_file(notes.clone()).expect("open");
+        assert!(app.swap_prompt.is_some());
+        app.recover_swap().expect("recover");
+        assert_eq!(app.editor.text(), "# C");
+        assert!(app.has_unsaved_changes());
+        app.save().expect("save");
+        assert!(!swap.exists());
+
+        std::fs::write(&swap, "# D").expect("write swap");
+        set_modified(&notes, older);
+        app.open_file(notes.clone()).expect("open");
+        app.discard_swap();
+        assert!(!swap.exists());
+        assert_eq!(app.editor.text(), "# C");
+
+        // Vim's binary swap files are not ours to recover
+        std::fs::write(&swap, b"b0VIM\0\0").expect("write vim swap");
+        set_modified(&notes, older);
+        app.open_file(notes).expect("open");
+        assert!(app.swap_prompt.is_none());
+        app.release_resources();
+        assert!(swap.exists());
+    }
+
+    #[test]
+    fn toggle_gitignore_reloads_tree() {
+        let root = tempdir().expect("tempdir");
+        let mut app =
+            App::with_settings(root.path().to_path_buf(), Settings::default()).expect("app");
+        assert!(app.respect_gitignore);
+
+        app.toggle_gitignore();
+        assert!(!app.respect_gitignore);
+        assert!(app.background_activity().is_some());
+        assert!(app.status.contains("Showing"));
+    }
+
+    fn app_with_open_file(contents: &str) -> (tempfile::TempDir, PathBuf, App) {
+        let root = tempdir().expect("tempdir");
+        let file = root.path().join("note.md");
+        std::fs::write(&file, contents).expect("write note");
+        let mut app =
+            App::with_settings(root.path().to_path_buf(), Settings::default()).expect("app");
+        app.editor.set_text(contents);
+        app.opened = Some(file.clone());
+        app.last_saved_text = Some(contents.to_string());
+        (root, file, app)
+    }
+
+    #[test]
+    fn display_line_motion_steps_through_wrapped_rows() {
+        let (_root, _file, mut app) = app_with_open_file("one two three four\nfive\n");
+        app.preview_width = 8;
+        app.preview_viewport = 10;
+        app.preview_col = 2;
+        // Rows come from the lines as the preview draws them
+        app.sync_preview_rows(&RichText::from(vec![
+            Line::from("one two three four"),
+            Line::from("five"),
+        ]));
+
+        // Off by default: down goes to the next line
+        app.move_cursor_down();
+        assert_eq!((app.preview_cursor, app.preview_col), (1, 2));
+        app.move_cursor_up();
+
+        let config = tempdir().expect("config dir");
+        let settings_path = config.path().join("settings.json");
+        std::fs::write(&settings_path, r#"{"move_by_display_line": true}"#)
+            .expect("write settings");
+        app.settings = Settings::load_from(Some(settings_path));
+        assert!(app.moves_by_display_line());
+
+        // "one two " / "three " / "four"
+        app.move_cursor_down();
+        assert_eq!((app.preview_cursor, app.preview_col), (0, 10));
+        assert_eq!(app.preview_wrap_row(), 1);
+        app.move_cursor_down();
+        assert_eq!((app.preview_cursor, app.preview_col), (0, 16));
+        app.move_cursor_down();
+        assert_eq!((app.preview_cursor, app.preview_col), (1, 2));
+        app.move_cursor_up();
+        assert_eq!((app.preview_cursor, app.preview_col), (0, 16));
+        assert_eq!(app.preview_wrap_row(), 2);
+
+        // The three rows of the first line push the second off a short screen
+        app.preview_viewport = 2;
+        app.move_cursor_down();
+        assert_eq!(app.preview_scroll, 1);
+    }
+
+    #[test]
+    fn terminal_editors_are_queued_for_the_host() {
+        assert!(is_gui_editor("code"));
+        assert!(is_gui_editor("/usr/local/bin/subl"));
+        assert!(!is_gui_editor("vim"));
+        assert!(!is_gui_editor("nano"));
+
+        let (_root, file, mut app) = app_with_open_file("# One\n");
+        app.pending_editor = Some(EditorLaunch {
+            program: "vim".into(),
+            args: vec!["-p".into()],
+            paths: vec![file.clone()],
+        });
+        let launch = app.take_editor_launch().expect("queued launch");
+        assert!(app.take_editor_launch().is_none());
+        let command = launch.command();
+        assert_eq!(command.get_program(), "vim");
+        assert_eq!(command.get_args().count(), 2);
+
+        // The edit is picked up right away while the buffer is clean
+        std::fs::write(&file, "# Two\n").expect("edit in vim");
+        app.finish_editor_launch(&launch, Ok(ExitStatus::default()));
+        assert_eq!(app.editor.text(), "# Two\n");
+        assert!(app.status.starts_with("Edited"));
+
+        app.finish_editor_launch(&launch, Err(io::ErrorKind::NotFound.into()));
+        assert!(app.status.starts_with("Failed to run vim"));
+    }
+
+    #[test]
+    fn external_change_on_clean_buffer_offers_reload() {
+        let (_root, file, mut app) = app_with_open_file("# One\n");
+        std::fs::write(&file, "# Two\n").expect("external write");
+
+        app.handle_opened_file_changed();
+        assert_eq!(app.file_change_prompt, Some(FileChangePrompt::Reload));
+
+        app.reload_opened_file().expect("reload");
+        assert_eq!(app.editor.text(), "# Two\n");
+        assert!(app.file_change_prompt.is_none());
+    }
+
+    #[test]
+    fn external_change_on_dirty_buffer_reports_conflict() {
+        let (_root, file, mut app) = app_with_open_file("# One\n");
+        app.editor.set_text("# One, edited\n");
+        std::fs::write(&file, "# Two\n").expect("external write");
+
+        app.handle_opened_file_changed();
+        assert_eq!(app.file_change_prompt, Some(FileChangePrompt::Conflict));
+
+        app.dismiss_file_change();
+        assert_eq!(app.editor.text(), "# One, edited\n");
+    }
+
+    #[test]
+    fn markdown_headings_skip_code_fences() {
+        let (_root, _file, app) = app_with_open_file(
+            "# Title\ntext\n```sh\n# not a heading\n```\n## Section ##\n#hashtag\n### Deep",
+        );
+        let headings = app.markdown_headings();
+        assert_eq!(
+            headings,
+            vec![
+                (1, "Title".to_string(), 0),
+                (2, "Section".to_string(), 5),
+                (3, "Deep".to_string(), 7),
+            ]
+        );
+    }
+
+    #[test]
+    fn toc_filter_and_jump() {
+        let (_root, _file, mut app) = app_with_open_file("# Intro\n\n## Install\n\n## Usage\nbody");
+        app.begin_toc();
+        app.toc_filter.insert_str("usa");
+        app.toc_filter_changed();
+        assert_eq!(app.toc_entries().len(), 1);
+
+        app.toc_activate();
+        assert!(!app.showing_toc);
+        assert_eq!(app.preview_cursor, 4);
+        assert_eq!(app.preview_scroll, 4);
+    }
+
+    #[test]
+    fn split_view_syncs_preview_with_editor() {
+        let (_root, _file, mut app) =
+            app_with_open_file("# Intro\none\ntwo\nthree\n\n## Usage\nrun it\n");
+        app.preview_cursor = 1;
+        app.toggle_split_view();
+        assert!(app.split_view_active());
+        assert_eq!(app.focus, Focus::Editor);
+        assert_eq!(app.editor.cursor().0, 1);
+
+        // Rendering joins the paragraph into one row
+        let rendered = RichText::from(vec![
+            ratatui::text::Line::from("Intro"),
+            ratatui::text::Line::from("one two three"),
+            ratatui::text::Line::from(""),
+            ratatui::text::Line::from("Usage"),
+            ratatui::text::Line::from("run it"),
+        ]);
+        app.editor.set_cursor(5, 0);
+        assert_eq!(app.sync_split_view(&rendered, 2), (3, 2));
+        app.editor.set_cursor(0, 0);
+        assert_eq!(app.sync_split_view(&rendered, 2), (0, 0));
+
+        // With the preview focused the editor follows it instead
+        app.show_raw_editor = false;
+        app.preview_cursor = 6;
+        app.sync_split_view(&rendered, 2);
+        assert_eq!(app.editor.cursor().0, 6);
+
+        app.toggle_split_view();
+        assert!(!app.split_view_active());
+    }
+
+    #[test]
+    fn page_and_jump_keep_cursor_in_view() {
+        let text: Vec<String> = (1..=25).map(|n| format!("line {n}")).collect();
+        let (_root, _file, mut app) = app_with_open_file(&text.join("\n"));
+        app.preview_viewport = 10;
+
+        app.page_down();
+        assert_eq!((app.preview_cursor, app.preview_scroll), (10, 10));
+        app.page_down();
+        // Scrolling stops with the last line at the bottom
+        assert_eq!((app.preview_cursor, app.preview_scroll), (20, 15));
+        app.page_down();
+        assert_eq!((app.preview_cursor, app.preview_scroll), (24, 15));
+
+        app.page_up();
+        assert_eq!((app.preview_cursor, app.preview_scroll), (14, 5));
+        app.move_cursor_top();
+        assert_eq!((app.preview_cursor, app.preview_scroll), (0, 0));
+        app.move_cursor_bottom();
+        assert_eq!((app.preview_cursor, app.preview_scroll), (24, 15));
+        assert_eq!(app.scroll_step(), crate::config::DEFAULT_SCROLL_STEP);
+    }
+
+    #[test]
+    fn smart_home_and_end_stop_at_text_first() {
+        let (_root, _file, mut app) = app_with_open_file("    let x = 1;  \n");
+        app.preview_col = 6;
+
+        app.move_col_to_start();
+        assert_eq!(app.preview_col, 4);
+        app.move_col_to_start();
+        assert_eq!(app.preview_col, 0);
+        app.move_col_to_start();
+        assert_eq!(app.preview_col, 4);
+
+        app.move_col_to_end();
+        assert_eq!(app.preview_col, 14);
+        app.move_col_to_end();
+        assert_eq!(app.preview_col, 16);
+        app.move_col_to_end();
+        assert_eq!(app.preview_col, 14);
+    }
+
+    #[test]
+    fn unwrapped_preview_follows_cursor_column() {
+        let (_root, _file, mut app) = app_with_open_file(&format!("{}\nshort", "x".repeat(100)));
+        app.sync_preview_hscroll(20);
+        assert_eq!(app.preview_hscroll, 0);
+
+        app.toggle_preview_wrap();
+        assert!(!app.preview_wrap());
+        for _ in 0..30 {
+            app.move_col_right();
+        }
+        app.sync_preview_hscroll(20);
+        assert_eq!(app.preview_hscroll, 11);
+
+        for _ in 0..15 {
+            app.move_col_left();
+        }
+        app.sync_preview_hscroll(20);
+        assert_eq!(app.preview_hscroll, 11);
+        for _ in 0..5 {
+            app.move_col_left();
+        }
+        app.sync_preview_hscroll(20);
+        assert_eq!(app.preview_hscroll, 10);
+
+        // Wrapping again drops the sideways scroll
+        app.toggle_preview_wrap();
+        assert_eq!(app.preview_hscroll, 0);
+    }
+
+    #[test]
+    fn preview_wrap_defaults_per_extension() {
+        let root = tempdir().expect("tempdir");
+        let config = tempdir().expect("config dir");
+        let settings_path = config.path().join("settings.json");
+        std::fs::write(&settings_path, r#"{"wrap": {"json": true}}"#).expect("write settings");
+
+        let mut app =
+            App::with_settings(root.path().to_path_buf(), Settings::default()).expect("app");
+        app.settings = Settings::load_from(Some(settings_path));
+        assert!(app.preview_wrap());
+
+        let wraps = |app: &mut App, name: &str| {
+            app.opened = Some(root.path().join(name));
+            app.preview_wrap()
+        };
+        assert!(wraps(&mut app, "notes.txt"));
+        assert!(!wraps(&mut app, "main.rs"));
+        assert!(wraps(&mut app, "package.json"));
+        assert!(wraps(&mut app, "README.md"));
+
+        // The toggle sticks to the file it was made on
+        app.opened = Some(root.path().join("main.rs"));
+        app.toggle_preview_wrap();
+        assert!(app.preview_wrap());
+        assert!(!wraps(&mut app, "lib.rs"));
+        assert!(wraps(&mut app, "main.rs"));
+    }
+
+    #[test]
+    fn format_timestamp_handles_hours() {
+        assert_eq!(format_timestamp(0.0), "0:00");
+        assert_eq!(format_timestamp(75.4), "1:15");
+        assert_eq!(format_timestamp(3_723.0), "1:02:03");
+    }
+
+    #[test]
+    fn playback_clock_freezes_and_restarts() {
+        let mut clock = PlaybackClock {
+            offset: 5.0,
+            started: None,
+        };
+        assert_eq!(clock.position(), 5.0);
+        clock.restart_at(20.0);
+        assert!(clock.position() >= 20.0);
+        clock.freeze();
+        let frozen = clock.position();
+        thread::sleep(Duration::from_millis(5));
+        assert_eq!(clock.position(), frozen);
+    }
+
+    #[test]
+    fn own_save_does_not_prompt() {
+        let (_root, _file, mut app) = app_with_open_file("# One\n");
+        app.editor.set_text("# One, saved\n");
+        app.save().expect("save");
+
+        app.handle_opened_file_changed();
+        assert!(app.file_change_prompt.is_none());
+    }
 }
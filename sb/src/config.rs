@@ -1,6 +1,36 @@
 //! Configuration management for Saorsa Browser
+//!
+//! [`Config`] holds per-run limits; [`Settings`] are preferences such as the
+//! pane layout that are remembered between runs in the user's config directory.
 
-use std::path::PathBuf;
+use crate::fs::TreeSort;
+use crate::icons::IconSet;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Settings file inside `<config dir>/sb`
+const SETTINGS_FILE: &str = "settings.json";
+
+/// Lines one mouse wheel notch scrolls unless the settings say otherwise
+pub const DEFAULT_SCROLL_STEP: usize = 3;
+
+/// Bytes per hex dump row unless the settings say otherwise
+pub const DEFAULT_HEX_ROW_BYTES: usize = 16;
+
+/// Widest hex dump row the settings may ask for
+pub const MAX_HEX_ROW_BYTES: usize = 64;
+
+/// Longest gap between two clicks on a tree entry that still opens it,
+/// unless the settings say otherwise
+pub const DEFAULT_DOUBLE_CLICK_MS: u64 = 400;
+
+/// Cells the pointer moves with the button held before a click on a tree
+/// entry becomes a drag, unless the settings say otherwise
+pub const DEFAULT_DRAG_THRESHOLD: u16 = 2;
 
 /// Application configuration
 #[derive(Debug, Clone)]
@@ -50,3 +80,225 @@ impl Config {
         self
     }
 }
+
+/// Left pane layout remembered for one workspace root
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LayoutPrefs {
+    /// Width of the left pane (percentage)
+    pub left_pane_width: u16,
+    /// Whether the left pane is shown
+    pub show_left_pane: bool,
+}
+
+/// Preferences persisted between runs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Settings {
+    /// Where the settings are saved; `None` keeps them in memory only
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    /// Pane layout keyed by canonical workspace root
+    #[serde(default)]
+    layouts: HashMap<String, LayoutPrefs>,
+    /// Whether `$EDITOR` opens its own window; guessed from its name when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    gui_editor: Option<bool>,
+    /// Preview line wrapping keyed by lowercase file extension, e.g.
+    /// `{"json": true}`; unlisted extensions wrap unless they are code
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    wrap: HashMap<String, bool>,
+    /// Lines one mouse wheel notch scrolls; [`DEFAULT_SCROLL_STEP`] when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    scroll_step: Option<usize>,
+    /// Bytes per row of the hex dump shown for binary files;
+    /// [`DEFAULT_HEX_ROW_BYTES`] when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hex_row_bytes: Option<usize>,
+    /// Directory levels below the root the file tree shows; no limit when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tree_max_depth: Option<usize>,
+    /// Glyphs drawn before tree entries; emoji when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tree_icons: Option<IconSet>,
+    /// Order of the entries within each directory of the tree
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tree_sort: Option<TreeSort>,
+    /// Show dotfiles in the tree and pickers at startup
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    show_hidden: Option<bool>,
+    /// Seconds between autosaves of unsaved edits to a swap file; off when
+    /// unset or 0
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    autosave_secs: Option<u64>,
+    /// Milliseconds between two clicks that make a double click;
+    /// [`DEFAULT_DOUBLE_CLICK_MS`] when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    double_click_ms: Option<u64>,
+    /// Cells a click has to move to become a drag; [`DEFAULT_DRAG_THRESHOLD`]
+    /// when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    drag_threshold: Option<u16>,
+    /// Move the preview cursor up and down by wrapped screen row instead of
+    /// by line; off when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    move_by_display_line: Option<bool>,
+}
+
+impl Settings {
+    /// Load settings from the user's config directory
+    pub fn load() -> Self {
+        Self::load_from(default_settings_path())
+    }
+
+    /// Load settings from `path`; a missing or malformed file starts empty
+    pub fn load_from(path: Option<PathBuf>) -> Self {
+        let mut settings = path
+            .as_deref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str::<Settings>(&contents).ok())
+            .unwrap_or_default();
+        settings.path = path;
+        settings
+    }
+
+    /// Whether `$EDITOR` was marked as a GUI (`true`) or terminal editor
+    pub fn gui_editor(&self) -> Option<bool> {
+        self.gui_editor
+    }
+
+    /// Whether the preview wraps long lines of `path` by default
+    ///
+    /// Prose wraps and code scrolls sideways unless the `wrap` map lists
+    /// the extension.
+    pub fn wrap_lines(&self, path: &Path) -> bool {
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+        self.wrap
+            .get(&ext)
+            .copied()
+            .unwrap_or_else(|| !crate::preview::is_code_file(path))
+    }
+
+    /// Lines one mouse wheel notch scrolls, at least one
+    pub fn scroll_step(&self) -> usize {
+        self.scroll_step.unwrap_or(DEFAULT_SCROLL_STEP).max(1)
+    }
+
+    /// Bytes per hex dump row, between 1 and [`MAX_HEX_ROW_BYTES`]
+    pub fn hex_row_bytes(&self) -> usize {
+        self.hex_row_bytes
+            .unwrap_or(DEFAULT_HEX_ROW_BYTES)
+            .clamp(1, MAX_HEX_ROW_BYTES)
+    }
+
+    /// Directory levels below the root the file tree shows, if limited
+    pub fn tree_max_depth(&self) -> Option<usize> {
+        self.tree_max_depth
+    }
+
+    /// Glyphs drawn before tree entries
+    pub fn tree_icons(&self) -> IconSet {
+        self.tree_icons.unwrap_or_default()
+    }
+
+    /// Order of the entries within each directory of the tree
+    pub fn tree_sort(&self) -> TreeSort {
+        self.tree_sort.unwrap_or_default()
+    }
+
+    /// Whether dotfiles are shown at startup
+    pub fn show_hidden(&self) -> bool {
+        self.show_hidden.unwrap_or(false)
+    }
+
+    /// How often unsaved edits are written to the swap file, if at all
+    pub fn autosave_interval(&self) -> Option<Duration> {
+        self.autosave_secs
+            .filter(|&secs| secs > 0)
+            .map(Duration::from_secs)
+    }
+
+    /// Longest gap between the clicks of a double click
+    pub fn double_click_interval(&self) -> Duration {
+        Duration::from_millis(self.double_click_ms.unwrap_or(DEFAULT_DOUBLE_CLICK_MS))
+    }
+
+    /// Cells a click has to move to become a drag, at least one
+    pub fn drag_threshold(&self) -> u16 {
+        self.drag_threshold.unwrap_or(DEFAULT_DRAG_THRESHOLD).max(1)
+    }
+
+    /// Whether the preview cursor steps through the rows of wrapped lines
+    pub fn move_by_display_line(&self) -> bool {
+        self.move_by_display_line.unwrap_or(false)
+    }
+
+    /// Remember the tree order, writing the file only when it changed
+    pub fn set_tree_sort(&mut self, sort: TreeSort) -> io::Result<()> {
+        if self.tree_sort == Some(sort) {
+            return Ok(());
+        }
+        self.tree_sort = Some(sort);
+        self.save()
+    }
+
+    /// Layout saved for `root`, if any
+    pub fn layout(&self, root: &Path) -> Option<LayoutPrefs> {
+        self.layouts.get(&root_key(root)).copied()
+    }
+
+    /// Remember the layout for `root`, writing the file only when it changed
+    pub fn set_layout(&mut self, root: &Path, layout: LayoutPrefs) -> io::Result<()> {
+        let key = root_key(root);
+        if self.layouts.get(&key) == Some(&layout) {
+            return Ok(());
+        }
+        self.layouts.insert(key, layout);
+        self.save()
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let data = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(path, data)
+    }
+}
+
+/// Whether to draw without colors, from `NO_COLOR` and `SAORSA_FORCE_COLOR`
+pub fn colors_disabled_from_env() -> bool {
+    let no_color = std::env::var("NO_COLOR").ok();
+    let force_color = std::env::var("SAORSA_FORCE_COLOR").ok();
+    colors_disabled(no_color.as_deref(), force_color.as_deref())
+}
+
+/// Whether these `NO_COLOR` and `SAORSA_FORCE_COLOR` values turn colors off
+///
+/// A non-empty `NO_COLOR` does, as <https://no-color.org> asks, unless
+/// `SAORSA_FORCE_COLOR` is set; that one turns colors off for `0`, `false`,
+/// `no` or `never` and keeps them for any other value.
+pub fn colors_disabled(no_color: Option<&str>, force_color: Option<&str>) -> bool {
+    match force_color.map(|value| value.trim().to_ascii_lowercase()) {
+        Some(value) if matches!(value.as_str(), "0" | "false" | "no" | "never") => true,
+        Some(value) if !value.is_empty() => false,
+        _ => no_color.is_some_and(|value| !value.is_empty()),
+    }
+}
+
+/// The same workspace is found again however it was spelled on the command line
+fn root_key(root: &Path) -> String {
+    fs::canonicalize(root)
+        .unwrap_or_else(|_| root.to_path_buf())
+        .display()
+        .to_string()
+}
+
+fn default_settings_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("sb").join(SETTINGS_FILE))
+}
@@ -0,0 +1,479 @@
+//! Copying and moving files and folders on a background thread
+//!
+//! [`spawn_copy`] copies in chunks and streams [`CopyEvent`]s back over a
+//! channel, which [`App::poll_background_tasks`] reads like the tree and
+//! search loaders. [`CopyJob::cancel`] stops the copy between chunks, and the
+//! worker then removes every file and folder it had created, so a cancelled
+//! or failed copy leaves nothing half-done behind. Files it was told to
+//! overwrite are finished rather than cut short. A destination that is a
+//! symbolic link is replaced, never written through.
+//!
+//! [`spawn_move`] renames on the same thread, and where the destination is
+//! on another filesystem it copies the same way and then removes the source.
+//!
+//! [`App::poll_background_tasks`]: crate::app::App::poll_background_tasks
+
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use saorsa_cli_core::format_bytes;
+use walkdir::WalkDir;
+
+/// Bytes read and written at a time, and how often cancellation is checked
+const CHUNK_SIZE: usize = 256 * 1024;
+
+/// Progress is reported at most this often
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How far a copy has got
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CopyProgress {
+    pub files: usize,
+    pub total_files: usize,
+    pub bytes: u64,
+    pub total_bytes: u64,
+}
+
+impl CopyProgress {
+    /// e.g. `3/10 files, 1.2 MB of 4.0 MB`
+    pub fn summary(&self) -> String {
+        format!(
+            "{}/{} files, {} of {}",
+            self.files,
+            self.total_files,
+            format_bytes(self.bytes),
+            format_bytes(self.total_bytes)
+        )
+    }
+}
+
+/// What a running copy reports
+#[derive(Debug)]
+pub enum CopyEvent {
+    Progress(CopyProgress),
+    Done(CopyProgress),
+    /// Stopped on request; what it had copied is gone again, apart from
+    /// the `overwritten` files it had already replaced
+    Cancelled {
+        overwritten: usize,
+    },
+    /// Stopped by an error; what it had copied is gone again
+    Failed(io::Error),
+}
+
+/// A copy running on a background thread
+pub struct CopyJob {
+    pub dest: PathBuf,
+    rx: Receiver<CopyEvent>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl CopyJob {
+    /// Ask the worker to stop; it answers with [`CopyEvent::Cancelled`]
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    pub fn try_recv(&self) -> Result<CopyEvent, TryRecvError> {
+        self.rx.try_recv()
+    }
+}
+
+/// Copy `src`, a file or a folder, to `dest` on a background thread
+///
+/// Existing files are only replaced when `overwrite` is set; otherwise the
+/// copy fails with [`io::ErrorKind::AlreadyExists`] when it meets one.
+pub fn spawn_copy(src: PathBuf, dest: PathBuf, overwrite: bool) -> CopyJob {
+    let (tx, rx) = mpsc::channel();
+    let cancel = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&cancel);
+    let to = dest.clone();
+    thread::spawn(move || {
+        let mut report = |progress| {
+            let _ = tx.send(CopyEvent::Progress(progress));
+        };
+        let event = copy_all(&src, &to, overwrite, &flag, &mut report).into();
+        let _ = tx.send(event);
+    });
+    CopyJob { dest, rx, cancel }
+}
+
+/// Move `src`, a file or a folder, to the free path `dest` on a background
+/// thread
+///
+/// Across filesystems the move is a copy followed by removing `src`; it
+/// reports progress and can be cancelled like [`spawn_copy`] until the copy
+/// is done, leaving `src` as it was.
+pub fn spawn_move(src: PathBuf, dest: PathBuf) -> CopyJob {
+    let (tx, rx) = mpsc::channel();
+    let cancel = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&cancel);
+    let to = dest.clone();
+    thread::spawn(move || {
+        let event = match fs::rename(&src, &to) {
+            Ok(()) => CopyEvent::Done(CopyProgress::default()),
+            Err(e) if crosses_devices(&e) => {
+                let mut report = |progress| {
+                    let _ = tx.send(CopyEvent::Progress(progress));
+                };
+                match copy_all(&src, &to, false, &flag, &mut report) {
+                    Ok(CopyOutcome::Done(progress)) => match remove_all(&src) {
+                        Ok(()) => CopyEvent::Done(progress),
+                        Err(e) => CopyEvent::Failed(io::Error::new(
+                            e.kind(),
+                            format!("copied, but could not remove {}: {e}", src.display()),
+                        )),
+                    },
+                    other => other.into(),
+                }
+            }
+            Err(e) => CopyEvent::Failed(e),
+        };
+        let _ = tx.send(event);
+    });
+    CopyJob { dest, rx, cancel }
+}
+
+/// Whether a rename failed only because the paths are on different
+/// filesystems (`EXDEV`, or `ERROR_NOT_SAME_DEVICE` on Windows)
+fn crosses_devices(err: &io::Error) -> bool {
+    #[cfg(unix)]
+    const CROSS_DEVICE: i32 = 18;
+    #[cfg(windows)]
+    const CROSS_DEVICE: i32 = 17;
+    #[cfg(not(any(unix, windows)))]
+    const CROSS_DEVICE: i32 = -1;
+    err.raw_os_error() == Some(CROSS_DEVICE)
+}
+
+fn remove_all(path: &Path) -> io::Result<()> {
+    if path.symlink_metadata()?.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
+
+/// How a copy ended, short of an error
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyOutcome {
+    Done(CopyProgress),
+    /// Stopped on request after replacing `overwritten` existing files,
+    /// which were finished; the files and folders it created are gone again
+    Cancelled {
+        overwritten: usize,
+    },
+}
+
+impl From<io::Result<CopyOutcome>> for CopyEvent {
+    fn from(result: io::Result<CopyOutcome>) -> Self {
+        match result {
+            Ok(CopyOutcome::Done(progress)) => CopyEvent::Done(progress),
+            Ok(CopyOutcome::Cancelled { overwritten }) => CopyEvent::Cancelled { overwritten },
+            Err(e) => CopyEvent::Failed(e),
+        }
+    }
+}
+
+/// Copy `src` to `dest`, calling `report` as it goes
+///
+/// Short of finishing, whether cancelled or failed, what it created has
+/// been removed again.
+pub fn copy_all(
+    src: &Path,
+    dest: &Path,
+    overwrite: bool,
+    cancel: &AtomicBool,
+    report: &mut dyn FnMut(CopyProgress),
+) -> io::Result<CopyOutcome> {
+    let (total_files, total_bytes) = totals(src);
+    let mut copier = Copier {
+        overwrite,
+        cancel,
+        report,
+        progress: CopyProgress {
+            total_files,
+            total_bytes,
+            ..CopyProgress::default()
+        },
+        reported: Instant::now(),
+        created: Vec::new(),
+        overwritten: 0,
+        buf: vec![0; CHUNK_SIZE],
+    };
+    (copier.report)(copier.progress);
+    let result = if src.is_dir() {
+        copier.copy_dir(src, dest)
+    } else {
+        copier.copy_file(src, dest)
+    };
+    match result {
+        Ok(true) => Ok(CopyOutcome::Done(copier.progress)),
+        Ok(false) => {
+            copier.clean_up();
+            Ok(CopyOutcome::Cancelled {
+                overwritten: copier.overwritten,
+            })
+        }
+        Err(e) => {
+            copier.clean_up();
+            Err(e)
+        }
+    }
+}
+
+/// Number of files under `src` (or 1 for a file) and their total size
+fn totals(src: &Path) -> (usize, u64) {
+    WalkDir::new(src)
+        .into_iter()
+        .flatten()
+        .filter(|entry| !entry.file_type().is_dir())
+        .fold((0, 0), |(files, bytes), entry| {
+            let len = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            (files + 1, bytes + len)
+        })
+}
+
+struct Copier<'a> {
+    overwrite: bool,
+    cancel: &'a AtomicBool,
+    report: &'a mut dyn FnMut(CopyProgress),
+    progress: CopyProgress,
+    reported: Instant,
+    /// Files and folders that did not exist before, in creation order
+    created: Vec<PathBuf>,
+    /// Existing files replaced so far
+    overwritten: usize,
+    buf: Vec<u8>,
+}
+
+impl Copier<'_> {
+    fn cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+
+    /// Returns `false` once cancelled
+    fn copy_dir(&mut self, src: &Path, dest: &Path) -> io::Result<bool> {
+        if dest
+            .symlink_metadata()
+            .is_ok_and(|m| m.file_type().is_symlink())
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} is a symbolic link", dest.display()),
+            ));
+        }
+        if !dest.exists() {
+            fs::create_dir(dest)?;
+            self.created.push(dest.to_path_buf());
+        }
+        for entry in fs::read_dir(src)? {
+            let entry = entry?;
+            let to = dest.join(entry.file_name());
+            let done = if entry.file_type()?.is_dir() {
+                self.copy_dir(&entry.path(), &to)?
+            } else {
+                self.copy_file(&entry.path(), &to)?
+            };
+            if !done {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Returns `false` once cancelled
+    fn copy_file(&mut self, from: &Path, to: &Path) -> io::Result<bool> {
+        let existing = to.symlink_metadata().ok();
+        let existed = existing.is_some();
+        if existed && !self.overwrite {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} already exists", to.display()),
+            ));
+        }
+        let mut reader = File::open(from)?;
+        // Replace a link rather than write into whatever it points at
+        if existing.is_some_and(|m| m.file_type().is_symlink()) {
+            fs::remove_file(to)?;
+        }
+        let mut writer = File::create(to)?;
+        if existed {
+            self.overwritten += 1;
+        } else {
+            self.created.push(to.to_path_buf());
+        }
+        loop {
+            if !existed && self.cancelled() {
+                return Ok(false);
+            }
+            let n = match reader.read(&mut self.buf) {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            writer.write_all(&self.buf[..n])?;
+            self.progress.bytes += n as u64;
+            self.maybe_report();
+        }
+        writer.set_permissions(reader.metadata()?.permissions())?;
+        self.progress.files += 1;
+        self.maybe_report();
+        Ok(!self.cancelled())
+    }
+
+    fn maybe_report(&mut self) {
+        if self.reported.elapsed() >= PROGRESS_INTERVAL {
+            (self.report)(self.progress);
+            self.reported = Instant::now();
+        }
+    }
+
+    /// Remove what this copy created, innermost first
+    fn clean_up(&mut self) {
+        for path in self.created.drain(..).rev() {
+            let result = if path.is_dir() {
+                fs::remove_dir(&path)
+            } else {
+                fs::remove_file(&path)
+            };
+            if let Err(e) = result {
+                tracing::warn!(error = %e, path = %path.display(), "could not remove partial copy");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn write_tree(dir: &Path) {
+        fs::create_dir_all(dir.join("media/clips")).expect("create dirs");
+        fs::write(dir.join("media/a.bin"), vec![1u8; 3000]).expect("write a");
+        fs::write(dir.join("media/clips/b.bin"), vec![2u8; 1000]).expect("write b");
+    }
+
+    #[test]
+    fn copies_folders_and_counts_progress() {
+        let root = tempdir().expect("tempdir");
+        write_tree(root.path());
+        let dest = root.path().join("backup");
+        let mut reports = Vec::new();
+
+        let outcome = copy_all(
+            &root.path().join("media"),
+            &dest,
+            false,
+            &AtomicBool::new(false),
+            &mut |p| reports.push(p),
+        )
+        .expect("copy");
+        let CopyOutcome::Done(done) = outcome else {
+            panic!("copy was cancelled");
+        };
+
+        assert_eq!(fs::read(dest.join("clips/b.bin")).unwrap(), vec![2u8; 1000]);
+        assert_eq!((done.files, done.total_files), (2, 2));
+        assert_eq!((done.bytes, done.total_bytes), (4000, 4000));
+        assert_eq!(reports[0].files, 0);
+        assert_eq!(done.summary(), "2/2 files, 3.9 KB of 3.9 KB");
+    }
+
+    #[test]
+    fn cancelling_removes_the_partial_copy() {
+        let root = tempdir().expect("tempdir");
+        write_tree(root.path());
+        let dest = root.path().join("backup");
+        let cancel = AtomicBool::new(false);
+
+        let result = copy_all(
+            &root.path().join("media"),
+            &dest,
+            false,
+            &cancel,
+            &mut |_| cancel.store(true, Ordering::Relaxed),
+        )
+        .expect("copy");
+
+        assert_eq!(result, CopyOutcome::Cancelled { overwritten: 0 });
+        assert!(!dest.exists());
+    }
+
+    #[test]
+    fn failed_copy_keeps_existing_files_and_removes_new_ones() {
+        let root = tempdir().expect("tempdir");
+        write_tree(root.path());
+        let dest = root.path().join("backup");
+        fs::create_dir_all(dest.join("clips")).expect("create dest");
+        fs::write(dest.join("clips/b.bin"), "old").expect("write old");
+
+        let err = copy_all(
+            &root.path().join("media"),
+            &dest,
+            false,
+            &AtomicBool::new(false),
+            &mut |_| {},
+        )
+        .expect_err("b.bin exists");
+
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(fs::read_to_string(dest.join("clips/b.bin")).unwrap(), "old");
+        assert!(!dest.join("a.bin").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn overwriting_a_symlink_replaces_the_link() {
+        let root = tempdir().expect("tempdir");
+        let target = root.path().join("target.txt");
+        fs::write(&target, "keep").expect("write target");
+        fs::write(root.path().join("new.txt"), "new").expect("write source");
+        let link = root.path().join("link.txt");
+        std::os::unix::fs::symlink(&target, &link).expect("symlink");
+
+        copy_all(
+            &root.path().join("new.txt"),
+            &link,
+            true,
+            &AtomicBool::new(false),
+            &mut |_| {},
+        )
+        .expect("copy");
+
+        assert_eq!(fs::read_to_string(&target).unwrap(), "keep");
+        assert!(!link.symlink_metadata().unwrap().file_type().is_symlink());
+        assert_eq!(fs::read_to_string(&link).unwrap(), "new");
+    }
+
+    #[test]
+    fn moves_run_in_the_background() {
+        let root = tempdir().expect("tempdir");
+        write_tree(root.path());
+        let dest = root.path().join("moved");
+
+        let job = spawn_move(root.path().join("media"), dest.clone());
+        let deadline = Instant::now() + Duration::from_secs(10);
+        loop {
+            match job.try_recv() {
+                Ok(CopyEvent::Done(_)) => break,
+                Ok(CopyEvent::Progress(_)) | Err(TryRecvError::Empty) => {}
+                Ok(other) => panic!("move ended with {other:?}"),
+                Err(TryRecvError::Disconnected) => panic!("move worker disconnected"),
+            }
+            assert!(Instant::now() < deadline, "move never finished");
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        assert!(!root.path().join("media").exists());
+        assert_eq!(fs::read(dest.join("clips/b.bin")).unwrap(), vec![2u8; 1000]);
+    }
+}
@@ -24,10 +24,19 @@ impl MainEditor {
             state.lines.push(Vec::new());
         }
 
+        // Absolute numbers by default; SB_RELATIVE_NUMBERS=1 starts in vim-style relative mode
+        let relative = std::env::var("SB_RELATIVE_NUMBERS")
+            .map(|v| matches!(v.as_str(), "1" | "true" | "yes" | "on"))
+            .unwrap_or(false);
+
         Self {
             state,
             handler: EditorEventHandler::vim_mode(),
-            line_numbers: LineNumbers::Relative,
+            line_numbers: if relative {
+                LineNumbers::Relative
+            } else {
+                LineNumbers::Absolute
+            },
             wrap: false,
         }
     }
@@ -88,10 +97,24 @@ impl MainEditor {
 
     pub fn insert_text(&mut self, text: &str) {
         for ch in text.chars() {
-            InsertChar(ch).execute(&mut self.state);
+            match ch {
+                '\n' => LineBreak(1).execute(&mut self.state),
+                '\r' => {}
+                _ => InsertChar(ch).execute(&mut self.state),
+            }
         }
     }
 
+    /// Text covered by the editor's visual selection, if any.
+    #[must_use]
+    pub fn selected_text(&self) -> Option<String> {
+        let selection = self.state.selection.as_ref()?;
+        let a = (selection.start.row, selection.start.col);
+        let b = (selection.end.row, selection.end.col);
+        let (start, end) = if a <= b { (a, b) } else { (b, a) };
+        Some(extract_selection(&self.lines_vec(), start, end))
+    }
+
     #[allow(dead_code)]
     pub fn insert_newline(&mut self) {
         LineBreak(1).execute(&mut self.state);
@@ -127,12 +150,50 @@ impl MainEditor {
             .wrap(self.wrap)
     }
 
+    #[must_use]
+    pub fn relative_line_numbers(&self) -> bool {
+        matches!(self.line_numbers, LineNumbers::Relative)
+    }
+
+    /// Switch the gutter between absolute and relative line numbers.
+    pub fn toggle_relative_line_numbers(&mut self) {
+        self.line_numbers = if self.relative_line_numbers() {
+            LineNumbers::Absolute
+        } else {
+            LineNumbers::Relative
+        };
+    }
+
     #[allow(dead_code)]
     pub fn set_wrap(&mut self, wrap: bool) {
         self.wrap = wrap;
     }
 }
 
+/// Extract the text between two `(row, col)` positions, both ends inclusive.
+///
+/// Columns are counted in chars and clamped to the line length.
+#[must_use]
+pub fn extract_selection(lines: &[String], start: (usize, usize), end: (usize, usize)) -> String {
+    let slice = |line: &str, from: usize, to: Option<usize>| -> String {
+        let chars = line.chars().skip(from);
+        match to {
+            Some(to) => chars.take((to + 1).saturating_sub(from)).collect(),
+            None => chars.collect(),
+        }
+    };
+    let mut out = Vec::new();
+    for row in start.0..=end.0 {
+        let Some(line) = lines.get(row) else {
+            break;
+        };
+        let from = if row == start.0 { start.1 } else { 0 };
+        let to = if row == end.0 { Some(end.1) } else { None };
+        out.push(slice(line, from, to));
+    }
+    out.join("\n")
+}
+
 impl Default for MainEditor {
     fn default() -> Self {
         Self::new()
@@ -148,3 +209,45 @@ impl std::fmt::Debug for MainEditor {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(src: &[&str]) -> Vec<String> {
+        src.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn extract_selection_single_line_is_inclusive() {
+        let buf = lines(&["hello world"]);
+        assert_eq!(extract_selection(&buf, (0, 0), (0, 4)), "hello");
+        assert_eq!(extract_selection(&buf, (0, 6), (0, 99)), "world");
+    }
+
+    #[test]
+    fn extract_selection_spans_lines() {
+        let buf = lines(&["alpha", "beta", "gamma"]);
+        assert_eq!(extract_selection(&buf, (0, 3), (2, 1)), "ha\nbeta\nga");
+    }
+
+    #[test]
+    fn toggle_relative_line_numbers_flips_mode() {
+        let mut editor = MainEditor::new();
+        let initial = editor.relative_line_numbers();
+        editor.toggle_relative_line_numbers();
+        assert_ne!(editor.relative_line_numbers(), initial);
+        editor.toggle_relative_line_numbers();
+        assert_eq!(editor.relative_line_numbers(), initial);
+    }
+
+    #[test]
+    fn insert_text_splits_lines() {
+        let mut editor = MainEditor::new();
+        editor.insert_text("one\ntwo");
+        assert_eq!(
+            editor.lines_vec(),
+            vec!["one".to_string(), "two".to_string()]
+        );
+    }
+}
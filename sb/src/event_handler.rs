@@ -8,6 +8,21 @@ use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use std::path::PathBuf;
 use tui_textarea::{Input as TextInput, Key as TextKey};
 
+/// Tell Ctrl+I apart from Tab before any handler sees the key
+///
+/// Legacy terminals send the same byte for both, so Ctrl+I normally arrives
+/// as a plain Tab. With the kitty keyboard protocol (`enhanced`) the two
+/// are reported separately and pass through untouched. Without it, a Tab
+/// that still carries Ctrl is taken to be Ctrl+I; a plain Tab stays Tab.
+pub fn normalize_key(key_event: KeyEvent, enhanced: bool) -> KeyEvent {
+    match key_event.code {
+        KeyCode::Tab if !enhanced && key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+            KeyEvent::new(KeyCode::Char('i'), key_event.modifiers)
+        }
+        _ => key_event,
+    }
+}
+
 fn to_textarea_input(key_event: KeyEvent) -> TextInput {
     let key = match key_event.code {
         KeyCode::Char(c) => TextKey::Char(c),
@@ -53,17 +68,27 @@ use crate::app::{App, Focus, OpMode};
 /// * `Some(())` - Event was handled, continue the main loop
 /// * `None` - Event requests application exit
 pub fn handle_key_event(app: &mut App, key_event: KeyEvent) -> Option<()> {
+    let key_event = normalize_key(key_event, app.keyboard_enhanced);
     match app.current_mode() {
+        AppMode::FileChanged => handle_file_changed(app, key_event),
+        AppMode::SwapRecovery => handle_swap_recovery(app, key_event),
         AppMode::FileCreation => handle_file_creation(app, key_event),
+        AppMode::GoToPath => handle_goto_path(app, key_event),
+        AppMode::Outline => handle_outline(app, key_event),
+        AppMode::Search => handle_search(app, key_event),
+        AppMode::Finder => handle_finder(app, key_event),
+        AppMode::BulkRename => handle_bulk_rename(app, key_event),
         AppMode::FilePicker => handle_file_picker(app, key_event),
         AppMode::MoveDestination => handle_move_destination(app, key_event),
         AppMode::GitStatus => handle_git_status(app, key_event),
+        AppMode::OverwriteConfirmation => handle_overwrite_confirmation(app, key_event),
         AppMode::Operation => handle_operation(app, key_event),
         AppMode::LineEdit => handle_line_edit(app, key_event),
         AppMode::EditorCommand => handle_editor_command(app, key_event),
         AppMode::RawEditor => handle_raw_editor(app, key_event),
         AppMode::Help => handle_help(app, key_event),
         AppMode::DeleteConfirmation => handle_delete_confirmation(app, key_event),
+        AppMode::SelectAllConfirmation => handle_select_all_confirmation(app, key_event),
         AppMode::Normal => handle_normal_mode(app, key_event),
     }
 }
@@ -71,14 +96,30 @@ pub fn handle_key_event(app: &mut App, key_event: KeyEvent) -> Option<()> {
 /// Application modes for cleaner event routing
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AppMode {
+    /// The opened file changed on disk and awaits a reload decision
+    FileChanged,
+    /// A swap file with unsaved edits awaits recover or discard
+    SwapRecovery,
     /// Creating a new file
     FileCreation,
+    /// Typing a path to reveal in the file tree
+    GoToPath,
+    /// Heading outline (table of contents) overlay
+    Outline,
+    /// Workspace content search overlay
+    Search,
+    /// Fuzzy file finder overlay
+    Finder,
+    /// Bulk rename prompt over the file picker
+    BulkRename,
     /// File picker overlay is active
     FilePicker,
     /// Move destination picker is active  
     MoveDestination,
     /// Git status display is active
     GitStatus,
+    /// A copy or move would replace existing files
+    OverwriteConfirmation,
     /// File operation mode (copy/move/etc.)
     Operation,
     /// Inline line editing mode
@@ -91,6 +132,8 @@ pub enum AppMode {
     Help,
     /// Delete confirmation dialog
     DeleteConfirmation,
+    /// Confirming a recursive select-all in the tree
+    SelectAllConfirmation,
     /// Normal browsing/navigation mode
     Normal,
 }
@@ -98,14 +141,30 @@ pub enum AppMode {
 impl App {
     /// Determine the current application mode for event routing
     pub fn current_mode(&self) -> AppMode {
-        if self.creating_file {
+        if self.file_change_prompt.is_some() {
+            AppMode::FileChanged
+        } else if self.swap_prompt.is_some() {
+            AppMode::SwapRecovery
+        } else if self.creating_file {
             AppMode::FileCreation
+        } else if self.going_to_path {
+            AppMode::GoToPath
+        } else if self.showing_toc {
+            AppMode::Outline
+        } else if self.showing_search {
+            AppMode::Search
+        } else if self.showing_finder {
+            AppMode::Finder
+        } else if self.bulk_renaming {
+            AppMode::BulkRename
         } else if self.picking_file {
             AppMode::FilePicker
         } else if self.showing_move_dest {
             AppMode::MoveDestination
         } else if self.showing_git_status {
             AppMode::GitStatus
+        } else if self.overwrite_prompt.is_some() {
+            AppMode::OverwriteConfirmation
         } else if !matches!(self.op_mode, OpMode::None) {
             AppMode::Operation
         } else if self.editing_line {
@@ -118,12 +177,43 @@ impl App {
             AppMode::Help
         } else if self.confirming_delete {
             AppMode::DeleteConfirmation
+        } else if self.is_confirming_select_all() {
+            AppMode::SelectAllConfirmation
         } else {
             AppMode::Normal
         }
     }
 }
 
+/// Handle the reload prompt shown after an external file change
+fn handle_file_changed(app: &mut App, key_event: KeyEvent) -> Option<()> {
+    match key_event.code {
+        KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+            if let Err(e) = app.reload_opened_file() {
+                app.status = format!("Reload failed: {e}");
+            }
+        }
+        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => app.dismiss_file_change(),
+        _ => {}
+    }
+    Some(())
+}
+
+/// Handle the prompt to recover edits from a swap file
+fn handle_swap_recovery(app: &mut App, key_event: KeyEvent) -> Option<()> {
+    match key_event.code {
+        KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+            if let Err(e) = app.recover_swap() {
+                app.status = format!("Recovery failed: {e}");
+            }
+        }
+        KeyCode::Char('n') | KeyCode::Char('N') => app.discard_swap(),
+        KeyCode::Esc => app.dismiss_swap(),
+        _ => {}
+    }
+    Some(())
+}
+
 /// Handle file creation mode events
 fn handle_file_creation(app: &mut App, key_event: KeyEvent) -> Option<()> {
     match (key_event.code, key_event.modifiers) {
@@ -140,6 +230,159 @@ fn handle_file_creation(app: &mut App, key_event: KeyEvent) -> Option<()> {
     Some(())
 }
 
+/// Handle go-to-path prompt events
+fn handle_goto_path(app: &mut App, key_event: KeyEvent) -> Option<()> {
+    match key_event.code {
+        KeyCode::Enter => app.confirm_goto_path(),
+        KeyCode::Esc => app.cancel_goto_path(),
+        _ => {
+            let _ = app.goto_input.input(to_textarea_input(key_event));
+        }
+    }
+    Some(())
+}
+
+/// Route a bracketed paste to whatever currently takes text input
+///
+/// The text lands as one edit: one-line prompts get it with line breaks
+/// turned into spaces, the editor and preview get it verbatim. Modes
+/// without a text field ignore it.
+pub fn handle_paste_event(app: &mut App, text: &str) {
+    let one_line = text
+        .trim_end_matches(['\r', '\n'])
+        .replace("\r\n", " ")
+        .replace(['\r', '\n'], " ");
+    let prompt = match app.current_mode() {
+        AppMode::FileCreation => &mut app.filename_input,
+        AppMode::GoToPath => &mut app.goto_input,
+        AppMode::Search => &mut app.search_input,
+        AppMode::BulkRename => &mut app.rename_input,
+        AppMode::Operation => &mut app.op_input,
+        AppMode::LineEdit => &mut app.line_input,
+        AppMode::EditorCommand => &mut app.editor_cmd_input,
+        AppMode::Outline => {
+            if app.toc_filter.insert_str(&one_line) {
+                app.toc_filter_changed();
+            }
+            return;
+        }
+        AppMode::Finder => {
+            if app.finder_input.insert_str(&one_line) {
+                app.finder_changed();
+            }
+            return;
+        }
+        AppMode::RawEditor => {
+            app.insert_pasted(text);
+            return;
+        }
+        AppMode::Normal if !matches!(app.focus, Focus::Left) => {
+            app.insert_pasted(text);
+            return;
+        }
+        _ => return,
+    };
+    let _ = prompt.insert_str(&one_line);
+}
+
+/// Handle the bulk rename prompt; the preview follows every keystroke
+fn handle_bulk_rename(app: &mut App, key_event: KeyEvent) -> Option<()> {
+    match key_event.code {
+        KeyCode::Enter => {
+            let _ = app.confirm_bulk_rename();
+        }
+        KeyCode::Esc => app.cancel_bulk_rename(),
+        _ => {
+            let _ = app.rename_input.input(to_textarea_input(key_event));
+        }
+    }
+    Some(())
+}
+
+/// Handle heading outline overlay events; typed characters filter the list
+fn handle_outline(app: &mut App, key_event: KeyEvent) -> Option<()> {
+    match (key_event.code, key_event.modifiers) {
+        (KeyCode::Esc, _) => app.cancel_toc(),
+        (KeyCode::Enter, _) => app.toc_activate(),
+        (KeyCode::Up, _) => app.toc_up(),
+        (KeyCode::Down, _) => app.toc_down(),
+        (KeyCode::PageUp, _) => {
+            for _ in 0..10 {
+                app.toc_up();
+            }
+        }
+        (KeyCode::PageDown, _) => {
+            for _ in 0..10 {
+                app.toc_down();
+            }
+        }
+        _ => {
+            if app.toc_filter.input(to_textarea_input(key_event)) {
+                app.toc_filter_changed();
+            }
+        }
+    }
+    Some(())
+}
+
+/// Handle workspace search overlay events
+fn handle_search(app: &mut App, key_event: KeyEvent) -> Option<()> {
+    match (key_event.code, key_event.modifiers) {
+        (KeyCode::Esc, _) => app.cancel_search(),
+        (KeyCode::Enter, _) => {
+            if let Err(e) = app.search_activate() {
+                app.status = format!("Open failed: {e}");
+            }
+        }
+        (KeyCode::Up, _) => app.search_up(),
+        (KeyCode::Down, _) => app.search_down(),
+        (KeyCode::PageUp, _) => {
+            for _ in 0..10 {
+                app.search_up();
+            }
+        }
+        (KeyCode::PageDown, _) => {
+            for _ in 0..10 {
+                app.search_down();
+            }
+        }
+        _ => {
+            let _ = app.search_input.input(to_textarea_input(key_event));
+        }
+    }
+    Some(())
+}
+
+/// Handle fuzzy file finder events; the matches follow every keystroke
+fn handle_finder(app: &mut App, key_event: KeyEvent) -> Option<()> {
+    match (key_event.code, key_event.modifiers) {
+        (KeyCode::Esc, _) => app.cancel_finder(),
+        (KeyCode::Enter, _) => {
+            if let Err(e) = app.finder_activate() {
+                app.status = format!("Open failed: {e}");
+            }
+        }
+        (KeyCode::Up, _) => app.finder_up(),
+        (KeyCode::Down, _) => app.finder_down(),
+        (KeyCode::PageUp, _) => {
+            for _ in 0..10 {
+                app.finder_up();
+            }
+        }
+        (KeyCode::PageDown, _) => {
+            for _ in 0..10 {
+                app.finder_down();
+            }
+        }
+        _ => {
+            if app.finder_input.input(to_textarea_input(key_event)) {
+                app.finder_changed();
+            }
+        }
+    }
+    Some(())
+}
+
 /// Handle file picker mode events
 fn handle_file_picker(app: &mut App, key_event: KeyEvent) -> Option<()> {
     match (key_event.code, key_event.modifiers) {
@@ -167,6 +410,10 @@ fn handle_file_picker(app: &mut App, key_event: KeyEvent) -> Option<()> {
         (KeyCode::Char('m') | KeyCode::Char('M'), _) => {
             let _ = app.picker_start_move();
         }
+        (KeyCode::Char('u') | KeyCode::Char('U'), _) => {
+            let _ = app.undo_file_op();
+        }
+        (KeyCode::Char('r') | KeyCode::Char('R'), _) => app.picker_begin_bulk_rename(),
         (KeyCode::Char('p') | KeyCode::Char('P'), _) => {
             let _ = app.picker_parent_dir();
         }
@@ -202,6 +449,7 @@ fn handle_file_picker(app: &mut App, key_event: KeyEvent) -> Option<()> {
             // Space to toggle selection of current item
             app.picker_toggle_selection();
         }
+        (KeyCode::Char('.'), KeyModifiers::NONE) => app.toggle_hidden(),
         (KeyCode::Char('a'), modifiers) if modifiers.contains(KeyModifiers::CONTROL) => {
             // Ctrl+A to select all
             app.picker_selection.clear();
@@ -230,6 +478,7 @@ fn handle_move_destination(app: &mut App, key_event: KeyEvent) -> Option<()> {
         (KeyCode::Right, _) | (KeyCode::Char('l'), _) => {
             let _ = app.move_dest_enter();
         }
+        (KeyCode::Char('.'), KeyModifiers::NONE) => app.toggle_hidden(),
         _ => {}
     }
     Some(())
@@ -260,6 +509,24 @@ fn handle_operation(app: &mut App, key_event: KeyEvent) -> Option<()> {
     Some(())
 }
 
+/// Handle the overwrite / keep both / cancel prompt of a copy or move
+fn handle_overwrite_confirmation(app: &mut App, key_event: KeyEvent) -> Option<()> {
+    let result = match key_event.code {
+        KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => app.confirm_overwrite(),
+        KeyCode::Char('r') | KeyCode::Char('R') => app.keep_both(),
+        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+            app.cancel_overwrite();
+            Ok(())
+        }
+        _ => Ok(()),
+    };
+    if let Err(e) = result {
+        app.cancel_op();
+        app.status = format!("Operation failed: {e}");
+    }
+    Some(())
+}
+
 /// Handle inline line editing mode events
 fn handle_line_edit(app: &mut App, key_event: KeyEvent) -> Option<()> {
     match (key_event.code, key_event.modifiers) {
@@ -296,6 +563,20 @@ fn handle_raw_editor(app: &mut App, key_event: KeyEvent) -> Option<()> {
             app.prefer_raw_editor = false;
             app.focus = Focus::Preview;
         }
+        (KeyCode::Char('c'), KeyModifiers::CONTROL) => app.copy_selection(),
+        (KeyCode::Char('v'), KeyModifiers::CONTROL) => app.paste(),
+        (KeyCode::Char('l'), KeyModifiers::CONTROL) => {
+            app.editor.toggle_relative_line_numbers();
+            app.status = if app.editor.relative_line_numbers() {
+                "Relative line numbers".into()
+            } else {
+                "Absolute line numbers".into()
+            };
+        }
+        (KeyCode::Char('i'), mods) if mods.contains(KeyModifiers::CONTROL) => {
+            let _ = app.begin_file_picker();
+        }
+        (KeyCode::F(3), _) => app.toggle_split_view(),
         (KeyCode::Tab, _) => {
             // Exit raw editor but handle Tab like in normal mode
             app.show_raw_editor = false;
@@ -334,13 +615,36 @@ fn handle_delete_confirmation(app: &mut App, key_event: KeyEvent) -> Option<()>
     Some(())
 }
 
+/// Handle the y/n prompt before selecting everything under the root
+fn handle_select_all_confirmation(app: &mut App, key_event: KeyEvent) -> Option<()> {
+    match key_event.code {
+        KeyCode::Enter | KeyCode::Char('y') | KeyCode::Char('Y') => {
+            app.confirm_select_all_recursive()
+        }
+        _ => app.cancel_select_all_recursive(),
+    }
+    Some(())
+}
+
 /// Handle normal browsing/navigation mode events
 fn handle_normal_mode(app: &mut App, key_event: KeyEvent) -> Option<()> {
     match (key_event.code, key_event.modifiers) {
+        // Clipboard (the file tree keeps Ctrl+C quit and Ctrl+V autoplay)
+        (KeyCode::Char('c'), KeyModifiers::CONTROL) if !matches!(app.focus, Focus::Left) => {
+            app.copy_selection();
+        }
+        (KeyCode::Char('v'), KeyModifiers::CONTROL) if !matches!(app.focus, Focus::Left) => {
+            app.paste();
+        }
+
         // Application exit commands
         (KeyCode::Char('q'), _) | (KeyCode::Char('c'), KeyModifiers::CONTROL) => {
             return None; // Signal exit
         }
+        (KeyCode::Esc, _) if app.copy_running() => app.cancel_copy(),
+        (KeyCode::Esc, _) if app.preview_selection_anchor.is_some() => {
+            app.clear_preview_selection();
+        }
         (KeyCode::Esc, _) => {
             if matches!(app.focus, Focus::Preview) {
                 return None; // Signal exit
@@ -348,11 +652,13 @@ fn handle_normal_mode(app: &mut App, key_event: KeyEvent) -> Option<()> {
         }
 
         // Focus and navigation commands
-        (KeyCode::Tab, mods) => handle_tab_navigation(app, mods),
+        (KeyCode::Tab, _) => handle_tab_navigation(app),
         (KeyCode::BackTab, _) => handle_back_tab_navigation(app),
 
         // Application commands
         (KeyCode::Char('?'), _) => app.toggle_help(),
+        (KeyCode::Char('f'), KeyModifiers::CONTROL) => app.begin_search(),
+        (KeyCode::Char('p'), KeyModifiers::CONTROL) => app.begin_finder(),
         (KeyCode::Char('i'), mods) if mods.contains(KeyModifiers::CONTROL) => {
             let _ = app.begin_file_picker();
         }
@@ -402,6 +708,7 @@ fn handle_normal_mode(app: &mut App, key_event: KeyEvent) -> Option<()> {
         (KeyCode::F(2), _) => {
             let _ = app.begin_file_picker();
         }
+        (KeyCode::F(3), _) => app.toggle_split_view(),
         (KeyCode::F(4), _) => {
             app.focus = Focus::Editor;
             if app.prefer_raw_editor {
@@ -443,11 +750,8 @@ fn handle_normal_mode(app: &mut App, key_event: KeyEvent) -> Option<()> {
 }
 
 /// Handle Tab navigation between panes
-fn handle_tab_navigation(app: &mut App, modifiers: KeyModifiers) {
-    if modifiers.contains(KeyModifiers::CONTROL) {
-        // Ctrl+I for file picker
-        let _ = app.begin_file_picker();
-    } else if app.show_left_pane {
+fn handle_tab_navigation(app: &mut App) {
+    if app.show_left_pane {
         // Tab between left pane and right pane
         app.focus = match app.focus {
             Focus::Left => {
@@ -547,6 +851,13 @@ fn handle_left_pane_keys(app: &mut App, key_event: KeyEvent) {
         (KeyCode::Char('r'), _) => {
             let _ = app.refresh_tree();
         }
+        (KeyCode::Char('I'), _) => app.toggle_gitignore(),
+        (KeyCode::Char('O'), _) => app.cycle_tree_sort(),
+        (KeyCode::Char('.'), KeyModifiers::NONE) => app.toggle_hidden(),
+        (KeyCode::Char('g'), _) => app.begin_goto_path(),
+        (KeyCode::Char('u'), _) => {
+            let _ = app.undo_file_op();
+        }
         (KeyCode::Char('h'), _) => app.toggle_left_pane(),
         (KeyCode::Char('s') | KeyCode::Char('S'), _) => {
             app.tree_accumulate_selection();
@@ -560,6 +871,7 @@ fn handle_left_pane_keys(app: &mut App, key_event: KeyEvent) {
         (KeyCode::Char('a'), modifiers) if modifiers.contains(KeyModifiers::CONTROL) => {
             app.tree_select_all();
         }
+        (KeyCode::Char('A'), _) => app.begin_select_all_recursive(),
         (KeyCode::F(5), _) => app.begin_copy(),
         (KeyCode::F(6), _) => app.begin_move(),
         (KeyCode::F(7), _) => app.begin_create_file(),
@@ -585,10 +897,24 @@ fn handle_editor_keys(app: &mut App, key_event: KeyEvent) {
 
 /// Handle key events when preview pane has focus
 fn handle_preview_keys(app: &mut App, key_event: KeyEvent) {
+    // `gg` needs its two presses back to back
+    let pending_g = std::mem::take(&mut app.preview_pending_g);
     match (key_event.code, key_event.modifiers) {
         (KeyCode::Up, _) | (KeyCode::Char('k'), _) => app.move_cursor_up(),
         (KeyCode::Down, _) | (KeyCode::Char('j'), _) => app.move_cursor_down(),
+        (KeyCode::PageUp, _) | (KeyCode::Char('u'), KeyModifiers::CONTROL) => app.page_up(),
+        (KeyCode::PageDown, _) | (KeyCode::Char('d'), KeyModifiers::CONTROL) => app.page_down(),
+        (KeyCode::Home, KeyModifiers::CONTROL) => app.move_cursor_top(),
+        (KeyCode::End, KeyModifiers::CONTROL) | (KeyCode::Char('G'), _) => app.move_cursor_bottom(),
+        (KeyCode::Home, _) => app.move_col_to_start(),
+        (KeyCode::End, _) => app.move_col_to_end(),
+        (KeyCode::Char('g'), KeyModifiers::NONE) if pending_g => app.move_cursor_top(),
+        (KeyCode::Char('g'), KeyModifiers::NONE) => app.preview_pending_g = true,
         (KeyCode::Char('i'), _) => app.begin_line_edit(),
+        (KeyCode::Char('v'), KeyModifiers::NONE) => app.toggle_preview_selection(),
+        (KeyCode::Char('t'), _) => app.begin_toc(),
+        (KeyCode::Char('b'), KeyModifiers::NONE) => app.toggle_blame(),
+        (KeyCode::Char('w'), KeyModifiers::NONE) => app.toggle_preview_wrap(),
         (KeyCode::Char('e'), _) => {
             app.focus = Focus::Editor;
             app.show_raw_editor = true;
@@ -602,16 +928,8 @@ fn handle_preview_keys(app: &mut App, key_event: KeyEvent) {
             app.show_raw_editor = true;
             app.prefer_raw_editor = true;
         }
-        (KeyCode::PageUp, _) => {
-            for _ in 0..10 {
-                app.move_cursor_up();
-            }
-        }
-        (KeyCode::PageDown, _) => {
-            for _ in 0..10 {
-                app.move_cursor_down();
-            }
-        }
+        (KeyCode::Left, _) if app.video_player.is_some() => app.seek_video(-10),
+        (KeyCode::Right, _) if app.video_player.is_some() => app.seek_video(10),
         (KeyCode::Left, _) => app.move_col_left(),
         (KeyCode::Right, _) => app.move_col_right(),
         _ => {}
@@ -621,11 +939,12 @@ fn handle_preview_keys(app: &mut App, key_event: KeyEvent) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::Settings;
     use std::path::PathBuf;
 
     #[test]
     fn test_app_mode_detection() {
-        let mut app = App::new(PathBuf::from(".")).unwrap();
+        let mut app = App::with_settings(PathBuf::from("."), Settings::default()).unwrap();
 
         // Test normal mode
         assert_eq!(app.current_mode(), AppMode::Normal);
@@ -635,9 +954,17 @@ mod tests {
         assert_eq!(app.current_mode(), AppMode::FileCreation);
         app.creating_file = false;
 
+        // Test go-to-path prompt
+        app.going_to_path = true;
+        assert_eq!(app.current_mode(), AppMode::GoToPath);
+        app.going_to_path = false;
+
         // Test file picker mode
         app.picking_file = true;
         assert_eq!(app.current_mode(), AppMode::FilePicker);
+        app.bulk_renaming = true;
+        assert_eq!(app.current_mode(), AppMode::BulkRename);
+        app.bulk_renaming = false;
         app.picking_file = false;
 
         // Test help mode
@@ -648,7 +975,7 @@ mod tests {
 
     #[test]
     fn test_key_event_routing() {
-        let mut app = App::new(PathBuf::from(".")).unwrap();
+        let mut app = App::with_settings(PathBuf::from("."), Settings::default()).unwrap();
 
         // Test normal mode key handling
         let key_event = KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE);
@@ -664,7 +991,7 @@ mod tests {
 
     #[test]
     fn test_preview_e_enters_raw_editor() {
-        let mut app = App::new(PathBuf::from(".")).unwrap();
+        let mut app = App::with_settings(PathBuf::from("."), Settings::default()).unwrap();
         app.focus = Focus::Preview;
         let key_event = KeyEvent::new(KeyCode::Char('e'), KeyModifiers::NONE);
         handle_key_event(&mut app, key_event);
@@ -672,9 +999,45 @@ mod tests {
         assert!(matches!(app.focus, Focus::Editor));
     }
 
+    #[test]
+    fn test_preview_w_toggles_wrap() {
+        let mut app = App::with_settings(PathBuf::from("."), Settings::default()).unwrap();
+        app.focus = Focus::Preview;
+        app.opened = Some(PathBuf::from("notes.md"));
+        let key_event = KeyEvent::new(KeyCode::Char('w'), KeyModifiers::NONE);
+        handle_key_event(&mut app, key_event);
+        assert!(!app.preview_wrap());
+        handle_key_event(&mut app, key_event);
+        assert!(app.preview_wrap());
+    }
+
+    #[test]
+    fn test_preview_v_toggles_selection_and_esc_clears() {
+        let mut app = App::with_settings(PathBuf::from("."), Settings::default()).unwrap();
+        app.focus = Focus::Preview;
+        handle_key_event(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('v'), KeyModifiers::NONE),
+        );
+        assert!(app.preview_selection_anchor.is_some());
+        let result = handle_key_event(&mut app, KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert!(result.is_some());
+        assert!(app.preview_selection_anchor.is_none());
+    }
+
+    #[test]
+    fn test_ctrl_c_copies_outside_file_tree() {
+        let mut app = App::with_settings(PathBuf::from("."), Settings::default()).unwrap();
+        app.focus = Focus::Preview;
+        app.editor.set_text("first line\nsecond");
+        let key_event = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert!(handle_key_event(&mut app, key_event).is_some());
+        assert_eq!(app.clipboard_register.as_deref(), Some("first line"));
+    }
+
     #[test]
     fn test_raw_editor_tab_falls_back_to_normal() {
-        let mut app = App::new(PathBuf::from(".")).unwrap();
+        let mut app = App::with_settings(PathBuf::from("."), Settings::default()).unwrap();
         app.focus = Focus::Editor;
         app.show_raw_editor = true;
         let key_event = KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE);
@@ -682,4 +1045,74 @@ mod tests {
         assert!(result.is_some());
         assert!(!app.show_raw_editor);
     }
+
+    #[test]
+    fn test_paste_goes_to_the_active_input() {
+        let mut app = App::with_settings(PathBuf::from("."), Settings::default()).unwrap();
+        app.begin_goto_path();
+        handle_paste_event(&mut app, "docs/\nnotes.md\n");
+        assert_eq!(app.goto_input.lines(), ["docs/ notes.md"]);
+        app.cancel_goto_path();
+
+        app.focus = Focus::Editor;
+        app.show_raw_editor = true;
+        app.editor.set_text("");
+        handle_paste_event(&mut app, "```rust\r\nfn main() {}\r\n```");
+        assert_eq!(app.editor.text(), "```rust\nfn main() {}\n```");
+
+        app.show_raw_editor = false;
+        app.focus = Focus::Left;
+        handle_paste_event(&mut app, "ignored");
+        assert_eq!(app.editor.text(), "```rust\nfn main() {}\n```");
+    }
+
+    #[test]
+    fn test_normalize_key_separates_ctrl_i_from_tab() {
+        let tab = KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE);
+        let ctrl_tab = KeyEvent::new(KeyCode::Tab, KeyModifiers::CONTROL);
+        let ctrl_i = KeyEvent::new(KeyCode::Char('i'), KeyModifiers::CONTROL);
+
+        assert_eq!(normalize_key(tab, false), tab);
+        assert_eq!(normalize_key(ctrl_tab, false), ctrl_i);
+        assert_eq!(normalize_key(ctrl_i, false), ctrl_i);
+
+        assert_eq!(normalize_key(tab, true), tab);
+        assert_eq!(normalize_key(ctrl_tab, true), ctrl_tab);
+        assert_eq!(normalize_key(ctrl_i, true), ctrl_i);
+    }
+
+    #[test]
+    fn test_tab_switches_focus_and_ctrl_i_opens_picker() {
+        let mut app = App::with_settings(PathBuf::from("."), Settings::default()).unwrap();
+        app.keyboard_enhanced = true;
+        app.focus = Focus::Left;
+        handle_key_event(&mut app, KeyEvent::new(KeyCode::Tab, KeyModifiers::CONTROL));
+        assert_eq!(app.focus, Focus::Preview);
+        assert!(!app.picking_file);
+
+        handle_key_event(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('i'), KeyModifiers::CONTROL),
+        );
+        assert!(app.picking_file);
+    }
+
+    #[test]
+    fn test_shift_o_sorts_the_tree_and_o_still_opens() {
+        let root = tempfile::tempdir().unwrap();
+        let mut app = App::with_settings(root.path().to_path_buf(), Settings::default()).unwrap();
+        app.focus = Focus::Left;
+        handle_key_event(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('O'), KeyModifiers::SHIFT),
+        );
+        assert_eq!(app.tree_sort, crate::fs::TreeSort::Modified);
+        assert_eq!(app.status, "Sorted by modified time");
+
+        handle_key_event(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('o'), KeyModifiers::NONE),
+        );
+        assert_eq!(app.tree_sort, crate::fs::TreeSort::Modified);
+    }
 }
@@ -0,0 +1,391 @@
+//! Fuzzy file finder over the workspace
+//!
+//! [`spawn_file_index`] lists every file under the root on a background
+//! thread, honouring the same ignore rules as the tree and the content
+//! search. [`rank`] then scores those paths against what the user types:
+//! the query only has to appear in order, not contiguously, and matches on
+//! the file name, at word starts or in runs score higher than scattered ones.
+//! A [`Ranker`] does that on its own thread, so typing never waits on it.
+
+use crate::fs::is_trash;
+use ignore::WalkBuilder;
+use saorsa_cli_core::IgnoreRules;
+use std::cmp::Reverse;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::sync::Arc;
+use std::thread;
+
+/// Stop indexing after this many files
+pub const MAX_FILES: usize = 100_000;
+
+/// Show at most this many matches
+pub const MAX_RESULTS: usize = 200;
+
+const SCORE_MATCH: i64 = 16;
+const BONUS_CONSECUTIVE: i64 = 24;
+const BONUS_WORD_START: i64 = 20;
+const BONUS_FILE_NAME: i64 = 40;
+const PENALTY_GAP: i64 = 1;
+const MAX_GAP_PENALTY: i64 = 8;
+
+/// A path that matches the query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FinderMatch {
+    /// Path relative to the workspace root, `/`-separated
+    pub path: String,
+    pub score: i64,
+    /// Character indices of `path` that matched the query
+    pub positions: Vec<usize>,
+}
+
+/// A file index being built by [`spawn_file_index`]
+///
+/// Dropping it stops the walk.
+pub struct FileIndex {
+    rx: Receiver<Vec<String>>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl FileIndex {
+    pub fn try_recv(&self) -> Result<Vec<String>, TryRecvError> {
+        self.rx.try_recv()
+    }
+}
+
+impl Drop for FileIndex {
+    fn drop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+/// List the files under `root` on a background thread
+///
+/// The paths come back relative to `root` and sorted, in one message once
+/// the walk is done.
+pub fn spawn_file_index(
+    root: PathBuf,
+    respect_gitignore: bool,
+    ignore_patterns: Vec<String>,
+    show_hidden: bool,
+) -> FileIndex {
+    let (tx, rx) = mpsc::channel();
+    let cancel = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&cancel);
+    thread::spawn(move || {
+        let extra = IgnoreRules::new(&root, false, &ignore_patterns);
+        let walker = WalkBuilder::new(&root)
+            .hidden(!show_hidden)
+            .git_ignore(respect_gitignore)
+            .git_global(false)
+            .git_exclude(false)
+            .require_git(false)
+            .filter_entry(move |entry| {
+                let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+                !is_trash(entry.path()) && !extra.is_ignored(entry.path(), is_dir)
+            })
+            .build();
+
+        let mut files: Vec<String> = walker
+            .take_while(|_| !flag.load(Ordering::Relaxed))
+            .filter_map(|e| e.ok())
+            .filter(|entry| entry.file_type().is_some_and(|t| t.is_file()))
+            .filter_map(|entry| {
+                let relative = entry.path().strip_prefix(&root).ok()?;
+                let parts: Vec<_> = relative
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect();
+                Some(parts.join("/"))
+            })
+            .take(MAX_FILES)
+            .collect();
+        if flag.load(Ordering::Relaxed) {
+            return;
+        }
+        files.sort();
+        let _ = tx.send(files);
+    });
+    FileIndex { rx, cancel }
+}
+
+/// Ranks queries against the index on a background thread
+///
+/// A query sent while another is being ranked replaces any still waiting,
+/// so a burst of keystrokes costs one ranking. Dropping the ranker stops
+/// the thread.
+pub struct Ranker {
+    jobs: Sender<(u64, String, Arc<[String]>)>,
+    results: Receiver<(u64, Vec<FinderMatch>)>,
+    sent: u64,
+    /// Whether the matches for the last query sent are still to come
+    waiting: bool,
+}
+
+impl Ranker {
+    pub fn spawn() -> Self {
+        let (jobs, job_rx) = mpsc::channel::<(u64, String, Arc<[String]>)>();
+        let (result_tx, results) = mpsc::channel();
+        thread::spawn(move || {
+            while let Ok(mut job) = job_rx.recv() {
+                while let Ok(newer) = job_rx.try_recv() {
+                    job = newer;
+                }
+                let (id, query, paths) = job;
+                if result_tx
+                    .send((id, rank(&query, &paths, MAX_RESULTS)))
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+        Self {
+            jobs,
+            results,
+            sent: 0,
+            waiting: false,
+        }
+    }
+
+    /// Rank `paths` against `query`; the matches come from [`Ranker::try_recv`]
+    pub fn send(&mut self, query: String, paths: Arc<[String]>) {
+        self.sent += 1;
+        // A stopped thread shows up as a disconnect in `try_recv`
+        self.waiting = self.jobs.send((self.sent, query, paths)).is_ok();
+    }
+
+    /// The matches for the last query sent, skipping those for older ones
+    pub fn try_recv(&mut self) -> Result<Vec<FinderMatch>, TryRecvError> {
+        loop {
+            let (id, matches) = self.results.try_recv()?;
+            if id == self.sent {
+                self.waiting = false;
+                return Ok(matches);
+            }
+        }
+    }
+
+    pub fn is_busy(&self) -> bool {
+        self.waiting
+    }
+}
+
+/// The best `limit` matches of `query` among `paths`, best first
+///
+/// Ties go to the shorter path, then alphabetical order. An empty query
+/// matches every path in the order given.
+pub fn rank(query: &str, paths: &[String], limit: usize) -> Vec<FinderMatch> {
+    if query.trim().is_empty() {
+        return paths
+            .iter()
+            .take(limit)
+            .map(|path| FinderMatch {
+                path: path.clone(),
+                score: 0,
+                positions: Vec::new(),
+            })
+            .collect();
+    }
+    let mut matches: Vec<FinderMatch> = paths
+        .iter()
+        .filter_map(|path| fuzzy_match(query, path))
+        .collect();
+    matches.sort_by(|a, b| {
+        (Reverse(a.score), a.path.len(), &a.path).cmp(&(Reverse(b.score), b.path.len(), &b.path))
+    });
+    matches.truncate(limit);
+    matches
+}
+
+/// Score `candidate` against `query`, or `None` if it does not contain the
+/// query's characters in order
+///
+/// Whitespace in the query is ignored. A query without uppercase letters
+/// matches case-insensitively. The file name is tried on its own first, so
+/// `main` prefers `src/main.rs` over `domain/lib.rs`.
+pub fn fuzzy_match(query: &str, candidate: &str) -> Option<FinderMatch> {
+    let ignore_case = !query.chars().any(char::is_uppercase);
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| fold(c, ignore_case))
+        .collect();
+    let chars: Vec<char> = candidate.chars().collect();
+    let name_start = chars.iter().rposition(|&c| c == '/').map_or(0, |i| i + 1);
+
+    let in_name = match_from(&query, &chars, name_start, ignore_case)
+        .map(|positions| (score(&chars, &positions) + BONUS_FILE_NAME, positions));
+    let in_path = match_from(&query, &chars, 0, ignore_case)
+        .map(|positions| (score(&chars, &positions), positions));
+    let (score, positions) = match (in_name, in_path) {
+        (Some(name), Some(path)) if path.0 > name.0 => path,
+        (Some(name), _) => name,
+        (None, path) => path?,
+    };
+    Some(FinderMatch {
+        path: candidate.to_string(),
+        score,
+        positions,
+    })
+}
+
+fn fold(c: char, ignore_case: bool) -> char {
+    if ignore_case {
+        c.to_lowercase().next().unwrap_or(c)
+    } else {
+        c
+    }
+}
+
+/// Positions of `query` in `chars` at or after `start`, matching greedily
+/// but preferring a word start or a run within reach of each character
+fn match_from(
+    query: &[char],
+    chars: &[char],
+    start: usize,
+    ignore_case: bool,
+) -> Option<Vec<usize>> {
+    let mut positions = Vec::with_capacity(query.len());
+    let mut from = start;
+    for (qi, &q) in query.iter().enumerate() {
+        let first = (from..chars.len()).find(|&i| fold(chars[i], ignore_case) == q)?;
+        let mut pick = first;
+        let after_previous = positions.last().is_some_and(|&p: &usize| p + 1 == first);
+        if !after_previous && !is_word_start(chars, first) {
+            // A later word start still leaves room for the rest of the query
+            let rest = &query[qi + 1..];
+            if let Some(better) = (first + 1..chars.len()).find(|&i| {
+                fold(chars[i], ignore_case) == q
+                    && is_word_start(chars, i)
+                    && contains_in_order(rest, &chars[i + 1..], ignore_case)
+            }) {
+                pick = better;
+            }
+        }
+        positions.push(pick);
+        from = pick + 1;
+    }
+    Some(positions)
+}
+
+fn contains_in_order(query: &[char], chars: &[char], ignore_case: bool) -> bool {
+    let mut chars = chars.iter();
+    query
+        .iter()
+        .all(|&q| chars.any(|&c| fold(c, ignore_case) == q))
+}
+
+fn is_word_start(chars: &[char], i: usize) -> bool {
+    match i.checked_sub(1).map(|p| chars[p]) {
+        None => true,
+        Some('/' | '_' | '-' | '.' | ' ') => true,
+        Some(prev) => prev.is_lowercase() && chars[i].is_uppercase(),
+    }
+}
+
+fn score(chars: &[char], positions: &[usize]) -> i64 {
+    let mut total = 0;
+    let mut previous: Option<usize> = None;
+    for &i in positions {
+        total += SCORE_MATCH;
+        if is_word_start(chars, i) {
+            total += BONUS_WORD_START;
+        }
+        match previous {
+            Some(p) if p + 1 == i => total += BONUS_CONSECUTIVE,
+            Some(p) => total -= (PENALTY_GAP * (i - p - 1) as i64).min(MAX_GAP_PENALTY),
+            None => {}
+        }
+        previous = Some(i);
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+    use tempfile::tempdir;
+
+    fn paths(list: &[&str]) -> Vec<String> {
+        list.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn subsequences_match_with_smart_case() {
+        let found = fuzzy_match("apmr", "sb/src/app/main.rs").expect("match");
+        assert_eq!(found.positions.len(), 4);
+        assert!(fuzzy_match("nim", "sb/src/main.rs").is_none());
+        assert!(fuzzy_match("readme", "README.md").is_some());
+        assert!(fuzzy_match("Readme", "README.md").is_none());
+        assert!(fuzzy_match("app rs", "src/app.rs").is_some());
+    }
+
+    #[test]
+    fn file_names_word_starts_and_runs_rank_first() {
+        let all = paths(&[
+            "domain/lib.rs",
+            "src/main.rs",
+            "docs/manual/index.md",
+            "src/event_handler.rs",
+            "src/every.rs",
+        ]);
+        let ranked = rank("main", &all, 10);
+        assert_eq!(ranked[0].path, "src/main.rs");
+        assert_eq!(ranked[0].positions, vec![4, 5, 6, 7]);
+
+        let ranked = rank("evh", &all, 10);
+        assert_eq!(ranked[0].path, "src/event_handler.rs");
+        assert_eq!(ranked.len(), 1);
+
+        assert_eq!(rank("", &all, 2).len(), 2);
+    }
+
+    #[test]
+    fn index_lists_files_and_skips_ignored_ones() {
+        let root = tempdir().expect("tempdir");
+        let dir = root.path();
+        std::fs::write(dir.join(".gitignore"), "build/\n").expect("write .gitignore");
+        std::fs::create_dir_all(dir.join("src/nested")).expect("create src");
+        std::fs::create_dir(dir.join("build")).expect("create build");
+        std::fs::write(dir.join("src/nested/deep.rs"), "").expect("write deep");
+        std::fs::write(dir.join("notes.md"), "").expect("write notes");
+        std::fs::write(dir.join("build/out.bin"), "").expect("write out");
+
+        let index = spawn_file_index(dir.to_path_buf(), true, Vec::new(), false);
+        let files = wait_for(|| index.try_recv().ok());
+        assert_eq!(files, paths(&["notes.md", "src/nested/deep.rs"]));
+
+        let index = spawn_file_index(dir.to_path_buf(), false, Vec::new(), true);
+        let files = wait_for(|| index.try_recv().ok());
+        assert!(files.contains(&"build/out.bin".to_string()));
+        assert!(files.contains(&".gitignore".to_string()));
+    }
+
+    #[test]
+    fn ranker_answers_only_the_latest_query() {
+        let all: Arc<[String]> = paths(&["src/main.rs", "src/event_handler.rs"]).into();
+        let mut ranker = Ranker::spawn();
+        ranker.send("main".into(), Arc::clone(&all));
+        ranker.send("evh".into(), all);
+        assert!(ranker.is_busy());
+
+        let matches = wait_for(|| ranker.try_recv().ok());
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].path, "src/event_handler.rs");
+        assert!(!ranker.is_busy());
+    }
+
+    fn wait_for<T>(mut poll: impl FnMut() -> Option<T>) -> T {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            if let Some(value) = poll() {
+                return value;
+            }
+            assert!(Instant::now() < deadline, "worker never answered");
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
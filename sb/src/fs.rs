@@ -1,4 +1,10 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::{Ordering, Reverse};
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Write};
 use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 #[allow(dead_code)]
 pub fn resolve_link(current_file: &Path, link: &str) -> PathBuf {
@@ -12,3 +18,431 @@ pub fn resolve_link(current_file: &Path, link: &str) -> PathBuf {
             .join(p)
     }
 }
+
+/// Whether `path` is a dotfile or dot-directory, hidden unless asked for
+pub fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+}
+
+/// Whether `path` is itself a symbolic link, without following it
+pub fn is_symlink(path: &Path) -> bool {
+    std::fs::symlink_metadata(path).is_ok_and(|meta| meta.file_type().is_symlink())
+}
+
+/// Whether the file tree should descend into `path`
+///
+/// Symlinked directories are only entered when `follow_symlinks` is set, and
+/// never when they resolve to a directory in `ancestors` (the canonical paths
+/// of the branch being built), so a self-referential link cannot loop.
+pub fn should_descend(path: &Path, follow_symlinks: bool, ancestors: &[PathBuf]) -> bool {
+    if !path.is_dir() {
+        return false;
+    }
+    if !is_symlink(path) {
+        return true;
+    }
+    follow_symlinks
+        && path
+            .canonicalize()
+            .is_ok_and(|target| !ancestors.contains(&target))
+}
+
+/// Order of the entries within a directory of the file tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TreeSort {
+    /// By name, with numbers compared by value
+    #[default]
+    Name,
+    /// Most recently modified first
+    Modified,
+    /// Largest file first, after the directories in name order
+    Size,
+    /// Directories first, then by extension
+    Type,
+}
+
+impl TreeSort {
+    /// The order `O` switches to after this one
+    pub fn next(self) -> Self {
+        match self {
+            TreeSort::Name => TreeSort::Modified,
+            TreeSort::Modified => TreeSort::Size,
+            TreeSort::Size => TreeSort::Type,
+            TreeSort::Type => TreeSort::Name,
+        }
+    }
+
+    /// Short description for the status line
+    pub fn label(self) -> &'static str {
+        match self {
+            TreeSort::Name => "name",
+            TreeSort::Modified => "modified time",
+            TreeSort::Size => "size",
+            TreeSort::Type => "type",
+        }
+    }
+
+    /// Sort `entries` by the paths `path` gives for them
+    ///
+    /// Ties fall back to the name, so the order never depends on how the
+    /// directory happened to be listed.
+    pub fn sort_by_path<T>(self, entries: &mut [T], path: impl Fn(&T) -> &Path) {
+        entries.sort_by_cached_key(|entry| SortKey::new(self, path(entry)));
+    }
+}
+
+/// What [`TreeSort::sort_by_path`] compares, most significant field first
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct SortKey {
+    /// Directories before files when sorting by type or size
+    is_file: bool,
+    /// Modification time or size, larger first
+    rank: Reverse<u128>,
+    /// Lowercase extension when sorting by type
+    extension: String,
+    name: NaturalName,
+}
+
+impl SortKey {
+    fn new(sort: TreeSort, path: &Path) -> Self {
+        let meta = std::fs::metadata(path).ok();
+        let rank = match sort {
+            TreeSort::Modified => meta
+                .as_ref()
+                .and_then(|m| m.modified().ok())
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |d| d.as_nanos()),
+            // A directory's own length says nothing about what it holds
+            TreeSort::Size => meta
+                .as_ref()
+                .filter(|m| !m.is_dir())
+                .map_or(0, |m| u128::from(m.len())),
+            TreeSort::Name | TreeSort::Type => 0,
+        };
+        let by_type = sort == TreeSort::Type;
+        Self {
+            is_file: matches!(sort, TreeSort::Type | TreeSort::Size)
+                && !meta.as_ref().is_some_and(|m| m.is_dir()),
+            rank: Reverse(rank),
+            extension: if by_type {
+                path.extension()
+                    .map(|e| e.to_string_lossy().to_lowercase())
+                    .unwrap_or_default()
+            } else {
+                String::new()
+            },
+            name: NaturalName(
+                path.file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_default(),
+            ),
+        }
+    }
+}
+
+/// A file name ordered by [`natural_cmp`]
+#[derive(PartialEq, Eq)]
+struct NaturalName(String);
+
+impl Ord for NaturalName {
+    fn cmp(&self, other: &Self) -> Ordering {
+        natural_cmp(&self.0, &other.0).then_with(|| self.0.cmp(&other.0))
+    }
+}
+
+impl PartialOrd for NaturalName {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Compare names ignoring case, with runs of digits compared by value, so
+/// `file2` comes before `file10`
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a.chars().peekable(), b.chars().peekable());
+    loop {
+        let ord = match (a.peek().copied(), b.peek().copied()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let x = take_digits(&mut a);
+                let y = take_digits(&mut b);
+                let (x, y) = (x.trim_start_matches('0'), y.trim_start_matches('0'));
+                x.len().cmp(&y.len()).then_with(|| x.cmp(y))
+            }
+            (Some(x), Some(y)) => {
+                a.next();
+                b.next();
+                x.to_lowercase().cmp(y.to_lowercase())
+            }
+        };
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+}
+
+fn take_digits(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> String {
+    let mut digits = String::new();
+    while let Some(c) = chars.next_if(char::is_ascii_digit) {
+        digits.push(c);
+    }
+    digits
+}
+
+/// Leading bytes checked for a NUL when deciding whether a file is binary
+const BINARY_SNIFF_LEN: usize = 8000;
+
+/// A file read for display, or the reason it was not loaded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileContents {
+    Text(String),
+    /// NUL bytes near the start, or not valid UTF-8
+    Binary(u64),
+    /// Larger than the size limit
+    TooLarge(u64),
+}
+
+impl FileContents {
+    /// Text shown instead of a file that was not loaded
+    pub fn placeholder(&self) -> Option<String> {
+        match self {
+            FileContents::Text(_) => None,
+            FileContents::Binary(len) => {
+                Some(format!("(binary file, {len} bytes — not displayed)"))
+            }
+            FileContents::TooLarge(len) => {
+                Some(format!("(file too large, {len} bytes — not displayed)"))
+            }
+        }
+    }
+}
+
+/// Read `path` as text unless it is binary or larger than `max_size` bytes
+pub fn read_text_file(path: &Path, max_size: u64) -> io::Result<FileContents> {
+    let len = std::fs::metadata(path)?.len();
+    if len > max_size {
+        return Ok(FileContents::TooLarge(len));
+    }
+    // The file may grow between the stat and the read
+    let mut bytes = Vec::new();
+    File::open(path)?
+        .take(max_size.saturating_add(1))
+        .read_to_end(&mut bytes)?;
+    let len = bytes.len() as u64;
+    if len > max_size {
+        return Ok(FileContents::TooLarge(len));
+    }
+    if bytes[..bytes.len().min(BINARY_SNIFF_LEN)].contains(&0) {
+        return Ok(FileContents::Binary(len));
+    }
+    Ok(String::from_utf8(bytes).map_or(FileContents::Binary(len), FileContents::Text))
+}
+
+/// Whether `path` looks binary, judged by its leading bytes as in [`read_text_file`]
+///
+/// Files that cannot be read count as text.
+pub fn is_binary_file(path: &Path) -> bool {
+    let mut head = Vec::new();
+    let read =
+        File::open(path).and_then(|file| file.take(BINARY_SNIFF_LEN as u64).read_to_end(&mut head));
+    if read.is_err() {
+        return false;
+    }
+    // A character cut off at the end of the sniffed bytes is still text
+    head.contains(&0) || std::str::from_utf8(&head).is_err_and(|e| e.error_len().is_some())
+}
+
+/// Replace the contents of `path` so that it is never left half-written
+///
+/// The data goes to a hidden temporary file in the same directory, is synced
+/// to disk and then renamed over the original, keeping its permissions. A
+/// symlink is followed and its target replaced. If any step fails the
+/// original is untouched and the temporary file is removed again.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no file name"))?;
+    let dir = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let mut n = 0;
+    let (tmp, mut file) = loop {
+        let tmp = dir.join(format!(
+            ".{}.sb-save-{}-{n}",
+            name.to_string_lossy(),
+            std::process::id()
+        ));
+        match OpenOptions::new().write(true).create_new(true).open(&tmp) {
+            Ok(file) => break (tmp, file),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => n += 1,
+            Err(e) => return Err(e),
+        }
+    };
+    let result = file
+        .write_all(contents)
+        .and_then(|()| match std::fs::metadata(&path) {
+            Ok(meta) => file.set_permissions(meta.permissions()),
+            Err(_) => Ok(()),
+        })
+        .and_then(|()| file.sync_all())
+        .and_then(|()| std::fs::rename(&tmp, &path));
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+    result
+}
+
+/// Recovery copy of unsaved edits to `path`, e.g. `notes/.todo.md.sb-swap`
+///
+/// The suffix differs from Vim's `.swp` so a file open in both never has its
+/// Vim swap file overwritten.
+pub fn swap_path(path: &Path) -> PathBuf {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!(".{name}.sb-swap"))
+}
+
+/// Directory under the workspace root that deleted entries are moved into
+pub const TRASH_DIR: &str = ".sb-trash";
+
+/// Whether `path` is the workspace trash, kept out of the tree, finder and search
+pub fn is_trash(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| name == TRASH_DIR)
+}
+
+/// Move `path` into a fresh slot of the workspace trash and return where it went
+///
+/// Every call gets its own directory so equal names never collide, and the
+/// trash carries a `*` `.gitignore` so it stays out of `git status`.
+pub fn move_to_trash(workspace_root: &Path, path: &Path) -> io::Result<PathBuf> {
+    let name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no file name"))?;
+    let trash = workspace_root.join(TRASH_DIR);
+    std::fs::create_dir_all(&trash)?;
+    let gitignore = trash.join(".gitignore");
+    if !gitignore.exists() {
+        std::fs::write(&gitignore, "*\n")?;
+    }
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or_default();
+    let mut n = 0;
+    let slot = loop {
+        let slot = trash.join(format!("{stamp}-{n}"));
+        if !slot.exists() {
+            break slot;
+        }
+        n += 1;
+    };
+    std::fs::create_dir(&slot)?;
+    let dest = slot.join(name);
+    std::fs::rename(path, &dest)?;
+    Ok(dest)
+}
+
+/// Move an entry returned by [`move_to_trash`] back to `original`
+pub fn restore_from_trash(trashed: &Path, original: &Path) -> io::Result<()> {
+    if original.symlink_metadata().is_ok() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", original.display()),
+        ));
+    }
+    if let Some(parent) = original.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::rename(trashed, original)?;
+    if let Some(slot) = trashed.parent() {
+        let _ = std::fs::remove_dir(slot);
+    }
+    Ok(())
+}
+
+/// Delete the workspace trash for good, returning how many entries it held
+pub fn empty_trash(workspace_root: &Path) -> io::Result<usize> {
+    let trash = workspace_root.join(TRASH_DIR);
+    let count = match std::fs::read_dir(&trash) {
+        Ok(slots) => slots
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_name() != ".gitignore")
+            .count(),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e),
+    };
+    std::fs::remove_dir_all(&trash)?;
+    Ok(count)
+}
+
+/// Work out the new names for a bulk rename without touching the disk
+///
+/// `pattern` is either `s/find/replace/`, replacing every literal occurrence
+/// in the file name, or a template where `{name}` is the file stem, `{ext}`
+/// the extension and `{n}` the 1-based position in `sources`. Files whose
+/// name stays the same are left out of the plan. Two files ending up with
+/// the same name, or a new name that already exists, fails the whole plan.
+pub fn plan_bulk_rename(sources: &[PathBuf], pattern: &str) -> io::Result<Vec<(PathBuf, PathBuf)>> {
+    let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidInput, message);
+    let collision = |message: String| io::Error::new(io::ErrorKind::AlreadyExists, message);
+    if pattern.trim().is_empty() {
+        return Err(invalid("Pattern is empty".into()));
+    }
+    let replace = match pattern.strip_prefix("s/") {
+        Some(rest) => {
+            let rest = rest.strip_suffix('/').unwrap_or(rest);
+            match rest.split_once('/') {
+                Some((find, with)) if !find.is_empty() => Some((find, with)),
+                _ => return Err(invalid("Use s/find/replace/".into())),
+            }
+        }
+        None => None,
+    };
+
+    let mut plan = Vec::new();
+    let mut targets = HashSet::new();
+    for (i, source) in sources.iter().enumerate() {
+        let lossy = |part: Option<&std::ffi::OsStr>| {
+            part.map(|p| p.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        };
+        let new_name = match replace {
+            Some((find, with)) => lossy(source.file_name()).replace(find, with),
+            None => pattern
+                .replace("{name}", &lossy(source.file_stem()))
+                .replace("{ext}", &lossy(source.extension()))
+                .replace("{n}", &(i + 1).to_string()),
+        };
+        if new_name.is_empty()
+            || new_name == "."
+            || new_name == ".."
+            || new_name.contains(['/', '\\'])
+        {
+            return Err(invalid(format!("'{new_name}' is not a valid file name")));
+        }
+        let target = source.with_file_name(&new_name);
+        if !targets.insert(target.clone()) {
+            return Err(collision(format!(
+                "More than one file would be named {new_name}"
+            )));
+        }
+        if target != *source {
+            plan.push((source.clone(), target));
+        }
+    }
+    if let Some((_, taken)) = plan
+        .iter()
+        .find(|(_, target)| target.symlink_metadata().is_ok())
+    {
+        return Err(collision(format!(
+            "{} already exists",
+            taken.file_name().unwrap_or_default().to_string_lossy()
+        )));
+    }
+    Ok(plan)
+}
@@ -2,7 +2,7 @@
 //!
 //! This module provides Git repository detection, status tracking, and diff functionality.
 
-use git2::{Repository, Status, StatusOptions};
+use git2::{Branch, ErrorCode, Repository, Status, StatusOptions};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
@@ -54,6 +54,71 @@ impl From<Status> for FileStatus {
     }
 }
 
+/// Per-line attribution parsed from `git blame --line-porcelain`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlameLine {
+    /// Full commit hash (all zeros for uncommitted lines)
+    pub hash: String,
+    pub author: String,
+    /// Author time as a Unix timestamp
+    pub author_time: i64,
+}
+
+impl BlameLine {
+    /// True for lines that are not yet committed.
+    pub fn is_uncommitted(&self) -> bool {
+        self.hash.chars().all(|c| c == '0')
+    }
+
+    pub fn short_hash(&self) -> &str {
+        &self.hash[..self.hash.len().min(7)]
+    }
+
+    /// Up to three uppercase initials from the author name.
+    pub fn author_initials(&self) -> String {
+        self.author
+            .split_whitespace()
+            .filter_map(|w| w.chars().next())
+            .flat_map(char::to_uppercase)
+            .take(3)
+            .collect()
+    }
+}
+
+/// Parse `git blame --line-porcelain` output into one entry per file line.
+pub fn parse_blame_porcelain(output: &str) -> Vec<BlameLine> {
+    let mut lines = Vec::new();
+    let mut current: Option<BlameLine> = None;
+    for line in output.lines() {
+        if let Some(_content) = line.strip_prefix('\t') {
+            if let Some(entry) = current.take() {
+                lines.push(entry);
+            }
+            continue;
+        }
+        match current.as_mut() {
+            None => {
+                let hash = line.split_whitespace().next().unwrap_or_default();
+                if hash.len() >= 40 && hash.chars().all(|c| c.is_ascii_hexdigit()) {
+                    current = Some(BlameLine {
+                        hash: hash.to_string(),
+                        author: String::new(),
+                        author_time: 0,
+                    });
+                }
+            }
+            Some(entry) => {
+                if let Some(author) = line.strip_prefix("author ") {
+                    entry.author = author.to_string();
+                } else if let Some(time) = line.strip_prefix("author-time ") {
+                    entry.author_time = time.trim().parse().unwrap_or(0);
+                }
+            }
+        }
+    }
+    lines
+}
+
 pub struct GitRepository {
     repo: Repository,
     root: PathBuf,
@@ -90,6 +155,56 @@ impl GitRepository {
         &self.root
     }
 
+    /// Name of the checked-out branch, or the short commit id when HEAD is
+    /// detached
+    pub fn current_branch(&self) -> Result<String, GitError> {
+        let head = match self.repo.head() {
+            Ok(head) => head,
+            // No commits yet: HEAD still names the branch it will create
+            Err(e) if e.code() == ErrorCode::UnbornBranch => {
+                let head = self.repo.find_reference("HEAD")?;
+                let target = head.symbolic_target().unwrap_or("HEAD");
+                return Ok(target.trim_start_matches("refs/heads/").to_string());
+            }
+            Err(e) => return Err(e.into()),
+        };
+        if head.is_branch() {
+            if let Some(name) = head.shorthand() {
+                return Ok(name.to_string());
+            }
+        }
+        let commit = head.peel_to_commit()?;
+        Ok(format!("{:.7}", commit.id()))
+    }
+
+    /// Commits the current branch is ahead of and behind its upstream
+    ///
+    /// `(0, 0)` when HEAD is detached or the branch has no upstream.
+    pub fn ahead_behind(&self) -> Result<(usize, usize), GitError> {
+        let head = match self.repo.head() {
+            Ok(head) if head.is_branch() => head,
+            Ok(_) => return Ok((0, 0)),
+            Err(e) if e.code() == ErrorCode::UnbornBranch => return Ok((0, 0)),
+            Err(e) => return Err(e.into()),
+        };
+        let branch = Branch::wrap(head);
+        let upstream = match branch.upstream() {
+            Ok(upstream) => upstream,
+            Err(e) if e.code() == ErrorCode::NotFound => return Ok((0, 0)),
+            Err(e) => return Err(e.into()),
+        };
+        match (branch.get().target(), upstream.get().target()) {
+            (Some(local), Some(remote)) => Ok(self.repo.graph_ahead_behind(local, remote)?),
+            _ => Ok((0, 0)),
+        }
+    }
+
+    /// Branch and upstream counts for the status bar, e.g. `⎇ main ↑2↓0`
+    pub fn branch_label(&self) -> Result<String, GitError> {
+        let (ahead, behind) = self.ahead_behind()?;
+        Ok(format!("⎇ {} ↑{ahead}↓{behind}", self.current_branch()?))
+    }
+
     /// Get the Git status of all files in the repository
     pub fn status(&self) -> Result<HashMap<PathBuf, FileStatus>, GitError> {
         let mut status_opts = StatusOptions::new();
@@ -179,74 +294,46 @@ impl GitRepository {
         Ok(diff_output)
     }
 
-    /// Get a formatted Git status summary
-    pub fn status_summary(&self) -> Result<String, GitError> {
-        let statuses = self.status()?;
-
-        if statuses.is_empty() {
-            return Ok("Working tree clean".to_string());
-        }
-
-        let mut summary = String::new();
-        let mut added = 0;
-        let mut modified = 0;
-        let mut deleted = 0;
-        let mut untracked = 0;
-        let mut conflicted = 0;
-
-        for status in statuses.values() {
-            match status {
-                FileStatus::Added => added += 1,
-                FileStatus::Modified => modified += 1,
-                FileStatus::Deleted => deleted += 1,
-                FileStatus::Untracked => untracked += 1,
-                FileStatus::Conflicted => conflicted += 1,
-                _ => {}
-            }
-        }
-
-        if added > 0 {
-            summary.push_str(&format!("Added: {} ", added));
-        }
-        if modified > 0 {
-            summary.push_str(&format!("Modified: {} ", modified));
-        }
-        if deleted > 0 {
-            summary.push_str(&format!("Deleted: {} ", deleted));
-        }
-        if untracked > 0 {
-            summary.push_str(&format!("Untracked: {} ", untracked));
-        }
-        if conflicted > 0 {
-            summary.push_str(&format!("Conflicted: {} ", conflicted));
-        }
+    /// Remove a file using Git
+    #[allow(dead_code)]
+    pub fn remove_file<P: AsRef<Path>>(&self, path: P) -> Result<(), GitError> {
+        self.remove_from_index(path.as_ref())?;
 
-        if summary.is_empty() {
-            summary = "Working tree clean".to_string();
+        // Also remove from working directory
+        if path.as_ref().exists() {
+            std::fs::remove_file(path.as_ref())?;
         }
 
-        Ok(summary.trim().to_string())
+        Ok(())
     }
 
-    /// Remove a file using Git
-    pub fn remove_file<P: AsRef<Path>>(&self, path: P) -> Result<(), GitError> {
-        let relative_path = path
-            .as_ref()
-            .strip_prefix(&self.root)
-            .map_err(|_| GitError::Git(git2::Error::from_str("Path not in repository")))?;
-
+    /// Remove a file from the index, leaving the working tree alone
+    ///
+    /// Returns `false` without touching the index if the file was not staged.
+    pub fn remove_from_index<P: AsRef<Path>>(&self, path: P) -> Result<bool, GitError> {
+        let relative_path = self.relative_path(path.as_ref())?;
         let mut index = self.repo.index()?;
+        if index.get_path(relative_path, 0).is_none() {
+            return Ok(false);
+        }
         index.remove_path(relative_path)?;
         index.write()?;
+        Ok(true)
+    }
 
-        // Also remove from working directory
-        if path.as_ref().exists() {
-            std::fs::remove_file(path.as_ref())?;
-        }
-
+    /// Stage a file, e.g. to put back one taken out with [`Self::remove_from_index`]
+    pub fn add_to_index<P: AsRef<Path>>(&self, path: P) -> Result<(), GitError> {
+        let mut index = self.repo.index()?;
+        index.add_path(self.relative_path(path.as_ref())?)?;
+        index.write()?;
         Ok(())
     }
 
+    fn relative_path<'a>(&self, path: &'a Path) -> Result<&'a Path, GitError> {
+        path.strip_prefix(&self.root)
+            .map_err(|_| GitError::Git(git2::Error::from_str("Path not in repository")))
+    }
+
     /// Move a file using Git (git mv)
     pub fn move_file<P: AsRef<Path>>(&self, from: P, to: P) -> Result<(), GitError> {
         let from_relative = from
@@ -271,6 +358,53 @@ impl GitRepository {
     }
 }
 
+/// Summarize a status map as counts per kind, e.g. `Modified: 2 Untracked: 1`
+pub fn summarize_status(statuses: &HashMap<PathBuf, FileStatus>) -> String {
+    if statuses.is_empty() {
+        return "Working tree clean".to_string();
+    }
+
+    let mut summary = String::new();
+    let mut added = 0;
+    let mut modified = 0;
+    let mut deleted = 0;
+    let mut untracked = 0;
+    let mut conflicted = 0;
+
+    for status in statuses.values() {
+        match status {
+            FileStatus::Added => added += 1,
+            FileStatus::Modified => modified += 1,
+            FileStatus::Deleted => deleted += 1,
+            FileStatus::Untracked => untracked += 1,
+            FileStatus::Conflicted => conflicted += 1,
+            _ => {}
+        }
+    }
+
+    if added > 0 {
+        summary.push_str(&format!("Added: {} ", added));
+    }
+    if modified > 0 {
+        summary.push_str(&format!("Modified: {} ", modified));
+    }
+    if deleted > 0 {
+        summary.push_str(&format!("Deleted: {} ", deleted));
+    }
+    if untracked > 0 {
+        summary.push_str(&format!("Untracked: {} ", untracked));
+    }
+    if conflicted > 0 {
+        summary.push_str(&format!("Conflicted: {} ", conflicted));
+    }
+
+    if summary.is_empty() {
+        summary = "Working tree clean".to_string();
+    }
+
+    summary.trim().to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -283,6 +417,42 @@ mod tests {
         assert!(!GitRepository::is_git_repo(temp_dir.path()));
     }
 
+    #[test]
+    fn test_branch_label_counts_commits_against_upstream() {
+        let temp_dir = TempDir::new().unwrap();
+        let raw = Repository::init(temp_dir.path()).unwrap();
+        let mut config = raw.config().unwrap();
+        config.set_str("user.name", "Test").unwrap();
+        config.set_str("user.email", "test@test.com").unwrap();
+        let repo = GitRepository::open(temp_dir.path()).unwrap();
+        let branch = repo.current_branch().unwrap();
+        assert_eq!(repo.branch_label().unwrap(), format!("⎇ {branch} ↑0↓0"));
+
+        let sig = raw.signature().unwrap();
+        let tree = raw
+            .find_tree(raw.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let base = raw
+            .commit(Some("HEAD"), &sig, &sig, "base", &tree, &[])
+            .unwrap();
+        let base = raw.find_commit(base).unwrap();
+        raw.branch("upstream", &base, false).unwrap();
+        raw.find_branch(&branch, git2::BranchType::Local)
+            .unwrap()
+            .set_upstream(Some("upstream"))
+            .unwrap();
+        let mut parent = base;
+        for message in ["one", "two"] {
+            let id = raw
+                .commit(Some("HEAD"), &sig, &sig, message, &tree, &[&parent])
+                .unwrap();
+            parent = raw.find_commit(id).unwrap();
+        }
+
+        assert_eq!(repo.ahead_behind().unwrap(), (2, 0));
+        assert_eq!(repo.branch_label().unwrap(), format!("⎇ {branch} ↑2↓0"));
+    }
+
     #[test]
     fn test_file_status_conversion() {
         let status = Status::WT_NEW;
@@ -293,4 +463,29 @@ mod tests {
         let file_status = FileStatus::from(status);
         assert_eq!(file_status, FileStatus::Modified);
     }
+
+    #[test]
+    fn test_parse_blame_porcelain() {
+        let output = "\
+1234567890abcdef1234567890abcdef12345678 1 1 1
+author Ada Lovelace
+author-mail <ada@example.com>
+author-time 1700000000
+summary first
+filename notes.md
+\t# Title
+0000000000000000000000000000000000000000 2 2 1
+author Not Committed Yet
+author-time 1700000100
+filename notes.md
+\tnew line
+";
+        let blame = parse_blame_porcelain(output);
+        assert_eq!(blame.len(), 2);
+        assert_eq!(blame[0].short_hash(), "1234567");
+        assert_eq!(blame[0].author_initials(), "AL");
+        assert_eq!(blame[0].author_time, 1_700_000_000);
+        assert!(!blame[0].is_uncommitted());
+        assert!(blame[1].is_uncommitted());
+    }
 }
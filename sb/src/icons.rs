@@ -0,0 +1,154 @@
+//! File type icons for the tree
+//!
+//! [`FileKind::of`] sorts an entry by its extension and [`IconSet::icon`]
+//! picks the marker drawn before its name. Emoji work in most terminals,
+//! Nerd Font glyphs need a patched font, and the ASCII set works everywhere;
+//! which one is used comes from `"tree_icons"` in the settings file or
+//! `--icons`.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Glyphs drawn before tree entries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum IconSet {
+    /// Emoji such as 🦀 and 📝
+    #[default]
+    Emoji,
+    /// Nerd Font glyphs; needs a patched font
+    Nerd,
+    /// Single ASCII characters, for terminals without either
+    Ascii,
+}
+
+/// What an entry holds, as far as its icon is concerned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileKind {
+    Directory,
+    Rust,
+    Markdown,
+    /// Source code other than Rust
+    Code,
+    /// Configuration and structured data, e.g. TOML, JSON, YAML
+    Config,
+    Image,
+    Video,
+    Audio,
+    Archive,
+    Pdf,
+    /// Anything else
+    File,
+}
+
+impl FileKind {
+    /// Kind of the entry at `path`, from its extension unless it is a directory
+    pub fn of(path: &Path) -> Self {
+        if path.is_dir() {
+            return FileKind::Directory;
+        }
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+        match ext.as_str() {
+            "rs" => FileKind::Rust,
+            "md" | "markdown" => FileKind::Markdown,
+            "toml" | "json" | "yaml" | "yml" | "ini" | "xml" | "csv" | "lock" => FileKind::Config,
+            "png" | "jpg" | "jpeg" | "gif" | "bmp" | "webp" | "svg" | "ico" => FileKind::Image,
+            "mp4" | "mov" | "mkv" | "webm" | "avi" | "m4v" => FileKind::Video,
+            "mp3" | "wav" | "flac" | "ogg" | "m4a" => FileKind::Audio,
+            "zip" | "tar" | "gz" | "tgz" | "bz2" | "xz" | "7z" => FileKind::Archive,
+            "pdf" => FileKind::Pdf,
+            _ if crate::preview::is_code_file(path) => FileKind::Code,
+            _ => FileKind::File,
+        }
+    }
+}
+
+impl IconSet {
+    /// Marker drawn before an entry of `kind`
+    pub fn icon(self, kind: FileKind) -> &'static str {
+        match self {
+            IconSet::Emoji => match kind {
+                FileKind::Directory => "📁",
+                FileKind::Rust => "🦀",
+                FileKind::Markdown => "📝",
+                FileKind::Code => "📜",
+                FileKind::Config => "🔧",
+                FileKind::Image => "🎨",
+                FileKind::Video => "🎬",
+                FileKind::Audio => "🎵",
+                FileKind::Archive => "📦",
+                FileKind::Pdf => "📕",
+                FileKind::File => "📄",
+            },
+            IconSet::Nerd => match kind {
+                FileKind::Directory => "\u{f07b}",
+                FileKind::Rust => "\u{e7a8}",
+                FileKind::Markdown => "\u{e73e}",
+                FileKind::Code => "\u{f121}",
+                FileKind::Config => "\u{e615}",
+                FileKind::Image => "\u{f1c5}",
+                FileKind::Video => "\u{f1c8}",
+                FileKind::Audio => "\u{f1c7}",
+                FileKind::Archive => "\u{f1c6}",
+                FileKind::Pdf => "\u{f1c1}",
+                FileKind::File => "\u{f15b}",
+            },
+            IconSet::Ascii => match kind {
+                FileKind::Directory => "+",
+                FileKind::Rust | FileKind::Code => "*",
+                FileKind::Markdown => "#",
+                FileKind::Config => "=",
+                FileKind::Image | FileKind::Video | FileKind::Audio => "~",
+                FileKind::Archive => "%",
+                FileKind::Pdf | FileKind::File => "-",
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn kinds_follow_extension_and_directories() {
+        let root = tempdir().expect("tempdir");
+        assert_eq!(FileKind::of(root.path()), FileKind::Directory);
+        assert_eq!(FileKind::of(Path::new("src/main.RS")), FileKind::Rust);
+        assert_eq!(FileKind::of(Path::new("notes.md")), FileKind::Markdown);
+        assert_eq!(FileKind::of(Path::new("app.py")), FileKind::Code);
+        assert_eq!(FileKind::of(Path::new("Cargo.toml")), FileKind::Config);
+        assert_eq!(FileKind::of(Path::new("logo.png")), FileKind::Image);
+        assert_eq!(FileKind::of(Path::new("LICENSE")), FileKind::File);
+    }
+
+    #[test]
+    fn ascii_icons_are_ascii() {
+        let kinds = [
+            FileKind::Directory,
+            FileKind::Rust,
+            FileKind::Markdown,
+            FileKind::Code,
+            FileKind::Config,
+            FileKind::Image,
+            FileKind::Video,
+            FileKind::Audio,
+            FileKind::Archive,
+            FileKind::Pdf,
+            FileKind::File,
+        ];
+        assert!(kinds
+            .iter()
+            .all(|&kind| IconSet::Ascii.icon(kind).is_ascii()));
+        assert_eq!(IconSet::Emoji.icon(FileKind::Rust), "🦀");
+        assert_eq!(
+            serde_json::from_str::<IconSet>("\"nerd\"").expect("parse"),
+            IconSet::Nerd
+        );
+    }
+}
@@ -3,10 +3,17 @@
 //! A secure, performant terminal-based file browser with markdown preview capabilities.
 
 pub mod app;
+pub mod copy;
 pub mod editor;
+pub mod error;
 pub mod event_handler;
+pub mod finder;
 pub mod fs;
+pub mod icons;
 pub mod preview;
+pub mod raster;
+pub mod search;
+pub mod view;
 
 // New modules for security and configuration
 pub mod config;
@@ -16,8 +23,8 @@ pub mod security;
 // Test modules are inline within each module file
 
 // Re-export commonly used types
-pub use app::{App, Focus, OpMode};
-pub use config::Config;
+pub use app::{App, EditorLaunch, FileChangePrompt, Focus, OpMode};
+pub use config::{Config, Settings};
 pub use event_handler::{handle_key_event, AppMode};
 pub use git::{FileStatus, GitError, GitRepository};
 pub use security::{check_file_size, validate_path, SecurityError};
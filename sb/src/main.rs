@@ -2,7 +2,10 @@ use anyhow::Result;
 use clap::Parser;
 use std::io::{self};
 use std::path::PathBuf;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+/// How long the main loop sleeps waiting for input when nothing is animating
+const IDLE_POLL_TIMEOUT: Duration = Duration::from_secs(1);
 
 /// Terminal Markdown Browser/Editor with Git integration, syntax highlighting, and media support
 #[derive(Parser, Debug)]
@@ -16,37 +19,130 @@ struct Args {
     /// Root directory to browse (defaults to current directory)
     #[arg(default_value = ".")]
     root: PathBuf,
+
+    /// Frame rate used when decoding video previews
+    #[arg(long, default_value_t = app::DEFAULT_VIDEO_FPS, value_parser = clap::value_parser!(u32).range(1..=60))]
+    video_fps: u32,
+
+    /// Largest file (in bytes) loaded into the editor; bigger or binary files show a placeholder
+    #[arg(long, default_value_t = app::DEFAULT_MAX_OPEN_SIZE)]
+    max_open_size: u64,
+
+    /// Show files matched by .gitignore in the tree (toggle with I)
+    #[arg(long)]
+    no_ignore: bool,
+
+    /// Extra gitignore-style pattern to hide from the tree (repeatable)
+    #[arg(long = "ignore", value_name = "PATTERN")]
+    ignore_patterns: Vec<String>,
+
+    /// Descend into symlinked directories in the tree (cycles are skipped)
+    #[arg(long)]
+    follow_symlinks: bool,
+
+    /// Directory levels below the root the tree can expand (overrides the settings file)
+    #[arg(long, value_name = "N")]
+    max_depth: Option<usize>,
+
+    /// Icons drawn before tree entries (overrides the settings file)
+    #[arg(long, value_enum, value_name = "SET")]
+    icons: Option<icons::IconSet>,
+
+    /// Seconds between autosaves of unsaved edits to `.<name>.sb-swap`; 0
+    /// turns it off (overrides the settings file)
+    #[arg(long, value_name = "SECS")]
+    autosave: Option<u64>,
+
+    /// Graphics protocol for images and video; `auto` asks the terminal
+    #[arg(long, value_enum, default_value_t = ImageProtocol::Auto)]
+    image_protocol: ImageProtocol,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum ImageProtocol {
+    Auto,
+    Halfblocks,
+    Sixel,
+    Kitty,
+    Iterm2,
+}
+
+impl ImageProtocol {
+    fn forced(self) -> Option<ratatui_image::picker::ProtocolType> {
+        use ratatui_image::picker::ProtocolType;
+        match self {
+            ImageProtocol::Auto => None,
+            ImageProtocol::Halfblocks => Some(ProtocolType::Halfblocks),
+            ImageProtocol::Sixel => Some(ProtocolType::Sixel),
+            ImageProtocol::Kitty => Some(ProtocolType::Kitty),
+            ImageProtocol::Iterm2 => Some(ProtocolType::Iterm2),
+        }
+    }
 }
 
 use crossterm::{
     event::{
-        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyModifiers,
-        MouseEventKind,
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        Event, KeyCode, KeyEvent, KeyModifiers, KeyboardEnhancementFlags, MouseButton,
+        MouseEventKind, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
     },
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{
+        disable_raw_mode, enable_raw_mode, supports_keyboard_enhancement, EnterAlternateScreen,
+        LeaveAlternateScreen,
+    },
 };
 use ratatui::{prelude::*, widgets::*};
 use tui_textarea::TextArea;
 
 mod app;
 use app::*;
-mod error;
-mod preview;
-use preview::*;
+// Only the saved pane layout is used by the binary
+#[allow(dead_code)]
+mod config;
+mod copy;
 mod editor;
+mod error;
 mod event_handler;
+mod finder;
 mod fs;
 mod git;
-use event_handler::handle_key_event;
+mod icons;
+mod preview;
+mod raster;
+mod search;
+#[allow(dead_code)]
+mod security;
+mod view;
+use event_handler::{handle_key_event, handle_paste_event};
 
 // Ensures terminal is restored even if the app panics or exits abruptly
-struct TermGuard;
+struct TermGuard {
+    /// Keyboard enhancement flags were pushed and are still to be popped
+    keyboard_enhanced: bool,
+}
+
+impl TermGuard {
+    /// Pop the keyboard enhancement flags, if pushed, so dropping won't again
+    fn pop_keyboard_enhancement(&mut self) -> io::Result<()> {
+        if std::mem::take(&mut self.keyboard_enhanced) {
+            execute!(io::stdout(), PopKeyboardEnhancementFlags)?;
+        }
+        Ok(())
+    }
+}
+
 impl Drop for TermGuard {
     fn drop(&mut self) {
-        let _ = disable_raw_mode();
+        let _ = self.pop_keyboard_enhancement();
         let mut stdout = std::io::stdout();
-        let _ = execute!(stdout, LeaveAlternateScreen, DisableMouseCapture);
+        let _ = disable_raw_mode();
+        let _ = execute!(
+            stdout,
+            LeaveAlternateScreen,
+            DisableMouseCapture,
+            DisableBracketedPaste
+        );
     }
 }
 
@@ -58,20 +154,85 @@ fn main() -> Result<()> {
         args.root
     };
     let mut app = App::new(root)?;
-    run(&mut app)
+    app.video_fps = args.video_fps;
+    app.max_open_size = args.max_open_size;
+    app.no_color = config::colors_disabled_from_env();
+    if args.no_ignore || !args.ignore_patterns.is_empty() {
+        app.set_ignore_options(!args.no_ignore, args.ignore_patterns);
+    }
+    if args.follow_symlinks {
+        app.set_follow_symlinks(true);
+    }
+    if args.max_depth.is_some() {
+        app.set_tree_max_depth(args.max_depth);
+    }
+    if let Some(icons) = args.icons {
+        app.set_tree_icons(icons);
+    }
+    if let Some(secs) = args.autosave {
+        app.autosave_interval = (secs > 0).then(|| Duration::from_secs(secs));
+    }
+    run(&mut app, args.image_protocol)
 }
 
-fn run(app: &mut App) -> Result<()> {
+fn run(app: &mut App, image_protocol: ImageProtocol) -> Result<()> {
     // Create a guard to always restore terminal state on exit/panic
-    let _tg = TermGuard;
+    let mut guard = TermGuard {
+        keyboard_enhanced: false,
+    };
     // Terminal setup
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(
+        stdout,
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableBracketedPaste
+    )?;
+    // Ask for kitty-protocol key reports so Ctrl+I and Tab arrive distinct
+    app.keyboard_enhanced = matches!(supports_keyboard_enhancement(), Ok(true));
+    if app.keyboard_enhanced {
+        execute!(
+            stdout,
+            PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES)
+        )?;
+        guard.keyboard_enhanced = true;
+    }
+    // Ask the terminal for sixel/kitty/iTerm2 support once, now that raw mode is on
+    app.image_picker = preview::detect_picker(image_protocol.forced());
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
+    app.on_start();
+
+    let result = run_loop(&mut terminal, app);
+    app.on_exit();
+
+    // Restore terminal
+    guard.pop_keyboard_enhancement()?;
+    let mut stdout = std::io::stdout();
+    disable_raw_mode()?;
+    execute!(
+        stdout,
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        DisableBracketedPaste
+    )?;
+    result
+}
 
-    // Main loop
+/// Drop every color from the frame, drawing highlighted cells reversed
+fn strip_colors(buf: &mut Buffer) {
+    for cell in &mut buf.content {
+        if cell.bg != Color::Reset {
+            cell.modifier.insert(Modifier::REVERSED);
+        }
+        cell.fg = Color::Reset;
+        cell.bg = Color::Reset;
+    }
+}
+
+/// Draw and handle events until the app quits
+fn run_loop(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut App) -> Result<()> {
     while !app.wants_quit() {
         // Store any rendering error to handle after draw completes
         let mut render_error: Option<anyhow::Error> = None;
@@ -79,11 +240,15 @@ fn run(app: &mut App) -> Result<()> {
             if let Err(e) = ui(f, app) {
                 render_error = Some(e);
             }
+            if app.no_color {
+                strip_colors(f.buffer_mut());
+            }
         })?;
         if let Some(e) = render_error {
             return Err(e);
         }
-        if event::poll(Duration::from_millis(200))? {
+        let timeout = app.desired_tick_interval().unwrap_or(IDLE_POLL_TIMEOUT);
+        if event::poll(timeout)? {
             match event::read()? {
                 Event::Key(k) => {
                     if handle_key_event(app, k).is_none() {
@@ -100,7 +265,7 @@ fn run(app: &mut App) -> Result<()> {
                                 ));
                             }
                         } else if matches!(app.focus, Focus::Preview) {
-                            for _ in 0..3 {
+                            for _ in 0..app.scroll_step() {
                                 app.move_cursor_down();
                             }
                         }
@@ -114,12 +279,12 @@ fn run(app: &mut App) -> Result<()> {
                                 ));
                             }
                         } else if matches!(app.focus, Focus::Preview) {
-                            for _ in 0..3 {
+                            for _ in 0..app.scroll_step() {
                                 app.move_cursor_up();
                             }
                         }
                     }
-                    MouseEventKind::Down(_) => {
+                    MouseEventKind::Down(button) => {
                         // Get terminal size for proper calculations
                         let terminal_size = crossterm::terminal::size().unwrap_or((80, 24));
                         let left_pane_width = app.calculate_left_pane_width(terminal_size.0);
@@ -137,10 +302,20 @@ fn run(app: &mut App) -> Result<()> {
 
                             // Check for Ctrl+Click for multi-selection
                             if me.modifiers.contains(KeyModifiers::CONTROL) {
-                                app.tree_toggle_selection();
+                                if app.tree_select_at(me.column, me.row) {
+                                    app.tree_toggle_selection();
+                                }
                             } else {
-                                // Regular click clears selection
+                                // Regular click clears selection, selects the
+                                // entry and opens it on a double click
                                 app.tree_clear_selection();
+                                if button == MouseButton::Left {
+                                    if let Err(e) =
+                                        app.tree_mouse_down(me.column, me.row, Instant::now())
+                                    {
+                                        app.status = format!("Open failed: {e}");
+                                    }
+                                }
                             }
                         } else if me.column
                             >= (if app.show_left_pane {
@@ -159,32 +334,88 @@ fn run(app: &mut App) -> Result<()> {
                         }
                     }
                     MouseEventKind::Drag(_) => {
-                        // Handle pane resizing by dragging
-                        if app.show_left_pane {
+                        // Dragging a tree entry moves it; anything else
+                        // resizes the panes
+                        if !app.tree_mouse_drag(me.column, me.row) && app.show_left_pane {
                             let terminal_size = crossterm::terminal::size().unwrap_or((80, 24));
                             app.resize_pane_from_mouse(terminal_size.0, me.column);
                         }
                     }
+                    MouseEventKind::Up(_) => {
+                        app.tree_mouse_up();
+                        app.save_pane_layout();
+                    }
                     _ => {}
                 },
                 Event::Resize(_, _) => {}
-                Event::FocusGained | Event::FocusLost | Event::Paste(_) => {}
+                Event::Paste(text) => handle_paste_event(app, &text),
+                Event::FocusGained | Event::FocusLost => {}
             }
         }
+        if let Some(launch) = app.take_editor_launch() {
+            let result = run_terminal_editor(terminal, app.keyboard_enhanced, &launch)?;
+            app.finish_editor_launch(&launch, result);
+        }
     }
+    Ok(())
+}
 
-    // Clean up before exiting
-    app.pause_video();
-
-    // Restore terminal
+/// Hand the terminal to a terminal editor and take it back once it exits
+///
+/// The outer error is a failure to restore the terminal; the inner one is
+/// the editor failing to start.
+fn run_terminal_editor(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    keyboard_enhanced: bool,
+    launch: &EditorLaunch,
+) -> Result<io::Result<std::process::ExitStatus>> {
+    let mut stdout = io::stdout();
+    if keyboard_enhanced {
+        execute!(stdout, PopKeyboardEnhancementFlags)?;
+    }
+    execute!(
+        stdout,
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        DisableBracketedPaste,
+        crossterm::cursor::Show
+    )?;
     disable_raw_mode()?;
-    let mut stdout = std::io::stdout();
-    execute!(stdout, LeaveAlternateScreen, DisableMouseCapture)?;
-    Ok(())
+
+    let status = launch.command().status();
+
+    enable_raw_mode()?;
+    execute!(
+        stdout,
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableBracketedPaste
+    )?;
+    if keyboard_enhanced {
+        execute!(
+            stdout,
+            PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES)
+        )?;
+    }
+    // The editor drew over everything, so repaint from scratch
+    terminal.clear()?;
+    Ok(status)
 }
 
+/// Below this size the panes and popups can't be laid out usefully
+const MIN_TERMINAL_WIDTH: u16 = 24;
+const MIN_TERMINAL_HEIGHT: u16 = 8;
+
+/// Colors for the pane borders; sb has no theme setting of its own
+static THEME: once_cell::sync::Lazy<saorsa_cli_core::Theme> =
+    once_cell::sync::Lazy::new(saorsa_cli_core::Theme::default);
+
 fn ui(f: &mut Frame, app: &mut App) -> Result<()> {
     app.poll_background_tasks();
+    if f.area().width < MIN_TERMINAL_WIDTH || f.area().height < MIN_TERMINAL_HEIGHT {
+        draw_too_small(f, f.area());
+        return Ok(());
+    }
     // First split vertically to reserve space for status bar
     let main_chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -207,162 +438,19 @@ fn ui(f: &mut Frame, app: &mut App) -> Result<()> {
 
     // --- Left pane
     if app.show_left_pane {
-        let left_border = if matches!(app.focus, Focus::Left) {
-            Color::Cyan
-        } else {
-            Color::Blue
-        };
-        let left_block = Block::default()
-            .title("Files")
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(left_border));
-        let left_tree = tui_tree_widget::Tree::new(&app.left_tree)
-            .map_err(|e| {
-                error::SbError::tree_widget(format!("Failed to create file tree widget: {}", e))
-            })?
-            .block(left_block)
-            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
-        f.render_stateful_widget(left_tree, chunks[0], &mut app.left_state);
-    }
-
-    // (Right tree and standalone editor hidden in 2-pane UX)
-
-    // --- Unified preview/editor (right)
-    // Prepare current editor buffer and metadata
-    let text = app.editor.text();
-    // Provide current path/text and preview cursor to preview for code highlighting/diff and raw-line overlay
-    if let Some(path) = app.opened.as_ref() {
-        std::env::set_var("SB_CURRENT_FILE", path);
-        std::env::set_var("SB_CURRENT_TEXT", &text);
-        // Only enable raw-line overlay when inline editing is active
-        if app.editing_line {
-            std::env::set_var("SB_OVERLAY", "1");
-        } else {
-            std::env::remove_var("SB_OVERLAY");
-        }
-        std::env::set_var("SB_PREVIEW_CURSOR", app.preview_cursor.to_string());
-        std::env::set_var("SB_PREVIEW_COL", app.preview_col.to_string());
-        std::env::set_var("SB_PREVIEW_SCROLL", app.preview_scroll.to_string());
-    }
-    let preview = if let Some(path) = app.opened.clone() {
-        // Check if we should show diff instead of regular preview
-        if app.should_show_diff(&path) {
-            if let Some(diff_content) = app.get_file_diff(&path) {
-                Preview {
-                    text: Text::raw(diff_content),
-                    images: vec![],
-                    videos: vec![],
-                }
-            } else {
-                Preview::from_markdown(&path, &text).unwrap_or_else(|_| Preview {
-                    text: Text::raw("(preview error)"),
-                    images: vec![],
-                    videos: vec![],
-                })
-            }
-        } else {
-            Preview::from_markdown(&path, &text).unwrap_or_else(|_| Preview {
-                text: Text::raw("(preview error)"),
-                images: vec![],
-                videos: vec![],
-            })
-        }
-    } else {
-        Preview {
-            text: Text::raw("(no file)"),
-            images: vec![],
-            videos: vec![],
-        }
-    };
-    // Auto start/stop video based on first detected link
-    if app.autoplay_video {
-        if let Some(first) = preview.videos.first() {
-            if app.video_path.as_ref() != Some(first) {
-                app.start_video(first.clone());
-            }
-        } else if app.video_player.is_some() {
-            app.stop_video();
-        }
+        view::render_tree_pane(f, chunks[0], app, &THEME)?;
     }
-    // Set preview viewport height (usable rows for text block)
-    let preview_text_rows = chunks[1].height.saturating_sub(2) as usize;
-    app.preview_viewport = preview_text_rows;
-    // Clamp scroll to valid range against file length
-    let total_lines = app.editor.line_count();
-    if app.preview_scroll + app.preview_viewport > total_lines.saturating_sub(1) {
-        app.preview_scroll = total_lines
-            .saturating_sub(app.preview_viewport)
-            .saturating_sub(0);
-    }
-    // Right pane: preview or full raw editor
-    if app.show_raw_editor {
-        let block = Block::default()
-            .title("Edit (raw)")
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Green));
-        let area = chunks[1];
-        f.render_widget(block.clone(), area);
-        let inner = block.inner(area);
-        let view = app.editor.view();
-        f.render_widget(view, inner);
+
+    // --- Unified preview/editor (right), or the editor beside the preview
+    let halves = if app.split_view_active() {
+        let [edit, view] = Layout::horizontal([Constraint::Percentage(50); 2]).areas(chunks[1]);
+        (edit, view)
     } else {
-        preview::render_preview(f, chunks[1], &preview);
-    }
-    // Editor command mode prompt overlays at bottom when active
-    if app.editor_cmd_mode {
-        let h = 1;
-        let area = Rect {
-            x: chunks[1].x + 1,
-            y: chunks[1].bottom().saturating_sub(h),
-            width: chunks[1].width.saturating_sub(2),
-            height: h,
-        };
-        f.render_widget(Clear, area);
-        f.render_widget(&app.editor_cmd_input, area);
-    }
-    if !app.show_raw_editor && matches!(app.focus, Focus::Preview) && app.editing_line {
-        // Draw an inline single-line editor at the bottom of preview as a simple approach
-        let h = 3;
-        let area = Rect {
-            x: chunks[1].x + 1,
-            y: chunks[1].bottom().saturating_sub(h),
-            width: chunks[1].width.saturating_sub(2),
-            height: h,
-        };
-        let block = Block::default()
-            .title(format!(
-                "Edit line {} (Enter=save, Esc=cancel)",
-                app.preview_cursor + 1
-            ))
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Green));
-        f.render_widget(Clear, area);
-        f.render_widget(block.clone(), area);
-        let inner = block.inner(area);
-        f.render_widget(&app.line_input, inner);
-    }
-
-    // If a video is playing, overlay the last frame below the text area similar to images
-    if !app.show_raw_editor && matches!(app.focus, Focus::Preview) {
-        if let Some(vp) = &app.video_player {
-            if let Some(img) = vp.last_frame() {
-                let picker = ratatui_image::picker::Picker::from_query_stdio()
-                    .unwrap_or_else(|_| ratatui_image::picker::Picker::halfblocks());
-                let mut state = picker.new_resize_protocol(img);
-                let widget =
-                    ratatui_image::StatefulImage::new().resize(ratatui_image::Resize::Fit(None));
-                // carve a small area at bottom of preview
-                let h = 12;
-                let area = Rect {
-                    x: chunks[1].x + 1,
-                    y: chunks[1].bottom().saturating_sub(h),
-                    width: chunks[1].width.saturating_sub(2),
-                    height: h,
-                };
-                f.render_stateful_widget(widget, area, &mut state);
-            }
-        }
-    }
+        (chunks[1], chunks[1])
+    };
+    // A preview that fails to build is drawn in its pane as a note, which is
+    // all the standalone browser shows of it
+    let _ = view::render_content(f, chunks[1], halves, app, &THEME);
 
     // Badge: indicate Files pane hidden
     if matches!(app.focus, Focus::Preview) {
@@ -392,7 +480,7 @@ fn ui(f: &mut Frame, app: &mut App) -> Result<()> {
     let quit_area = Rect {
         x: 0,
         y: 0,
-        width: quit_hint.len() as u16,
+        width: (quit_hint.len() as u16).min(f.area().width),
         height: 1,
     };
     f.render_widget(Clear, quit_area);
@@ -434,7 +522,7 @@ fn ui(f: &mut Frame, app: &mut App) -> Result<()> {
         let line = app.preview_cursor + 1;
         let col = app.preview_col + 1;
         let dirty = match &app.last_saved_text {
-            Some(saved) => saved != &text,
+            Some(saved) => saved != &app.editor.text(),
             None => false,
         };
         (
@@ -446,7 +534,7 @@ fn ui(f: &mut Frame, app: &mut App) -> Result<()> {
         ("(no file)".to_string(), String::new(), "")
     };
 
-    let status_text = match (&app.focus, app.show_raw_editor, app.picking_file) {
+    let mut status_text = match (&app.focus, app.show_raw_editor, app.picking_file) {
         // File picker mode
         (_, _, true) => {
             // File picker has its own status bar, skip main status
@@ -455,7 +543,7 @@ fn ui(f: &mut Frame, app: &mut App) -> Result<()> {
         // Editor mode
         (Focus::Editor, true, false) | (_, true, false) if app.prefer_raw_editor => {
             format!(
-                "EDITOR │ {}{} │ {} │ Ctrl+S save │ ESC preview │ {}",
+                "EDITOR │ {}{} │ {} │ Ctrl+S save │ Ctrl+L numbers │ ESC preview │ {}",
                 file_label, dirty_mark, pos_label, app.status
             )
         }
@@ -463,12 +551,12 @@ fn ui(f: &mut Frame, app: &mut App) -> Result<()> {
         (Focus::Preview, false, false) => {
             if app.show_left_pane {
                 format!(
-                    "PREVIEW │ {}{} │ {} │ ↑↓ scroll ← files e edit Ctrl+S save F2 picker ? help │ {}",
+                    "PREVIEW │ {}{} │ {} │ ↑↓ scroll ← files e edit t outline Ctrl+S save F2 picker ? help │ {}",
                     file_label, dirty_mark, pos_label, app.status
                 )
             } else {
                 format!(
-                    "PREVIEW │ {}{} │ {} │ ↑↓ scroll e edit Ctrl+S save F2 picker ? help │ {}",
+                    "PREVIEW │ {}{} │ {} │ ↑↓ scroll e edit t outline Ctrl+S save F2 picker ? help │ {}",
                     file_label, dirty_mark, pos_label, app.status
                 )
             }
@@ -485,6 +573,16 @@ fn ui(f: &mut Frame, app: &mut App) -> Result<()> {
             )
         }
     };
+    if let Some(bytes) = app.hex_status().filter(|_| !status_text.is_empty()) {
+        status_text = format!("{status_text} │ {bytes}");
+    }
+    if let Some(branch) = app
+        .git_branch
+        .as_deref()
+        .filter(|_| !status_text.is_empty())
+    {
+        status_text = format!("{status_text} │ {branch}");
+    }
 
     if !status_text.is_empty() {
         let status = Paragraph::new(status_text)
@@ -521,11 +619,21 @@ fn ui(f: &mut Frame, app: &mut App) -> Result<()> {
         draw_file_picker(f, f.area(), app);
     }
 
+    // --- Bulk rename prompt over the picker
+    if app.bulk_renaming {
+        draw_bulk_rename(f, f.area(), app);
+    }
+
     // --- Operation input overlay (Copy/Move/Mkdir)
     if !matches!(app.op_mode, app::OpMode::None) {
         draw_op_input(f, f.area(), app);
     }
 
+    // --- Overwrite prompt over the operation input
+    if let Some(prompt) = &app.overwrite_prompt {
+        draw_overwrite_prompt(f, f.area(), prompt);
+    }
+
     // --- Move destination picker overlay
     if app.showing_move_dest {
         draw_move_destination_picker(f, f.area(), app);
@@ -536,33 +644,107 @@ fn ui(f: &mut Frame, app: &mut App) -> Result<()> {
         draw_git_status(f, f.area(), app);
     }
 
+    // --- Heading outline overlay
+    if app.showing_toc {
+        draw_toc(f, f.area(), app);
+    }
+
+    // --- Workspace search overlay
+    if app.showing_search {
+        draw_search(f, f.area(), app);
+    }
+
+    // --- Fuzzy file finder overlay
+    if app.showing_finder {
+        draw_finder(f, f.area(), app);
+    }
+
+    // --- External file change prompt
+    if let Some(prompt) = app.file_change_prompt {
+        draw_file_change_prompt(f, f.area(), prompt);
+    }
+
+    // --- Swap file recovery prompt
+    if let Some(swap) = &app.swap_prompt {
+        draw_swap_prompt(f, f.area(), swap);
+    }
+
     Ok(())
 }
 
+fn draw_too_small(f: &mut Frame, area: Rect) {
+    let message = format!(
+        "Terminal too small ({}x{}, need {}x{})",
+        area.width, area.height, MIN_TERMINAL_WIDTH, MIN_TERMINAL_HEIGHT
+    );
+    let y = area.y + area.height / 2;
+    let row = Rect {
+        y,
+        height: area.height.min(1),
+        ..area
+    };
+    f.render_widget(Paragraph::new(message).alignment(Alignment::Center), row);
+}
+
+/// Center a `width` x `height` popup in `area`, shrinking it to fit
+fn centered_rect(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
+
 fn draw_centered_help(f: &mut Frame, area: Rect) {
     let help = [
         "sb — Markdown TUI",
         "",
         "Focus: Tab / Shift+Tab",
         "Tree: ↑↓←→ or j/k, Enter toggles/open",
+        "Tree mouse: click selects, double-click opens, drag moves",
         "Editor: type freely (Enter = newline)",
         "New file: N",
         "Delete: d (confirm)",
+        "Undo delete/move/rename: u",
+        "Empty the trash: :empty-trash",
+        "Bulk rename (picker): R on selected files",
         "Insert link (picker): F2 or Ctrl+I",
         "Save: Ctrl+S",
         "Open externally: o",
+        "Copy / paste: Ctrl+C / Ctrl+V (preview/editor)",
+        "Preview selection: v (Esc clears)",
+        "Jump to heading: t (type to filter)",
+        "Search all files: Ctrl+F (/regex/ for patterns)",
+        "Find file by name: Ctrl+P (fuzzy)",
+        "Raw editor line numbers: Ctrl+L (absolute/relative)",
+        "Split raw editor | rendered preview: F3",
+        "Git blame gutter (code preview): b",
+        "Wrap / scroll long lines (preview): w",
+        "Page (preview): PgUp/PgDn or Ctrl+U/Ctrl+D",
+        "Line start / end (preview): Home / End (twice for column 0 / EOL)",
+        "Top / bottom (preview): Ctrl+Home or gg / Ctrl+End or G",
         "",
         "Selection:",
         "Select/Unselect: S (accumulates)",
         "Range select: Shift+↑↓",
-        "Select all: Ctrl+A",
+        "Select all visible: Ctrl+A",
+        "Select everything below root: A (y to confirm)",
         "Clear selections: Esc",
+        "Show/hide .gitignored files: I",
+        "Sort tree by name/modified/size/type: O",
+        "Show/hide dotfiles (tree and pickers): .",
+        "Go to path: g (or :cd <path>)",
         "",
         "Pane Resize:",
         "Widen left: Ctrl+. or Ctrl+=",
         "Narrow left: Ctrl+, or Ctrl+-",
         "Mouse drag: Click and drag separator",
         "",
+        "Video: Space pause, ←/→ seek 10s, s stop",
+        "",
         "Help: ? (toggle)",
         "Quit: Q / Esc",
     ]
@@ -570,31 +752,269 @@ fn draw_centered_help(f: &mut Frame, area: Rect) {
     let paragraph = Paragraph::new(help)
         .block(Block::default().title("Help").borders(Borders::ALL))
         .wrap(Wrap { trim: false });
-    let w = area.width.min(60);
-    let h = area.height.min(22);
-    let x = area.x + (area.width.saturating_sub(w)) / 2;
-    let y = area.y + (area.height.saturating_sub(h)) / 2;
-    let popup = Rect {
-        x,
-        y,
-        width: w,
-        height: h,
+    let popup = centered_rect(area, 60, 35);
+    f.render_widget(Clear, popup);
+    f.render_widget(paragraph, popup);
+}
+
+fn draw_toc(f: &mut Frame, area: Rect, app: &App) {
+    let popup = centered_rect(area, 70, 24);
+    let block = Block::default()
+        .title("Outline")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Magenta));
+    f.render_widget(Clear, popup);
+    f.render_widget(block.clone(), popup);
+    let inner = block.inner(popup);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    f.render_widget(&app.toc_filter, chunks[0]);
+
+    let entries = app.toc_entries();
+    let items: Vec<ListItem> = if entries.is_empty() {
+        vec![ListItem::new("(no matching headings)").style(Style::default().fg(Color::DarkGray))]
+    } else {
+        entries
+            .iter()
+            .map(|(level, title, line)| {
+                let indent = "  ".repeat(level.saturating_sub(1));
+                let style = if *level == 1 {
+                    Style::default().add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{indent}{title}"), style),
+                    Span::styled(
+                        format!("  :{}", line + 1),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ]))
+            })
+            .collect()
+    };
+    let mut state = ListState::default();
+    if !entries.is_empty() {
+        state.select(Some(app.toc_index.min(entries.len() - 1)));
+    }
+    let list = List::new(items).highlight_style(
+        Style::default()
+            .fg(Color::Black)
+            .bg(Color::Magenta)
+            .add_modifier(Modifier::BOLD),
+    );
+    f.render_stateful_widget(list, chunks[1], &mut state);
+
+    let status_bar = Paragraph::new("type to filter  ↑↓ select  ENTER jump  ESC close")
+        .style(Style::default().fg(Color::White).bg(Color::Magenta));
+    f.render_widget(status_bar, chunks[2]);
+}
+
+fn draw_search(f: &mut Frame, area: Rect, app: &App) {
+    let popup = centered_rect(area, 100, 30);
+    let block = Block::default()
+        .title("Search files")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+    f.render_widget(Clear, popup);
+    f.render_widget(block.clone(), popup);
+    let inner = block.inner(popup);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    f.render_widget(&app.search_input, chunks[0]);
+
+    let items: Vec<ListItem> = if app.search_results.is_empty() {
+        let message = if app.background_activity() == Some("Searching files...") {
+            "(searching...)"
+        } else {
+            "(no matches)"
+        };
+        vec![ListItem::new(message).style(Style::default().fg(Color::DarkGray))]
+    } else {
+        app.search_results
+            .iter()
+            .map(|hit| {
+                let path = hit.path.strip_prefix(&app.root).unwrap_or(&hit.path);
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{}:{}  ", path.display(), hit.line + 1),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                    Span::raw(hit.text.clone()),
+                ]))
+            })
+            .collect()
+    };
+    let mut state = ListState::default();
+    if !app.search_results.is_empty() {
+        state.select(Some(app.search_index.min(app.search_results.len() - 1)));
+    }
+    let list = List::new(items).highlight_style(
+        Style::default()
+            .fg(Color::Black)
+            .bg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    );
+    f.render_stateful_widget(list, chunks[1], &mut state);
+
+    let status_bar = Paragraph::new("ENTER search / open  ↑↓ select  ESC close")
+        .style(Style::default().fg(Color::Black).bg(Color::Yellow));
+    f.render_widget(status_bar, chunks[2]);
+}
+
+fn draw_finder(f: &mut Frame, area: Rect, app: &App) {
+    let popup = centered_rect(area, 70, 24);
+    let block = Block::default()
+        .title("Find file")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Green));
+    f.render_widget(Clear, popup);
+    f.render_widget(block.clone(), popup);
+    let inner = block.inner(popup);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    f.render_widget(&app.finder_input, chunks[0]);
+
+    let items: Vec<ListItem> = if app.finder_results.is_empty() {
+        let message = match app.background_activity() {
+            Some("Indexing files...") => "(indexing...)",
+            Some("Matching files...") => "(matching...)",
+            _ => "(no matching files)",
+        };
+        vec![ListItem::new(message).style(Style::default().fg(Color::DarkGray))]
+    } else {
+        let matched = Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD);
+        app.finder_results
+            .iter()
+            .map(|found| {
+                let spans: Vec<Span> = found
+                    .path
+                    .chars()
+                    .enumerate()
+                    .map(|(i, c)| {
+                        if found.positions.contains(&i) {
+                            Span::styled(c.to_string(), matched)
+                        } else {
+                            Span::raw(c.to_string())
+                        }
+                    })
+                    .collect();
+                ListItem::new(Line::from(spans))
+            })
+            .collect()
+    };
+    let mut state = ListState::default();
+    if !app.finder_results.is_empty() {
+        state.select(Some(app.finder_index.min(app.finder_results.len() - 1)));
+    }
+    let list = List::new(items).highlight_style(
+        Style::default()
+            .fg(Color::Black)
+            .bg(Color::Green)
+            .add_modifier(Modifier::BOLD),
+    );
+    f.render_stateful_widget(list, chunks[1], &mut state);
+
+    let status_bar = Paragraph::new("type to filter  ↑↓ select  ENTER open  ESC close")
+        .style(Style::default().fg(Color::Black).bg(Color::Green));
+    f.render_widget(status_bar, chunks[2]);
+}
+
+fn draw_file_change_prompt(f: &mut Frame, area: Rect, prompt: FileChangePrompt) {
+    let (title, body, color) = match prompt {
+        FileChangePrompt::Reload => (
+            "File changed on disk",
+            "The opened file was modified outside sb.\n\ny / Enter: reload    n / Esc: ignore",
+            Color::Cyan,
+        ),
+        FileChangePrompt::Conflict => (
+            "Conflict: file changed on disk",
+            "The file was modified outside sb and you have unsaved edits.\n\ny / Enter: reload (discard edits)    n / Esc: keep edits",
+            Color::Red,
+        ),
     };
+    let popup = centered_rect(area, 64, 6);
+    let paragraph = Paragraph::new(body)
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(color)),
+        )
+        .wrap(Wrap { trim: false });
     f.render_widget(Clear, popup);
     f.render_widget(paragraph, popup);
 }
 
-fn draw_new_file_prompt(f: &mut Frame, area: Rect, input: &TextArea) {
-    let w = area.width.min(60);
-    let h = 5;
-    let x = area.x + (area.width.saturating_sub(w)) / 2;
-    let y = area.y + (area.height.saturating_sub(h)) / 2;
-    let popup = Rect {
-        x,
-        y,
-        width: w,
-        height: h,
+fn draw_swap_prompt(f: &mut Frame, area: Rect, swap: &std::path::Path) {
+    let body = format!(
+        "{} holds edits newer than the file.\n\ny / Enter: recover    n: discard    Esc: decide later",
+        swap.display()
+    );
+    let popup = centered_rect(area, 64, 6);
+    let paragraph = Paragraph::new(body)
+        .block(
+            Block::default()
+                .title("Recover unsaved edits?")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow)),
+        )
+        .wrap(Wrap { trim: false });
+    f.render_widget(Clear, popup);
+    f.render_widget(paragraph, popup);
+}
+
+fn draw_overwrite_prompt(f: &mut Frame, area: Rect, prompt: &OverwritePrompt) {
+    let what = if prompt.conflicts == 1 {
+        "1 existing file".to_string()
+    } else {
+        format!("{} existing files", prompt.conflicts)
     };
+    let body = format!(
+        "{}\nwould replace {what}.\n\ny / Enter: overwrite    r: keep both    n / Esc: cancel",
+        prompt.dest.display()
+    );
+    let popup = centered_rect(area, 64, 6);
+    let paragraph = Paragraph::new(body)
+        .block(
+            Block::default()
+                .title("Destination exists")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow)),
+        )
+        .wrap(Wrap { trim: false });
+    f.render_widget(Clear, popup);
+    f.render_widget(paragraph, popup);
+}
+
+fn draw_new_file_prompt(f: &mut Frame, area: Rect, input: &TextArea) {
+    let popup = centered_rect(area, 60, 5);
     let block = Block::default()
         .title("New file name (.md)")
         .borders(Borders::ALL);
@@ -613,16 +1033,7 @@ fn draw_delete_confirm(f: &mut Frame, area: Rect, target: Option<&std::path::Pat
         .and_then(|path| path.symlink_metadata().ok())
         .is_some_and(|meta| meta.is_dir());
 
-    let w = area.width.min(60);
-    let h = 8;
-    let x = area.x + (area.width.saturating_sub(w)) / 2;
-    let y = area.y + (area.height.saturating_sub(h)) / 2;
-    let popup = Rect {
-        x,
-        y,
-        width: w,
-        height: h,
-    };
+    let popup = centered_rect(area, 60, 8);
 
     // Azure-style blue border with white background
     let block = Block::default()
@@ -689,16 +1100,11 @@ fn draw_delete_confirm(f: &mut Frame, area: Rect, target: Option<&std::path::Pat
 
 fn draw_file_picker(f: &mut Frame, area: Rect, app: &App) {
     // Create centered popup
-    let w = 70.min(area.width - 4);
-    let h = 25.min(area.height - 4);
-    let x = area.x + (area.width.saturating_sub(w)) / 2;
-    let y = area.y + (area.height.saturating_sub(h)) / 2;
-    let popup = Rect {
-        x,
-        y,
-        width: w,
-        height: h,
-    };
+    let popup = centered_rect(
+        area,
+        area.width.saturating_sub(4).min(70),
+        area.height.saturating_sub(4).min(25),
+    );
 
     // Clear the area and draw border
     f.render_widget(Clear, popup);
@@ -735,31 +1141,19 @@ fn draw_file_picker(f: &mut Frame, area: Rect, app: &App) {
             let is_in_selection = app.picker_selection.contains(&i);
             let display_name = path.file_name().unwrap_or_default().to_string_lossy();
 
-            let prefix = if path.is_dir() { "📁 " } else { "📄 " };
+            let prefix = format!("{} ", app.tree_icons.icon(icons::FileKind::of(path)));
 
             // Add selection marker
             let selection_marker = if is_in_selection { "✓ " } else { "  " };
 
-            // Add Git status if available
-            let status_indicator = if let Some(ref repo) = app.git_repo {
-                if let Ok(statuses) = repo.status() {
-                    if let Some(status) = statuses.get(path) {
-                        match status {
-                            crate::git::FileStatus::Modified => " [M]",
-                            crate::git::FileStatus::Added => " [A]",
-                            crate::git::FileStatus::Deleted => " [D]",
-                            crate::git::FileStatus::Untracked => " [?]",
-                            crate::git::FileStatus::Conflicted => " [C]",
-                            _ => "",
-                        }
-                    } else {
-                        ""
-                    }
-                } else {
-                    ""
-                }
-            } else {
-                ""
+            // Add Git status from the cached snapshot (never query git while drawing)
+            let status_indicator = match app.get_file_git_status(path) {
+                Some(crate::git::FileStatus::Modified) => " [M]",
+                Some(crate::git::FileStatus::Added) => " [A]",
+                Some(crate::git::FileStatus::Deleted) => " [D]",
+                Some(crate::git::FileStatus::Untracked) => " [?]",
+                Some(crate::git::FileStatus::Conflicted) => " [C]",
+                _ => "",
             };
 
             let text = format!(
@@ -782,6 +1176,8 @@ fn draw_file_picker(f: &mut Frame, area: Rect, app: &App) {
             } else if is_in_selection {
                 // Selected items (subtle highlight)
                 Style::default().bg(Color::DarkGray).fg(Color::Yellow)
+            } else if fs::is_hidden(path) {
+                Style::default().fg(Color::DarkGray)
             } else {
                 Style::default().fg(Color::White)
             };
@@ -799,9 +1195,9 @@ fn draw_file_picker(f: &mut Frame, area: Rect, app: &App) {
     // Draw status bar at bottom with commands
     let selected_count = app.picker_selection.len();
     let status_text = if selected_count > 0 {
-        format!("↑↓:navigate  Shift+↑↓:select  Space:toggle  Ctrl+A:all  D:delete({})  O:open({})  ESC:cancel", selected_count, selected_count)
+        format!("↑↓:navigate  Shift+↑↓:select  Space:toggle  Ctrl+A:all  D:delete({})  R:rename({})  O:open({})  ESC:cancel", selected_count, selected_count, selected_count)
     } else {
-        "↑↓:navigate  Shift+↑↓:select  Space:toggle  Ctrl+A:all  O:open  D:delete  P:parent  ESC:cancel".to_string()
+        "↑↓:navigate  Shift+↑↓:select  Space:toggle  Ctrl+A:all  O:open  D:delete  R:rename  P:parent  ESC:cancel".to_string()
     };
     let status = Paragraph::new(status_text)
         .style(
@@ -815,18 +1211,80 @@ fn draw_file_picker(f: &mut Frame, area: Rect, app: &App) {
     f.render_widget(status, chunks[1]);
 }
 
+fn draw_bulk_rename(f: &mut Frame, area: Rect, app: &App) {
+    let sources = app.rename_sources();
+    let popup = centered_rect(
+        area,
+        80,
+        u16::try_from(sources.len()).map_or(u16::MAX, |n| n.saturating_add(6)),
+    );
+    let block = Block::default()
+        .title(format!(" Rename {} files ", sources.len()))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+    f.render_widget(Clear, popup);
+    f.render_widget(block.clone(), popup);
+    let inner = block.inner(popup);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    f.render_widget(&app.rename_input, chunks[0]);
+
+    let name = |path: &std::path::Path| {
+        path.file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned()
+    };
+    let (items, footer): (Vec<ListItem>, Line) = match app.bulk_rename_preview() {
+        Ok(plan) => {
+            let items = sources
+                .iter()
+                .map(|source| {
+                    let target = plan
+                        .iter()
+                        .find(|(from, _)| from == source)
+                        .map(|(_, to)| to);
+                    match target {
+                        Some(to) => ListItem::new(Line::from(vec![
+                            Span::raw(name(source)),
+                            Span::styled(" → ", Style::default().fg(Color::DarkGray)),
+                            Span::styled(name(to), Style::default().fg(Color::Green)),
+                        ])),
+                        None => ListItem::new(format!("{} (unchanged)", name(source)))
+                            .style(Style::default().fg(Color::DarkGray)),
+                    }
+                })
+                .collect();
+            let footer =
+                Line::from("ENTER rename  ESC cancel  {name} {ext} {n} or s/find/replace/");
+            (items, footer)
+        }
+        Err(err) => {
+            let items = sources
+                .iter()
+                .map(|source| ListItem::new(name(source)))
+                .collect();
+            (
+                items,
+                Line::styled(err.to_string(), Style::default().fg(Color::Red)),
+            )
+        }
+    };
+    f.render_widget(List::new(items), chunks[1]);
+    f.render_widget(Paragraph::new(footer), chunks[2]);
+}
+
 fn draw_op_input(f: &mut Frame, area: Rect, app: &App) {
     use app::OpMode;
-    let w = area.width.min(70);
-    let h = 5;
-    let x = area.x + (area.width.saturating_sub(w)) / 2;
-    let y = area.y + (area.height.saturating_sub(h)) / 2;
-    let popup = Rect {
-        x,
-        y,
-        width: w,
-        height: h,
-    };
+    let popup = centered_rect(area, 70, 5);
     let title = match app.op_mode {
         OpMode::Copy => "Copy to (name or path)",
         OpMode::Move => "Move to (name or path)",
@@ -844,16 +1302,7 @@ fn draw_op_input(f: &mut Frame, area: Rect, app: &App) {
 }
 
 fn draw_move_destination_picker(f: &mut Frame, area: Rect, app: &App) {
-    let w = area.width.min(60);
-    let h = area.height.min(18);
-    let x = area.x + (area.width.saturating_sub(w)) / 2;
-    let y = area.y + (area.height.saturating_sub(h)) / 2;
-    let popup = Rect {
-        x,
-        y,
-        width: w,
-        height: h,
-    };
+    let popup = centered_rect(area, 60, 18);
 
     let source_name = app
         .move_source
@@ -900,6 +1349,8 @@ fn draw_move_destination_picker(f: &mut Frame, area: Rect, app: &App) {
                 Style::default()
                     .fg(Color::Green)
                     .add_modifier(Modifier::BOLD)
+            } else if fs::is_hidden(p) {
+                Style::default().fg(Color::DarkGray)
             } else {
                 Style::default()
             };
@@ -919,16 +1370,7 @@ fn draw_move_destination_picker(f: &mut Frame, area: Rect, app: &App) {
 }
 
 fn draw_git_status(f: &mut Frame, area: Rect, app: &App) {
-    let w = area.width.min(80);
-    let h = area.height.min(20);
-    let x = area.x + (area.width.saturating_sub(w)) / 2;
-    let y = area.y + (area.height.saturating_sub(h)) / 2;
-    let popup = Rect {
-        x,
-        y,
-        width: w,
-        height: h,
-    };
+    let popup = centered_rect(area, 80, 20);
 
     let git_root = app
         .git_repo
@@ -3,28 +3,50 @@ use image::ImageReader;
 use once_cell::sync::Lazy;
 use pathdiff::diff_paths;
 use ratatui::{prelude::*, text::Text, widgets::*};
-use ratatui_image::{picker::Picker, Resize, StatefulImage};
+use ratatui_image::{
+    picker::{Picker, ProtocolType},
+    Resize, StatefulImage,
+};
 use similar::{ChangeTag, TextDiff};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::Mutex;
+use std::time::SystemTime;
 use syntect::{easy::HighlightLines, highlighting::ThemeSet, parsing::SyntaxSet};
 use tui_markdown as md;
-// v8 Picker re-exports ProtocolType from picker; no direct use needed here
+
+use crate::fs::is_binary_file;
+use crate::git::{parse_blame_porcelain, BlameLine};
+use crate::raster::{self, Raster, RasterJob};
 
 pub struct Preview {
     pub text: Text<'static>,
     pub images: Vec<PathBuf>,
     pub videos: Vec<PathBuf>,
+    pub diagrams: Vec<Diagram>,
 }
 
 impl Preview {
     pub fn from_markdown(_path: &Path, src: &str) -> Result<Self> {
+        // Leading YAML frontmatter becomes a styled header instead of raw markdown
+        let (frontmatter, body) = match parse_frontmatter(src) {
+            Some((fm, body)) => (Some(fm), body),
+            None => (None, src),
+        };
         // Normalize headings so `##Heading` (no space) becomes `## Heading`, and add spacing
-        let normalized = normalize_headings(src);
+        let normalized = normalize_headings(body);
         // Convert markdown → styled Text, then take an owned copy (no unsafe lifetime tricks)
         let text_parsed: Text<'_> = md::from_str(&normalized);
         let text_owned: Text<'static> = to_owned_text(text_parsed);
-        let text: Text<'static> = apply_heading_styles(text_owned);
+        let mut text: Text<'static> = apply_heading_styles(text_owned);
+        if let Some(fm) = frontmatter {
+            let mut lines = frontmatter_header(&fm);
+            lines.append(&mut text.lines);
+            text = Text::from(lines);
+        }
 
         let mut images = vec![];
         for (_alt, p) in find_md_images(src) {
@@ -33,6 +55,13 @@ impl Preview {
                 images.push(abs);
             }
         }
+        // Linked PDFs render as a first-page thumbnail alongside images
+        for p in find_md_pdfs(src) {
+            let abs = resolve_relative(_path, &p);
+            if abs.exists() && !images.contains(&abs) {
+                images.push(abs);
+            }
+        }
         let mut videos = vec![];
         for (_alt, p) in find_md_videos(src) {
             let abs = resolve_relative(_path, &p);
@@ -40,13 +69,256 @@ impl Preview {
                 videos.push(abs);
             }
         }
+        let diagrams = find_md_diagrams(src);
         Ok(Self {
             text,
             images,
             videos,
+            diagrams,
+        })
+    }
+
+    /// Preview of `path` built by `renderer`, as picked for it on load
+    pub fn for_file(renderer: Renderer, path: &Path, src: &str) -> Result<Self> {
+        (renderer.build)(path, src)
+    }
+
+    /// Preview showing `src` as it is, line for line
+    pub fn plain(src: &str) -> Self {
+        Self {
+            text: Text::raw(src.to_string()),
+            images: vec![],
+            videos: vec![],
+            diagrams: vec![],
+        }
+    }
+}
+
+/// How one type of file is previewed
+#[derive(Clone, Copy)]
+pub struct Renderer {
+    /// Short name, e.g. `"markdown"`
+    pub name: &'static str,
+    /// Turn the file's path and editor text into a [`Preview`]
+    pub build: fn(&Path, &str) -> Result<Preview>,
+    /// Draw a built preview
+    pub draw: fn(&mut Frame, Rect, &Preview, &Picker),
+}
+
+/// Rendered markdown with its images, PDFs, diagrams and videos
+pub const MARKDOWN: Renderer = Renderer {
+    name: "markdown",
+    build: Preview::from_markdown,
+    draw: draw_document,
+};
+
+/// Syntax-highlighted source with an inline diff against `HEAD`
+pub const CODE: Renderer = Renderer {
+    name: "code",
+    build: build_plain,
+    draw: draw_code,
+};
+
+/// The image, or the first page of a PDF, filling the pane
+pub const IMAGE: Renderer = Renderer {
+    name: "image",
+    build: build_image,
+    draw: draw_image_preview,
+};
+
+/// The text as it is, without markdown formatting
+pub const PLAIN_TEXT: Renderer = Renderer {
+    name: "plain text",
+    build: build_plain,
+    draw: draw_document,
+};
+
+/// A hex dump of a binary file, read a screenful at a time
+pub const HEX: Renderer = Renderer {
+    name: "hex",
+    build: build_plain,
+    draw: draw_hex,
+};
+
+const CODE_EXTENSIONS: &[&str] = &[
+    "rs", "ts", "tsx", "js", "jsx", "py", "rb", "go", "java", "cpp", "c", "h", "hpp", "cs", "php",
+    "swift", "kt", "scala", "sh", "bash", "zsh", "fish", "ps1", "yml", "yaml", "toml", "json",
+    "xml", "html", "css", "scss", "sass", "sql", "tex",
+];
+
+/// Renderers by file extension
+///
+/// Extensions are matched case-insensitively. Files with an extension that
+/// has no renderer get [`HEX`] if they look binary and [`MARKDOWN`] otherwise.
+pub struct RendererRegistry {
+    by_extension: HashMap<String, Renderer>,
+}
+
+impl RendererRegistry {
+    /// A registry with no extensions registered
+    pub fn empty() -> Self {
+        Self {
+            by_extension: HashMap::new(),
+        }
+    }
+
+    /// Preview files ending in any of `extensions` with `renderer`, replacing
+    /// whatever was registered for them before
+    pub fn register(&mut self, extensions: &[&str], renderer: Renderer) {
+        for ext in extensions {
+            self.by_extension.insert(ext.to_lowercase(), renderer);
+        }
+    }
+
+    /// Renderer registered for the extension of `path`; never reads the file
+    pub fn by_extension(&self, path: &Path) -> Option<Renderer> {
+        let ext = path.extension()?.to_str()?.to_lowercase();
+        self.by_extension.get(&ext).copied()
+    }
+
+    /// Renderer for `path`, falling back on its contents when the extension
+    /// has none
+    ///
+    /// That fallback reads the file, so callers ask once when the file is
+    /// opened rather than on every frame.
+    pub fn for_path(&self, path: &Path) -> Renderer {
+        self.by_extension(path)
+            .unwrap_or_else(|| if is_binary_file(path) { HEX } else { MARKDOWN })
+    }
+}
+
+impl Default for RendererRegistry {
+    /// The built-in renderers; new file types are added here
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register(&["md", "markdown"], MARKDOWN);
+        registry.register(CODE_EXTENSIONS, CODE);
+        registry.register(&["png", "jpg", "jpeg", "pdf"], IMAGE);
+        registry.register(&["txt", "text", "log", "csv"], PLAIN_TEXT);
+        registry.register(
+            &[
+                "bin", "exe", "dll", "so", "dylib", "o", "a", "class", "wasm",
+            ],
+            HEX,
+        );
+        registry
+    }
+}
+
+/// The renderers opened files are matched against
+pub static RENDERERS: Lazy<RendererRegistry> = Lazy::new(RendererRegistry::default);
+
+fn build_plain(_path: &Path, src: &str) -> Result<Preview> {
+    Ok(Preview::plain(src))
+}
+
+fn build_image(path: &Path, _src: &str) -> Result<Preview> {
+    Ok(Preview {
+        text: Text::default(),
+        images: vec![path.to_path_buf()],
+        videos: vec![],
+        diagrams: vec![],
+    })
+}
+
+/// Up to `len` bytes of `path` starting at byte `start`
+pub fn read_hex_window(path: &Path, start: u64, len: u64) -> io::Result<Vec<u8>> {
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(start))?;
+    let mut bytes = Vec::new();
+    file.take(len).read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// `xxd`-style rows of `bytes`, which start at offset `start` of the file:
+/// the offset, `row_bytes` bytes in hex and those printable as ASCII
+///
+/// Bytes whose offsets fall within `highlight` (inclusive) are drawn
+/// reversed in both columns.
+pub fn hex_lines(
+    bytes: &[u8],
+    start: u64,
+    row_bytes: usize,
+    highlight: Option<(u64, u64)>,
+) -> Vec<Line<'static>> {
+    let marked = Style::default().add_modifier(Modifier::REVERSED);
+    bytes
+        .chunks(row_bytes.max(1))
+        .enumerate()
+        .map(|(row, chunk)| {
+            let row_start = start + (row * row_bytes) as u64;
+            let style = |i: usize| {
+                let offset = row_start + i as u64;
+                match highlight {
+                    Some((from, to)) if (from..=to).contains(&offset) => marked,
+                    _ => Style::default(),
+                }
+            };
+            let mut spans = vec![Span::styled(
+                format!("{row_start:08x}  "),
+                Style::default().fg(Color::DarkGray),
+            )];
+            for i in 0..row_bytes {
+                if i > 0 {
+                    spans.push(Span::raw(" "));
+                }
+                spans.push(match chunk.get(i) {
+                    Some(b) => Span::styled(format!("{b:02x}"), style(i)),
+                    None => Span::raw("  "),
+                });
+            }
+            spans.push(Span::styled("  |", Style::default().fg(Color::Cyan)));
+            for (i, &b) in chunk.iter().enumerate() {
+                let c = if b.is_ascii_graphic() || b == b' ' {
+                    b as char
+                } else {
+                    '.'
+                };
+                spans.push(Span::styled(c.to_string(), style(i).fg(Color::Cyan)));
+            }
+            spans.push(Span::styled("|", Style::default().fg(Color::Cyan)));
+            Line::from(spans)
         })
+        .collect()
+}
+/// Row of the rendered `text` that shows source line `line`, best effort
+///
+/// Rendering joins paragraphs and drops markup, so rows and source lines
+/// drift apart. `headings` pairs each heading's source line with its title;
+/// headings found again in the rendered text pin the two together, and the
+/// lines between two of them are spread evenly over the rows between.
+pub fn rendered_line(
+    text: &Text<'_>,
+    headings: &[(usize, &str)],
+    source_lines: usize,
+    line: usize,
+) -> usize {
+    let rows: Vec<String> = text
+        .lines
+        .iter()
+        .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+        .collect();
+    let mut anchors = vec![(0, 0)];
+    let mut from = 0;
+    for &(source, title) in headings {
+        if let Some(offset) = rows[from..].iter().position(|row| row.trim() == title) {
+            anchors.push((source, from + offset));
+            from += offset + 1;
+        }
     }
+    anchors.push((source_lines.max(1), rows.len()));
+
+    let next = anchors.partition_point(|&(source, _)| source <= line);
+    let (source, row) = anchors[next - 1];
+    let row = match anchors.get(next) {
+        Some(&(next_source, next_row)) => {
+            row + (line - source) * next_row.saturating_sub(row) / (next_source - source)
+        }
+        None => row + (line - source),
+    };
+    row.min(rows.len().saturating_sub(1))
 }
+
 fn to_owned_text(input: Text<'_>) -> Text<'static> {
     let mut out_lines: Vec<Line<'static>> = Vec::with_capacity(input.lines.len());
     for line in input.lines.iter() {
@@ -59,13 +331,144 @@ fn to_owned_text(input: Text<'_>) -> Text<'static> {
     Text::from(out_lines)
 }
 
+fn is_light_theme() -> bool {
+    std::env::var("SB_THEME")
+        .ok()
+        .map(|v| v.eq_ignore_ascii_case("light"))
+        .unwrap_or(false)
+}
+
+/// Key/value pairs from a note's leading `---` YAML block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frontmatter {
+    /// Fields in document order; scalars have a single value
+    pub fields: Vec<(String, Vec<String>)>,
+}
+
+impl Frontmatter {
+    pub fn get(&self, key: &str) -> Option<&[String]> {
+        self.fields
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.as_slice())
+    }
+}
+
+/// Split leading frontmatter from the markdown body
+///
+/// Only flat `key: value` pairs and lists (`[a, b]` or `- item` lines) are
+/// understood. Anything else, or a missing closing `---`, returns `None` so
+/// the text renders as written.
+pub fn parse_frontmatter(src: &str) -> Option<(Frontmatter, &str)> {
+    let rest = src
+        .strip_prefix("---\n")
+        .or_else(|| src.strip_prefix("---\r\n"))?;
+    let mut fields: Vec<(String, Vec<String>)> = Vec::new();
+    let mut offset = src.len() - rest.len();
+    for line in rest.split_inclusive('\n') {
+        offset += line.len();
+        let line = line.trim_end_matches(['\n', '\r']);
+        if line == "---" || line == "..." {
+            return Some((Frontmatter { fields }, &src[offset..]));
+        }
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if let Some(item) = trimmed.strip_prefix("- ") {
+            // Block list item under the previous `key:` line
+            let (_, values) = fields.last_mut().filter(|_| line.starts_with([' ', '-']))?;
+            values.push(unquote(item).to_string());
+            continue;
+        }
+        if line.starts_with([' ', '\t']) {
+            return None;
+        }
+        let (key, value) = line.split_once(':')?;
+        let key = key.trim();
+        if key.is_empty()
+            || !key
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+        {
+            return None;
+        }
+        let value = value.trim();
+        let values = if let Some(list) = value.strip_prefix('[') {
+            list.strip_suffix(']')?
+                .split(',')
+                .map(|v| unquote(v.trim()).to_string())
+                .filter(|v| !v.is_empty())
+                .collect()
+        } else if value.is_empty() {
+            Vec::new()
+        } else {
+            vec![unquote(value).to_string()]
+        };
+        fields.push((key.to_string(), values));
+    }
+    None
+}
+
+fn unquote(value: &str) -> &str {
+    ['"', '\'']
+        .iter()
+        .find_map(|q| value.strip_prefix(*q)?.strip_suffix(*q))
+        .unwrap_or(value)
+}
+
+/// Compact header lines for parsed frontmatter: title, date and tags, then other fields
+fn frontmatter_header(fm: &Frontmatter) -> Vec<Line<'static>> {
+    let (title_style, accent) = if is_light_theme() {
+        (Style::default().fg(Color::Blue), Color::Magenta)
+    } else {
+        (Style::default().fg(Color::Yellow), Color::Cyan)
+    };
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut lines = Vec::new();
+    if let Some(title) = fm.get("title").and_then(|v| v.first()) {
+        lines.push(Line::from(Span::styled(
+            title.clone(),
+            title_style.add_modifier(Modifier::BOLD),
+        )));
+    }
+    let mut meta: Vec<Span<'static>> = Vec::new();
+    if let Some(date) = fm.get("date").and_then(|v| v.first()) {
+        meta.push(Span::styled(date.clone(), dim));
+    }
+    for tag in fm.get("tags").unwrap_or_default() {
+        if !meta.is_empty() {
+            meta.push(Span::raw(" "));
+        }
+        meta.push(Span::styled(
+            format!("#{}", tag.trim_start_matches('#')),
+            Style::default().fg(accent),
+        ));
+    }
+    if !meta.is_empty() {
+        lines.push(Line::from(meta));
+    }
+    for (key, values) in &fm.fields {
+        if ["title", "date", "tags"]
+            .iter()
+            .any(|k| key.eq_ignore_ascii_case(k))
+        {
+            continue;
+        }
+        lines.push(Line::from(vec![
+            Span::styled(format!("{key}: "), dim),
+            Span::raw(values.join(", ")),
+        ]));
+    }
+    lines.push(Line::from(Span::styled("─".repeat(24), dim)));
+    lines.push(Line::default());
+    lines
+}
+
 fn apply_heading_styles(text: Text<'static>) -> Text<'static> {
     // Convert leading "# ", "## ", ... into styled heading lines without the markers.
     // This compensates for markdown backends that leave '#' in plain text.
-    let is_light = std::env::var("SB_THEME")
-        .ok()
-        .map(|v| v.eq_ignore_ascii_case("light"))
-        .unwrap_or(false);
+    let is_light = is_light_theme();
     let mut new_lines: Vec<Line<'static>> = Vec::with_capacity(text.lines.len());
     for line in text.lines.into_iter() {
         // Reconstruct full content to detect heading markers
@@ -190,24 +593,107 @@ pub fn find_md_images(src: &str) -> Vec<(String, String)> {
     out
 }
 
-pub fn render_preview(f: &mut Frame, area: Rect, preview: &Preview) {
-    // If opened file is a code file, show highlighted + diff view
-    if try_render_code_preview(f, area).is_some() {
-        return;
+/// Pick the best image protocol the terminal supports
+///
+/// This queries the terminal, so call it once after entering raw mode and
+/// keep the result. `forced` overrides the answer for debugging; without a
+/// usable answer images fall back to halfblocks.
+pub fn detect_picker(forced: Option<ProtocolType>) -> Picker {
+    if matches!(forced, Some(ProtocolType::Halfblocks)) {
+        return Picker::halfblocks();
+    }
+    let mut picker = Picker::from_query_stdio().unwrap_or_else(|_| Picker::halfblocks());
+    if let Some(protocol) = forced {
+        picker.set_protocol_type(protocol);
     }
+    picker
+}
+
+/// Sideways scroll offset, set by the caller only while line wrapping is off
+fn preview_hscroll() -> Option<u16> {
+    std::env::var("SB_PREVIEW_HSCROLL")
+        .ok()
+        .and_then(|s| s.parse().ok())
+}
+
+/// Screen rows above the scroll position and above the cursor's row, set
+/// by the caller only while the cursor moves by display line
+fn preview_wrap_rows() -> Option<(usize, usize)> {
+    let rows = std::env::var("SB_PREVIEW_WRAP_ROWS").ok()?;
+    let (scroll, cursor) = rows.split_once(':')?;
+    Some((scroll.parse().ok()?, cursor.parse().ok()?))
+}
+
+/// Character offsets where the screen rows of `line` start once it is
+/// word-wrapped to `width` columns
+///
+/// Rows break after the last whitespace that fits, like the wrapping
+/// paragraphs of the preview; a word wider than a row is split. The first
+/// row always starts at 0, and a `width` of 0 leaves the line unwrapped.
+pub fn wrap_starts(line: &str, width: usize) -> Vec<usize> {
+    let mut starts = vec![0];
+    if width == 0 {
+        return starts;
+    }
+    let widths: Vec<(char, usize)> = line
+        .chars()
+        .map(|c| (c, Span::raw(c.to_string()).width()))
+        .collect();
+    let mut row_width = 0;
+    // Where the row would break: just after the last whitespace in it
+    let mut last_break = None;
+    for (i, &(c, w)) in widths.iter().enumerate() {
+        if row_width + w > width && !c.is_whitespace() {
+            let start = match last_break {
+                Some(b) if b > *starts.last().unwrap_or(&0) => b,
+                _ => i,
+            };
+            starts.push(start);
+            row_width = widths[start..i].iter().map(|&(_, w)| w).sum();
+            last_break = None;
+        }
+        if c.is_whitespace() {
+            last_break = Some(i + 1);
+        }
+        row_width += w;
+    }
+    starts
+}
+
+/// Row of `starts` (from [`wrap_starts`]) that holds character `col`
+pub fn wrap_row_of(starts: &[usize], col: usize) -> usize {
+    starts.iter().rposition(|&start| start <= col).unwrap_or(0)
+}
+
+/// Draw `preview` with the renderer picked for the opened file
+pub fn render_preview(
+    f: &mut Frame,
+    area: Rect,
+    preview: &Preview,
+    renderer: Renderer,
+    picker: &Picker,
+) {
+    (renderer.draw)(f, area, preview, picker);
+}
+
+/// Draw the preview text, with images and PDF thumbnails drawn by `picker`
+fn draw_document(f: &mut Frame, area: Rect, preview: &Preview, picker: &Picker) {
     // Determine if we should overlay raw current line and dim rendered output
     // Show raw-line overlay only when explicitly enabled (e.g., during inline edit)
     let show_overlay = matches!(std::env::var("SB_OVERLAY").as_deref(), Ok("1"));
-    // Text preview
+    // Text preview, wrapped unless the caller scrolls it sideways
+    let hscroll = preview_hscroll();
     let mut paragraph = Paragraph::new(preview.text.clone())
-        .wrap(Wrap { trim: true })
         .block(Block::default().title("Preview").borders(Borders::ALL));
+    if hscroll.is_none() {
+        paragraph = paragraph.wrap(Wrap { trim: true });
+    }
     if show_overlay {
         paragraph = paragraph.style(Style::default().fg(Color::DarkGray));
     }
 
     // Split area vertically and render text + images + video placeholders
-    let total_media = preview.images.len() + preview.videos.len();
+    let total_media = preview.images.len() + preview.diagrams.len() + preview.videos.len();
     let mut rows = vec![Constraint::Min(
         area.height.saturating_sub((total_media as u16) * 12).max(3),
     )];
@@ -223,9 +709,42 @@ pub fn render_preview(f: &mut Frame, area: Rect, preview: &Preview) {
         .ok()
         .and_then(|s| s.parse().ok())
         .unwrap_or(0);
-    let paragraph = paragraph.scroll((scroll_top, 0));
+    // While the cursor moves by display line the caller counts the screen
+    // rows of wrapped lines above the scroll position and the cursor
+    let wrap_rows = preview_wrap_rows().filter(|_| hscroll.is_none());
+    let scroll_rows = match wrap_rows {
+        Some((rows, _)) => u16::try_from(rows).unwrap_or(u16::MAX),
+        None => scroll_top,
+    };
+    let paragraph = paragraph.scroll((scroll_rows, hscroll.unwrap_or(0)));
     f.render_widget(paragraph, chunks[0]);
 
+    // Highlight the rows covered by an active visual selection
+    if let Some((sel_start, sel_end)) = std::env::var("SB_PREVIEW_SELECTION")
+        .ok()
+        .and_then(|s| {
+            let (a, b) = s.split_once(':')?;
+            Some((a.parse::<usize>().ok()?, b.parse::<usize>().ok()?))
+        })
+        .filter(|_| chunks[0].height > 2)
+    {
+        let inner_y = chunks[0].y.saturating_add(1);
+        let inner_x = chunks[0].x.saturating_add(1);
+        let inner_h = chunks[0].height.saturating_sub(2) as usize;
+        let inner_w = chunks[0].width.saturating_sub(2);
+        let top = scroll_top as usize;
+        for line in sel_start.max(top)..=sel_end.min(top + inner_h.saturating_sub(1)) {
+            let area = Rect {
+                x: inner_x,
+                y: inner_y + (line - top) as u16,
+                width: inner_w,
+                height: 1,
+            };
+            f.buffer_mut()
+                .set_style(area, Style::default().bg(Color::Blue));
+        }
+    }
+
     // Always show a visible cursor line highlight in the preview's inner area
     if let Some(cursor) = std::env::var("SB_PREVIEW_CURSOR")
         .ok()
@@ -236,8 +755,11 @@ pub fn render_preview(f: &mut Frame, area: Rect, preview: &Preview) {
         let inner_h = chunks[0].height.saturating_sub(2);
         let inner_w = chunks[0].width.saturating_sub(2);
         if inner_h > 0 && inner_w > 0 && cursor >= scroll_top as usize {
-            let rel = cursor - scroll_top as usize;
-            if (rel as u16) < inner_h {
+            let rel = match wrap_rows {
+                Some((scroll, cursor)) => cursor.saturating_sub(scroll),
+                None => cursor - scroll_top as usize,
+            };
+            if rel < inner_h as usize {
                 let area = Rect {
                     x: inner_x,
                     y: inner_y + rel as u16,
@@ -350,21 +872,32 @@ pub fn render_preview(f: &mut Frame, area: Rect, preview: &Preview) {
 
     let mut idx = 1;
     for path in preview.images.iter() {
-        if let Ok(reader) = ImageReader::open(path) {
-            if let Ok(img) = reader.decode() {
-                let picker = Picker::halfblocks();
+        draw_image(f, chunks[idx], path, picker);
+        idx += 1;
+    }
+    for diagram in preview.diagrams.iter() {
+        match raster::lookup(&RasterJob::diagram(diagram)) {
+            Some(Raster::Rendered(img)) => {
                 let mut state = picker.new_resize_protocol(img);
                 let widget = StatefulImage::new().resize(Resize::Fit(None));
                 f.render_stateful_widget(widget, chunks[idx], &mut state);
-            } else {
-                let line = Line::from(format!("(image decode failed) {}", path.display()));
-                let p = Paragraph::new(line);
-                f.render_widget(p, chunks[idx]);
             }
-        } else {
-            let line = Line::from(format!("(image load failed) {}", path.display()));
-            let p = Paragraph::new(line);
-            f.render_widget(p, chunks[idx]);
+            None => {
+                let line = Line::from(format!("(rendering {} diagram...)", diagram.kind.name()));
+                f.render_widget(Paragraph::new(line), chunks[idx]);
+            }
+            Some(Raster::ToolMissing) => {
+                let line = Line::from(format!(
+                    "(install {} to render) {} diagram",
+                    diagram.kind.package(),
+                    diagram.kind.name()
+                ));
+                f.render_widget(Paragraph::new(line), chunks[idx]);
+            }
+            Some(Raster::Failed(err)) => {
+                let line = Line::from(format!("({} render failed: {err})", diagram.kind.name()));
+                f.render_widget(Paragraph::new(line), chunks[idx]);
+            }
         }
         idx += 1;
     }
@@ -376,59 +909,233 @@ pub fn render_preview(f: &mut Frame, area: Rect, preview: &Preview) {
     }
 }
 
+/// Hex dump of the rows of the opened file that fit in `area`
+///
+/// The caller fills the preview text from [`App::hex_text`], which reads
+/// only the bytes on screen, so large files scroll as quickly as small
+/// ones, and sets the file (`SB_CURRENT_FILE`) and its length
+/// (`SB_HEX_LEN`) for the title.
+///
+/// [`App::hex_text`]: crate::app::App::hex_text
+fn draw_hex(f: &mut Frame, area: Rect, preview: &Preview, picker: &Picker) {
+    let (Ok(path), Some(len)) = (
+        std::env::var("SB_CURRENT_FILE"),
+        std::env::var("SB_HEX_LEN")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok()),
+    ) else {
+        draw_document(f, area, preview, picker);
+        return;
+    };
+    let name = Path::new(&path)
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let block = Block::default()
+        .title(format!("Hex - {name} ({len} bytes)"))
+        .borders(Borders::ALL);
+    f.render_widget(Paragraph::new(preview.text.clone()).block(block), area);
+}
+
+/// Draw the image, or the first page of the PDF, at `path` into `area`
+fn draw_image(f: &mut Frame, area: Rect, path: &Path, picker: &Picker) {
+    if is_pdf(path) {
+        match raster::lookup(&RasterJob::pdf_page(path)) {
+            Some(Raster::Rendered(img)) => {
+                let mut state = picker.new_resize_protocol(img);
+                let widget = StatefulImage::new().resize(Resize::Fit(None));
+                f.render_stateful_widget(widget, area, &mut state);
+            }
+            None => {
+                let line = Line::from(format!("(rendering PDF...) {}", path.display()));
+                f.render_widget(Paragraph::new(line), area);
+            }
+            Some(Raster::ToolMissing) => {
+                let line = Line::from(format!(
+                    "(install poppler for PDF preview) {}",
+                    path.display()
+                ));
+                f.render_widget(Paragraph::new(line), area);
+            }
+            Some(Raster::Failed(err)) => {
+                let line = Line::from(format!("(pdf render failed: {err}) {}", path.display()));
+                f.render_widget(Paragraph::new(line), area);
+            }
+        }
+        return;
+    }
+    if let Ok(reader) = ImageReader::open(path) {
+        if let Ok(img) = reader.decode() {
+            let mut state = picker.new_resize_protocol(img);
+            let widget = StatefulImage::new().resize(Resize::Fit(None));
+            f.render_stateful_widget(widget, area, &mut state);
+        } else {
+            let line = Line::from(format!("(image decode failed) {}", path.display()));
+            let p = Paragraph::new(line);
+            f.render_widget(p, area);
+        }
+    } else {
+        let line = Line::from(format!("(image load failed) {}", path.display()));
+        let p = Paragraph::new(line);
+        f.render_widget(p, area);
+    }
+}
+
+/// An image file on its own, scaled to fill the pane
+fn draw_image_preview(f: &mut Frame, area: Rect, preview: &Preview, picker: &Picker) {
+    let block = Block::default().title("Preview").borders(Borders::ALL);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    if let Some(path) = preview.images.first() {
+        draw_image(f, inner, path, picker);
+    }
+}
+
+fn is_pdf(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("pdf"))
+}
+
+/// Fenced code block languages that can be drawn by an external tool
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiagramKind {
+    /// ```` ```mermaid ```` rendered by `mmdc` (mermaid-cli)
+    Mermaid,
+    /// ```` ```dot ```` rendered by `dot` (graphviz)
+    Graphviz,
+}
+
+impl DiagramKind {
+    fn from_fence_info(info: &str) -> Option<Self> {
+        let lang = info.split_whitespace().next()?;
+        if lang.eq_ignore_ascii_case("mermaid") {
+            Some(Self::Mermaid)
+        } else if lang.eq_ignore_ascii_case("dot") || lang.eq_ignore_ascii_case("graphviz") {
+            Some(Self::Graphviz)
+        } else {
+            None
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Mermaid => "mermaid",
+            Self::Graphviz => "graphviz",
+        }
+    }
+
+    /// Package that provides the renderer, for the install hint
+    fn package(self) -> &'static str {
+        match self {
+            Self::Mermaid => "mermaid-cli",
+            Self::Graphviz => "graphviz",
+        }
+    }
+}
+
+/// A diagram code block found in markdown
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagram {
+    pub kind: DiagramKind,
+    pub source: String,
+}
+
+/// Blame results keyed by path, invalidated when the file's mtime changes.
+static BLAME_CACHE: Lazy<Mutex<HashMap<PathBuf, (Option<SystemTime>, Option<Vec<BlameLine>>)>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+const BLAME_GUTTER_WIDTH: usize = 12;
+
+fn blame_enabled() -> bool {
+    matches!(std::env::var("SB_BLAME").as_deref(), Ok("1"))
+}
+
+/// Run `git blame --line-porcelain` for `path`, cached until the file changes.
+fn load_blame(path: &Path) -> Option<Vec<BlameLine>> {
+    let mtime = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    if let Ok(cache) = BLAME_CACHE.lock() {
+        if let Some((cached_mtime, blame)) = cache.get(path) {
+            if *cached_mtime == mtime {
+                return blame.clone();
+            }
+        }
+    }
+    let file_name = path.file_name()?;
+    let blame = Command::new("git")
+        .args(["blame", "--line-porcelain", "--"])
+        .arg(file_name)
+        .current_dir(path.parent().unwrap_or(Path::new(".")))
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| parse_blame_porcelain(&String::from_utf8_lossy(&o.stdout)));
+    if let Ok(mut cache) = BLAME_CACHE.lock() {
+        cache.insert(path.to_path_buf(), (mtime, blame.clone()));
+    }
+    blame
+}
+
+/// Gutter cell for a working-tree line; `None` line (deleted in diff) gets blank padding.
+fn blame_span(blame: Option<&[BlameLine]>, line: Option<usize>) -> Option<Span<'static>> {
+    let blame = blame?;
+    let entry = line.and_then(|l| blame.get(l));
+    let (text, style) = match entry {
+        Some(b) if b.is_uncommitted() => (
+            "uncommitted".to_string(),
+            Style::default().fg(Color::Yellow),
+        ),
+        Some(b) => (
+            format!("{} {}", b.short_hash(), b.author_initials()),
+            Style::default().fg(Color::Magenta),
+        ),
+        // Lines beyond the committed file (e.g. unsaved additions)
+        None if line.is_some() => (
+            "uncommitted".to_string(),
+            Style::default().fg(Color::Yellow),
+        ),
+        None => (String::new(), Style::default()),
+    };
+    Some(Span::styled(
+        format!("{text:<width$} ", width = BLAME_GUTTER_WIDTH - 1),
+        style,
+    ))
+}
+
 static SYNTAX_SET: Lazy<SyntaxSet> = Lazy::new(SyntaxSet::load_defaults_newlines);
 static THEME_SET: Lazy<ThemeSet> = Lazy::new(ThemeSet::load_defaults);
 
+/// Whether the preview shows `path` as highlighted code rather than markdown
+pub fn is_code_file(path: &Path) -> bool {
+    RENDERERS
+        .by_extension(path)
+        .is_some_and(|renderer| renderer.name == CODE.name)
+}
+
+/// Highlighted source, or the plain text when it cannot be read
+fn draw_code(f: &mut Frame, area: Rect, preview: &Preview, picker: &Picker) {
+    if try_render_code_preview(f, area).is_none() {
+        draw_document(f, area, preview, picker);
+    }
+}
+
 fn try_render_code_preview(f: &mut Frame, area: Rect) -> Option<()> {
     // We need the current file path and buffer; pull from global if exposed
     // Since we don't have direct access to `App` here, detect via environment variables
     // Simplify: read env SB_CURRENT_FILE and SB_CURRENT_TEXT set by caller
     let path = std::env::var("SB_CURRENT_FILE").ok()?;
-    let ext = std::path::Path::new(&path)
+    let ext = Path::new(&path)
         .extension()
         .and_then(|e| e.to_str())
         .unwrap_or("")
         .to_lowercase();
-    let is_code = matches!(
-        ext.as_str(),
-        "rs" | "ts"
-            | "tsx"
-            | "js"
-            | "jsx"
-            | "py"
-            | "rb"
-            | "go"
-            | "java"
-            | "cpp"
-            | "c"
-            | "h"
-            | "hpp"
-            | "cs"
-            | "php"
-            | "swift"
-            | "kt"
-            | "scala"
-            | "sh"
-            | "bash"
-            | "zsh"
-            | "fish"
-            | "ps1"
-            | "yml"
-            | "yaml"
-            | "toml"
-            | "json"
-            | "xml"
-            | "html"
-            | "css"
-            | "scss"
-            | "sass"
-            | "sql"
-            | "tex"
-    );
-    if !is_code {
-        return None;
-    }
     let text = std::env::var("SB_CURRENT_TEXT").ok().unwrap_or_default();
+    let blame = if blame_enabled() {
+        load_blame(Path::new(&path))
+    } else {
+        None
+    };
+    let blame = blame.as_deref();
 
     // Get git diff if available
     let rel_for_git = Command::new("git")
@@ -508,6 +1215,11 @@ fn try_render_code_preview(f: &mut Frame, area: Rect) -> Option<()> {
                     ChangeTag::Insert | ChangeTag::Equal => line_num - 1 == cursor_line,
                 };
 
+                spans.extend(blame_span(
+                    blame,
+                    (change.tag() != ChangeTag::Delete).then(|| line_num - 1),
+                ));
+
                 // Add diff marker and line number
                 match change.tag() {
                     ChangeTag::Delete => {
@@ -590,6 +1302,10 @@ fn try_render_code_preview(f: &mut Frame, area: Rect) -> Option<()> {
         } else {
             // Fallback without syntax highlighting
             for change in diff.iter_all_changes() {
+                let gutter = blame_span(
+                    blame,
+                    (change.tag() != ChangeTag::Delete).then(|| line_num - 1),
+                );
                 let (prefix, color) = match change.tag() {
                     ChangeTag::Delete => (format!("{line_num:4} - "), Color::Red),
                     ChangeTag::Insert => {
@@ -604,10 +1320,13 @@ fn try_render_code_preview(f: &mut Frame, area: Rect) -> Option<()> {
                     }
                 };
                 let content = change.to_string_lossy();
-                lines.push(Line::from(vec![
-                    Span::styled(prefix, Style::default().fg(color)),
-                    Span::styled(content.to_string(), Style::default().fg(color)),
-                ]));
+                let mut spans: Vec<Span> = gutter.into_iter().collect();
+                spans.push(Span::styled(prefix, Style::default().fg(color)));
+                spans.push(Span::styled(
+                    content.to_string(),
+                    Style::default().fg(color),
+                ));
+                lines.push(Line::from(spans));
             }
         }
     } else {
@@ -619,6 +1338,7 @@ fn try_render_code_preview(f: &mut Frame, area: Rect) -> Option<()> {
                 let is_cursor_line = (line_num - 1) == cursor_line;
                 let regions = h.highlight_line(raw, &SYNTAX_SET).unwrap_or_default();
                 let mut spans: Vec<Span> = Vec::new();
+                spans.extend(blame_span(blame, Some(line_num - 1)));
 
                 let prefix_style = if is_cursor_line {
                     Style::default().fg(Color::White).bg(Color::DarkGray)
@@ -657,10 +1377,11 @@ fn try_render_code_preview(f: &mut Frame, area: Rect) -> Option<()> {
                     Style::default()
                 };
 
-                lines.push(Line::from(vec![
-                    Span::styled(format!("{line_num:4}   "), prefix_style),
-                    Span::styled(raw.to_string(), text_style),
-                ]));
+                let mut spans: Vec<Span> =
+                    blame_span(blame, Some(line_num - 1)).into_iter().collect();
+                spans.push(Span::styled(format!("{line_num:4}   "), prefix_style));
+                spans.push(Span::styled(raw.to_string(), text_style));
+                lines.push(Line::from(spans));
                 line_num += 1;
             }
         }
@@ -689,17 +1410,25 @@ fn try_render_code_preview(f: &mut Frame, area: Rect) -> Option<()> {
             total_lines
         )
     };
+    let title = if blame.is_some() {
+        format!("{title} [blame]")
+    } else {
+        title
+    };
 
-    let para = Paragraph::new(Text::from(lines))
-        .wrap(Wrap { trim: false })
+    let hscroll = preview_hscroll();
+    let mut para = Paragraph::new(Text::from(lines))
         .block(Block::default().title(title).borders(Borders::ALL))
         .scroll((
             std::env::var("SB_PREVIEW_SCROLL")
                 .ok()
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(0),
-            0,
+            hscroll.unwrap_or(0),
         ));
+    if hscroll.is_none() {
+        para = para.wrap(Wrap { trim: false });
+    }
     f.render_widget(para, area);
 
     Some(())
@@ -731,6 +1460,64 @@ pub fn find_md_videos(src: &str) -> Vec<(String, String)> {
     out
 }
 
+pub fn find_md_pdfs(src: &str) -> Vec<String> {
+    // [text](file.pdf) and ![alt](file.pdf)
+    let mut out = vec![];
+    let mut rest = src;
+    while let Some(start) = rest.find("](") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find(')') else {
+            break;
+        };
+        let target = after[..end].trim();
+        if target.to_ascii_lowercase().ends_with(".pdf") && !target.contains("://") {
+            out.push(target.to_string());
+        }
+        rest = &after[end..];
+    }
+    out
+}
+
+/// Fenced ```` ```mermaid ```` and ```` ```dot ```` blocks, in document order
+pub fn find_md_diagrams(src: &str) -> Vec<Diagram> {
+    let mut out = vec![];
+    // (fence char, fence length, diagram kind, body) of the open block
+    let mut open: Option<(char, usize, Option<DiagramKind>, String)> = None;
+    for line in src.lines() {
+        let trimmed = line.trim_start();
+        let fence_char = trimmed.chars().next().filter(|c| *c == '`' || *c == '~');
+        let fence_len = fence_char.map_or(0, |c| trimmed.chars().take_while(|x| *x == c).count());
+        match open.as_mut() {
+            None => {
+                if let Some(c) = fence_char.filter(|_| fence_len >= 3) {
+                    let kind = DiagramKind::from_fence_info(&trimmed[fence_len..]);
+                    open = Some((c, fence_len, kind, String::new()));
+                }
+            }
+            Some((c, len, kind, body)) => {
+                let closes = fence_char == Some(*c)
+                    && fence_len >= *len
+                    && trimmed[fence_len..].trim().is_empty();
+                if !closes {
+                    body.push_str(line);
+                    body.push('\n');
+                    continue;
+                }
+                if let Some(kind) = *kind {
+                    if !body.trim().is_empty() {
+                        out.push(Diagram {
+                            kind,
+                            source: std::mem::take(body),
+                        });
+                    }
+                }
+                open = None;
+            }
+        }
+    }
+    out
+}
+
 // Utility to compute a Rect for a given line index if we wanted to highlight it.
 #[allow(dead_code)]
 pub fn line_rect(area: Rect, line_idx: usize, total_lines: usize) -> Option<Rect> {
@@ -748,3 +1535,199 @@ pub fn line_rect(area: Rect, line_idx: usize, total_lines: usize) -> Option<Rect
         height: 1,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrap_starts_break_after_whitespace_or_inside_long_words() {
+        assert_eq!(wrap_starts("one two three", 7), vec![0, 8]);
+        assert_eq!(wrap_starts("one two three", 20), vec![0]);
+        assert_eq!(wrap_starts("abcdefghij", 4), vec![0, 4, 8]);
+        assert_eq!(wrap_starts("ab    cd", 4), vec![0, 6]);
+        assert_eq!(wrap_starts("anything", 0), vec![0]);
+        assert_eq!(wrap_starts("", 5), vec![0]);
+
+        let starts = wrap_starts("one two three", 7);
+        assert_eq!(wrap_row_of(&starts, 7), 0);
+        assert_eq!(wrap_row_of(&starts, 8), 1);
+        assert_eq!(wrap_row_of(&starts, 40), 1);
+    }
+
+    #[test]
+    fn rendered_line_follows_headings() {
+        let text = Text::from(vec![
+            Line::from("Title"),
+            Line::from(""),
+            Line::from("one two three"),
+            Line::from(""),
+            Line::from("Usage"),
+            Line::from("run it"),
+        ]);
+        // Source: heading, blank, three paragraph lines, blank, heading, body
+        let headings = [(0, "Title"), (6, "Usage")];
+        assert_eq!(rendered_line(&text, &headings, 8, 0), 0);
+        assert_eq!(rendered_line(&text, &headings, 8, 3), 2);
+        assert_eq!(rendered_line(&text, &headings, 8, 6), 4);
+        assert_eq!(rendered_line(&text, &headings, 8, 7), 5);
+        assert_eq!(rendered_line(&text, &headings, 8, 40), 5);
+        // A heading the renderer changed is skipped rather than misplaced
+        assert_eq!(rendered_line(&text, &[(6, "Usage!")], 8, 6), 4);
+    }
+
+    #[test]
+    fn find_md_pdfs_matches_local_links_only() {
+        let src = "See [spec](docs/spec.PDF) and ![scan](scan.pdf).\n\
+                   Remote [paper](https://example.com/p.pdf), image ![x](a.png)";
+        assert_eq!(find_md_pdfs(src), vec!["docs/spec.PDF", "scan.pdf"]);
+    }
+
+    #[test]
+    fn find_md_diagrams_picks_mermaid_and_dot_fences() {
+        let src = "# Flow\n\
+                   ```mermaid\n\
+                   graph TD; A-->B\n\
+                   ```\n\
+                   ```rust\n\
+                   fn main() {}\n\
+                   ```\n\
+                   ~~~dot\n\
+                   digraph { a -> b }\n\
+                   ~~~\n";
+        assert_eq!(
+            find_md_diagrams(src),
+            vec![
+                Diagram {
+                    kind: DiagramKind::Mermaid,
+                    source: "graph TD; A-->B\n".into(),
+                },
+                Diagram {
+                    kind: DiagramKind::Graphviz,
+                    source: "digraph { a -> b }\n".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn find_md_diagrams_ignores_nested_and_unclosed_fences() {
+        let nested = "````markdown\n```mermaid\ngraph TD; A-->B\n```\n````\n";
+        assert!(find_md_diagrams(nested).is_empty());
+        assert!(find_md_diagrams("```mermaid\ngraph TD; A-->B\n").is_empty());
+    }
+
+    #[test]
+    fn parse_frontmatter_reads_scalars_and_lists() {
+        let src = "---\n\
+                   title: \"Weekly notes\"\n\
+                   date: 2024-05-01\n\
+                   tags: [rust, tui]\n\
+                   aliases:\n  - week-18\n  - 'w18'\n\
+                   ---\n# Body\n";
+        let (fm, body) = parse_frontmatter(src).expect("frontmatter");
+        assert_eq!(body, "# Body\n");
+        assert_eq!(fm.get("title"), Some(&["Weekly notes".to_string()][..]));
+        assert_eq!(fm.get("date"), Some(&["2024-05-01".to_string()][..]));
+        assert_eq!(
+            fm.get("tags"),
+            Some(&["rust".to_string(), "tui".to_string()][..])
+        );
+        assert_eq!(
+            fm.get("aliases"),
+            Some(&["week-18".to_string(), "w18".to_string()][..])
+        );
+    }
+
+    #[test]
+    fn parse_frontmatter_rejects_malformed_blocks() {
+        // Not at the start of the file
+        assert!(parse_frontmatter("intro\n---\ntitle: x\n---\n").is_none());
+        // No closing fence
+        assert!(parse_frontmatter("---\ntitle: x\n").is_none());
+        // Not key/value
+        assert!(parse_frontmatter("---\njust some text\n---\n").is_none());
+        // Nested maps are not understood
+        assert!(parse_frontmatter("---\nauthor:\n  name: x\n---\n").is_none());
+    }
+
+    #[test]
+    fn from_markdown_hides_raw_frontmatter() {
+        let src = "---\ntitle: Hello\ntags: [a]\n---\nBody text\n";
+        let preview = Preview::from_markdown(Path::new("note.md"), src).expect("preview");
+        let rendered: Vec<String> = preview.text.lines.iter().map(|l| l.to_string()).collect();
+        assert_eq!(rendered[0], "Hello");
+        assert_eq!(rendered[1], "#a");
+        assert!(rendered.iter().any(|l| l.contains("Body text")));
+        assert!(!rendered.iter().any(|l| l.contains("title:") || l == "---"));
+    }
+
+    #[test]
+    fn is_pdf_is_case_insensitive() {
+        assert!(is_pdf(Path::new("a/b.Pdf")));
+        assert!(!is_pdf(Path::new("a/b.png")));
+    }
+
+    #[test]
+    fn renderers_follow_extension_then_contents() {
+        let name = |path: &str| RENDERERS.for_path(Path::new(path)).name;
+        assert_eq!(name("notes.md"), "markdown");
+        assert_eq!(name("src/main.RS"), "code");
+        assert_eq!(name("logo.png"), "image");
+        assert_eq!(name("build.log"), "plain text");
+        assert!(is_code_file(Path::new("Cargo.toml")));
+        assert!(!is_code_file(Path::new("notes.md")));
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let blob = dir.path().join("blob");
+        std::fs::write(&blob, b"\x7fELF\0\x02").expect("write blob");
+        let readme = dir.path().join("README");
+        std::fs::write(&readme, "# Read me").expect("write readme");
+        assert_eq!(RENDERERS.for_path(&blob).name, "hex");
+        assert_eq!(RENDERERS.for_path(&readme).name, "markdown");
+
+        let mut registry = RendererRegistry::empty();
+        registry.register(&["MD"], PLAIN_TEXT);
+        assert_eq!(registry.for_path(Path::new("notes.md")).name, "plain text");
+    }
+
+    #[test]
+    fn hex_preview_reads_a_window_and_marks_the_selection() {
+        let text = |lines: &[Line<'static>]| -> Vec<String> {
+            lines
+                .iter()
+                .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+                .collect()
+        };
+        assert_eq!(
+            text(&hex_lines(b"\x7fELF\0\x02 hi\nthere, world", 0, 16, None)),
+            [
+                "00000000  7f 45 4c 46 00 02 20 68 69 0a 74 68 65 72 65 2c  |.ELF.. hi.there,|",
+                "00000010  20 77 6f 72 6c 64                                | world|",
+            ]
+        );
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let blob = dir.path().join("big.bin");
+        let contents: Vec<u8> = (0..=255).cycle().take(100_000).collect();
+        std::fs::write(&blob, &contents).expect("write blob");
+        let window = read_hex_window(&blob, 0x1000, 8).expect("read window");
+        assert_eq!(window, &contents[0x1000..0x1008]);
+        let lines = hex_lines(&window, 0x1000, 4, Some((0x1002, 0x1004)));
+        assert_eq!(
+            text(&lines),
+            [
+                "00001000  00 01 02 03  |....|",
+                "00001004  04 05 06 07  |....|"
+            ]
+        );
+        let marked: Vec<&str> = lines
+            .iter()
+            .flat_map(|line| &line.spans)
+            .filter(|span| span.style.add_modifier.contains(Modifier::REVERSED))
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert_eq!(marked, ["02", "03", ".", ".", "04", "."]);
+        assert_eq!(read_hex_window(&blob, 99_998, 8).expect("tail").len(), 2);
+    }
+}
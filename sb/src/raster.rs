@@ -0,0 +1,305 @@
+//! Rasterizing PDFs and diagrams for the preview on a background thread
+//!
+//! External renderers such as `pdftoppm`, `mmdc` and `dot` take long enough
+//! to stall a frame,
+//! so the draw path only ever looks results up with [`lookup`]. A miss
+//! queues the job for a single worker thread and draws a placeholder; the
+//! worker sends each image back over a channel, and [`poll`], called from
+//! [`App::poll_background_tasks`] like the other loaders, moves it into the
+//! cache so the next frame shows it. The cache keeps the
+//! [`MAX_CACHED`] most recently drawn results.
+//!
+//! The worker writes its intermediate files into a fresh directory only
+//! the current user can enter, removed again when the process exits.
+//!
+//! [`App::poll_background_tasks`]: crate::app::App::poll_background_tasks
+
+use crate::preview::{Diagram, DiagramKind};
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Mutex;
+use std::thread;
+use std::time::SystemTime;
+use tempfile::TempDir;
+
+/// Results kept before the least recently drawn is dropped
+pub const MAX_CACHED: usize = 32;
+
+/// What to rasterize; also the cache key
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RasterJob {
+    /// First page of the PDF at the path, as it was at the given mtime
+    PdfPage(PathBuf, Option<SystemTime>),
+    /// A diagram code block, by its source
+    Diagram(DiagramKind, String),
+}
+
+impl RasterJob {
+    /// First page of the PDF at `path`, keyed by its current mtime so an
+    /// edited file is rendered again
+    pub fn pdf_page(path: &Path) -> Self {
+        let mtime = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+        Self::PdfPage(path.to_path_buf(), mtime)
+    }
+
+    pub fn diagram(diagram: &Diagram) -> Self {
+        Self::Diagram(diagram.kind, diagram.source.clone())
+    }
+}
+
+/// Outcome of a rasterizing job
+#[derive(Clone)]
+pub enum Raster {
+    Rendered(image::DynamicImage),
+    /// The renderer isn't installed
+    ToolMissing,
+    Failed(String),
+}
+
+struct Rasterizer {
+    jobs: Sender<RasterJob>,
+    results: Receiver<(RasterJob, Raster)>,
+    pending: HashSet<RasterJob>,
+    /// Results with the tick they were last looked up at
+    cache: HashMap<RasterJob, (u64, Raster)>,
+    tick: u64,
+}
+
+static RASTERIZER: Lazy<Mutex<Rasterizer>> = Lazy::new(|| Mutex::new(Rasterizer::spawn()));
+
+impl Rasterizer {
+    fn spawn() -> Self {
+        let (jobs, job_rx) = mpsc::channel::<RasterJob>();
+        let (result_tx, results) = mpsc::channel();
+        thread::spawn(move || {
+            let scratch = private_dir();
+            while let Ok(job) = job_rx.recv() {
+                let raster = match &scratch {
+                    Ok(dir) => rasterize(&job, dir.path()),
+                    Err(e) => Raster::Failed(format!("no scratch directory: {e}")),
+                };
+                if result_tx.send((job, raster)).is_err() {
+                    break;
+                }
+            }
+        });
+        Self {
+            jobs,
+            results,
+            pending: HashSet::new(),
+            cache: HashMap::new(),
+            tick: 0,
+        }
+    }
+
+    fn insert(&mut self, job: RasterJob, raster: Raster) {
+        self.tick += 1;
+        self.cache.insert(job, (self.tick, raster));
+        while self.cache.len() > MAX_CACHED {
+            let oldest = self
+                .cache
+                .iter()
+                .min_by_key(|(_, (used, _))| *used)
+                .map(|(job, _)| job.clone());
+            match oldest {
+                Some(job) => self.cache.remove(&job),
+                None => break,
+            };
+        }
+    }
+}
+
+/// The finished result of `job`, or `None` while it is still being drawn
+///
+/// The first call for a job queues it on the worker.
+pub fn lookup(job: &RasterJob) -> Option<Raster> {
+    let mut rasterizer = RASTERIZER.lock().ok()?;
+    rasterizer.tick += 1;
+    let tick = rasterizer.tick;
+    if let Some((used, raster)) = rasterizer.cache.get_mut(job) {
+        *used = tick;
+        return Some(raster.clone());
+    }
+    if rasterizer.pending.insert(job.clone()) && rasterizer.jobs.send(job.clone()).is_err() {
+        rasterizer.pending.remove(job);
+        return Some(Raster::Failed("rasterizer stopped".into()));
+    }
+    None
+}
+
+/// Move finished jobs into the cache, returning whether any arrived
+pub fn poll() -> bool {
+    let Ok(mut rasterizer) = RASTERIZER.lock() else {
+        return false;
+    };
+    let mut arrived = false;
+    while let Ok((job, raster)) = rasterizer.results.try_recv() {
+        rasterizer.pending.remove(&job);
+        rasterizer.insert(job, raster);
+        arrived = true;
+    }
+    arrived
+}
+
+/// Whether any job is still being drawn
+pub fn busy() -> bool {
+    RASTERIZER
+        .lock()
+        .is_ok_and(|rasterizer| !rasterizer.pending.is_empty())
+}
+
+/// A new directory only the current user can read or enter
+fn private_dir() -> io::Result<TempDir> {
+    let mut builder = tempfile::Builder::new();
+    builder.prefix("sb-raster-");
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        builder.permissions(std::fs::Permissions::from_mode(0o700));
+    }
+    builder.tempdir()
+}
+
+fn rasterize(job: &RasterJob, scratch: &Path) -> Raster {
+    match job {
+        RasterJob::PdfPage(path, _) => rasterize_pdf_first_page(path, scratch),
+        RasterJob::Diagram(kind, source) => rasterize_diagram(*kind, source, scratch),
+    }
+}
+
+/// Shell out to `pdftoppm` (poppler) to render page 1 as a PNG.
+fn rasterize_pdf_first_page(path: &Path, scratch: &Path) -> Raster {
+    let out_root = scratch.join("pdf-page");
+    let result = Command::new("pdftoppm")
+        .args([
+            "-png",
+            "-f",
+            "1",
+            "-l",
+            "1",
+            "-singlefile",
+            "-scale-to",
+            "800",
+        ])
+        .arg(path)
+        .arg(&out_root)
+        .output();
+    let output = match result {
+        Ok(output) => output,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Raster::ToolMissing,
+        Err(e) => return Raster::Failed(e.to_string()),
+    };
+    let png = out_root.with_extension("png");
+    if !output.status.success() {
+        let _ = std::fs::remove_file(&png);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Raster::Failed(stderr.lines().next().unwrap_or("pdftoppm failed").into());
+    }
+    decode_png(&png)
+}
+
+/// Shell out to `mmdc` or `dot` to render a diagram as a PNG.
+fn rasterize_diagram(kind: DiagramKind, source: &str, scratch: &Path) -> Raster {
+    let stem = scratch.join("diagram");
+    let input = stem.with_extension(match kind {
+        DiagramKind::Mermaid => "mmd",
+        DiagramKind::Graphviz => "dot",
+    });
+    let png = stem.with_extension("png");
+    if let Err(e) = std::fs::write(&input, source) {
+        return Raster::Failed(e.to_string());
+    }
+    let mut command = match kind {
+        DiagramKind::Mermaid => {
+            let mut c = Command::new("mmdc");
+            c.arg("-i").arg(&input).arg("-o").arg(&png);
+            c
+        }
+        DiagramKind::Graphviz => {
+            let mut c = Command::new("dot");
+            c.arg("-Tpng").arg("-o").arg(&png).arg(&input);
+            c
+        }
+    };
+    let result = command.output();
+    let _ = std::fs::remove_file(&input);
+    let output = match result {
+        Ok(output) => output,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Raster::ToolMissing,
+        Err(e) => return Raster::Failed(e.to_string()),
+    };
+    if !output.status.success() {
+        let _ = std::fs::remove_file(&png);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let first = stderr.lines().find(|l| !l.trim().is_empty());
+        return Raster::Failed(first.unwrap_or("renderer failed").trim().into());
+    }
+    decode_png(&png)
+}
+
+/// Decode and remove a PNG a renderer wrote
+fn decode_png(png: &Path) -> Raster {
+    let decoded = image::open(png);
+    let _ = std::fs::remove_file(png);
+    match decoded {
+        Ok(img) => Raster::Rendered(img),
+        Err(e) => Raster::Failed(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[cfg(unix)]
+    #[test]
+    fn scratch_directory_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = private_dir().expect("tempdir");
+        let mode = std::fs::metadata(dir.path())
+            .expect("metadata")
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o700);
+    }
+
+    #[test]
+    fn cache_drops_the_least_recently_drawn_result() {
+        let mut rasterizer = Rasterizer::spawn();
+        let job = |i: usize| RasterJob::Diagram(DiagramKind::Graphviz, format!("graph {i}"));
+        for i in 0..MAX_CACHED {
+            rasterizer.insert(job(i), Raster::ToolMissing);
+        }
+        // Drawing the first one again keeps it
+        rasterizer.tick += 1;
+        rasterizer.cache.get_mut(&job(0)).expect("cached").0 = rasterizer.tick;
+        rasterizer.insert(job(MAX_CACHED), Raster::ToolMissing);
+
+        assert_eq!(rasterizer.cache.len(), MAX_CACHED);
+        assert!(rasterizer.cache.contains_key(&job(0)));
+        assert!(!rasterizer.cache.contains_key(&job(1)));
+    }
+
+    #[test]
+    fn lookups_queue_once_and_poll_collects_the_result() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let job = RasterJob::pdf_page(&dir.path().join("missing.pdf"));
+        assert!(lookup(&job).is_none());
+        assert!(lookup(&job).is_none());
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while lookup(&job).is_none() {
+            poll();
+            assert!(Instant::now() < deadline, "rasterizer never answered");
+            thread::sleep(Duration::from_millis(10));
+        }
+        // pdftoppm is missing or fails on a missing file; either way no image
+        assert!(!matches!(lookup(&job), Some(Raster::Rendered(_))));
+    }
+}
@@ -0,0 +1,216 @@
+//! Recursive content search across the workspace
+//!
+//! [`spawn_search`] walks the tree on a background thread and streams
+//! matching lines back over a channel, the same way the tree and git status
+//! loaders report to [`App::poll_background_tasks`].
+//!
+//! [`App::poll_background_tasks`]: crate::app::App::poll_background_tasks
+
+use crate::fs::{is_trash, read_text_file, FileContents};
+use ignore::WalkBuilder;
+use regex::{Regex, RegexBuilder};
+use saorsa_cli_core::IgnoreRules;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+/// Files larger than this (1MB) are skipped by default
+pub const DEFAULT_MAX_FILE_SIZE: u64 = 1024 * 1024;
+
+/// Stop reporting after this many matches
+pub const MAX_HITS: usize = 2000;
+
+/// One matching line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchHit {
+    pub path: PathBuf,
+    /// Zero-based line index
+    pub line: usize,
+    pub text: String,
+}
+
+/// Progress reported by a running search
+#[derive(Debug)]
+pub enum SearchEvent {
+    Hit(SearchHit),
+    /// The walk finished after reading `files` files
+    Done {
+        files: usize,
+        truncated: bool,
+    },
+}
+
+/// What to search for and which files to look at
+#[derive(Debug, Clone)]
+pub struct SearchOptions {
+    pub respect_gitignore: bool,
+    pub ignore_patterns: Vec<String>,
+    pub max_file_size: u64,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            respect_gitignore: true,
+            ignore_patterns: Vec::new(),
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+        }
+    }
+}
+
+/// Compile a query typed by the user
+///
+/// `/pattern/` is a regular expression; anything else is matched literally.
+/// Queries without uppercase letters match case-insensitively.
+pub fn compile_query(query: &str) -> Result<Regex, regex::Error> {
+    let pattern = match query
+        .strip_prefix('/')
+        .and_then(|rest| rest.strip_suffix('/'))
+    {
+        Some(pattern) if !pattern.is_empty() => pattern.to_string(),
+        _ => regex::escape(query),
+    };
+    RegexBuilder::new(&pattern)
+        .case_insensitive(!query.chars().any(char::is_uppercase))
+        .build()
+}
+
+/// Search every file under `root` on a background thread
+///
+/// Dropping the receiver cancels the search at the next match.
+pub fn spawn_search(root: PathBuf, query: Regex, options: SearchOptions) -> Receiver<SearchEvent> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let extra = IgnoreRules::new(&root, false, &options.ignore_patterns);
+        let walker = WalkBuilder::new(&root)
+            .git_ignore(options.respect_gitignore)
+            .git_global(false)
+            .git_exclude(false)
+            .require_git(false)
+            .filter_entry(move |entry| {
+                let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+                !is_trash(entry.path()) && !extra.is_ignored(entry.path(), is_dir)
+            })
+            .build();
+
+        let mut files = 0;
+        let mut hits = 0;
+        for entry in walker.filter_map(|e| e.ok()) {
+            if !entry.file_type().is_some_and(|t| t.is_file()) {
+                continue;
+            }
+            let Some(matches) = search_file(entry.path(), &query, options.max_file_size) else {
+                continue;
+            };
+            files += 1;
+            for hit in matches {
+                if hits == MAX_HITS {
+                    let _ = tx.send(SearchEvent::Done {
+                        files,
+                        truncated: true,
+                    });
+                    return;
+                }
+                if tx.send(SearchEvent::Hit(hit)).is_err() {
+                    return;
+                }
+                hits += 1;
+            }
+        }
+        let _ = tx.send(SearchEvent::Done {
+            files,
+            truncated: false,
+        });
+    });
+    rx
+}
+
+/// Matching lines of one file, or `None` for unreadable, binary or large files
+fn search_file(path: &Path, query: &Regex, max_size: u64) -> Option<Vec<SearchHit>> {
+    let FileContents::Text(text) = read_text_file(path, max_size).ok()? else {
+        return None;
+    };
+    Some(
+        text.lines()
+            .enumerate()
+            .filter(|(_, line)| query.is_match(line))
+            .map(|(line, text)| SearchHit {
+                path: path.to_path_buf(),
+                line,
+                text: text.trim().to_string(),
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+    use tempfile::tempdir;
+
+    fn collect(rx: Receiver<SearchEvent>) -> (Vec<SearchHit>, usize) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut hits = Vec::new();
+        while Instant::now() < deadline {
+            match rx.recv_timeout(Duration::from_millis(50)) {
+                Ok(SearchEvent::Hit(hit)) => hits.push(hit),
+                Ok(SearchEvent::Done { files, .. }) => {
+                    hits.sort_by(|a, b| (&a.path, a.line).cmp(&(&b.path, b.line)));
+                    return (hits, files);
+                }
+                Err(_) => {}
+            }
+        }
+        panic!("search did not finish");
+    }
+
+    #[test]
+    fn literal_and_regex_queries() {
+        let literal = compile_query("a.b").expect("literal");
+        assert!(literal.is_match("xa.by"));
+        assert!(!literal.is_match("axb"));
+        assert!(compile_query("TODO").expect("case").is_match("TODO"));
+        assert!(!compile_query("TODO").expect("case").is_match("todo"));
+        assert!(compile_query("todo").expect("smart case").is_match("TODO"));
+
+        let regex = compile_query("/fo+ba?r/").expect("regex");
+        assert!(regex.is_match("fooobr"));
+        assert!(compile_query("/(/").is_err());
+    }
+
+    #[test]
+    fn search_streams_matches_and_skips_ignored_and_large_files() {
+        let root = tempdir().expect("tempdir");
+        let dir = root.path();
+        std::fs::write(dir.join(".gitignore"), "build/\n").expect("write .gitignore");
+        std::fs::write(dir.join("notes.md"), "intro\nneedle here\nend\n").expect("notes");
+        std::fs::create_dir(dir.join("build")).expect("build dir");
+        std::fs::write(dir.join("build/out.txt"), "needle").expect("build file");
+        std::fs::write(dir.join("big.txt"), "needle\n".repeat(100)).expect("big file");
+        std::fs::write(dir.join("blob.bin"), b"needle\0").expect("binary");
+
+        let options = SearchOptions {
+            max_file_size: 64,
+            ..SearchOptions::default()
+        };
+        let query = compile_query("needle").expect("query");
+        let (hits, _) = collect(spawn_search(dir.to_path_buf(), query.clone(), options));
+        assert_eq!(
+            hits,
+            vec![SearchHit {
+                path: dir.join("notes.md"),
+                line: 1,
+                text: "needle here".into(),
+            }]
+        );
+
+        let options = SearchOptions {
+            respect_gitignore: false,
+            ..SearchOptions::default()
+        };
+        let (hits, _) = collect(spawn_search(dir.to_path_buf(), query, options));
+        assert!(hits.iter().any(|hit| hit.path.ends_with("build/out.txt")));
+        assert!(hits.iter().any(|hit| hit.path.ends_with("big.txt")));
+    }
+}
@@ -1,13 +1,14 @@
 #[cfg(test)]
 mod tests {
     use crate::app::{App, Focus};
+    use crate::config::Settings;
     use crate::file_entry::FileEntry;
     use std::path::PathBuf;
     use tempfile::TempDir;
 
     fn create_test_app() -> (App, TempDir) {
         let temp_dir = TempDir::new().unwrap();
-        let app = App::new(temp_dir.path().to_path_buf());
+        let app = App::with_settings(temp_dir.path().to_path_buf(), Settings::default());
         (app, temp_dir)
     }
 
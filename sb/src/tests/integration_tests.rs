@@ -2,6 +2,7 @@
 mod integration_tests {
     use super::*;
     use crate::app::{App, Focus};
+    use crate::config::Settings;
     use crate::fs::{FileEntry, FileType};
     use std::fs;
     use std::path::PathBuf;
@@ -30,7 +31,7 @@ mod integration_tests {
     #[test]
     fn test_app_file_system_integration() {
         let temp_dir = create_test_directory_structure();
-        let app = App::new(temp_dir.path().to_path_buf()).unwrap();
+        let app = App::with_settings(temp_dir.path().to_path_buf(), Settings::default()).unwrap();
 
         // Verify app can read the directory structure
         assert!(!app.left_tree.is_empty());
@@ -43,7 +44,8 @@ mod integration_tests {
     #[test]
     fn test_file_operations_integration() {
         let temp_dir = create_test_directory_structure();
-        let mut app = App::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut app =
+            App::with_settings(temp_dir.path().to_path_buf(), Settings::default()).unwrap();
 
         // Test opening a file
         let readme_path = temp_dir.path().join("README.md");
@@ -56,7 +58,8 @@ mod integration_tests {
     #[test]
     fn test_tree_navigation_integration() {
         let temp_dir = create_test_directory_structure();
-        let mut app = App::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut app =
+            App::with_settings(temp_dir.path().to_path_buf(), Settings::default()).unwrap();
 
         // Test expanding directories
         if let Some(root_item) = app.left_tree.first() {
@@ -71,7 +74,8 @@ mod integration_tests {
     #[test]
     fn test_search_functionality_integration() {
         let temp_dir = create_test_directory_structure();
-        let mut app = App::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut app =
+            App::with_settings(temp_dir.path().to_path_buf(), Settings::default()).unwrap();
 
         // Test search for "Test"
         app.search_buffer = "Test".to_string();
@@ -104,7 +108,7 @@ mod integration_tests {
             .output()
             .ok();
 
-        let app = App::new(temp_dir.path().to_path_buf()).unwrap();
+        let app = App::with_settings(temp_dir.path().to_path_buf(), Settings::default()).unwrap();
 
         // App should detect git repository
         // Note: This test might be flaky if git is not available
@@ -114,7 +118,8 @@ mod integration_tests {
     #[test]
     fn test_markdown_preview_integration() {
         let temp_dir = create_test_directory_structure();
-        let mut app = App::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut app =
+            App::with_settings(temp_dir.path().to_path_buf(), Settings::default()).unwrap();
 
         let readme_path = temp_dir.path().join("README.md");
         app.open_file(&readme_path).unwrap();
@@ -131,7 +136,8 @@ mod integration_tests {
     #[test]
     fn test_editor_integration() {
         let temp_dir = create_test_directory_structure();
-        let mut app = App::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut app =
+            App::with_settings(temp_dir.path().to_path_buf(), Settings::default()).unwrap();
 
         let test_file_path = temp_dir.path().join("test_edit.md");
         fs::write(&test_file_path, "Initial content").unwrap();
@@ -148,7 +154,7 @@ mod integration_tests {
     #[test]
     fn test_file_type_detection_integration() {
         let temp_dir = create_test_directory_structure();
-        let app = App::new(temp_dir.path().to_path_buf()).unwrap();
+        let app = App::with_settings(temp_dir.path().to_path_buf(), Settings::default()).unwrap();
 
         // Test various file types are detected correctly
         let rust_file = temp_dir.path().join("src/main.rs");
@@ -181,7 +187,7 @@ mod integration_tests {
         }
 
         // Test that app can handle large directory structures
-        let app = App::new(root.to_path_buf()).unwrap();
+        let app = App::with_settings(root.to_path_buf(), Settings::default()).unwrap();
 
         // Should not panic and should load the structure
         assert!(!app.left_tree.is_empty());
@@ -190,7 +196,8 @@ mod integration_tests {
     #[test]
     fn test_error_recovery_integration() {
         let temp_dir = create_test_directory_structure();
-        let mut app = App::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut app =
+            App::with_settings(temp_dir.path().to_path_buf(), Settings::default()).unwrap();
 
         // Test opening non-existent file
         let nonexistent = temp_dir.path().join("nonexistent.md");
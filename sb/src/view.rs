@@ -0,0 +1,283 @@
+//! Drawing the file tree and the preview/editor panes
+//!
+//! sb's own `ui` and the Files tab in `saorsa` both draw their panes through
+//! here, so the preview's environment, scroll clamping and split view are
+//! set up in one place. Callers lay the panes out and draw everything
+//! around them (status bar, popups, hints) themselves.
+
+use crate::app::{format_timestamp, App, Focus};
+use crate::error::SbError;
+use crate::preview::{render_preview, Preview};
+use anyhow::Result;
+use ratatui::prelude::*;
+use ratatui::widgets::{Clear, Paragraph};
+use saorsa_cli_core::Theme;
+use saorsa_ui::renderer::{pane_block, render_pane_frame, render_scrollbar};
+
+/// Draw the Files pane: breadcrumb (or go-to-path prompt) above the tree
+pub fn render_tree_pane(f: &mut Frame, area: Rect, app: &mut App, theme: &Theme) -> Result<()> {
+    let inner = render_pane_frame(f, area, "Files", matches!(app.focus, Focus::Left), theme);
+    let [crumb_area, tree_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(inner);
+    if app.going_to_path {
+        f.render_widget(&app.goto_input, crumb_area);
+    } else {
+        let crumb = Paragraph::new(app.breadcrumb(crumb_area.width as usize))
+            .style(Style::default().fg(theme.colors.muted));
+        f.render_widget(crumb, crumb_area);
+    }
+    let tree = tui_tree_widget::Tree::new(&app.left_tree)
+        .map_err(|e| SbError::tree_widget(format!("Failed to create file tree widget: {}", e)))?
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    f.render_stateful_widget(tree, tree_area, &mut app.left_state);
+    Ok(())
+}
+
+/// Draw the right-hand side: the preview, the raw editor, or both
+///
+/// `edit` and `view` are the editor and preview halves of `area` while the
+/// split view is on, and `area` itself otherwise; either may be empty when
+/// the other is zoomed. A preview that fails to build is drawn as a note and
+/// its error returned.
+pub fn render_content(
+    f: &mut Frame,
+    area: Rect,
+    (edit, view): (Rect, Rect),
+    app: &mut App,
+    theme: &Theme,
+) -> Result<()> {
+    if area.is_empty() {
+        return Ok(());
+    }
+    let result = if app.show_raw_editor && !app.split_view_active() {
+        // Green border marks edit mode regardless of focus
+        render_editor(f, area, app, theme);
+        Ok(())
+    } else {
+        render_preview_pane(f, area, (edit, view), app, theme)
+    };
+    render_overlays(f, edit, view, app, theme);
+    result
+}
+
+fn render_editor(f: &mut Frame, area: Rect, app: &mut App, theme: &Theme) {
+    let block = pane_block("Edit (raw)", app.show_raw_editor, theme);
+    let block = if app.show_raw_editor {
+        block.border_style(Style::default().fg(theme.colors.success))
+    } else {
+        block
+    };
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    f.render_widget(app.editor.view(), inner);
+}
+
+fn render_preview_pane(
+    f: &mut Frame,
+    area: Rect,
+    (edit, view): (Rect, Rect),
+    app: &mut App,
+    theme: &Theme,
+) -> Result<()> {
+    let focused = matches!(app.focus, Focus::Preview);
+    let Some(path) = app.opened.clone() else {
+        let block = pane_block("Preview", focused, theme);
+        f.render_widget(
+            Paragraph::new("Select a file to preview").block(block),
+            area,
+        );
+        return Ok(());
+    };
+    // The preview reads the file, cursor and overlays from these
+    let text = app.editor.text();
+    std::env::set_var("SB_CURRENT_FILE", &path);
+    std::env::set_var("SB_CURRENT_TEXT", &text);
+    // Only enable raw-line overlay when inline editing is active
+    if app.editing_line {
+        std::env::set_var("SB_OVERLAY", "1");
+    } else {
+        std::env::remove_var("SB_OVERLAY");
+    }
+    std::env::set_var("SB_PREVIEW_COL", app.preview_col.to_string());
+    if app.show_blame {
+        std::env::set_var("SB_BLAME", "1");
+    } else {
+        std::env::remove_var("SB_BLAME");
+    }
+    match app.preview_selection_range() {
+        Some(((start, _), (end, _))) => {
+            std::env::set_var("SB_PREVIEW_SELECTION", format!("{start}:{end}"))
+        }
+        None => std::env::remove_var("SB_PREVIEW_SELECTION"),
+    }
+    match app.hex_view() {
+        Some(len) => std::env::set_var("SB_HEX_LEN", len.to_string()),
+        None => std::env::remove_var("SB_HEX_LEN"),
+    }
+
+    // Show the diff instead of the file when asked to
+    let diff = app
+        .should_show_diff(&path)
+        .then(|| app.get_file_diff(&path))
+        .flatten();
+    let built = match diff {
+        Some(diff) => Ok(Preview {
+            text: Text::raw(diff),
+            images: vec![],
+            videos: vec![],
+            diagrams: vec![],
+        }),
+        None => Preview::for_file(app.renderer, &path, &text),
+    };
+    let mut preview = match built {
+        Ok(preview) => preview,
+        Err(e) => {
+            let block = pane_block("Preview", focused, theme);
+            let note = Paragraph::new(format!("(preview error: {e:#})")).block(block);
+            f.render_widget(note, area);
+            return Err(e.context(format!("preview of {} failed", path.display())));
+        }
+    };
+
+    // Auto start/stop video based on first detected link
+    if app.autoplay_video {
+        if let Some(first) = preview.videos.first() {
+            if app.video_path.as_ref() != Some(first) {
+                app.start_video(first.clone());
+            }
+        } else if app.video_player.is_some() {
+            app.stop_video();
+        }
+    }
+
+    // Usable rows inside the border, and the width text wraps at
+    app.preview_viewport = area.height.saturating_sub(2) as usize;
+    if !view.is_empty() {
+        app.preview_width = view.width.saturating_sub(2) as usize;
+        // Without wrapping, scroll sideways to keep the cursor column in view
+        app.sync_preview_hscroll(app.preview_width);
+        app.sync_preview_rows(&preview.text);
+    }
+    if app.preview_wrap() {
+        std::env::remove_var("SB_PREVIEW_HSCROLL");
+    } else {
+        std::env::set_var("SB_PREVIEW_HSCROLL", app.preview_hscroll.to_string());
+    }
+    // Clamp scroll to valid range against file length
+    let total_lines = app.preview_line_count();
+    app.preview_scroll = app
+        .preview_scroll
+        .min(total_lines.saturating_sub(app.preview_viewport));
+    if app.hex_view().is_some() {
+        preview.text = app.hex_text(app.preview_viewport);
+    }
+
+    let split = app.split_view_active();
+    let (cursor, scroll) = if split {
+        // Sync first: while the preview has focus the editor follows it
+        let viewport = app.preview_viewport;
+        let synced = app.sync_split_view(&preview.text, viewport);
+        if !edit.is_empty() {
+            render_editor(f, edit, app, theme);
+        }
+        synced
+    } else {
+        (app.preview_cursor, app.preview_scroll)
+    };
+    std::env::set_var("SB_PREVIEW_CURSOR", cursor.to_string());
+    std::env::set_var("SB_PREVIEW_SCROLL", scroll.to_string());
+    // Highlight the cursor's row of a wrapped line when moving by display line
+    if app.moves_by_display_line() && !split {
+        let (scroll, cursor) = app.preview_wrap_rows();
+        std::env::set_var("SB_PREVIEW_WRAP_ROWS", format!("{scroll}:{cursor}"));
+    } else {
+        std::env::remove_var("SB_PREVIEW_WRAP_ROWS");
+    }
+
+    // The preview half is hidden behind a zoomed raw editor
+    if !view.is_empty() {
+        render_preview(f, view, &preview, app.renderer, &app.image_picker);
+        render_scrollbar(
+            f,
+            view.inner(Margin::new(0, 1)),
+            scroll,
+            total_lines,
+            app.preview_viewport,
+        );
+    }
+    Ok(())
+}
+
+/// The `:` prompt, the inline line editor and the playing video's frame
+fn render_overlays(f: &mut Frame, edit: Rect, view: Rect, app: &App, theme: &Theme) {
+    // Editor command mode prompt overlays at bottom when active
+    if app.editor_cmd_mode && !edit.is_empty() {
+        let area = Rect {
+            x: edit.x + 1,
+            y: edit.bottom().saturating_sub(1),
+            width: edit.width.saturating_sub(2),
+            height: 1,
+        };
+        f.render_widget(Clear, area);
+        f.render_widget(&app.editor_cmd_input, area);
+    }
+    if app.show_raw_editor || !matches!(app.focus, Focus::Preview) || view.is_empty() {
+        return;
+    }
+    if app.editing_line {
+        // Draw an inline single-line editor at the bottom of the preview
+        let h = view.height.min(3);
+        let area = Rect {
+            x: view.x + 1,
+            y: view.bottom().saturating_sub(h),
+            width: view.width.saturating_sub(2),
+            height: h,
+        };
+        let title = format!(
+            "Edit line {} (Enter=save, Esc=cancel)",
+            app.preview_cursor + 1
+        );
+        let block =
+            pane_block(title, true, theme).border_style(Style::default().fg(theme.colors.success));
+        let inner = block.inner(area);
+        f.render_widget(Clear, area);
+        f.render_widget(block, area);
+        f.render_widget(&app.line_input, inner);
+    }
+
+    // If a video is playing, overlay the last frame below the text area similar to images
+    let Some(vp) = &app.video_player else {
+        return;
+    };
+    let Some(img) = vp.last_frame() else {
+        return;
+    };
+    let mut state = app.image_picker.new_resize_protocol(img);
+    let widget = ratatui_image::StatefulImage::new().resize(ratatui_image::Resize::Fit(None));
+    // carve a small area at bottom of preview
+    let h = view.height.min(12);
+    let area = Rect {
+        x: view.x + 1,
+        y: view.bottom().saturating_sub(h),
+        width: view.width.saturating_sub(2),
+        height: h,
+    };
+    f.render_stateful_widget(widget, area, &mut state);
+    let label = if vp.is_finished() {
+        " ■ ended  ← rewind ".to_string()
+    } else if vp.is_paused() {
+        format!(" ⏸ {} ", format_timestamp(vp.position()))
+    } else {
+        format!(" ▶ {} ", format_timestamp(vp.position()))
+    };
+    let label_area = Rect {
+        x: area.x,
+        y: area.y,
+        width: (label.chars().count() as u16).min(area.width),
+        height: 1,
+    };
+    f.render_widget(
+        Paragraph::new(label).style(Style::default().fg(Color::Black).bg(Color::Gray)),
+        label_area,
+    );
+}